@@ -9,6 +9,14 @@ import java.io.File
  */
 class MyriadNode private constructor(private val nodePtr: Long) {
 
+    /**
+     * Receives messages delivered to this node.
+     * Called on a native thread; post to the main thread before touching UI.
+     */
+    fun interface MessageListener {
+        fun onMessageReceived(source: String, payload: ByteArray)
+    }
+
     /**
      * Start the node and begin mesh networking operations.
      */
@@ -50,6 +58,63 @@ class MyriadNode private constructor(private val nodePtr: Long) {
         }
     }
 
+    /**
+     * Register the listener for incoming messages, replacing any previous one.
+     *
+     * @return true if the listener was registered
+     */
+    fun setMessageListener(listener: MessageListener): Boolean {
+        return try {
+            nativeSetMessageCallback(nodePtr, listener)
+        } catch (e: Exception) {
+            Timber.e(e, "Failed to set message listener")
+            false
+        }
+    }
+
+    /**
+     * Enable or disable all adapters of a type, e.g. to turn off cellular
+     * on a metered connection.
+     *
+     * @param adapterType Adapter type name (e.g. "cellular", "ble", "ethernet")
+     * @param enabled Whether adapters of this type should run
+     * @return true if the adapters were updated
+     */
+    fun setAdapterEnabled(adapterType: String, enabled: Boolean): Boolean {
+        return try {
+            nativeSetAdapterEnabled(nodePtr, adapterType, enabled)
+        } catch (e: IllegalArgumentException) {
+            Timber.e(e, "Invalid adapter type: $adapterType")
+            false
+        } catch (e: Exception) {
+            Timber.e(e, "Failed to update adapter $adapterType")
+            false
+        }
+    }
+
+    /**
+     * Set how adapters are ranked for outgoing traffic. Switch to "battery"
+     * when the device enters power-save mode.
+     *
+     * @param mode One of "default", "battery", "performance", "reliability", "privacy"
+     * @return true if the mode was applied, false for unknown modes
+     */
+    fun setScoringMode(mode: String): Boolean {
+        return try {
+            nativeSetScoringMode(nodePtr, mode)
+        } catch (e: Exception) {
+            Timber.e(e, "Failed to set scoring mode")
+            false
+        }
+    }
+
+    /**
+     * Get the state of all registered adapters as a JSON array.
+     */
+    fun getAdapterStates(): String {
+        return nativeGetAdapterStates(nodePtr)
+    }
+
     /**
      * Get the node's public ID.
      */
@@ -92,10 +157,11 @@ class MyriadNode private constructor(private val nodePtr: Long) {
          * Initialize a new MyriadNode instance.
          *
          * @param configPath Path to the configuration file
-         * @param dataDir Path to the data directory
+         * @param dataDir Path to the data directory; the identity keystore lives here
+         * @param passphrase Passphrase protecting the identity keystore
          * @return A new MyriadNode instance or null if initialization failed
          */
-        fun initialize(configPath: String, dataDir: String): MyriadNode? {
+        fun initialize(configPath: String, dataDir: String, passphrase: String): MyriadNode? {
             return try {
                 val configFile = File(configPath)
                 if (!configFile.exists()) {
@@ -108,7 +174,7 @@ class MyriadNode private constructor(private val nodePtr: Long) {
                     dataDirFile.mkdirs()
                 }
 
-                val nodePtr = nativeInit(configPath, dataDir)
+                val nodePtr = nativeInit(configPath, dataDir, passphrase)
                 if (nodePtr == 0L) {
                     Timber.e("Native initialization returned null pointer")
                     null
@@ -123,7 +189,11 @@ class MyriadNode private constructor(private val nodePtr: Long) {
 
         // Native method declarations
         @JvmStatic
-        private external fun nativeInit(configPath: String, dataDir: String): Long
+        private external fun nativeInit(
+            configPath: String,
+            dataDir: String,
+            passphrase: String
+        ): Long
 
         @JvmStatic
         private external fun nativeStart(nodePtr: Long): Boolean
@@ -139,6 +209,25 @@ class MyriadNode private constructor(private val nodePtr: Long) {
             priority: Int
         ): Boolean
 
+        @JvmStatic
+        private external fun nativeSetMessageCallback(
+            nodePtr: Long,
+            callback: MessageListener
+        ): Boolean
+
+        @JvmStatic
+        private external fun nativeSetAdapterEnabled(
+            nodePtr: Long,
+            adapterType: String,
+            enabled: Boolean
+        ): Boolean
+
+        @JvmStatic
+        private external fun nativeSetScoringMode(nodePtr: Long, mode: String): Boolean
+
+        @JvmStatic
+        private external fun nativeGetAdapterStates(nodePtr: Long): String
+
         @JvmStatic
         private external fun nativeGetNodeId(nodePtr: Long): String
 
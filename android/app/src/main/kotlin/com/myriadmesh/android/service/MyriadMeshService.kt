@@ -51,7 +51,7 @@ class MyriadMeshService : Service() {
             try {
                 Timber.d("Starting mesh networking...")
                 // TODO: Initialize MyriadNode via JNI
-                // val node = MyriadNode.initialize(configPath, dataDir.absolutePath)
+                // val node = MyriadNode.initialize(configPath, dataDir.absolutePath, passphrase)
                 // node?.start()
 
                 // For now, just keep the service running
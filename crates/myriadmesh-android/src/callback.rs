@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+use jni::objects::{GlobalRef, JObject, JValue};
+use jni::{JNIEnv, JavaVM};
+use tokio::sync::mpsc;
+
+/// Java method invoked for each received message.
+const CALLBACK_METHOD: &str = "onMessageReceived";
+
+/// JNI signature of `void onMessageReceived(String source, byte[] payload)`.
+const CALLBACK_SIGNATURE: &str = "(Ljava/lang/String;[B)V";
+
+/// A message received by the node, as handed to the platform layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingMessage {
+    /// Hex-encoded source NodeId
+    pub source: String,
+    /// Message payload
+    pub payload: Vec<u8>,
+}
+
+/// Destination for received messages.
+///
+/// The JNI layer forwards into a Java callback object; tests substitute
+/// an in-memory sink.
+pub trait MessageSink: Send + 'static {
+    /// Deliver one received message.
+    fn deliver(&self, message: &IncomingMessage) -> Result<()>;
+}
+
+/// Sink that calls `onMessageReceived(String, byte[])` on a Java object.
+pub struct JniMessageSink {
+    vm: JavaVM,
+    callback: GlobalRef,
+}
+
+impl JniMessageSink {
+    /// Create a sink holding a global reference to `callback`.
+    ///
+    /// The global reference keeps the Java object alive after the JNI call
+    /// that registered it returns, and is released when the sink is dropped.
+    pub fn new(env: &mut JNIEnv, callback: &JObject) -> Result<Self> {
+        let vm = env.get_java_vm().context("Failed to get JavaVM")?;
+        let callback = env
+            .new_global_ref(callback)
+            .context("Failed to create global reference to callback")?;
+        Ok(Self { vm, callback })
+    }
+}
+
+impl MessageSink for JniMessageSink {
+    fn deliver(&self, message: &IncomingMessage) -> Result<()> {
+        // Runtime worker threads are not attached to the JVM. The guard
+        // detaches on drop, unless the thread was already attached.
+        let mut env = self
+            .vm
+            .attach_current_thread()
+            .context("Failed to attach thread to JVM")?;
+
+        // Local frame frees the string and array refs even if this thread
+        // stays attached
+        let result = env.with_local_frame(4, |env| -> Result<()> {
+            let source = env.new_string(&message.source)?;
+            let payload = env.byte_array_from_slice(&message.payload)?;
+            env.call_method(
+                self.callback.as_obj(),
+                CALLBACK_METHOD,
+                CALLBACK_SIGNATURE,
+                &[JValue::Object(&source), JValue::Object(&payload)],
+            )?;
+            Ok(())
+        });
+
+        // SECURITY: Never leave a Java exception pending on a native thread
+        if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_describe();
+            let _ = env.exception_clear();
+            anyhow::bail!("Java callback threw an exception");
+        }
+
+        result
+    }
+}
+
+/// Forward received messages to `sink` until the channel closes or
+/// `is_live` reports that the owning handle is gone.
+pub async fn forward_messages<F>(
+    mut rx: mpsc::UnboundedReceiver<IncomingMessage>,
+    sink: Box<dyn MessageSink>,
+    is_live: F,
+) where
+    F: Fn() -> bool + Send + 'static,
+{
+    while let Some(message) = rx.recv().await {
+        if !is_live() {
+            log::debug!("Node handle destroyed, stopping message callbacks");
+            break;
+        }
+        if let Err(e) = sink.deliver(&message) {
+            log::error!("Failed to deliver message from {}: {:?}", message.source, e);
+        }
+    }
+}
@@ -1,12 +1,15 @@
-use jni::objects::{JByteArray, JClass, JString};
+use jni::objects::{JByteArray, JClass, JObject, JString};
 use jni::sys::{jboolean, jint, jlong, jstring, JNI_FALSE, JNI_TRUE};
 use jni::JNIEnv;
+use myriadmesh_protocol::types::AdapterType;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
+mod callback;
 mod node;
+use callback::{JniMessageSink, MessageSink};
 use node::AndroidNode;
 
 // SECURITY C10: Global handle registry for safe JNI pointer management
@@ -16,6 +19,75 @@ static ANDROID_NODES: Lazy<Mutex<HashMap<u64, Arc<Mutex<AndroidNode>>>>> =
 
 static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
 
+/// Add a node to the handle registry and return its handle.
+fn register_node(node: AndroidNode) -> Option<u64> {
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::SeqCst);
+
+    match ANDROID_NODES.lock() {
+        Ok(mut nodes) => {
+            nodes.insert(handle, Arc::new(Mutex::new(node)));
+            Some(handle)
+        }
+        Err(e) => {
+            log::error!("Failed to acquire lock on node registry: {:?}", e);
+            None
+        }
+    }
+}
+
+/// Check whether a handle is still present in the registry.
+fn is_live(handle: u64) -> bool {
+    ANDROID_NODES
+        .lock()
+        .map(|nodes| nodes.contains_key(&handle))
+        .unwrap_or(false)
+}
+
+/// Register the message sink for the node behind `handle`.
+///
+/// SECURITY C10: The forwarding task only holds the handle, never the node,
+/// and stops delivering as soon as the handle leaves the registry.
+fn set_message_sink(handle: u64, sink: Box<dyn MessageSink>) -> bool {
+    let node_arc = match ANDROID_NODES.lock() {
+        Ok(nodes) => match nodes.get(&handle) {
+            Some(node_arc) => Arc::clone(node_arc),
+            None => {
+                log::error!("Invalid handle: {}", handle);
+                return false;
+            }
+        },
+        Err(e) => {
+            log::error!("Failed to acquire lock on node registry: {:?}", e);
+            return false;
+        }
+    };
+
+    let mut node = match node_arc.lock() {
+        Ok(node) => node,
+        Err(e) => {
+            log::error!("Failed to lock node: {:?}", e);
+            return false;
+        }
+    };
+    node.set_message_sink(sink, move || is_live(handle));
+    true
+}
+
+/// Remove a node from the registry, returning whether it existed.
+fn destroy_node(handle: u64) -> bool {
+    // SECURITY C10: Release the registry lock before the node is dropped.
+    // Dropping the node shuts down its runtime, which waits for forwarding
+    // tasks that may themselves be checking the registry.
+    let removed = match ANDROID_NODES.lock() {
+        Ok(mut nodes) => nodes.remove(&handle),
+        Err(e) => {
+            log::error!("Failed to acquire lock on node registry: {:?}", e);
+            return false;
+        }
+    };
+    removed.is_some()
+}
+
 /// Initialize the MyriadNode for Android.
 ///
 /// # Safety
@@ -27,6 +99,7 @@ pub unsafe extern "C" fn Java_com_myriadmesh_android_core_MyriadNode_nativeInit(
     _class: JClass,
     config_path: JString,
     data_dir: JString,
+    passphrase: JString,
 ) -> jlong {
     // Initialize Android logger
     android_logger::init_once(
@@ -54,25 +127,28 @@ pub unsafe extern "C" fn Java_com_myriadmesh_android_core_MyriadNode_nativeInit(
         }
     };
 
+    // SECURITY: Never log the passphrase
+    let passphrase: String = match env.get_string(&passphrase) {
+        Ok(s) => s.into(),
+        Err(e) => {
+            log::error!("Failed to get keystore passphrase: {:?}", e);
+            return 0;
+        }
+    };
+
     log::debug!("Config path: {}", config_path);
     log::debug!("Data dir: {}", data_dir);
 
     // Create the node
-    match AndroidNode::new(config_path, data_dir) {
+    match AndroidNode::new(config_path, data_dir, &passphrase) {
         Ok(node) => {
             // SECURITY C10: Use handle registry instead of raw pointers
-            let handle = NEXT_HANDLE.fetch_add(1, Ordering::SeqCst);
-
-            match ANDROID_NODES.lock() {
-                Ok(mut nodes) => {
-                    nodes.insert(handle, Arc::new(Mutex::new(node)));
+            match register_node(node) {
+                Some(handle) => {
                     log::info!("MyriadNode initialized successfully (handle: {})", handle);
                     handle as jlong
                 }
-                Err(e) => {
-                    log::error!("Failed to acquire lock on node registry: {:?}", e);
-                    0
-                }
+                None => 0,
             }
         }
         Err(e) => {
@@ -260,6 +336,233 @@ pub unsafe extern "C" fn Java_com_myriadmesh_android_core_MyriadNode_nativeSendM
     }
 }
 
+/// Register a Java object to receive incoming messages.
+///
+/// The object must implement `void onMessageReceived(String source, byte[] payload)`.
+/// Calls arrive on a native thread attached to the JVM for the duration of
+/// each call. Registering again replaces the previous callback.
+///
+/// # Safety
+/// This function is called from JNI and must handle all errors safely.
+/// SECURITY C10: Uses handle validation to prevent use-after-free.
+#[no_mangle]
+pub unsafe extern "C" fn Java_com_myriadmesh_android_core_MyriadNode_nativeSetMessageCallback(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    callback: JObject,
+) -> jboolean {
+    if handle == 0 {
+        log::error!("Invalid handle (0)");
+        return JNI_FALSE;
+    }
+
+    if callback.is_null() {
+        log::error!("Message callback is null");
+        return JNI_FALSE;
+    }
+
+    let sink = match JniMessageSink::new(&mut env, &callback) {
+        Ok(sink) => sink,
+        Err(e) => {
+            log::error!("Failed to create message callback: {:?}", e);
+            return JNI_FALSE;
+        }
+    };
+
+    if set_message_sink(handle as u64, Box::new(sink)) {
+        log::info!("Message callback registered (handle: {})", handle);
+        JNI_TRUE
+    } else {
+        JNI_FALSE
+    }
+}
+
+/// Enable or disable all adapters of a type (e.g. "cellular", "ble").
+///
+/// Throws `IllegalArgumentException` for an unknown adapter type.
+///
+/// # Safety
+/// This function is called from JNI and must handle all errors safely.
+/// SECURITY C10: Uses handle validation to prevent use-after-free.
+#[no_mangle]
+pub unsafe extern "C" fn Java_com_myriadmesh_android_core_MyriadNode_nativeSetAdapterEnabled(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    adapter_type: JString,
+    enabled: jboolean,
+) -> jboolean {
+    if handle == 0 {
+        log::error!("Invalid handle (0)");
+        return JNI_FALSE;
+    }
+
+    let adapter_type: String = match env.get_string(&adapter_type) {
+        Ok(s) => s.into(),
+        Err(e) => {
+            log::error!("Failed to get adapter type: {:?}", e);
+            return JNI_FALSE;
+        }
+    };
+
+    let adapter_type: AdapterType = match adapter_type.parse() {
+        Ok(adapter_type) => adapter_type,
+        Err(e) => {
+            log::error!("{}", e);
+            let _ = env.throw_new("java/lang/IllegalArgumentException", &e);
+            return JNI_FALSE;
+        }
+    };
+
+    let nodes = match ANDROID_NODES.lock() {
+        Ok(nodes) => nodes,
+        Err(e) => {
+            log::error!("Failed to acquire lock on node registry: {:?}", e);
+            return JNI_FALSE;
+        }
+    };
+
+    match nodes.get(&(handle as u64)) {
+        Some(node_arc) => {
+            let mut node = match node_arc.lock() {
+                Ok(node) => node,
+                Err(e) => {
+                    log::error!("Failed to lock node: {:?}", e);
+                    return JNI_FALSE;
+                }
+            };
+
+            match node.set_adapter_enabled(adapter_type, enabled == JNI_TRUE) {
+                Ok(_) => JNI_TRUE,
+                Err(e) => {
+                    log::error!("Failed to update {} adapters: {:?}", adapter_type.name(), e);
+                    JNI_FALSE
+                }
+            }
+        }
+        None => {
+            log::error!("Invalid handle: {}", handle);
+            JNI_FALSE
+        }
+    }
+}
+
+/// Set the adapter scoring mode: "default", "battery", "performance",
+/// "reliability" or "privacy". Returns false for unknown modes.
+///
+/// # Safety
+/// This function is called from JNI and must handle all errors safely.
+/// SECURITY C10: Uses handle validation to prevent use-after-free.
+#[no_mangle]
+pub unsafe extern "C" fn Java_com_myriadmesh_android_core_MyriadNode_nativeSetScoringMode(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    mode: JString,
+) -> jboolean {
+    if handle == 0 {
+        log::error!("Invalid handle (0)");
+        return JNI_FALSE;
+    }
+
+    let mode: String = match env.get_string(&mode) {
+        Ok(s) => s.into(),
+        Err(e) => {
+            log::error!("Failed to get scoring mode: {:?}", e);
+            return JNI_FALSE;
+        }
+    };
+
+    let nodes = match ANDROID_NODES.lock() {
+        Ok(nodes) => nodes,
+        Err(e) => {
+            log::error!("Failed to acquire lock on node registry: {:?}", e);
+            return JNI_FALSE;
+        }
+    };
+
+    match nodes.get(&(handle as u64)) {
+        Some(node_arc) => {
+            let mut node = match node_arc.lock() {
+                Ok(node) => node,
+                Err(e) => {
+                    log::error!("Failed to lock node: {:?}", e);
+                    return JNI_FALSE;
+                }
+            };
+
+            match node.set_scoring_mode(&mode) {
+                Ok(_) => JNI_TRUE,
+                Err(e) => {
+                    log::error!("Failed to set scoring mode: {:?}", e);
+                    JNI_FALSE
+                }
+            }
+        }
+        None => {
+            log::error!("Invalid handle: {}", handle);
+            JNI_FALSE
+        }
+    }
+}
+
+/// Get the state of all registered adapters as a JSON array.
+///
+/// # Safety
+/// This function is called from JNI and must handle all errors safely.
+/// SECURITY C10: Uses handle validation to prevent use-after-free.
+#[no_mangle]
+#[allow(unused_mut)] // env.new_string() requires mutable borrow
+pub unsafe extern "C" fn Java_com_myriadmesh_android_core_MyriadNode_nativeGetAdapterStates(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    if handle == 0 {
+        log::error!("Invalid handle (0)");
+        return std::ptr::null_mut();
+    }
+
+    let nodes = match ANDROID_NODES.lock() {
+        Ok(nodes) => nodes,
+        Err(e) => {
+            log::error!("Failed to acquire lock on node registry: {:?}", e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    match nodes.get(&(handle as u64)) {
+        Some(node_arc) => {
+            let node = match node_arc.lock() {
+                Ok(node) => node,
+                Err(e) => {
+                    log::error!("Failed to lock node: {:?}", e);
+                    return std::ptr::null_mut();
+                }
+            };
+
+            match node.get_adapter_states() {
+                Ok(states) => match env.new_string(&states) {
+                    Ok(s) => s.into_raw(),
+                    Err(e) => {
+                        log::error!("Failed to create JString: {:?}", e);
+                        std::ptr::null_mut()
+                    }
+                },
+                Err(e) => {
+                    log::error!("Failed to get adapter states: {:?}", e);
+                    std::ptr::null_mut()
+                }
+            }
+        }
+        None => {
+            log::error!("Invalid handle: {}", handle);
+            std::ptr::null_mut()
+        }
+    }
+}
+
 /// Get the node's public ID.
 ///
 /// # Safety
@@ -390,16 +693,119 @@ pub unsafe extern "C" fn Java_com_myriadmesh_android_core_MyriadNode_nativeDestr
     }
 
     // SECURITY C10: Remove from registry, Arc will drop when last reference is gone
-    match ANDROID_NODES.lock() {
-        Ok(mut nodes) => {
-            if nodes.remove(&(handle as u64)).is_some() {
-                log::info!("MyriadNode destroyed (handle: {})", handle);
-            } else {
-                log::warn!("Attempted to destroy non-existent handle: {}", handle);
-            }
-        }
-        Err(e) => {
-            log::error!("Failed to acquire lock on node registry: {:?}", e);
+    if destroy_node(handle as u64) {
+        log::info!("MyriadNode destroyed (handle: {})", handle);
+    } else {
+        log::warn!("Attempted to destroy non-existent handle: {}", handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use callback::IncomingMessage;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    /// Sink that records deliveries on a channel
+    struct MockSink {
+        tx: Mutex<mpsc::Sender<IncomingMessage>>,
+    }
+
+    impl MessageSink for MockSink {
+        fn deliver(&self, message: &IncomingMessage) -> anyhow::Result<()> {
+            self.tx.lock().unwrap().send(message.clone())?;
+            Ok(())
         }
     }
+
+    fn mock_sink() -> (Box<dyn MessageSink>, mpsc::Receiver<IncomingMessage>) {
+        let (tx, rx) = mpsc::channel();
+        (Box::new(MockSink { tx: Mutex::new(tx) }), rx)
+    }
+
+    fn new_handle() -> u64 {
+        let data_dir = tempfile::tempdir().unwrap();
+        let node = AndroidNode::new(
+            "config.yaml".into(),
+            data_dir.path().to_string_lossy().into_owned(),
+            "passphrase",
+        )
+        .unwrap();
+        register_node(node).unwrap()
+    }
+
+    fn deliver(handle: u64, source: &str, payload: &[u8]) -> bool {
+        let nodes = ANDROID_NODES.lock().unwrap();
+        let node = nodes[&handle].lock().unwrap();
+        node.deliver_incoming(source.to_string(), payload.to_vec())
+    }
+
+    #[test]
+    fn test_message_callback_delivery() {
+        let handle = new_handle();
+        assert!(!deliver(handle, "aa", b"dropped"));
+
+        let (sink, rx) = mock_sink();
+        assert!(set_message_sink(handle, sink));
+        assert!(deliver(handle, "aa", b"hello"));
+        assert!(deliver(handle, "bb", b"world"));
+
+        let first = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        let second = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            first,
+            IncomingMessage {
+                source: "aa".to_string(),
+                payload: b"hello".to_vec(),
+            }
+        );
+        assert_eq!(second.source, "bb");
+        assert_eq!(second.payload, b"world");
+
+        // Replacing the callback stops the previous one
+        let (sink, replacement_rx) = mock_sink();
+        assert!(set_message_sink(handle, sink));
+        assert!(deliver(handle, "cc", b"again"));
+        assert_eq!(
+            replacement_rx
+                .recv_timeout(Duration::from_secs(5))
+                .unwrap()
+                .source,
+            "cc"
+        );
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+        assert!(destroy_node(handle));
+    }
+
+    #[test]
+    fn test_destroy_stops_message_callbacks() {
+        let handle = new_handle();
+        let (sink, rx) = mock_sink();
+        assert!(set_message_sink(handle, sink));
+
+        // Keep the node alive past destroy to queue a message afterwards
+        let node_arc = Arc::clone(&ANDROID_NODES.lock().unwrap()[&handle]);
+        assert!(destroy_node(handle));
+        assert!(!is_live(handle));
+        assert!(!destroy_node(handle));
+
+        assert!(node_arc
+            .lock()
+            .unwrap()
+            .deliver_incoming("aa".to_string(), b"late".to_vec()));
+        assert!(rx.recv_timeout(Duration::from_millis(200)).is_err());
+
+        // Dropping the node ends the forwarding task and releases the sink
+        drop(node_arc);
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_secs(5)),
+            Err(mpsc::RecvTimeoutError::Disconnected)
+        ));
+
+        // Unknown handles are rejected
+        let (sink, _rx) = mock_sink();
+        assert!(!set_message_sink(handle, sink));
+    }
 }
@@ -1,32 +1,68 @@
+use crate::callback::{forward_messages, IncomingMessage, MessageSink};
 use anyhow::{Context, Result};
+use myriadmesh_crypto::identity::NodeIdentity;
+use myriadmesh_crypto::keystore;
+use myriadmesh_network::{AdapterManager, AdapterStatus, NetworkAdapter, PowerConsumption};
+use myriadmesh_protocol::types::AdapterType;
+use myriadnode::failover::FailoverManager;
+use myriadnode::scoring::AdapterMetrics;
+use myriadnode::{AdapterScorer, ScoringWeights};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::runtime::Runtime;
+use tokio::sync::{mpsc, RwLock};
+
+/// Keystore file holding the node identity, relative to the data directory.
+const KEYSTORE_FILE: &str = "identity.keystore";
 
 /// Android wrapper for MyriadNode.
 /// This struct manages the node lifecycle and provides a safe interface for JNI.
 pub struct AndroidNode {
     config_path: String,
     data_dir: String,
-    #[allow(dead_code)] // Will be used when MyriadNode is integrated
+    identity: NodeIdentity,
     runtime: Arc<Runtime>,
     // TODO: Add actual MyriadNode instance when ready
     // node: Option<Arc<Mutex<myriadnode::Node>>>,
     is_running: bool,
+    adapter_manager: Arc<RwLock<AdapterManager>>,
+    /// Adapter types switched off by the user
+    disabled_adapters: HashSet<AdapterType>,
+    scoring_mode: String,
+    scorer: AdapterScorer,
+    /// Feeds the registered message callback, if any
+    incoming_tx: Option<mpsc::UnboundedSender<IncomingMessage>>,
 }
 
 impl AndroidNode {
     /// Create a new AndroidNode instance.
-    pub fn new(config_path: String, data_dir: String) -> Result<Self> {
+    ///
+    /// Loads the identity from the encrypted keystore in `data_dir`, or
+    /// generates and stores a new one on first run, so the NodeId survives
+    /// restarts and reinstalls that keep the data directory.
+    pub fn new(config_path: String, data_dir: String, passphrase: &str) -> Result<Self> {
         log::info!("Creating AndroidNode with config: {}", config_path);
 
+        myriadmesh_crypto::init().context("Failed to initialize cryptography")?;
+        let identity = load_or_create_identity(Path::new(&data_dir), passphrase)?;
+        log::info!("Node identity: {}", identity.node_id.to_hex());
+
         // Create a Tokio runtime for async operations
         let runtime = Runtime::new().context("Failed to create Tokio runtime")?;
 
         Ok(Self {
             config_path,
             data_dir,
+            identity,
             runtime: Arc::new(runtime),
             is_running: false,
+            adapter_manager: Arc::new(RwLock::new(AdapterManager::new())),
+            disabled_adapters: HashSet::new(),
+            scoring_mode: "default".to_string(),
+            scorer: AdapterScorer::new_with_defaults(),
+            incoming_tx: None,
         })
     }
 
@@ -83,11 +119,182 @@ impl AndroidNode {
         Ok(())
     }
 
+    /// Register and start a network adapter.
+    ///
+    /// Adapters of a type the user has disabled are stopped right away.
+    #[allow(dead_code)] // Will be called when platform adapters are wired up
+    pub fn register_adapter(&self, id: String, adapter: Box<dyn NetworkAdapter>) -> Result<()> {
+        let disabled = self
+            .disabled_adapters
+            .contains(&adapter.get_capabilities().adapter_type);
+
+        self.runtime.block_on(async {
+            let mut manager = self.adapter_manager.write().await;
+            manager
+                .register_adapter(id.clone(), adapter)
+                .await
+                .with_context(|| format!("Failed to register adapter {}", id))?;
+
+            if disabled {
+                if let Some(adapter) = manager.get_adapter(&id) {
+                    adapter.write().await.stop().await?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Enable or disable every adapter of the given type.
+    ///
+    /// The setting is remembered, so adapters of a disabled type registered
+    /// later stay stopped.
+    pub fn set_adapter_enabled(&mut self, adapter_type: AdapterType, enabled: bool) -> Result<()> {
+        log::info!(
+            "{} {} adapters",
+            if enabled { "Enabling" } else { "Disabling" },
+            adapter_type.name()
+        );
+
+        if enabled {
+            self.disabled_adapters.remove(&adapter_type);
+        } else {
+            self.disabled_adapters.insert(adapter_type);
+        }
+
+        self.runtime.block_on(async {
+            let manager = self.adapter_manager.read().await;
+            for id in manager.adapter_ids() {
+                let matches = manager
+                    .get_capabilities(&id)
+                    .is_some_and(|caps| caps.adapter_type == adapter_type);
+                let Some(adapter) = manager.get_adapter(&id).filter(|_| matches) else {
+                    continue;
+                };
+
+                let mut adapter = adapter.write().await;
+                let result = if enabled {
+                    adapter.start().await
+                } else {
+                    adapter.stop().await
+                };
+                result.with_context(|| format!("Failed to update adapter {}", id))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Get the state of every registered adapter as JSON.
+    pub fn get_adapter_states(&self) -> Result<String> {
+        let states = self.runtime.block_on(async {
+            let manager = self.adapter_manager.read().await;
+            let mut ids = manager.adapter_ids();
+            ids.sort();
+
+            let mut states = Vec::new();
+            for id in ids {
+                let (Some(adapter), Some(caps)) =
+                    (manager.get_adapter(&id), manager.get_capabilities(&id))
+                else {
+                    continue;
+                };
+                let status = adapter.read().await.get_status();
+
+                states.push(serde_json::json!({
+                    "id": id,
+                    "adapter_type": caps.adapter_type.name(),
+                    "enabled": !self.disabled_adapters.contains(&caps.adapter_type),
+                    "status": status.to_string(),
+                }));
+            }
+            states
+        });
+
+        Ok(serde_json::Value::Array(states).to_string())
+    }
+
+    /// Switch the adapter scoring mode
+    /// ("default", "battery", "performance", "reliability" or "privacy").
+    pub fn set_scoring_mode(&mut self, mode: &str) -> Result<()> {
+        let weights = ScoringWeights::for_mode(mode)
+            .with_context(|| format!("Unknown scoring mode: {}", mode))?;
+
+        log::info!("Switching adapter scoring mode to {}", mode);
+        self.scorer.set_weights(weights);
+        self.scoring_mode = mode.to_string();
+        Ok(())
+    }
+
+    /// Pick the best ready, enabled adapter under the current scoring mode.
+    #[allow(dead_code)] // Will be used for outbound adapter selection
+    pub fn select_adapter(&self) -> Option<String> {
+        let candidates = self.runtime.block_on(async {
+            let manager = self.adapter_manager.read().await;
+            let mut candidates = HashMap::new();
+
+            for id in manager.adapter_ids() {
+                let (Some(adapter), Some(caps)) =
+                    (manager.get_adapter(&id), manager.get_capabilities(&id))
+                else {
+                    continue;
+                };
+                if self.disabled_adapters.contains(&caps.adapter_type)
+                    || adapter.read().await.get_status() != AdapterStatus::Ready
+                {
+                    continue;
+                }
+
+                let metrics = AdapterMetrics {
+                    latency_ms: caps.typical_latency_ms,
+                    bandwidth_bps: caps.typical_bandwidth_bps,
+                    reliability: caps.reliability,
+                    power_consumption: match caps.power_consumption {
+                        PowerConsumption::None => 0.0,
+                        PowerConsumption::VeryLow => 0.1,
+                        PowerConsumption::Low => 0.3,
+                        PowerConsumption::Medium => 0.5,
+                        PowerConsumption::High => 0.7,
+                        PowerConsumption::VeryHigh => 0.9,
+                    },
+                    privacy_level: FailoverManager::estimate_privacy_level(&id),
+                };
+                candidates.insert(id, metrics);
+            }
+            candidates
+        });
+
+        self.scorer
+            .get_best_adapter(candidates)
+            .map(|score| score.adapter_id)
+    }
+
+    /// Register the sink that receives incoming messages.
+    ///
+    /// Spawns a forwarding task on the node's runtime. Replacing the sink
+    /// drops the previous channel, which ends the previous task. The task
+    /// also stops once `is_live` returns false or the node is dropped.
+    pub fn set_message_sink<F>(&mut self, sink: Box<dyn MessageSink>, is_live: F)
+    where
+        F: Fn() -> bool + Send + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.incoming_tx = Some(tx);
+        self.runtime.spawn(forward_messages(rx, sink, is_live));
+    }
+
+    /// Hand a received message to the registered sink.
+    ///
+    /// Returns false if no sink is registered.
+    #[allow(dead_code)] // Will be called from MyriadNode local delivery
+    pub fn deliver_incoming(&self, source: String, payload: Vec<u8>) -> bool {
+        match &self.incoming_tx {
+            Some(tx) => tx.send(IncomingMessage { source, payload }).is_ok(),
+            None => false,
+        }
+    }
+
     /// Get the node's public ID.
     pub fn get_node_id(&self) -> Result<String> {
-        // TODO: Get actual node ID from MyriadNode
-        // For now, return a placeholder
-        Ok("android-node-placeholder".to_string())
+        Ok(self.identity.node_id.to_hex())
     }
 
     /// Get the node's status as JSON.
@@ -96,6 +303,7 @@ impl AndroidNode {
         // For now, return a simple JSON
         let status = serde_json::json!({
             "running": self.is_running,
+            "scoring_mode": self.scoring_mode,
             "config_path": self.config_path,
             "data_dir": self.data_dir,
         });
@@ -104,6 +312,35 @@ impl AndroidNode {
     }
 }
 
+/// Load the node identity from `data_dir`, creating it if none exists.
+fn load_or_create_identity(data_dir: &Path, passphrase: &str) -> Result<NodeIdentity> {
+    let path = data_dir.join(KEYSTORE_FILE);
+
+    if path.exists() {
+        let data = fs::read(&path)
+            .with_context(|| format!("Failed to read keystore {}", path.display()))?;
+        // SECURITY: Never replace an unreadable keystore; that would silently
+        // discard the user's identity
+        return keystore::open(&data, passphrase)
+            .with_context(|| format!("Failed to open keystore {}", path.display()));
+    }
+
+    log::warn!("No identity keystore found, generating new identity");
+    let identity = NodeIdentity::generate().context("Failed to generate identity")?;
+    let sealed = keystore::seal(&identity, passphrase).context("Failed to seal identity")?;
+
+    // Write to a temporary file first so a crash cannot leave a partial keystore
+    fs::create_dir_all(data_dir)
+        .with_context(|| format!("Failed to create data dir {}", data_dir.display()))?;
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, sealed)
+        .with_context(|| format!("Failed to write keystore {}", tmp_path.display()))?;
+    fs::rename(&tmp_path, &path)
+        .with_context(|| format!("Failed to write keystore {}", path.display()))?;
+
+    Ok(identity)
+}
+
 impl Drop for AndroidNode {
     fn drop(&mut self) {
         if self.is_running {
@@ -112,3 +349,275 @@ impl Drop for AndroidNode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myriadmesh_network::adapter::{PeerInfo, TestResults};
+    use myriadmesh_network::{AdapterCapabilities, Address, FeatureFlags, NetworkError};
+    use myriadmesh_protocol::Frame;
+
+    struct MockAdapter {
+        status: AdapterStatus,
+        capabilities: AdapterCapabilities,
+    }
+
+    impl MockAdapter {
+        fn new(adapter_type: AdapterType) -> Box<Self> {
+            Self::with_profile(adapter_type, 50.0, 1_000_000, PowerConsumption::Medium)
+        }
+
+        fn with_profile(
+            adapter_type: AdapterType,
+            typical_latency_ms: f64,
+            typical_bandwidth_bps: u64,
+            power_consumption: PowerConsumption,
+        ) -> Box<Self> {
+            Box::new(Self {
+                status: AdapterStatus::Uninitialized,
+                capabilities: AdapterCapabilities {
+                    adapter_type,
+                    max_message_size: 1400,
+                    typical_latency_ms,
+                    typical_bandwidth_bps,
+                    reliability: 0.95,
+                    range_meters: 0.0,
+                    power_consumption,
+                    cost_per_mb: 0.0,
+                    features: FeatureFlags::empty(),
+                },
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl NetworkAdapter for MockAdapter {
+        async fn initialize(&mut self) -> myriadmesh_network::Result<()> {
+            Ok(())
+        }
+
+        async fn start(&mut self) -> myriadmesh_network::Result<()> {
+            self.status = AdapterStatus::Ready;
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> myriadmesh_network::Result<()> {
+            self.status = AdapterStatus::Unavailable;
+            Ok(())
+        }
+
+        async fn send(
+            &self,
+            _destination: &Address,
+            _frame: &Frame,
+        ) -> myriadmesh_network::Result<()> {
+            Ok(())
+        }
+
+        async fn receive(&self, _timeout_ms: u64) -> myriadmesh_network::Result<(Address, Frame)> {
+            Err(NetworkError::ReceiveFailed("Not implemented".to_string()))
+        }
+
+        async fn discover_peers(&self) -> myriadmesh_network::Result<Vec<PeerInfo>> {
+            Ok(Vec::new())
+        }
+
+        fn get_status(&self) -> AdapterStatus {
+            self.status
+        }
+
+        fn get_capabilities(&self) -> &AdapterCapabilities {
+            &self.capabilities
+        }
+
+        async fn test_connection(
+            &self,
+            _destination: &Address,
+        ) -> myriadmesh_network::Result<TestResults> {
+            Ok(TestResults {
+                success: true,
+                rtt_ms: Some(50.0),
+                error: None,
+            })
+        }
+
+        fn get_local_address(&self) -> Option<Address> {
+            None
+        }
+
+        fn parse_address(&self, addr_str: &str) -> myriadmesh_network::Result<Address> {
+            Ok(Address::Unknown(addr_str.to_string()))
+        }
+
+        fn supports_address(&self, _address: &Address) -> bool {
+            true
+        }
+    }
+
+    fn new_node(data_dir: &tempfile::TempDir) -> AndroidNode {
+        AndroidNode::new(
+            "config.yaml".into(),
+            data_dir.path().to_string_lossy().into_owned(),
+            "passphrase",
+        )
+        .unwrap()
+    }
+
+    fn node_with_adapters(data_dir: &tempfile::TempDir) -> AndroidNode {
+        let node = new_node(data_dir);
+        node.register_adapter("cellular".into(), MockAdapter::new(AdapterType::Cellular))
+            .unwrap();
+        node.register_adapter(
+            "bluetooth_le".into(),
+            MockAdapter::new(AdapterType::BluetoothLE),
+        )
+        .unwrap();
+        node
+    }
+
+    fn adapter_states(node: &AndroidNode) -> Vec<serde_json::Value> {
+        serde_json::from_str(&node.get_adapter_states().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_disable_and_enable_adapter() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut node = node_with_adapters(&data_dir);
+
+        let states = adapter_states(&node);
+        assert_eq!(states.len(), 2);
+        assert!(states.iter().all(|s| s["enabled"] == true));
+        assert!(states.iter().all(|s| s["status"] == "Ready"));
+
+        node.set_adapter_enabled(AdapterType::Cellular, false)
+            .unwrap();
+        let states = adapter_states(&node);
+        assert_eq!(states[0]["id"], "bluetooth_le");
+        assert_eq!(states[0]["enabled"], true);
+        assert_eq!(states[0]["status"], "Ready");
+        assert_eq!(states[1]["id"], "cellular");
+        assert_eq!(states[1]["adapter_type"], "Cellular");
+        assert_eq!(states[1]["enabled"], false);
+        assert_eq!(states[1]["status"], "Unavailable");
+
+        node.set_adapter_enabled(AdapterType::Cellular, true)
+            .unwrap();
+        let states = adapter_states(&node);
+        assert_eq!(states[1]["enabled"], true);
+        assert_eq!(states[1]["status"], "Ready");
+    }
+
+    #[test]
+    fn test_disabled_type_applies_to_new_adapters() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut node = new_node(&data_dir);
+        assert_eq!(node.get_adapter_states().unwrap(), "[]");
+
+        node.set_adapter_enabled(AdapterType::BluetoothLE, false)
+            .unwrap();
+        node.register_adapter("ble0".into(), MockAdapter::new(AdapterType::BluetoothLE))
+            .unwrap();
+
+        let states = adapter_states(&node);
+        assert_eq!(states[0]["enabled"], false);
+        assert_eq!(states[0]["status"], "Unavailable");
+    }
+
+    #[test]
+    fn test_battery_scoring_mode_changes_selection() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut node = new_node(&data_dir);
+        // Fast but power hungry vs. slow but frugal
+        node.register_adapter(
+            "cellular".into(),
+            MockAdapter::with_profile(
+                AdapterType::Cellular,
+                40.0,
+                50_000_000,
+                PowerConsumption::VeryHigh,
+            ),
+        )
+        .unwrap();
+        node.register_adapter(
+            "bluetooth_le".into(),
+            MockAdapter::with_profile(
+                AdapterType::BluetoothLE,
+                300.0,
+                1_000_000,
+                PowerConsumption::VeryLow,
+            ),
+        )
+        .unwrap();
+
+        node.set_scoring_mode("performance").unwrap();
+        assert_eq!(node.select_adapter().as_deref(), Some("cellular"));
+
+        node.set_scoring_mode("battery").unwrap();
+        assert_eq!(
+            node.scorer.get_weights(),
+            &ScoringWeights::battery_optimized()
+        );
+        assert_eq!(node.select_adapter().as_deref(), Some("bluetooth_le"));
+
+        let status: serde_json::Value = serde_json::from_str(&node.get_status().unwrap()).unwrap();
+        assert_eq!(status["scoring_mode"], "battery");
+
+        // Disabled adapters are never selected
+        node.set_adapter_enabled(AdapterType::BluetoothLE, false)
+            .unwrap();
+        assert_eq!(node.select_adapter().as_deref(), Some("cellular"));
+    }
+
+    #[test]
+    fn test_unknown_scoring_mode_rejected() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut node = new_node(&data_dir);
+        node.set_scoring_mode("battery").unwrap();
+
+        let err = node.set_scoring_mode("turbo").unwrap_err();
+        assert_eq!(err.to_string(), "Unknown scoring mode: turbo");
+        assert_eq!(
+            node.scorer.get_weights(),
+            &ScoringWeights::battery_optimized()
+        );
+    }
+
+    #[test]
+    fn test_identity_persists_across_restarts() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let first = new_node(&data_dir).get_node_id().unwrap();
+        assert_eq!(first.len(), 128);
+        assert!(data_dir.path().join(KEYSTORE_FILE).exists());
+
+        let second = new_node(&data_dir).get_node_id().unwrap();
+        assert_eq!(first, second);
+
+        // A different data dir gets a different identity
+        let other_dir = tempfile::tempdir().unwrap();
+        assert_ne!(new_node(&other_dir).get_node_id().unwrap(), first);
+    }
+
+    #[test]
+    fn test_bad_keystore_errors() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let path = data_dir.path().to_string_lossy().into_owned();
+        new_node(&data_dir);
+
+        let err = AndroidNode::new("config.yaml".into(), path.clone(), "wrong")
+            .err()
+            .unwrap();
+        assert!(format!("{:#}", err).contains("Decryption failed"));
+
+        let keystore_path = data_dir.path().join(KEYSTORE_FILE);
+        fs::write(&keystore_path, b"not a keystore").unwrap();
+        let err = AndroidNode::new("config.yaml".into(), path, "passphrase")
+            .err()
+            .unwrap();
+        let message = format!("{:#}", err);
+        assert!(message.contains("Failed to open keystore"));
+        assert!(message.contains("Invalid keystore"));
+
+        // The corrupt file is left in place rather than replaced
+        assert_eq!(fs::read(&keystore_path).unwrap(), b"not a keystore");
+    }
+}
@@ -9,7 +9,7 @@ use tokio::fs;
 use tokio::sync::RwLock;
 
 /// Message priority levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum MessagePriority {
     Low = 0,
@@ -141,6 +141,8 @@ pub struct PriorityStats {
 pub struct MessageCacheConfig {
     pub max_messages_per_device: usize,
     pub max_total_messages: usize,
+    /// Optional cap on cached messages of each priority (across all devices)
+    pub max_messages_per_priority: HashMap<MessagePriority, usize>,
 }
 
 impl Default for MessageCacheConfig {
@@ -148,6 +150,7 @@ impl Default for MessageCacheConfig {
         Self {
             max_messages_per_device: 1000,
             max_total_messages: 10000,
+            max_messages_per_priority: HashMap::new(),
         }
     }
 }
@@ -235,18 +238,31 @@ impl MessageCache {
 
         if device_count >= self.config.max_messages_per_device {
             // Try to evict delivered or low-priority messages for this device
-            Self::evict_messages_locked(&mut data, &message.device_id, &self.config);
+            let excess = device_count + 1 - self.config.max_messages_per_device;
+            if !Self::evict_for_locked(&mut data, message, excess, |m| {
+                m.device_id == message.device_id
+            }) {
+                drop(data);
+                return Err(ApplianceError::CacheFull);
+            }
+        }
 
-            // Re-count after eviction
-            let device_count = data
+        // Check per-priority cap atomically
+        if let Some(&cap) = self.config.max_messages_per_priority.get(&message.priority) {
+            let priority_count = data
                 .messages
                 .values()
-                .filter(|m| m.device_id == message.device_id)
+                .filter(|m| m.priority == message.priority)
                 .count();
 
-            if device_count >= self.config.max_messages_per_device {
-                drop(data);
-                return Err(ApplianceError::CacheFull);
+            if priority_count >= cap {
+                let excess = priority_count + 1 - cap;
+                if !Self::evict_for_locked(&mut data, message, excess, |m| {
+                    m.priority == message.priority
+                }) {
+                    drop(data);
+                    return Err(ApplianceError::CacheFull);
+                }
             }
         }
 
@@ -256,10 +272,14 @@ impl MessageCache {
             // Try to evict globally
             Self::evict_global_locked(&mut data, &self.config);
 
-            // Re-check after eviction
-            if data.messages.len() >= self.config.max_total_messages {
-                drop(data);
-                return Err(ApplianceError::CacheFull);
+            // Then fall back to the lowest-priority, oldest messages
+            let total_count = data.messages.len();
+            if total_count >= self.config.max_total_messages {
+                let excess = total_count + 1 - self.config.max_total_messages;
+                if !Self::evict_for_locked(&mut data, message, excess, |_| true) {
+                    drop(data);
+                    return Err(ApplianceError::CacheFull);
+                }
             }
         }
 
@@ -374,48 +394,48 @@ impl MessageCache {
         self.save().await
     }
 
-    /// Evict messages for a device (lock-free version for atomic operations)
+    /// Evict `count` messages matching `filter` to make room for `incoming`
+    ///
+    /// Delivered messages go first, then the lowest-priority, oldest ones.
+    /// Messages of higher priority than `incoming` are never evicted, and
+    /// nothing is evicted unless enough room can be made.
     ///
     /// # TOCTOU Race Prevention
     ///
     /// This helper operates on an already-held write lock, allowing check-and-evict
     /// to be a single atomic operation in store().
-    fn evict_messages_locked(
+    fn evict_for_locked(
         data: &mut CacheStoreData,
-        device_id: &str,
-        config: &MessageCacheConfig,
-    ) {
-        // Remove delivered messages first
-        data.messages
-            .retain(|_, m| !(m.device_id == device_id && m.delivered));
-
-        // If still over limit, remove oldest low-priority messages
-        let mut device_messages: Vec<_> = data
+        incoming: &CachedMessage,
+        count: usize,
+        filter: impl Fn(&CachedMessage) -> bool,
+    ) -> bool {
+        let mut candidates: Vec<_> = data
             .messages
-            .iter()
-            .filter(|(_, m)| m.device_id == device_id)
-            .map(|(id, m)| (id.clone(), m.priority, m.received_at))
+            .values()
+            .filter(|m| m.message_id != incoming.message_id)
+            .filter(|m| m.delivered || m.priority <= incoming.priority)
+            .filter(|m| filter(m))
+            .map(|m| {
+                (
+                    !m.delivered,
+                    m.priority,
+                    m.received_at,
+                    m.message_id.clone(),
+                )
+            })
             .collect();
 
-        if device_messages.len() > config.max_messages_per_device {
-            // Sort by priority (ascending) and received_at (ascending)
-            device_messages.sort_by(|(_, a_priority, a_time), (_, b_priority, b_time)| {
-                (a_priority, a_time).cmp(&(b_priority, b_time))
-            });
-
-            // Collect IDs to remove
-            let to_remove = device_messages.len() - config.max_messages_per_device;
-            let ids_to_remove: Vec<_> = device_messages
-                .iter()
-                .take(to_remove)
-                .map(|(id, _, _)| id.clone())
-                .collect();
+        if candidates.len() < count {
+            return false;
+        }
 
-            // Remove them
-            for id in ids_to_remove {
-                data.messages.remove(&id);
-            }
+        // Delivered first, then priority ascending, then age descending
+        candidates.sort();
+        for (_, _, _, id) in candidates.into_iter().take(count) {
+            data.messages.remove(&id);
         }
+        true
     }
 
     /// Global eviction across all devices (lock-free version for atomic operations)
@@ -653,4 +673,110 @@ mod tests {
         assert_eq!(all.len(), 1);
         assert!(all[0].delivered);
     }
+
+    fn message_at(id: &str, priority: MessagePriority, age_secs: i64) -> CachedMessage {
+        let mut msg = CachedMessage::new(
+            id.to_string(),
+            "device-1".to_string(),
+            MessageDirection::Inbound,
+            priority,
+            vec![1, 2, 3],
+            None,
+            None,
+        );
+        msg.received_at = Utc::now() - Duration::seconds(age_secs);
+        msg
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_evicts_oldest_low_priority() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let config = MessageCacheConfig {
+            max_total_messages: 5,
+            ..Default::default()
+        };
+        let cache = MessageCache::new(temp_file.path(), config).await.unwrap();
+
+        for i in 0..5 {
+            let msg = message_at(&format!("low-{}", i), MessagePriority::Low, 100 - i);
+            cache.store(&msg).await.unwrap();
+        }
+        cache
+            .store(&message_at("normal-0", MessagePriority::Normal, 200))
+            .await
+            .unwrap();
+        cache
+            .store(&message_at("high-0", MessagePriority::High, 0))
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = cache
+            .retrieve("device-1", None, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.message_id)
+            .collect();
+        assert_eq!(ids, vec!["high-0", "normal-0", "low-2", "low-3", "low-4"]);
+
+        // A low-priority message cannot displace higher-priority ones
+        cache
+            .store(&message_at("low-5", MessagePriority::Low, 0))
+            .await
+            .unwrap();
+        for i in 0..3 {
+            let msg = message_at(&format!("urgent-{}", i), MessagePriority::Urgent, 0);
+            cache.store(&msg).await.unwrap();
+        }
+        assert!(matches!(
+            cache
+                .store(&message_at("low-6", MessagePriority::Low, 0))
+                .await,
+            Err(ApplianceError::CacheFull)
+        ));
+        let stats = cache.get_stats("device-1").await.unwrap();
+        assert_eq!(stats.by_priority.low, 0);
+        assert_eq!(stats.by_priority.urgent, 3);
+    }
+
+    #[tokio::test]
+    async fn test_per_priority_cap() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let config = MessageCacheConfig {
+            max_messages_per_priority: HashMap::from([
+                (MessagePriority::Low, 2),
+                (MessagePriority::Urgent, 0),
+            ]),
+            ..Default::default()
+        };
+        let cache = MessageCache::new(temp_file.path(), config).await.unwrap();
+
+        for i in 0..4 {
+            let msg = message_at(&format!("low-{}", i), MessagePriority::Low, 100 - i);
+            cache.store(&msg).await.unwrap();
+            let msg = message_at(&format!("normal-{}", i), MessagePriority::Normal, 100 - i);
+            cache.store(&msg).await.unwrap();
+        }
+
+        let stats = cache.get_stats("device-1").await.unwrap();
+        assert_eq!(stats.by_priority.low, 2);
+        assert_eq!(stats.by_priority.normal, 4);
+
+        let low: Vec<_> = cache
+            .retrieve("device-1", None, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|m| m.priority == MessagePriority::Low)
+            .map(|m| m.message_id)
+            .collect();
+        assert_eq!(low, vec!["low-2", "low-3"]);
+
+        assert!(matches!(
+            cache
+                .store(&message_at("urgent-0", MessagePriority::Urgent, 0))
+                .await,
+            Err(ApplianceError::CacheFull)
+        ));
+    }
 }
@@ -0,0 +1,358 @@
+//! Configuration synchronization between paired devices
+//!
+//! Each setting carries a vector clock with one counter per writer. Merging
+//! keeps whichever write causally follows the other (last writer wins). Writes
+//! made concurrently to different values cannot be ordered, so the local value
+//! is kept and the conflict is reported until the caller resolves it.
+//!
+//! SECURITY: a device may only advance its own counter. Entries pushed by a
+//! device that claim another writer, or that move another writer's counter
+//! past what this replica has seen, are rejected by [`ConfigSync::merge_from`].
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+/// A typed configuration value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum ConfigValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+}
+
+/// Vector clock keyed by writer (device or appliance) id
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorClock(BTreeMap<String, u64>);
+
+impl VectorClock {
+    /// Create an empty clock
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counter for a writer
+    pub fn get(&self, writer: &str) -> u64 {
+        self.0.get(writer).copied().unwrap_or(0)
+    }
+
+    /// Advance a writer's counter
+    pub fn increment(&mut self, writer: &str) {
+        let counter = self.0.entry(writer.to_string()).or_insert(0);
+        *counter = counter.saturating_add(1);
+    }
+
+    /// Pointwise maximum of both clocks
+    pub fn merge(&mut self, other: &VectorClock) {
+        for (writer, &counter) in &other.0 {
+            let entry = self.0.entry(writer.clone()).or_insert(0);
+            *entry = (*entry).max(counter);
+        }
+    }
+
+    /// Causal order of two clocks, or `None` if they are concurrent
+    pub fn compare(&self, other: &VectorClock) -> Option<Ordering> {
+        let writers = self.0.keys().chain(other.0.keys());
+        let mut ordering = Ordering::Equal;
+
+        for writer in writers {
+            match (self.get(writer).cmp(&other.get(writer)), ordering) {
+                (Ordering::Equal, _) => {}
+                (step, Ordering::Equal) => ordering = step,
+                (step, current) if step != current => return None,
+                _ => {}
+            }
+        }
+
+        Some(ordering)
+    }
+}
+
+/// A setting together with its causal history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigEntry {
+    pub value: ConfigValue,
+    pub clock: VectorClock,
+    /// Writer of the current value
+    pub writer: String,
+}
+
+/// Concurrent writes to the same setting that could not be ordered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigConflict {
+    pub key: String,
+    pub local: ConfigEntry,
+    pub remote: ConfigEntry,
+}
+
+/// Outcome of merging a remote snapshot
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MergeReport {
+    /// Keys whose value was taken from the remote
+    pub applied: Vec<String>,
+    /// Keys left unresolved
+    pub conflicts: Vec<ConfigConflict>,
+    /// Keys refused because the sender wrote on behalf of another writer
+    pub rejected: Vec<String>,
+}
+
+/// Mergeable configuration map
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigSync {
+    entries: HashMap<String, ConfigEntry>,
+    conflicts: HashMap<String, ConfigConflict>,
+}
+
+impl ConfigSync {
+    /// Create an empty configuration map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a setting
+    pub fn get(&self, key: &str) -> Option<&ConfigValue> {
+        self.entries.get(key).map(|e| &e.value)
+    }
+
+    /// All settings with their clocks, for sending to another replica
+    pub fn snapshot(&self) -> HashMap<String, ConfigEntry> {
+        self.entries.clone()
+    }
+
+    /// Write a setting on behalf of `writer`
+    ///
+    /// The write follows everything this replica has seen for the key,
+    /// including both sides of an outstanding conflict, which it resolves.
+    pub fn set(&mut self, writer: &str, key: &str, value: ConfigValue) -> &ConfigEntry {
+        let mut clock = self
+            .entries
+            .get(key)
+            .map(|e| e.clock.clone())
+            .unwrap_or_default();
+        if let Some(conflict) = self.conflicts.remove(key) {
+            clock.merge(&conflict.remote.clock);
+        }
+        clock.increment(writer);
+
+        self.entries.insert(
+            key.to_string(),
+            ConfigEntry {
+                value,
+                clock,
+                writer: writer.to_string(),
+            },
+        );
+        &self.entries[key]
+    }
+
+    /// Merge settings pushed by the authenticated device `sender`
+    ///
+    /// An entry is only accepted if every counter other than the sender's is
+    /// one this replica has already seen, and if it advances the sender's
+    /// counter it must name the sender as writer.
+    pub fn merge_from(
+        &mut self,
+        sender: &str,
+        remote: &HashMap<String, ConfigEntry>,
+    ) -> MergeReport {
+        let mut accepted = HashMap::new();
+        let mut rejected = Vec::new();
+
+        for (key, theirs) in remote {
+            let known = self.entries.get(key).map(|e| &e.clock);
+            let seen = |writer: &str| known.map_or(0, |clock| clock.get(writer));
+
+            let forged = theirs
+                .clock
+                .0
+                .iter()
+                .any(|(writer, &counter)| writer != sender && counter > seen(writer));
+            let advances_sender = theirs.clock.get(sender) > seen(sender);
+
+            if forged || (advances_sender && theirs.writer != sender) {
+                rejected.push(key.clone());
+            } else {
+                accepted.insert(key.clone(), theirs.clone());
+            }
+        }
+
+        let mut report = self.merge(&accepted);
+        rejected.sort();
+        report.rejected = rejected;
+        report
+    }
+
+    /// Merge settings from a trusted replica
+    pub fn merge(&mut self, remote: &HashMap<String, ConfigEntry>) -> MergeReport {
+        let mut report = MergeReport::default();
+
+        for (key, theirs) in remote {
+            let Some(ours) = self.entries.get_mut(key) else {
+                self.entries.insert(key.clone(), theirs.clone());
+                report.applied.push(key.clone());
+                continue;
+            };
+
+            match ours.clock.compare(&theirs.clock) {
+                Some(Ordering::Less) => {
+                    *ours = theirs.clone();
+                    self.conflicts.remove(key);
+                    report.applied.push(key.clone());
+                }
+                Some(_) => {}
+                None if ours.value == theirs.value => {
+                    // Same value written independently
+                    ours.clock.merge(&theirs.clock);
+                }
+                None => {
+                    let conflict = ConfigConflict {
+                        key: key.clone(),
+                        local: ours.clone(),
+                        remote: theirs.clone(),
+                    };
+                    self.conflicts.insert(key.clone(), conflict.clone());
+                    report.conflicts.push(conflict);
+                }
+            }
+        }
+
+        report.applied.sort();
+        report.conflicts.sort_by(|a, b| a.key.cmp(&b.key));
+        report
+    }
+
+    /// Unresolved conflicts
+    pub fn conflicts(&self) -> Vec<ConfigConflict> {
+        let mut conflicts: Vec<_> = self.conflicts.values().cloned().collect();
+        conflicts.sort_by(|a, b| a.key.cmp(&b.key));
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_conflicting_merge() {
+        let mut phone = ConfigSync::new();
+        phone.set("phone", "relay_enabled", ConfigValue::Bool(true));
+
+        let mut tablet = ConfigSync::new();
+        tablet.set("tablet", "cache_ttl_days", ConfigValue::Integer(7));
+
+        let report = phone.merge(&tablet.snapshot());
+        assert_eq!(report.applied, vec!["cache_ttl_days"]);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(phone.get("relay_enabled"), Some(&ConfigValue::Bool(true)));
+        assert_eq!(phone.get("cache_ttl_days"), Some(&ConfigValue::Integer(7)));
+    }
+
+    #[test]
+    fn test_last_writer_wins() {
+        let mut phone = ConfigSync::new();
+        phone.set("phone", "theme", ConfigValue::Text("light".to_string()));
+
+        // The tablet sees the phone's write, then overrides it
+        let mut tablet = ConfigSync::new();
+        tablet.merge(&phone.snapshot());
+        tablet.set("tablet", "theme", ConfigValue::Text("dark".to_string()));
+
+        let report = phone.merge(&tablet.snapshot());
+        assert_eq!(report.applied, vec!["theme"]);
+        assert!(report.conflicts.is_empty());
+        assert_eq!(
+            phone.get("theme"),
+            Some(&ConfigValue::Text("dark".to_string()))
+        );
+
+        // The overridden write does not travel back
+        assert!(tablet.merge(&phone.snapshot()).applied.is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_edit_reports_conflict() {
+        let mut phone = ConfigSync::new();
+        phone.set("phone", "max_hops", ConfigValue::Integer(5));
+
+        let mut tablet = ConfigSync::new();
+        tablet.merge(&phone.snapshot());
+
+        // Both edit without seeing each other's change
+        phone.set("phone", "max_hops", ConfigValue::Integer(8));
+        tablet.set("tablet", "max_hops", ConfigValue::Integer(3));
+
+        let report = phone.merge(&tablet.snapshot());
+        assert!(report.applied.is_empty());
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].local.value, ConfigValue::Integer(8));
+        assert_eq!(report.conflicts[0].remote.value, ConfigValue::Integer(3));
+        assert_eq!(phone.get("max_hops"), Some(&ConfigValue::Integer(8)));
+        assert_eq!(phone.conflicts().len(), 1);
+
+        // Resolving writes a value that follows both edits
+        phone.set("phone", "max_hops", ConfigValue::Integer(4));
+        assert!(phone.conflicts().is_empty());
+        let report = tablet.merge(&phone.snapshot());
+        assert_eq!(report.applied, vec!["max_hops"]);
+        assert_eq!(tablet.get("max_hops"), Some(&ConfigValue::Integer(4)));
+    }
+
+    #[test]
+    fn test_merge_from_rejects_writes_for_other_devices() {
+        let mut appliance = ConfigSync::new();
+        appliance.set("phone", "max_hops", ConfigValue::Integer(5));
+
+        // The tablet forges a newer phone write
+        let mut forged = appliance.snapshot();
+        let entry = forged.get_mut("max_hops").unwrap();
+        entry.clock.increment("phone");
+        entry.value = ConfigValue::Integer(1);
+        let mut entry = entry.clone();
+
+        let report = appliance.merge_from("tablet", &forged);
+        assert_eq!(report.rejected, vec!["max_hops"]);
+        assert_eq!(appliance.get("max_hops"), Some(&ConfigValue::Integer(5)));
+
+        // Advancing its own counter under someone else's name is refused too
+        entry.clock = appliance.snapshot()["max_hops"].clock.clone();
+        entry.clock.increment("tablet");
+        forged.insert("max_hops".to_string(), entry.clone());
+        assert_eq!(
+            appliance.merge_from("tablet", &forged).rejected,
+            vec!["max_hops"]
+        );
+
+        // The same write under the tablet's own name is accepted
+        entry.writer = "tablet".to_string();
+        forged.insert("max_hops".to_string(), entry);
+        let report = appliance.merge_from("tablet", &forged);
+        assert!(report.rejected.is_empty());
+        assert_eq!(report.applied, vec!["max_hops"]);
+        assert_eq!(appliance.get("max_hops"), Some(&ConfigValue::Integer(1)));
+    }
+
+    #[test]
+    fn test_clock_increment_saturates() {
+        let mut clock = VectorClock(BTreeMap::from([("phone".to_string(), u64::MAX)]));
+        clock.increment("phone");
+        assert_eq!(clock.get("phone"), u64::MAX);
+    }
+
+    #[test]
+    fn test_concurrent_identical_values_merge_silently() {
+        let mut phone = ConfigSync::new();
+        phone.set("phone", "relay_enabled", ConfigValue::Bool(false));
+        let mut tablet = ConfigSync::new();
+        tablet.set("tablet", "relay_enabled", ConfigValue::Bool(false));
+
+        let report = phone.merge(&tablet.snapshot());
+        assert!(report.conflicts.is_empty());
+        assert!(tablet
+            .merge(&phone.snapshot())
+            .applied
+            .contains(&"relay_enabled".to_string()));
+    }
+}
@@ -1,6 +1,6 @@
 //! Paired device management
 
-use crate::types::{ApplianceResult, DevicePreferences};
+use crate::types::{ApplianceError, ApplianceResult, DevicePreferences};
 use blake2::Digest;
 use chrono::{DateTime, Utc};
 use myriadmesh_crypto::identity::NodeId;
@@ -143,6 +143,9 @@ impl From<&PairedDevice> for PairedDeviceInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DeviceStoreData {
     devices: HashMap<String, PairedDevice>,
+    /// First-seen public key (hex) per device id, kept across unpairing
+    #[serde(default)]
+    known_keys: HashMap<String, String>,
 }
 
 pub struct DeviceStore {
@@ -161,6 +164,7 @@ impl DeviceStore {
             if contents.is_empty() {
                 DeviceStoreData {
                     devices: HashMap::new(),
+                    known_keys: HashMap::new(),
                 }
             } else {
                 serde_json::from_str(&contents)?
@@ -168,6 +172,7 @@ impl DeviceStore {
         } else {
             DeviceStoreData {
                 devices: HashMap::new(),
+                known_keys: HashMap::new(),
             }
         };
 
@@ -228,6 +233,26 @@ impl DeviceStore {
         self.save().await
     }
 
+    /// Public key trusted for a device id, if it has ever been paired
+    pub async fn known_key(&self, device_id: &str) -> ApplianceResult<Option<Vec<u8>>> {
+        let data = self.data.read().await;
+        if let Some(key) = data.known_keys.get(device_id) {
+            let key = hex::decode(key).map_err(|e| ApplianceError::Crypto(e.to_string()))?;
+            return Ok(Some(key));
+        }
+        // Devices paired before keys were tracked separately
+        Ok(data.devices.get(device_id).map(|d| d.public_key.clone()))
+    }
+
+    /// Trust a public key for a device id, replacing any previous key
+    pub async fn remember_key(&self, device_id: &str, public_key: &[u8]) -> ApplianceResult<()> {
+        let mut data = self.data.write().await;
+        data.known_keys
+            .insert(device_id.to_string(), hex::encode(public_key));
+        drop(data);
+        self.save().await
+    }
+
     /// Update last seen timestamp
     pub async fn update_last_seen(&self, device_id: &str) -> ApplianceResult<()> {
         let mut data = self.data.write().await;
@@ -7,23 +7,35 @@
 //!
 //! - **Device Pairing**: Secure QR code and PIN-based pairing with mobile devices
 //! - **Message Caching**: Store-and-forward messaging with priority queues
-//! - **Configuration Sync**: Synchronize preferences and routing policies
+//! - **Configuration Sync**: Synchronize preferences and routing policies, with
+//!   vector-clock conflict detection
 //! - **Relay & Bridge**: Proxy routing for mobile devices
 
 pub mod cache;
+pub mod config_sync;
 pub mod device;
 pub mod manager;
 pub mod pairing;
 pub mod power;
+pub mod relay;
 pub mod types;
 
 // Re-export commonly used types
 pub use cache::{CachedMessage, MessageCache, MessageCacheConfig, MessagePriority};
+pub use config_sync::{
+    ConfigConflict, ConfigEntry, ConfigSync, ConfigValue, MergeReport, VectorClock,
+};
 pub use device::{PairedDevice, PairedDeviceInfo};
-pub use manager::{ApplianceManager, ApplianceManagerConfig, ApplianceStats};
-pub use pairing::{PairingMethod, PairingRequest, PairingResponse, PairingResult, PairingToken};
+pub use manager::{
+    ApplianceManager, ApplianceManagerConfig, ApplianceStats, PairingPin, PinLockoutStatus,
+};
+pub use pairing::{
+    PairingMethod, PairingRequest, PairingResponse, PairingResult, PairingToken,
+    MAX_PIN_ATTEMPTS_PER_TOKEN,
+};
 pub use power::{
     BatteryThreshold, DataUsagePolicy, DataUsageTracker, PowerAction, PowerManager,
     PowerManagerConfig, PowerSupply, QuotaCheck, ResetPeriod,
 };
+pub use relay::{RelayAccounting, RelayQuotaConfig, RelayUsage};
 pub use types::{ApplianceCapabilities, ApplianceError, ApplianceResult};
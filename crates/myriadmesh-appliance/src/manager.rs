@@ -1,20 +1,25 @@
 //! Appliance manager - coordinates all appliance functionality
 
 use crate::cache::{CachedMessage, MessageCache, MessageCacheConfig};
+use crate::config_sync::{ConfigConflict, ConfigEntry, ConfigSync, ConfigValue, MergeReport};
 use crate::device::{DeviceStore, PairedDevice, PairedDeviceInfo};
 use crate::pairing::{
     PairingManager, PairingRequest, PairingResponse, PairingResult, PairingToken,
 };
+use crate::relay::{RelayAccounting, RelayQuotaConfig, RelayUsage};
 use crate::types::{ApplianceCapabilities, ApplianceError, ApplianceResult, DevicePreferences};
 use blake2::Digest;
+use chrono::{DateTime, Utc};
 use ed25519_dalek::SigningKey;
 use myriadmesh_crypto::identity::NodeId;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tokio::task::JoinHandle;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// Appliance manager configuration
 #[derive(Debug, Clone)]
@@ -28,6 +33,11 @@ pub struct ApplianceManagerConfig {
     pub pairing_methods: Vec<String>,
     pub cache_config: MessageCacheConfig,
     pub data_directory: PathBuf,
+    /// Failed PIN attempts allowed per client within the window before
+    /// pairing locks for that client; must be at least 1
+    pub max_pin_attempts: u32,
+    pub pin_attempt_window_secs: i64,
+    pub relay_quota: RelayQuotaConfig,
 }
 
 impl Default for ApplianceManagerConfig {
@@ -42,6 +52,9 @@ impl Default for ApplianceManagerConfig {
             pairing_methods: vec!["qr_code".to_string(), "pin".to_string()],
             cache_config: MessageCacheConfig::default(),
             data_directory: PathBuf::from("./data/appliance"),
+            max_pin_attempts: 5,
+            pin_attempt_window_secs: 900, // 15 minutes
+            relay_quota: RelayQuotaConfig::default(),
         }
     }
 }
@@ -53,6 +66,32 @@ pub struct ApplianceStats {
     pub paired_devices: usize,
     pub total_cached_messages: usize,
     pub adapters_online: usize,
+    /// Relay usage by device id
+    pub relay_usage: HashMap<String, RelayUsage>,
+}
+
+/// Maximum clients tracked for PIN lockout; the stalest is forgotten first
+const MAX_PIN_CLIENTS: usize = 1024;
+
+/// A pairing PIN to show on the appliance itself
+///
+/// SECURITY: The PIN never leaves the appliance through the pairing API; it
+/// is only published here, for a local display or console to show.
+#[derive(Debug, Clone)]
+pub struct PairingPin {
+    pub device_id: String,
+    pub pin: String,
+    /// When the PIN stops working (Unix timestamp)
+    pub expires_at: i64,
+}
+
+/// PIN brute-force protection status for one client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinLockoutStatus {
+    pub locked_out: bool,
+    pub remaining_attempts: u32,
+    /// When pairing unlocks again (Unix timestamp), if locked out
+    pub locked_until: Option<i64>,
 }
 
 /// Main appliance manager
@@ -61,7 +100,15 @@ pub struct ApplianceManager {
     device_store: Arc<DeviceStore>,
     message_cache: Arc<MessageCache>,
     pairing_manager: Arc<PairingManager>,
+    relay_accounting: RelayAccounting,
     cleanup_task: RwLock<Option<JoinHandle<()>>>,
+    /// Recent failed PIN attempts per client, oldest first
+    pin_failures: RwLock<HashMap<IpAddr, VecDeque<DateTime<Utc>>>>,
+    /// PINs for the local display
+    pin_display: broadcast::Sender<PairingPin>,
+    /// Settings shared between paired devices
+    config_sync: RwLock<ConfigSync>,
+    config_sync_path: PathBuf,
 }
 
 impl ApplianceManager {
@@ -70,6 +117,17 @@ impl ApplianceManager {
         config: ApplianceManagerConfig,
         signing_key: SigningKey,
     ) -> ApplianceResult<Self> {
+        if config.max_pin_attempts == 0 {
+            return Err(ApplianceError::Configuration(
+                "max_pin_attempts must be at least 1".to_string(),
+            ));
+        }
+        if config.pin_attempt_window_secs <= 0 {
+            return Err(ApplianceError::Configuration(
+                "pin_attempt_window_secs must be positive".to_string(),
+            ));
+        }
+
         // Ensure data directory exists
         tokio::fs::create_dir_all(&config.data_directory).await?;
 
@@ -89,12 +147,32 @@ impl ApplianceManager {
             config.require_pairing_approval,
         ));
 
+        // Load synchronized configuration
+        let config_sync_path = config.data_directory.join("config_sync.json");
+        let config_sync = if config_sync_path.exists() {
+            let contents = tokio::fs::read_to_string(&config_sync_path).await?;
+            if contents.is_empty() {
+                ConfigSync::new()
+            } else {
+                serde_json::from_str(&contents)?
+            }
+        } else {
+            ConfigSync::new()
+        };
+
+        let relay_accounting = RelayAccounting::new(config.relay_quota.clone());
+
         let manager = Self {
             config,
             device_store,
             message_cache,
             pairing_manager,
+            relay_accounting,
             cleanup_task: RwLock::new(None),
+            pin_failures: RwLock::new(HashMap::new()),
+            pin_display: broadcast::channel(16).0,
+            config_sync: RwLock::new(config_sync),
+            config_sync_path,
         };
 
         // Start cleanup task
@@ -146,8 +224,22 @@ impl ApplianceManager {
         })
     }
 
-    /// Initiate device pairing
-    pub async fn initiate_pairing(&self, request: PairingRequest) -> ApplianceResult<PairingToken> {
+    /// Subscribe to PINs generated for PIN pairing
+    ///
+    /// The appliance's local display or console shows these to the user,
+    /// who enters the PIN on the device being paired.
+    pub fn subscribe_pairing_pins(&self) -> broadcast::Receiver<PairingPin> {
+        self.pin_display.subscribe()
+    }
+
+    /// Initiate device pairing on behalf of `client`
+    pub async fn initiate_pairing(
+        &self,
+        request: PairingRequest,
+        client: IpAddr,
+    ) -> ApplianceResult<PairingToken> {
+        self.check_pin_lockout(client).await?;
+
         // Check if max devices reached
         let current_paired = self.device_store.count_active().await?;
         if current_paired >= self.config.max_paired_devices {
@@ -164,7 +256,22 @@ impl ApplianceManager {
         }
 
         // Initiate pairing
-        self.pairing_manager.initiate_pairing(request).await
+        let device_id = request.device_id.clone();
+        let token = self.pairing_manager.initiate_pairing(request).await?;
+        if let Some(pin) = &token.pin {
+            if self
+                .pin_display
+                .send(PairingPin {
+                    device_id,
+                    pin: pin.clone(),
+                    expires_at: token.expires_at,
+                })
+                .is_err()
+            {
+                warn!("No local display for the pairing PIN; PIN pairing cannot complete");
+            }
+        }
+        Ok(token)
     }
 
     /// Approve a pending pairing
@@ -177,15 +284,69 @@ impl ApplianceManager {
         self.pairing_manager.reject_pairing(token).await
     }
 
-    /// Complete device pairing
+    /// Complete device pairing on behalf of `client`
+    ///
+    /// `device_id` and `public_key` must match the pending pairing request;
+    /// the device is stored under the identity the challenge was verified
+    /// against.
     pub async fn complete_pairing(
         &self,
         response: PairingResponse,
         device_id: String,
         node_id: NodeId,
         public_key: Vec<u8>,
+        client: IpAddr,
     ) -> ApplianceResult<PairingResult> {
-        let result = self.pairing_manager.complete_pairing(response).await?;
+        self.check_pin_lockout(client).await?;
+
+        // SECURITY: Check continuity against the key the token was issued
+        // for, not a caller-supplied one that is never verified.
+        let pending = self
+            .pairing_manager
+            .pending_request(&response.pairing_token)
+            .await?;
+        if pending.device_id != device_id || pending.public_key != public_key {
+            warn!(
+                "Pairing completion for {} does not match its pairing request",
+                device_id
+            );
+            return Ok(PairingResult {
+                success: false,
+                session_token: None,
+                error: Some(
+                    "Device ID or public key does not match the pairing request".to_string(),
+                ),
+                key_mismatch: false,
+            });
+        }
+
+        // SECURITY: Trust on first use - a known device presenting a new key
+        // may be an impersonation attempt, so require operator confirmation.
+        // The pending pairing is left in place until then.
+        if let Some(known_key) = self.device_store.known_key(&pending.device_id).await? {
+            if known_key != pending.public_key {
+                warn!(
+                    "Public key mismatch for device {}: refusing re-pair until re-keying is confirmed",
+                    pending.device_id
+                );
+                return Ok(PairingResult {
+                    success: false,
+                    session_token: None,
+                    error: Some(
+                        "Device public key does not match the key from first pairing".to_string(),
+                    ),
+                    key_mismatch: true,
+                });
+            }
+        }
+
+        let (result, request) = match self.pairing_manager.complete_pairing(response).await {
+            Err(ApplianceError::InvalidPin) => {
+                self.record_pin_failure(client).await;
+                return Err(ApplianceError::InvalidPin);
+            }
+            other => other?,
+        };
 
         if result.success {
             if let Some(session_token) = &result.session_token {
@@ -194,7 +355,11 @@ impl ApplianceManager {
                     format!("{:x}", blake2::Blake2b512::digest(session_token.as_bytes()));
 
                 // Store paired device
-                let device = PairedDevice::new(device_id, node_id, public_key, token_hash);
+                self.device_store
+                    .remember_key(&request.device_id, &request.public_key)
+                    .await?;
+                let device =
+                    PairedDevice::new(request.device_id, node_id, request.public_key, token_hash);
                 self.device_store.store(&device).await?;
 
                 info!("Device paired successfully: {}", device.device_id);
@@ -204,6 +369,91 @@ impl ApplianceManager {
         Ok(result)
     }
 
+    /// Current PIN lockout status for `client`
+    ///
+    /// Lockout is per client, so one client guessing PINs can't lock
+    /// everyone else out of pairing.
+    pub async fn pin_lockout_status(&self, client: IpAddr) -> PinLockoutStatus {
+        let now = Utc::now();
+        let mut clients = self.pin_failures.write().await;
+        self.prune_pin_failures(&mut clients, now);
+        let failures = clients.get(&client);
+        let count = failures.map_or(0, VecDeque::len);
+
+        let max_attempts = self.config.max_pin_attempts as usize;
+        let locked_out = count >= max_attempts;
+        // Unlocks once enough failures age out of the window
+        let locked_until = failures
+            .filter(|_| locked_out)
+            .and_then(|failures| failures.get(count - max_attempts))
+            .map(|at| (*at + self.pin_attempt_window()).timestamp());
+
+        PinLockoutStatus {
+            locked_out,
+            remaining_attempts: max_attempts.saturating_sub(count) as u32,
+            locked_until,
+        }
+    }
+
+    /// SECURITY: Refuse pairing while a client's PIN attempts are locked out
+    async fn check_pin_lockout(&self, client: IpAddr) -> ApplianceResult<()> {
+        let status = self.pin_lockout_status(client).await;
+        match status.locked_until {
+            Some(until) if status.locked_out => Err(ApplianceError::PairingLockedOut(
+                (until - Utc::now().timestamp()).max(0),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    async fn record_pin_failure(&self, client: IpAddr) {
+        let now = Utc::now();
+        let mut clients = self.pin_failures.write().await;
+        self.prune_pin_failures(&mut clients, now);
+        if !clients.contains_key(&client) && clients.len() >= MAX_PIN_CLIENTS {
+            let stalest = clients
+                .iter()
+                .min_by_key(|(_, failures)| failures.back().copied())
+                .map(|(client, _)| *client);
+            if let Some(stalest) = stalest {
+                clients.remove(&stalest);
+            }
+        }
+        let failures = clients.entry(client).or_default();
+        failures.push_back(now);
+        warn!(
+            "Failed pairing PIN attempt from {} ({} within window)",
+            client,
+            failures.len()
+        );
+    }
+
+    fn prune_pin_failures(
+        &self,
+        clients: &mut HashMap<IpAddr, VecDeque<DateTime<Utc>>>,
+        now: DateTime<Utc>,
+    ) {
+        let cutoff = now - self.pin_attempt_window();
+        clients.retain(|_, failures| {
+            while failures.front().is_some_and(|at| *at <= cutoff) {
+                failures.pop_front();
+            }
+            !failures.is_empty()
+        });
+    }
+
+    fn pin_attempt_window(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.config.pin_attempt_window_secs)
+    }
+
+    /// Confirm that a device has legitimately changed its key
+    ///
+    /// Replaces the trusted key so the next pairing attempt with it succeeds.
+    pub async fn confirm_rekey(&self, device_id: &str, public_key: Vec<u8>) -> ApplianceResult<()> {
+        warn!("Operator confirmed re-keying of device {}", device_id);
+        self.device_store.remember_key(device_id, &public_key).await
+    }
+
     /// Get paired device information
     pub async fn get_paired_device(
         &self,
@@ -261,6 +511,83 @@ impl ApplianceManager {
         Ok(())
     }
 
+    /// Merge configuration changes pushed by a paired device
+    ///
+    /// The device must present its session token, and may only write
+    /// settings under its own `device_id`. Concurrent edits to the same
+    /// setting are reported in the result and left unresolved until
+    /// `resolve_config_conflict` is called.
+    pub async fn sync_config(
+        &self,
+        device_id: &str,
+        session_token: &str,
+        remote: HashMap<String, ConfigEntry>,
+    ) -> ApplianceResult<MergeReport> {
+        // Verify device is paired
+        let device = self
+            .device_store
+            .get(device_id)
+            .await?
+            .ok_or_else(|| ApplianceError::DeviceNotFound(device_id.to_string()))?;
+
+        if !device.active {
+            return Err(ApplianceError::DeviceNotFound(device_id.to_string()));
+        }
+        if !device.verify_session_token(session_token) {
+            return Err(ApplianceError::InvalidSessionToken(device_id.to_string()));
+        }
+
+        let report = self
+            .config_sync
+            .write()
+            .await
+            .merge_from(device_id, &remote);
+        for key in &report.rejected {
+            warn!(
+                "Rejected configuration write to '{}' from device {} on behalf of another writer",
+                key, device_id
+            );
+        }
+        for conflict in &report.conflicts {
+            warn!(
+                "Configuration conflict on '{}' from device {}",
+                conflict.key, device_id
+            );
+        }
+        self.save_config_sync().await?;
+
+        Ok(report)
+    }
+
+    /// Current synchronized configuration, for sending to devices
+    pub async fn config_snapshot(&self) -> HashMap<String, ConfigEntry> {
+        self.config_sync.read().await.snapshot()
+    }
+
+    /// Unresolved configuration conflicts
+    pub async fn config_conflicts(&self) -> Vec<ConfigConflict> {
+        self.config_sync.read().await.conflicts()
+    }
+
+    /// Resolve a configuration conflict by choosing the value to keep
+    pub async fn resolve_config_conflict(
+        &self,
+        key: &str,
+        value: ConfigValue,
+    ) -> ApplianceResult<()> {
+        self.config_sync
+            .write()
+            .await
+            .set(&self.config.node_id, key, value);
+        self.save_config_sync().await
+    }
+
+    async fn save_config_sync(&self) -> ApplianceResult<()> {
+        let json = serde_json::to_string_pretty(&*self.config_sync.read().await)?;
+        tokio::fs::write(&self.config_sync_path, json).await?;
+        Ok(())
+    }
+
     /// Cache a message for a device
     pub async fn cache_message(&self, message: CachedMessage) -> ApplianceResult<()> {
         if !self.config.message_caching {
@@ -286,6 +613,42 @@ impl ApplianceManager {
         Ok(())
     }
 
+    /// Account for traffic relayed on behalf of a device
+    ///
+    /// Returns `QuotaExceeded` if the device has used up its relay quota for
+    /// the current window, in which case the traffic must not be relayed.
+    pub async fn record_relay(&self, device_id: &str, bytes: u64) -> ApplianceResult<()> {
+        if !self.config.relay_enabled {
+            return Err(ApplianceError::Configuration(
+                "Relay is disabled".to_string(),
+            ));
+        }
+
+        // Verify device is paired
+        let device = self
+            .device_store
+            .get(device_id)
+            .await?
+            .ok_or_else(|| ApplianceError::DeviceNotFound(device_id.to_string()))?;
+
+        if !device.active {
+            return Err(ApplianceError::DeviceNotFound(device_id.to_string()));
+        }
+
+        self.relay_accounting
+            .record(device_id, bytes)
+            .await
+            .map_err(|e| {
+                warn!("Throttling relay for device {}: {}", device_id, e);
+                e
+            })
+    }
+
+    /// Relay usage for a device
+    pub async fn relay_usage(&self, device_id: &str) -> RelayUsage {
+        self.relay_accounting.usage(device_id).await
+    }
+
     /// Retrieve cached messages for a device
     pub async fn retrieve_messages(
         &self,
@@ -358,6 +721,7 @@ impl ApplianceManager {
             paired_devices,
             total_cached_messages: total_cached,
             adapters_online,
+            relay_usage: self.relay_accounting.all_usage().await,
         })
     }
 
@@ -418,18 +782,25 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp(),
         };
 
-        let token = manager.initiate_pairing(request).await.unwrap();
+        let token = manager.initiate_pairing(request, client()).await.unwrap();
 
         // Complete pairing
         let challenge_sig = device_signing_key.sign(&token.challenge);
         let response = PairingResponse {
             pairing_token: token.token.clone(),
             challenge_signature: challenge_sig.to_bytes().to_vec(),
+            pin: None,
         };
 
         let node_id = NodeId::from_bytes([0u8; 64]);
         let result = manager
-            .complete_pairing(response, "mobile-1".to_string(), node_id, device_public_key)
+            .complete_pairing(
+                response,
+                "mobile-1".to_string(),
+                node_id,
+                device_public_key,
+                client(),
+            )
             .await
             .unwrap();
 
@@ -496,4 +867,439 @@ mod tests {
         let stats = manager.get_cache_stats("mobile-1").await.unwrap();
         assert_eq!(stats.undelivered, 0);
     }
+
+    fn client() -> IpAddr {
+        IpAddr::from([127, 0, 0, 1])
+    }
+
+    fn device_key() -> SigningKey {
+        let mut key_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut key_bytes);
+        SigningKey::from_bytes(&key_bytes)
+    }
+
+    async fn pair(
+        manager: &ApplianceManager,
+        device_id: &str,
+        device_key: &SigningKey,
+    ) -> (PairingResponse, PairingResult) {
+        let public_key = device_key.verifying_key().to_bytes().to_vec();
+        let request = PairingRequest {
+            device_id: device_id.to_string(),
+            public_key: public_key.clone(),
+            method: PairingMethod::QrCode,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        let token = manager.initiate_pairing(request, client()).await.unwrap();
+        let response = PairingResponse {
+            pairing_token: token.token.clone(),
+            challenge_signature: device_key.sign(&token.challenge).to_bytes().to_vec(),
+            pin: token.pin.clone(),
+        };
+        let result = manager
+            .complete_pairing(
+                response.clone(),
+                device_id.to_string(),
+                NodeId::from_bytes([0u8; 64]),
+                public_key,
+                client(),
+            )
+            .await
+            .unwrap();
+        (response, result)
+    }
+
+    async fn tofu_manager(temp_dir: &TempDir) -> ApplianceManager {
+        let config = ApplianceManagerConfig {
+            node_id: "test-appliance".to_string(),
+            data_directory: temp_dir.path().to_path_buf(),
+            require_pairing_approval: false,
+            ..Default::default()
+        };
+        ApplianceManager::new(config, device_key()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_first_pairing_records_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = tofu_manager(&temp_dir).await;
+        let key = device_key();
+
+        let (_, result) = pair(&manager, "mobile-1", &key).await;
+        assert!(result.success);
+        assert!(!result.key_mismatch);
+
+        let known = manager.device_store.known_key("mobile-1").await.unwrap();
+        assert_eq!(known, Some(key.verifying_key().to_bytes().to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_repair_with_same_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = tofu_manager(&temp_dir).await;
+        let key = device_key();
+
+        assert!(pair(&manager, "mobile-1", &key).await.1.success);
+        manager.unpair_device("mobile-1").await.unwrap();
+
+        let (_, result) = pair(&manager, "mobile-1", &key).await;
+        assert!(result.success);
+        assert!(!result.key_mismatch);
+    }
+
+    #[tokio::test]
+    async fn test_repair_with_different_key_flagged() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = tofu_manager(&temp_dir).await;
+
+        assert!(pair(&manager, "mobile-1", &device_key()).await.1.success);
+        manager.unpair_device("mobile-1").await.unwrap();
+
+        let new_key = device_key();
+        let new_public_key = new_key.verifying_key().to_bytes().to_vec();
+        let (response, result) = pair(&manager, "mobile-1", &new_key).await;
+        assert!(!result.success);
+        assert!(result.key_mismatch);
+        assert!(result.session_token.is_none());
+        assert!(manager
+            .get_paired_device("mobile-1")
+            .await
+            .unwrap()
+            .is_none());
+
+        // Once the operator confirms, the pending pairing can complete
+        manager
+            .confirm_rekey("mobile-1", new_public_key.clone())
+            .await
+            .unwrap();
+        let result = manager
+            .complete_pairing(
+                response,
+                "mobile-1".to_string(),
+                NodeId::from_bytes([0u8; 64]),
+                new_public_key.clone(),
+                client(),
+            )
+            .await
+            .unwrap();
+        assert!(result.success);
+        let device = manager
+            .get_paired_device("mobile-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(device.public_key, new_public_key);
+    }
+
+    #[tokio::test]
+    async fn test_substituted_known_key_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = tofu_manager(&temp_dir).await;
+
+        let victim_key = device_key();
+        let victim_public_key = victim_key.verifying_key().to_bytes().to_vec();
+        assert!(pair(&manager, "mobile-1", &victim_key).await.1.success);
+        manager.unpair_device("mobile-1").await.unwrap();
+
+        // Attacker pairs as the victim with their own key, then claims the
+        // victim's known key in the completion body
+        let attacker_key = device_key();
+        let request = PairingRequest {
+            device_id: "mobile-1".to_string(),
+            public_key: attacker_key.verifying_key().to_bytes().to_vec(),
+            method: PairingMethod::QrCode,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        let token = manager.initiate_pairing(request, client()).await.unwrap();
+        let response = PairingResponse {
+            pairing_token: token.token.clone(),
+            challenge_signature: attacker_key.sign(&token.challenge).to_bytes().to_vec(),
+            pin: None,
+        };
+        let result = manager
+            .complete_pairing(
+                response,
+                "mobile-1".to_string(),
+                NodeId::from_bytes([0u8; 64]),
+                victim_public_key,
+                client(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.session_token.is_none());
+        assert!(manager
+            .get_paired_device("mobile-1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    /// Start PIN pairing for `device_id` from `client`
+    async fn initiate_pin_pairing(
+        manager: &ApplianceManager,
+        device_id: &str,
+        key: &SigningKey,
+        client: IpAddr,
+    ) -> ApplianceResult<PairingToken> {
+        let request = PairingRequest {
+            device_id: device_id.to_string(),
+            public_key: key.verifying_key().to_bytes().to_vec(),
+            method: PairingMethod::Pin,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        manager.initiate_pairing(request, client).await
+    }
+
+    /// Answer a pairing token with `pin` from `client`
+    async fn complete_pin_pairing(
+        manager: &ApplianceManager,
+        device_id: &str,
+        key: &SigningKey,
+        token: &PairingToken,
+        pin: &str,
+        client: IpAddr,
+    ) -> ApplianceResult<PairingResult> {
+        let response = PairingResponse {
+            pairing_token: token.token.clone(),
+            challenge_signature: key.sign(&token.challenge).to_bytes().to_vec(),
+            pin: Some(pin.to_string()),
+        };
+        manager
+            .complete_pairing(
+                response,
+                device_id.to_string(),
+                NodeId::from_bytes([0u8; 64]),
+                key.verifying_key().to_bytes().to_vec(),
+                client,
+            )
+            .await
+    }
+
+    fn wrong_pin(pin: &str) -> &'static str {
+        if pin == "000000" {
+            "111111"
+        } else {
+            "000000"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_wrong_pins_lock_out_client() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ApplianceManagerConfig {
+            node_id: "test-appliance".to_string(),
+            data_directory: temp_dir.path().to_path_buf(),
+            require_pairing_approval: false,
+            max_pin_attempts: 3,
+            ..Default::default()
+        };
+        let manager = ApplianceManager::new(config, device_key()).await.unwrap();
+        let attacker = IpAddr::from([10, 0, 0, 66]);
+
+        // Wrong PINs count against the client even when spread over tokens
+        let key = device_key();
+        let mut token = initiate_pin_pairing(&manager, "mobile-1", &key, attacker)
+            .await
+            .unwrap();
+        for remaining in (0..3).rev() {
+            if remaining == 0 {
+                token = initiate_pin_pairing(&manager, "mobile-1", &key, attacker)
+                    .await
+                    .unwrap();
+            }
+            let pin = token.pin.clone().unwrap();
+            assert!(matches!(
+                complete_pin_pairing(
+                    &manager,
+                    "mobile-1",
+                    &key,
+                    &token,
+                    wrong_pin(&pin),
+                    attacker
+                )
+                .await,
+                Err(ApplianceError::InvalidPin)
+            ));
+            assert_eq!(
+                manager
+                    .pin_lockout_status(attacker)
+                    .await
+                    .remaining_attempts,
+                remaining
+            );
+        }
+
+        let status = manager.pin_lockout_status(attacker).await;
+        assert!(status.locked_out);
+        assert!(status.locked_until.unwrap() > chrono::Utc::now().timestamp());
+
+        // Even the correct PIN is refused while locked out
+        let pin = token.pin.clone().unwrap();
+        assert!(matches!(
+            complete_pin_pairing(&manager, "mobile-1", &key, &token, &pin, attacker).await,
+            Err(ApplianceError::PairingLockedOut(_))
+        ));
+        assert!(matches!(
+            initiate_pin_pairing(&manager, "mobile-1", &key, attacker).await,
+            Err(ApplianceError::PairingLockedOut(_))
+        ));
+
+        // Other clients can still pair
+        assert!(manager.get_capabilities().await.unwrap().pairing_available);
+        assert!(!manager.pin_lockout_status(client()).await.locked_out);
+        let key = device_key();
+        let token = initiate_pin_pairing(&manager, "mobile-2", &key, client())
+            .await
+            .unwrap();
+        let pin = token.pin.clone().unwrap();
+        let result = complete_pin_pairing(&manager, "mobile-2", &key, &token, &pin, client())
+            .await
+            .unwrap();
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn test_pairing_pin_shown_locally() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = tofu_manager(&temp_dir).await;
+        let mut display = manager.subscribe_pairing_pins();
+
+        let key = device_key();
+        let token = initiate_pin_pairing(&manager, "mobile-1", &key, client())
+            .await
+            .unwrap();
+        let shown = display.try_recv().unwrap();
+        assert_eq!(shown.device_id, "mobile-1");
+        assert_eq!(Some(shown.pin), token.pin);
+        assert_eq!(shown.expires_at, token.expires_at);
+
+        // The PIN isn't part of what the pairing API returns
+        let json = serde_json::to_value(&token).unwrap();
+        assert!(json.get("pin").is_none());
+
+        // QR pairing has no PIN to show
+        let (_, result) = pair(&manager, "mobile-2", &device_key()).await;
+        assert!(result.success);
+        assert!(display.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_zero_pin_attempts_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ApplianceManagerConfig {
+            data_directory: temp_dir.path().to_path_buf(),
+            max_pin_attempts: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            ApplianceManager::new(config, device_key()).await,
+            Err(ApplianceError::Configuration(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_config_sync_between_devices() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = tofu_manager(&temp_dir).await;
+        let phone_token = pair(&manager, "phone", &device_key())
+            .await
+            .1
+            .session_token
+            .unwrap();
+        let tablet_token = pair(&manager, "tablet", &device_key())
+            .await
+            .1
+            .session_token
+            .unwrap();
+
+        // Unpaired devices cannot push configuration
+        assert!(manager
+            .sync_config("stranger", &phone_token, ConfigSync::new().snapshot())
+            .await
+            .is_err());
+
+        // Nor can a device posing as another
+        let mut phone = ConfigSync::new();
+        phone.set("phone", "max_hops", ConfigValue::Integer(5));
+        assert!(matches!(
+            manager
+                .sync_config("phone", &tablet_token, phone.snapshot())
+                .await,
+            Err(ApplianceError::InvalidSessionToken(_))
+        ));
+        let report = manager
+            .sync_config("tablet", &tablet_token, phone.snapshot())
+            .await
+            .unwrap();
+        assert_eq!(report.rejected, vec!["max_hops"]);
+
+        let report = manager
+            .sync_config("phone", &phone_token, phone.snapshot())
+            .await
+            .unwrap();
+        assert_eq!(report.applied, vec!["max_hops"]);
+
+        // The tablet edits from a stale view
+        let mut tablet = ConfigSync::new();
+        tablet.set("tablet", "max_hops", ConfigValue::Integer(3));
+        let report = manager
+            .sync_config("tablet", &tablet_token, tablet.snapshot())
+            .await
+            .unwrap();
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(manager.config_conflicts().await.len(), 1);
+
+        manager
+            .resolve_config_conflict("max_hops", ConfigValue::Integer(3))
+            .await
+            .unwrap();
+        assert!(manager.config_conflicts().await.is_empty());
+
+        // Resolution survives a restart and supersedes both devices' edits
+        drop(manager);
+        let manager = tofu_manager(&temp_dir).await;
+        let snapshot = manager.config_snapshot().await;
+        assert_eq!(snapshot["max_hops"].value, ConfigValue::Integer(3));
+        assert!(tablet
+            .merge(&snapshot)
+            .applied
+            .contains(&"max_hops".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_relay_quota_per_device() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ApplianceManagerConfig {
+            node_id: "test-appliance".to_string(),
+            data_directory: temp_dir.path().to_path_buf(),
+            require_pairing_approval: false,
+            relay_quota: RelayQuotaConfig {
+                max_bytes_per_window: 1000,
+                max_messages_per_window: 0,
+                window_secs: 3600,
+            },
+            ..Default::default()
+        };
+        let manager = ApplianceManager::new(config, device_key()).await.unwrap();
+        assert!(pair(&manager, "phone", &device_key()).await.1.success);
+        assert!(pair(&manager, "tablet", &device_key()).await.1.success);
+
+        manager.record_relay("phone", 800).await.unwrap();
+        assert!(matches!(
+            manager.record_relay("phone", 300).await,
+            Err(ApplianceError::QuotaExceeded(_))
+        ));
+
+        // Other devices keep their own quota
+        manager.record_relay("tablet", 900).await.unwrap();
+        assert!(manager.record_relay("stranger", 1).await.is_err());
+
+        let stats = manager.get_stats(0, 0).await.unwrap();
+        assert_eq!(stats.relay_usage["phone"].window_bytes, 800);
+        assert_eq!(stats.relay_usage["phone"].throttled, 1);
+        assert_eq!(stats.relay_usage["tablet"].window_bytes, 900);
+        assert_eq!(manager.relay_usage("tablet").await.total_messages, 1);
+    }
 }
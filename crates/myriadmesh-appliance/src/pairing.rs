@@ -4,12 +4,15 @@ use crate::types::{ApplianceError, ApplianceResult};
 use chrono::{DateTime, Duration, Utc};
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use rand::rngs::OsRng;
-use rand::RngCore;
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+/// Wrong PINs a single pairing token tolerates before it is discarded
+pub const MAX_PIN_ATTEMPTS_PER_TOKEN: u32 = 3;
+
 /// Pairing methods supported
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -36,6 +39,12 @@ pub struct PairingToken {
     pub timestamp: i64,
     pub expires_at: i64,
     pub signature: Vec<u8>,
+    /// One-time PIN for `PairingMethod::Pin`, shown on the appliance only
+    ///
+    /// SECURITY: Never serialized, so the PIN cannot leak through the
+    /// pairing API or QR code and must be entered by the user.
+    #[serde(skip)]
+    pub pin: Option<String>,
 }
 
 impl PairingToken {
@@ -60,9 +69,16 @@ impl PairingToken {
             timestamp,
             expires_at,
             signature,
+            pin: None,
         }
     }
 
+    /// Attach a freshly generated 6-digit PIN
+    pub fn with_pin(mut self) -> Self {
+        self.pin = Some(format!("{:06}", OsRng.gen_range(0..1_000_000)));
+        self
+    }
+
     /// Verify token signature
     pub fn verify(&self, public_key: &VerifyingKey) -> ApplianceResult<()> {
         let message = format!("{}{}{}", self.token, self.node_id, self.timestamp);
@@ -92,6 +108,9 @@ impl PairingToken {
 pub struct PairingResponse {
     pub pairing_token: String,
     pub challenge_signature: Vec<u8>,
+    /// PIN entered by the user, required for PIN pairing
+    #[serde(default)]
+    pub pin: Option<String>,
 }
 
 /// Result of a pairing operation
@@ -100,6 +119,9 @@ pub struct PairingResult {
     pub success: bool,
     pub session_token: Option<String>,
     pub error: Option<String>,
+    /// Device presented a different key than on first pairing
+    #[serde(default)]
+    pub key_mismatch: bool,
 }
 
 /// Pending pairing information
@@ -109,6 +131,8 @@ struct PendingPairing {
     pub token: PairingToken,
     pub created_at: DateTime<Utc>,
     pub approved: bool,
+    /// Wrong PINs entered against this token
+    pub pin_failures: u32,
 }
 
 /// Pairing manager handles device pairing operations
@@ -146,7 +170,10 @@ impl PairingManager {
         }
 
         // Generate pairing token
-        let token = PairingToken::new(self.node_id.clone(), &self.signing_key);
+        let mut token = PairingToken::new(self.node_id.clone(), &self.signing_key);
+        if request.method == PairingMethod::Pin {
+            token = token.with_pin();
+        }
 
         // Store pending pairing
         let pending = PendingPairing {
@@ -154,6 +181,7 @@ impl PairingManager {
             token: token.clone(),
             created_at: Utc::now(),
             approved: !self.require_approval, // Auto-approve if not required
+            pin_failures: 0,
         };
 
         let mut pending_map = self.pending.write().await;
@@ -179,40 +207,74 @@ impl PairingManager {
         Ok(())
     }
 
+    /// Request a pending pairing token was issued for
+    pub async fn pending_request(&self, token: &str) -> ApplianceResult<PairingRequest> {
+        self.pending
+            .read()
+            .await
+            .get(token)
+            .map(|p| p.request.clone())
+            .ok_or_else(|| ApplianceError::InvalidPairingToken(token.to_string()))
+    }
+
     /// Complete pairing with challenge response
+    ///
+    /// Tokens are single-use: a successful pairing consumes the token.
+    /// Returns the request the token was issued for, whose public key the
+    /// challenge was verified against.
     pub async fn complete_pairing(
         &self,
         response: PairingResponse,
-    ) -> ApplianceResult<PairingResult> {
+    ) -> ApplianceResult<(PairingResult, PairingRequest)> {
         let mut pending_map = self.pending.write().await;
 
         let pending = pending_map
             .get(&response.pairing_token)
             .ok_or_else(|| ApplianceError::InvalidPairingToken(response.pairing_token.clone()))?;
+        let request = pending.request.clone();
 
         // Check if expired
         if pending.token.is_expired() {
             pending_map.remove(&response.pairing_token);
-            return Ok(PairingResult {
+            let result = PairingResult {
                 success: false,
                 session_token: None,
                 error: Some("Pairing token expired".to_string()),
-            });
+                key_mismatch: false,
+            };
+            return Ok((result, request));
         }
 
         // Check if approved
         if !pending.approved {
-            return Ok(PairingResult {
+            let result = PairingResult {
                 success: false,
                 session_token: None,
                 error: Some("Pairing not yet approved".to_string()),
-            });
+                key_mismatch: false,
+            };
+            return Ok((result, request));
+        }
+
+        // Check PIN. SECURITY: A token only survives a few wrong guesses,
+        // so spreading attempts across clients can't brute-force its PIN.
+        if let Some(expected) = &pending.token.pin {
+            let entered = response.pin.as_deref().unwrap_or_default();
+            if !constant_time_eq(expected.as_bytes(), entered.as_bytes()) {
+                let token = &response.pairing_token;
+                if let Some(pending) = pending_map.get_mut(token) {
+                    pending.pin_failures += 1;
+                    if pending.pin_failures >= MAX_PIN_ATTEMPTS_PER_TOKEN {
+                        pending_map.remove(token);
+                    }
+                }
+                return Err(ApplianceError::InvalidPin);
+            }
         }
 
         // Verify challenge signature
         let device_public_key = VerifyingKey::from_bytes(
-            &pending
-                .request
+            &request
                 .public_key
                 .clone()
                 .try_into()
@@ -233,11 +295,13 @@ impl PairingManager {
         // Remove from pending
         pending_map.remove(&response.pairing_token);
 
-        Ok(PairingResult {
+        let result = PairingResult {
             success: true,
             session_token: Some(session_token),
             error: None,
-        })
+            key_mismatch: false,
+        };
+        Ok((result, request))
     }
 
     /// Get pending pairing requests (for UI display)
@@ -271,6 +335,11 @@ impl PairingManager {
     }
 }
 
+/// Compare secrets without leaking the mismatch position through timing
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 /// Pairing request information for UI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PairingRequestInfo {
@@ -320,11 +389,13 @@ mod tests {
         let response = PairingResponse {
             pairing_token: token.token.clone(),
             challenge_signature: challenge_sig.to_bytes().to_vec(),
+            pin: None,
         };
 
-        let result = manager.complete_pairing(response).await.unwrap();
+        let (result, paired) = manager.complete_pairing(response).await.unwrap();
         assert!(result.success);
         assert!(result.session_token.is_some());
+        assert_eq!(paired.device_id, "mobile-device-1");
     }
 
     #[tokio::test]
@@ -354,9 +425,10 @@ mod tests {
         let response = PairingResponse {
             pairing_token: token.token.clone(),
             challenge_signature: challenge_sig.to_bytes().to_vec(),
+            pin: None,
         };
 
-        let result = manager.complete_pairing(response.clone()).await.unwrap();
+        let (result, _) = manager.complete_pairing(response.clone()).await.unwrap();
         assert!(!result.success);
         assert!(result.error.unwrap().contains("not yet approved"));
 
@@ -364,7 +436,108 @@ mod tests {
         manager.approve_pairing(&token.token).await.unwrap();
 
         // Now complete should succeed
-        let result = manager.complete_pairing(response).await.unwrap();
+        let (result, _) = manager.complete_pairing(response).await.unwrap();
         assert!(result.success);
     }
+
+    #[tokio::test]
+    async fn test_pin_token_is_single_use() {
+        let mut key_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut key_bytes);
+        let manager = PairingManager::new(
+            SigningKey::from_bytes(&key_bytes),
+            "node".to_string(),
+            false,
+        );
+
+        let mut device_key_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut device_key_bytes);
+        let device_signing_key = SigningKey::from_bytes(&device_key_bytes);
+
+        let request = PairingRequest {
+            device_id: "mobile-device-1".to_string(),
+            public_key: device_signing_key.verifying_key().to_bytes().to_vec(),
+            method: PairingMethod::Pin,
+            timestamp: Utc::now().timestamp(),
+        };
+        let token = manager.initiate_pairing(request).await.unwrap();
+        let pin = token.pin.clone().unwrap();
+        assert_eq!(pin.len(), 6);
+        assert!(!token.to_qr_data().unwrap().contains(&pin));
+
+        let mut response = PairingResponse {
+            pairing_token: token.token.clone(),
+            challenge_signature: device_signing_key
+                .sign(&token.challenge)
+                .to_bytes()
+                .to_vec(),
+            pin: None,
+        };
+        assert!(matches!(
+            manager.complete_pairing(response.clone()).await,
+            Err(ApplianceError::InvalidPin)
+        ));
+
+        response.pin = Some(pin);
+        assert!(
+            manager
+                .complete_pairing(response.clone())
+                .await
+                .unwrap()
+                .0
+                .success
+        );
+
+        // Reusing the consumed token fails
+        assert!(matches!(
+            manager.complete_pairing(response).await,
+            Err(ApplianceError::InvalidPairingToken(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pin_token_discarded_after_wrong_guesses() {
+        let mut key_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut key_bytes);
+        let manager = PairingManager::new(
+            SigningKey::from_bytes(&key_bytes),
+            "node".to_string(),
+            false,
+        );
+
+        let mut device_key_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut device_key_bytes);
+        let device_signing_key = SigningKey::from_bytes(&device_key_bytes);
+
+        let request = PairingRequest {
+            device_id: "mobile-device-1".to_string(),
+            public_key: device_signing_key.verifying_key().to_bytes().to_vec(),
+            method: PairingMethod::Pin,
+            timestamp: Utc::now().timestamp(),
+        };
+        let token = manager.initiate_pairing(request).await.unwrap();
+        let pin = token.pin.clone().unwrap();
+
+        let mut response = PairingResponse {
+            pairing_token: token.token.clone(),
+            challenge_signature: device_signing_key
+                .sign(&token.challenge)
+                .to_bytes()
+                .to_vec(),
+            pin: Some(if pin == "000000" { "111111" } else { "000000" }.to_string()),
+        };
+        for _ in 0..MAX_PIN_ATTEMPTS_PER_TOKEN {
+            assert!(matches!(
+                manager.complete_pairing(response.clone()).await,
+                Err(ApplianceError::InvalidPin)
+            ));
+        }
+
+        // The right PIN is too late; the token is gone
+        response.pin = Some(pin);
+        assert!(matches!(
+            manager.complete_pairing(response).await,
+            Err(ApplianceError::InvalidPairingToken(_))
+        ));
+    }
 }
@@ -0,0 +1,226 @@
+//! Relay accounting and per-device quotas
+//!
+//! Tracks traffic relayed on behalf of each paired device over a rolling
+//! window so that one device cannot monopolize the appliance's uplink.
+
+use crate::types::{ApplianceError, ApplianceResult};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use tokio::sync::RwLock;
+
+/// Per-device relay quota
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayQuotaConfig {
+    /// Bytes a device may relay per window (0 = unlimited)
+    pub max_bytes_per_window: u64,
+    /// Messages a device may relay per window (0 = unlimited)
+    pub max_messages_per_window: u32,
+    /// Rolling window length in seconds
+    pub window_secs: u64,
+}
+
+impl Default for RelayQuotaConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes_per_window: 100 * 1024 * 1024, // 100 MB
+            max_messages_per_window: 10_000,
+            window_secs: 3600, // 1 hour
+        }
+    }
+}
+
+/// Relay usage for one device
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayUsage {
+    /// Bytes relayed within the current window
+    pub window_bytes: u64,
+    /// Messages relayed within the current window
+    pub window_messages: u32,
+    /// Bytes relayed since the appliance started
+    pub total_bytes: u64,
+    /// Messages relayed since the appliance started
+    pub total_messages: u64,
+    /// Relay attempts rejected by the quota
+    pub throttled: u64,
+}
+
+#[derive(Debug, Default)]
+struct DeviceRelayLog {
+    recent: VecDeque<(DateTime<Utc>, u64)>,
+    total_bytes: u64,
+    total_messages: u64,
+    throttled: u64,
+}
+
+impl DeviceRelayLog {
+    fn prune(&mut self, cutoff: DateTime<Utc>) {
+        while self.recent.front().is_some_and(|(at, _)| *at <= cutoff) {
+            self.recent.pop_front();
+        }
+    }
+
+    fn usage(&self) -> RelayUsage {
+        RelayUsage {
+            window_bytes: self.recent.iter().map(|(_, bytes)| bytes).sum(),
+            window_messages: self.recent.len() as u32,
+            total_bytes: self.total_bytes,
+            total_messages: self.total_messages,
+            throttled: self.throttled,
+        }
+    }
+}
+
+/// Rolling-window relay accounting for all devices
+pub struct RelayAccounting {
+    config: RelayQuotaConfig,
+    devices: RwLock<HashMap<String, DeviceRelayLog>>,
+}
+
+impl RelayAccounting {
+    /// Create relay accounting with the given quota
+    pub fn new(config: RelayQuotaConfig) -> Self {
+        Self {
+            config,
+            devices: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record a relayed message, or reject it if it would exceed the quota
+    pub async fn record(&self, device_id: &str, bytes: u64) -> ApplianceResult<()> {
+        self.record_at(device_id, bytes, Utc::now()).await
+    }
+
+    /// Record a relayed message at a given time
+    pub async fn record_at(
+        &self,
+        device_id: &str,
+        bytes: u64,
+        now: DateTime<Utc>,
+    ) -> ApplianceResult<()> {
+        let mut devices = self.devices.write().await;
+        let log = devices.entry(device_id.to_string()).or_default();
+        log.prune(now - self.window());
+        let usage = log.usage();
+
+        let max_messages = self.config.max_messages_per_window;
+        if max_messages > 0 && usage.window_messages >= max_messages {
+            log.throttled += 1;
+            return Err(ApplianceError::QuotaExceeded(format!(
+                "{} relayed {} messages in {}s (limit {})",
+                device_id, usage.window_messages, self.config.window_secs, max_messages
+            )));
+        }
+
+        let max_bytes = self.config.max_bytes_per_window;
+        if max_bytes > 0 && usage.window_bytes + bytes > max_bytes {
+            log.throttled += 1;
+            return Err(ApplianceError::QuotaExceeded(format!(
+                "{} relayed {} bytes in {}s (limit {})",
+                device_id, usage.window_bytes, self.config.window_secs, max_bytes
+            )));
+        }
+
+        log.recent.push_back((now, bytes));
+        log.total_bytes += bytes;
+        log.total_messages += 1;
+        Ok(())
+    }
+
+    /// Usage for one device
+    pub async fn usage(&self, device_id: &str) -> RelayUsage {
+        self.usage_at(device_id, Utc::now()).await
+    }
+
+    /// Usage for one device at a given time
+    pub async fn usage_at(&self, device_id: &str, now: DateTime<Utc>) -> RelayUsage {
+        let mut devices = self.devices.write().await;
+        match devices.get_mut(device_id) {
+            Some(log) => {
+                log.prune(now - self.window());
+                log.usage()
+            }
+            None => RelayUsage::default(),
+        }
+    }
+
+    /// Usage for every device that has relayed traffic
+    pub async fn all_usage(&self) -> HashMap<String, RelayUsage> {
+        let cutoff = Utc::now() - self.window();
+        let mut devices = self.devices.write().await;
+        devices
+            .iter_mut()
+            .map(|(device_id, log)| {
+                log.prune(cutoff);
+                (device_id.clone(), log.usage())
+            })
+            .collect()
+    }
+
+    fn window(&self) -> Duration {
+        Duration::seconds(self.config.window_secs as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_quota_resets_over_window() {
+        let accounting = RelayAccounting::new(RelayQuotaConfig {
+            max_bytes_per_window: 1000,
+            max_messages_per_window: 0,
+            window_secs: 60,
+        });
+        let start = Utc::now();
+
+        accounting.record_at("phone", 600, start).await.unwrap();
+        accounting
+            .record_at("phone", 400, start + Duration::seconds(30))
+            .await
+            .unwrap();
+        assert!(matches!(
+            accounting
+                .record_at("phone", 1, start + Duration::seconds(45))
+                .await,
+            Err(ApplianceError::QuotaExceeded(_))
+        ));
+
+        // The first 600 bytes age out of the window
+        accounting
+            .record_at("phone", 500, start + Duration::seconds(61))
+            .await
+            .unwrap();
+
+        let usage = accounting
+            .usage_at("phone", start + Duration::seconds(61))
+            .await;
+        assert_eq!(usage.window_bytes, 900);
+        assert_eq!(usage.window_messages, 2);
+        assert_eq!(usage.total_bytes, 1500);
+        assert_eq!(usage.total_messages, 3);
+        assert_eq!(usage.throttled, 1);
+
+        // Everything has aged out after a full idle window
+        let usage = accounting
+            .usage_at("phone", start + Duration::seconds(200))
+            .await;
+        assert_eq!(usage.window_bytes, 0);
+        assert_eq!(usage.total_bytes, 1500);
+    }
+
+    #[tokio::test]
+    async fn test_message_quota() {
+        let accounting = RelayAccounting::new(RelayQuotaConfig {
+            max_bytes_per_window: 0,
+            max_messages_per_window: 2,
+            window_secs: 60,
+        });
+
+        accounting.record("phone", 10).await.unwrap();
+        accounting.record("phone", 10).await.unwrap();
+        assert!(accounting.record("phone", 10).await.is_err());
+        accounting.record("tablet", 10).await.unwrap();
+    }
+}
@@ -24,12 +24,24 @@ pub enum ApplianceError {
     #[error("Pairing expired")]
     PairingExpired,
 
+    #[error("Invalid pairing PIN")]
+    InvalidPin,
+
+    #[error("Pairing locked out for {0} seconds after repeated failed PIN attempts")]
+    PairingLockedOut(i64),
+
+    #[error("Invalid session token for device: {0}")]
+    InvalidSessionToken(String),
+
     #[error("Signature verification failed")]
     SignatureVerificationFailed,
 
     #[error("Cache full: cannot store more messages")]
     CacheFull,
 
+    #[error("Relay quota exceeded: {0}")]
+    QuotaExceeded(String),
+
     #[error("Message not found: {0}")]
     MessageNotFound(String),
 
@@ -7,6 +7,11 @@
 //! - Routing (priority queuing, rate limiting, message routing)
 //! - Network (multi-transport abstraction, adapters)
 //! - i2p (capability tokens, privacy layers, onion routing)
+//!
+//! [`NodeBuilder`] assembles these into a running [`Node`].
+
+pub mod metrics;
+pub mod node;
 
 pub use myriadmesh_crypto as crypto;
 pub use myriadmesh_dht as dht;
@@ -18,6 +23,9 @@ pub use myriadmesh_routing as routing;
 pub use crypto::CryptoError;
 pub use protocol::ProtocolError;
 
+pub use metrics::{render_prometheus, AdapterSnapshot, RouterSnapshot, PROMETHEUS_CONTENT_TYPE};
+pub use node::{Node, NodeBuilder, NodeConfig, NodeError};
+
 /// Initialize the MyriadMesh library
 pub fn init() -> Result<(), CryptoError> {
     crypto::init()
@@ -52,6 +60,8 @@ mod tests {
             reputation,
             last_seen: 0,
             rtt_ms: 0.0,
+            pow_nonce: 0,
+            nat_type: None,
         };
         assert_eq!(public_info.node_id, node_id);
 
@@ -0,0 +1,657 @@
+//! Prometheus metrics export
+//!
+//! Renders adapter, router and onion routing statistics in the Prometheus
+//! text exposition format (version 0.0.4), ready to be served from an HTTP
+//! `/metrics` endpoint. Adapter series are labelled with the adapter ID and
+//! type, outbound queue depth with the priority level. Router and onion
+//! families are only emitted when the caller has those components.
+
+use std::fmt::{Display, Write};
+
+use myriadmesh_i2p::OnionRouterStats;
+use myriadmesh_network::AdapterMetrics;
+use myriadmesh_protocol::types::AdapterType;
+use myriadmesh_routing::{PriorityQueueStats, RouterStats};
+
+/// Content type of the rendered text, for the HTTP response header
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Metrics of one registered adapter
+#[derive(Debug, Clone, Copy)]
+pub struct AdapterSnapshot<'a> {
+    /// ID the adapter was registered under
+    pub id: &'a str,
+    pub adapter_type: AdapterType,
+    pub metrics: &'a AdapterMetrics,
+}
+
+/// Statistics of the message router and its outbound queue
+#[derive(Debug, Clone, Copy)]
+pub struct RouterSnapshot<'a> {
+    pub stats: &'a RouterStats,
+    pub queues: &'a PriorityQueueStats,
+}
+
+/// Render node metrics as Prometheus exposition text
+///
+/// Pass `None` for components the node does not run; their families are
+/// left out rather than reported as zero.
+pub fn render_prometheus(
+    adapters: &[AdapterSnapshot<'_>],
+    router: Option<RouterSnapshot<'_>>,
+    onion: Option<&OnionRouterStats>,
+) -> String {
+    let mut out = Exposition::default();
+    render_adapters(&mut out, adapters);
+    if let Some(router) = router {
+        render_router(&mut out, router.stats, router.queues);
+    }
+    if let Some(onion) = onion {
+        render_onion(&mut out, onion);
+    }
+    out.text
+}
+
+fn render_adapters(out: &mut Exposition, adapters: &[AdapterSnapshot<'_>]) {
+    type Field = fn(&AdapterMetrics) -> u64;
+    let counters: [(&str, &str, Field); 5] = [
+        (
+            "myriadmesh_adapter_messages_sent_total",
+            "Messages sent successfully",
+            |m| m.messages_sent,
+        ),
+        (
+            "myriadmesh_adapter_messages_received_total",
+            "Messages received",
+            |m| m.messages_received,
+        ),
+        (
+            "myriadmesh_adapter_send_failures_total",
+            "Failed send attempts",
+            |m| m.send_failures,
+        ),
+        ("myriadmesh_adapter_bytes_sent_total", "Bytes sent", |m| {
+            m.bytes_sent
+        }),
+        (
+            "myriadmesh_adapter_bytes_received_total",
+            "Bytes received",
+            |m| m.bytes_received,
+        ),
+    ];
+    for (name, help, field) in counters {
+        out.family(name, "counter", help);
+        for adapter in adapters {
+            out.sample(name, &adapter_labels(adapter), field(adapter.metrics));
+        }
+    }
+
+    type Gauge = fn(&AdapterMetrics) -> f64;
+    let gauges: [(&str, &str, Gauge); 3] = [
+        (
+            "myriadmesh_adapter_latency_seconds",
+            "Moving average of send latency",
+            |m| m.latency_ms / 1000.0,
+        ),
+        (
+            "myriadmesh_adapter_reliability_ratio",
+            "Successful sends over send attempts",
+            |m| m.reliability,
+        ),
+        (
+            "myriadmesh_adapter_bandwidth_bits_per_second",
+            "Estimated bandwidth",
+            |m| m.bandwidth_bps as f64,
+        ),
+    ];
+    for (name, help, field) in gauges {
+        out.family(name, "gauge", help);
+        for adapter in adapters {
+            out.sample(
+                name,
+                &adapter_labels(adapter),
+                Float(field(adapter.metrics)),
+            );
+        }
+    }
+
+    let name = "myriadmesh_adapter_send_latency_seconds";
+    out.family(name, "histogram", "Send latency distribution");
+    for adapter in adapters {
+        let histogram = &adapter.metrics.latency_histogram;
+        let labels = adapter_labels(adapter);
+        for (bound_ms, count) in histogram.cumulative() {
+            let le = Float(bound_ms / 1000.0).to_string();
+            out.sample(&format!("{}_bucket", name), &with_le(&labels, &le), count);
+        }
+        out.sample(
+            &format!("{}_bucket", name),
+            &with_le(&labels, "+Inf"),
+            histogram.count(),
+        );
+        out.sample(
+            &format!("{}_sum", name),
+            &labels,
+            Float(histogram.sum_ms() / 1000.0),
+        );
+        out.sample(&format!("{}_count", name), &labels, histogram.count());
+    }
+}
+
+fn render_router(out: &mut Exposition, router: &RouterStats, queues: &PriorityQueueStats) {
+    let counters = [
+        (
+            "myriadmesh_router_messages_routed_total",
+            "Messages accepted for delivery or forwarding",
+            router.messages_routed,
+        ),
+        (
+            "myriadmesh_router_messages_dropped_total",
+            "Messages dropped for any reason",
+            router.messages_dropped,
+        ),
+        (
+            "myriadmesh_router_messages_sent_total",
+            "Outbound messages transmitted",
+            router.messages_sent,
+        ),
+        (
+            "myriadmesh_router_rate_limit_hits_total",
+            "Messages rejected by the rate limiter",
+            router.rate_limit_hits,
+        ),
+        (
+            "myriadmesh_router_spam_detections_total",
+            "Messages flagged as spam",
+            router.spam_detections,
+        ),
+        (
+            "myriadmesh_router_burst_limit_hits_total",
+            "Messages rejected by burst protection",
+            router.burst_limit_hits,
+        ),
+        (
+            "myriadmesh_router_invalid_messages_total",
+            "Messages failing validation",
+            router.invalid_messages,
+        ),
+        (
+            "myriadmesh_router_ttl_expired_total",
+            "Messages dropped because their TTL ran out",
+            router.ttl_expired,
+        ),
+        (
+            "myriadmesh_router_send_retries_total",
+            "Failed sends scheduled for another attempt",
+            router.send_retries,
+        ),
+        (
+            "myriadmesh_router_retries_exhausted_total",
+            "Messages dropped after exhausting their send attempts",
+            router.retries_exhausted,
+        ),
+        (
+            "myriadmesh_router_non_retryable_failures_total",
+            "Messages dropped on a non-retryable send error",
+            router.non_retryable_failures,
+        ),
+        (
+            "myriadmesh_router_tag_filtered_total",
+            "Tagged messages matching no subscription",
+            router.tag_filtered,
+        ),
+        (
+            "myriadmesh_router_reorder_skipped_total",
+            "Sequence numbers ordered delivery gave up waiting for",
+            router.reorder_skipped,
+        ),
+        (
+            "myriadmesh_router_congestion_escalations_total",
+            "Transitions to a higher congestion level",
+            router.congestion_escalations,
+        ),
+        (
+            "myriadmesh_router_congestion_deescalations_total",
+            "Transitions to a lower congestion level",
+            router.congestion_deescalations,
+        ),
+    ];
+    for (name, help, value) in counters {
+        out.family(name, "counter", help);
+        out.sample(name, &[], value);
+    }
+
+    let name = "myriadmesh_router_queue_depth";
+    out.family(name, "gauge", "Messages waiting in the outbound queue");
+    for (priority, depth) in [
+        ("emergency", queues.emergency),
+        ("high", queues.high),
+        ("normal", queues.normal),
+        ("low", queues.low),
+        ("background", queues.background),
+    ] {
+        out.sample(name, &[("priority", priority)], depth);
+    }
+}
+
+fn render_onion(out: &mut Exposition, onion: &OnionRouterStats) {
+    let name = "myriadmesh_onion_active_routes";
+    out.family(name, "gauge", "Unexpired onion routes");
+    out.sample(name, &[], onion.active_routes);
+
+    let counters = [
+        (
+            "myriadmesh_onion_routes_built_total",
+            "Onion routes selected",
+            onion.routes_built,
+        ),
+        (
+            "myriadmesh_onion_onions_built_total",
+            "Onions wrapped for sending",
+            onion.onions_built,
+        ),
+        (
+            "myriadmesh_onion_layers_peeled_total",
+            "Onion layers decrypted",
+            onion.layers_peeled,
+        ),
+        (
+            "myriadmesh_onion_peel_failures_total",
+            "Onion layers that failed to decrypt",
+            onion.peel_failures,
+        ),
+    ];
+    for (name, help, value) in counters {
+        out.family(name, "counter", help);
+        out.sample(name, &[], value);
+    }
+}
+
+fn adapter_labels<'a>(adapter: &AdapterSnapshot<'a>) -> Vec<(&'a str, &'a str)> {
+    vec![
+        ("adapter", adapter.id),
+        ("adapter_type", adapter.adapter_type.as_str()),
+    ]
+}
+
+fn with_le<'a>(labels: &[(&'a str, &'a str)], le: &'a str) -> Vec<(&'a str, &'a str)> {
+    let mut labels = labels.to_vec();
+    labels.push(("le", le));
+    labels
+}
+
+/// Float sample value in the spelling Prometheus expects for non-finite values
+struct Float(f64);
+
+impl Display for Float {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            v if v.is_nan() => f.write_str("NaN"),
+            v if v == f64::INFINITY => f.write_str("+Inf"),
+            v if v == f64::NEG_INFINITY => f.write_str("-Inf"),
+            v => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Exposition text under construction
+#[derive(Default)]
+struct Exposition {
+    text: String,
+}
+
+impl Exposition {
+    /// Start a metric family with its HELP and TYPE lines
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.text, "# HELP {} {}", name, help);
+        let _ = writeln!(self.text, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        self.text.push_str(name);
+        if !labels.is_empty() {
+            self.text.push('{');
+            for (i, (key, value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.text.push(',');
+                }
+                let _ = write!(self.text, "{}=\"{}\"", key, escape_label(value));
+            }
+            self.text.push('}');
+        }
+        let _ = writeln!(self.text, " {}", value);
+    }
+}
+
+/// Escape a label value: backslash, double quote and newline
+fn escape_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+    use std::time::Duration;
+
+    /// One parsed sample line
+    #[derive(Debug)]
+    struct Sample {
+        name: String,
+        labels: HashMap<String, String>,
+        value: f64,
+    }
+
+    fn is_metric_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+    }
+
+    fn parse_value(value: &str) -> f64 {
+        match value {
+            "+Inf" => f64::INFINITY,
+            "-Inf" => f64::NEG_INFINITY,
+            "NaN" => f64::NAN,
+            v => v.parse().unwrap_or_else(|_| panic!("bad value {:?}", v)),
+        }
+    }
+
+    /// Parse `key="value",...}` up to and including the closing brace
+    fn parse_labels(text: &str) -> (HashMap<String, String>, &str) {
+        let mut labels = HashMap::new();
+        let mut rest = text;
+        loop {
+            if let Some(after) = rest.strip_prefix('}') {
+                return (labels, after);
+            }
+            let eq = rest.find('=').expect("label without '='");
+            let key = &rest[..eq];
+            assert!(is_metric_name(key) && !key.contains(':'), "label {:?}", key);
+
+            let mut chars = rest[eq + 1..].char_indices();
+            assert_eq!(chars.next(), Some((0, '"')), "unquoted label value");
+            let mut value = String::new();
+            let end = loop {
+                match chars.next().expect("unterminated label value") {
+                    (_, '\\') => match chars.next().expect("dangling escape").1 {
+                        '\\' => value.push('\\'),
+                        '"' => value.push('"'),
+                        'n' => value.push('\n'),
+                        c => panic!("bad escape \\{}", c),
+                    },
+                    (i, '"') => break i,
+                    (_, c) => value.push(c),
+                }
+            };
+            assert!(labels.insert(key.to_string(), value).is_none());
+
+            rest = &rest[eq + 1 + end + 1..];
+            rest = rest.strip_prefix(',').unwrap_or(rest);
+        }
+    }
+
+    /// Check `text` is well-formed exposition text and return its samples
+    /// along with each family's declared type
+    fn parse(text: &str) -> (Vec<Sample>, HashMap<String, String>) {
+        let mut types = HashMap::new();
+        let mut helped = HashSet::new();
+        let mut samples = Vec::new();
+        assert!(text.ends_with('\n'));
+
+        for line in text.lines() {
+            if let Some(help) = line.strip_prefix("# HELP ") {
+                let name = help.split(' ').next().unwrap();
+                assert!(is_metric_name(name), "{:?}", line);
+                assert!(helped.insert(name.to_string()), "duplicate HELP {}", name);
+                continue;
+            }
+            if let Some(declared) = line.strip_prefix("# TYPE ") {
+                let (name, kind) = declared.split_once(' ').unwrap();
+                assert!(is_metric_name(name), "{:?}", line);
+                assert!(
+                    ["counter", "gauge", "histogram", "summary", "untyped"].contains(&kind),
+                    "{:?}",
+                    line
+                );
+                assert!(
+                    types.insert(name.to_string(), kind.to_string()).is_none(),
+                    "duplicate TYPE {}",
+                    name
+                );
+                continue;
+            }
+            assert!(!line.starts_with('#'), "unexpected comment {:?}", line);
+
+            let name_end = line.find(['{', ' ']).expect("sample without value");
+            let name = &line[..name_end];
+            assert!(is_metric_name(name), "{:?}", line);
+            let (labels, rest) = match line[name_end..].strip_prefix('{') {
+                Some(labels) => parse_labels(labels),
+                None => (HashMap::new(), &line[name_end..]),
+            };
+            let value = rest.strip_prefix(' ').expect("missing space before value");
+            samples.push(Sample {
+                name: name.to_string(),
+                labels,
+                value: parse_value(value),
+            });
+
+            // Samples belong to a family whose TYPE came first
+            let family = ["_bucket", "_sum", "_count"]
+                .iter()
+                .filter_map(|suffix| name.strip_suffix(suffix))
+                .find(|family| types.get(*family).is_some_and(|t| t == "histogram"))
+                .unwrap_or(name);
+            assert!(types.contains_key(family), "no TYPE before {}", name);
+        }
+        (samples, types)
+    }
+
+    fn sample<'a>(samples: &'a [Sample], name: &str, labels: &[(&str, &str)]) -> &'a Sample {
+        samples
+            .iter()
+            .find(|s| {
+                s.name == name
+                    && labels
+                        .iter()
+                        .all(|(k, v)| s.labels.get(*k).map(String::as_str) == Some(*v))
+            })
+            .unwrap_or_else(|| panic!("no sample {} {:?}", name, labels))
+    }
+
+    fn render_example() -> String {
+        let mut ethernet = AdapterMetrics::new();
+        for ms in [2, 8, 40, 300, 20_000] {
+            ethernet.record_send(100, Duration::from_millis(ms));
+        }
+        ethernet.record_send_failure();
+        ethernet.record_receive(64);
+        let lora = AdapterMetrics::new();
+
+        let router = RouterStats {
+            messages_routed: 12,
+            messages_dropped: 3,
+            tag_filtered: 1,
+            ..Default::default()
+        };
+        let queues = PriorityQueueStats {
+            emergency: 1,
+            high: 0,
+            normal: 4,
+            low: 0,
+            background: 2,
+            total: 7,
+        };
+        let onion = OnionRouterStats {
+            active_routes: 2,
+            layers_peeled: 5,
+            ..Default::default()
+        };
+
+        render_prometheus(
+            &[
+                AdapterSnapshot {
+                    id: "eth0",
+                    adapter_type: AdapterType::Ethernet,
+                    metrics: &ethernet,
+                },
+                AdapterSnapshot {
+                    id: "radio \"1\"",
+                    adapter_type: AdapterType::LoRaWAN,
+                    metrics: &lora,
+                },
+            ],
+            Some(RouterSnapshot {
+                stats: &router,
+                queues: &queues,
+            }),
+            Some(&onion),
+        )
+    }
+
+    #[test]
+    fn test_output_is_well_formed() {
+        let (samples, types) = parse(&render_example());
+
+        assert_eq!(types["myriadmesh_adapter_messages_sent_total"], "counter");
+        assert_eq!(types["myriadmesh_adapter_latency_seconds"], "gauge");
+        assert_eq!(
+            types["myriadmesh_adapter_send_latency_seconds"],
+            "histogram"
+        );
+        assert_eq!(types["myriadmesh_router_queue_depth"], "gauge");
+        assert_eq!(types["myriadmesh_onion_active_routes"], "gauge");
+        for (name, kind) in &types {
+            assert_eq!(kind == "counter", name.ends_with("_total"), "{}", name);
+        }
+
+        // Every declared family has samples
+        for name in types.keys() {
+            assert!(
+                samples.iter().any(|s| s.name.starts_with(name.as_str())),
+                "{} has no samples",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_expected_values_and_labels() {
+        let (samples, _) = parse(&render_example());
+
+        let eth = [("adapter", "eth0"), ("adapter_type", "ethernet")];
+        assert_eq!(
+            sample(&samples, "myriadmesh_adapter_messages_sent_total", &eth).value,
+            5.0
+        );
+        assert_eq!(
+            sample(&samples, "myriadmesh_adapter_send_failures_total", &eth).value,
+            1.0
+        );
+        assert_eq!(
+            sample(&samples, "myriadmesh_adapter_bytes_received_total", &eth).value,
+            64.0
+        );
+
+        // Label values are escaped and round-trip
+        let lora = [("adapter", "radio \"1\""), ("adapter_type", "lorawan")];
+        assert_eq!(
+            sample(&samples, "myriadmesh_adapter_messages_sent_total", &lora).value,
+            0.0
+        );
+
+        assert_eq!(
+            sample(&samples, "myriadmesh_router_messages_routed_total", &[]).value,
+            12.0
+        );
+        assert_eq!(
+            sample(&samples, "myriadmesh_router_tag_filtered_total", &[]).value,
+            1.0
+        );
+        for (priority, depth) in [("emergency", 1.0), ("normal", 4.0), ("background", 2.0)] {
+            let labels = [("priority", priority)];
+            assert_eq!(
+                sample(&samples, "myriadmesh_router_queue_depth", &labels).value,
+                depth
+            );
+        }
+        assert_eq!(
+            sample(&samples, "myriadmesh_onion_active_routes", &[]).value,
+            2.0
+        );
+        assert_eq!(
+            sample(&samples, "myriadmesh_onion_layers_peeled_total", &[]).value,
+            5.0
+        );
+    }
+
+    #[test]
+    fn test_latency_histogram() {
+        let (samples, _) = parse(&render_example());
+        let name = "myriadmesh_adapter_send_latency_seconds";
+
+        let buckets: Vec<(f64, f64)> = samples
+            .iter()
+            .filter(|s| s.name == format!("{}_bucket", name) && s.labels["adapter"] == "eth0")
+            .map(|s| (parse_value(&s.labels["le"]), s.value))
+            .collect();
+        assert!(buckets.len() > 1);
+
+        // Bounds ascend, counts are cumulative and end at +Inf == _count
+        for pair in buckets.windows(2) {
+            assert!(pair[0].0 < pair[1].0);
+            assert!(pair[0].1 <= pair[1].1);
+        }
+        let eth = [("adapter", "eth0")];
+        let count = sample(&samples, &format!("{}_count", name), &eth).value;
+        assert_eq!(buckets.last(), Some(&(f64::INFINITY, count)));
+        assert_eq!(count, 5.0);
+
+        assert_eq!(
+            sample(&samples, &format!("{}_bucket", name), &[("le", "0.01")]).value,
+            2.0
+        );
+        // The 20s send only lands in +Inf
+        assert_eq!(buckets[buckets.len() - 2].1, 4.0);
+
+        let sum = sample(&samples, &format!("{}_sum", name), &eth).value;
+        assert!((sum - 20.35).abs() < 1e-6, "sum = {}", sum);
+    }
+
+    #[test]
+    fn test_empty_node_renders() {
+        let text = render_prometheus(&[], None, None);
+        let (samples, types) = parse(&text);
+        assert!(types.contains_key("myriadmesh_adapter_messages_sent_total"));
+        assert!(samples.is_empty());
+
+        // Components the node does not run are left out, not zeroed
+        assert!(!types
+            .keys()
+            .any(|name| name.starts_with("myriadmesh_router_")));
+        assert!(!types
+            .keys()
+            .any(|name| name.starts_with("myriadmesh_onion_")));
+    }
+
+    #[test]
+    fn test_float_formatting() {
+        assert_eq!(Float(0.25).to_string(), "0.25");
+        assert_eq!(Float(1.0).to_string(), "1");
+        assert_eq!(Float(f64::INFINITY).to_string(), "+Inf");
+        assert_eq!(Float(f64::NAN).to_string(), "NaN");
+        assert_eq!(escape_label("a\\b\n\"c\""), "a\\\\b\\n\\\"c\\\"");
+    }
+}
@@ -0,0 +1,525 @@
+//! Node assembly
+//!
+//! [`NodeBuilder`] wires identity, routing, DHT, network adapters and onion
+//! routing into a running [`Node`]. Every subsystem has a default and can be
+//! replaced before building, which is how tests substitute in-memory parts.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use myriadmesh_crypto::identity::NodeIdentity;
+use myriadmesh_crypto::keyexchange::KeyExchangeKeypair;
+use myriadmesh_crypto::CryptoError;
+use myriadmesh_dht::RoutingTable;
+use myriadmesh_i2p::onion::RouteNode;
+use myriadmesh_i2p::{OnionConfig, OnionRoute, OnionRouter};
+use myriadmesh_network::{AdapterManager, NetworkAdapter, NetworkError};
+use myriadmesh_protocol::{Frame, Message, NodeId};
+use myriadmesh_routing::{PriorityRateLimits, RetryPolicy, Router, RoutingError};
+use thiserror::Error;
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::task::JoinHandle;
+
+use crate::metrics::{render_prometheus, AdapterSnapshot, RouterSnapshot};
+
+/// How long each adapter `receive` call waits before re-checking for shutdown
+const RECEIVE_POLL_MS: u64 = 100;
+
+/// Pause after a receive error so a failing adapter does not spin
+const RECEIVE_ERROR_BACKOFF_MS: u64 = 50;
+
+/// Errors raised while assembling a node
+#[derive(Error, Debug)]
+pub enum NodeError {
+    #[error("Crypto error: {0}")]
+    Crypto(#[from] CryptoError),
+
+    #[error("Network error: {0}")]
+    Network(#[from] NetworkError),
+
+    #[error("{0} was created for a different node ID")]
+    IdentityMismatch(&'static str),
+}
+
+/// Settings for subsystems the builder creates itself
+#[derive(Debug, Clone)]
+pub struct NodeConfig {
+    /// Router messages per minute per source node
+    pub per_node_rate_limit: u32,
+    /// Router messages per minute in total
+    pub global_rate_limit: u32,
+    /// Per-priority rate limits with an Emergency reserve (off when None)
+    pub priority_rate_limits: Option<PriorityRateLimits>,
+    /// Outbound queue capacity per priority level
+    pub queue_capacity: usize,
+    /// Retry policy for outbound sends
+    pub retry_policy: RetryPolicy,
+    /// Onion routing settings
+    pub onion: OnionConfig,
+}
+
+impl Default for NodeConfig {
+    fn default() -> Self {
+        Self {
+            per_node_rate_limit: 60,
+            global_rate_limit: 1000,
+            priority_rate_limits: None,
+            queue_capacity: 1000,
+            retry_policy: RetryPolicy::default(),
+            onion: OnionConfig::default(),
+        }
+    }
+}
+
+/// Builder for a fully wired [`Node`]
+///
+/// Subsystems not supplied are created from the [`NodeConfig`]: a fresh
+/// identity, a router, an empty DHT routing table, an adapter manager and an
+/// onion router with a new key exchange keypair.
+pub struct NodeBuilder {
+    config: NodeConfig,
+    identity: Option<NodeIdentity>,
+    router: Option<Router>,
+    dht: Option<RoutingTable>,
+    adapter_manager: Option<AdapterManager>,
+    adapters: Vec<(String, Box<dyn NetworkAdapter>)>,
+    onion_router: Option<OnionRouter>,
+}
+
+impl NodeBuilder {
+    /// Create a builder using `config` for default subsystems
+    pub fn new(config: NodeConfig) -> Self {
+        Self {
+            config,
+            identity: None,
+            router: None,
+            dht: None,
+            adapter_manager: None,
+            adapters: Vec::new(),
+            onion_router: None,
+        }
+    }
+
+    /// Use an existing identity instead of generating one
+    pub fn with_identity(mut self, identity: NodeIdentity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Use a preconfigured router
+    ///
+    /// Its local delivery channel is replaced by the node's.
+    pub fn with_router(mut self, router: Router) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    /// Use a preconfigured DHT routing table
+    pub fn with_dht(mut self, dht: RoutingTable) -> Self {
+        self.dht = Some(dht);
+        self
+    }
+
+    /// Use a preconfigured adapter manager
+    pub fn with_adapter_manager(mut self, adapter_manager: AdapterManager) -> Self {
+        self.adapter_manager = Some(adapter_manager);
+        self
+    }
+
+    /// Register a network adapter when the node is built
+    pub fn with_adapter(mut self, id: impl Into<String>, adapter: Box<dyn NetworkAdapter>) -> Self {
+        self.adapters.push((id.into(), adapter));
+        self
+    }
+
+    /// Use a preconfigured onion router
+    pub fn with_onion_router(mut self, onion_router: OnionRouter) -> Self {
+        self.onion_router = Some(onion_router);
+        self
+    }
+
+    /// Assemble the subsystems and start the node
+    ///
+    /// Registers (and so starts) the adapters, then spawns the outbound
+    /// queue processor and a receive loop per adapter. Adapters must be
+    /// added through the builder to be received from.
+    pub async fn build(self) -> Result<Node, NodeError> {
+        myriadmesh_crypto::init()?;
+
+        let identity = match self.identity {
+            Some(identity) => identity,
+            None => NodeIdentity::generate()?,
+        };
+        if NodeIdentity::derive_node_id(&identity.public_key) != identity.node_id {
+            return Err(NodeError::IdentityMismatch("Identity public key"));
+        }
+        let node_id = NodeId::from_bytes(*identity.node_id.as_bytes());
+
+        let dht = self.dht.unwrap_or_else(|| RoutingTable::new(node_id));
+        if *dht.local_node_id() != node_id {
+            return Err(NodeError::IdentityMismatch("DHT routing table"));
+        }
+
+        let mut router = match self.router {
+            Some(router) if router.node_id() != node_id => {
+                return Err(NodeError::IdentityMismatch("Router"));
+            }
+            Some(router) => router,
+            None => {
+                let router = Router::new(
+                    node_id,
+                    self.config.per_node_rate_limit,
+                    self.config.global_rate_limit,
+                    self.config.queue_capacity,
+                );
+                router
+                    .set_priority_rate_limits(self.config.priority_rate_limits)
+                    .await;
+                router
+            }
+        };
+        let (local_tx, local_rx) = Router::create_local_delivery_channel();
+        router.set_local_delivery_channel(local_tx);
+
+        let onion_router = self.onion_router.unwrap_or_else(|| {
+            OnionRouter::new(node_id, KeyExchangeKeypair::generate(), self.config.onion)
+        });
+        if *onion_router.local_node_id() != node_id {
+            return Err(NodeError::IdentityMismatch("Onion router"));
+        }
+
+        let mut adapter_manager = self.adapter_manager.unwrap_or_default();
+        for (id, adapter) in self.adapters {
+            adapter_manager.register_adapter(id, adapter).await?;
+        }
+
+        let node = Node {
+            node_id,
+            identity: Arc::new(identity),
+            router: Arc::new(router),
+            dht: Arc::new(RwLock::new(dht)),
+            adapter_manager: Arc::new(RwLock::new(adapter_manager)),
+            onion_router: Arc::new(RwLock::new(onion_router)),
+            local_rx: Some(local_rx),
+            shutdown_tx: broadcast::channel(1).0,
+            tasks: Vec::new(),
+        };
+        Ok(node.start(self.config.retry_policy).await)
+    }
+}
+
+impl Default for NodeBuilder {
+    fn default() -> Self {
+        Self::new(NodeConfig::default())
+    }
+}
+
+/// A running node with handles to its subsystems
+pub struct Node {
+    node_id: NodeId,
+    identity: Arc<NodeIdentity>,
+    router: Arc<Router>,
+    dht: Arc<RwLock<RoutingTable>>,
+    adapter_manager: Arc<RwLock<AdapterManager>>,
+    onion_router: Arc<RwLock<OnionRouter>>,
+    local_rx: Option<mpsc::UnboundedReceiver<Message>>,
+    shutdown_tx: broadcast::Sender<()>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Node {
+    /// Spawn the outbound queue processor, adapter receive loops and the
+    /// quarantine re-probe loop
+    async fn start(mut self, retry_policy: RetryPolicy) -> Self {
+        let router = Arc::clone(&self.router);
+        let dht = Arc::clone(&self.dht);
+        let adapter_manager = Arc::clone(&self.adapter_manager);
+        let shutdown = self.shutdown_tx.subscribe();
+        self.tasks.push(tokio::spawn(async move {
+            router
+                .run_queue_processor(
+                    retry_policy,
+                    |message| {
+                        transmit(
+                            Arc::clone(&router),
+                            Arc::clone(&dht),
+                            Arc::clone(&adapter_manager),
+                            message,
+                        )
+                    },
+                    shutdown,
+                )
+                .await
+        }));
+
+        let adapters: Vec<_> = {
+            let manager = self.adapter_manager.read().await;
+            manager
+                .adapter_ids()
+                .iter()
+                .filter_map(|id| manager.get_adapter(id))
+                .collect()
+        };
+        for adapter in adapters {
+            self.tasks.push(tokio::spawn(receive_loop(
+                adapter,
+                Arc::clone(&self.router),
+                self.shutdown_tx.subscribe(),
+            )));
+        }
+
+        // Bring quarantined adapters back once they pass a re-probe
+        self.tasks
+            .push(tokio::spawn(AdapterManager::run_reprobe_loop(
+                Arc::clone(&self.adapter_manager),
+                self.shutdown_tx.subscribe(),
+            )));
+
+        self
+    }
+
+    /// This node's ID
+    pub fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// This node's identity
+    pub fn identity(&self) -> Arc<NodeIdentity> {
+        Arc::clone(&self.identity)
+    }
+
+    /// Message router
+    pub fn router(&self) -> Arc<Router> {
+        Arc::clone(&self.router)
+    }
+
+    /// DHT routing table
+    pub fn dht(&self) -> Arc<RwLock<RoutingTable>> {
+        Arc::clone(&self.dht)
+    }
+
+    /// Network adapter manager
+    pub fn adapter_manager(&self) -> Arc<RwLock<AdapterManager>> {
+        Arc::clone(&self.adapter_manager)
+    }
+
+    /// Onion router
+    pub fn onion_router(&self) -> Arc<RwLock<OnionRouter>> {
+        Arc::clone(&self.onion_router)
+    }
+
+    /// Select an onion route, rating hops the DHT knows by reputation
+    ///
+    /// Candidates in the routing table take their effective reputation
+    /// (with any version penalty) as `reliability`, so nodes running
+    /// vulnerable adapters are picked less often. Others keep theirs.
+    pub async fn select_onion_route(
+        &self,
+        destination: NodeId,
+        candidates: &[RouteNode],
+    ) -> Result<OnionRoute, String> {
+        let candidates: Vec<RouteNode> = {
+            let dht = self.dht.read().await;
+            candidates
+                .iter()
+                .cloned()
+                .map(|mut node| {
+                    if let Some(info) = dht.find_node(&node.node_id) {
+                        node.reliability = info.reputation.effective_score();
+                    }
+                    node
+                })
+                .collect()
+        };
+        self.onion_router
+            .write()
+            .await
+            .select_route(destination, &candidates)
+    }
+
+    /// Current adapter, router and onion metrics as Prometheus exposition text
+    pub async fn prometheus_metrics(&self) -> String {
+        let router = self.router.get_stats().await;
+        let queues = self.router.get_queue_stats().await;
+        let onion = self.onion_router.read().await.stats();
+
+        let manager = self.adapter_manager.read().await;
+        let mut ids = manager.adapter_ids();
+        ids.sort();
+        let adapters: Vec<_> = ids
+            .iter()
+            .filter_map(|id| {
+                Some(AdapterSnapshot {
+                    id,
+                    adapter_type: manager.get_capabilities(id)?.adapter_type,
+                    metrics: manager.get_metrics(id)?,
+                })
+            })
+            .collect();
+
+        render_prometheus(
+            &adapters,
+            Some(RouterSnapshot {
+                stats: &router,
+                queues: &queues,
+            }),
+            Some(&onion),
+        )
+    }
+
+    /// Take the receiver for messages delivered to this node
+    ///
+    /// Returns `None` after the first call.
+    pub fn take_local_receiver(&mut self) -> Option<mpsc::UnboundedReceiver<Message>> {
+        self.local_rx.take()
+    }
+
+    /// Stop background tasks and adapters
+    pub async fn shutdown(mut self) -> Result<(), NodeError> {
+        let _ = self.shutdown_tx.send(());
+        for task in self.tasks.drain(..) {
+            let _ = task.await;
+        }
+        self.adapter_manager.write().await.stop_all().await?;
+        Ok(())
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        // Background tasks exit on their own once signalled
+        let _ = self.shutdown_tx.send(());
+    }
+}
+
+/// Send a message over the first adapter that reaches the router's next hop
+///
+/// Addresses come from the DHT entry for the hop. A source-routed message
+/// whose hop cannot be reached fails with [`RoutingError::HopUnreachable`],
+/// which the router records as the hop being down.
+async fn transmit(
+    router: Arc<Router>,
+    dht: Arc<RwLock<RoutingTable>>,
+    adapter_manager: Arc<RwLock<AdapterManager>>,
+    message: Message,
+) -> Result<(), RoutingError> {
+    let next_hop = router.next_hop(&message);
+    match transmit_to(&router, dht, adapter_manager, next_hop, &message).await {
+        Err(
+            RoutingError::DestinationNotFound(_)
+            | RoutingError::NoRoute
+            | RoutingError::SendFailed { .. },
+        ) if message.is_source_routed() => Err(RoutingError::HopUnreachable(next_hop)),
+        result => result,
+    }
+}
+
+/// Send a frame for `message` to `next_hop` over any adapter that reaches it
+async fn transmit_to(
+    router: &Router,
+    dht: Arc<RwLock<RoutingTable>>,
+    adapter_manager: Arc<RwLock<AdapterManager>>,
+    next_hop: NodeId,
+    message: &Message,
+) -> Result<(), RoutingError> {
+    let targets = dht
+        .read()
+        .await
+        .find_node(&next_hop)
+        .map(|info| info.adapters.clone())
+        .ok_or_else(|| RoutingError::DestinationNotFound(format!("{:?}", next_hop)))?;
+
+    // TODO: Sign frames once receivers can look up the sender's public key
+    let mut frame = Frame::from_message(message)?;
+    // Tell downstream nodes this hop is congested so senders can back off
+    router.stamp_congestion(&mut frame).await;
+
+    // Quarantined adapters are skipped by `find_adapter_by_type`
+    let candidates: Vec<_> = {
+        let manager = adapter_manager.read().await;
+        targets
+            .iter()
+            .filter(|t| t.active)
+            .filter_map(|target| {
+                let id = manager.find_adapter_by_type(target.adapter_type)?;
+                let adapter = manager.get_adapter(&id)?;
+                Some((id, adapter, target.address.clone()))
+            })
+            .collect()
+    };
+
+    // Retry later if any adapter might succeed then, even when the last
+    // one failed for good
+    let mut last_error = RoutingError::NoRoute;
+    let mut retryable = false;
+    for (id, adapter, address) in candidates {
+        let started = Instant::now();
+        let (destination, result) = {
+            let adapter = adapter.read().await;
+            match adapter.parse_address(&address) {
+                Ok(destination) => {
+                    let result = adapter.send(&destination, &frame).await;
+                    (Some(destination), result)
+                }
+                Err(e) => (None, Err(e)),
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                adapter_manager.write().await.record_send_success(
+                    &id,
+                    frame.size(),
+                    started.elapsed(),
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                // An unparseable address says nothing about the adapter
+                if let Some(destination) = destination {
+                    adapter_manager
+                        .write()
+                        .await
+                        .record_send_failure(&id, &destination);
+                }
+                retryable |= e.is_retryable();
+                last_error = RoutingError::SendFailed {
+                    message: e.to_string(),
+                    retryable,
+                }
+            }
+        }
+    }
+
+    Err(last_error)
+}
+
+/// Feed frames received on one adapter into the router
+async fn receive_loop(
+    adapter: Arc<RwLock<Box<dyn NetworkAdapter>>>,
+    router: Arc<Router>,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    loop {
+        let received = tokio::select! {
+            _ = shutdown.recv() => break,
+            received = async { adapter.read().await.receive(RECEIVE_POLL_MS).await } => received,
+        };
+
+        match received {
+            Ok((_, frame)) => {
+                // Rejected messages are counted in the router's stats
+                if let Ok(message) = frame.to_message() {
+                    let _ = router.route_message(message).await;
+                }
+            }
+            Err(_) => {
+                // Timeouts and transient errors: back off and poll again
+                tokio::select! {
+                    _ = shutdown.recv() => break,
+                    _ = tokio::time::sleep(Duration::from_millis(RECEIVE_ERROR_BACKOFF_MS)) => {}
+                }
+            }
+        }
+    }
+}
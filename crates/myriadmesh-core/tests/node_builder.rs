@@ -0,0 +1,546 @@
+//! End-to-end tests for NodeBuilder using an in-memory transport
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use myriadmesh_core::crypto::identity::NodeIdentity;
+use myriadmesh_core::crypto::keyexchange::{KeyExchangeKeypair, X25519PublicKey};
+use myriadmesh_core::dht::{AdapterInfo, NodeInfo, RoutingTable};
+use myriadmesh_core::i2p::onion::RouteNode;
+use myriadmesh_core::i2p::{OnionConfig, OnionRouter, RouteSelectionStrategy};
+use myriadmesh_core::network::adapter::{PeerInfo, TestResults};
+use myriadmesh_core::network::{
+    AdapterCapabilities, AdapterManager, AdapterStatus, Address, FeatureFlags, NetworkAdapter,
+    NetworkError, PowerConsumption, QuarantineConfig,
+};
+use myriadmesh_core::protocol::frame::FrameFlags;
+use myriadmesh_core::protocol::types::{AdapterType, Priority, NODE_ID_SIZE};
+use myriadmesh_core::protocol::{Frame, Message, MessageId, MessageType, NodeId};
+use myriadmesh_core::routing::{Router, RoutingError};
+use myriadmesh_core::{NodeBuilder, NodeConfig, NodeError};
+use tokio::sync::{mpsc, Mutex};
+
+static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+
+/// A frame and the address it came from or goes to
+type Delivery = (Address, Frame);
+
+/// Transport backed by channels: frames pushed into `inbound` are received,
+/// frames sent appear on `outbound`
+struct MemoryTransport {
+    status: AdapterStatus,
+    capabilities: AdapterCapabilities,
+    inbound: Mutex<mpsc::UnboundedReceiver<Delivery>>,
+    outbound: mpsc::UnboundedSender<Delivery>,
+}
+
+/// Create a transport and the test's ends of its channels
+fn memory_transport() -> (
+    MemoryTransport,
+    mpsc::UnboundedSender<Delivery>,
+    mpsc::UnboundedReceiver<Delivery>,
+) {
+    let (inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+    let (outbound_tx, outbound_rx) = mpsc::unbounded_channel();
+    let transport = MemoryTransport {
+        status: AdapterStatus::Uninitialized,
+        capabilities: AdapterCapabilities {
+            adapter_type: AdapterType::Ethernet,
+            max_message_size: 65535,
+            typical_latency_ms: 1.0,
+            typical_bandwidth_bps: 1_000_000_000,
+            reliability: 1.0,
+            range_meters: 0.0,
+            power_consumption: PowerConsumption::None,
+            cost_per_mb: 0.0,
+            features: FeatureFlags::empty(),
+        },
+        inbound: Mutex::new(inbound_rx),
+        outbound: outbound_tx,
+    };
+    (transport, inbound_tx, outbound_rx)
+}
+
+#[async_trait::async_trait]
+impl NetworkAdapter for MemoryTransport {
+    async fn initialize(&mut self) -> myriadmesh_core::network::Result<()> {
+        self.status = AdapterStatus::Ready;
+        Ok(())
+    }
+
+    async fn start(&mut self) -> myriadmesh_core::network::Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> myriadmesh_core::network::Result<()> {
+        self.status = AdapterStatus::ShuttingDown;
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        destination: &Address,
+        frame: &Frame,
+    ) -> myriadmesh_core::network::Result<()> {
+        self.outbound
+            .send((destination.clone(), frame.clone()))
+            .map_err(|e| NetworkError::SendFailed(e.to_string()))
+    }
+
+    async fn receive(&self, timeout_ms: u64) -> myriadmesh_core::network::Result<(Address, Frame)> {
+        let mut inbound = self.inbound.lock().await;
+        match tokio::time::timeout(Duration::from_millis(timeout_ms), inbound.recv()).await {
+            Ok(Some(received)) => Ok(received),
+            _ => Err(NetworkError::ReceiveFailed("No frame".to_string())),
+        }
+    }
+
+    async fn discover_peers(&self) -> myriadmesh_core::network::Result<Vec<PeerInfo>> {
+        Ok(Vec::new())
+    }
+
+    fn get_status(&self) -> AdapterStatus {
+        self.status
+    }
+
+    fn get_capabilities(&self) -> &AdapterCapabilities {
+        &self.capabilities
+    }
+
+    async fn test_connection(
+        &self,
+        _destination: &Address,
+    ) -> myriadmesh_core::network::Result<TestResults> {
+        Ok(TestResults {
+            success: true,
+            rtt_ms: Some(1.0),
+            error: None,
+        })
+    }
+
+    fn get_local_address(&self) -> Option<Address> {
+        Some(Address::Unknown("memory".to_string()))
+    }
+
+    fn parse_address(&self, addr_str: &str) -> myriadmesh_core::network::Result<Address> {
+        Ok(Address::Unknown(addr_str.to_string()))
+    }
+
+    fn supports_address(&self, address: &Address) -> bool {
+        matches!(address, Address::Unknown(_))
+    }
+}
+
+fn test_message(source: NodeId, destination: NodeId) -> Message {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    let sequence = SEQUENCE.fetch_add(1, Ordering::SeqCst);
+    let payload = vec![7u8; 256];
+
+    Message {
+        id: MessageId::generate(&source, &destination, &payload, timestamp, sequence),
+        source,
+        destination,
+        message_type: MessageType::Data,
+        channel: 0,
+        priority: Priority::normal(),
+        ttl: 16,
+        timestamp,
+        sequence,
+        payload,
+        compressed: false,
+        tags: Vec::new(),
+        source_route: Vec::new(),
+        expires_at: None,
+    }
+}
+
+#[tokio::test]
+async fn test_local_message_routed_end_to_end() {
+    let (transport, inbound, _outbound) = memory_transport();
+    let mut node = NodeBuilder::new(NodeConfig::default())
+        .with_adapter("memory", Box::new(transport))
+        .build()
+        .await
+        .unwrap();
+    let mut local_rx = node.take_local_receiver().unwrap();
+    assert!(node.take_local_receiver().is_none());
+
+    // A peer's frame arrives on the transport, addressed to this node
+    let peer = NodeId::from_bytes([9u8; NODE_ID_SIZE]);
+    let message = test_message(peer, node.node_id());
+    let frame = Frame::from_message(&message).unwrap();
+    inbound
+        .send((Address::Unknown("peer".to_string()), frame))
+        .unwrap();
+
+    let delivered = tokio::time::timeout(Duration::from_secs(2), local_rx.recv())
+        .await
+        .expect("message should be delivered locally")
+        .unwrap();
+    assert_eq!(delivered.id, message.id);
+    assert_eq!(delivered.source, peer);
+    assert_eq!(delivered.payload, message.payload);
+
+    node.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_outbound_message_sent_via_dht_address() {
+    myriadmesh_core::init().unwrap();
+    let identity = NodeIdentity::generate().unwrap();
+    let node_id = NodeId::from_bytes(*identity.node_id.as_bytes());
+    let peer = NodeId::from_bytes([9u8; NODE_ID_SIZE]);
+
+    // Overridden DHT that already knows how to reach the peer
+    let mut dht = RoutingTable::with_pow_difficulty(node_id, 0);
+    dht.add_or_update(NodeInfo::with_adapters(
+        peer,
+        vec![AdapterInfo {
+            adapter_type: AdapterType::Ethernet,
+            address: "peer:4001".to_string(),
+            active: true,
+            reflexive_address: None,
+            nat_type: None,
+        }],
+    ))
+    .unwrap();
+
+    let (transport, _inbound, mut outbound) = memory_transport();
+    let node = NodeBuilder::default()
+        .with_identity(identity)
+        .with_dht(dht)
+        .with_adapter("memory", Box::new(transport))
+        .build()
+        .await
+        .unwrap();
+
+    let message = test_message(node.node_id(), peer);
+    node.router().route_message(message.clone()).await.unwrap();
+
+    let (address, frame) = tokio::time::timeout(Duration::from_secs(2), outbound.recv())
+        .await
+        .expect("frame should be sent")
+        .unwrap();
+    assert_eq!(address, Address::Unknown("peer:4001".to_string()));
+    assert_eq!(frame.header.destination, peer);
+    assert_eq!(frame.header.message_id, message.id);
+
+    // Stats are recorded once the send returns
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while node.router().get_stats().await.messages_sent == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("send should be counted");
+    let manager = node.adapter_manager();
+    let metrics = manager.read().await.get_metrics("memory").cloned().unwrap();
+    assert_eq!(metrics.messages_sent, 1);
+    assert_eq!(metrics.consecutive_failures, 0);
+
+    node.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_failing_adapter_quarantined_by_sends() {
+    myriadmesh_core::init().unwrap();
+    let identity = NodeIdentity::generate().unwrap();
+    let node_id = NodeId::from_bytes(*identity.node_id.as_bytes());
+    let peer = NodeId::from_bytes([9u8; NODE_ID_SIZE]);
+
+    let mut dht = RoutingTable::with_pow_difficulty(node_id, 0);
+    dht.add_or_update(NodeInfo::with_adapters(
+        peer,
+        vec![AdapterInfo {
+            adapter_type: AdapterType::Ethernet,
+            address: "peer:4001".to_string(),
+            active: true,
+            reflexive_address: None,
+            nat_type: None,
+        }],
+    ))
+    .unwrap();
+
+    let mut adapter_manager = AdapterManager::new();
+    adapter_manager.set_quarantine_config(QuarantineConfig {
+        failure_threshold: 1,
+        reprobe_interval: Duration::from_secs(3600),
+    });
+
+    // Every send fails once the far end of the transport is gone
+    let (transport, _inbound, outbound) = memory_transport();
+    drop(outbound);
+    let node = NodeBuilder::default()
+        .with_identity(identity)
+        .with_dht(dht)
+        .with_adapter_manager(adapter_manager)
+        .with_adapter("memory", Box::new(transport))
+        .build()
+        .await
+        .unwrap();
+
+    let message = test_message(node.node_id(), peer);
+    node.router().route_message(message).await.unwrap();
+
+    let manager = node.adapter_manager();
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while !manager.read().await.is_quarantined("memory") {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("failing adapter should be quarantined");
+    assert!(
+        manager
+            .read()
+            .await
+            .get_metrics("memory")
+            .unwrap()
+            .send_failures
+            >= 1
+    );
+    assert_eq!(
+        manager
+            .read()
+            .await
+            .find_adapter_by_type(AdapterType::Ethernet),
+        None
+    );
+
+    node.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_source_routed_message_sent_to_next_hop() {
+    myriadmesh_core::init().unwrap();
+    let identity = NodeIdentity::generate().unwrap();
+    let node_id = NodeId::from_bytes(*identity.node_id.as_bytes());
+    let relay = NodeId::from_bytes([5u8; NODE_ID_SIZE]);
+    let unknown_relay = NodeId::from_bytes([6u8; NODE_ID_SIZE]);
+    let destination = NodeId::from_bytes([9u8; NODE_ID_SIZE]);
+
+    // Only the relay is reachable; the destination is not in the DHT
+    let mut dht = RoutingTable::with_pow_difficulty(node_id, 0);
+    dht.add_or_update(NodeInfo::with_adapters(
+        relay,
+        vec![AdapterInfo {
+            adapter_type: AdapterType::Ethernet,
+            address: "relay:4001".to_string(),
+            active: true,
+            reflexive_address: None,
+            nat_type: None,
+        }],
+    ))
+    .unwrap();
+
+    let (transport, _inbound, mut outbound) = memory_transport();
+    let node = NodeBuilder::default()
+        .with_identity(identity)
+        .with_dht(dht)
+        .with_adapter("memory", Box::new(transport))
+        .build()
+        .await
+        .unwrap();
+
+    let message = test_message(node.node_id(), destination)
+        .with_source_route(vec![relay])
+        .unwrap();
+    node.router().route_message(message).await.unwrap();
+
+    let (address, frame) = tokio::time::timeout(Duration::from_secs(2), outbound.recv())
+        .await
+        .expect("frame should be sent")
+        .unwrap();
+    assert_eq!(address, Address::Unknown("relay:4001".to_string()));
+    assert_eq!(frame.header.destination, destination);
+    assert_eq!(frame.header.source_route, vec![relay]);
+
+    // A hop no adapter can reach is marked down for later messages
+    let via_unknown = || {
+        test_message(node.node_id(), destination)
+            .with_source_route(vec![unknown_relay])
+            .unwrap()
+    };
+    node.router().route_message(via_unknown()).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while node.router().pending_retry_count().await == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("send should fail");
+    assert!(matches!(
+        node.router().route_message(via_unknown()).await,
+        Err(RoutingError::HopUnreachable(hop)) if hop == unknown_relay
+    ));
+
+    node.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_outbound_frames_stamped_when_congested() {
+    myriadmesh_core::init().unwrap();
+    let identity = NodeIdentity::generate().unwrap();
+    let node_id = NodeId::from_bytes(*identity.node_id.as_bytes());
+    let peer = NodeId::from_bytes([9u8; NODE_ID_SIZE]);
+
+    let mut dht = RoutingTable::with_pow_difficulty(node_id, 0);
+    dht.add_or_update(NodeInfo::with_adapters(
+        peer,
+        vec![AdapterInfo {
+            adapter_type: AdapterType::Ethernet,
+            address: "peer:4001".to_string(),
+            active: true,
+            reflexive_address: None,
+            nat_type: None,
+        }],
+    ))
+    .unwrap();
+
+    let (transport, _inbound, mut outbound) = memory_transport();
+    let config = NodeConfig {
+        queue_capacity: 4,
+        ..NodeConfig::default()
+    };
+    let node = NodeBuilder::new(config)
+        .with_identity(identity)
+        .with_dht(dht)
+        .with_adapter("memory", Box::new(transport))
+        .build()
+        .await
+        .unwrap();
+
+    // Fill the queue past the Yellow threshold before the processor drains it
+    for _ in 0..3 {
+        let message = test_message(node.node_id(), peer);
+        node.router().route_message(message).await.unwrap();
+    }
+    let mut flags = Vec::new();
+    for _ in 0..3 {
+        let (_, frame) = tokio::time::timeout(Duration::from_secs(2), outbound.recv())
+            .await
+            .expect("frame should be sent")
+            .unwrap();
+        flags.push(
+            frame
+                .header
+                .flags
+                .contains(FrameFlags::CONGESTION_EXPERIENCED),
+        );
+    }
+    assert!(flags[0]);
+    assert!(!flags[2]);
+
+    node.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_mismatched_dht_rejected() {
+    let dht = RoutingTable::new(NodeId::from_bytes([1u8; NODE_ID_SIZE]));
+    let result = NodeBuilder::default().with_dht(dht).build().await;
+    assert!(matches!(result, Err(NodeError::IdentityMismatch(_))));
+}
+
+#[tokio::test]
+async fn test_mismatched_subsystems_rejected() {
+    myriadmesh_core::init().unwrap();
+    let other = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+
+    let mut forged = NodeIdentity::generate().unwrap();
+    forged.node_id = NodeIdentity::generate().unwrap().node_id;
+    let result = NodeBuilder::default().with_identity(forged).build().await;
+    assert!(matches!(
+        result,
+        Err(NodeError::IdentityMismatch("Identity public key"))
+    ));
+
+    let router = Router::new(other, 60, 1000, 100);
+    let result = NodeBuilder::default().with_router(router).build().await;
+    assert!(matches!(result, Err(NodeError::IdentityMismatch("Router"))));
+
+    let onion = OnionRouter::new_default(other, KeyExchangeKeypair::generate());
+    let result = NodeBuilder::default()
+        .with_onion_router(onion)
+        .build()
+        .await;
+    assert!(matches!(
+        result,
+        Err(NodeError::IdentityMismatch("Onion router"))
+    ));
+}
+
+#[tokio::test]
+async fn test_onion_hops_avoid_version_penalized_nodes() {
+    myriadmesh_core::init().unwrap();
+    let identity = NodeIdentity::generate().unwrap();
+    let node_id = NodeId::from_bytes(*identity.node_id.as_bytes());
+    let outdated = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+    let current = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+    let unknown = NodeId::from_bytes([3u8; NODE_ID_SIZE]);
+    let destination = NodeId::from_bytes([4u8; NODE_ID_SIZE]);
+
+    let mut dht = RoutingTable::with_pow_difficulty(node_id, 0);
+    dht.add_or_update(NodeInfo::new(outdated)).unwrap();
+    dht.add_or_update(NodeInfo::new(current)).unwrap();
+    assert!(dht.set_version_penalty(&outdated, 0.9));
+
+    let config = NodeConfig {
+        onion: OnionConfig {
+            num_hops: 1,
+            selection_strategy: RouteSelectionStrategy::HighReliability,
+            ..OnionConfig::default()
+        },
+        ..NodeConfig::default()
+    };
+    let node = NodeBuilder::new(config)
+        .with_identity(identity)
+        .with_dht(dht)
+        .build()
+        .await
+        .unwrap();
+
+    let candidates: Vec<RouteNode> = [outdated, current, unknown, destination]
+        .into_iter()
+        .map(|id| RouteNode {
+            node_id: id,
+            // Caller ratings; DHT reputation replaces them for known nodes
+            reliability: if id == unknown { 0.1 } else { 1.0 },
+            latency_ms: 50.0,
+            available: true,
+            public_key: X25519PublicKey::from(&KeyExchangeKeypair::generate().public_key),
+            kem_public_key: None,
+        })
+        .collect();
+
+    // The top two are picked from; the penalized node is never among them
+    for _ in 0..20 {
+        let route = node
+            .select_onion_route(destination, &candidates)
+            .await
+            .unwrap();
+        assert_ne!(route.hops, vec![outdated]);
+    }
+
+    node.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_prometheus_metrics_cover_adapters() {
+    let (transport, _inbound, _outbound) = memory_transport();
+    let node = NodeBuilder::new(NodeConfig::default())
+        .with_adapter("memory", Box::new(transport))
+        .build()
+        .await
+        .unwrap();
+
+    let text = node.prometheus_metrics().await;
+    assert!(text.contains("# TYPE myriadmesh_adapter_send_latency_seconds histogram"));
+    assert!(text.contains("myriadmesh_adapter_messages_sent_total{adapter=\"memory\""));
+    assert!(text.contains("myriadmesh_router_queue_depth{priority=\"emergency\"} 0"));
+    assert!(text.contains("myriadmesh_onion_active_routes 0"));
+
+    node.shutdown().await.unwrap();
+}
@@ -48,6 +48,9 @@ pub enum CryptoError {
     #[error("Deserialization error: {0}")]
     DeserializationError(String),
 
+    #[error("Invalid keystore: {0}")]
+    InvalidKeystore(String),
+
     #[error("Invalid state: {0}")]
     InvalidState(String),
 
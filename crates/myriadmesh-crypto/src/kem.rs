@@ -0,0 +1,331 @@
+//! Post-quantum key encapsulation using ML-KEM-768 (FIPS 203, Kyber)
+//!
+//! Used together with X25519 for hybrid key exchange (see
+//! [`crate::keyexchange`]): traffic recorded today stays confidential unless
+//! both the classical and the post-quantum exchange are broken.
+//!
+//! SECURITY: Wraps the RustCrypto `ml-kem` implementation rather than
+//! implementing FIPS 203 here. Decapsulation uses implicit rejection, so a
+//! tampered ciphertext yields an unrelated shared secret rather than an
+//! error. Decapsulation keys and shared secrets are zeroized on drop.
+
+use ml_kem::kem::{Decapsulate, DecapsulationKey, Encapsulate, EncapsulationKey};
+use ml_kem::{Encoded, EncodedSizeUser, KemCore, MlKem768, MlKem768Params, B32};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use serde_big_array::BigArray;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::error::{CryptoError, Result};
+
+/// Size of an ML-KEM-768 encapsulation (public) key in bytes
+pub const KEM_PUBLIC_KEY_SIZE: usize = 1184;
+
+/// Size of an ML-KEM-768 decapsulation (secret) key in bytes
+pub const KEM_SECRET_KEY_SIZE: usize = 2400;
+
+/// Size of an ML-KEM-768 ciphertext in bytes
+pub const KEM_CIPHERTEXT_SIZE: usize = 1088;
+
+/// Size of the encapsulated shared secret in bytes
+pub const KEM_SHARED_SECRET_SIZE: usize = 32;
+
+/// Shared secret, wiped when dropped
+pub type KemSharedSecret = Zeroizing<[u8; KEM_SHARED_SECRET_SIZE]>;
+
+type MlKemEncapsulationKey = EncapsulationKey<MlKem768Params>;
+type MlKemDecapsulationKey = DecapsulationKey<MlKem768Params>;
+
+/// Copy an `ml-kem` shared key out, wiping the library's copy
+fn take_shared_secret(mut shared: B32) -> KemSharedSecret {
+    let mut secret = Zeroizing::new([0u8; KEM_SHARED_SECRET_SIZE]);
+    secret.copy_from_slice(&shared);
+    shared.as_mut_slice().zeroize();
+    secret
+}
+
+/// ML-KEM-768 encapsulation key
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KemPublicKey(#[serde(with = "BigArray")] [u8; KEM_PUBLIC_KEY_SIZE]);
+
+impl KemPublicKey {
+    /// Create from bytes
+    ///
+    /// Fails the FIPS 203 modulus check if any coefficient is not reduced.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let encoded = Encoded::<MlKemEncapsulationKey>::try_from(bytes).map_err(|_| {
+            CryptoError::InvalidKeyLength {
+                expected: KEM_PUBLIC_KEY_SIZE,
+                actual: bytes.len(),
+            }
+        })?;
+
+        // Decoding reduces coefficients mod q, so an unreduced key does not
+        // survive the round trip
+        if MlKemEncapsulationKey::from_bytes(&encoded).as_bytes() != encoded {
+            return Err(CryptoError::InvalidKeyFormat);
+        }
+
+        let mut key = [0u8; KEM_PUBLIC_KEY_SIZE];
+        key.copy_from_slice(bytes);
+        Ok(KemPublicKey(key))
+    }
+
+    /// Get bytes
+    pub fn as_bytes(&self) -> &[u8; KEM_PUBLIC_KEY_SIZE] {
+        &self.0
+    }
+
+    fn to_ml_kem(&self) -> MlKemEncapsulationKey {
+        MlKemEncapsulationKey::from_bytes(&Encoded::<MlKemEncapsulationKey>::from(self.0))
+    }
+}
+
+impl std::fmt::Debug for KemPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "KemPublicKey({}...)", hex::encode(&self.0[..8]))
+    }
+}
+
+/// ML-KEM-768 ciphertext carrying an encapsulated shared secret
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KemCiphertext(#[serde(with = "BigArray")] [u8; KEM_CIPHERTEXT_SIZE]);
+
+impl KemCiphertext {
+    /// Create from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let ciphertext = bytes
+            .try_into()
+            .map_err(|_| CryptoError::InvalidKeyLength {
+                expected: KEM_CIPHERTEXT_SIZE,
+                actual: bytes.len(),
+            })?;
+        Ok(KemCiphertext(ciphertext))
+    }
+
+    /// Get bytes
+    pub fn as_bytes(&self) -> &[u8; KEM_CIPHERTEXT_SIZE] {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for KemCiphertext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "KemCiphertext({}...)", hex::encode(&self.0[..8]))
+    }
+}
+
+/// ML-KEM-768 key pair
+///
+/// SECURITY: The decapsulation key is zeroized when the key pair (or any
+/// clone of it) is dropped.
+#[derive(Clone)]
+pub struct KemKeypair {
+    pub public_key: KemPublicKey,
+    secret_key: Box<MlKemDecapsulationKey>,
+}
+
+impl KemKeypair {
+    /// Generate a new random key pair
+    pub fn generate() -> Self {
+        let (dk, _) = MlKem768::generate(&mut OsRng);
+        Self::from_decapsulation_key(dk)
+    }
+
+    /// Derive a key pair from the FIPS 203 seeds `d` and `z`
+    pub fn from_seed(d: &[u8; 32], z: &[u8; 32]) -> Self {
+        let mut d = B32::from(*d);
+        let mut z = B32::from(*z);
+        let (dk, _) = MlKem768::generate_deterministic(&d, &z);
+        d.as_mut_slice().zeroize();
+        z.as_mut_slice().zeroize();
+        Self::from_decapsulation_key(dk)
+    }
+
+    fn from_decapsulation_key(dk: MlKemDecapsulationKey) -> Self {
+        let mut public_key = [0u8; KEM_PUBLIC_KEY_SIZE];
+        public_key.copy_from_slice(&dk.encapsulation_key().as_bytes());
+        KemKeypair {
+            public_key: KemPublicKey(public_key),
+            secret_key: Box::new(dk),
+        }
+    }
+
+    /// Recover the shared secret from a ciphertext
+    ///
+    /// Never fails: a ciphertext that was not produced for this key decrypts
+    /// to a pseudorandom secret (implicit rejection), so a tampered layer is
+    /// detected by the AEAD keyed from it.
+    pub fn decapsulate(&self, ciphertext: &KemCiphertext) -> KemSharedSecret {
+        let shared = self
+            .secret_key
+            .decapsulate(&ciphertext.0.into())
+            .expect("ML-KEM decapsulation is infallible");
+        take_shared_secret(shared)
+    }
+}
+
+impl std::fmt::Debug for KemKeypair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KemKeypair")
+            .field("public_key", &self.public_key)
+            .finish()
+    }
+}
+
+/// Encapsulate a fresh shared secret to `public_key`
+pub fn encapsulate(public_key: &KemPublicKey) -> (KemCiphertext, KemSharedSecret) {
+    let (ciphertext, shared) = public_key
+        .to_ml_kem()
+        .encapsulate(&mut OsRng)
+        .expect("ML-KEM encapsulation is infallible");
+    (KemCiphertext(ciphertext.into()), take_shared_secret(shared))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ml_kem::EncapsulateDeterministic;
+    use serde_json::Value;
+
+    /// NIST ACVP vectors for ML-KEM-768 (see the file's `source` field)
+    const ACVP_VECTORS: &str = include_str!("../tests/data/ml-kem-768-acvp.json");
+
+    fn acvp() -> Value {
+        serde_json::from_str(ACVP_VECTORS).unwrap()
+    }
+
+    fn hex_field<const L: usize>(test: &Value, field: &str) -> [u8; L] {
+        hex::decode(test[field].as_str().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(
+            Encoded::<MlKemEncapsulationKey>::default().len(),
+            KEM_PUBLIC_KEY_SIZE
+        );
+        assert_eq!(
+            Encoded::<MlKemDecapsulationKey>::default().len(),
+            KEM_SECRET_KEY_SIZE
+        );
+        assert_eq!(
+            ml_kem::Ciphertext::<MlKem768>::default().len(),
+            KEM_CIPHERTEXT_SIZE
+        );
+    }
+
+    #[test]
+    fn test_encapsulate_decapsulate() {
+        let keypair = KemKeypair::generate();
+        let (ciphertext, shared) = encapsulate(&keypair.public_key);
+        assert_eq!(keypair.decapsulate(&ciphertext), shared);
+
+        // Fresh randomness per encapsulation
+        let (ciphertext2, shared2) = encapsulate(&keypair.public_key);
+        assert_ne!(ciphertext, ciphertext2);
+        assert_ne!(shared, shared2);
+    }
+
+    #[test]
+    fn test_acvp_key_generation() {
+        for test in acvp()["keyGen"].as_array().unwrap() {
+            let keypair = KemKeypair::from_seed(&hex_field(test, "d"), &hex_field(test, "z"));
+            let ek: [u8; KEM_PUBLIC_KEY_SIZE] = hex_field(test, "ek");
+            let dk: [u8; KEM_SECRET_KEY_SIZE] = hex_field(test, "dk");
+
+            assert_eq!(keypair.public_key.as_bytes(), &ek, "tcId {}", test["tcId"]);
+            assert_eq!(
+                keypair.secret_key.as_bytes().as_slice(),
+                &dk[..],
+                "tcId {}",
+                test["tcId"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_acvp_encapsulation() {
+        for test in acvp()["encapsulation"].as_array().unwrap() {
+            let public_key =
+                KemPublicKey::from_bytes(&hex_field::<KEM_PUBLIC_KEY_SIZE>(test, "ek")).unwrap();
+            let m = B32::from(hex_field::<32>(test, "m"));
+            let (ciphertext, shared) = public_key
+                .to_ml_kem()
+                .encapsulate_deterministic(&m)
+                .unwrap();
+
+            let c: [u8; KEM_CIPHERTEXT_SIZE] = hex_field(test, "c");
+            let k: [u8; KEM_SHARED_SECRET_SIZE] = hex_field(test, "k");
+            assert_eq!(ciphertext.as_slice(), &c[..], "tcId {}", test["tcId"]);
+            assert_eq!(shared.as_slice(), &k[..], "tcId {}", test["tcId"]);
+        }
+    }
+
+    #[test]
+    fn test_acvp_decapsulation() {
+        // Covers both valid and modified ciphertexts (implicit rejection)
+        let vectors = acvp();
+        let group = &vectors["decapsulation"];
+        let dk: [u8; KEM_SECRET_KEY_SIZE] = hex_field(group, "dk");
+        let keypair =
+            KemKeypair::from_decapsulation_key(MlKemDecapsulationKey::from_bytes(&Encoded::<
+                MlKemDecapsulationKey,
+            >::from(
+                dk
+            )));
+
+        for test in group["tests"].as_array().unwrap() {
+            let ciphertext = KemCiphertext(hex_field(test, "c"));
+            let k: [u8; KEM_SHARED_SECRET_SIZE] = hex_field(test, "k");
+            assert_eq!(
+                *keypair.decapsulate(&ciphertext),
+                k,
+                "tcId {} ({})",
+                test["tcId"],
+                test["reason"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_implicit_rejection() {
+        let keypair = KemKeypair::generate();
+        let (ciphertext, shared) = encapsulate(&keypair.public_key);
+
+        let mut tampered = ciphertext.clone();
+        tampered.0[0] ^= 1;
+        let rejected = keypair.decapsulate(&tampered);
+        assert_ne!(rejected, shared);
+        // Rejection is deterministic for a given ciphertext
+        assert_eq!(keypair.decapsulate(&tampered), rejected);
+
+        // Another key pair can't recover the secret
+        let other = KemKeypair::generate();
+        assert_ne!(other.decapsulate(&ciphertext), shared);
+    }
+
+    #[test]
+    fn test_public_key_validation() {
+        let keypair = KemKeypair::from_seed(&[7u8; 32], &[8u8; 32]);
+        let parsed = KemPublicKey::from_bytes(keypair.public_key.as_bytes()).unwrap();
+        assert_eq!(parsed, keypair.public_key);
+
+        assert!(matches!(
+            KemPublicKey::from_bytes(&[0u8; 32]),
+            Err(CryptoError::InvalidKeyLength { .. })
+        ));
+
+        // A coefficient of 0xfff is not reduced mod q
+        let mut bytes = *keypair.public_key.as_bytes();
+        bytes[0] = 0xff;
+        bytes[1] |= 0x0f;
+        assert_eq!(
+            KemPublicKey::from_bytes(&bytes),
+            Err(CryptoError::InvalidKeyFormat)
+        );
+    }
+}
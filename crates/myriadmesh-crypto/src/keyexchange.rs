@@ -1,12 +1,19 @@
 //! Key exchange using X25519 ECDH and HKDF for key derivation
 //!
 //! This module provides functionality for establishing shared secrets between nodes.
+//!
+//! The hybrid mode additionally encapsulates an ML-KEM-768 secret to the
+//! responder and mixes both shared secrets through BLAKE2b, so session keys
+//! stay secret unless X25519 and ML-KEM are both broken. This protects
+//! recorded traffic against future quantum adversaries.
 
+use blake2::{Blake2b512, Digest};
 use serde::{Deserialize, Serialize};
 use sodiumoxide::crypto::kx;
 
 use crate::encryption::SymmetricKey;
 use crate::error::{CryptoError, Result};
+use crate::kem::{self, KemCiphertext, KemKeypair, KemPublicKey};
 
 /// Size of X25519 public key in bytes
 pub const X25519_PUBLIC_KEY_SIZE: usize = 32;
@@ -128,6 +135,88 @@ pub struct SessionKeys {
     pub rx_key: SymmetricKey,
 }
 
+/// Which key exchange establishes session keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum KeyExchangeMode {
+    /// X25519 only
+    #[default]
+    Classical,
+    /// X25519 combined with ML-KEM-768
+    Hybrid,
+}
+
+/// Domain separation for the hybrid key derivation
+const HYBRID_KDF_LABEL: &[u8] = b"myriadmesh-hybrid-kx-v1";
+
+/// Mix classical session keys with a KEM shared secret
+///
+/// Keys are given from the client's point of view. The ciphertext is bound
+/// into the derivation so a substituted ciphertext yields unrelated keys.
+/// Returns the client's (rx, tx) keys.
+fn derive_hybrid_keys(
+    client_rx: &[u8],
+    client_tx: &[u8],
+    kem_secret: &[u8],
+    ciphertext: &KemCiphertext,
+) -> Result<(SymmetricKey, SymmetricKey)> {
+    let mut hasher = Blake2b512::new();
+    hasher.update(HYBRID_KDF_LABEL);
+    hasher.update(client_rx);
+    hasher.update(client_tx);
+    hasher.update(kem_secret);
+    hasher.update(ciphertext.as_bytes());
+    let okm = hasher.finalize();
+
+    Ok((
+        SymmetricKey::from_bytes(&okm[..32])?,
+        SymmetricKey::from_bytes(&okm[32..])?,
+    ))
+}
+
+/// Perform hybrid key exchange as the client (initiator)
+///
+/// Returns the session keys and the KEM ciphertext, which must be sent to
+/// the server along with the client's X25519 public key.
+pub fn hybrid_client_session_keys(
+    client_keypair: &KeyExchangeKeypair,
+    server_public_key: &X25519PublicKey,
+    server_kem_key: &KemPublicKey,
+) -> Result<(SessionKeys, KemCiphertext)> {
+    let classical = client_session_keys(client_keypair, server_public_key)?;
+    let (ciphertext, kem_secret) = kem::encapsulate(server_kem_key);
+
+    let (rx_key, tx_key) = derive_hybrid_keys(
+        classical.rx_key.as_bytes(),
+        classical.tx_key.as_bytes(),
+        &kem_secret[..],
+        &ciphertext,
+    )?;
+    Ok((SessionKeys { tx_key, rx_key }, ciphertext))
+}
+
+/// Perform hybrid key exchange as the server (responder)
+pub fn hybrid_server_session_keys(
+    server_keypair: &KeyExchangeKeypair,
+    server_kem_keypair: &KemKeypair,
+    client_public_key: &X25519PublicKey,
+    ciphertext: &KemCiphertext,
+) -> Result<SessionKeys> {
+    let classical = server_session_keys(server_keypair, client_public_key)?;
+    let kem_secret = server_kem_keypair.decapsulate(ciphertext);
+
+    // The server's tx key is the client's rx key and vice versa
+    let (client_rx, client_tx) = derive_hybrid_keys(
+        classical.tx_key.as_bytes(),
+        classical.rx_key.as_bytes(),
+        &kem_secret[..],
+        ciphertext,
+    )?;
+    Ok(SessionKeys {
+        tx_key: client_rx,
+        rx_key: client_tx,
+    })
+}
+
 /// Perform key exchange as the client (initiator)
 pub fn client_session_keys(
     client_keypair: &KeyExchangeKeypair,
@@ -232,4 +321,56 @@ mod tests {
         let parsed = X25519PublicKey::from_hex(&hex).unwrap();
         assert_eq!(pub_key, parsed);
     }
+
+    #[test]
+    fn test_hybrid_key_exchange() {
+        crate::init().unwrap();
+
+        let client_kp = KeyExchangeKeypair::generate();
+        let server_kp = KeyExchangeKeypair::generate();
+        let server_kem = KemKeypair::generate();
+
+        let client_pub = X25519PublicKey::from(&client_kp.public_key);
+        let server_pub = X25519PublicKey::from(&server_kp.public_key);
+
+        let (client_keys, ciphertext) =
+            hybrid_client_session_keys(&client_kp, &server_pub, &server_kem.public_key).unwrap();
+        let server_keys =
+            hybrid_server_session_keys(&server_kp, &server_kem, &client_pub, &ciphertext).unwrap();
+
+        let encrypted = encrypt(&client_keys.tx_key, b"Hello from client").unwrap();
+        let decrypted = decrypt(&server_keys.rx_key, &encrypted).unwrap();
+        assert_eq!(decrypted, b"Hello from client");
+
+        let encrypted = encrypt(&server_keys.tx_key, b"Hello from server").unwrap();
+        let decrypted = decrypt(&client_keys.rx_key, &encrypted).unwrap();
+        assert_eq!(decrypted, b"Hello from server");
+
+        // Hybrid keys differ from the classical keys for the same X25519 pair
+        let classical = client_session_keys(&client_kp, &server_pub).unwrap();
+        assert_ne!(classical.tx_key.as_bytes(), client_keys.tx_key.as_bytes());
+    }
+
+    #[test]
+    fn test_hybrid_key_exchange_needs_kem_secret() {
+        crate::init().unwrap();
+
+        let client_kp = KeyExchangeKeypair::generate();
+        let server_kp = KeyExchangeKeypair::generate();
+        let server_kem = KemKeypair::generate();
+
+        let client_pub = X25519PublicKey::from(&client_kp.public_key);
+        let server_pub = X25519PublicKey::from(&server_kp.public_key);
+
+        let (client_keys, ciphertext) =
+            hybrid_client_session_keys(&client_kp, &server_pub, &server_kem.public_key).unwrap();
+
+        // Knowing the X25519 secret alone is not enough
+        let wrong_kem = KemKeypair::generate();
+        let server_keys =
+            hybrid_server_session_keys(&server_kp, &wrong_kem, &client_pub, &ciphertext).unwrap();
+
+        let encrypted = encrypt(&client_keys.tx_key, b"secret").unwrap();
+        assert!(decrypt(&server_keys.rx_key, &encrypted).is_err());
+    }
 }
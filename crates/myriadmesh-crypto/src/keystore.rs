@@ -0,0 +1,164 @@
+//! Passphrase-encrypted storage for node identities
+//!
+//! The keystore holds a node's Ed25519 key pair encrypted with a key derived
+//! from a passphrase (Argon2id), so the identity can be kept on disk and the
+//! NodeId stays stable across restarts and reinstalls.
+//!
+//! Layout: `MAGIC | version | salt | nonce | ciphertext`
+
+use sodiumoxide::crypto::pwhash::argon2id13;
+
+use crate::encryption::{self, EncryptedMessage, Nonce, SymmetricKey, KEY_SIZE, NONCE_SIZE};
+use crate::error::{CryptoError, Result};
+use crate::identity::NodeIdentity;
+
+/// Magic bytes identifying a keystore file
+const MAGIC: &[u8; 4] = b"MMKS";
+
+/// Current keystore format version
+const VERSION: u8 = 1;
+
+/// Size of the Argon2id salt
+const SALT_SIZE: usize = argon2id13::SALTBYTES;
+
+/// Size of the header preceding the ciphertext
+const HEADER_SIZE: usize = MAGIC.len() + 1 + SALT_SIZE + NONCE_SIZE;
+
+/// Size of the encrypted key pair (public key, secret key)
+const KEYPAIR_SIZE: usize = 32 + 64;
+
+/// Encrypt a node identity with a passphrase
+pub fn seal(identity: &NodeIdentity, passphrase: &str) -> Result<Vec<u8>> {
+    let salt = argon2id13::gen_salt();
+    let key = derive_key(passphrase, &salt)?;
+
+    let mut plaintext = Vec::with_capacity(KEYPAIR_SIZE);
+    plaintext.extend_from_slice(identity.export_public_key());
+    plaintext.extend_from_slice(identity.export_secret_key());
+    let encrypted = encryption::encrypt(&key, &plaintext)?;
+
+    let mut out = Vec::with_capacity(HEADER_SIZE + encrypted.ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&salt.0);
+    out.extend_from_slice(encrypted.nonce.as_bytes());
+    out.extend_from_slice(&encrypted.ciphertext);
+    Ok(out)
+}
+
+/// Decrypt a node identity sealed with [`seal`]
+///
+/// Returns `InvalidKeystore` if the data is not a keystore or is truncated,
+/// and `DecryptionFailed` if the passphrase is wrong or the data was modified.
+pub fn open(data: &[u8], passphrase: &str) -> Result<NodeIdentity> {
+    if data.len() < HEADER_SIZE || &data[..MAGIC.len()] != MAGIC {
+        return Err(CryptoError::InvalidKeystore(
+            "not a MyriadMesh keystore".to_string(),
+        ));
+    }
+
+    let version = data[MAGIC.len()];
+    if version != VERSION {
+        return Err(CryptoError::InvalidKeystore(format!(
+            "unsupported version {}",
+            version
+        )));
+    }
+
+    let salt_start = MAGIC.len() + 1;
+    let nonce_start = salt_start + SALT_SIZE;
+    let salt = argon2id13::Salt::from_slice(&data[salt_start..nonce_start])
+        .ok_or(CryptoError::InvalidKeyFormat)?;
+    let mut nonce = [0u8; NONCE_SIZE];
+    nonce.copy_from_slice(&data[nonce_start..HEADER_SIZE]);
+
+    let key = derive_key(passphrase, &salt)?;
+    let encrypted = EncryptedMessage {
+        nonce: Nonce::from_bytes(nonce),
+        ciphertext: data[HEADER_SIZE..].to_vec(),
+    };
+    let plaintext = encryption::decrypt(&key, &encrypted)?;
+
+    if plaintext.len() != KEYPAIR_SIZE {
+        return Err(CryptoError::InvalidKeystore(format!(
+            "expected {} bytes of key material, got {}",
+            KEYPAIR_SIZE,
+            plaintext.len()
+        )));
+    }
+    NodeIdentity::from_bytes(&plaintext[..32], &plaintext[32..])
+}
+
+/// Derive the keystore encryption key from a passphrase
+fn derive_key(passphrase: &str, salt: &argon2id13::Salt) -> Result<SymmetricKey> {
+    let mut key = [0u8; KEY_SIZE];
+    argon2id13::derive_key(
+        &mut key,
+        passphrase.as_bytes(),
+        salt,
+        argon2id13::OPSLIMIT_INTERACTIVE,
+        argon2id13::MEMLIMIT_INTERACTIVE,
+    )
+    .map_err(|_| CryptoError::KeyDerivationFailed)?;
+    SymmetricKey::from_bytes(&key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        crate::init().unwrap();
+        let identity = NodeIdentity::generate().unwrap();
+
+        let sealed = seal(&identity, "correct horse").unwrap();
+        assert_eq!(&sealed[..4], MAGIC);
+
+        let restored = open(&sealed, "correct horse").unwrap();
+        assert_eq!(restored.node_id, identity.node_id);
+        assert_eq!(restored.export_secret_key(), identity.export_secret_key());
+    }
+
+    #[test]
+    fn test_wrong_passphrase_rejected() {
+        crate::init().unwrap();
+        let identity = NodeIdentity::generate().unwrap();
+        let sealed = seal(&identity, "correct horse").unwrap();
+
+        assert_eq!(
+            open(&sealed, "battery staple").unwrap_err(),
+            CryptoError::DecryptionFailed
+        );
+    }
+
+    #[test]
+    fn test_corrupt_keystore_rejected() {
+        crate::init().unwrap();
+        let identity = NodeIdentity::generate().unwrap();
+        let mut sealed = seal(&identity, "pw").unwrap();
+
+        assert!(matches!(
+            open(b"garbage", "pw"),
+            Err(CryptoError::InvalidKeystore(_))
+        ));
+        assert!(matches!(
+            open(&sealed[..HEADER_SIZE - 1], "pw"),
+            Err(CryptoError::InvalidKeystore(_))
+        ));
+
+        // Flipped ciphertext bit fails authentication
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x01;
+        assert_eq!(
+            open(&sealed, "pw").unwrap_err(),
+            CryptoError::DecryptionFailed
+        );
+
+        sealed[MAGIC.len()] = 9;
+        assert_eq!(
+            open(&sealed, "pw").unwrap_err(),
+            CryptoError::InvalidKeystore("unsupported version 9".to_string())
+        );
+    }
+}
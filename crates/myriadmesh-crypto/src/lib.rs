@@ -2,17 +2,20 @@
 //!
 //! This module provides the core cryptographic primitives for the MyriadMesh protocol:
 //! - Node identity generation (Ed25519 key pairs)
-//! - Key exchange (X25519 ECDH)
+//! - Key exchange (X25519 ECDH, optionally hybrid with ML-KEM-768)
 //! - Message encryption (XSalsa20-Poly1305 AEAD)
 //! - Message signing (Ed25519 signatures)
 //! - Key derivation (HKDF)
 //! - Encrypted channels for end-to-end encryption
+//! - Passphrase-encrypted identity keystore
 
 pub mod channel;
 pub mod encryption;
 pub mod error;
 pub mod identity;
+pub mod kem;
 pub mod keyexchange;
+pub mod keystore;
 pub mod signing;
 
 pub use error::{CryptoError, Result};
@@ -32,6 +32,15 @@ pub enum DhtError {
         max_bytes: usize,
     },
 
+    #[error("Inbox full: {messages} messages, {bytes} bytes")]
+    InboxFull { messages: usize, bytes: usize },
+
+    #[error("Sender inbox quota exceeded: {messages} messages, {bytes} bytes held")]
+    InboxSenderQuotaExceeded { messages: usize, bytes: usize },
+
+    #[error("No outstanding inbox challenge")]
+    InboxChallengeMissing,
+
     #[error("Invalid signature")]
     InvalidSignature,
 
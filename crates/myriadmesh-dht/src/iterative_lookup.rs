@@ -275,6 +275,45 @@ impl IterativeLookup {
         false
     }
 
+    /// Get the next batch of unqueried nodes among the k closest live candidates
+    ///
+    /// Used for the final sweep once a round finds nothing closer: every
+    /// remaining node in the shortlist is queried, not just alpha of them.
+    pub fn next_final_batch(&mut self) -> Vec<PublicNodeInfo> {
+        let mut live: Vec<_> = self
+            .candidates
+            .values()
+            .filter(|c| c.state != NodeState::Failed)
+            .collect();
+        live.sort_by(|a, b| a.distance.cmp(&b.distance));
+
+        let batch: Vec<PublicNodeInfo> = live
+            .iter()
+            .take(self.k)
+            .filter(|c| c.state == NodeState::Pending)
+            .map(|c| c.node.clone())
+            .collect();
+
+        let now = Instant::now();
+        for node in &batch {
+            if let Some(candidate) = self.candidates.get_mut(&node.node_id) {
+                candidate.state = NodeState::Queried;
+                candidate.queried_at = Some(now);
+            }
+        }
+
+        batch
+    }
+
+    /// Distance of the closest node that has responded so far
+    pub fn closest_responded_distance(&self) -> Option<Vec<u8>> {
+        self.candidates
+            .values()
+            .filter(|c| c.state == NodeState::Responded)
+            .map(|c| c.distance.clone())
+            .min()
+    }
+
     /// Advance to the next round
     pub fn next_round(&mut self) {
         self.current_round += 1;
@@ -363,6 +402,8 @@ mod tests {
             reputation: Default::default(),
             last_seen: 0,
             rtt_ms: 0.0,
+            pow_nonce: 0,
+            nat_type: None,
         }
     }
 
@@ -5,6 +5,7 @@ use crate::node_info::NodeInfo;
 use crate::K;
 use myriadmesh_protocol::NodeId;
 use std::collections::{HashMap, VecDeque};
+use std::future::Future;
 use std::net::IpAddr;
 
 /// SECURITY H2: Maximum nodes from same /24 subnet (Eclipse attack prevention)
@@ -58,6 +59,20 @@ fn get_node_id_prefix(node_id: &NodeId) -> [u8; 2] {
     [bytes[0], bytes[1]]
 }
 
+/// Outcome of `KBucket::try_insert`
+#[derive(Debug, Clone)]
+pub enum InsertOutcome {
+    /// Node was added to a bucket with free space
+    Added,
+    /// Node was already present and moved to most-recently-seen
+    Updated,
+    /// Bucket full with a live LRU node (or diversity rejected); node stashed
+    /// in the replacement cache
+    Cached,
+    /// The unresponsive LRU node was evicted and the newcomer admitted
+    Replaced { evicted: Box<NodeInfo> },
+}
+
 /// A k-bucket for storing nodes at a specific distance
 #[derive(Debug, Clone)]
 pub struct KBucket {
@@ -72,6 +87,9 @@ pub struct KBucket {
 
     /// Last time this bucket was updated
     pub last_updated: u64,
+
+    /// Last time a refresh lookup was run for this bucket's range
+    pub last_refreshed: u64,
 }
 
 impl KBucket {
@@ -82,6 +100,7 @@ impl KBucket {
             nodes: VecDeque::with_capacity(K),
             replacement_cache: VecDeque::with_capacity(K),
             last_updated: 0,
+            last_refreshed: 0,
         }
     }
 
@@ -172,6 +191,72 @@ impl KBucket {
         Ok(false)
     }
 
+    /// Insert a node, probing the least-recently-seen node if the bucket is full
+    ///
+    /// When the bucket is full, `ping_fn` is called on the LRU node. If it
+    /// responds it is kept (and moved to most-recently-seen) and the newcomer
+    /// goes to the bounded replacement cache; if it does not, it is evicted
+    /// and the newcomer takes its place.
+    ///
+    /// SECURITY H2: Diversity constraints are checked before any probe.
+    pub async fn try_insert<F, Fut>(
+        &mut self,
+        node: NodeInfo,
+        current_time: u64,
+        ping_fn: F,
+    ) -> Result<InsertOutcome>
+    where
+        F: FnOnce(NodeInfo) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        if let Some(pos) = self.nodes.iter().position(|n| n.node_id == node.node_id) {
+            self.nodes.remove(pos);
+            self.nodes.push_back(node);
+            self.last_updated = current_time;
+            return Ok(InsertOutcome::Updated);
+        }
+
+        // SECURITY H2: Check diversity constraints
+        if !self.check_diversity(&node) {
+            self.add_to_replacement_cache(node);
+            return Ok(InsertOutcome::Cached);
+        }
+
+        if !self.is_full() {
+            self.nodes.push_back(node);
+            self.last_updated = current_time;
+            return Ok(InsertOutcome::Added);
+        }
+
+        let lru = match self.nodes.front() {
+            Some(lru) => lru.clone(),
+            None => return Ok(InsertOutcome::Cached),
+        };
+
+        if ping_fn(lru.clone()).await {
+            // LRU is alive: it becomes most recently seen, newcomer waits
+            if let Some(pos) = self.nodes.iter().position(|n| n.node_id == lru.node_id) {
+                if let Some(mut alive) = self.nodes.remove(pos) {
+                    alive.last_seen = current_time;
+                    alive.failures = 0;
+                    self.nodes.push_back(alive);
+                }
+            }
+            self.add_to_replacement_cache(node);
+            Ok(InsertOutcome::Cached)
+        } else {
+            let evicted = match self.nodes.iter().position(|n| n.node_id == lru.node_id) {
+                Some(pos) => self.nodes.remove(pos).unwrap_or(lru),
+                None => lru,
+            };
+            self.nodes.push_back(node);
+            self.last_updated = current_time;
+            Ok(InsertOutcome::Replaced {
+                evicted: Box::new(evicted),
+            })
+        }
+    }
+
     /// Add node to replacement cache
     fn add_to_replacement_cache(&mut self, node: NodeInfo) {
         let node_id = node.node_id;
@@ -437,6 +522,8 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: format!("192.168.1.{}:8080", i),
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             }];
             let added = bucket.add_or_update(node, 0).unwrap();
             assert!(added, "First 2 nodes from subnet should be accepted");
@@ -448,6 +535,8 @@ mod tests {
             adapter_type: AdapterType::Ethernet,
             address: "192.168.1.100:8080".to_string(),
             active: true,
+            reflexive_address: None,
+            nat_type: None,
         }];
         let added = bucket.add_or_update(node3, 0).unwrap();
         assert!(!added, "3rd node from same subnet should be rejected");
@@ -501,6 +590,8 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: format!("192.168.{}.1:8080", i), // Different subnet for each
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             }];
             let added = bucket.add_or_update(node, 0).unwrap();
             assert!(added, "Nodes from different subnets should be accepted");
@@ -543,6 +634,8 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: format!("10.0.0.{}:8080", i), // All from same subnet
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             }];
 
             if bucket.add_or_update(node, 0).unwrap() {
@@ -560,4 +653,95 @@ mod tests {
         assert_eq!(rejected, 18, "Remaining 18 should be rejected");
         assert_eq!(bucket.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_try_insert_full_bucket_live_lru_caches_newcomer() {
+        let mut bucket = KBucket::new(0);
+        for i in 0..K {
+            let outcome = bucket
+                .try_insert(create_test_node(i as u8), 0, |_| async { true })
+                .await
+                .unwrap();
+            assert!(matches!(outcome, InsertOutcome::Added));
+        }
+        let lru_id = bucket.nodes().front().unwrap().node_id;
+
+        let mut pinged = None;
+        let outcome = bucket
+            .try_insert(create_test_node(99), 10, |lru| {
+                pinged = Some(lru.node_id);
+                async { true }
+            })
+            .await
+            .unwrap();
+
+        assert!(matches!(outcome, InsertOutcome::Cached));
+        assert_eq!(pinged, Some(lru_id));
+        assert_eq!(bucket.len(), K);
+        assert!(bucket.find_node(&create_test_node(99).node_id).is_none());
+        assert_eq!(bucket.replacement_cache().len(), 1);
+
+        // The live LRU node is now the most recently seen
+        assert_eq!(bucket.nodes().back().unwrap().node_id, lru_id);
+
+        // A later eviction promotes the cached newcomer
+        let victim = bucket.nodes().front().unwrap().node_id;
+        bucket.remove(&victim);
+        assert!(bucket.find_node(&create_test_node(99).node_id).is_some());
+        assert!(bucket.replacement_cache().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_try_insert_full_bucket_dead_lru_admits_newcomer() {
+        let mut bucket = KBucket::new(0);
+        for i in 0..K {
+            bucket
+                .try_insert(create_test_node(i as u8), 0, |_| async { true })
+                .await
+                .unwrap();
+        }
+        let lru_id = bucket.nodes().front().unwrap().node_id;
+
+        let outcome = bucket
+            .try_insert(create_test_node(99), 10, |_| async { false })
+            .await
+            .unwrap();
+
+        match outcome {
+            InsertOutcome::Replaced { evicted } => assert_eq!(evicted.node_id, lru_id),
+            other => panic!("expected replacement, got {:?}", other),
+        }
+        assert_eq!(bucket.len(), K);
+        assert!(bucket.find_node(&lru_id).is_none());
+        assert_eq!(
+            bucket.nodes().back().unwrap().node_id,
+            create_test_node(99).node_id
+        );
+        assert!(bucket.replacement_cache().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_try_insert_existing_node_skips_ping() {
+        let mut bucket = KBucket::new(0);
+        bucket
+            .try_insert(create_test_node(1), 0, |_| async { true })
+            .await
+            .unwrap();
+        bucket
+            .try_insert(create_test_node(2), 0, |_| async { true })
+            .await
+            .unwrap();
+
+        let outcome = bucket
+            .try_insert(create_test_node(1), 5, |_| async {
+                panic!("should not ping")
+            })
+            .await
+            .unwrap();
+        assert!(matches!(outcome, InsertOutcome::Updated));
+        assert_eq!(
+            bucket.nodes().back().unwrap().node_id,
+            create_test_node(1).node_id
+        );
+    }
 }
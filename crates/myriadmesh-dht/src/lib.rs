@@ -17,12 +17,19 @@ pub mod storage;
 
 pub use error::{DhtError, Result};
 pub use iterative_lookup::{IterativeLookup, LookupResult, LookupStats};
-pub use kbucket::KBucket;
-pub use node_info::{AdapterInfo, NodeCapabilities, NodeInfo, PublicNodeInfo};
+pub use kbucket::{InsertOutcome, KBucket};
+pub use node_info::{
+    generate_pow_nonce, AdapterInfo, NatType, NodeCapabilities, NodeInfo, PublicNodeInfo,
+};
 pub use operations::{FindNodeRequest, FindNodeResponse, FindValueRequest, FindValueResponse};
-pub use reputation::{NodeReputation, ReputationManager};
-pub use routing_table::RoutingTable;
-pub use storage::{DhtStorage, StorageEntry};
+pub use reputation::{NodeReputation, ReputationManager, ReputationSnapshot, MAX_VERSION_PENALTY};
+pub use routing_table::{key_target, FoundValue, RoutingTable, MAX_RANGE_QUERY_PEERS};
+pub use storage::{
+    inbox_challenge_message, key_distance, DhtStorage, InboxMessage, StorageEntry,
+    INBOX_CHALLENGE_TTL_SECS, MAX_CACHED_VALUE_TTL_SECS, MAX_INBOX_BYTES,
+    MAX_INBOX_BYTES_PER_SENDER, MAX_INBOX_MESSAGES, MAX_INBOX_MESSAGES_PER_SENDER,
+    MAX_INBOX_TTL_SECS, MAX_RANGE_QUERY_RESULTS,
+};
 
 /// Kademlia k parameter (nodes per k-bucket)
 pub const K: usize = 20;
@@ -5,7 +5,9 @@
 use blake2::{Blake2b512, Digest};
 use myriadmesh_protocol::types::{AdapterType, NODE_ID_SIZE};
 use myriadmesh_protocol::NodeId as ProtocolNodeId;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::reputation::NodeReputation;
@@ -37,6 +39,55 @@ fn count_leading_zero_bits(data: &[u8]) -> u32 {
     count
 }
 
+/// SECURITY C2: Find a PoW nonce for `node_id` at the given difficulty
+///
+/// Expected cost is 2^difficulty hash attempts.
+pub fn generate_pow_nonce(node_id: &ProtocolNodeId, difficulty: u32) -> u64 {
+    let mut nonce = 0u64;
+    while !NodeInfo::verify_pow_internal(node_id, nonce, difficulty) {
+        nonce += 1;
+    }
+    nonce
+}
+
+/// NAT behaviour observed in front of an adapter
+///
+/// Ordered from most to least reachable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum NatType {
+    /// No NAT: the advertised address is public
+    Open,
+    /// One mapping per internal port, reachable by anyone once created
+    FullCone,
+    /// Mapping only accepts hosts the node has sent to
+    RestrictedCone,
+    /// Mapping only accepts host and port pairs the node has sent to
+    PortRestrictedCone,
+    /// New mapping per destination; hole-punching rarely works
+    Symmetric,
+}
+
+impl NatType {
+    /// Whether peers can reach the node without a hole-punch
+    pub fn accepts_unsolicited(&self) -> bool {
+        matches!(self, NatType::Open | NatType::FullCone)
+    }
+
+    /// Whether a coordinated hole-punch between the two NATs can succeed
+    ///
+    /// A symmetric NAT's mapping port can't be predicted, so only a peer
+    /// that accepts any source port can meet it.
+    pub fn can_hole_punch_with(&self, other: &NatType) -> bool {
+        use NatType::*;
+        !matches!(
+            (self, other),
+            (Symmetric, Symmetric)
+                | (Symmetric, PortRestrictedCone)
+                | (PortRestrictedCone, Symmetric)
+        )
+    }
+}
+
 /// Information about a network adapter
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdapterInfo {
@@ -48,6 +99,14 @@ pub struct AdapterInfo {
 
     /// Whether this adapter is currently active
     pub active: bool,
+
+    /// Public address peers observed this adapter at, when behind NAT
+    #[serde(default)]
+    pub reflexive_address: Option<String>,
+
+    /// NAT behaviour in front of this adapter, if detected
+    #[serde(default)]
+    pub nat_type: Option<NatType>,
 }
 
 /// Node capabilities (safe for public sharing in DHT)
@@ -124,6 +183,20 @@ pub struct NodeInfo {
 
     /// Total successful communications
     pub total_successes: u64,
+
+    /// NAT type the node advertised in its public record
+    ///
+    /// Self-reported; see [`NodeInfo::reachability_probe`].
+    #[serde(default)]
+    pub advertised_nat: Option<NatType>,
+
+    /// Outcome of the last unsolicited reachability probe, if any
+    ///
+    /// `Some(true)` means a contact the node never solicited was answered,
+    /// confirming it accepts unsolicited traffic. Local observation, never
+    /// taken from the node's own announcement.
+    #[serde(default)]
+    pub reachability_probe: Option<bool>,
 }
 
 impl NodeInfo {
@@ -141,9 +214,24 @@ impl NodeInfo {
             capabilities: NodeCapabilities::default(),
             first_seen: now,
             total_successes: 0,
+            advertised_nat: None,
+            reachability_probe: None,
         }
     }
 
+    /// Create node info from a record learned through the DHT
+    ///
+    /// Keeps the advertised NAT type as an unverified hint; the record's
+    /// reputation is the sender's opinion and is not adopted.
+    pub fn from_public(public: &PublicNodeInfo) -> Self {
+        let mut info = Self::new(public.node_id);
+        info.pow_nonce = public.pow_nonce;
+        info.capabilities = public.capabilities.clone();
+        info.rtt_ms = public.rtt_ms;
+        info.advertised_nat = public.nat_type;
+        info
+    }
+
     /// Create with adapters
     pub fn with_adapters(node_id: ProtocolNodeId, adapters: Vec<AdapterInfo>) -> Self {
         let mut info = Self::new(node_id);
@@ -156,21 +244,25 @@ impl NodeInfo {
     /// Finds a nonce such that hash(node_id || nonce) has required leading zero bits.
     /// This is computationally expensive (~65k attempts average for 16-bit difficulty).
     pub fn compute_pow(&mut self) -> u64 {
-        let mut nonce = 0u64;
-        loop {
-            if Self::verify_pow_internal(&self.node_id, nonce, REQUIRED_POW_DIFFICULTY) {
-                self.pow_nonce = nonce;
-                return nonce;
-            }
-            nonce += 1;
-        }
+        self.compute_pow_with_difficulty(REQUIRED_POW_DIFFICULTY)
+    }
+
+    /// SECURITY C2: Compute Proof-of-Work at a custom difficulty
+    pub fn compute_pow_with_difficulty(&mut self, difficulty: u32) -> u64 {
+        self.pow_nonce = generate_pow_nonce(&self.node_id, difficulty);
+        self.pow_nonce
     }
 
     /// SECURITY C2: Verify Proof-of-Work for a NodeId + nonce
     ///
     /// Returns true if hash(node_id || nonce) has at least `difficulty` leading zero bits.
     pub fn verify_pow(&self) -> bool {
-        Self::verify_pow_internal(&self.node_id, self.pow_nonce, REQUIRED_POW_DIFFICULTY)
+        self.verify_pow_with_difficulty(REQUIRED_POW_DIFFICULTY)
+    }
+
+    /// SECURITY C2: Verify Proof-of-Work against a custom difficulty
+    pub fn verify_pow_with_difficulty(&self, difficulty: u32) -> bool {
+        Self::verify_pow_internal(&self.node_id, self.pow_nonce, difficulty)
     }
 
     /// Internal PoW verification
@@ -217,6 +309,37 @@ impl NodeInfo {
         self.adapters.iter().find(|a| a.active)
     }
 
+    /// Most reachable NAT type reported by an active adapter
+    ///
+    /// Falls back to the advertised NAT type when no adapter reports one.
+    /// Either way this is the node's claim, not an observation.
+    pub fn nat_type(&self) -> Option<NatType> {
+        self.adapters
+            .iter()
+            .filter(|a| a.active)
+            .filter_map(|a| a.nat_type)
+            .min()
+            .or(self.advertised_nat)
+    }
+
+    /// Whether the node claims to accept unsolicited traffic but no probe
+    /// has checked the claim yet
+    pub fn needs_reachability_probe(&self) -> bool {
+        self.capabilities.can_relay
+            && self.reachability_probe.is_none()
+            && self.nat_type().is_some_and(|nat| nat.accepts_unsolicited())
+    }
+
+    /// Whether this node can coordinate hole-punches for NATed peers
+    ///
+    /// Requires relaying, a NAT type that accepts unsolicited traffic, and
+    /// a reachability probe that confirmed it.
+    pub fn is_rendezvous_capable(&self) -> bool {
+        self.capabilities.can_relay
+            && self.reachability_probe == Some(true)
+            && self.nat_type().is_some_and(|nat| nat.accepts_unsolicited())
+    }
+
     /// Calculate XOR distance to another node
     ///
     /// SECURITY C6: Returns 64-byte XOR distance for enhanced collision resistance
@@ -244,6 +367,8 @@ impl NodeInfo {
             reputation: self.reputation.clone(),
             last_seen: self.last_seen,
             rtt_ms: self.rtt_ms,
+            pow_nonce: self.pow_nonce,
+            nat_type: self.nat_type(),
         }
     }
 
@@ -283,7 +408,10 @@ impl NodeInfo {
 ///
 /// This struct is deliberately designed WITHOUT any address fields
 /// to prevent accidental inclusion of identifying information.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Binary serialization is prefixed with a layout version, and records from
+/// peers that predate the PoW nonce and NAT hint still decode.
+#[derive(Debug, Clone)]
 pub struct PublicNodeInfo {
     /// Node identifier (32 bytes)
     pub node_id: ProtocolNodeId,
@@ -299,6 +427,161 @@ pub struct PublicNodeInfo {
 
     /// Round-trip time in milliseconds
     pub rtt_ms: f64,
+
+    /// SECURITY C2: Proof-of-Work nonce, so peers can check the NodeId
+    /// before admitting it to their routing tables
+    pub pow_nonce: u64,
+
+    /// NAT traversal hint
+    ///
+    /// SECURITY H11: Only the NAT type is published; reflexive addresses
+    /// stay in the local `AdapterInfo` like every other address.
+    pub nat_type: Option<NatType>,
+}
+
+/// Binary layout version of [`PublicNodeInfo`], written before its fields
+///
+/// Peers predating the PoW nonce and NAT hint send bare fields, which start
+/// with the node id's length prefix. In bincode that prefix reads as the
+/// same `u64` as this version, so `NODE_ID_SIZE` marks a legacy record and
+/// versions must never take that value.
+const PUBLIC_NODE_INFO_VERSION: u64 = 2;
+
+/// Fields of [`PublicNodeInfo`] as serialized
+///
+/// Self-describing formats use this struct directly, so records without the
+/// PoW nonce or NAT hint decode with those fields unset.
+#[derive(Deserialize)]
+struct PublicNodeInfoFields {
+    node_id: ProtocolNodeId,
+    capabilities: NodeCapabilities,
+    reputation: NodeReputation,
+    last_seen: u64,
+    rtt_ms: f64,
+    #[serde(default)]
+    pow_nonce: u64,
+    #[serde(default)]
+    nat_type: Option<NatType>,
+}
+
+/// Borrowed [`PublicNodeInfoFields`]
+#[derive(Serialize)]
+#[serde(rename = "PublicNodeInfo")]
+struct PublicNodeInfoFieldsRef<'a> {
+    node_id: &'a ProtocolNodeId,
+    capabilities: &'a NodeCapabilities,
+    reputation: &'a NodeReputation,
+    last_seen: u64,
+    rtt_ms: f64,
+    pow_nonce: u64,
+    nat_type: Option<NatType>,
+}
+
+/// Fields following the node id in a legacy binary record
+#[derive(Deserialize)]
+struct LegacyPublicNodeInfoTail {
+    capabilities: NodeCapabilities,
+    reputation: NodeReputation,
+    last_seen: u64,
+    rtt_ms: f64,
+}
+
+impl From<PublicNodeInfoFields> for PublicNodeInfo {
+    fn from(info: PublicNodeInfoFields) -> Self {
+        PublicNodeInfo {
+            node_id: info.node_id,
+            capabilities: info.capabilities,
+            reputation: info.reputation,
+            last_seen: info.last_seen,
+            rtt_ms: info.rtt_ms,
+            pow_nonce: info.pow_nonce,
+            nat_type: info.nat_type,
+        }
+    }
+}
+
+impl Serialize for PublicNodeInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields = PublicNodeInfoFieldsRef {
+            node_id: &self.node_id,
+            capabilities: &self.capabilities,
+            reputation: &self.reputation,
+            last_seen: self.last_seen,
+            rtt_ms: self.rtt_ms,
+            pow_nonce: self.pow_nonce,
+            nat_type: self.nat_type,
+        };
+        if serializer.is_human_readable() {
+            return fields.serialize(serializer);
+        }
+
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&PUBLIC_NODE_INFO_VERSION)?;
+        tuple.serialize_element(&fields)?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicNodeInfo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return PublicNodeInfoFields::deserialize(deserializer).map(PublicNodeInfo::from);
+        }
+
+        struct PublicNodeInfoVisitor;
+
+        impl<'de> Visitor<'de> for PublicNodeInfoVisitor {
+            type Value = PublicNodeInfo;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a versioned or legacy public node info")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PublicNodeInfo, A::Error> {
+                let lead: u64 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+                if lead == PUBLIC_NODE_INFO_VERSION {
+                    let fields: PublicNodeInfoFields = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                    return Ok(fields.into());
+                }
+                if lead != NODE_ID_SIZE as u64 {
+                    return Err(de::Error::custom(format!(
+                        "Unsupported public node info version {}",
+                        lead
+                    )));
+                }
+
+                // Legacy record: `lead` was the node id's length prefix.
+                // These predate the PoW nonce, so they only pass admission
+                // when no difficulty is required.
+                let mut node_id = [0u8; NODE_ID_SIZE];
+                for (i, byte) in node_id.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i + 1, &self))?;
+                }
+                let info: LegacyPublicNodeInfoTail = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(NODE_ID_SIZE + 1, &self))?;
+                Ok(PublicNodeInfo {
+                    node_id: ProtocolNodeId::from_bytes(node_id),
+                    capabilities: info.capabilities,
+                    reputation: info.reputation,
+                    last_seen: info.last_seen,
+                    rtt_ms: info.rtt_ms,
+                    pow_nonce: 0,
+                    nat_type: None,
+                })
+            }
+        }
+
+        // Long enough for a legacy record, whose node id is read bytewise
+        deserializer.deserialize_tuple(NODE_ID_SIZE + 2, PublicNodeInfoVisitor)
+    }
 }
 
 impl PublicNodeInfo {
@@ -310,9 +593,28 @@ impl PublicNodeInfo {
             reputation: NodeReputation::new(),
             last_seen: now(),
             rtt_ms: 0.0,
+            pow_nonce: 0,
+            nat_type: None,
         }
     }
 
+    /// Whether peers can reach this node without a hole-punch
+    pub fn is_publicly_reachable(&self) -> bool {
+        self.nat_type.is_some_and(|nat| nat.accepts_unsolicited())
+    }
+
+    /// SECURITY C2: Compute Proof-of-Work for this NodeId at `difficulty`
+    pub fn compute_pow(&mut self, difficulty: u32) -> u64 {
+        self.pow_nonce = generate_pow_nonce(&self.node_id, difficulty);
+        self.pow_nonce
+    }
+
+    /// SECURITY C2: Verify the NodeId hashes with `pow_nonce` to at least
+    /// `difficulty` leading zero bits (difficulty 0 accepts any nonce)
+    pub fn verify_pow(&self, difficulty: u32) -> bool {
+        NodeInfo::verify_pow_internal(&self.node_id, self.pow_nonce, difficulty)
+    }
+
     /// Calculate XOR distance to another node
     ///
     /// SECURITY C6: Returns 64-byte XOR distance for enhanced collision resistance
@@ -393,6 +695,8 @@ mod tests {
             adapter_type: AdapterType::Ethernet,
             address: "192.168.1.1:4001".to_string(),
             active: true,
+            reflexive_address: None,
+            nat_type: None,
         }];
 
         let node = NodeInfo::with_adapters(node_id, adapters.clone());
@@ -408,11 +712,15 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: "192.168.1.1:4001".to_string(),
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             },
             AdapterInfo {
                 adapter_type: AdapterType::I2P,
                 address: "ukeu3k5o...b32.i2p".to_string(),
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             },
         ];
 
@@ -560,6 +868,45 @@ mod tests {
         assert!(NodeInfo::verify_pow_internal(&node_id, nonce, 4));
     }
 
+    #[test]
+    fn test_public_node_pow_generate_and_verify() {
+        // SECURITY C2: PublicNodeInfo carries a PoW nonce peers can check
+        let mut public = PublicNodeInfo::new(
+            ProtocolNodeId::from_bytes([7u8; NODE_ID_SIZE]),
+            NodeCapabilities::default(),
+        );
+
+        let nonce = public.compute_pow(8);
+        assert_eq!(public.pow_nonce, nonce);
+        assert!(public.verify_pow(8));
+        assert_eq!(nonce, generate_pow_nonce(&public.node_id, 8));
+
+        // Difficulty zero accepts any nonce
+        public.pow_nonce = 12345;
+        assert!(public.verify_pow(0));
+    }
+
+    #[test]
+    fn test_public_node_pow_rejects_insufficient_work() {
+        // SECURITY C2: A nonce valid at low difficulty fails a higher target
+        let node_id = ProtocolNodeId::from_bytes([8u8; NODE_ID_SIZE]);
+        let mut node = NodeInfo::new(node_id);
+        node.compute_pow_with_difficulty(4);
+
+        let mut public = node.to_public();
+        assert_eq!(public.pow_nonce, node.pow_nonce);
+        assert!(public.verify_pow(4));
+
+        // Find a nonce with exactly 4 leading zero bits so it can't satisfy 5
+        while NodeInfo::verify_pow_internal(&node_id, public.pow_nonce, 5) || !public.verify_pow(4)
+        {
+            public.pow_nonce += 1;
+        }
+        assert!(public.verify_pow(4));
+        assert!(!public.verify_pow(5));
+        assert!(!public.verify_pow(REQUIRED_POW_DIFFICULTY));
+    }
+
     // SECURITY H11: Mode 2 Separation Tests
 
     #[test]
@@ -572,11 +919,15 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: "192.168.1.100:4001".to_string(),
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             },
             AdapterInfo {
                 adapter_type: AdapterType::I2P,
                 address: "ukeu3k5oykqjktxj4i6zqmqw3afkrqshnqgw2a9pafb3b6qw7evq.b32.i2p".to_string(),
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             },
         ];
 
@@ -674,6 +1025,8 @@ mod tests {
             adapter_type: AdapterType::Ethernet,
             address: "10.0.0.5:4001".to_string(),
             active: true,
+            reflexive_address: None,
+            nat_type: None,
         }];
 
         let node = NodeInfo::with_adapters(node_id, adapters);
@@ -727,6 +1080,8 @@ mod tests {
             adapter_type: AdapterType::I2P,
             address: "secretdestination.b32.i2p".to_string(),
             active: true,
+            reflexive_address: None,
+            nat_type: None,
         }];
 
         let mut private_node = NodeInfo::with_adapters(node_id, private_adapters);
@@ -770,4 +1125,128 @@ mod tests {
         // 3. Out-of-band discovery is handled separately
         // (not tested here, but Mode 2 is enforced at this layer) ✓
     }
+
+    fn nat_adapter(active: bool, nat_type: Option<NatType>) -> AdapterInfo {
+        AdapterInfo {
+            adapter_type: AdapterType::Ethernet,
+            address: "192.168.1.20:4001".to_string(),
+            active,
+            reflexive_address: nat_type.map(|_| "203.0.113.7:61002".to_string()),
+            nat_type,
+        }
+    }
+
+    #[test]
+    fn test_nat_hints_reported_without_addresses() {
+        let node_id = ProtocolNodeId::from_bytes([5u8; NODE_ID_SIZE]);
+        let mut node = NodeInfo::with_adapters(
+            node_id,
+            vec![
+                nat_adapter(true, Some(NatType::Symmetric)),
+                nat_adapter(true, Some(NatType::RestrictedCone)),
+                // Inactive adapters don't count
+                nat_adapter(false, Some(NatType::Open)),
+                nat_adapter(true, None),
+            ],
+        );
+
+        assert_eq!(node.nat_type(), Some(NatType::RestrictedCone));
+        let public = node.to_public();
+        assert_eq!(public.nat_type, Some(NatType::RestrictedCone));
+        assert!(!public.is_publicly_reachable());
+
+        // SECURITY H11: the reflexive address never reaches the public record
+        let encoded = bincode::serialize(&public).unwrap();
+        assert!(!encoded.windows(11).any(|w| w == b"203.0.113.7"));
+
+        node.adapters[2].active = true;
+        assert!(node.to_public().is_publicly_reachable());
+        // Claimed but not yet probed
+        assert!(node.needs_reachability_probe());
+        assert!(!node.is_rendezvous_capable());
+        node.reachability_probe = Some(true);
+        assert!(!node.needs_reachability_probe());
+        // Reachable but not relaying
+        node.capabilities.can_relay = false;
+        assert!(!node.is_rendezvous_capable());
+        node.capabilities.can_relay = true;
+        assert!(node.is_rendezvous_capable());
+        // A failed probe refutes the claim
+        node.reachability_probe = Some(false);
+        assert!(!node.is_rendezvous_capable());
+        assert!(!node.needs_reachability_probe());
+
+        assert_eq!(create_test_node().to_public().nat_type, None);
+    }
+
+    #[test]
+    fn test_nat_hole_punch_compatibility() {
+        use NatType::*;
+
+        assert!(Open.accepts_unsolicited());
+        assert!(FullCone.accepts_unsolicited());
+        assert!(!RestrictedCone.accepts_unsolicited());
+        assert!(!Symmetric.accepts_unsolicited());
+
+        assert!(RestrictedCone.can_hole_punch_with(&PortRestrictedCone));
+        assert!(Symmetric.can_hole_punch_with(&RestrictedCone));
+        assert!(Symmetric.can_hole_punch_with(&Open));
+        assert!(!Symmetric.can_hole_punch_with(&PortRestrictedCone));
+        assert!(!PortRestrictedCone.can_hole_punch_with(&Symmetric));
+        assert!(!Symmetric.can_hole_punch_with(&Symmetric));
+    }
+
+    #[test]
+    fn test_public_info_serialization_is_versioned() {
+        let mut public = create_test_node().to_public();
+        public.pow_nonce = 12345;
+        public.nat_type = Some(NatType::FullCone);
+
+        let bytes = bincode::serialize(&public).unwrap();
+        assert_eq!(bytes[..8], PUBLIC_NODE_INFO_VERSION.to_le_bytes());
+        let decoded: PublicNodeInfo = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded.node_id, public.node_id);
+        assert_eq!(decoded.pow_nonce, 12345);
+        assert_eq!(decoded.nat_type, Some(NatType::FullCone));
+
+        // Unknown versions are rejected instead of misread
+        let mut future = bytes.clone();
+        future[..8].copy_from_slice(&9u64.to_le_bytes());
+        assert!(bincode::deserialize::<PublicNodeInfo>(&future).is_err());
+    }
+
+    /// `PublicNodeInfo` as derived before the PoW nonce and NAT hint
+    #[derive(Serialize)]
+    struct BaselinePublicNodeInfo {
+        node_id: ProtocolNodeId,
+        capabilities: NodeCapabilities,
+        reputation: NodeReputation,
+        last_seen: u64,
+        rtt_ms: f64,
+    }
+
+    #[test]
+    fn test_legacy_public_info_has_no_pow_nonce() {
+        let public = create_test_node().to_public();
+        let baseline = BaselinePublicNodeInfo {
+            node_id: public.node_id,
+            capabilities: public.capabilities.clone(),
+            reputation: public.reputation.clone(),
+            last_seen: public.last_seen,
+            rtt_ms: public.rtt_ms,
+        };
+        let bytes = bincode::serialize(&baseline).unwrap();
+
+        let decoded: PublicNodeInfo = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded.node_id, public.node_id);
+        assert_eq!(decoded.pow_nonce, 0);
+        assert_eq!(decoded.nat_type, None);
+        assert!(decoded.verify_pow(0));
+        assert!(!decoded.verify_pow(REQUIRED_POW_DIFFICULTY));
+
+        // Also inside the node lists of DHT responses
+        let list = bincode::serialize(&vec![baseline]).unwrap();
+        let decoded: Vec<PublicNodeInfo> = bincode::deserialize(&list).unwrap();
+        assert_eq!(decoded[0].node_id, public.node_id);
+    }
 }
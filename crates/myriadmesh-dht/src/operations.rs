@@ -117,10 +117,15 @@ impl FindValueRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FindValueResponse {
     /// Value was found
+    ///
+    /// Carries the publisher's signed record unchanged, so the querier can
+    /// verify it whether it came from the publisher's storage node or a cache.
     Found {
         query_id: QueryId,
         key: [u8; 32],
         value: Vec<u8>,
+        expires_at: u64,
+        publisher_public_key: [u8; 32],
         signature: Vec<u8>,
     },
 
@@ -179,6 +184,8 @@ mod tests {
             query_id,
             key,
             value: value.clone(),
+            expires_at: 0,
+            publisher_public_key: [0u8; 32],
             signature,
         };
 
@@ -1,8 +1,22 @@
 //! Node reputation system for Sybil resistance
 
+use myriadmesh_protocol::NodeId;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+/// Default half-life for reputation decay toward neutral (7 days)
+pub const DEFAULT_REPUTATION_HALF_LIFE: Duration = Duration::from_secs(7 * 86400);
+
+/// Largest version penalty, leaving a penalized node 5% of its reputation
+///
+/// Matches the cap of `calculate_version_penalty` in `myriadmesh-network`.
+pub const MAX_VERSION_PENALTY: f64 = 0.95;
+
+fn default_half_life() -> Duration {
+    DEFAULT_REPUTATION_HALF_LIFE
+}
+
 /// Get current Unix timestamp
 fn now() -> u64 {
     SystemTime::now()
@@ -41,6 +55,16 @@ pub struct NodeReputation {
 
     /// Recent activity rate (messages per hour) for growth rate analysis
     recent_activity_rate: f64,
+
+    /// Half-life of inactivity decay toward neutral (set by the manager)
+    #[serde(skip, default = "default_half_life")]
+    half_life: Duration,
+
+    /// Penalty for outdated adapter versions (0.0 - MAX_VERSION_PENALTY)
+    ///
+    /// Not persisted; it returns with the node's next version reports.
+    #[serde(skip)]
+    version_penalty: f64,
 }
 
 impl NodeReputation {
@@ -50,8 +74,16 @@ impl NodeReputation {
     /// Good reputation for relay selection
     pub const GOOD_REPUTATION: f64 = 0.7;
 
+    /// Score of a node with no history; decay converges here
+    pub const NEUTRAL_REPUTATION: f64 = 0.2;
+
     /// Create new reputation for a node
     pub fn new() -> Self {
+        Self::with_half_life(DEFAULT_REPUTATION_HALF_LIFE)
+    }
+
+    /// Create new reputation that decays toward neutral with `half_life`
+    pub fn with_half_life(half_life: Duration) -> Self {
         let now = now();
         NodeReputation {
             successful_relays: 0,
@@ -60,9 +92,11 @@ impl NodeReputation {
             first_seen: now,
             last_updated: now,
             last_activity: now,
-            score: 0.2, // SECURITY C7: Start with low reputation (trust must be earned)
+            score: Self::NEUTRAL_REPUTATION, // SECURITY C7: Start with low reputation (trust must be earned)
             penalty_count: 0,
             recent_activity_rate: 0.0,
+            half_life,
+            version_penalty: 0.0,
         }
     }
 
@@ -145,28 +179,32 @@ impl NodeReputation {
     ///
     /// SECURITY M4: Faster decay for suspicious/penalized nodes
     fn update_score(&mut self) {
-        let current_time = now();
+        self.score = self.score_at(now());
+    }
 
+    /// Reputation score as of `current_time`, without storing it
+    fn score_at(&self, current_time: u64) -> f64 {
         // SECURITY C7 + M4: Apply time decay for inactivity with accelerated decay for suspicious nodes
         let time_since_activity = current_time.saturating_sub(self.last_activity);
 
         // SECURITY M4: Calculate decay rate based on penalty count
-        // - Normal nodes: 10% per day after 24 hours
+        // - Normal nodes: no extra decay (the half-life pull below applies)
         // - Penalized nodes (1-3 penalties): 20% per day after 12 hours
         // - Highly suspicious nodes (4+ penalties): 30% per day after 6 hours
-        let (decay_rate, decay_threshold): (f64, u64) = if self.penalty_count >= 4 {
-            (0.7, 6 * 3600) // 30% per day, starts after 6 hours
+        let accelerated: Option<(f64, u64)> = if self.penalty_count >= 4 {
+            Some((0.7, 6 * 3600)) // 30% per day, starts after 6 hours
         } else if self.penalty_count >= 1 {
-            (0.8, 12 * 3600) // 20% per day, starts after 12 hours
+            Some((0.8, 12 * 3600)) // 20% per day, starts after 12 hours
         } else {
-            (0.9, 86400) // 10% per day, starts after 24 hours
+            None
         };
 
-        let decay_factor = if time_since_activity > decay_threshold {
-            let time_units = time_since_activity as f64 / 86400.0; // Still measure in days
-            decay_rate.powf(time_units).max(0.05) // Min 5% for suspicious nodes
-        } else {
-            1.0
+        let decay_factor = match accelerated {
+            Some((decay_rate, decay_threshold)) if time_since_activity > decay_threshold => {
+                let time_units = time_since_activity as f64 / 86400.0; // Still measure in days
+                decay_rate.powf(time_units).max(0.05) // Min 5% for suspicious nodes
+            }
+            _ => 1.0,
         };
 
         // Relay reliability (50% weight)
@@ -214,7 +252,16 @@ impl NodeReputation {
         let base_score = reliability * 0.5 + uptime_score * 0.25 + age_score * 0.15 + 0.1;
 
         // Apply decay and penalties multiplicatively
-        self.score = (base_score * decay_factor * penalty_factor).clamp(0.0, 1.0);
+        let score = (base_score * decay_factor * penalty_factor).clamp(0.0, 1.0);
+
+        // Inactivity pulls the score toward neutral: each half-life without
+        // relay activity halves the remaining distance
+        let half_life = self.half_life.as_secs_f64();
+        if half_life <= 0.0 {
+            return score;
+        }
+        let factor = 0.5_f64.powf(time_since_activity as f64 / half_life);
+        Self::NEUTRAL_REPUTATION + (score - Self::NEUTRAL_REPUTATION) * factor
     }
 
     /// Get current reputation score
@@ -261,6 +308,40 @@ impl NodeReputation {
     pub fn recalculate(&mut self) {
         self.update_score();
     }
+
+    /// Recalculate the stored score as of `current_time`
+    pub fn recalculate_at(&mut self, current_time: u64) {
+        self.score = self.score_at(current_time);
+        self.last_updated = self.last_updated.max(current_time);
+    }
+
+    /// Get the half-life of inactivity decay
+    pub fn half_life(&self) -> Duration {
+        self.half_life
+    }
+
+    /// Set the penalty for outdated adapter versions
+    ///
+    /// `penalty` is the output of `calculate_version_penalty` (or its
+    /// neighbor reports equivalent), clamped to `0.0..=MAX_VERSION_PENALTY`.
+    /// It replaces any earlier penalty; zero clears it.
+    pub fn set_version_penalty(&mut self, penalty: f64) {
+        self.version_penalty = if penalty.is_nan() {
+            0.0
+        } else {
+            penalty.clamp(0.0, MAX_VERSION_PENALTY)
+        };
+    }
+
+    /// Get the penalty for outdated adapter versions
+    pub fn version_penalty(&self) -> f64 {
+        self.version_penalty
+    }
+
+    /// Score scaled down by the version penalty, for relay selection
+    pub fn effective_score(&self) -> f64 {
+        self.score * (1.0 - self.version_penalty)
+    }
 }
 
 impl Default for NodeReputation {
@@ -269,14 +350,210 @@ impl Default for NodeReputation {
     }
 }
 
+/// Persistable snapshot of all tracked reputations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReputationSnapshot {
+    /// When the snapshot was taken (Unix timestamp)
+    pub taken_at: u64,
+
+    /// Reputations recalculated as of `taken_at`
+    pub entries: Vec<(NodeId, NodeReputation)>,
+}
+
 /// Reputation manager for tracking multiple nodes
+///
+/// Scores decay toward neutral with a configurable half-life, measured from
+/// each node's last relay activity. Reads compute the decayed score through
+/// the same path that `apply_decay` and new observations store, so no
+/// background task is needed.
+///
+/// Nodes running outdated or vulnerable adapter versions carry a version
+/// penalty that scales their effective score down. Penalties come from the
+/// node's latest version reports and are replaced, not accumulated, on each
+/// report.
+#[derive(Debug, Clone)]
 pub struct ReputationManager {
-    // Could add persistence layer here in future
+    /// Reputation per node
+    reputations: HashMap<NodeId, NodeReputation>,
+
+    /// Half-life of decay toward neutral
+    half_life: Duration,
 }
 
 impl ReputationManager {
     pub fn new() -> Self {
-        ReputationManager {}
+        Self::with_half_life(DEFAULT_REPUTATION_HALF_LIFE)
+    }
+
+    /// Create a manager with a custom decay half-life
+    pub fn with_half_life(half_life: Duration) -> Self {
+        ReputationManager {
+            reputations: HashMap::new(),
+            half_life,
+        }
+    }
+
+    /// Get the decay half-life
+    pub fn half_life(&self) -> Duration {
+        self.half_life
+    }
+
+    /// Get number of tracked nodes
+    pub fn len(&self) -> usize {
+        self.reputations.len()
+    }
+
+    /// Check if no nodes are tracked
+    pub fn is_empty(&self) -> bool {
+        self.reputations.is_empty()
+    }
+
+    /// Get the reputation record for a node, creating a neutral one if unknown
+    pub fn get_or_create(&mut self, node_id: NodeId) -> &mut NodeReputation {
+        let half_life = self.half_life;
+        self.reputations
+            .entry(node_id)
+            .or_insert_with(|| NodeReputation::with_half_life(half_life))
+    }
+
+    /// Get the stored reputation record for a node
+    ///
+    /// Its score reflects decay up to the last update or `apply_decay`.
+    pub fn get(&self, node_id: &NodeId) -> Option<&NodeReputation> {
+        self.reputations.get(node_id)
+    }
+
+    /// Record a successful relay by a node
+    pub fn record_success(&mut self, node_id: NodeId) {
+        self.get_or_create(node_id).record_success();
+    }
+
+    /// Record a failed relay by a node
+    pub fn record_failure(&mut self, node_id: NodeId) {
+        self.get_or_create(node_id).record_failure();
+    }
+
+    /// Get a node's current score with decay applied
+    pub fn score(&self, node_id: &NodeId) -> Option<f64> {
+        self.score_at(node_id, now())
+    }
+
+    /// Get a node's score with decay applied up to `current_time`
+    pub fn score_at(&self, node_id: &NodeId, current_time: u64) -> Option<f64> {
+        self.reputations
+            .get(node_id)
+            .map(|rep| rep.score_at(current_time))
+    }
+
+    /// Store decay up to now in every tracked reputation
+    pub fn apply_decay(&mut self) {
+        self.apply_decay_at(now());
+    }
+
+    /// Store decay up to `current_time` in every tracked reputation
+    pub fn apply_decay_at(&mut self, current_time: u64) {
+        for rep in self.reputations.values_mut() {
+            rep.recalculate_at(current_time);
+        }
+    }
+
+    /// Set a tracked node's version penalty from its latest version reports
+    ///
+    /// See [`NodeReputation::set_version_penalty`]. Reports about nodes with
+    /// no relay history are ignored, so they cannot be used to grow the
+    /// table; returns whether the node is tracked.
+    pub fn set_version_penalty(&mut self, node_id: NodeId, penalty: f64) -> bool {
+        match self.reputations.get_mut(&node_id) {
+            Some(rep) => {
+                rep.set_version_penalty(penalty);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get a node's version penalty (0.0 if none)
+    pub fn version_penalty(&self, node_id: &NodeId) -> f64 {
+        self.reputations
+            .get(node_id)
+            .map_or(0.0, NodeReputation::version_penalty)
+    }
+
+    /// Get a node's score with decay and version penalty applied
+    ///
+    /// This is the score to use when picking relays: onion hops (as
+    /// `RouteNode::reliability`) and preferred DHT peers.
+    pub fn effective_score(&self, node_id: &NodeId) -> Option<f64> {
+        self.effective_score_at(node_id, now())
+    }
+
+    /// Get a node's effective score with decay applied up to `current_time`
+    pub fn effective_score_at(&self, node_id: &NodeId, current_time: u64) -> Option<f64> {
+        self.reputations
+            .get(node_id)
+            .map(|rep| rep.score_at(current_time) * (1.0 - rep.version_penalty))
+    }
+
+    /// Order nodes by effective score, most trusted first
+    ///
+    /// Untracked nodes rank as neutral. Ties keep their input order.
+    pub fn rank_nodes(&self, nodes: &[NodeId]) -> Vec<NodeId> {
+        self.rank_nodes_at(nodes, now())
+    }
+
+    /// Order nodes by effective score as of `current_time`
+    pub fn rank_nodes_at(&self, nodes: &[NodeId], current_time: u64) -> Vec<NodeId> {
+        let mut scored: Vec<(NodeId, f64)> = nodes
+            .iter()
+            .map(|node_id| {
+                let score = self
+                    .effective_score_at(node_id, current_time)
+                    .unwrap_or(NodeReputation::NEUTRAL_REPUTATION);
+                (*node_id, score)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().map(|(node_id, _)| node_id).collect()
+    }
+
+    /// Take a snapshot of all reputations for persistence
+    pub fn snapshot(&self) -> ReputationSnapshot {
+        self.snapshot_at(now())
+    }
+
+    /// Take a snapshot with decay applied up to `current_time`
+    pub fn snapshot_at(&self, current_time: u64) -> ReputationSnapshot {
+        let entries = self
+            .reputations
+            .iter()
+            .map(|(node_id, rep)| {
+                let mut rep = rep.clone();
+                rep.recalculate_at(current_time);
+                (*node_id, rep)
+            })
+            .collect();
+
+        ReputationSnapshot {
+            taken_at: current_time,
+            entries,
+        }
+    }
+
+    /// Replace all tracked reputations with those from a snapshot
+    ///
+    /// Decay continues from the snapshot time, so downtime counts as inactivity.
+    /// Version penalties are not persisted; they return with the next
+    /// version reports.
+    pub fn restore(&mut self, snapshot: ReputationSnapshot) {
+        let half_life = self.half_life;
+        self.reputations = snapshot
+            .entries
+            .into_iter()
+            .map(|(node_id, mut rep)| {
+                rep.half_life = half_life;
+                (node_id, rep)
+            })
+            .collect();
     }
 }
 
@@ -348,6 +625,184 @@ mod tests {
         assert!(rep.score() > 0.4);
         assert!(rep.is_trustworthy());
     }
+
+    fn test_node_id(byte: u8) -> NodeId {
+        NodeId::from_bytes([byte; myriadmesh_protocol::types::NODE_ID_SIZE])
+    }
+
+    #[test]
+    fn test_manager_score_decays_toward_neutral() {
+        let half_life = Duration::from_secs(86400);
+        let mut manager = ReputationManager::with_half_life(half_life);
+        let node = test_node_id(1);
+
+        // Old enough that the age component no longer grows
+        let rep = manager.get_or_create(node);
+        rep.first_seen -= 31 * 86400;
+        rep.update_uptime(Duration::from_secs(30 * 86400));
+        for _ in 0..200 {
+            manager.record_success(node);
+        }
+
+        let start = manager.get(&node).unwrap().last_activity;
+        let fresh = manager.score_at(&node, start).unwrap();
+        assert!(fresh > NodeReputation::GOOD_REPUTATION - 0.2);
+
+        // One half-life halves the distance to neutral
+        let one = manager.score_at(&node, start + 86400).unwrap();
+        let expected =
+            NodeReputation::NEUTRAL_REPUTATION + (fresh - NodeReputation::NEUTRAL_REPUTATION) / 2.0;
+        assert!((one - expected).abs() < 1e-9);
+
+        // After many half-lives the old score is effectively neutral
+        let old = manager.score_at(&node, start + 30 * 86400).unwrap();
+        assert!((old - NodeReputation::NEUTRAL_REPUTATION).abs() < 1e-6);
+
+        // Bad scores recover toward neutral too
+        let bad = test_node_id(2);
+        for _ in 0..100 {
+            manager.record_failure(bad);
+        }
+        let bad_start = manager.get(&bad).unwrap().last_activity;
+        let bad_fresh = manager.score_at(&bad, bad_start).unwrap();
+        assert!(bad_fresh < NodeReputation::NEUTRAL_REPUTATION);
+        let bad_later = manager.score_at(&bad, bad_start + 3 * 86400).unwrap();
+        assert!(bad_later > bad_fresh && bad_later < NodeReputation::NEUTRAL_REPUTATION);
+
+        assert_eq!(manager.score(&test_node_id(3)), None);
+    }
+
+    #[test]
+    fn test_manager_snapshot_restore_preserves_decayed_scores() {
+        let half_life = Duration::from_secs(86400);
+        let mut manager = ReputationManager::with_half_life(half_life);
+        let node = test_node_id(1);
+        for _ in 0..150 {
+            manager.record_success(node);
+        }
+
+        let start = manager.get(&node).unwrap().last_activity;
+        let snapshot_time = start + 2 * 86400;
+        let decayed = manager.score_at(&node, snapshot_time).unwrap();
+
+        // Round-trip through serialization as persistence would
+        let snapshot = manager.snapshot_at(snapshot_time);
+        assert_eq!(snapshot.taken_at, snapshot_time);
+        let bytes = bincode::serialize(&snapshot).unwrap();
+        let restored_snapshot: ReputationSnapshot = bincode::deserialize(&bytes).unwrap();
+
+        let mut restored = ReputationManager::with_half_life(half_life);
+        restored.restore(restored_snapshot);
+        assert_eq!(restored.len(), 1);
+
+        let restored_score = restored.score_at(&node, snapshot_time).unwrap();
+        assert!((restored_score - decayed).abs() < 1e-9);
+        assert!(restored_score < manager.score_at(&node, start).unwrap());
+
+        // Decay continues from the snapshot time at the same rate
+        let later = restored.score_at(&node, snapshot_time + 86400).unwrap();
+        let original_later = manager.score_at(&node, snapshot_time + 86400).unwrap();
+        assert!((later - original_later).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_manager_apply_decay_updates_stored_score() {
+        let half_life = Duration::from_secs(86400);
+        let mut manager = ReputationManager::with_half_life(half_life);
+        let node = test_node_id(1);
+        for _ in 0..150 {
+            manager.record_success(node);
+        }
+
+        let start = manager.get(&node).unwrap().last_activity;
+        let fresh = manager.get(&node).unwrap().score();
+        let later = start + 2 * 86400;
+        let expected = manager.score_at(&node, later).unwrap();
+
+        manager.apply_decay_at(later);
+        let rep = manager.get(&node).unwrap();
+        assert!((rep.score() - expected).abs() < 1e-9);
+        assert!(rep.score() < fresh);
+        assert_eq!(rep.half_life(), half_life);
+
+        // Updates that are not relay activity keep the inactivity decay
+        let mut idle = manager.get(&node).unwrap().clone();
+        idle.last_activity -= 2 * 86400;
+        let mut active = idle.clone();
+        active.last_activity += 2 * 86400;
+        idle.apply_penalty("Contradictory routing information");
+        active.apply_penalty("Contradictory routing information");
+        assert!(idle.score() < active.score());
+    }
+
+    #[test]
+    fn test_version_penalty_ranks_node_below_equal_peer() {
+        let mut manager = ReputationManager::new();
+        let current = test_node_id(1);
+        let outdated = test_node_id(2);
+
+        manager
+            .get_or_create(current)
+            .update_uptime(Duration::from_secs(30 * 86400));
+        for _ in 0..100 {
+            manager.record_success(current);
+        }
+        let record = manager.get(&current).unwrap().clone();
+        *manager.get_or_create(outdated) = record;
+        let at = manager.get(&current).unwrap().last_updated;
+        assert_eq!(
+            manager.score_at(&current, at),
+            manager.score_at(&outdated, at)
+        );
+
+        manager.set_version_penalty(outdated, 0.6);
+        let current_score = manager.effective_score_at(&current, at).unwrap();
+        let outdated_score = manager.effective_score_at(&outdated, at).unwrap();
+        assert!((outdated_score - current_score * 0.4).abs() < 1e-9);
+
+        // Outdated node listed first, ranked last
+        assert_eq!(
+            manager.rank_nodes_at(&[outdated, current], at),
+            vec![current, outdated]
+        );
+
+        // An update clears the penalty and the tie keeps input order
+        manager.set_version_penalty(outdated, 0.0);
+        assert_eq!(manager.version_penalty(&outdated), 0.0);
+        assert_eq!(
+            manager.rank_nodes_at(&[outdated, current], at),
+            vec![outdated, current]
+        );
+    }
+
+    #[test]
+    fn test_version_penalty_clamped() {
+        let mut manager = ReputationManager::new();
+        let node = test_node_id(1);
+        manager.get_or_create(node);
+
+        assert!(manager.set_version_penalty(node, 3.0));
+        assert_eq!(manager.version_penalty(&node), MAX_VERSION_PENALTY);
+        assert!(manager.effective_score(&node).unwrap() > 0.0);
+
+        manager.set_version_penalty(node, -1.0);
+        assert_eq!(manager.version_penalty(&node), 0.0);
+        assert_eq!(
+            manager.effective_score(&node),
+            Some(NodeReputation::NEUTRAL_REPUTATION)
+        );
+    }
+
+    #[test]
+    fn test_version_penalty_ignores_unknown_nodes() {
+        let mut manager = ReputationManager::new();
+        let stranger = test_node_id(9);
+
+        assert!(!manager.set_version_penalty(stranger, 0.5));
+        assert!(manager.is_empty());
+        assert_eq!(manager.version_penalty(&stranger), 0.0);
+        assert_eq!(manager.effective_score(&stranger), None);
+    }
 }
 
 #[test]
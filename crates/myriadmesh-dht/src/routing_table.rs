@@ -1,10 +1,51 @@
 //! Kademlia routing table
 
 use crate::error::Result;
+use crate::iterative_lookup::IterativeLookup;
 use crate::kbucket::KBucket;
-use crate::node_info::NodeInfo;
+use crate::node_info::{NodeInfo, PublicNodeInfo, REQUIRED_POW_DIFFICULTY};
+use crate::operations::{FindNodeRequest, FindNodeResponse, FindValueRequest, FindValueResponse};
+use crate::storage::StorageEntry;
+use crate::{ALPHA, K};
+use myriadmesh_protocol::types::NODE_ID_SIZE;
 use myriadmesh_protocol::NodeId;
-use std::time::{SystemTime, UNIX_EPOCH};
+use rand::RngCore;
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Upper bound on query rounds in an iterative lookup
+const MAX_LOOKUP_ROUNDS: usize = 32;
+
+/// Maximum peers a range query is fanned out to
+pub const MAX_RANGE_QUERY_PEERS: usize = K * ALPHA;
+
+/// Result of a successful iterative FIND_VALUE lookup
+#[derive(Debug, Clone)]
+pub struct FoundValue {
+    /// Key that was looked up
+    pub key: [u8; 32],
+
+    /// Signed entry returned by the first node that had it, with the
+    /// publisher's signature verified
+    pub entry: StorageEntry,
+
+    /// Closest queried node that did not have the value; the caller should
+    /// cache the value there with a short TTL
+    pub cache_at: Option<PublicNodeInfo>,
+
+    /// Number of nodes queried before the value was found
+    pub nodes_queried: usize,
+}
+
+/// Map a 32-byte storage key onto the node ID space
+///
+/// The key occupies the high-order bytes, so XOR distance to a key is
+/// dominated by the same prefix bits that order nodes in the routing table.
+pub fn key_target(key: &[u8; 32]) -> NodeId {
+    let mut bytes = [0u8; NODE_ID_SIZE];
+    bytes[..32].copy_from_slice(key);
+    NodeId::from_bytes(bytes)
+}
 
 /// Get current timestamp
 fn now() -> u64 {
@@ -25,6 +66,9 @@ pub struct RoutingTable {
 
     /// Total nodes in routing table
     node_count: usize,
+
+    /// SECURITY C2: Leading zero bits required of a node's PoW
+    pow_difficulty: u32,
 }
 
 impl RoutingTable {
@@ -39,9 +83,31 @@ impl RoutingTable {
             local_node_id,
             buckets,
             node_count: 0,
+            pow_difficulty: REQUIRED_POW_DIFFICULTY,
         }
     }
 
+    /// Create a routing table with a custom PoW difficulty
+    ///
+    /// SECURITY C2: A difficulty of 0 disables the check; use only for testing.
+    pub fn with_pow_difficulty(local_node_id: NodeId, pow_difficulty: u32) -> Self {
+        RoutingTable {
+            pow_difficulty,
+            ..Self::new(local_node_id)
+        }
+    }
+
+    /// Get the PoW difficulty required for admission
+    pub fn pow_difficulty(&self) -> u32 {
+        self.pow_difficulty
+    }
+
+    /// SECURITY C2: Check whether a node learned from a peer has enough PoW
+    /// to be admitted to this routing table
+    pub fn accepts(&self, node: &PublicNodeInfo) -> bool {
+        node.verify_pow(self.pow_difficulty)
+    }
+
     /// Get our local node ID
     pub fn local_node_id(&self) -> &NodeId {
         &self.local_node_id
@@ -77,14 +143,14 @@ impl RoutingTable {
     /// Add or update a node in the routing table
     ///
     /// SECURITY C2: Verifies Proof-of-Work before admitting nodes to prevent Sybil attacks
-    pub fn add_or_update(&mut self, node: NodeInfo) -> Result<()> {
+    pub fn add_or_update(&mut self, mut node: NodeInfo) -> Result<()> {
         // Don't add ourselves
         if node.node_id == self.local_node_id {
             return Ok(());
         }
 
         // SECURITY C2: Verify Proof-of-Work to prevent Sybil attacks
-        if !node.verify_pow() {
+        if !node.verify_pow_with_difficulty(self.pow_difficulty) {
             return Err(crate::error::DhtError::InvalidProofOfWork(format!(
                 "Node {} has invalid PoW nonce {}",
                 hex::encode(node.node_id.as_bytes()),
@@ -95,7 +161,23 @@ impl RoutingTable {
         let bucket_idx = self.bucket_index(&node.node_id);
         let bucket = &mut self.buckets[bucket_idx];
 
-        let was_present = bucket.find_node(&node.node_id).is_some();
+        // The version penalty and probe outcome come from local
+        // observation, not the node's own announcement, so they survive a
+        // refresh. A changed NAT claim needs probing again.
+        let existing = bucket.find_node(&node.node_id);
+        let was_present = existing.is_some();
+        if let Some(existing) = existing {
+            node.reputation
+                .set_version_penalty(existing.reputation.version_penalty());
+            if node.advertised_nat.is_none() {
+                node.advertised_nat = existing.advertised_nat;
+            }
+            node.reachability_probe = if node.nat_type() == existing.nat_type() {
+                existing.reachability_probe
+            } else {
+                None
+            };
+        }
         let added = bucket.add_or_update(node, now())?;
 
         // Update node count
@@ -240,6 +322,204 @@ impl RoutingTable {
         selected
     }
 
+    /// Run an iterative FIND_NODE lookup converging on the k closest nodes to `target`
+    ///
+    /// Seeds the shortlist from this table and queries `ALPHA` of the closest
+    /// unqueried nodes at a time via `query_fn`, which issues the network
+    /// request. When a round finds nothing closer, all unqueried nodes among
+    /// the k closest are queried, and the lookup stops once none remain.
+    /// Nodes whose query fails (or answers a different query id) are excluded
+    /// and the lookup continues without them.
+    pub async fn iterative_find_node<F, Fut>(
+        &self,
+        target: NodeId,
+        query_fn: F,
+    ) -> Vec<PublicNodeInfo>
+    where
+        F: Fn(PublicNodeInfo, FindNodeRequest) -> Fut,
+        Fut: Future<Output = Result<FindNodeResponse>>,
+    {
+        let seeds = self
+            .get_k_closest(&target, K)
+            .iter()
+            .map(NodeInfo::to_public)
+            .collect();
+        let mut lookup = IterativeLookup::with_params(target, seeds, K, ALPHA, MAX_LOOKUP_ROUNDS);
+
+        let mut closest = None;
+        let mut final_sweep = false;
+
+        for _ in 0..MAX_LOOKUP_ROUNDS {
+            let batch = if final_sweep {
+                lookup.next_final_batch()
+            } else {
+                lookup.next_query_batch()
+            };
+            if batch.is_empty() {
+                break;
+            }
+
+            let queries = batch.into_iter().map(|node| {
+                let request = FindNodeRequest::new(target, self.local_node_id);
+                let node_id = node.node_id;
+                let query_id = request.query_id;
+                let response = query_fn(node, request);
+                async move { (node_id, query_id, response.await) }
+            });
+
+            for (node_id, query_id, response) in futures::future::join_all(queries).await {
+                match response {
+                    Ok(response) if response.query_id == query_id => {
+                        lookup.mark_responded(&node_id);
+                        let discovered = response
+                            .nodes
+                            .into_iter()
+                            .filter(|n| n.node_id != self.local_node_id)
+                            .collect();
+                        lookup.add_discovered_nodes(discovered);
+                    }
+                    _ => lookup.mark_failed(&node_id),
+                }
+            }
+            lookup.next_round();
+
+            let round_closest = lookup.closest_responded_distance();
+            let improved = match (&round_closest, &closest) {
+                (Some(new), Some(old)) => new < old,
+                (Some(_), None) => true,
+                _ => false,
+            };
+
+            // Once progress stalls, keep sweeping until every live node in
+            // the k closest has been queried
+            if improved {
+                closest = round_closest;
+                final_sweep = false;
+            } else {
+                final_sweep = true;
+            }
+        }
+
+        lookup.get_closest_nodes()
+    }
+
+    /// Perform an iterative FIND_VALUE lookup for `key`
+    ///
+    /// Runs the same alpha-parallel lookup as `iterative_find_node`, but
+    /// stops at the first round in which a queried node returns the value.
+    /// A returned value whose publisher signature doesn't verify counts as a
+    /// failed query. The result names the closest node that missed, so the
+    /// caller can cache the value there (see `DhtStorage::cache_value`).
+    pub async fn iterative_find_value<F, Fut>(
+        &self,
+        key: [u8; 32],
+        query_fn: F,
+    ) -> Option<FoundValue>
+    where
+        F: Fn(PublicNodeInfo, FindValueRequest) -> Fut,
+        Fut: Future<Output = Result<FindValueResponse>>,
+    {
+        let target = key_target(&key);
+        let seeds = self
+            .get_k_closest(&target, K)
+            .iter()
+            .map(NodeInfo::to_public)
+            .collect();
+        let mut lookup = IterativeLookup::with_params(target, seeds, K, ALPHA, MAX_LOOKUP_ROUNDS);
+
+        let mut misses: Vec<PublicNodeInfo> = Vec::new();
+        let mut nodes_queried = 0;
+        let mut closest = None;
+        let mut final_sweep = false;
+
+        for _ in 0..MAX_LOOKUP_ROUNDS {
+            let batch = if final_sweep {
+                lookup.next_final_batch()
+            } else {
+                lookup.next_query_batch()
+            };
+            if batch.is_empty() {
+                break;
+            }
+            nodes_queried += batch.len();
+
+            let queries = batch.into_iter().map(|node| {
+                let request = FindValueRequest::new(key, self.local_node_id);
+                let query_id = request.query_id;
+                let response = query_fn(node.clone(), request);
+                async move { (node, query_id, response.await) }
+            });
+
+            let mut found = None;
+            for (node, query_id, response) in futures::future::join_all(queries).await {
+                match response {
+                    Ok(FindValueResponse::Found {
+                        query_id: id,
+                        key: found_key,
+                        value,
+                        expires_at,
+                        publisher_public_key,
+                        signature,
+                    }) if id == query_id && found_key == key => {
+                        match StorageEntry::from_signed(
+                            key,
+                            value,
+                            expires_at,
+                            publisher_public_key,
+                            &signature,
+                        ) {
+                            Ok(entry) => {
+                                lookup.mark_responded(&node.node_id);
+                                found.get_or_insert(entry);
+                            }
+                            Err(_) => lookup.mark_failed(&node.node_id),
+                        }
+                    }
+                    Ok(FindValueResponse::NotFound {
+                        query_id: id,
+                        nodes,
+                    }) if id == query_id => {
+                        lookup.mark_responded(&node.node_id);
+                        let discovered = nodes
+                            .into_iter()
+                            .filter(|n| n.node_id != self.local_node_id)
+                            .collect();
+                        lookup.add_discovered_nodes(discovered);
+                        misses.push(node);
+                    }
+                    _ => lookup.mark_failed(&node.node_id),
+                }
+            }
+
+            if let Some(entry) = found {
+                let cache_at = misses.into_iter().min_by_key(|n| n.distance_to(&target));
+                return Some(FoundValue {
+                    key,
+                    entry,
+                    cache_at,
+                    nodes_queried,
+                });
+            }
+            lookup.next_round();
+
+            let round_closest = lookup.closest_responded_distance();
+            let improved = match (&round_closest, &closest) {
+                (Some(new), Some(old)) => new < old,
+                (Some(_), None) => true,
+                _ => false,
+            };
+
+            if improved {
+                closest = round_closest;
+                final_sweep = false;
+            } else {
+                final_sweep = true;
+            }
+        }
+
+        None
+    }
+
     /// Get random nodes from routing table
     pub fn get_random_nodes(&self, count: usize) -> Vec<NodeInfo> {
         use rand::seq::SliceRandom;
@@ -258,13 +538,52 @@ impl RoutingTable {
         all_nodes.into_iter().take(count).collect()
     }
 
-    /// Get nodes with good reputation for relay
+    /// Set the version penalty of a known node from its version reports
+    ///
+    /// Lowers the node's effective reputation for relay and rendezvous
+    /// preference. Returns `false`, changing nothing, for unknown nodes.
+    pub fn set_version_penalty(&mut self, node_id: &NodeId, penalty: f64) -> bool {
+        match self.find_node_mut(node_id) {
+            Some(node) => {
+                node.reputation.set_version_penalty(penalty);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record the outcome of an unsolicited reachability probe
+    ///
+    /// A probe contacts the node from an address it never sent to; an
+    /// answer confirms its NAT accepts unsolicited traffic. Returns `false`,
+    /// changing nothing, for unknown nodes.
+    pub fn record_reachability_probe(&mut self, node_id: &NodeId, reachable: bool) -> bool {
+        match self.find_node_mut(node_id) {
+            Some(node) => {
+                node.reachability_probe = Some(reachable);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Relays whose claim to accept unsolicited traffic is still unverified
+    pub fn get_reachability_probe_targets(&self) -> Vec<NodeInfo> {
+        self.buckets
+            .iter()
+            .flat_map(|bucket| bucket.nodes().iter())
+            .filter(|node| node.needs_reachability_probe())
+            .cloned()
+            .collect()
+    }
+
+    /// Get nodes with good effective reputation for relay
     pub fn get_good_reputation_nodes(&self, min_reputation: f64) -> Vec<NodeInfo> {
         let mut nodes = Vec::new();
 
         for bucket in &self.buckets {
             for node in bucket.nodes().iter() {
-                if node.reputation.score() >= min_reputation {
+                if node.reputation.effective_score() >= min_reputation {
                     nodes.push(node.clone());
                 }
             }
@@ -273,6 +592,32 @@ impl RoutingTable {
         nodes
     }
 
+    /// Nodes able to coordinate a hole-punch for peers behind NAT
+    ///
+    /// Candidates relay and claim, through their adapters or their public
+    /// record, to be reachable without a hole-punch of their own; the claim
+    /// must have been confirmed by [`Self::record_reachability_probe`].
+    /// Returns up to `count`, highest effective reputation first, then
+    /// lowest RTT.
+    pub fn get_rendezvous_candidates(&self, count: usize) -> Vec<NodeInfo> {
+        let mut candidates: Vec<NodeInfo> = self
+            .buckets
+            .iter()
+            .flat_map(|bucket| bucket.nodes().iter())
+            .filter(|node| node.is_rendezvous_capable())
+            .cloned()
+            .collect();
+
+        candidates.sort_by(|a, b| {
+            b.reputation
+                .effective_score()
+                .total_cmp(&a.reputation.effective_score())
+                .then(a.rtt_ms.total_cmp(&b.rtt_ms))
+        });
+        candidates.truncate(count);
+        candidates
+    }
+
     /// Prune stale nodes from all buckets
     pub fn prune_stale(&mut self, max_age_secs: u64) -> usize {
         let mut total_pruned = 0;
@@ -303,6 +648,120 @@ impl RoutingTable {
         stale
     }
 
+    /// Get buckets due for a refresh lookup, each with a random target ID
+    ///
+    /// A non-empty bucket is due when it has been neither updated nor
+    /// refreshed within `interval`. Looking up the returned target (which
+    /// falls in the bucket's XOR range) repopulates the bucket; call
+    /// `mark_bucket_refreshed` once the lookup has run.
+    pub fn buckets_needing_refresh(&self, interval: Duration) -> Vec<(usize, NodeId)> {
+        self.buckets_needing_refresh_at(now(), interval)
+    }
+
+    fn buckets_needing_refresh_at(
+        &self,
+        current_time: u64,
+        interval: Duration,
+    ) -> Vec<(usize, NodeId)> {
+        self.buckets
+            .iter()
+            .filter(|bucket| !bucket.is_empty())
+            .filter(|bucket| {
+                let last_active = bucket.last_updated.max(bucket.last_refreshed);
+                current_time.saturating_sub(last_active) > interval.as_secs()
+            })
+            .map(|bucket| (bucket.index, self.random_id_in_bucket(bucket.index)))
+            .collect()
+    }
+
+    /// Record that a refresh lookup was run for a bucket
+    pub fn mark_bucket_refreshed(&mut self, index: usize) {
+        if let Some(bucket) = self.buckets.get_mut(index) {
+            bucket.last_refreshed = now();
+        }
+    }
+
+    /// Generate a random node ID that falls in bucket `index`
+    ///
+    /// The XOR distance to the local ID has exactly `index` leading zero
+    /// bits followed by a one bit; the remaining bits are random.
+    fn random_id_in_bucket(&self, index: usize) -> NodeId {
+        let mut distance = [0u8; NODE_ID_SIZE];
+        rand::thread_rng().fill_bytes(&mut distance);
+
+        let byte_idx = index / 8;
+        let bit = 7 - (index % 8);
+        distance[..byte_idx].fill(0);
+        distance[byte_idx] &= (1u8 << bit) - 1;
+        distance[byte_idx] |= 1u8 << bit;
+
+        let local = self.local_node_id.as_bytes();
+        let mut target = [0u8; NODE_ID_SIZE];
+        for (i, byte) in target.iter_mut().enumerate() {
+            *byte = local[i] ^ distance[i];
+        }
+        NodeId::from_bytes(target)
+    }
+
+    /// Whether the local node is among the `k` known nodes closest to `target`
+    ///
+    /// Used to decide whether to hold data addressed to `target`, such as a
+    /// recipient's store-and-forward inbox.
+    pub fn is_among_closest(&self, target: &NodeId, k: usize) -> bool {
+        let local_distance = target.distance(&self.local_node_id);
+        let closer = self
+            .buckets
+            .iter()
+            .flat_map(|bucket| bucket.nodes())
+            .filter(|node| target.distance(&node.node_id) < local_distance)
+            .count();
+        closer < k
+    }
+
+    /// Buckets that may hold nodes storing keys within `max_distance` of
+    /// `target`
+    ///
+    /// Every key in range shares `target`'s leading bits up to the first set
+    /// bit of `max_distance`; a bucket is relevant if its slice of the ID
+    /// space shares that prefix.
+    pub fn range_query_buckets(&self, target: &[u8; 32], max_distance: &[u8; 32]) -> Vec<usize> {
+        let prefix_len = max_distance
+            .iter()
+            .position(|byte| *byte != 0)
+            .map_or(256, |i| i * 8 + max_distance[i].leading_zeros() as usize);
+        let target_bucket = self
+            .bucket_index(&key_target(target))
+            .min(self.buckets.len() - 1);
+
+        if target_bucket < prefix_len {
+            // The range lies inside the target's own bucket
+            vec![target_bucket]
+        } else {
+            // The range covers our side of the ID space from that prefix on
+            (prefix_len.min(self.buckets.len() - 1)..self.buckets.len()).collect()
+        }
+    }
+
+    /// Peers to fan a range query out to, closest to `target` first
+    ///
+    /// Covers the nodes in [`RoutingTable::range_query_buckets`] plus the
+    /// `K` closest to `target`, which also hold keys near the edge of the
+    /// range. Capped at [`MAX_RANGE_QUERY_PEERS`].
+    pub fn range_query_peers(&self, target: &[u8; 32], max_distance: &[u8; 32]) -> Vec<NodeInfo> {
+        let target_id = key_target(target);
+        let mut peers: Vec<NodeInfo> = self
+            .range_query_buckets(target, max_distance)
+            .into_iter()
+            .flat_map(|index| self.buckets[index].nodes().iter().cloned())
+            .chain(self.get_k_closest(&target_id, K))
+            .collect();
+
+        peers.sort_by_key(|node| target_id.distance(&node.node_id));
+        peers.dedup_by_key(|node| node.node_id);
+        peers.truncate(MAX_RANGE_QUERY_PEERS);
+        peers
+    }
+
     /// Get all nodes in routing table
     pub fn get_all_nodes(&self) -> Vec<NodeInfo> {
         let mut all_nodes = Vec::new();
@@ -417,6 +876,175 @@ mod tests {
         assert_eq!(random.len(), 3);
     }
 
+    #[test]
+    fn test_rendezvous_candidates_are_publicly_reachable() {
+        use crate::node_info::{AdapterInfo, NatType};
+        use myriadmesh_protocol::types::AdapterType;
+
+        let local_id = NodeId::from_bytes([0; NODE_ID_SIZE]);
+        let mut table = RoutingTable::new(local_id);
+
+        let hints = [
+            (1, Some(NatType::Open), true),
+            (2, Some(NatType::FullCone), true),
+            (3, Some(NatType::Symmetric), true),
+            (4, Some(NatType::PortRestrictedCone), true),
+            (5, None, true),
+            // Reachable but not a relay
+            (6, Some(NatType::Open), false),
+        ];
+        for (id, nat_type, can_relay) in hints {
+            let mut node = create_test_node(id);
+            node.capabilities.can_relay = can_relay;
+            node.adapters = vec![AdapterInfo {
+                adapter_type: AdapterType::Ethernet,
+                address: format!("10.0.0.{}:4001", id),
+                active: true,
+                reflexive_address: Some(format!("198.51.100.{}:4001", id)),
+                nat_type,
+            }];
+            table.add_or_update(node).unwrap();
+        }
+        // Nothing is a candidate until a probe confirms the claim
+        assert!(table.get_rendezvous_candidates(10).is_empty());
+        let mut targets: Vec<u8> = table
+            .get_reachability_probe_targets()
+            .iter()
+            .map(|n| n.node_id.as_bytes()[0])
+            .collect();
+        targets.sort();
+        assert_eq!(targets, vec![1, 2]);
+        for id in [1, 2] {
+            assert!(table.record_reachability_probe(&NodeId::from_bytes([id; NODE_ID_SIZE]), true));
+        }
+        assert!(!table.record_reachability_probe(&NodeId::from_bytes([9; NODE_ID_SIZE]), true));
+        // Equal reputation: lower RTT first
+        table
+            .find_node_mut(&NodeId::from_bytes([1; NODE_ID_SIZE]))
+            .unwrap()
+            .rtt_ms = 200.0;
+        table
+            .find_node_mut(&NodeId::from_bytes([2; NODE_ID_SIZE]))
+            .unwrap()
+            .rtt_ms = 20.0;
+
+        let candidates = table.get_rendezvous_candidates(10);
+        let ids: Vec<u8> = candidates.iter().map(|n| n.node_id.as_bytes()[0]).collect();
+        assert_eq!(ids, vec![2, 1]);
+        assert!(candidates
+            .iter()
+            .all(|n| n.to_public().is_publicly_reachable()));
+
+        assert_eq!(table.get_rendezvous_candidates(1).len(), 1);
+    }
+
+    #[test]
+    fn test_version_penalty_lowers_relay_preference() {
+        use crate::node_info::{AdapterInfo, NatType};
+        use myriadmesh_protocol::types::AdapterType;
+
+        let local_id = NodeId::from_bytes([0; NODE_ID_SIZE]);
+        let mut table = RoutingTable::new(local_id);
+        let announce = |id: u8| {
+            let mut node = create_test_node(id);
+            node.capabilities.can_relay = true;
+            node.adapters = vec![AdapterInfo {
+                adapter_type: AdapterType::Ethernet,
+                address: format!("10.0.0.{}:4001", id),
+                active: true,
+                reflexive_address: Some(format!("198.51.100.{}:4001", id)),
+                nat_type: Some(NatType::Open),
+            }];
+            node
+        };
+        table.add_or_update(announce(1)).unwrap();
+        table.add_or_update(announce(2)).unwrap();
+        for id in [1, 2] {
+            table.record_reachability_probe(&NodeId::from_bytes([id; NODE_ID_SIZE]), true);
+        }
+        let outdated = NodeId::from_bytes([1; NODE_ID_SIZE]);
+
+        assert!(table.set_version_penalty(&outdated, 0.9));
+        assert!(!table.set_version_penalty(&NodeId::from_bytes([9; NODE_ID_SIZE]), 0.9));
+
+        // Otherwise equal; the outdated node ranks last
+        let ids: Vec<u8> = table
+            .get_rendezvous_candidates(10)
+            .iter()
+            .map(|n| n.node_id.as_bytes()[0])
+            .collect();
+        assert_eq!(ids, vec![2, 1]);
+        let floor = crate::reputation::NodeReputation::NEUTRAL_REPUTATION / 2.0;
+        let good: Vec<NodeId> = table
+            .get_good_reputation_nodes(floor)
+            .iter()
+            .map(|n| n.node_id)
+            .collect();
+        assert!(!good.contains(&outdated));
+
+        // A re-announcement keeps the locally derived penalty
+        table.add_or_update(announce(1)).unwrap();
+        assert_eq!(
+            table
+                .find_node(&outdated)
+                .unwrap()
+                .reputation
+                .version_penalty(),
+            0.9
+        );
+    }
+
+    #[test]
+    fn test_advertised_nat_needs_probe_before_rendezvous() {
+        use crate::node_info::{NatType, PublicNodeInfo};
+
+        let local_id = NodeId::from_bytes([0; NODE_ID_SIZE]);
+        let mut table = RoutingTable::new(local_id);
+        let difficulty = table.pow_difficulty();
+        let learn = |id: u8, nat_type| {
+            let mut public =
+                PublicNodeInfo::new(NodeId::from_bytes([id; NODE_ID_SIZE]), Default::default());
+            public.compute_pow(difficulty);
+            public.nat_type = nat_type;
+            NodeInfo::from_public(&public)
+        };
+        let open = NodeId::from_bytes([1; NODE_ID_SIZE]);
+        let liar = NodeId::from_bytes([2; NODE_ID_SIZE]);
+        table.add_or_update(learn(1, Some(NatType::Open))).unwrap();
+        table
+            .add_or_update(learn(2, Some(NatType::FullCone)))
+            .unwrap();
+        table
+            .add_or_update(learn(3, Some(NatType::Symmetric)))
+            .unwrap();
+
+        assert_eq!(table.get_reachability_probe_targets().len(), 2);
+        table.record_reachability_probe(&open, true);
+        table.record_reachability_probe(&liar, false);
+        let ids: Vec<NodeId> = table
+            .get_rendezvous_candidates(10)
+            .iter()
+            .map(|n| n.node_id)
+            .collect();
+        assert_eq!(ids, vec![open]);
+        assert!(table.get_reachability_probe_targets().is_empty());
+
+        // Re-announcing the same claim keeps the verdict; a new claim is
+        // probed again
+        table
+            .add_or_update(learn(2, Some(NatType::FullCone)))
+            .unwrap();
+        assert_eq!(
+            table.find_node(&liar).unwrap().reachability_probe,
+            Some(false)
+        );
+        table
+            .add_or_update(learn(1, Some(NatType::FullCone)))
+            .unwrap();
+        assert!(table.get_rendezvous_candidates(10).is_empty());
+        assert_eq!(table.get_reachability_probe_targets().len(), 1);
+    }
+
     #[test]
     fn test_bucket_index() {
         let local_id = NodeId::from_bytes([0; NODE_ID_SIZE]);
@@ -453,6 +1081,60 @@ mod tests {
         assert_eq!(table.node_count(), 1);
     }
 
+    #[test]
+    fn test_buckets_needing_refresh() {
+        let local_id = NodeId::from_bytes([0x33; NODE_ID_SIZE]);
+        let mut table = RoutingTable::with_pow_difficulty(local_id, 0);
+        let interval = Duration::from_secs(3600);
+
+        let mut far = [0x33; NODE_ID_SIZE];
+        far[0] ^= 0x80;
+        let mut near = [0x33; NODE_ID_SIZE];
+        near[2] ^= 0x04;
+        for id in [far, near] {
+            table
+                .add_or_update(NodeInfo::new(NodeId::from_bytes(id)))
+                .unwrap();
+        }
+        let far_idx = table.bucket_index(&NodeId::from_bytes(far));
+        let near_idx = table.bucket_index(&NodeId::from_bytes(near));
+        assert_eq!((far_idx, near_idx), (0, 21));
+
+        // Freshly touched buckets are not flagged; empty ones never are
+        assert!(table.buckets_needing_refresh(interval).is_empty());
+
+        // Two hours on, both are stale
+        let later = now() + 7200;
+        let mut due = table.buckets_needing_refresh_at(later, interval);
+        due.sort_by_key(|(idx, _)| *idx);
+        assert_eq!(
+            due.iter().map(|(idx, _)| *idx).collect::<Vec<_>>(),
+            vec![far_idx, near_idx]
+        );
+
+        // Each target lies in its bucket's XOR range
+        for (idx, target) in &due {
+            assert_eq!(table.bucket_index(target), *idx);
+            let distance = local_id.distance(target);
+            let leading_zeros: usize = distance.iter().take_while(|b| **b == 0).count() * 8
+                + distance.iter().find(|b| **b != 0).unwrap().leading_zeros() as usize;
+            assert_eq!(leading_zeros, *idx);
+        }
+
+        // Targets are randomised within the range
+        let (_, a) = table.buckets_needing_refresh_at(later, interval)[0];
+        let (_, b) = table.buckets_needing_refresh_at(later, interval)[0];
+        assert_ne!(a, b);
+
+        // Refreshing a bucket resets its clock
+        table.mark_bucket_refreshed(near_idx);
+        assert!(table.buckets[near_idx].last_refreshed >= table.buckets[near_idx].last_updated);
+        table.buckets[near_idx].last_refreshed = later - 1800;
+        let due = table.buckets_needing_refresh_at(later, interval);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, far_idx);
+    }
+
     // SECURITY C2: Proof-of-Work enforcement tests
 
     #[test]
@@ -518,6 +1200,33 @@ mod tests {
         assert_eq!(table.node_count(), 3);
     }
 
+    #[test]
+    fn test_configurable_pow_difficulty() {
+        // SECURITY C2: Difficulty is configurable, and zero disables the check
+        let local_id = NodeId::from_bytes([0; NODE_ID_SIZE]);
+
+        let mut open_table = RoutingTable::with_pow_difficulty(local_id, 0);
+        let mut unproven = NodeInfo::new(NodeId::from_bytes([42; NODE_ID_SIZE]));
+        unproven.pow_nonce = 12345;
+        assert!(open_table.accepts(&unproven.to_public()));
+        open_table.add_or_update(unproven.clone()).unwrap();
+        assert_eq!(open_table.node_count(), 1);
+
+        // Work done at a low difficulty only satisfies a table asking for it
+        let mut low_table = RoutingTable::with_pow_difficulty(local_id, 6);
+        let mut node = NodeInfo::new(NodeId::from_bytes([43; NODE_ID_SIZE]));
+        node.compute_pow_with_difficulty(6);
+        assert!(low_table.accepts(&node.to_public()));
+        low_table.add_or_update(node.clone()).unwrap();
+
+        assert!(!low_table.accepts(&unproven.to_public()));
+        assert!(low_table.add_or_update(unproven).is_err());
+
+        let strict_table = RoutingTable::new(local_id);
+        assert_eq!(strict_table.pow_difficulty(), REQUIRED_POW_DIFFICULTY);
+        assert!(!strict_table.accepts(&node.to_public()));
+    }
+
     // SECURITY H2: Eclipse attack prevention tests
 
     #[test]
@@ -536,6 +1245,8 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: format!("192.168.1.{}:8080", i),
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             }];
             table.add_or_update(node).unwrap();
         }
@@ -547,6 +1258,8 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: format!("192.168.{}.1:8080", i), // Different subnet
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             }];
             table.add_or_update(node).unwrap();
         }
@@ -638,6 +1351,8 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: format!("192.168.1.{}:8080", i), // All same subnet
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             }];
             table.add_or_update(node).unwrap();
         }
@@ -670,6 +1385,8 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: format!("10.0.0.{}:8080", i), // Attacker's subnet
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             }];
             table.add_or_update(node).ok(); // May fail due to diversity
         }
@@ -681,6 +1398,8 @@ mod tests {
                 adapter_type: AdapterType::Ethernet,
                 address: format!("192.168.{}.1:8080", i - 100), // Different subnets
                 active: true,
+                reflexive_address: None,
+                nat_type: None,
             }];
             table.add_or_update(node).ok();
         }
@@ -719,4 +1438,308 @@ mod tests {
             legitimate_nodes
         );
     }
+
+    /// Synthetic network for lookup tests: each node knows its nearest
+    /// neighbours plus a few far-away nodes
+    struct MockNetwork {
+        known: std::collections::HashMap<NodeId, Vec<NodeId>>,
+        dead: std::collections::HashSet<NodeId>,
+    }
+
+    impl MockNetwork {
+        fn new(ids: &[NodeId], dead_every: usize) -> Self {
+            let mut known = std::collections::HashMap::new();
+            for (i, id) in ids.iter().enumerate() {
+                let mut by_distance: Vec<NodeId> =
+                    ids.iter().filter(|other| *other != id).copied().collect();
+                by_distance.sort_by_key(|other| id.distance(other));
+
+                let mut contacts: Vec<NodeId> = by_distance.iter().take(K).copied().collect();
+                for j in 1..=8 {
+                    contacts.push(ids[(i + j * 37) % ids.len()]);
+                }
+                known.insert(*id, contacts);
+            }
+
+            let dead = ids
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| dead_every > 0 && i % dead_every == 0)
+                .map(|(_, id)| *id)
+                .collect();
+
+            MockNetwork { known, dead }
+        }
+
+        fn query(
+            &self,
+            node: &PublicNodeInfo,
+            request: &FindNodeRequest,
+        ) -> Result<FindNodeResponse> {
+            if self.dead.contains(&node.node_id) {
+                return Err(crate::error::DhtError::Timeout);
+            }
+            let mut contacts = self.known[&node.node_id].clone();
+            contacts.sort_by_key(|id| request.target.distance(id));
+            Ok(FindNodeResponse {
+                query_id: request.query_id,
+                nodes: contacts
+                    .into_iter()
+                    .take(K)
+                    .map(|id| PublicNodeInfo::new(id, Default::default()))
+                    .collect(),
+            })
+        }
+    }
+
+    fn synthetic_ids(count: usize) -> Vec<NodeId> {
+        use blake2::{Blake2b512, Digest};
+        (0..count)
+            .map(|i| {
+                let hash = Blake2b512::digest((i as u64).to_le_bytes());
+                let mut bytes = [0u8; NODE_ID_SIZE];
+                bytes.copy_from_slice(&hash);
+                NodeId::from_bytes(bytes)
+            })
+            .collect()
+    }
+
+    async fn run_lookup(dead_every: usize) {
+        let ids = synthetic_ids(300);
+        let local_id = ids[0];
+        let network = std::sync::Arc::new(MockNetwork::new(&ids, dead_every));
+
+        // Bootstrap from a few far-away nodes only
+        let mut table = RoutingTable::new(local_id);
+        for id in [ids[101], ids[151], ids[251]] {
+            let mut node = NodeInfo::new(id);
+            node.compute_pow();
+            table.add_or_update(node).unwrap();
+        }
+
+        let target = NodeId::from_bytes([0x5a; NODE_ID_SIZE]);
+        let result = table
+            .iterative_find_node(target, |node, request| {
+                let network = network.clone();
+                async move { network.query(&node, &request) }
+            })
+            .await;
+
+        let mut expected: Vec<NodeId> = ids
+            .iter()
+            .filter(|id| **id != local_id && !network.dead.contains(id))
+            .copied()
+            .collect();
+        expected.sort_by_key(|id| target.distance(id));
+        expected.truncate(K);
+
+        let found: Vec<NodeId> = result.iter().map(|n| n.node_id).collect();
+        assert_eq!(found, expected);
+    }
+
+    #[tokio::test]
+    async fn test_iterative_find_node_converges() {
+        run_lookup(0).await;
+    }
+
+    #[tokio::test]
+    async fn test_iterative_find_node_skips_unresponsive() {
+        run_lookup(7).await;
+    }
+
+    /// Mock network where one node holds a value and others may cache it
+    struct ValueNetwork {
+        nodes: MockNetwork,
+        holder: NodeId,
+        holder_online: std::sync::atomic::AtomicBool,
+        entry: StorageEntry,
+        caches: std::sync::Mutex<std::collections::HashMap<NodeId, crate::storage::DhtStorage>>,
+    }
+
+    impl ValueNetwork {
+        fn query(
+            &self,
+            node: &PublicNodeInfo,
+            request: &FindValueRequest,
+        ) -> Result<FindValueResponse> {
+            use std::sync::atomic::Ordering;
+
+            let holds = node.node_id == self.holder && self.holder_online.load(Ordering::SeqCst);
+            let cached = self
+                .caches
+                .lock()
+                .unwrap()
+                .get(&node.node_id)
+                .and_then(|storage| storage.get_cached(&request.key).cloned());
+
+            if holds || cached.is_some() {
+                let entry = cached.unwrap_or_else(|| self.entry.clone());
+                return Ok(FindValueResponse::Found {
+                    query_id: request.query_id,
+                    key: request.key,
+                    value: entry.value,
+                    expires_at: entry.expires_at,
+                    publisher_public_key: entry.publisher_public_key,
+                    signature: entry.signature.to_vec(),
+                });
+            }
+
+            let find_node = FindNodeRequest {
+                query_id: request.query_id,
+                target: key_target(&request.key),
+                requestor: request.requestor,
+            };
+            let response = self.nodes.query(node, &find_node)?;
+            Ok(FindValueResponse::NotFound {
+                query_id: response.query_id,
+                nodes: response.nodes,
+            })
+        }
+    }
+
+    /// A value signed by a freshly generated publisher
+    fn signed_entry(key: [u8; 32], value: &[u8]) -> StorageEntry {
+        use sodiumoxide::crypto::sign::ed25519;
+
+        let _ = sodiumoxide::init();
+        let (pk, sk) = ed25519::gen_keypair();
+        let expires_at = now() + 3600;
+        let mut message = key.to_vec();
+        message.extend_from_slice(value);
+        message.extend_from_slice(&expires_at.to_le_bytes());
+        let signature = ed25519::sign_detached(&message, &sk).to_bytes();
+
+        let mut pk_bytes = [0u8; 32];
+        pk_bytes.copy_from_slice(&pk[..]);
+        StorageEntry::from_signed(key, value.to_vec(), expires_at, pk_bytes, &signature).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_iterative_find_value_caches_along_path() {
+        use std::sync::atomic::Ordering;
+
+        let ids = synthetic_ids(300);
+        let key = [0x5a; 32];
+        let target = key_target(&key);
+
+        let mut by_distance = ids[1..].to_vec();
+        by_distance.sort_by_key(|id| target.distance(id));
+        let holder = by_distance[0];
+
+        let network = std::sync::Arc::new(ValueNetwork {
+            nodes: MockNetwork::new(&ids, 0),
+            holder,
+            holder_online: std::sync::atomic::AtomicBool::new(true),
+            entry: signed_entry(key, b"popular value"),
+            caches: Default::default(),
+        });
+
+        // First lookup walks from far-away nodes to the holder
+        let mut table = RoutingTable::new(ids[0]);
+        for id in [ids[101], ids[151], ids[251]] {
+            let mut node = NodeInfo::new(id);
+            node.compute_pow();
+            table.add_or_update(node).unwrap();
+        }
+        let query = |node: PublicNodeInfo, request: FindValueRequest| {
+            let network = network.clone();
+            async move { network.query(&node, &request) }
+        };
+
+        let first = table.iterative_find_value(key, query).await.unwrap();
+        assert_eq!(first.entry.value, network.entry.value);
+        assert!(first.nodes_queried > ALPHA);
+
+        // Cache at the closest node that missed, as the querying layer would
+        let cache_at = first.cache_at.unwrap();
+        assert_ne!(cache_at.node_id, holder);
+        let mut cache = crate::storage::DhtStorage::new();
+        cache.cache_value(first.entry.clone(), 600).unwrap();
+        network
+            .caches
+            .lock()
+            .unwrap()
+            .insert(cache_at.node_id, cache);
+
+        // A later querier that knows the caching node hits immediately,
+        // even with the original holder offline
+        network.holder_online.store(false, Ordering::SeqCst);
+        let mut other = RoutingTable::new(ids[1]);
+        for id in [ids[101], cache_at.node_id] {
+            let mut node = NodeInfo::new(id);
+            node.compute_pow();
+            other.add_or_update(node).unwrap();
+        }
+
+        let second = other.iterative_find_value(key, query).await.unwrap();
+        assert_eq!(second.entry.value, network.entry.value);
+        assert_eq!(second.entry.signature, network.entry.signature);
+        assert!(second.nodes_queried <= ALPHA);
+        assert!(second.nodes_queried < first.nodes_queried);
+    }
+
+    #[test]
+    fn test_is_among_closest() {
+        let local_id = NodeId::from_bytes([0x10; NODE_ID_SIZE]);
+        let mut table = RoutingTable::new(local_id);
+        for id in [0x11, 0x12, 0x80] {
+            table.add_or_update(create_test_node(id)).unwrap();
+        }
+
+        // Nodes 0x11 and 0x12 are closer to 0x13 than we are
+        let target = NodeId::from_bytes([0x13; NODE_ID_SIZE]);
+        assert!(!table.is_among_closest(&target, 2));
+        assert!(table.is_among_closest(&target, 3));
+
+        // Nobody is closer to our own ID
+        assert!(table.is_among_closest(&local_id, 1));
+    }
+
+    #[test]
+    fn test_range_query_buckets() {
+        let table = RoutingTable::new(NodeId::from_bytes([0; NODE_ID_SIZE]));
+        let mut target = [0u8; 32];
+        target[0] = 0x40;
+        let mut max_distance = [0xFFu8; 32];
+        max_distance[0] = 0x0F;
+
+        // 0x40-0x4F all sit in bucket 1 (first differing bit from 0x00)
+        assert_eq!(table.range_query_buckets(&target, &max_distance), vec![1]);
+
+        // A range around our own prefix spans every bucket from its length on
+        let table = RoutingTable::new(NodeId::from_bytes([0x33; NODE_ID_SIZE]));
+        let mut max_distance = [0xFFu8; 32];
+        max_distance[0] = 0;
+        let buckets = table.range_query_buckets(&[0x33; 32], &max_distance);
+        assert_eq!(buckets, (8..256).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_range_query_peers_closest_first() {
+        let local_id = NodeId::from_bytes([0; NODE_ID_SIZE]);
+        let mut table = RoutingTable::with_pow_difficulty(local_id, 0);
+        let node = |high: u8| {
+            let mut id = [0x01; NODE_ID_SIZE];
+            id[0] = high;
+            NodeInfo::new(NodeId::from_bytes(id))
+        };
+        for high in (0x40..=0x4F).chain([0x20, 0x80, 0xC0]) {
+            table.add_or_update(node(high)).unwrap();
+        }
+
+        let mut target = [0u8; 32];
+        target[0] = 0x44;
+        let mut max_distance = [0xFFu8; 32];
+        max_distance[0] = 0x0F;
+
+        let peers = table.range_query_peers(&target, &max_distance);
+        assert_eq!(peers.len(), table.node_count());
+        assert!(peers.len() <= MAX_RANGE_QUERY_PEERS);
+
+        // The in-range nodes come first, the nearest one leading
+        assert_eq!(peers[0].node_id.as_bytes()[0], 0x44);
+        assert!(peers[..16]
+            .iter()
+            .all(|peer| (0x40..=0x4F).contains(&peer.node_id.as_bytes()[0])));
+    }
 }
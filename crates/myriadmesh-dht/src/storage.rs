@@ -1,10 +1,13 @@
 //! DHT storage for key-value pairs
 
 use crate::error::{DhtError, Result};
-use crate::{MAX_DHT_KEYS, MAX_DHT_STORAGE_BYTES, MAX_VALUE_SIZE};
+use crate::routing_table::RoutingTable;
+use crate::{K, MAX_DHT_KEYS, MAX_DHT_STORAGE_BYTES, MAX_VALUE_SIZE};
+use myriadmesh_protocol::NodeId;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use serde_big_array::BigArray;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Get current timestamp with graceful fallback on system time errors
@@ -26,6 +29,18 @@ fn now() -> u64 {
     }
 }
 
+/// Maximum entries returned by a range query
+pub const MAX_RANGE_QUERY_RESULTS: usize = 256;
+
+/// XOR distance between two storage keys, compared as a big-endian integer
+pub fn key_distance(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut distance = [0u8; 32];
+    for (i, byte) in distance.iter_mut().enumerate() {
+        *byte = a[i] ^ b[i];
+    }
+    distance
+}
+
 /// A stored value with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageEntry {
@@ -55,9 +70,53 @@ pub struct StorageEntry {
     /// SECURITY H7: Signature from publisher to prevent DHT poisoning
     #[serde(with = "BigArray")]
     pub signature: [u8; 64],
+
+    /// Whether the local node is the original publisher of this value
+    /// (only the publisher can re-sign it with a fresh expiry)
+    #[serde(default)]
+    pub original_publisher: bool,
+
+    /// When this value was last (re)published to the network (Unix timestamp)
+    #[serde(default)]
+    pub last_published: u64,
 }
 
 impl StorageEntry {
+    /// Rebuild a signed entry received from another node
+    ///
+    /// SECURITY H7: Derives the publisher's node ID from its public key and
+    /// verifies the publisher's signature, so values relayed by a node other
+    /// than the publisher (such as a FIND_VALUE response) can't be forged.
+    pub fn from_signed(
+        key: [u8; 32],
+        value: Vec<u8>,
+        expires_at: u64,
+        publisher_public_key: [u8; 32],
+        signature: &[u8],
+    ) -> Result<Self> {
+        use blake2::{Blake2b512, Digest};
+
+        let signature: [u8; 64] = signature
+            .try_into()
+            .map_err(|_| DhtError::InvalidSignature)?;
+        let mut publisher_node_id = [0u8; 64];
+        publisher_node_id.copy_from_slice(&Blake2b512::digest(publisher_public_key));
+
+        let entry = StorageEntry {
+            key,
+            value,
+            stored_at: now(),
+            expires_at,
+            publisher_public_key,
+            publisher_node_id,
+            signature,
+            original_publisher: false,
+            last_published: 0,
+        };
+        entry.verify_signature()?;
+        Ok(entry)
+    }
+
     /// Check if entry is expired
     pub fn is_expired(&self) -> bool {
         now() >= self.expires_at
@@ -108,6 +167,128 @@ impl StorageEntry {
     }
 }
 
+/// Maximum lifetime of an opportunistically cached value (1 hour)
+pub const MAX_CACHED_VALUE_TTL_SECS: u64 = 3600;
+
+/// Maximum time an undelivered inbox message is held (7 days)
+pub const MAX_INBOX_TTL_SECS: u64 = 7 * 24 * 3600;
+
+/// Maximum messages held for a single recipient
+pub const MAX_INBOX_MESSAGES: usize = 256;
+
+/// Maximum bytes held for a single recipient
+pub const MAX_INBOX_BYTES: usize = 4 * 1024 * 1024; // 4MB
+
+/// Maximum messages held from a single sender, across all inboxes
+pub const MAX_INBOX_MESSAGES_PER_SENDER: usize = 64;
+
+/// Maximum bytes held from a single sender, across all inboxes
+pub const MAX_INBOX_BYTES_PER_SENDER: usize = 1024 * 1024; // 1MB
+
+/// How long a recipient has to answer an inbox challenge
+pub const INBOX_CHALLENGE_TTL_SECS: u64 = 60;
+
+/// Maximum outstanding inbox challenges
+const MAX_INBOX_CHALLENGES: usize = 1024;
+
+/// Domain separator for inbox retrieval signatures
+const INBOX_CHALLENGE_DOMAIN: &[u8] = b"myriadmesh-dht-inbox-take-v1";
+
+/// Message held for an offline recipient
+///
+/// The payload is end-to-end encrypted by the sender; storage nodes only
+/// see its size and the recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboxMessage {
+    /// Encrypted message
+    pub payload: Vec<u8>,
+
+    /// Peer that handed the message over for storage
+    pub sender: NodeId,
+
+    /// When the message was stored (Unix timestamp)
+    pub stored_at: u64,
+
+    /// When the message is dropped if undelivered (Unix timestamp)
+    pub expires_at: u64,
+}
+
+/// Messages held for one recipient, oldest first
+#[derive(Debug, Default)]
+struct Inbox {
+    messages: VecDeque<InboxMessage>,
+    bytes: usize,
+}
+
+impl Inbox {
+    /// Drop expired messages, returning the bytes freed
+    fn prune_expired(
+        &mut self,
+        current_time: u64,
+        senders: &mut HashMap<NodeId, SenderUsage>,
+    ) -> usize {
+        let before = self.bytes;
+        let bytes = &mut self.bytes;
+        self.messages.retain(|message| {
+            let live = message.expires_at > current_time;
+            if !live {
+                *bytes -= message.payload.len();
+                release_sender(senders, &message.sender, message.payload.len());
+            }
+            live
+        });
+        before - self.bytes
+    }
+}
+
+/// Inbox messages held from one sender
+#[derive(Debug, Default, Clone, Copy)]
+struct SenderUsage {
+    messages: usize,
+    bytes: usize,
+}
+
+/// Forget one message from `sender`
+fn release_sender(senders: &mut HashMap<NodeId, SenderUsage>, sender: &NodeId, bytes: usize) {
+    if let Some(usage) = senders.get_mut(sender) {
+        usage.messages -= 1;
+        usage.bytes -= bytes;
+        if usage.messages == 0 {
+            senders.remove(sender);
+        }
+    }
+}
+
+/// Nonce a recipient must sign to empty its inbox
+#[derive(Debug, Clone, Copy)]
+struct InboxChallenge {
+    nonce: [u8; 32],
+    expires_at: u64,
+}
+
+/// Bytes a recipient signs to answer an inbox challenge
+///
+/// SECURITY: Binds the signature to the recipient and a single-use nonce, so
+/// it can't be replayed against another storage node's challenge or inbox.
+pub fn inbox_challenge_message(recipient: &NodeId, nonce: &[u8; 32]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(INBOX_CHALLENGE_DOMAIN.len() + 64 + 32);
+    message.extend_from_slice(INBOX_CHALLENGE_DOMAIN);
+    message.extend_from_slice(recipient.as_bytes());
+    message.extend_from_slice(nonce);
+    message
+}
+
+/// Value cached along a FIND_VALUE lookup path
+///
+/// Cached copies are not authoritative: they keep the publisher's original
+/// signature so they can be served on, but never count against publisher
+/// quotas and are pruned first under pressure.
+#[derive(Debug, Clone)]
+struct CachedValue {
+    entry: StorageEntry,
+    expires_at: u64,
+}
+
 /// Per-node storage tracking
 #[derive(Debug, Clone)]
 struct NodeQuota {
@@ -141,6 +322,24 @@ pub struct DhtStorage {
 
     /// SECURITY M2: Maximum bytes per node
     max_bytes_per_node: usize,
+
+    /// Values cached from lookups, kept apart from authoritative entries
+    cache: HashMap<[u8; 32], CachedValue>,
+
+    /// Bytes used by cached values
+    cache_size: usize,
+
+    /// Store-and-forward inboxes for offline recipients
+    inboxes: HashMap<NodeId, Inbox>,
+
+    /// Bytes used by inbox messages
+    inbox_size: usize,
+
+    /// Inbox messages held per sender
+    inbox_senders: HashMap<NodeId, SenderUsage>,
+
+    /// Outstanding inbox retrieval challenges by recipient
+    inbox_challenges: HashMap<NodeId, InboxChallenge>,
 }
 
 /// SECURITY M2: Default maximum keys per node (10% of total)
@@ -160,6 +359,12 @@ impl DhtStorage {
             node_quotas: HashMap::new(),
             max_keys_per_node: DEFAULT_MAX_KEYS_PER_NODE,
             max_bytes_per_node: DEFAULT_MAX_BYTES_PER_NODE,
+            cache: HashMap::new(),
+            cache_size: 0,
+            inboxes: HashMap::new(),
+            inbox_size: 0,
+            inbox_senders: HashMap::new(),
+            inbox_challenges: HashMap::new(),
         }
     }
 
@@ -173,6 +378,12 @@ impl DhtStorage {
             node_quotas: HashMap::new(),
             max_keys_per_node: max_keys / 10,  // 10% per node
             max_bytes_per_node: max_size / 10, // 10% per node
+            cache: HashMap::new(),
+            cache_size: 0,
+            inboxes: HashMap::new(),
+            inbox_size: 0,
+            inbox_senders: HashMap::new(),
+            inbox_challenges: HashMap::new(),
         }
     }
 
@@ -192,6 +403,12 @@ impl DhtStorage {
             node_quotas: HashMap::new(),
             max_keys_per_node,
             max_bytes_per_node,
+            cache: HashMap::new(),
+            cache_size: 0,
+            inboxes: HashMap::new(),
+            inbox_size: 0,
+            inbox_senders: HashMap::new(),
+            inbox_challenges: HashMap::new(),
         }
     }
 
@@ -273,8 +490,418 @@ impl DhtStorage {
     }
 
     /// Check if storage has capacity for a value
+    ///
+    /// Cached values and inbox messages share the global limits with
+    /// authoritative entries.
     fn has_capacity(&self, value_size: usize) -> bool {
-        self.key_count() < self.max_keys && (self.current_size + value_size) <= self.max_size
+        self.key_count() + self.cache.len() < self.max_keys && self.has_byte_capacity(value_size)
+    }
+
+    fn has_byte_capacity(&self, value_size: usize) -> bool {
+        self.current_size + self.cache_size + self.inbox_size + value_size <= self.max_size
+    }
+
+    /// Hold a message for an offline recipient
+    ///
+    /// Inboxes live on the nodes closest to the recipient's ID, where the
+    /// recipient looks for them when it comes back online, so the message is
+    /// refused with `NotResponsible` unless `routing_table` puts this node
+    /// among the `K` closest. The TTL is capped at `MAX_INBOX_TTL_SECS`;
+    /// each recipient is limited to `MAX_INBOX_MESSAGES` messages and
+    /// `MAX_INBOX_BYTES` bytes so one recipient can't be used to fill the
+    /// store, and each `sender` (the peer handing the message over) to
+    /// `MAX_INBOX_MESSAGES_PER_SENDER` and `MAX_INBOX_BYTES_PER_SENDER`
+    /// across all inboxes so one sender can't fill many of them.
+    pub fn put_inbox(
+        &mut self,
+        routing_table: &RoutingTable,
+        recipient: NodeId,
+        sender: NodeId,
+        encrypted_msg: Vec<u8>,
+        ttl_secs: u64,
+    ) -> Result<()> {
+        if !routing_table.is_among_closest(&recipient, K) {
+            return Err(DhtError::NotResponsible);
+        }
+        self.put_inbox_at(recipient, sender, encrypted_msg, ttl_secs, now())
+    }
+
+    fn put_inbox_at(
+        &mut self,
+        recipient: NodeId,
+        sender: NodeId,
+        encrypted_msg: Vec<u8>,
+        ttl_secs: u64,
+        current_time: u64,
+    ) -> Result<()> {
+        if encrypted_msg.len() > MAX_VALUE_SIZE {
+            return Err(DhtError::ValueTooLarge {
+                size: encrypted_msg.len(),
+                max: MAX_VALUE_SIZE,
+            });
+        }
+
+        self.prune_expired_inboxes(current_time);
+
+        let (count, bytes) = self
+            .inboxes
+            .get(&recipient)
+            .map(|inbox| (inbox.messages.len(), inbox.bytes))
+            .unwrap_or((0, 0));
+        if count >= MAX_INBOX_MESSAGES || bytes + encrypted_msg.len() > MAX_INBOX_BYTES {
+            return Err(DhtError::InboxFull {
+                messages: count,
+                bytes,
+            });
+        }
+
+        let usage = self.inbox_senders.get(&sender).copied().unwrap_or_default();
+        if usage.messages >= MAX_INBOX_MESSAGES_PER_SENDER
+            || usage.bytes + encrypted_msg.len() > MAX_INBOX_BYTES_PER_SENDER
+        {
+            return Err(DhtError::InboxSenderQuotaExceeded {
+                messages: usage.messages,
+                bytes: usage.bytes,
+            });
+        }
+
+        if !self.has_byte_capacity(encrypted_msg.len()) {
+            self.prune_expired_cache(current_time);
+            while !self.has_byte_capacity(encrypted_msg.len()) {
+                let key = self
+                    .cache
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.expires_at)
+                    .map(|(key, _)| *key);
+                match key {
+                    Some(key) => self.remove_cached(&key),
+                    None => return Err(DhtError::StorageFull { max: self.max_size }),
+                }
+            }
+        }
+
+        let usage = self.inbox_senders.entry(sender).or_default();
+        usage.messages += 1;
+        usage.bytes += encrypted_msg.len();
+
+        let inbox = self.inboxes.entry(recipient).or_default();
+        inbox.bytes += encrypted_msg.len();
+        self.inbox_size += encrypted_msg.len();
+        inbox.messages.push_back(InboxMessage {
+            payload: encrypted_msg,
+            sender,
+            stored_at: current_time,
+            expires_at: current_time + ttl_secs.min(MAX_INBOX_TTL_SECS),
+        });
+
+        Ok(())
+    }
+
+    /// Issue a single-use nonce `recipient` must sign to take its inbox
+    ///
+    /// A new challenge replaces any outstanding one for the same recipient.
+    /// The recipient signs [`inbox_challenge_message`] with the Ed25519 key
+    /// its node ID is derived from and passes the signature to
+    /// [`Self::take_inbox`] within `INBOX_CHALLENGE_TTL_SECS`.
+    pub fn inbox_challenge(&mut self, recipient: NodeId) -> [u8; 32] {
+        self.inbox_challenge_at(recipient, now())
+    }
+
+    fn inbox_challenge_at(&mut self, recipient: NodeId, current_time: u64) -> [u8; 32] {
+        self.inbox_challenges
+            .retain(|_, challenge| challenge.expires_at > current_time);
+        if self.inbox_challenges.len() >= MAX_INBOX_CHALLENGES
+            && !self.inbox_challenges.contains_key(&recipient)
+        {
+            let oldest = self
+                .inbox_challenges
+                .iter()
+                .min_by_key(|(_, challenge)| challenge.expires_at)
+                .map(|(id, _)| *id);
+            if let Some(oldest) = oldest {
+                self.inbox_challenges.remove(&oldest);
+            }
+        }
+
+        let mut nonce = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        self.inbox_challenges.insert(
+            recipient,
+            InboxChallenge {
+                nonce,
+                expires_at: current_time + INBOX_CHALLENGE_TTL_SECS,
+            },
+        );
+        nonce
+    }
+
+    /// Remove and return every unexpired message held for `recipient`,
+    /// oldest first
+    ///
+    /// SECURITY: Only the recipient may empty its inbox. `public_key` must
+    /// be the Ed25519 key the recipient's node ID is derived from, and
+    /// `signature` must sign [`inbox_challenge_message`] for the nonce from
+    /// [`Self::inbox_challenge`]. The challenge is consumed by the attempt,
+    /// whether or not it succeeds.
+    pub fn take_inbox(
+        &mut self,
+        recipient: &NodeId,
+        public_key: &[u8; 32],
+        signature: &[u8; 64],
+    ) -> Result<Vec<InboxMessage>> {
+        self.take_inbox_at(recipient, public_key, signature, now())
+    }
+
+    fn take_inbox_at(
+        &mut self,
+        recipient: &NodeId,
+        public_key: &[u8; 32],
+        signature: &[u8; 64],
+        current_time: u64,
+    ) -> Result<Vec<InboxMessage>> {
+        use blake2::{Blake2b512, Digest};
+        use sodiumoxide::crypto::sign::ed25519;
+
+        let challenge = self
+            .inbox_challenges
+            .remove(recipient)
+            .filter(|challenge| challenge.expires_at > current_time)
+            .ok_or(DhtError::InboxChallengeMissing)?;
+
+        // NodeID = BLAKE2b-512(Ed25519_PublicKey)
+        let derived_id = Blake2b512::digest(public_key);
+        if recipient.as_bytes()[..] != derived_id[..] {
+            return Err(DhtError::InvalidPublicKey);
+        }
+        let public_key =
+            ed25519::PublicKey::from_slice(public_key).ok_or(DhtError::InvalidPublicKey)?;
+        let signature =
+            ed25519::Signature::from_bytes(signature).map_err(|_| DhtError::InvalidSignature)?;
+        let message = inbox_challenge_message(recipient, &challenge.nonce);
+        if !ed25519::verify_detached(&signature, &message, &public_key) {
+            return Err(DhtError::InvalidSignature);
+        }
+
+        Ok(self.remove_inbox(recipient, current_time))
+    }
+
+    /// Remove `recipient`'s inbox, returning its unexpired messages
+    fn remove_inbox(&mut self, recipient: &NodeId, current_time: u64) -> Vec<InboxMessage> {
+        match self.inboxes.remove(recipient) {
+            Some(inbox) => {
+                self.inbox_size -= inbox.bytes;
+                for message in &inbox.messages {
+                    release_sender(
+                        &mut self.inbox_senders,
+                        &message.sender,
+                        message.payload.len(),
+                    );
+                }
+                inbox
+                    .messages
+                    .into_iter()
+                    .filter(|message| message.expires_at > current_time)
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Number of messages held for `recipient`, including any that have
+    /// expired but not yet been pruned
+    pub fn inbox_len(&self, recipient: &NodeId) -> usize {
+        self.inboxes
+            .get(recipient)
+            .map_or(0, |inbox| inbox.messages.len())
+    }
+
+    /// Total bytes held in inboxes
+    pub fn inbox_size(&self) -> usize {
+        self.inbox_size
+    }
+
+    /// Drop expired inbox messages, returning how many were removed
+    fn prune_expired_inboxes(&mut self, current_time: u64) -> usize {
+        let mut removed = 0;
+        let inbox_size = &mut self.inbox_size;
+        let senders = &mut self.inbox_senders;
+        self.inboxes.retain(|_, inbox| {
+            let before = inbox.messages.len();
+            *inbox_size -= inbox.prune_expired(current_time, senders);
+            removed += before - inbox.messages.len();
+            !inbox.messages.is_empty()
+        });
+        removed
+    }
+
+    /// Get number of cached (non-authoritative) values
+    pub fn cached_count(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Cache a value seen during a FIND_VALUE lookup
+    ///
+    /// Kademlia caches found values at the closest node on the lookup path
+    /// that missed, so later lookups for popular keys terminate sooner.
+    /// SECURITY H7: The entry must carry the publisher's original signature,
+    /// which is verified before the copy is accepted and served unchanged.
+    /// The copy lives for at most `MAX_CACHED_VALUE_TTL_SECS` and never
+    /// outlives the signed expiry. Caching never displaces authoritative
+    /// entries; it only evicts other cached values.
+    pub fn cache_value(&mut self, entry: StorageEntry, ttl_secs: u64) -> Result<()> {
+        self.cache_value_at(entry, ttl_secs, now())
+    }
+
+    fn cache_value_at(
+        &mut self,
+        mut entry: StorageEntry,
+        ttl_secs: u64,
+        current_time: u64,
+    ) -> Result<()> {
+        if entry.value.len() > MAX_VALUE_SIZE {
+            return Err(DhtError::ValueTooLarge {
+                size: entry.value.len(),
+                max: MAX_VALUE_SIZE,
+            });
+        }
+
+        entry.verify_signature()?;
+
+        // The authoritative copy is always preferred, and expired values
+        // aren't worth holding
+        if self.entries.contains_key(&entry.key) || entry.expires_at <= current_time {
+            return Ok(());
+        }
+
+        let key = entry.key;
+        let size = entry.value.len();
+        self.remove_cached(&key);
+        self.prune_expired_cache(current_time);
+        if !self.has_capacity(size) && !self.evict_cached_for(size) {
+            return Err(DhtError::StorageFull { max: self.max_size });
+        }
+
+        entry.stored_at = current_time;
+        entry.original_publisher = false;
+        let expires_at =
+            (current_time + ttl_secs.min(MAX_CACHED_VALUE_TTL_SECS)).min(entry.expires_at);
+        self.cache_size += size;
+        self.cache.insert(key, CachedValue { entry, expires_at });
+
+        Ok(())
+    }
+
+    /// Retrieve a cached value, with its publisher's signature
+    pub fn get_cached(&self, key: &[u8; 32]) -> Option<&StorageEntry> {
+        self.cache
+            .get(key)
+            .filter(|cached| cached.expires_at > now())
+            .map(|cached| &cached.entry)
+    }
+
+    fn remove_cached(&mut self, key: &[u8; 32]) {
+        if let Some(cached) = self.cache.remove(key) {
+            self.cache_size -= cached.entry.value.len();
+        }
+    }
+
+    fn prune_expired_cache(&mut self, current_time: u64) {
+        let cache_size = &mut self.cache_size;
+        self.cache.retain(|_, cached| {
+            let live = cached.expires_at > current_time;
+            if !live {
+                *cache_size -= cached.entry.value.len();
+            }
+            live
+        });
+    }
+
+    /// Evict cached values, soonest-expiring first, until `value_size` fits
+    fn evict_cached_for(&mut self, value_size: usize) -> bool {
+        while !self.has_capacity(value_size) {
+            let key = self
+                .cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.expires_at)
+                .map(|(key, _)| *key);
+
+            match key {
+                Some(key) => self.remove_cached(&key),
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    /// SECURITY M2: How far a publisher's usage is over its fair share of storage
+    ///
+    /// The fair share is the global limit split evenly across `publishers`.
+    /// A result above 1.0 means the publisher holds more than its share.
+    fn fair_share_ratio(&self, key_count: usize, bytes_used: usize, publishers: usize) -> f64 {
+        let publishers = publishers.max(1) as f64;
+        let fair_keys = (self.max_keys as f64 / publishers).max(1.0);
+        let fair_bytes = (self.max_size as f64 / publishers).max(1.0);
+        (key_count as f64 / fair_keys).max(bytes_used as f64 / fair_bytes)
+    }
+
+    /// SECURITY M2: Make room for a store by evicting from over-share publishers
+    ///
+    /// Under global pressure, entries are evicted from whichever publisher is
+    /// most over its fair share, so a flood of keys from one publisher cannot
+    /// push out honest data. The incoming publisher may only trigger eviction
+    /// while it would itself stay within its fair share. Values we published
+    /// ourselves are evicted last, and soonest-expiring values first.
+    fn evict_for(
+        &mut self,
+        publisher_node_id: &[u8; 64],
+        value_size: usize,
+        is_update: bool,
+    ) -> bool {
+        while !self.has_capacity(value_size) {
+            let publishers = self.node_quotas.len()
+                + usize::from(!self.node_quotas.contains_key(publisher_node_id));
+
+            let (keys, bytes) = self.get_node_usage(publisher_node_id);
+            let projected_keys = keys + usize::from(!is_update);
+            let projected_bytes = bytes + value_size;
+            if self.fair_share_ratio(projected_keys, projected_bytes, publishers) > 1.0 {
+                return false;
+            }
+
+            let victim = self
+                .node_quotas
+                .iter()
+                .filter(|(node_id, _)| *node_id != publisher_node_id)
+                .map(|(node_id, quota)| {
+                    let ratio =
+                        self.fair_share_ratio(quota.key_count, quota.bytes_used, publishers);
+                    (*node_id, ratio)
+                })
+                .filter(|(_, ratio)| *ratio > 1.0)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(node_id, _)| node_id);
+
+            let Some(victim) = victim else {
+                return false;
+            };
+
+            let key = self
+                .entries
+                .values()
+                .filter(|entry| entry.publisher_node_id == victim)
+                .min_by_key(|entry| (entry.original_publisher, entry.expires_at))
+                .map(|entry| entry.key);
+
+            match key {
+                Some(key) => {
+                    self.remove(&key);
+                }
+                None => return false,
+            }
+        }
+
+        true
     }
 
     /// Store a value
@@ -288,6 +915,81 @@ impl DhtStorage {
         publisher_public_key: [u8; 32],
         publisher_node_id: [u8; 64],
         signature: [u8; 64],
+    ) -> Result<()> {
+        self.store_at(
+            key,
+            value,
+            ttl_secs,
+            publisher_public_key,
+            publisher_node_id,
+            signature,
+            now(),
+        )
+    }
+
+    /// Store a value as of `current_time` (Unix seconds)
+    ///
+    /// The entry expires at `current_time + ttl_secs`, which is the
+    /// expiry the publisher must have signed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn store_at(
+        &mut self,
+        key: [u8; 32],
+        value: Vec<u8>,
+        ttl_secs: u64,
+        publisher_public_key: [u8; 32],
+        publisher_node_id: [u8; 64],
+        signature: [u8; 64],
+        current_time: u64,
+    ) -> Result<()> {
+        self.store_entry(
+            key,
+            value,
+            ttl_secs,
+            publisher_public_key,
+            publisher_node_id,
+            signature,
+            false,
+            current_time,
+        )
+    }
+
+    /// Store a value published by the local node
+    ///
+    /// Marks the entry as originally published here so that it is returned by
+    /// `entries_needing_republish` once it ages past the republish interval.
+    pub fn store_original(
+        &mut self,
+        key: [u8; 32],
+        value: Vec<u8>,
+        ttl_secs: u64,
+        publisher_public_key: [u8; 32],
+        publisher_node_id: [u8; 64],
+        signature: [u8; 64],
+    ) -> Result<()> {
+        self.store_entry(
+            key,
+            value,
+            ttl_secs,
+            publisher_public_key,
+            publisher_node_id,
+            signature,
+            true,
+            now(),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn store_entry(
+        &mut self,
+        key: [u8; 32],
+        value: Vec<u8>,
+        ttl_secs: u64,
+        publisher_public_key: [u8; 32],
+        publisher_node_id: [u8; 64],
+        signature: [u8; 64],
+        original_publisher: bool,
+        current_time: u64,
     ) -> Result<()> {
         // Check value size
         if value.len() > MAX_VALUE_SIZE {
@@ -297,17 +999,20 @@ impl DhtStorage {
             });
         }
 
-        let expires_at = now() + ttl_secs;
+        let stored_at = current_time;
+        let expires_at = stored_at + ttl_secs;
 
         // Create entry for verification
-        let entry = StorageEntry {
+        let mut entry = StorageEntry {
             key,
             value: value.clone(),
-            stored_at: now(),
+            stored_at,
             expires_at,
             publisher_public_key,
             publisher_node_id,
             signature,
+            original_publisher,
+            last_published: stored_at,
         };
 
         // SECURITY H7: Verify signature before storing
@@ -321,6 +1026,14 @@ impl DhtStorage {
             None
         };
 
+        // A value we published stays ours when the same publisher's update
+        // arrives back from the network
+        if let Some(ref old) = old_entry {
+            if old.original_publisher && old.publisher_node_id == publisher_node_id {
+                entry.original_publisher = true;
+            }
+        }
+
         // SECURITY M2: Check per-node quota
         if !self.node_has_quota(&publisher_node_id, value.len(), is_update) {
             // Restore old entry if this was an update
@@ -347,10 +1060,14 @@ impl DhtStorage {
 
         // Check global capacity
         if !self.has_capacity(value.len()) {
-            // Try to make space by removing expired entries
+            // Try to make space by dropping cached copies, then expired entries
+            self.evict_cached_for(value.len());
             self.cleanup_expired();
 
-            if !self.has_capacity(value.len()) {
+            // Then by evicting from publishers over their fair share
+            if !self.has_capacity(value.len())
+                && !self.evict_for(&publisher_node_id, value.len(), is_update)
+            {
                 // Restore old entry if this was an update
                 if let Some(old) = old_entry {
                     self.current_size += old.value.len();
@@ -361,6 +1078,9 @@ impl DhtStorage {
             }
         }
 
+        // An authoritative copy supersedes any cached one
+        self.remove_cached(&key);
+
         // SECURITY M2: Update node quota
         let key_delta = if is_update { 0 } else { 1 };
         self.update_node_quota(publisher_node_id, key_delta, value.len() as i64);
@@ -383,6 +1103,26 @@ impl DhtStorage {
         })
     }
 
+    /// Entries whose key is within XOR distance `max_distance` of `target`
+    ///
+    /// Results are sorted closest first and capped at
+    /// [`MAX_RANGE_QUERY_RESULTS`], so a wide range cannot produce an
+    /// unbounded response. Cached values are not included.
+    pub fn range_query(&self, target: &[u8; 32], max_distance: &[u8; 32]) -> Vec<&StorageEntry> {
+        let mut in_range: Vec<_> = self
+            .entries
+            .values()
+            .filter(|entry| !entry.is_expired())
+            .map(|entry| (key_distance(&entry.key, target), entry))
+            .filter(|(distance, _)| distance <= max_distance)
+            .collect();
+
+        // Keys are unique, so are their distances to the target
+        in_range.sort_unstable_by_key(|(distance, _)| *distance);
+        in_range.truncate(MAX_RANGE_QUERY_RESULTS);
+        in_range.into_iter().map(|(_, entry)| entry).collect()
+    }
+
     /// Remove a value
     /// SECURITY M2: Updates node quotas
     pub fn remove(&mut self, key: &[u8; 32]) -> Option<StorageEntry> {
@@ -399,8 +1139,12 @@ impl DhtStorage {
     /// Cleanup expired entries
     /// SECURITY M2: Updates node quotas for removed entries
     pub fn cleanup_expired(&mut self) -> usize {
-        let current_time = now();
+        self.expire_stale(now())
+    }
 
+    /// Remove entries that have expired as of `current_time` (Unix timestamp)
+    /// SECURITY M2: Updates node quotas for removed entries
+    pub fn expire_stale(&mut self, current_time: u64) -> usize {
         // Collect expired entries first to avoid borrow checker issues
         let expired_entries: Vec<_> = self
             .entries
@@ -416,6 +1160,8 @@ impl DhtStorage {
             // SECURITY M2: Update node quota
             self.update_node_quota(*publisher_node_id, -1, -(*value_len as i64));
         }
+        self.prune_expired_cache(current_time);
+        self.prune_expired_inboxes(current_time);
 
         expired_entries.len()
     }
@@ -438,12 +1184,40 @@ impl DhtStorage {
             .collect()
     }
 
+    /// Get keys the local node should re-announce
+    ///
+    /// Returns unexpired values originally published by this node that were
+    /// last published at least `interval_secs` ago. Values stored on behalf of
+    /// other publishers are not included, since their signature covers
+    /// `expires_at` and only the publisher can extend it.
+    pub fn entries_needing_republish(&self, interval_secs: u64) -> Vec<[u8; 32]> {
+        self.entries_needing_republish_at(now(), interval_secs)
+    }
+
+    fn entries_needing_republish_at(&self, current_time: u64, interval_secs: u64) -> Vec<[u8; 32]> {
+        self.entries
+            .values()
+            .filter(|entry| {
+                entry.original_publisher
+                    && entry.expires_at > current_time
+                    && current_time.saturating_sub(entry.last_published) >= interval_secs
+            })
+            .map(|entry| entry.key)
+            .collect()
+    }
+
     /// Clear all storage
     /// SECURITY M2: Clears node quotas
     pub fn clear(&mut self) {
         self.entries.clear();
         self.current_size = 0;
         self.node_quotas.clear();
+        self.cache.clear();
+        self.cache_size = 0;
+        self.inboxes.clear();
+        self.inbox_size = 0;
+        self.inbox_senders.clear();
+        self.inbox_challenges.clear();
     }
 }
 
@@ -735,6 +1509,8 @@ mod tests {
             publisher_public_key: pk_bytes,
             publisher_node_id: node_id,
             signature: [0u8; 64],
+            original_publisher: false,
+            last_published: now(),
         };
 
         let ttl = entry.ttl_remaining();
@@ -1123,4 +1899,663 @@ mod tests {
         assert_eq!(keys, 1);
         assert_eq!(bytes, value2.len());
     }
+
+    #[test]
+    fn test_expire_stale() {
+        let mut storage = DhtStorage::new();
+
+        let short_key = [1u8; 32];
+        let short_value = b"short".to_vec();
+        let (pk1, node_id1, sig1) = create_signed_value(short_key, short_value.clone(), 60);
+        storage
+            .store(short_key, short_value, 60, pk1, node_id1, sig1)
+            .unwrap();
+
+        let long_key = [2u8; 32];
+        let long_value = b"long".to_vec();
+        let (pk2, node_id2, sig2) = create_signed_value(long_key, long_value.clone(), 7200);
+        storage
+            .store(long_key, long_value, 7200, pk2, node_id2, sig2)
+            .unwrap();
+
+        // Nothing has expired yet
+        assert_eq!(storage.expire_stale(now()), 0);
+        assert_eq!(storage.key_count(), 2);
+
+        // An hour later only the short-lived entry is gone, and its quota released
+        assert_eq!(storage.expire_stale(now() + 3600), 1);
+        assert_eq!(storage.key_count(), 1);
+        assert!(storage.get(&long_key).is_some());
+        assert_eq!(storage.get_node_usage(&node_id1), (0, 0));
+        assert_eq!(storage.size(), 4);
+    }
+
+    #[test]
+    fn test_entries_needing_republish() {
+        let mut storage = DhtStorage::new();
+        let current = now();
+
+        // Three values we published, plus one stored for someone else
+        let mut keys = Vec::new();
+        for i in 0..4u8 {
+            let key = [i + 1; 32];
+            let value = vec![i; 8];
+            let (pk, node_id, sig) = create_signed_value(key, value.clone(), 86400);
+            if i < 3 {
+                storage
+                    .store_original(key, value, 86400, pk, node_id, sig)
+                    .unwrap();
+            } else {
+                storage.store(key, value, 86400, pk, node_id, sig).unwrap();
+            }
+            keys.push(key);
+        }
+
+        // Age the entries: 2h, 30min, 0 and 2h (not ours)
+        for (key, age) in keys.iter().zip([7200u64, 1800, 0, 7200]) {
+            storage.entries.get_mut(key).unwrap().last_published = current - age;
+        }
+
+        let due = storage.entries_needing_republish_at(current, 3600);
+        assert_eq!(due, vec![keys[0]]);
+
+        let mut due = storage.entries_needing_republish_at(current, 1800);
+        due.sort();
+        assert_eq!(due, vec![keys[0], keys[1]]);
+
+        // Expired values are never republished
+        assert!(storage
+            .entries_needing_republish_at(current + 86400, 3600)
+            .is_empty());
+
+        assert!(!storage.get(&keys[3]).unwrap().original_publisher);
+        assert!(storage.get(&keys[0]).unwrap().original_publisher);
+    }
+
+    /// Helper to create a publisher identity
+    /// Returns (publisher_public_key, publisher_node_id, secret_key)
+    fn create_publisher() -> ([u8; 32], [u8; 64], ed25519::SecretKey) {
+        use blake2::{Blake2b512, Digest};
+
+        init_sodiumoxide();
+        let (pk, sk) = ed25519::gen_keypair();
+        let mut pk_bytes = [0u8; 32];
+        pk_bytes.copy_from_slice(&pk[..]);
+
+        let mut hasher = Blake2b512::new();
+        hasher.update(pk_bytes);
+        let mut node_id = [0u8; 64];
+        node_id.copy_from_slice(&hasher.finalize());
+
+        (pk_bytes, node_id, sk)
+    }
+
+    #[test]
+    fn test_single_publisher_cannot_fill_store() {
+        // SECURITY TEST M2: Default quotas cap one publisher at 10% of the store
+        let mut storage = DhtStorage::with_limits(10_000, 100);
+        let current = now();
+        let (pk, node_id, sk) = create_publisher();
+        let value = vec![0u8; 50];
+
+        let mut stored = 0;
+        for i in 0..100u8 {
+            let key = [i; 32];
+            let sig = sign_value(&key, &value, current + 3600, &sk);
+            match storage.store_at(key, value.clone(), 3600, pk, node_id, sig, current) {
+                Ok(()) => stored += 1,
+                Err(DhtError::NodeQuotaExceeded { .. }) => break,
+                Err(e) => panic!("unexpected error: {e}"),
+            }
+        }
+
+        assert_eq!(stored, 10);
+        let (keys, bytes) = storage.get_node_usage(&node_id);
+        assert!(keys <= 10 && bytes <= 1000);
+        assert_eq!(storage.key_count(), 10);
+    }
+
+    #[test]
+    fn test_flood_evicts_over_share_publisher() {
+        // SECURITY TEST M2: Honest data survives a flood that fills the store
+        // Per-node quotas are loose so the attacker can fill the store
+        let mut storage = DhtStorage::with_quotas(10_000, 10, 10, 10_000);
+        let current = now();
+        let value = b"value".to_vec();
+
+        let (honest_pk, honest_id, honest_sk) = create_publisher();
+        let honest_keys: Vec<[u8; 32]> = (0..2u8).map(|i| [i; 32]).collect();
+        for key in &honest_keys {
+            let sig = sign_value(key, &value, current + 3600, &honest_sk);
+            storage
+                .store_at(
+                    *key,
+                    value.clone(),
+                    3600,
+                    honest_pk,
+                    honest_id,
+                    sig,
+                    current,
+                )
+                .unwrap();
+        }
+
+        // Attacker fills every remaining slot
+        let (attacker_pk, attacker_id, attacker_sk) = create_publisher();
+        for i in 0..8u8 {
+            let key = [100 + i; 32];
+            let sig = sign_value(&key, &value, current + 3600, &attacker_sk);
+            storage
+                .store_at(
+                    key,
+                    value.clone(),
+                    3600,
+                    attacker_pk,
+                    attacker_id,
+                    sig,
+                    current,
+                )
+                .unwrap();
+        }
+        assert_eq!(storage.key_count(), 10);
+
+        // A new honest publisher still gets in, at the attacker's expense
+        let (new_pk, new_id, new_sk) = create_publisher();
+        let new_key = [50u8; 32];
+        let sig = sign_value(&new_key, &value, current + 3600, &new_sk);
+        storage
+            .store_at(new_key, value.clone(), 3600, new_pk, new_id, sig, current)
+            .unwrap();
+        assert_eq!(storage.key_count(), 10);
+        assert_eq!(storage.get_node_usage(&attacker_id).0, 7);
+
+        // The attacker, being over its share, cannot evict anyone to flood further
+        let key = [200u8; 32];
+        let sig = sign_value(&key, &value, current + 3600, &attacker_sk);
+        let result = storage.store_at(
+            key,
+            value.clone(),
+            3600,
+            attacker_pk,
+            attacker_id,
+            sig,
+            current,
+        );
+        assert!(matches!(result, Err(DhtError::StorageFull { .. })));
+
+        for key in &honest_keys {
+            assert!(storage.get(key).is_some());
+        }
+        assert!(storage.get(&new_key).is_some());
+        assert_eq!(storage.get_node_usage(&honest_id).0, 2);
+    }
+
+    /// A signed entry for `key` as relayed by a FIND_VALUE response
+    fn relayed_entry(key: [u8; 32], value: &[u8], expires_at: u64) -> StorageEntry {
+        let (pk, _, sk) = create_publisher();
+        let sig = sign_value(&key, value, expires_at, &sk);
+        StorageEntry::from_signed(key, value.to_vec(), expires_at, pk, &sig).unwrap()
+    }
+
+    #[test]
+    fn test_cached_values_pruned_before_authoritative() {
+        let mut storage = DhtStorage::with_quotas(10_000, 4, 4, 10_000);
+        let value = b"value".to_vec();
+
+        // TTL is capped for cached copies
+        let current = now();
+        let entry = relayed_entry([1u8; 32], &value, current + 86400);
+        storage.cache_value_at(entry, 86400, current).unwrap();
+        assert_eq!(
+            storage.cache[&[1u8; 32]].expires_at,
+            current + MAX_CACHED_VALUE_TTL_SECS
+        );
+        let entry = relayed_entry([2u8; 32], &value, now() + 3600);
+        storage.cache_value(entry.clone(), 60).unwrap();
+        assert_eq!(storage.cached_count(), 2);
+        let cached = storage.get_cached(&[2u8; 32]).unwrap();
+        assert_eq!(cached.value, value);
+        assert_eq!(cached.signature, entry.signature);
+        assert_eq!(cached.publisher_public_key, entry.publisher_public_key);
+        assert!(storage.get(&[2u8; 32]).is_none());
+
+        // Authoritative stores fill the store, displacing cached copies
+        let (pk, node_id, sk) = create_publisher();
+        for i in 0..4u8 {
+            let key = [10 + i; 32];
+            let sig = sign_value(&key, &value, now() + 3600, &sk);
+            storage
+                .store(key, value.clone(), 3600, pk, node_id, sig)
+                .unwrap();
+        }
+        assert_eq!(storage.key_count(), 4);
+        assert_eq!(storage.cached_count(), 0);
+        assert_eq!(storage.size(), 4 * value.len());
+
+        // A cached copy can't displace authoritative data
+        let result = storage.cache_value(relayed_entry([3u8; 32], &value, now() + 3600), 60);
+        assert!(matches!(result, Err(DhtError::StorageFull { .. })));
+
+        // Caching a key we hold authoritatively is a no-op
+        storage
+            .cache_value(relayed_entry([10u8; 32], b"stale", now() + 3600), 60)
+            .unwrap();
+        assert_eq!(storage.cached_count(), 0);
+        assert_eq!(storage.get(&[10u8; 32]).unwrap().value, value);
+    }
+
+    #[test]
+    fn test_cached_values_keep_publisher_signature() {
+        let mut storage = DhtStorage::new();
+        let current = now();
+
+        // A tampered value is refused
+        let mut forged = relayed_entry([1u8; 32], b"genuine", current + 3600);
+        forged.value = b"forged".to_vec();
+        assert!(matches!(
+            storage.cache_value_at(forged, 600, current),
+            Err(DhtError::InvalidSignature)
+        ));
+
+        // As is a signed value relayed with a stretched expiry, under another
+        // publisher's key, or with a malformed signature
+        let genuine = relayed_entry([1u8; 32], b"genuine", current + 60);
+        let relay = |expires_at, pk, signature: &[u8]| {
+            StorageEntry::from_signed([1u8; 32], b"genuine".to_vec(), expires_at, pk, signature)
+        };
+        let (other_pk, _, _) = create_publisher();
+        let pk = genuine.publisher_public_key;
+        assert!(relay(current + 60, pk, &genuine.signature).is_ok());
+        assert!(relay(current + 3600, pk, &genuine.signature).is_err());
+        assert!(relay(current + 60, other_pk, &genuine.signature).is_err());
+        assert!(relay(current + 60, pk, &[0u8; 10]).is_err());
+        assert_eq!(storage.cached_count(), 0);
+
+        // A cached copy never outlives the publisher's signed expiry
+        let entry = relayed_entry([2u8; 32], b"genuine", current + 60);
+        storage.cache_value_at(entry.clone(), 600, current).unwrap();
+        assert_eq!(storage.cache[&[2u8; 32]].expires_at, current + 60);
+        assert_eq!(storage.cache[&[2u8; 32]].entry.expires_at, current + 60);
+        assert!(storage.cache[&[2u8; 32]].entry.verify_signature().is_ok());
+
+        // Expired values aren't cached at all
+        let stale = relayed_entry([3u8; 32], b"old", current);
+        storage.cache_value_at(stale, 600, current).unwrap();
+        assert!(!storage.cache.contains_key(&[3u8; 32]));
+    }
+
+    fn recipient(id: u8) -> NodeId {
+        NodeId::from_bytes([id; 64])
+    }
+
+    /// A recipient keypair and the node ID derived from it
+    fn inbox_owner() -> (NodeId, [u8; 32], ed25519::SecretKey) {
+        use blake2::{Blake2b512, Digest};
+
+        init_sodiumoxide();
+        let (pk, sk) = ed25519::gen_keypair();
+        let mut pk_bytes = [0u8; 32];
+        pk_bytes.copy_from_slice(&pk[..]);
+        let mut node_id = [0u8; 64];
+        node_id.copy_from_slice(&Blake2b512::digest(pk_bytes));
+        (NodeId::from_bytes(node_id), pk_bytes, sk)
+    }
+
+    /// Answer a fresh inbox challenge for `recipient`
+    fn sign_challenge(
+        storage: &mut DhtStorage,
+        recipient: &NodeId,
+        sk: &ed25519::SecretKey,
+    ) -> [u8; 64] {
+        let nonce = storage.inbox_challenge(*recipient);
+        let message = inbox_challenge_message(recipient, &nonce);
+        ed25519::sign_detached(&message, sk).to_bytes()
+    }
+
+    #[test]
+    fn test_inbox_store_and_take() {
+        let mut storage = DhtStorage::new();
+        let table = RoutingTable::new(recipient(0));
+        let (alice, alice_pk, alice_sk) = inbox_owner();
+        let bob = recipient(2);
+        let sender = recipient(9);
+
+        for payload in [&b"first"[..], b"second"] {
+            storage
+                .put_inbox(&table, alice, sender, payload.to_vec(), 3600)
+                .unwrap();
+        }
+        storage
+            .put_inbox(&table, bob, sender, b"for bob".to_vec(), 3600)
+            .unwrap();
+        assert_eq!(storage.inbox_len(&alice), 2);
+        assert_eq!(storage.inbox_size(), 5 + 6 + 7);
+
+        // Retrieval returns messages oldest first and empties the inbox
+        let signature = sign_challenge(&mut storage, &alice, &alice_sk);
+        let messages = storage.take_inbox(&alice, &alice_pk, &signature).unwrap();
+        let payloads: Vec<&[u8]> = messages.iter().map(|m| m.payload.as_slice()).collect();
+        assert_eq!(payloads, vec![&b"first"[..], &b"second"[..]]);
+        assert!(messages.iter().all(|m| m.sender == sender));
+        assert_eq!(storage.inbox_len(&alice), 0);
+        let signature = sign_challenge(&mut storage, &alice, &alice_sk);
+        assert!(storage
+            .take_inbox(&alice, &alice_pk, &signature)
+            .unwrap()
+            .is_empty());
+
+        // Other recipients are unaffected
+        assert_eq!(storage.inbox_len(&bob), 1);
+        assert_eq!(storage.inbox_size(), 7);
+    }
+
+    #[test]
+    fn test_inbox_take_requires_signed_challenge() {
+        let mut storage = DhtStorage::new();
+        let table = RoutingTable::new(recipient(0));
+        let (alice, alice_pk, alice_sk) = inbox_owner();
+        let (_, mallory_pk, mallory_sk) = inbox_owner();
+        storage
+            .put_inbox(&table, alice, recipient(9), b"secret".to_vec(), 3600)
+            .unwrap();
+
+        // No challenge issued
+        let nonce = [0u8; 32];
+        let message = inbox_challenge_message(&alice, &nonce);
+        let forged = ed25519::sign_detached(&message, &alice_sk).to_bytes();
+        assert!(matches!(
+            storage.take_inbox(&alice, &alice_pk, &forged),
+            Err(DhtError::InboxChallengeMissing)
+        ));
+
+        // Someone else's key doesn't match the recipient's ID
+        let signature = sign_challenge(&mut storage, &alice, &mallory_sk);
+        assert!(matches!(
+            storage.take_inbox(&alice, &mallory_pk, &signature),
+            Err(DhtError::InvalidPublicKey)
+        ));
+
+        // The right key must actually sign the nonce
+        let signature = sign_challenge(&mut storage, &alice, &mallory_sk);
+        assert!(matches!(
+            storage.take_inbox(&alice, &alice_pk, &signature),
+            Err(DhtError::InvalidSignature)
+        ));
+        assert_eq!(storage.inbox_len(&alice), 1);
+
+        // A valid answer works once; replaying it fails
+        let signature = sign_challenge(&mut storage, &alice, &alice_sk);
+        assert_eq!(
+            storage
+                .take_inbox(&alice, &alice_pk, &signature)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(matches!(
+            storage.take_inbox(&alice, &alice_pk, &signature),
+            Err(DhtError::InboxChallengeMissing)
+        ));
+
+        // Challenges expire
+        let current = now();
+        let nonce = storage.inbox_challenge_at(alice, current);
+        let message = inbox_challenge_message(&alice, &nonce);
+        let signature = ed25519::sign_detached(&message, &alice_sk).to_bytes();
+        assert!(matches!(
+            storage.take_inbox_at(
+                &alice,
+                &alice_pk,
+                &signature,
+                current + INBOX_CHALLENGE_TTL_SECS
+            ),
+            Err(DhtError::InboxChallengeMissing)
+        ));
+    }
+
+    #[test]
+    fn test_inbox_requires_closest_node() {
+        let mut storage = DhtStorage::new();
+        let mut table = RoutingTable::new(NodeId::from_bytes([0x80; 64]));
+        let target = recipient(0);
+
+        // K known nodes are all closer to the recipient than we are
+        for id in 1..=K as u8 {
+            let mut node = crate::NodeInfo::new(recipient(id));
+            node.compute_pow();
+            table.add_or_update(node).unwrap();
+        }
+        assert!(matches!(
+            storage.put_inbox(&table, target, recipient(0xff), b"msg".to_vec(), 3600),
+            Err(DhtError::NotResponsible)
+        ));
+        assert_eq!(storage.inbox_len(&target), 0);
+
+        // But we're responsible for recipients near our own ID
+        let near = NodeId::from_bytes([0x81; 64]);
+        storage
+            .put_inbox(&table, near, recipient(0xff), b"msg".to_vec(), 3600)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_inbox_ttl_expiry() {
+        let mut storage = DhtStorage::new();
+        let alice = recipient(1);
+        let sender = recipient(9);
+        let current = now();
+
+        storage
+            .put_inbox_at(alice, sender, b"short".to_vec(), 60, current)
+            .unwrap();
+        storage
+            .put_inbox_at(alice, sender, b"long".to_vec(), 86400 * 30, current)
+            .unwrap();
+
+        // TTL is capped
+        let expiries: Vec<u64> = storage.inboxes[&alice]
+            .messages
+            .iter()
+            .map(|m| m.expires_at)
+            .collect();
+        assert_eq!(expiries, vec![current + 60, current + MAX_INBOX_TTL_SECS]);
+
+        // Expired messages are not delivered
+        let messages = storage.remove_inbox(&alice, current + 61);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].payload, b"long".to_vec());
+
+        // And are pruned along with other expired data
+        storage
+            .put_inbox_at(alice, sender, b"short".to_vec(), 60, current)
+            .unwrap();
+        storage.expire_stale(current + 61);
+        assert_eq!(storage.inbox_len(&alice), 0);
+        assert_eq!(storage.inbox_size(), 0);
+        assert!(storage.inbox_senders.is_empty());
+    }
+
+    #[test]
+    fn test_inbox_limits() {
+        let mut storage = DhtStorage::new();
+        let table = RoutingTable::new(recipient(0));
+        let alice = recipient(1);
+
+        for i in 0..MAX_INBOX_MESSAGES {
+            let sender = recipient(100 + (i / MAX_INBOX_MESSAGES_PER_SENDER) as u8);
+            storage
+                .put_inbox(&table, alice, sender, vec![0u8; 8], 3600)
+                .unwrap();
+        }
+        let result = storage.put_inbox(&table, alice, recipient(99), vec![0u8; 8], 3600);
+        assert!(matches!(
+            result,
+            Err(DhtError::InboxFull {
+                messages: MAX_INBOX_MESSAGES,
+                ..
+            })
+        ));
+
+        // Byte limit per recipient
+        let bob = recipient(2);
+        let chunk = MAX_VALUE_SIZE;
+        for i in 0..MAX_INBOX_BYTES / chunk {
+            let sender = recipient(200 + (i * chunk / MAX_INBOX_BYTES_PER_SENDER) as u8);
+            storage
+                .put_inbox(&table, bob, sender, vec![0u8; chunk], 3600)
+                .unwrap();
+        }
+        assert!(matches!(
+            storage.put_inbox(&table, bob, recipient(99), vec![0u8; 1], 3600),
+            Err(DhtError::InboxFull { .. })
+        ));
+
+        // Oversized messages are rejected outright
+        assert!(matches!(
+            storage.put_inbox(
+                &table,
+                recipient(3),
+                recipient(99),
+                vec![0u8; MAX_VALUE_SIZE + 1],
+                3600
+            ),
+            Err(DhtError::ValueTooLarge { .. })
+        ));
+
+        // Inbox messages count against global storage
+        let mut small = DhtStorage::with_limits(100, 10);
+        small
+            .put_inbox(&table, alice, recipient(99), vec![0u8; 80], 3600)
+            .unwrap();
+        assert!(matches!(
+            small.put_inbox(&table, bob, recipient(99), vec![0u8; 30], 3600),
+            Err(DhtError::StorageFull { .. })
+        ));
+    }
+
+    #[test]
+    fn test_inbox_sender_limits() {
+        let mut storage = DhtStorage::new();
+        let table = RoutingTable::new(recipient(0));
+        let mallory = recipient(66);
+
+        // One sender can't fill many inboxes
+        for i in 0..MAX_INBOX_MESSAGES_PER_SENDER {
+            storage
+                .put_inbox(&table, recipient(i as u8), mallory, vec![0u8; 8], 3600)
+                .unwrap();
+        }
+        assert!(matches!(
+            storage.put_inbox(&table, recipient(200), mallory, vec![0u8; 8], 3600),
+            Err(DhtError::InboxSenderQuotaExceeded {
+                messages: MAX_INBOX_MESSAGES_PER_SENDER,
+                ..
+            })
+        ));
+
+        // Other senders are unaffected
+        storage
+            .put_inbox(&table, recipient(200), recipient(67), vec![0u8; 8], 3600)
+            .unwrap();
+
+        // Delivery frees the sender's quota
+        storage.remove_inbox(&recipient(0), now());
+        storage
+            .put_inbox(&table, recipient(200), mallory, vec![0u8; 8], 3600)
+            .unwrap();
+
+        // Byte limit per sender
+        let eve = recipient(68);
+        let chunk = MAX_VALUE_SIZE;
+        for i in 0..MAX_INBOX_BYTES_PER_SENDER / chunk {
+            storage
+                .put_inbox(
+                    &table,
+                    recipient(100 + i as u8),
+                    eve,
+                    vec![0u8; chunk],
+                    3600,
+                )
+                .unwrap();
+        }
+        assert!(matches!(
+            storage.put_inbox(&table, recipient(201), eve, vec![0u8; 1], 3600),
+            Err(DhtError::InboxSenderQuotaExceeded { .. })
+        ));
+    }
+
+    /// Store an entry under `key`, signed by a new publisher, as of `current`
+    fn store_signed(storage: &mut DhtStorage, key: [u8; 32], current: u64) {
+        let value = key[..2].to_vec();
+        let (pk, node_id, sk) = create_publisher();
+        let sig = sign_value(&key, &value, current + 3600, &sk);
+        storage
+            .store_at(key, value, 3600, pk, node_id, sig, current)
+            .unwrap();
+    }
+
+    fn synthetic_key(high: u8, low: u8) -> [u8; 32] {
+        let mut key = [0u8; 32];
+        key[0] = high;
+        key[1] = low;
+        key
+    }
+
+    #[test]
+    fn test_range_query_returns_only_in_range_keys() {
+        let mut storage = DhtStorage::new();
+        let current = now();
+        for high in 0..=255u8 {
+            store_signed(&mut storage, synthetic_key(high, 0), current);
+        }
+
+        // Within 0x0FFF.. of 0x40 is exactly the 0x40-0x4F prefix block
+        let target = synthetic_key(0x40, 0);
+        let mut max_distance = [0xFFu8; 32];
+        max_distance[0] = 0x0F;
+
+        let results = storage.range_query(&target, &max_distance);
+        let highs: Vec<u8> = results.iter().map(|entry| entry.key[0]).collect();
+        assert_eq!(highs, (0x40..=0x4F).collect::<Vec<u8>>());
+        for entry in &results {
+            assert!(key_distance(&entry.key, &target) <= max_distance);
+        }
+
+        // Closest first, not in prefix order
+        let target = synthetic_key(0x47, 0);
+        let results = storage.range_query(&target, &max_distance);
+        assert_eq!(results.len(), 16);
+        assert_eq!(results[0].key[0], 0x47);
+        assert_eq!(results[1].key[0], 0x46);
+
+        // Distance zero is an exact lookup
+        let results = storage.range_query(&synthetic_key(0x80, 0), &[0u8; 32]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].key, synthetic_key(0x80, 0));
+    }
+
+    #[test]
+    fn test_range_query_caps_results() {
+        let mut storage = DhtStorage::new();
+        let current = now();
+        for i in 0..(MAX_RANGE_QUERY_RESULTS + 44) {
+            store_signed(
+                &mut storage,
+                synthetic_key((i >> 8) as u8, i as u8),
+                current,
+            );
+        }
+
+        let target = [0u8; 32];
+        let results = storage.range_query(&target, &[0xFFu8; 32]);
+        assert_eq!(results.len(), MAX_RANGE_QUERY_RESULTS);
+
+        // The cap keeps the closest keys
+        let farthest = results
+            .iter()
+            .map(|entry| key_distance(&entry.key, &target))
+            .max()
+            .unwrap();
+        assert_eq!(farthest, synthetic_key(0, 255));
+    }
 }
@@ -3,9 +3,10 @@
 //! Implements privacy-preserving i2p destination sharing via signed capability tokens.
 //! Tokens are exchanged privately (NOT in public DHT) to authorize i2p communication.
 
+use blake2::{Blake2b512, Digest};
 use myriadmesh_crypto::identity::NodeIdentity;
 use myriadmesh_protocol::NodeId;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sodiumoxide::crypto::sign::ed25519;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -50,6 +51,45 @@ impl std::fmt::Display for I2pDestination {
     }
 }
 
+/// Domain separator for deriving a token audience from a channel peer key
+const AUDIENCE_DOMAIN: &[u8] = b"myriadmesh-i2p-token-audience-v1";
+
+/// Transport context a capability token is bound to
+///
+/// Typically derived from the public key of the secure channel the token was
+/// delivered over, so the token is only honoured on that channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenAudience([u8; 32]);
+
+impl TokenAudience {
+    /// Wrap raw audience bytes
+    pub fn new(bytes: [u8; 32]) -> Self {
+        TokenAudience(bytes)
+    }
+
+    /// Derive the audience for a channel from its peer's public key
+    pub fn from_peer_key(peer_public_key: &[u8]) -> Self {
+        let mut hasher = Blake2b512::new();
+        hasher.update(AUDIENCE_DOMAIN);
+        hasher.update(peer_public_key);
+        let digest = hasher.finalize();
+
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&digest[..32]);
+        TokenAudience(bytes)
+    }
+
+    /// Raw audience bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Constant-time comparison
+    fn matches(&self, other: &TokenAudience) -> bool {
+        sodiumoxide::utils::memcmp(&self.0, &other.0)
+    }
+}
+
 /// i2p Capability Token
 ///
 /// Allows authorized access to a node's i2p destination.
@@ -60,7 +100,10 @@ impl std::fmt::Display for I2pDestination {
 /// - Direct encrypted messages
 /// - QR codes (in-person)
 /// - Out-of-band secure channels
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Serialized with a version tag; [`Self::from_bytes`] also accepts the
+/// untagged layout of tokens issued before audience binding.
+#[derive(Debug, Clone)]
 pub struct I2pCapabilityToken {
     /// Who can use this token (recipient's clearnet NodeID)
     pub for_node: NodeId,
@@ -83,6 +126,12 @@ pub struct I2pCapabilityToken {
 
     /// Issuer's clearnet NodeID (for signature verification)
     pub issuer_node_id: NodeId,
+
+    /// Transport context the token is bound to (None: usable anywhere)
+    ///
+    /// SECURITY: Covered by the signature, so a leaked bound token cannot be
+    /// rebound to another channel.
+    pub audience: Option<TokenAudience>,
 }
 
 impl I2pCapabilityToken {
@@ -105,9 +154,16 @@ impl I2pCapabilityToken {
             expires_at,
             signature: Vec::new(),
             issuer_node_id,
+            audience: None,
         }
     }
 
+    /// Bind this token to a transport context (before signing)
+    pub fn with_audience(mut self, audience: TokenAudience) -> Self {
+        self.audience = Some(audience);
+        self
+    }
+
     /// Sign this token with clearnet identity
     pub fn sign(&mut self, identity: &NodeIdentity) -> Result<(), String> {
         let message = self.signing_message();
@@ -146,10 +202,35 @@ impl I2pCapabilityToken {
     }
 
     /// Check if token is valid
+    ///
+    /// SECURITY: Tokens bound to an audience are rejected here; use
+    /// [`Self::is_valid_for_audience`] with the channel they arrived on.
     pub fn is_valid(
         &self,
         recipient_node_id: &NodeId,
         issuer_public_key: &ed25519::PublicKey,
+    ) -> Result<bool, String> {
+        self.check_validity(recipient_node_id, issuer_public_key, None)
+    }
+
+    /// Check if token is valid when presented over the channel `audience`
+    ///
+    /// Unbound tokens are accepted on any channel; bound tokens only on the
+    /// channel they were issued for.
+    pub fn is_valid_for_audience(
+        &self,
+        recipient_node_id: &NodeId,
+        issuer_public_key: &ed25519::PublicKey,
+        audience: &TokenAudience,
+    ) -> Result<bool, String> {
+        self.check_validity(recipient_node_id, issuer_public_key, Some(audience))
+    }
+
+    fn check_validity(
+        &self,
+        recipient_node_id: &NodeId,
+        issuer_public_key: &ed25519::PublicKey,
+        presented_audience: Option<&TokenAudience>,
     ) -> Result<bool, String> {
         // SECURITY FIX C1: Verify the public key matches the claimed issuer
         // Derive NodeId from the provided public key (using crypto module)
@@ -173,6 +254,14 @@ impl I2pCapabilityToken {
             return Ok(false);
         }
 
+        // SECURITY: A bound token is only usable on its own channel
+        if let Some(bound) = &self.audience {
+            match presented_audience {
+                Some(presented) if bound.matches(presented) => {}
+                _ => return Ok(false),
+            }
+        }
+
         // Verify signature (now we know the key belongs to the claimed issuer)
         self.verify(issuer_public_key)
     }
@@ -192,6 +281,11 @@ impl I2pCapabilityToken {
         message.extend_from_slice(&self.issued_at.to_le_bytes());
         message.extend_from_slice(&self.expires_at.to_le_bytes());
         message.extend_from_slice(self.issuer_node_id.as_bytes());
+        // Unbound tokens keep their original signing message
+        if let Some(audience) = &self.audience {
+            message.push(1);
+            message.extend_from_slice(audience.as_bytes());
+        }
         message
     }
 
@@ -201,8 +295,119 @@ impl I2pCapabilityToken {
     }
 
     /// Deserialize token from bytes
+    ///
+    /// Legacy tokens start with the length prefix of `for_node`, which is
+    /// never a valid version tag, so they fail the tagged decode and are
+    /// retried as the untagged layout.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
-        bincode::deserialize(bytes).map_err(|e| format!("Deserialization failed: {}", e))
+        bincode::deserialize(bytes).or_else(|e| {
+            bincode::deserialize::<LegacyCapabilityToken>(bytes)
+                .map(I2pCapabilityToken::from)
+                .map_err(|_| format!("Deserialization failed: {}", e))
+        })
+    }
+}
+
+/// Serialized layout of [`I2pCapabilityToken`], tagged with its format version
+///
+/// Tokens issued before audience binding were the bare fields of
+/// [`LegacyCapabilityToken`] with no tag; later layouts are new variants.
+#[derive(Deserialize)]
+enum VersionedCapabilityToken {
+    V2(CapabilityTokenV2),
+}
+
+/// Variant name and index `I2pCapabilityToken` is serialized as
+const CURRENT_TOKEN_VERSION: (&str, u32) = ("V2", 0);
+
+/// Untagged layout of tokens issued before audience binding
+#[derive(Deserialize)]
+struct LegacyCapabilityToken {
+    for_node: NodeId,
+    i2p_destination: I2pDestination,
+    i2p_node_id: NodeId,
+    issued_at: u64,
+    expires_at: u64,
+    signature: Vec<u8>,
+    issuer_node_id: NodeId,
+}
+
+impl From<LegacyCapabilityToken> for I2pCapabilityToken {
+    fn from(t: LegacyCapabilityToken) -> Self {
+        I2pCapabilityToken {
+            for_node: t.for_node,
+            i2p_destination: t.i2p_destination,
+            i2p_node_id: t.i2p_node_id,
+            issued_at: t.issued_at,
+            expires_at: t.expires_at,
+            signature: t.signature,
+            issuer_node_id: t.issuer_node_id,
+            audience: None,
+        }
+    }
+}
+
+/// Adds the audience binding
+#[derive(Deserialize)]
+struct CapabilityTokenV2 {
+    for_node: NodeId,
+    i2p_destination: I2pDestination,
+    i2p_node_id: NodeId,
+    issued_at: u64,
+    expires_at: u64,
+    signature: Vec<u8>,
+    issuer_node_id: NodeId,
+    audience: Option<TokenAudience>,
+}
+
+/// Borrowed [`CapabilityTokenV2`]
+#[derive(Serialize)]
+struct CapabilityTokenV2Ref<'a> {
+    for_node: &'a NodeId,
+    i2p_destination: &'a I2pDestination,
+    i2p_node_id: &'a NodeId,
+    issued_at: u64,
+    expires_at: u64,
+    signature: &'a [u8],
+    issuer_node_id: &'a NodeId,
+    audience: Option<TokenAudience>,
+}
+
+impl Serialize for I2pCapabilityToken {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (variant, index) = CURRENT_TOKEN_VERSION;
+        serializer.serialize_newtype_variant(
+            "VersionedCapabilityToken",
+            index,
+            variant,
+            &CapabilityTokenV2Ref {
+                for_node: &self.for_node,
+                i2p_destination: &self.i2p_destination,
+                i2p_node_id: &self.i2p_node_id,
+                issued_at: self.issued_at,
+                expires_at: self.expires_at,
+                signature: &self.signature,
+                issuer_node_id: &self.issuer_node_id,
+                audience: self.audience,
+            },
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for I2pCapabilityToken {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match VersionedCapabilityToken::deserialize(deserializer)? {
+            VersionedCapabilityToken::V2(t) => I2pCapabilityToken {
+                for_node: t.for_node,
+                i2p_destination: t.i2p_destination,
+                i2p_node_id: t.i2p_node_id,
+                issued_at: t.issued_at,
+                expires_at: t.expires_at,
+                signature: t.signature,
+                issuer_node_id: t.issuer_node_id,
+                audience: t.audience,
+            },
+        })
     }
 }
 
@@ -478,4 +683,149 @@ mod tests {
         assert_eq!(removed, 1);
         assert_eq!(storage.token_count(), 0);
     }
+
+    fn bound_token(
+        issuer: &NodeIdentity,
+        for_node: NodeId,
+        audience: TokenAudience,
+    ) -> I2pCapabilityToken {
+        let mut token = I2pCapabilityToken::new(
+            for_node,
+            I2pDestination::new("bound.b32.i2p".to_string()),
+            NodeId::from_bytes([2u8; NODE_ID_SIZE]),
+            NodeId::from_bytes(*issuer.node_id.as_bytes()),
+            30,
+        )
+        .with_audience(audience);
+        token.sign(issuer).unwrap();
+        token
+    }
+
+    #[test]
+    fn test_audience_bound_token() {
+        myriadmesh_crypto::init().unwrap();
+        let issuer = NodeIdentity::generate().unwrap();
+        let for_node = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let channel = TokenAudience::from_peer_key(b"channel peer key");
+        let other_channel = TokenAudience::from_peer_key(b"another peer key");
+        assert_ne!(channel, other_channel);
+
+        let token = bound_token(&issuer, for_node, channel);
+        assert!(token
+            .is_valid_for_audience(&for_node, &issuer.public_key, &channel)
+            .unwrap());
+
+        // Wrong channel, or no channel at all
+        assert!(!token
+            .is_valid_for_audience(&for_node, &issuer.public_key, &other_channel)
+            .unwrap());
+        assert!(!token.is_valid(&for_node, &issuer.public_key).unwrap());
+    }
+
+    #[test]
+    fn test_audience_covered_by_signature() {
+        myriadmesh_crypto::init().unwrap();
+        let issuer = NodeIdentity::generate().unwrap();
+        let for_node = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let channel = TokenAudience::from_peer_key(b"channel peer key");
+        let stolen_on = TokenAudience::from_peer_key(b"attacker peer key");
+
+        // Rebinding a leaked token to the thief's channel breaks the signature
+        let mut token = bound_token(&issuer, for_node, channel);
+        token.audience = Some(stolen_on);
+        assert!(!token
+            .is_valid_for_audience(&for_node, &issuer.public_key, &stolen_on)
+            .unwrap());
+
+        // So does stripping the binding
+        token.audience = None;
+        assert!(!token.is_valid(&for_node, &issuer.public_key).unwrap());
+    }
+
+    #[test]
+    fn test_unbound_token_valid_on_any_channel() {
+        myriadmesh_crypto::init().unwrap();
+        let issuer = NodeIdentity::generate().unwrap();
+        let for_node = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let mut token = I2pCapabilityToken::new(
+            for_node,
+            I2pDestination::new("open.b32.i2p".to_string()),
+            NodeId::from_bytes([2u8; NODE_ID_SIZE]),
+            NodeId::from_bytes(*issuer.node_id.as_bytes()),
+            30,
+        );
+        token.sign(&issuer).unwrap();
+
+        let channel = TokenAudience::from_peer_key(b"any peer key");
+        assert!(token
+            .is_valid_for_audience(&for_node, &issuer.public_key, &channel)
+            .unwrap());
+
+        let restored = I2pCapabilityToken::from_bytes(&token.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.audience, None);
+    }
+
+    #[test]
+    fn test_token_encoding_is_versioned() {
+        myriadmesh_crypto::init().unwrap();
+        let issuer = NodeIdentity::generate().unwrap();
+        let for_node = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let channel = TokenAudience::from_peer_key(b"channel peer key");
+        let token = bound_token(&issuer, for_node, channel);
+
+        let bytes = token.to_bytes().unwrap();
+        assert_eq!(bytes[..4], CURRENT_TOKEN_VERSION.1.to_le_bytes());
+        let restored = I2pCapabilityToken::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.audience, Some(channel));
+        assert!(restored
+            .is_valid_for_audience(&for_node, &issuer.public_key, &channel)
+            .unwrap());
+
+        // Unknown versions are rejected instead of misread
+        let mut future = bytes.clone();
+        future[..4].copy_from_slice(&5u32.to_le_bytes());
+        assert!(I2pCapabilityToken::from_bytes(&future).is_err());
+    }
+
+    /// `I2pCapabilityToken` as derived before audience binding
+    #[derive(Serialize)]
+    struct BaselineToken {
+        for_node: NodeId,
+        i2p_destination: I2pDestination,
+        i2p_node_id: NodeId,
+        issued_at: u64,
+        expires_at: u64,
+        signature: Vec<u8>,
+        issuer_node_id: NodeId,
+    }
+
+    #[test]
+    fn test_legacy_token_still_verifies() {
+        myriadmesh_crypto::init().unwrap();
+        let issuer = NodeIdentity::generate().unwrap();
+        let for_node = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let mut token = I2pCapabilityToken::new(
+            for_node,
+            I2pDestination::new("old.b32.i2p".to_string()),
+            NodeId::from_bytes([2u8; NODE_ID_SIZE]),
+            NodeId::from_bytes(*issuer.node_id.as_bytes()),
+            30,
+        );
+        token.sign(&issuer).unwrap();
+
+        let bytes = bincode::serialize(&BaselineToken {
+            for_node: token.for_node,
+            i2p_destination: token.i2p_destination.clone(),
+            i2p_node_id: token.i2p_node_id,
+            issued_at: token.issued_at,
+            expires_at: token.expires_at,
+            signature: token.signature.clone(),
+            issuer_node_id: token.issuer_node_id,
+        })
+        .unwrap();
+
+        let restored = I2pCapabilityToken::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.audience, None);
+        assert!(restored.is_valid(&for_node, &issuer.public_key).unwrap());
+    }
 }
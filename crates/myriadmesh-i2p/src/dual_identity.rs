@@ -3,11 +3,58 @@
 //! Manages separate clearnet and i2p identities to prevent de-anonymization.
 //! Clearnet NodeID is used for public DHT, i2p NodeID is used only over i2p.
 
-use crate::capability_token::{I2pCapabilityToken, I2pDestination, TokenStorage};
+use crate::capability_token::{I2pCapabilityToken, I2pDestination, TokenAudience, TokenStorage};
 use myriadmesh_crypto::identity::NodeIdentity;
 use myriadmesh_protocol::NodeId;
 use serde::{Deserialize, Serialize};
 use sodiumoxide::crypto::sign::ed25519;
+use std::fmt;
+use std::ops::Deref;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Signing identity whose secret key is wiped when dropped
+///
+/// SECURITY: Every clone is a separate wrapper and wipes its own copy, so
+/// cloning a `DualIdentity` leaves no secret key behind once all clones
+/// are gone.
+#[derive(Clone)]
+pub struct SecretIdentity(NodeIdentity);
+
+impl SecretIdentity {
+    /// Take ownership of an identity's key material
+    pub fn new(identity: NodeIdentity) -> Self {
+        SecretIdentity(identity)
+    }
+}
+
+impl Deref for SecretIdentity {
+    type Target = NodeIdentity;
+
+    fn deref(&self) -> &NodeIdentity {
+        &self.0
+    }
+}
+
+impl Zeroize for SecretIdentity {
+    fn zeroize(&mut self) {
+        self.0.secret_key.0.zeroize();
+    }
+}
+
+impl Drop for SecretIdentity {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretIdentity {}
+
+impl fmt::Debug for SecretIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // NodeIdentity's Debug never prints the secret key
+        self.0.fmt(f)
+    }
+}
 
 /// Dual identity configuration for Mode 2
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,14 +64,14 @@ pub struct DualIdentity {
 
     /// Clearnet identity (for signing, stored privately)
     #[serde(skip)]
-    clearnet_identity: Option<NodeIdentity>,
+    clearnet_identity: Option<SecretIdentity>,
 
     /// Private i2p identity (NEVER linked publicly)
     pub i2p_node_id: NodeId,
 
     /// i2p identity (for signing, stored privately)
     #[serde(skip)]
-    i2p_identity: Option<NodeIdentity>,
+    i2p_identity: Option<SecretIdentity>,
 
     /// i2p destination address
     pub i2p_destination: I2pDestination,
@@ -47,9 +94,9 @@ impl DualIdentity {
 
         DualIdentity {
             clearnet_node_id,
-            clearnet_identity: Some(clearnet_identity),
+            clearnet_identity: Some(SecretIdentity::new(clearnet_identity)),
             i2p_node_id,
-            i2p_identity: Some(i2p_identity),
+            i2p_identity: Some(SecretIdentity::new(i2p_identity)),
             i2p_destination,
             token_storage: TokenStorage::new(),
         }
@@ -100,6 +147,29 @@ impl DualIdentity {
         &self,
         contact_node_id: NodeId,
         validity_days: u64,
+    ) -> Result<I2pCapabilityToken, String> {
+        self.issue_token(contact_node_id, validity_days, None)
+    }
+
+    /// Grant i2p access with a token bound to one transport context
+    ///
+    /// The token is only accepted when presented over the channel `audience`
+    /// identifies (see [`TokenAudience::from_peer_key`]), so a leaked token
+    /// is useless elsewhere.
+    pub fn grant_i2p_access_bound(
+        &self,
+        contact_node_id: NodeId,
+        validity_days: u64,
+        audience: TokenAudience,
+    ) -> Result<I2pCapabilityToken, String> {
+        self.issue_token(contact_node_id, validity_days, Some(audience))
+    }
+
+    fn issue_token(
+        &self,
+        contact_node_id: NodeId,
+        validity_days: u64,
+        audience: Option<TokenAudience>,
     ) -> Result<I2pCapabilityToken, String> {
         let clearnet_identity = self
             .clearnet_identity
@@ -113,6 +183,7 @@ impl DualIdentity {
             self.clearnet_node_id,
             validity_days,
         );
+        token.audience = audience;
 
         // Sign token with clearnet identity
         token.sign(clearnet_identity)?;
@@ -138,6 +209,27 @@ impl DualIdentity {
         Ok(())
     }
 
+    /// Check a token presented to this node before honouring it
+    ///
+    /// `channel` identifies the transport context the token arrived over;
+    /// a token bound to another channel is rejected, as is one this node
+    /// did not issue or issued to someone other than `presenter`.
+    pub fn verify_presented_token(
+        &self,
+        token: &I2pCapabilityToken,
+        presenter: &NodeId,
+        channel: &TokenAudience,
+    ) -> Result<bool, String> {
+        if token.issuer_node_id != self.clearnet_node_id {
+            return Ok(false);
+        }
+
+        let public_key = self
+            .get_clearnet_public_key()
+            .ok_or("Clearnet identity not available")?;
+        token.is_valid_for_audience(presenter, public_key, channel)
+    }
+
     /// Get capability token for reaching a specific node via i2p
     pub fn get_capability_token(&self, node_id: &NodeId) -> Option<&I2pCapabilityToken> {
         self.token_storage.get_token(node_id)
@@ -193,8 +285,8 @@ impl DualIdentity {
 
     /// Set identities after deserialization (identities are not serialized)
     pub fn set_identities(&mut self, clearnet_identity: NodeIdentity, i2p_identity: NodeIdentity) {
-        self.clearnet_identity = Some(clearnet_identity);
-        self.i2p_identity = Some(i2p_identity);
+        self.clearnet_identity = Some(SecretIdentity::new(clearnet_identity));
+        self.i2p_identity = Some(SecretIdentity::new(i2p_identity));
     }
 }
 
@@ -232,6 +324,55 @@ mod tests {
         assert!(!token.signature.is_empty());
     }
 
+    #[test]
+    fn test_grant_i2p_access_bound() {
+        let identity = create_test_identity();
+        let contact_node_id = NodeId::from_bytes([5u8; NODE_ID_SIZE]);
+        let public_key = identity.get_clearnet_public_key().unwrap();
+
+        let channel = TokenAudience::from_peer_key(&[7u8; 32]);
+        let token = identity
+            .grant_i2p_access_bound(contact_node_id, 30, channel)
+            .unwrap();
+        assert_eq!(token.audience, Some(channel));
+        assert!(token
+            .is_valid_for_audience(&contact_node_id, public_key, &channel)
+            .unwrap());
+
+        // Presented over a different channel: rejected
+        let wrong = TokenAudience::from_peer_key(&[8u8; 32]);
+        assert!(!token
+            .is_valid_for_audience(&contact_node_id, public_key, &wrong)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_verify_presented_token() {
+        let alice = create_test_identity();
+        let bob = create_test_identity();
+        let bob_id = bob.get_clearnet_node_id();
+        let channel = TokenAudience::from_peer_key(&[7u8; 32]);
+        let wrong = TokenAudience::from_peer_key(&[8u8; 32]);
+
+        let token = alice.grant_i2p_access_bound(bob_id, 30, channel).unwrap();
+        assert!(alice
+            .verify_presented_token(&token, &bob_id, &channel)
+            .unwrap());
+
+        // Leaked onto another channel, replayed by someone else, or
+        // presented to a node that did not issue it
+        assert!(!alice
+            .verify_presented_token(&token, &bob_id, &wrong)
+            .unwrap());
+        let mallory = NodeId::from_bytes([9u8; NODE_ID_SIZE]);
+        assert!(!alice
+            .verify_presented_token(&token, &mallory, &channel)
+            .unwrap());
+        assert!(!bob
+            .verify_presented_token(&token, &bob_id, &channel)
+            .unwrap());
+    }
+
     #[test]
     fn test_store_and_retrieve_token() {
         let alice = create_test_identity();
@@ -346,4 +487,39 @@ mod tests {
         let i2p_bytes = identity.i2p_node_id.as_bytes();
         assert_ne!(clearnet_bytes, i2p_bytes);
     }
+
+    fn assert_zeroize_on_drop<T: Zeroize + ZeroizeOnDrop>() {}
+
+    #[test]
+    fn test_secret_identity_zeroizes() {
+        assert_zeroize_on_drop::<SecretIdentity>();
+
+        myriadmesh_crypto::init().unwrap();
+        let mut secret = SecretIdentity::new(NodeIdentity::generate().unwrap());
+        assert!(secret.secret_key.0.iter().any(|&b| b != 0));
+
+        secret.zeroize();
+        assert!(secret.secret_key.0.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_secret_identity_clone_is_independent() {
+        myriadmesh_crypto::init().unwrap();
+        let original = SecretIdentity::new(NodeIdentity::generate().unwrap());
+        let key = original.secret_key.0;
+
+        // A clone owns its own copy and wipes it without touching the original
+        let mut copy = original.clone();
+        assert_ne!(copy.secret_key.0.as_ptr(), original.secret_key.0.as_ptr());
+        copy.zeroize();
+        assert!(copy.secret_key.0.iter().all(|&b| b == 0));
+        assert_eq!(original.secret_key.0, key);
+
+        // Cloned dual identities hold wrapped keys too, so they wipe on drop
+        let identity = create_test_identity();
+        let cloned = identity.clone();
+        let wrapped: Option<&SecretIdentity> = cloned.clearnet_identity.as_ref();
+        assert!(wrapped.is_some());
+        assert!(cloned.i2p_identity.is_some());
+    }
 }
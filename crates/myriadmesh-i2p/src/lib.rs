@@ -43,12 +43,13 @@ pub mod onion;
 pub mod privacy;
 pub mod secure_token_exchange;
 
-pub use capability_token::{I2pCapabilityToken, I2pDestination, TokenStorage};
-pub use dual_identity::DualIdentity;
+pub use capability_token::{I2pCapabilityToken, I2pDestination, TokenAudience, TokenStorage};
+pub use dual_identity::{DualIdentity, SecretIdentity};
 pub use onion::{
-    OnionConfig, OnionLayer as OnionRouteLayer, OnionRoute, OnionRouter, RouteSelectionStrategy,
+    NeighborTraffic, OnionClock, OnionConfig, OnionLayer as OnionRouteLayer, OnionRoute,
+    OnionRouter, OnionRouterStats, RouteSelectionStrategy, SystemClock,
 };
-pub use privacy::{PaddingStrategy, PrivacyConfig, PrivacyLayer, TimingStrategy};
+pub use privacy::{DecoyCandidate, PaddingStrategy, PrivacyConfig, PrivacyLayer, TimingStrategy};
 pub use secure_token_exchange::{EncryptedTokenMessage, SecureTokenExchange};
 
 #[cfg(test)]
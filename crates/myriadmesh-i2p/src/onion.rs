@@ -10,15 +10,29 @@
 //! - Route randomization prevents traffic correlation
 //! - Minimum 3 hops recommended for strong anonymity
 //! - SECURITY C5: Timing obfuscation prevents correlation attacks
-
-use myriadmesh_crypto::encryption::{decrypt, encrypt, EncryptedMessage};
-use myriadmesh_crypto::keyexchange::{client_session_keys, KeyExchangeKeypair, X25519PublicKey};
+//! - Optional hybrid X25519 + ML-KEM-768 layers, so recorded onions stay
+//!   confidential against future quantum adversaries
+//!
+//! ## Layer Format
+//!
+//! Classical: `ephemeral_pk (32) || nonce (24) || ciphertext`
+//!
+//! Hybrid: `ephemeral_pk (32) || kem_ciphertext (1088) || nonce (24) || ciphertext`
+
+use myriadmesh_crypto::encryption::{decrypt, encrypt, EncryptedMessage, NONCE_SIZE};
+use myriadmesh_crypto::kem::{KemCiphertext, KemKeypair, KemPublicKey, KEM_CIPHERTEXT_SIZE};
+use myriadmesh_crypto::keyexchange::{
+    client_session_keys, hybrid_client_session_keys, hybrid_server_session_keys,
+    KeyExchangeKeypair, KeyExchangeMode, X25519PublicKey, X25519_PUBLIC_KEY_SIZE,
+};
 use myriadmesh_protocol::{types::NODE_ID_SIZE, NodeId};
 use rand::seq::SliceRandom;
-use rand::Rng;
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::time::sleep;
 
 /// Minimum number of hops for onion routing
@@ -46,6 +60,50 @@ pub const TARGET_BUILD_TIME_MS: u64 = 100;
 /// Prevents excessive route reuse that could compromise anonymity
 pub const MAX_ROUTE_USES: u64 = 1000;
 
+/// Default window (seconds) for per-neighbor traffic accounting
+pub const DEFAULT_TRAFFIC_WINDOW_SECS: u64 = 60;
+
+/// Monotonic time source for timing protection and traffic accounting
+///
+/// Tests substitute a manual clock so timing behaviour is reproducible.
+pub trait OnionClock: Send + Sync {
+    /// Current monotonic time
+    fn now(&self) -> Instant;
+}
+
+/// Clock backed by `Instant::now`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl OnionClock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Default router RNG, drawing from `rand::thread_rng()` on every call
+///
+/// The thread RNG itself is not `Send`, so it cannot be stored in the router.
+struct ThreadRandom;
+
+impl RngCore for ThreadRandom {
+    fn next_u32(&mut self) -> u32 {
+        rand::thread_rng().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand::thread_rng().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand::thread_rng().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        rand::thread_rng().try_fill_bytes(dest)
+    }
+}
+
 /// Onion routing configuration
 #[derive(Debug, Clone)]
 pub struct OnionConfig {
@@ -60,6 +118,16 @@ pub struct OnionConfig {
 
     /// Enable route randomization
     pub randomize_routes: bool,
+
+    /// Key exchange used for the layers of new routes
+    ///
+    /// Hybrid routes only use hops that advertise an ML-KEM key, and add
+    /// about 1.1KB per layer.
+    pub key_exchange: KeyExchangeMode,
+
+    /// Rolling window (seconds) over which traffic peeled for each previous
+    /// hop is counted
+    pub traffic_window_secs: u64,
 }
 
 impl Default for OnionConfig {
@@ -69,6 +137,8 @@ impl Default for OnionConfig {
             selection_strategy: RouteSelectionStrategy::Random,
             max_route_lifetime: 3600, // 1 hour
             randomize_routes: true,
+            key_exchange: KeyExchangeMode::Classical,
+            traffic_window_secs: DEFAULT_TRAFFIC_WINDOW_SECS,
         }
     }
 }
@@ -97,14 +167,37 @@ pub struct OnionLayer {
 
     /// Encrypted payload (contains next hop + inner layers)
     pub encrypted_payload: Vec<u8>,
+
+    /// Key exchange the layer was built with; every layer of a route uses
+    /// the same one, so forwarding hops pass it on unchanged
+    #[serde(default)]
+    pub key_exchange: KeyExchangeMode,
 }
 
 impl OnionLayer {
     /// Create new onion layer
     pub fn new(node_id: NodeId, encrypted_payload: Vec<u8>) -> Self {
+        Self::with_key_exchange(node_id, encrypted_payload, KeyExchangeMode::Classical)
+    }
+
+    /// Create an onion layer built with the given key exchange
+    pub fn with_key_exchange(
+        node_id: NodeId,
+        encrypted_payload: Vec<u8>,
+        key_exchange: KeyExchangeMode,
+    ) -> Self {
         OnionLayer {
             node_id,
             encrypted_payload,
+            key_exchange,
+        }
+    }
+
+    /// Size of the key exchange header before the nonce
+    fn header_len(&self) -> usize {
+        match self.key_exchange {
+            KeyExchangeMode::Classical => X25519_PUBLIC_KEY_SIZE,
+            KeyExchangeMode::Hybrid => X25519_PUBLIC_KEY_SIZE + KEM_CIPHERTEXT_SIZE,
         }
     }
 }
@@ -128,6 +221,12 @@ pub struct OnionRoute {
     /// Maps NodeId -> X25519 public key
     pub hop_public_keys: HashMap<NodeId, X25519PublicKey>,
 
+    /// ML-KEM public keys for each hop (hybrid routes only)
+    pub hop_kem_keys: HashMap<NodeId, KemPublicKey>,
+
+    /// Key exchange used to build this route's layers
+    pub key_exchange: KeyExchangeMode,
+
     /// Route creation time
     pub created_at: u64,
 
@@ -159,10 +258,23 @@ impl OnionRoute {
 
     /// Create new onion route
     pub fn new(source: NodeId, destination: NodeId, hops: Vec<NodeId>, lifetime_secs: u64) -> Self {
-        let now = Self::get_current_time();
+        Self::with_route_id(
+            rand::thread_rng().gen(),
+            source,
+            destination,
+            hops,
+            lifetime_secs,
+        )
+    }
 
-        let mut rng = rand::thread_rng();
-        let route_id = rng.gen();
+    fn with_route_id(
+        route_id: u64,
+        source: NodeId,
+        destination: NodeId,
+        hops: Vec<NodeId>,
+        lifetime_secs: u64,
+    ) -> Self {
+        let now = Self::get_current_time();
 
         OnionRoute {
             route_id,
@@ -170,17 +282,30 @@ impl OnionRoute {
             destination,
             hops,
             hop_public_keys: HashMap::new(),
+            hop_kem_keys: HashMap::new(),
+            key_exchange: KeyExchangeMode::Classical,
             created_at: now,
             expires_at: now + lifetime_secs,
             use_count: 0,
         }
     }
 
+    /// Use the given key exchange for this route's layers
+    pub fn with_key_exchange(mut self, key_exchange: KeyExchangeMode) -> Self {
+        self.key_exchange = key_exchange;
+        self
+    }
+
     /// Set public key for a hop
     pub fn set_hop_public_key(&mut self, node_id: NodeId, public_key: X25519PublicKey) {
         self.hop_public_keys.insert(node_id, public_key);
     }
 
+    /// Set ML-KEM public key for a hop
+    pub fn set_hop_kem_key(&mut self, node_id: NodeId, kem_key: KemPublicKey) {
+        self.hop_kem_keys.insert(node_id, kem_key);
+    }
+
     /// Check if route is expired
     pub fn is_expired(&self) -> bool {
         let now = Self::get_current_time();
@@ -221,6 +346,135 @@ pub struct RouteNode {
     pub available: bool,
     /// X25519 public key for this node (for onion encryption)
     pub public_key: X25519PublicKey,
+    /// ML-KEM public key, if the node supports hybrid layers
+    pub kem_public_key: Option<KemPublicKey>,
+}
+
+/// Traffic peeled on behalf of one previous hop within the accounting window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NeighborTraffic {
+    /// Layer bytes received
+    pub bytes: u64,
+    /// Layers received
+    pub packets: u64,
+}
+
+impl NeighborTraffic {
+    fn add(&mut self, other: &NeighborTraffic) {
+        self.bytes += other.bytes;
+        self.packets += other.packets;
+    }
+}
+
+/// Onion router activity counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OnionRouterStats {
+    /// Routes currently usable
+    pub active_routes: u64,
+    /// Routes selected since startup
+    pub routes_built: u64,
+    /// Onions wrapped for sending
+    pub onions_built: u64,
+    /// Layers decrypted for this node
+    pub layers_peeled: u64,
+    /// Layers rejected (not addressed to us, malformed or undecryptable)
+    pub peel_failures: u64,
+}
+
+/// Lifetime counters behind [`OnionRouterStats`]
+#[derive(Debug, Default)]
+struct OnionCounters {
+    routes_built: AtomicU64,
+    onions_built: AtomicU64,
+    layers_peeled: AtomicU64,
+    peel_failures: AtomicU64,
+}
+
+/// Rolling per-neighbor traffic counts in one-second slots
+///
+/// SECURITY: Lets a relay notice a previous hop pushing a disproportionate
+/// share of traffic through it (flooding or correlation attacks). Memory is
+/// bounded by the window length per active neighbor.
+///
+/// Recording only expires the sending neighbor's slots; neighbors that went
+/// quiet are dropped by a sweep over all of them at most once per window.
+#[derive(Debug)]
+struct TrafficAccounting {
+    window: Duration,
+    epoch: Instant,
+    neighbors: HashMap<NodeId, VecDeque<(u64, NeighborTraffic)>>,
+    /// Slot of the last sweep over all neighbors
+    last_sweep: u64,
+}
+
+impl TrafficAccounting {
+    fn new(window: Duration, epoch: Instant) -> Self {
+        TrafficAccounting {
+            window,
+            epoch,
+            neighbors: HashMap::new(),
+            last_sweep: 0,
+        }
+    }
+
+    fn slot(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.epoch).as_secs()
+    }
+
+    fn record(&mut self, neighbor: NodeId, bytes: usize, now: Instant) {
+        let slot = self.slot(now);
+        let traffic = NeighborTraffic {
+            bytes: bytes as u64,
+            packets: 1,
+        };
+
+        let window = self.window.as_secs();
+        let slots = self.neighbors.entry(neighbor).or_default();
+        Self::expire(slots, slot, window);
+        match slots.back_mut() {
+            Some((last, counts)) if *last == slot => counts.add(&traffic),
+            _ => slots.push_back((slot, traffic)),
+        }
+
+        if slot.saturating_sub(self.last_sweep) >= window {
+            self.prune(now);
+        }
+    }
+
+    /// Drop slots that have left the window, and neighbors with none left
+    fn prune(&mut self, now: Instant) {
+        let current = self.slot(now);
+        let window = self.window.as_secs();
+        self.neighbors.retain(|_, slots| {
+            Self::expire(slots, current, window);
+            !slots.is_empty()
+        });
+        self.last_sweep = current;
+    }
+
+    /// Drop one neighbor's slots that have left the window
+    fn expire(slots: &mut VecDeque<(u64, NeighborTraffic)>, current: u64, window: u64) {
+        while slots
+            .front()
+            .is_some_and(|(slot, _)| current.saturating_sub(*slot) >= window)
+        {
+            slots.pop_front();
+        }
+    }
+
+    fn totals(&mut self, now: Instant) -> HashMap<NodeId, NeighborTraffic> {
+        self.prune(now);
+        self.neighbors
+            .iter()
+            .map(|(neighbor, slots)| {
+                let mut total = NeighborTraffic::default();
+                for (_, counts) in slots {
+                    total.add(counts);
+                }
+                (*neighbor, total)
+            })
+            .collect()
+    }
 }
 
 /// Onion router for managing routes
@@ -229,7 +483,17 @@ pub struct OnionRouter {
     local_node_id: NodeId,
     /// Local keypair for decrypting layers intended for this node
     local_keypair: KeyExchangeKeypair,
+    /// Local ML-KEM keypair for decrypting hybrid layers
+    local_kem_keypair: Option<KemKeypair>,
     active_routes: Vec<OnionRoute>,
+    /// Traffic peeled per previous hop
+    traffic: Mutex<TrafficAccounting>,
+    /// Lifetime activity counters
+    counters: OnionCounters,
+    /// Source of hop selection, route IDs and timing jitter
+    rng: Mutex<Box<dyn RngCore + Send>>,
+    /// Time source for timing protection and traffic accounting
+    clock: Arc<dyn OnionClock>,
 }
 
 impl OnionRouter {
@@ -239,14 +503,57 @@ impl OnionRouter {
         local_keypair: KeyExchangeKeypair,
         config: OnionConfig,
     ) -> Self {
+        let clock: Arc<dyn OnionClock> = Arc::new(SystemClock);
+        let traffic =
+            TrafficAccounting::new(Duration::from_secs(config.traffic_window_secs), clock.now());
         OnionRouter {
             config,
             local_node_id,
             local_keypair,
+            local_kem_keypair: None,
             active_routes: Vec::new(),
+            traffic: Mutex::new(traffic),
+            counters: OnionCounters::default(),
+            rng: Mutex::new(Box::new(ThreadRandom)),
+            clock,
         }
     }
 
+    /// Draw hop selection, route IDs and timing jitter from `rng`
+    ///
+    /// Defaults to the thread RNG. A seeded RNG makes route selection and
+    /// forwarding delays reproducible; never use one in production.
+    pub fn with_rng<R: RngCore + Send + 'static>(mut self, rng: R) -> Self {
+        self.rng = Mutex::new(Box::new(rng));
+        self
+    }
+
+    /// Measure timing protection and traffic windows with `clock`
+    ///
+    /// Traffic recorded so far is discarded.
+    pub fn with_clock(mut self, clock: Arc<dyn OnionClock>) -> Self {
+        let window = Duration::from_secs(self.config.traffic_window_secs);
+        self.traffic = Mutex::new(TrafficAccounting::new(window, clock.now()));
+        self.clock = clock;
+        self
+    }
+
+    /// Accept hybrid layers using this ML-KEM keypair
+    pub fn with_kem_keypair(mut self, kem_keypair: KemKeypair) -> Self {
+        self.local_kem_keypair = Some(kem_keypair);
+        self
+    }
+
+    /// ML-KEM public key to advertise, if hybrid layers are accepted
+    pub fn kem_public_key(&self) -> Option<&KemPublicKey> {
+        self.local_kem_keypair.as_ref().map(|kp| &kp.public_key)
+    }
+
+    /// Node ID routes are built from
+    pub fn local_node_id(&self) -> &NodeId {
+        &self.local_node_id
+    }
+
     /// Create with default configuration
     pub fn new_default(local_node_id: NodeId, local_keypair: KeyExchangeKeypair) -> Self {
         Self::new(local_node_id, local_keypair, OnionConfig::default())
@@ -260,10 +567,14 @@ impl OnionRouter {
         destination: NodeId,
         available_nodes: &[RouteNode],
     ) -> Result<OnionRoute, String> {
-        // Filter out unavailable nodes, source, and destination
+        let hybrid = self.config.key_exchange == KeyExchangeMode::Hybrid;
+
+        // Filter out unavailable nodes, source, and destination; hybrid
+        // routes also need every hop to support ML-KEM
         let candidates: Vec<_> = available_nodes
             .iter()
             .filter(|n| n.available && n.node_id != self.local_node_id && n.node_id != destination)
+            .filter(|n| !hybrid || n.kem_public_key.is_some())
             .collect();
 
         if candidates.len() < self.config.num_hops {
@@ -278,22 +589,33 @@ impl OnionRouter {
         let hops = self.select_hops(&candidates, self.config.num_hops)?;
 
         // Create route
-        let mut route = OnionRoute::new(
+        let route_id = self.rng.lock().unwrap_or_else(|e| e.into_inner()).gen();
+        let mut route = OnionRoute::with_route_id(
+            route_id,
             self.local_node_id,
             destination,
             hops.clone(),
             self.config.max_route_lifetime,
-        );
+        )
+        .with_key_exchange(self.config.key_exchange);
 
         // Store public keys for selected hops
         for node in available_nodes {
             if hops.contains(&node.node_id) || node.node_id == destination {
                 route.set_hop_public_key(node.node_id, node.public_key);
+                if let Some(kem_key) = &node.kem_public_key {
+                    route.set_hop_kem_key(node.node_id, kem_key.clone());
+                }
             }
         }
 
+        if hybrid && !route.hop_kem_keys.contains_key(&destination) {
+            return Err("Destination does not support hybrid key exchange".to_string());
+        }
+
         // Store active route
         self.active_routes.push(route.clone());
+        self.counters.routes_built.fetch_add(1, Ordering::Relaxed);
 
         Ok(route)
     }
@@ -304,13 +626,14 @@ impl OnionRouter {
         candidates: &[&RouteNode],
         num_hops: usize,
     ) -> Result<Vec<NodeId>, String> {
-        let mut rng = rand::thread_rng();
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        let rng = &mut *rng;
 
         match self.config.selection_strategy {
             RouteSelectionStrategy::Random => {
                 // Completely random selection
                 let selected: Vec<NodeId> = candidates
-                    .choose_multiple(&mut rng, num_hops)
+                    .choose_multiple(rng, num_hops)
                     .map(|n| n.node_id)
                     .collect();
 
@@ -329,7 +652,7 @@ impl OnionRouter {
                 // Pick from top 2*num_hops to add some randomness
                 let pool_size = (num_hops * 2).min(sorted.len());
                 let selected: Vec<NodeId> = sorted[..pool_size]
-                    .choose_multiple(&mut rng, num_hops)
+                    .choose_multiple(rng, num_hops)
                     .map(|n| n.node_id)
                     .collect();
 
@@ -347,7 +670,7 @@ impl OnionRouter {
 
                 let pool_size = (num_hops * 2).min(sorted.len());
                 let selected: Vec<NodeId> = sorted[..pool_size]
-                    .choose_multiple(&mut rng, num_hops)
+                    .choose_multiple(rng, num_hops)
                     .map(|n| n.node_id)
                     .collect();
 
@@ -369,7 +692,7 @@ impl OnionRouter {
 
                 let pool_size = (num_hops * 2).min(scored.len());
                 let selected: Vec<NodeId> = scored[..pool_size]
-                    .choose_multiple(&mut rng, num_hops)
+                    .choose_multiple(rng, num_hops)
                     .map(|(n, _)| n.node_id)
                     .collect();
 
@@ -404,6 +727,17 @@ impl OnionRouter {
             .count()
     }
 
+    /// Snapshot of route and layer counters
+    pub fn stats(&self) -> OnionRouterStats {
+        OnionRouterStats {
+            active_routes: self.active_route_count() as u64,
+            routes_built: self.counters.routes_built.load(Ordering::Relaxed),
+            onions_built: self.counters.onions_built.load(Ordering::Relaxed),
+            layers_peeled: self.counters.layers_peeled.load(Ordering::Relaxed),
+            peel_failures: self.counters.peel_failures.load(Ordering::Relaxed),
+        }
+    }
+
     /// Build onion layers with timing protection (async)
     ///
     /// SECURITY C5: Normalizes processing time regardless of hop count to
@@ -421,8 +755,6 @@ impl OnionRouter {
         route: &OnionRoute,
         payload: &[u8],
     ) -> Result<Vec<OnionLayer>, String> {
-        use std::time::Instant;
-
         // SECURITY H10: Verify route has not expired
         if route.is_expired() {
             return Err("Route has expired".to_string());
@@ -436,28 +768,48 @@ impl OnionRouter {
             ));
         }
 
-        let start = Instant::now();
+        let start = self.clock.now();
 
         // Build layers synchronously
         let layers = self.build_onion_layers_sync(route, payload)?;
 
-        let elapsed = start.elapsed();
-
-        // SECURITY C5: Normalize processing time to TARGET_BUILD_TIME_MS
-        // This prevents timing analysis from revealing the number of hops
-        let target = Duration::from_millis(TARGET_BUILD_TIME_MS);
-        if elapsed < target {
-            let remaining = target - elapsed;
-            // Add some randomness to the padding delay (±20%)
-            let mut rng = rand::thread_rng();
-            let jitter_factor = rng.gen_range(0.8..=1.2);
-            let delay = remaining.mul_f64(jitter_factor);
+        let elapsed = self.clock.now().saturating_duration_since(start);
+        if let Some(delay) = self.padding_delay(elapsed) {
             sleep(delay).await;
         }
 
         Ok(layers)
     }
 
+    /// Padding that stretches a layer build taking `elapsed` to the target
+    /// build time
+    ///
+    /// SECURITY C5: Normalizing processing time to TARGET_BUILD_TIME_MS
+    /// prevents timing analysis from revealing the number of hops.
+    fn padding_delay(&self, elapsed: Duration) -> Option<Duration> {
+        let remaining = Duration::from_millis(TARGET_BUILD_TIME_MS).checked_sub(elapsed)?;
+        if remaining.is_zero() {
+            return None;
+        }
+        // Add some randomness to the padding delay (±20%)
+        let jitter_factor = self
+            .rng
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .gen_range(0.8..=1.2);
+        Some(remaining.mul_f64(jitter_factor))
+    }
+
+    /// Random delay applied before peeling a layer (SECURITY C5)
+    fn forward_delay(&self) -> Duration {
+        let delay = self
+            .rng
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .gen_range(MIN_FORWARD_DELAY_MS..=MAX_FORWARD_JITTER_MS);
+        Duration::from_millis(delay)
+    }
+
     /// Build onion layers (synchronous, no timing protection)
     ///
     /// WARNING: This method does NOT include timing protection and processing
@@ -505,9 +857,24 @@ impl OnionRouter {
             // Generate ephemeral keypair for this layer
             let ephemeral_keypair = KeyExchangeKeypair::generate();
 
-            // Derive shared secret using ECDH
-            let session_keys = client_session_keys(&ephemeral_keypair, hop_public_key)
-                .map_err(|e| format!("Key exchange failed: {}", e))?;
+            // Derive shared secret using ECDH, plus ML-KEM for hybrid routes
+            let (session_keys, kem_ciphertext) = match route.key_exchange {
+                KeyExchangeMode::Classical => {
+                    let keys = client_session_keys(&ephemeral_keypair, hop_public_key)
+                        .map_err(|e| format!("Key exchange failed: {}", e))?;
+                    (keys, None)
+                }
+                KeyExchangeMode::Hybrid => {
+                    let kem_key = route
+                        .hop_kem_keys
+                        .get(&node_id)
+                        .ok_or_else(|| format!("No ML-KEM key for hop {}", node_id))?;
+                    let (keys, ciphertext) =
+                        hybrid_client_session_keys(&ephemeral_keypair, hop_public_key, kem_key)
+                            .map_err(|e| format!("Key exchange failed: {}", e))?;
+                    (keys, Some(ciphertext))
+                }
+            };
 
             // Add next hop info if not the last hop
             let layer_data = if i < path.len() - 1 {
@@ -531,13 +898,18 @@ impl OnionRouter {
             encrypted_bytes.extend_from_slice(encrypted.nonce.as_bytes());
             encrypted_bytes.extend_from_slice(&encrypted.ciphertext);
 
-            // Prepend ephemeral public key so recipient can derive shared secret
+            // Prepend ephemeral public key (and KEM ciphertext) so recipient
+            // can derive shared secret
             let mut full_layer = Vec::new();
             full_layer.extend_from_slice(ephemeral_keypair.public_bytes());
+            if let Some(ciphertext) = &kem_ciphertext {
+                full_layer.extend_from_slice(ciphertext.as_bytes());
+            }
             full_layer.extend_from_slice(&encrypted_bytes);
 
             // Create the layer
-            let layer = OnionLayer::new(node_id, full_layer.clone());
+            let layer =
+                OnionLayer::with_key_exchange(node_id, full_layer.clone(), route.key_exchange);
             layers.push(layer);
 
             // This encrypted layer becomes the payload for the next iteration
@@ -546,9 +918,79 @@ impl OnionRouter {
 
         // Reverse to get correct order (source first)
         layers.reverse();
+        self.counters.onions_built.fetch_add(1, Ordering::Relaxed);
         Ok(layers)
     }
 
+    /// Peel a layer received from `previous_hop`, with timing protection
+    ///
+    /// Like `peel_layer_with_timing_protection()`, but counts the layer
+    /// against `previous_hop` in the traffic accounting. Layers are counted
+    /// whether or not they decrypt, since junk is part of a flood.
+    pub async fn peel_layer_from(
+        &self,
+        previous_hop: NodeId,
+        layer: &OnionLayer,
+    ) -> Result<(Option<NodeId>, Vec<u8>), String> {
+        self.record_traffic(previous_hop, layer, self.clock.now());
+        self.peel_layer_with_timing_protection(layer).await
+    }
+
+    /// Peel a layer received from `previous_hop` (synchronous, no timing
+    /// protection), counting it in the traffic accounting
+    pub fn peel_layer_from_sync(
+        &self,
+        previous_hop: NodeId,
+        layer: &OnionLayer,
+    ) -> Result<(Option<NodeId>, Vec<u8>), String> {
+        self.record_traffic(previous_hop, layer, self.clock.now());
+        self.peel_layer_sync(layer)
+    }
+
+    fn record_traffic(&self, previous_hop: NodeId, layer: &OnionLayer, now: Instant) {
+        self.traffic
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .record(previous_hop, layer.encrypted_payload.len(), now);
+    }
+
+    /// Traffic peeled for `neighbor` within the accounting window
+    pub fn neighbor_traffic(&self, neighbor: &NodeId) -> NeighborTraffic {
+        self.neighbor_traffic_at(neighbor, self.clock.now())
+    }
+
+    fn neighbor_traffic_at(&self, neighbor: &NodeId, now: Instant) -> NeighborTraffic {
+        self.all_neighbor_traffic_at(now)
+            .remove(neighbor)
+            .unwrap_or_default()
+    }
+
+    /// Traffic peeled per neighbor within the accounting window
+    pub fn all_neighbor_traffic(&self) -> HashMap<NodeId, NeighborTraffic> {
+        self.all_neighbor_traffic_at(self.clock.now())
+    }
+
+    fn all_neighbor_traffic_at(&self, now: Instant) -> HashMap<NodeId, NeighborTraffic> {
+        self.traffic
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .totals(now)
+    }
+
+    /// Fraction (0.0-1.0) of bytes peeled within the window that came from
+    /// `neighbor`
+    ///
+    /// A neighbor far above `1 / number of neighbors` is a candidate for
+    /// throttling.
+    pub fn neighbor_traffic_share(&self, neighbor: &NodeId) -> f64 {
+        let all = self.all_neighbor_traffic();
+        let total: u64 = all.values().map(|t| t.bytes).sum();
+        if total == 0 {
+            return 0.0;
+        }
+        all.get(neighbor).map_or(0, |t| t.bytes) as f64 / total as f64
+    }
+
     /// Peel one layer from onion with timing protection (async)
     ///
     /// SECURITY C5: Adds random delay before forwarding to prevent timing
@@ -565,9 +1007,7 @@ impl OnionRouter {
         // SECURITY C5: Add random delay BEFORE processing to prevent timing attacks
         // This ensures that even if decryption timing varies, external observers
         // cannot correlate timing patterns to determine hop position or route structure
-        let mut rng = rand::thread_rng();
-        let delay = rng.gen_range(MIN_FORWARD_DELAY_MS..=MAX_FORWARD_JITTER_MS);
-        sleep(Duration::from_millis(delay)).await;
+        sleep(self.forward_delay()).await;
 
         // Perform the actual layer peeling
         self.peel_layer_sync(layer)
@@ -583,6 +1023,17 @@ impl OnionRouter {
     /// payload. Returns (next_hop, decrypted_payload) where
     /// decrypted_payload is the inner layers.
     pub fn peel_layer_sync(&self, layer: &OnionLayer) -> Result<(Option<NodeId>, Vec<u8>), String> {
+        let result = self.decrypt_layer(layer);
+        let counter = match result {
+            Ok(_) => &self.counters.layers_peeled,
+            Err(_) => &self.counters.peel_failures,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
+    /// Decrypt a layer addressed to this node
+    fn decrypt_layer(&self, layer: &OnionLayer) -> Result<(Option<NodeId>, Vec<u8>), String> {
         use myriadmesh_crypto::encryption::Nonce;
         use myriadmesh_crypto::keyexchange::server_session_keys;
 
@@ -592,31 +1043,53 @@ impl OnionRouter {
         }
 
         let encrypted_payload = &layer.encrypted_payload;
+        let header_len = layer.header_len();
 
-        // Extract ephemeral public key (first 32 bytes)
-        if encrypted_payload.len() < 32 {
+        // Extract key exchange header (ephemeral public key, then KEM
+        // ciphertext for hybrid layers)
+        if encrypted_payload.len() < header_len {
             return Err("Encrypted payload too short".to_string());
         }
 
-        let mut ephemeral_public_bytes = [0u8; 32];
-        ephemeral_public_bytes.copy_from_slice(&encrypted_payload[0..32]);
+        let mut ephemeral_public_bytes = [0u8; X25519_PUBLIC_KEY_SIZE];
+        ephemeral_public_bytes.copy_from_slice(&encrypted_payload[..X25519_PUBLIC_KEY_SIZE]);
         let ephemeral_public = X25519PublicKey::from_bytes(ephemeral_public_bytes);
 
         // Derive shared secret using our secret key
-        let session_keys = server_session_keys(&self.local_keypair, &ephemeral_public)
-            .map_err(|e| format!("Key exchange failed: {}", e))?;
+        let session_keys = match layer.key_exchange {
+            KeyExchangeMode::Classical => {
+                server_session_keys(&self.local_keypair, &ephemeral_public)
+            }
+            KeyExchangeMode::Hybrid => {
+                let kem_keypair = self
+                    .local_kem_keypair
+                    .as_ref()
+                    .ok_or_else(|| "Hybrid layer but no local ML-KEM keypair".to_string())?;
+                let kem_ciphertext = KemCiphertext::from_bytes(
+                    &encrypted_payload[X25519_PUBLIC_KEY_SIZE..header_len],
+                )
+                .map_err(|e| format!("Invalid KEM ciphertext: {}", e))?;
+                hybrid_server_session_keys(
+                    &self.local_keypair,
+                    kem_keypair,
+                    &ephemeral_public,
+                    &kem_ciphertext,
+                )
+            }
+        }
+        .map_err(|e| format!("Key exchange failed: {}", e))?;
 
-        // Extract nonce (24 bytes after public key)
-        if encrypted_payload.len() < 32 + 24 {
+        // Extract nonce (24 bytes after the header)
+        if encrypted_payload.len() < header_len + NONCE_SIZE {
             return Err("Encrypted payload missing nonce".to_string());
         }
 
-        let mut nonce_bytes = [0u8; 24];
-        nonce_bytes.copy_from_slice(&encrypted_payload[32..56]);
+        let mut nonce_bytes = [0u8; NONCE_SIZE];
+        nonce_bytes.copy_from_slice(&encrypted_payload[header_len..header_len + NONCE_SIZE]);
         let nonce = Nonce::from_bytes(nonce_bytes);
 
         // Extract ciphertext (remainder)
-        let ciphertext = encrypted_payload[56..].to_vec();
+        let ciphertext = encrypted_payload[header_len + NONCE_SIZE..].to_vec();
 
         // Decrypt the layer
         let encrypted_msg = EncryptedMessage { nonce, ciphertext };
@@ -660,6 +1133,7 @@ mod tests {
                     latency_ms: 50.0,
                     available: true,
                     public_key: X25519PublicKey::from(&keypair.public_key),
+                    kem_public_key: None,
                 }
             })
             .collect()
@@ -1261,4 +1735,385 @@ mod tests {
         let result = router.build_onion_layers_sync(&route, payload);
         assert!(result.is_ok());
     }
+
+    /// Route through `hops` to `dest` with hybrid layers; returns the route
+    /// and the routers of each node on it (source first)
+    fn hybrid_route(hops: &[u8], dest: u8) -> (OnionRoute, Vec<OnionRouter>) {
+        let ids: Vec<NodeId> = std::iter::once(0)
+            .chain(hops.iter().copied())
+            .chain(std::iter::once(dest))
+            .map(|id| NodeId::from_bytes([id; NODE_ID_SIZE]))
+            .collect();
+
+        let mut route = OnionRoute::new(
+            ids[0],
+            ids[ids.len() - 1],
+            ids[1..ids.len() - 1].to_vec(),
+            3600,
+        )
+        .with_key_exchange(KeyExchangeMode::Hybrid);
+        let mut routers = Vec::new();
+        for &id in &ids {
+            let keypair = KeyExchangeKeypair::generate();
+            let kem_keypair = KemKeypair::generate();
+            route.set_hop_public_key(id, X25519PublicKey::from(&keypair.public_key));
+            route.set_hop_kem_key(id, kem_keypair.public_key.clone());
+            routers.push(OnionRouter::new_default(id, keypair).with_kem_keypair(kem_keypair));
+        }
+        (route, routers)
+    }
+
+    #[test]
+    fn test_hybrid_end_to_end_onion_encryption() {
+        myriadmesh_crypto::init().unwrap();
+
+        let (route, routers) = hybrid_route(&[1, 2], 3);
+        let original_payload = b"Secret message for destination";
+        let layers = routers[0]
+            .build_onion_layers_sync(&route, original_payload)
+            .unwrap();
+        assert_eq!(layers.len(), 4);
+        assert!(layers
+            .iter()
+            .all(|layer| layer.key_exchange == KeyExchangeMode::Hybrid));
+
+        // Each hybrid layer carries the ML-KEM ciphertext on top of the
+        // classical overhead
+        let classical_overhead = X25519_PUBLIC_KEY_SIZE + NONCE_SIZE + 16;
+        assert_eq!(
+            layers[3].encrypted_payload.len(),
+            original_payload.len() + classical_overhead + KEM_CIPHERTEXT_SIZE
+        );
+
+        // Peel hop by hop, forwarding with the incoming layer's mode
+        let mut layer = layers[1].clone();
+        for (i, router) in routers.iter().enumerate().skip(1) {
+            let (next_hop, payload) = router.peel_layer_sync(&layer).unwrap();
+            if i == routers.len() - 1 {
+                assert_eq!(next_hop, None);
+                assert_eq!(payload, original_payload);
+            } else {
+                let next_hop = next_hop.unwrap();
+                assert_eq!(next_hop, route.full_path()[i + 1]);
+                layer = OnionLayer::with_key_exchange(next_hop, payload, layer.key_exchange);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hybrid_layer_needs_kem_keypair() {
+        myriadmesh_crypto::init().unwrap();
+
+        let (route, routers) = hybrid_route(&[1], 2);
+        let layers = routers[0]
+            .build_onion_layers_sync(&route, b"payload")
+            .unwrap();
+
+        // The hop's X25519 key alone can't peel a hybrid layer
+        let classical_only =
+            OnionRouter::new_default(routers[1].local_node_id, routers[1].local_keypair.clone());
+        assert!(classical_only.peel_layer_sync(&layers[1]).is_err());
+
+        // Nor can a different ML-KEM key
+        let wrong_kem =
+            OnionRouter::new_default(routers[1].local_node_id, routers[1].local_keypair.clone())
+                .with_kem_keypair(KemKeypair::generate());
+        assert!(wrong_kem.peel_layer_sync(&layers[1]).is_err());
+
+        // Missing ML-KEM keys fail the build
+        let mut incomplete = route.clone();
+        incomplete.hop_kem_keys.clear();
+        assert!(routers[0]
+            .build_onion_layers_sync(&incomplete, b"payload")
+            .is_err());
+    }
+
+    #[test]
+    fn test_hybrid_route_selection() {
+        myriadmesh_crypto::init().unwrap();
+        let local = NodeId::from_bytes([0u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([255u8; NODE_ID_SIZE]);
+
+        let mut nodes = create_test_nodes(10);
+        for node in nodes.iter_mut().step_by(2) {
+            node.kem_public_key = Some(KemKeypair::generate().public_key);
+        }
+        let pq_capable: Vec<NodeId> = nodes
+            .iter()
+            .filter(|n| n.kem_public_key.is_some())
+            .map(|n| n.node_id)
+            .collect();
+
+        let config = OnionConfig {
+            key_exchange: KeyExchangeMode::Hybrid,
+            ..Default::default()
+        };
+        let mut router = OnionRouter::new(local, KeyExchangeKeypair::generate(), config);
+
+        // The destination must support ML-KEM too
+        let mut all_nodes = nodes.clone();
+        all_nodes.push(RouteNode {
+            node_id: dest,
+            reliability: 1.0,
+            latency_ms: 10.0,
+            available: true,
+            public_key: X25519PublicKey::from(&KeyExchangeKeypair::generate().public_key),
+            kem_public_key: None,
+        });
+        assert!(router.select_route(dest, &all_nodes).is_err());
+
+        all_nodes.last_mut().unwrap().kem_public_key = Some(KemKeypair::generate().public_key);
+        let route = router.select_route(dest, &all_nodes).unwrap();
+        assert_eq!(route.key_exchange, KeyExchangeMode::Hybrid);
+        assert!(route.hops.iter().all(|hop| pq_capable.contains(hop)));
+        assert!(route
+            .hops
+            .iter()
+            .all(|hop| route.hop_kem_keys.contains_key(hop)));
+        assert!(route.hop_kem_keys.contains_key(&dest));
+    }
+
+    #[test]
+    fn test_neighbor_traffic_accounting() {
+        myriadmesh_crypto::init().unwrap();
+
+        let local = NodeId::from_bytes([0u8; NODE_ID_SIZE]);
+        let relay = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let flooder = NodeId::from_bytes([9u8; NODE_ID_SIZE]);
+        let honest = NodeId::from_bytes([8u8; NODE_ID_SIZE]);
+
+        let relay_kp = KeyExchangeKeypair::generate();
+        let mut route = OnionRoute::new(local, dest, vec![relay], 3600);
+        route.set_hop_public_key(
+            local,
+            X25519PublicKey::from(&KeyExchangeKeypair::generate().public_key),
+        );
+        route.set_hop_public_key(relay, X25519PublicKey::from(&relay_kp.public_key));
+        route.set_hop_public_key(
+            dest,
+            X25519PublicKey::from(&KeyExchangeKeypair::generate().public_key),
+        );
+
+        let source = OnionRouter::new_default(local, KeyExchangeKeypair::generate());
+        let layer = source.build_onion_layers_sync(&route, b"payload").unwrap()[1].clone();
+        let layer_len = layer.encrypted_payload.len() as u64;
+
+        let relay_router = OnionRouter::new_default(relay, relay_kp);
+        for _ in 0..5 {
+            let (next_hop, _) = relay_router.peel_layer_from_sync(flooder, &layer).unwrap();
+            assert_eq!(next_hop, Some(dest));
+        }
+        relay_router.peel_layer_from_sync(honest, &layer).unwrap();
+
+        assert_eq!(
+            relay_router.neighbor_traffic(&flooder),
+            NeighborTraffic {
+                bytes: 5 * layer_len,
+                packets: 5,
+            }
+        );
+        assert_eq!(relay_router.neighbor_traffic(&honest).packets, 1);
+        assert_eq!(relay_router.all_neighbor_traffic().len(), 2);
+        let share = relay_router.neighbor_traffic_share(&flooder);
+        assert!((share - 5.0 / 6.0).abs() < 1e-9);
+
+        // Layers that fail to peel still count
+        let mut junk = layer.clone();
+        junk.encrypted_payload.truncate(10);
+        assert!(relay_router.peel_layer_from_sync(honest, &junk).is_err());
+        assert_eq!(relay_router.neighbor_traffic(&honest).packets, 2);
+
+        // Unknown neighbors have no traffic
+        assert_eq!(
+            relay_router.neighbor_traffic(&dest),
+            NeighborTraffic::default()
+        );
+    }
+
+    #[test]
+    fn test_neighbor_traffic_window_ages_out() {
+        let local = NodeId::from_bytes([0u8; NODE_ID_SIZE]);
+        let neighbor = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let config = OnionConfig {
+            traffic_window_secs: 10,
+            ..Default::default()
+        };
+        let router = OnionRouter::new(local, KeyExchangeKeypair::generate(), config);
+        let layer = OnionLayer::new(local, vec![0u8; 100]);
+
+        let start = Instant::now();
+        router.record_traffic(neighbor, &layer, start);
+        router.record_traffic(neighbor, &layer, start + Duration::from_secs(6));
+
+        let at = |secs| router.neighbor_traffic_at(&neighbor, start + Duration::from_secs(secs));
+        assert_eq!(at(9).packets, 2);
+        assert_eq!(at(9).bytes, 200);
+
+        // The first layer leaves the window after 10 seconds
+        assert_eq!(at(11).packets, 1);
+
+        // Everything has aged out; the neighbor is forgotten
+        assert_eq!(at(17), NeighborTraffic::default());
+        assert!(router
+            .all_neighbor_traffic_at(start + Duration::from_secs(17))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_quiet_neighbors_swept_once_per_window() {
+        let quiet = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let busy = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut traffic = TrafficAccounting::new(Duration::from_secs(10), start);
+
+        traffic.record(quiet, 100, at(0));
+        traffic.record(busy, 100, at(5));
+        // Within the first window nothing is swept
+        traffic.record(busy, 100, at(9));
+        assert_eq!(traffic.neighbors.len(), 2);
+
+        // The quiet neighbor has aged out by the next sweep
+        traffic.record(busy, 100, at(12));
+        assert_eq!(traffic.neighbors.len(), 1);
+        assert_eq!(traffic.last_sweep, 12);
+        // The busy neighbor's own slots expire as it records: 5 is gone
+        traffic.record(busy, 100, at(16));
+        let slots: Vec<u64> = traffic.neighbors[&busy].iter().map(|(s, _)| *s).collect();
+        assert_eq!(slots, vec![9, 12, 16]);
+    }
+
+    #[test]
+    fn test_stats_count_built_and_peeled_layers() {
+        myriadmesh_crypto::init().unwrap();
+
+        let local = NodeId::from_bytes([0u8; NODE_ID_SIZE]);
+        let next = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+
+        let local_kp = KeyExchangeKeypair::generate();
+        let next_kp = KeyExchangeKeypair::generate();
+        let dest_kp = KeyExchangeKeypair::generate();
+
+        let mut route = OnionRoute::new(local, dest, vec![next], 3600);
+        route.set_hop_public_key(local, X25519PublicKey::from(&local_kp.public_key));
+        route.set_hop_public_key(next, X25519PublicKey::from(&next_kp.public_key));
+        route.set_hop_public_key(dest, X25519PublicKey::from(&dest_kp.public_key));
+
+        let router = OnionRouter::new_default(local, local_kp);
+        assert_eq!(router.stats(), OnionRouterStats::default());
+        let layers = router.build_onion_layers_sync(&route, b"payload").unwrap();
+        assert_eq!(router.stats().onions_built, 1);
+
+        let next_router = OnionRouter::new_default(next, next_kp);
+        next_router.peel_layer_sync(&layers[1]).unwrap();
+        // Layer for the destination is not ours to open
+        assert!(next_router.peel_layer_sync(&layers[2]).is_err());
+
+        let stats = next_router.stats();
+        assert_eq!(stats.layers_peeled, 1);
+        assert_eq!(stats.peel_failures, 1);
+        assert_eq!(stats.onions_built, 0);
+    }
+
+    /// Clock that only moves when told to
+    struct ManualClock(Mutex<Instant>);
+
+    impl ManualClock {
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+
+    impl OnionClock for ManualClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn seeded_router(seed: u64, strategy: RouteSelectionStrategy) -> OnionRouter {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let config = OnionConfig {
+            selection_strategy: strategy,
+            ..Default::default()
+        };
+        OnionRouter::new(
+            NodeId::from_bytes([0xFFu8; NODE_ID_SIZE]),
+            KeyExchangeKeypair::generate(),
+            config,
+        )
+        .with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    #[test]
+    fn test_seeded_rng_selects_same_route() {
+        let nodes = create_test_nodes(20);
+        let dest = NodeId::from_bytes([0xEEu8; NODE_ID_SIZE]);
+
+        for strategy in [
+            RouteSelectionStrategy::Random,
+            RouteSelectionStrategy::HighReliability,
+            RouteSelectionStrategy::LowLatency,
+            RouteSelectionStrategy::Balanced,
+        ] {
+            let mut first = seeded_router(42, strategy);
+            let mut second = seeded_router(42, strategy);
+
+            for _ in 0..5 {
+                let a = first.select_route(dest, &nodes).unwrap();
+                let b = second.select_route(dest, &nodes).unwrap();
+                assert_eq!(a.hops, b.hops);
+                assert_eq!(a.route_id, b.route_id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeded_rng_reproduces_delays() {
+        let first = seeded_router(7, RouteSelectionStrategy::Random);
+        let second = seeded_router(7, RouteSelectionStrategy::Random);
+
+        for _ in 0..10 {
+            let delay = first.forward_delay();
+            assert_eq!(delay, second.forward_delay());
+            assert!(delay >= Duration::from_millis(MIN_FORWARD_DELAY_MS));
+            assert!(delay <= Duration::from_millis(MAX_FORWARD_JITTER_MS));
+        }
+
+        let elapsed = Duration::from_millis(40);
+        let padding = first.padding_delay(elapsed).unwrap();
+        assert_eq!(Some(padding), second.padding_delay(elapsed));
+        // 60ms remaining, ±20%
+        assert!(padding >= Duration::from_millis(48) && padding <= Duration::from_millis(72));
+
+        assert_eq!(
+            first.padding_delay(Duration::from_millis(TARGET_BUILD_TIME_MS)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_manual_clock_drives_traffic_window() {
+        let clock = Arc::new(ManualClock(Mutex::new(Instant::now())));
+        let local = NodeId::from_bytes([0u8; NODE_ID_SIZE]);
+        let neighbor = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let config = OnionConfig {
+            traffic_window_secs: 10,
+            ..Default::default()
+        };
+        let router = OnionRouter::new(local, KeyExchangeKeypair::generate(), config)
+            .with_clock(clock.clone());
+
+        let layer = OnionLayer::new(local, vec![0u8; 100]);
+        let _ = router.peel_layer_from_sync(neighbor, &layer);
+        assert_eq!(router.neighbor_traffic(&neighbor).packets, 1);
+
+        clock.advance(Duration::from_secs(11));
+        assert_eq!(
+            router.neighbor_traffic(&neighbor),
+            NeighborTraffic::default()
+        );
+    }
 }
@@ -4,9 +4,12 @@
 //! - Message padding (prevent traffic analysis)
 //! - Timing obfuscation (prevent timing correlation)
 //! - Cover traffic generation (prevent traffic pattern analysis)
+//! - Decoy destinations for cover traffic (hide the real peer set)
 //!
 //! SECURITY C5: Comprehensive timing attack prevention through random delays
 
+use myriadmesh_protocol::NodeId;
+use rand::distributions::{Distribution, WeightedIndex};
 use rand::Rng;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -17,6 +20,25 @@ pub const MIN_MESSAGE_SIZE: usize = 512;
 /// Maximum padding size (bytes)
 pub const MAX_PADDING_SIZE: usize = 1024;
 
+/// Default number of messages released together in one batch
+pub const DEFAULT_BATCH_SIZE: usize = 8;
+
+/// SECURITY H6: Granular bucket sizes to reduce information leakage.
+/// Smaller increments at lower sizes where most messages fall. The 16-32KB
+/// buckets cover onions built with hybrid (ML-KEM) layers, which add about
+/// 1.1KB per hop.
+const PADDING_BUCKETS: [usize; 27] = [
+    256, 384, 512, 640, 768, 896, 1024, // 128-byte increments up to 1KB
+    1280, 1536, 1792, 2048, // 256-byte increments 1-2KB
+    2560, 3072, 3584, 4096, // 512-byte increments 2-4KB
+    5120, 6144, 7168, 8192, // 1KB increments 4-8KB
+    10240, 12288, 14336, 16384, // 2KB increments 8-16KB
+    20480, 24576, 28672, 32768, // 4KB increments 16-32KB
+];
+
+/// Largest padding `FixedBuckets` can add (the widest bucket increment)
+const MAX_BUCKET_PADDING: usize = 4096;
+
 /// Message padding strategy
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PaddingStrategy {
@@ -78,6 +100,16 @@ pub struct PrivacyConfig {
 
     /// Cover traffic rate (messages per hour)
     pub cover_traffic_rate: u32,
+
+    /// Number of messages released together by `PrivacyLayer::batch`
+    pub batch_size: usize,
+
+    /// Minimum fraction (0.0-1.0) of every batch reserved for cover messages
+    pub batch_cover_ratio: f64,
+
+    /// Fraction (0.0-1.0) of cover messages sent to decoy destinations
+    /// instead of real peers (needs decoy candidates on the layer)
+    pub decoy_ratio: f64,
 }
 
 impl Default for PrivacyConfig {
@@ -91,20 +123,112 @@ impl Default for PrivacyConfig {
             max_delay_ms: 500,
             enable_cover_traffic: false,
             cover_traffic_rate: 10,
+            batch_size: DEFAULT_BATCH_SIZE,
+            batch_cover_ratio: 0.25,
+            decoy_ratio: 0.0,
         }
     }
 }
 
+/// Destination cover traffic may be sent to without being a real contact
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecoyCandidate {
+    pub destination: NodeId,
+    /// Relative selection weight (non-positive weights are never chosen)
+    pub weight: f64,
+}
+
 /// Privacy protection layer
 #[derive(Default)]
 pub struct PrivacyLayer {
     config: PrivacyConfig,
+    /// Decoy destinations for cover traffic
+    decoys: Vec<DecoyCandidate>,
 }
 
 impl PrivacyLayer {
     /// Create new privacy layer
     pub fn new(config: PrivacyConfig) -> Self {
-        PrivacyLayer { config }
+        PrivacyLayer {
+            config,
+            decoys: Vec::new(),
+        }
+    }
+
+    /// Replace the decoy destinations cover traffic may target
+    pub fn set_decoy_candidates(&mut self, candidates: Vec<DecoyCandidate>) {
+        self.decoys = candidates;
+    }
+
+    /// Use `candidates` as decoys, weighted like real traffic
+    ///
+    /// `real_message_counts` holds messages sent to each real peer. Decoys
+    /// take on those counts by rank, so the busiest decoy is chosen as often
+    /// as the busiest real peer is contacted and so on; an observer sees the
+    /// same skewed destination distribution for decoys as for real contacts.
+    /// With no real traffic yet, decoys are weighted equally.
+    pub fn set_decoys_mimicking(&mut self, candidates: &[NodeId], real_message_counts: &[u64]) {
+        let mut ranked: Vec<u64> = real_message_counts
+            .iter()
+            .copied()
+            .filter(|&count| count > 0)
+            .collect();
+        ranked.sort_unstable_by(|a, b| b.cmp(a));
+
+        self.decoys = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, &destination)| DecoyCandidate {
+                destination,
+                weight: match ranked.len() {
+                    0 => 1.0,
+                    n => ranked[i % n] as f64,
+                },
+            })
+            .collect();
+    }
+
+    /// Decoy destinations currently configured
+    pub fn decoy_candidates(&self) -> &[DecoyCandidate] {
+        &self.decoys
+    }
+
+    /// Pick the destination for the next cover message
+    ///
+    /// SECURITY: With probability `decoy_ratio` a decoy is drawn by weight,
+    /// otherwise a real peer is drawn uniformly, so cover traffic does not
+    /// enumerate the real peer set. Returns `None` if there is nothing to
+    /// pick from.
+    pub fn select_cover_destination(&self, real_peers: &[NodeId]) -> Option<NodeId> {
+        self.select_cover_destination_with(real_peers, &mut rand::thread_rng())
+    }
+
+    /// [`Self::select_cover_destination`] with a caller-supplied RNG
+    pub fn select_cover_destination_with<R: Rng>(
+        &self,
+        real_peers: &[NodeId],
+        rng: &mut R,
+    ) -> Option<NodeId> {
+        let ratio = self.config.decoy_ratio.clamp(0.0, 1.0);
+        if real_peers.is_empty() || rng.gen_bool(ratio) {
+            if let Some(decoy) = self.choose_decoy(rng) {
+                return Some(decoy);
+            }
+        }
+        real_peers.choose(rng).copied()
+    }
+
+    fn choose_decoy<R: Rng>(&self, rng: &mut R) -> Option<NodeId> {
+        let weights = self.decoys.iter().map(|decoy| {
+            if decoy.weight.is_finite() {
+                decoy.weight.max(0.0)
+            } else {
+                0.0
+            }
+        });
+        // Fails when there are no decoys or every weight is zero
+        let index = WeightedIndex::new(weights).ok()?.sample(rng);
+        Some(self.decoys[index].destination)
     }
 
     /// Apply message padding to data
@@ -125,23 +249,7 @@ impl PrivacyLayer {
             }
 
             PaddingStrategy::FixedBuckets => {
-                // SECURITY H6: More granular bucket sizes to reduce information leakage
-                // Smaller increments at lower sizes where most messages fall
-                let buckets = [
-                    256, 384, 512, 640, 768, 896, 1024, // 128-byte increments up to 1KB
-                    1280, 1536, 1792, 2048, // 256-byte increments 1-2KB
-                    2560, 3072, 3584, 4096, // 512-byte increments 2-4KB
-                    5120, 6144, 7168, 8192, // 1KB increments 4-8KB
-                    10240, 12288, 14336, 16384, // 2KB increments 8-16KB
-                ];
-
-                // Find the smallest bucket that fits the data and respects min_message_size
-                let min_size = self.config.min_message_size.max(data.len() + 2);
-                let target_size = buckets
-                    .iter()
-                    .find(|&&size| size >= min_size)
-                    .copied()
-                    .unwrap_or(min_size);
+                let target_size = self.bucket_size(data.len());
 
                 if target_size > data.len() + 2 {
                     let padding_needed = target_size - data.len() - 2;
@@ -198,7 +306,8 @@ impl PrivacyLayer {
         // Read the last portion to find padding_length
         // We iterate from largest to smallest to find the actual padding before any spurious matches
         // in the random padding bytes
-        let max_potential = MAX_PADDING_SIZE.min(total_len.saturating_sub(2));
+        let max_padding = MAX_PADDING_SIZE.max(MAX_BUCKET_PADDING);
+        let max_potential = max_padding.min(total_len.saturating_sub(2));
         for potential_padding_len in (0..=max_potential).rev() {
             if total_len < potential_padding_len + 2 {
                 continue;
@@ -215,7 +324,7 @@ impl PrivacyLayer {
 
             // Check if this indicated length matches our position
             if indicated_padding_len == potential_padding_len
-                && indicated_padding_len <= MAX_PADDING_SIZE
+                && indicated_padding_len <= max_padding
             {
                 // This looks like the correct padding length
                 // Data is everything before the padding_length u16
@@ -228,6 +337,82 @@ impl PrivacyLayer {
         Ok(padded.to_vec())
     }
 
+    /// Smallest padding bucket that fits `data_len` plus the length indicator
+    /// and respects `min_message_size`
+    fn bucket_size(&self, data_len: usize) -> usize {
+        let min_size = self.config.min_message_size.max(data_len + 2);
+        PADDING_BUCKETS
+            .iter()
+            .find(|&&size| size >= min_size)
+            .copied()
+            .unwrap_or(min_size)
+    }
+
+    /// Mix real messages with cover messages and release them as shuffled batches
+    ///
+    /// SECURITY: Releasing messages one at a time leaks message counts even when
+    /// each message is delayed. Every batch contains exactly `batch_size`
+    /// elements, at least `batch_cover_ratio` of which are cover messages, in a
+    /// random order. All elements of a batch are independently padded to the
+    /// same size so batch membership cannot be inferred from sizing.
+    ///
+    /// Batch elements always use the padding format, so real messages are
+    /// recovered with `unpad_message` (unless the strategy is `None`). Real
+    /// messages that do not fit into one batch spill over into further batches;
+    /// an empty input yields a single batch of pure cover traffic.
+    pub fn batch(&self, messages: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        let batch_size = self.config.batch_size.max(1);
+        let ratio = self.config.batch_cover_ratio.clamp(0.0, 1.0);
+        let min_cover = (batch_size as f64 * ratio).ceil() as usize;
+        let real_capacity = batch_size.saturating_sub(min_cover).max(1);
+
+        let mut groups: Vec<Vec<Vec<u8>>> = Vec::new();
+        let mut current: Vec<Vec<u8>> = Vec::new();
+        for message in messages {
+            // Start a new batch when full, or when padding every element to the
+            // common size would exceed what unpad_message can recover
+            let fits = current.is_empty() || {
+                let min_len = current.iter().map(Vec::len).min().unwrap_or(0);
+                let max_len = current.iter().map(Vec::len).max().unwrap_or(0);
+                let target = self.bucket_size(max_len.max(message.len()));
+                target - min_len.min(message.len()) - 2 <= MAX_PADDING_SIZE
+            };
+            if current.len() >= real_capacity || !fits {
+                groups.push(std::mem::take(&mut current));
+            }
+            current.push(message);
+        }
+        if !current.is_empty() || groups.is_empty() {
+            groups.push(current);
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut output = Vec::with_capacity(groups.len() * batch_size);
+        for group in groups {
+            let max_len = group.iter().map(Vec::len).max().unwrap_or(0);
+            let target_size = self.bucket_size(max_len);
+
+            let mut batch: Vec<Vec<u8>> = group
+                .iter()
+                .map(|data| self.apply_padding(data, target_size - data.len() - 2))
+                .collect();
+
+            while batch.len() < batch_size {
+                // Cover bodies are random bytes with a random length, so after
+                // padding they are indistinguishable from real elements
+                let max_body = (target_size - 2).min(max_len.max(1));
+                let body_len = rng.gen_range(0..=max_body);
+                let body: Vec<u8> = (0..body_len).map(|_| rng.gen()).collect();
+                batch.push(self.apply_padding(&body, target_size - body_len - 2));
+            }
+
+            rand::seq::SliceRandom::shuffle(batch.as_mut_slice(), &mut rng);
+            output.extend(batch);
+        }
+
+        output
+    }
+
     /// Apply padding to data
     fn apply_padding(&self, data: &[u8], padding_size: usize) -> Vec<u8> {
         let mut result = Vec::with_capacity(data.len() + padding_size + 2);
@@ -854,6 +1039,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_batch_mixes_cover_traffic() {
+        let config = PrivacyConfig {
+            padding_strategy: PaddingStrategy::FixedBuckets,
+            batch_size: 8,
+            batch_cover_ratio: 0.25,
+            ..Default::default()
+        };
+
+        let layer = PrivacyLayer::new(config);
+        let real = vec![b"first real message".to_vec(), b"second".to_vec()];
+        let batch = layer.batch(real.clone());
+
+        // Exactly batch_size outputs, all the same size
+        assert_eq!(batch.len(), 8);
+        let sizes: std::collections::HashSet<_> = batch.iter().map(|m| m.len()).collect();
+        assert_eq!(
+            sizes.len(),
+            1,
+            "Batch elements must be indistinguishable by size"
+        );
+
+        // Both real messages are recoverable after unpadding
+        let unpadded: Vec<Vec<u8>> = batch
+            .iter()
+            .map(|m| layer.unpad_message(m).unwrap())
+            .collect();
+        for message in &real {
+            assert!(unpadded.contains(message));
+        }
+    }
+
+    #[test]
+    fn test_batch_overflow_and_empty() {
+        let config = PrivacyConfig {
+            batch_size: 4,
+            batch_cover_ratio: 0.5,
+            ..Default::default()
+        };
+
+        let layer = PrivacyLayer::new(config);
+
+        // Only 2 real slots per batch, so 3 real messages need two batches
+        let batch = layer.batch(vec![vec![1; 10], vec![2; 10], vec![3; 10]]);
+        assert_eq!(batch.len(), 8);
+
+        // An empty input still emits a full batch of cover traffic
+        let batch = layer.batch(Vec::new());
+        assert_eq!(batch.len(), 4);
+    }
+
     #[test]
     fn test_bucket_granularity_improvement() {
         // SECURITY H6: Compare old vs new bucket granularity
@@ -879,4 +1115,127 @@ mod tests {
             padded_600.len()
         );
     }
+
+    #[test]
+    fn test_padding_roundtrip_large_messages() {
+        // Hybrid onions land in the 8-32KB buckets, where padding can exceed
+        // MAX_PADDING_SIZE
+        let layer = PrivacyLayer::new(PrivacyConfig {
+            padding_strategy: PaddingStrategy::FixedBuckets,
+            ..Default::default()
+        });
+
+        for (size, bucket) in [(9000, 10240), (16500, 20480), (28673, 32768)] {
+            let data = vec![0u8; size];
+            let padded = layer.pad_message(&data);
+            assert_eq!(padded.len(), bucket);
+            assert_eq!(layer.unpad_message(&padded).unwrap(), data);
+        }
+    }
+
+    fn node(byte: u8) -> NodeId {
+        NodeId::from_bytes([byte; myriadmesh_protocol::types::NODE_ID_SIZE])
+    }
+
+    /// Fraction of `draws` cover messages sent to each destination
+    fn cover_distribution(
+        layer: &PrivacyLayer,
+        real_peers: &[NodeId],
+        draws: usize,
+    ) -> std::collections::HashMap<NodeId, f64> {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..draws {
+            let destination = layer
+                .select_cover_destination_with(real_peers, &mut rng)
+                .unwrap();
+            *counts.entry(destination).or_insert(0usize) += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(node, count)| (node, count as f64 / draws as f64))
+            .collect()
+    }
+
+    #[test]
+    fn test_decoys_follow_configured_weights() {
+        let mut layer = PrivacyLayer::new(PrivacyConfig {
+            decoy_ratio: 1.0,
+            ..Default::default()
+        });
+        layer.set_decoy_candidates(vec![
+            DecoyCandidate {
+                destination: node(10),
+                weight: 1.0,
+            },
+            DecoyCandidate {
+                destination: node(11),
+                weight: 3.0,
+            },
+            DecoyCandidate {
+                destination: node(12),
+                weight: 6.0,
+            },
+            DecoyCandidate {
+                destination: node(13),
+                weight: 0.0,
+            },
+        ]);
+
+        let shares = cover_distribution(&layer, &[node(1)], 20_000);
+        for (destination, expected) in [(node(10), 0.1), (node(11), 0.3), (node(12), 0.6)] {
+            let share = shares[&destination];
+            assert!((share - expected).abs() < 0.02, "share {}", share);
+        }
+        // Zero weight decoys and, at ratio 1.0, real peers are never picked
+        assert!(!shares.contains_key(&node(13)));
+        assert!(!shares.contains_key(&node(1)));
+    }
+
+    #[test]
+    fn test_decoy_ratio_mixes_real_and_decoy() {
+        let mut layer = PrivacyLayer::new(PrivacyConfig {
+            decoy_ratio: 0.3,
+            ..Default::default()
+        });
+        layer.set_decoys_mimicking(&[node(10), node(11)], &[]);
+
+        let real = [node(1), node(2)];
+        let shares = cover_distribution(&layer, &real, 20_000);
+        let decoy_share = shares[&node(10)] + shares[&node(11)];
+        assert!((decoy_share - 0.3).abs() < 0.02, "decoys {}", decoy_share);
+        // No real traffic yet: decoys weighted equally
+        assert!((shares[&node(10)] - shares[&node(11)]).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_decoys_mimic_real_traffic_skew() {
+        let mut layer = PrivacyLayer::new(PrivacyConfig {
+            decoy_ratio: 1.0,
+            ..Default::default()
+        });
+        // Real traffic: one busy contact, two quieter ones, one idle
+        layer.set_decoys_mimicking(&[node(10), node(11), node(12)], &[10, 70, 0, 20]);
+
+        let weights: Vec<f64> = layer.decoy_candidates().iter().map(|d| d.weight).collect();
+        assert_eq!(weights, vec![70.0, 20.0, 10.0]);
+
+        let shares = cover_distribution(&layer, &[], 20_000);
+        for (destination, expected) in [(node(10), 0.7), (node(11), 0.2), (node(12), 0.1)] {
+            let share = shares[&destination];
+            assert!((share - expected).abs() < 0.02, "share {}", share);
+        }
+    }
+
+    #[test]
+    fn test_cover_destination_without_decoys() {
+        let layer = PrivacyLayer::new(PrivacyConfig {
+            decoy_ratio: 1.0,
+            ..Default::default()
+        });
+        // No decoys configured: fall back to real peers
+        assert_eq!(layer.select_cover_destination(&[node(1)]), Some(node(1)));
+        assert_eq!(layer.select_cover_destination(&[]), None);
+    }
 }
@@ -3,10 +3,10 @@
 //! This module provides utilities for securely transmitting i2p capability
 //! tokens between nodes using end-to-end encryption.
 
-use crate::capability_token::I2pCapabilityToken;
+use crate::capability_token::{I2pCapabilityToken, TokenAudience};
 use crate::dual_identity::DualIdentity;
 use myriadmesh_crypto::channel::{EncryptedChannel, KeyExchangeRequest, KeyExchangeResponse};
-use myriadmesh_crypto::keyexchange::KeyExchangeKeypair;
+use myriadmesh_crypto::keyexchange::{KeyExchangeKeypair, X25519PublicKey};
 use serde::{Deserialize, Serialize};
 
 /// Encrypted capability token message
@@ -34,6 +34,19 @@ impl SecureTokenExchange {
         }
     }
 
+    /// Audience of the channel whose remote end holds `peer_public_key`
+    ///
+    /// Tokens granted over a channel are bound to the requester's key, so
+    /// they are only honoured when presented over a channel with that peer.
+    pub fn channel_audience(peer_public_key: &X25519PublicKey) -> TokenAudience {
+        TokenAudience::from_peer_key(peer_public_key.as_bytes())
+    }
+
+    /// Audience tokens granted to this node over its channels are bound to
+    pub fn local_audience(&self) -> TokenAudience {
+        Self::channel_audience(&X25519PublicKey::from(&self.kx_keypair.public_key))
+    }
+
     /// Create a key exchange request to establish encrypted channel
     pub fn create_key_exchange_request(
         &self,
@@ -124,8 +137,18 @@ impl SecureTokenExchange {
             .map_err(|e| format!("Decryption failed: {}", e))?;
 
         // Deserialize token
-        bincode::deserialize(&decrypted_bytes)
-            .map_err(|e| format!("Token deserialization failed: {}", e))
+        let token = I2pCapabilityToken::from_bytes(&decrypted_bytes)
+            .map_err(|e| format!("Token deserialization failed: {}", e))?;
+
+        // SECURITY: A bound token must be bound to this end of the channel,
+        // otherwise it was issued for someone else and forwarded here
+        if let Some(audience) = &token.audience {
+            if *audience != self.local_audience() {
+                return Err("Token bound to a different channel".to_string());
+            }
+        }
+
+        Ok(token)
     }
 
     /// Grant i2p access and send encrypted token
@@ -140,10 +163,14 @@ impl SecureTokenExchange {
         validity_days: u32,
         kx_request: &KeyExchangeRequest,
     ) -> Result<(I2pCapabilityToken, EncryptedTokenMessage), String> {
-        // Grant access (creates token)
+        // Grant access (creates token), bound to the requester's channel key
         let token = self
             .identity
-            .grant_i2p_access(recipient_node_id, validity_days as u64)
+            .grant_i2p_access_bound(
+                recipient_node_id,
+                validity_days as u64,
+                Self::channel_audience(&kx_request.public_key),
+            )
             .map_err(|e| format!("Failed to grant access: {}", e))?;
 
         // Create response for key exchange
@@ -265,4 +292,59 @@ mod tests {
         );
         assert_eq!(alice_token.i2p_node_id, alice_identity.get_i2p_node_id());
     }
+
+    #[test]
+    fn test_granted_token_bound_to_requester_channel() {
+        myriadmesh_crypto::init().unwrap();
+
+        let alice_dest = I2pDestination::new("alice.b32.i2p".to_string());
+        let alice_identity = DualIdentity::generate(alice_dest).unwrap();
+        let mut alice_exchange =
+            SecureTokenExchange::new(alice_identity.clone(), KeyExchangeKeypair::generate());
+
+        let bob_dest = I2pDestination::new("bob.b32.i2p".to_string());
+        let bob_identity = DualIdentity::generate(bob_dest).unwrap();
+        let bob_id = bob_identity.get_clearnet_node_id();
+        let bob_exchange =
+            SecureTokenExchange::new(bob_identity.clone(), KeyExchangeKeypair::generate());
+
+        let kx_request = bob_exchange
+            .create_key_exchange_request(alice_identity.get_clearnet_node_id())
+            .unwrap();
+        let (token, _encrypted) = alice_exchange
+            .grant_access_with_encryption(bob_id, 30, &kx_request)
+            .unwrap();
+        assert_eq!(token.audience, Some(bob_exchange.local_audience()));
+
+        // Alice honours it only over a channel with Bob's key
+        assert!(alice_identity
+            .verify_presented_token(&token, &bob_id, &bob_exchange.local_audience())
+            .unwrap());
+        assert!(!alice_identity
+            .verify_presented_token(&token, &bob_id, &alice_exchange.local_audience())
+            .unwrap());
+
+        // Bob accepts a token bound to his end of the channel
+        let kx_response = alice_exchange
+            .process_key_exchange_request(&kx_request)
+            .unwrap();
+        let encrypted = alice_exchange
+            .encrypt_token(&token, &kx_request, &kx_response)
+            .unwrap();
+        let decrypted = bob_exchange
+            .decrypt_token(&encrypted, &kx_request, &kx_response)
+            .unwrap();
+        assert_eq!(decrypted.audience, token.audience);
+
+        // but refuses one bound to another channel
+        let misbound = alice_identity
+            .grant_i2p_access_bound(bob_id, 30, alice_exchange.local_audience())
+            .unwrap();
+        let encrypted = alice_exchange
+            .encrypt_token(&misbound, &kx_request, &kx_response)
+            .unwrap();
+        assert!(bob_exchange
+            .decrypt_token(&encrypted, &kx_request, &kx_response)
+            .is_err());
+    }
 }
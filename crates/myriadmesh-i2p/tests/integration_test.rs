@@ -7,7 +7,7 @@
 //! - Onion routing
 
 use myriadmesh_crypto::identity::NodeIdentity;
-use myriadmesh_crypto::keyexchange::{KeyExchangeKeypair, X25519PublicKey};
+use myriadmesh_crypto::keyexchange::{KeyExchangeKeypair, KeyExchangeMode, X25519PublicKey};
 use myriadmesh_i2p::{
     DualIdentity, I2pDestination, OnionConfig, OnionRouter, PaddingStrategy, PrivacyConfig,
     PrivacyLayer, RouteSelectionStrategy, TimingStrategy,
@@ -30,6 +30,7 @@ fn create_test_route_node(
         latency_ms: latency,
         available: true,
         public_key: X25519PublicKey::from(&keypair.public_key),
+        kem_public_key: None,
     }
 }
 
@@ -110,6 +111,9 @@ fn test_privacy_layer_message_protection() {
         max_delay_ms: 200,
         enable_cover_traffic: true,
         cover_traffic_rate: 10,
+        batch_size: 8,
+        batch_cover_ratio: 0.25,
+        decoy_ratio: 0.5,
     };
 
     let layer = PrivacyLayer::new(config);
@@ -173,6 +177,8 @@ fn test_onion_routing_multi_hop() {
         selection_strategy: RouteSelectionStrategy::Balanced,
         max_route_lifetime: 3600,
         randomize_routes: true,
+        key_exchange: KeyExchangeMode::Classical,
+        traffic_window_secs: 60,
     };
 
     let local_keypair = KeyExchangeKeypair::generate();
@@ -13,6 +13,8 @@ pub enum AdapterStatus {
     Uninitialized,
     /// Adapter is initializing
     Initializing,
+    /// Adapter is connected but still building its anonymity tunnels
+    BuildingTunnels,
     /// Adapter is ready and operational
     Ready,
     /// Adapter is temporarily unavailable
@@ -28,6 +30,7 @@ impl fmt::Display for AdapterStatus {
         match self {
             AdapterStatus::Uninitialized => write!(f, "Uninitialized"),
             AdapterStatus::Initializing => write!(f, "Initializing"),
+            AdapterStatus::BuildingTunnels => write!(f, "Building Tunnels"),
             AdapterStatus::Ready => write!(f, "Ready"),
             AdapterStatus::Unavailable => write!(f, "Unavailable"),
             AdapterStatus::Error => write!(f, "Error"),
@@ -76,6 +79,17 @@ pub trait NetworkAdapter: Send + Sync {
     /// Send a frame to a destination
     async fn send(&self, destination: &Address, frame: &Frame) -> Result<()>;
 
+    /// Attempt to send a frame without waiting
+    ///
+    /// Returns `Ok(true)` if the frame was handed to the transport, or
+    /// `Ok(false)` if sending would have to wait (busy socket, full buffer).
+    /// Intended for latency-critical traffic that would rather fall back to
+    /// another adapter than queue. Adapters without a non-blocking path
+    /// always return `Ok(false)`.
+    fn try_send(&self, _destination: &Address, _frame: &Frame) -> Result<bool> {
+        Ok(false)
+    }
+
     /// Receive the next frame (blocking until frame arrives or timeout)
     async fn receive(&self, timeout_ms: u64) -> Result<(Address, Frame)>;
 
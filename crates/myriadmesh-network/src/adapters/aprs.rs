@@ -15,15 +15,56 @@
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
 use crate::license::LicenseManager;
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
-use myriadmesh_protocol::{types::AdapterType, Frame};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
+use myriadmesh_protocol::{types::AdapterType, Frame, NodeId};
+use myriadmesh_routing::GeoCoordinates;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
 use tokio::task::JoinHandle;
 
 type FrameReceiver = Arc<RwLock<Option<mpsc::Receiver<(Address, Frame)>>>>;
+type HeardStations = Arc<RwLock<HashMap<Callsign, HeardStation>>>;
+
+/// Amateur radio callsign with optional SSID (e.g., "N0CALL-1")
+pub type Callsign = String;
+
+/// Metres per foot, for the APRS "/A=" altitude extension
+const METERS_PER_FOOT: f64 = 0.3048;
+
+/// APRS map symbol: table selector plus symbol code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AprsSymbol {
+    /// '/' primary table, '\' alternate table (or an overlay character)
+    pub table: char,
+    /// Symbol code within the table
+    pub code: char,
+}
+
+impl AprsSymbol {
+    /// Digipeater (green star), used for mesh nodes
+    pub const NODE: AprsSymbol = AprsSymbol {
+        table: '/',
+        code: '#',
+    };
+}
+
+impl Default for AprsSymbol {
+    fn default() -> Self {
+        Self::NODE
+    }
+}
+
+/// What we know about a station heard on APRS
+#[derive(Debug, Clone, Default)]
+struct HeardStation {
+    /// Last reported position
+    position: Option<GeoCoordinates>,
+    /// MyriadMesh NodeId, learned from frames the station has sent
+    node_id: Option<NodeId>,
+}
 
 /// APRS adapter configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +87,15 @@ pub struct AprsConfig {
     pub license_check: bool,
     /// Use mock TNC (for testing)
     pub use_mock: bool,
+    /// Position to beacon during peer discovery (None = don't beacon)
+    #[serde(default)]
+    pub beacon_position: Option<GeoCoordinates>,
+    /// Map symbol for position beacons
+    #[serde(default)]
+    pub beacon_symbol: AprsSymbol,
+    /// Encode beacons in the compressed (base-91) position format
+    #[serde(default)]
+    pub beacon_compressed: bool,
 }
 
 impl Default for AprsConfig {
@@ -60,6 +110,9 @@ impl Default for AprsConfig {
             use_internet_gateway: true,
             license_check: true,
             use_mock: true, // Default to mock for safety
+            beacon_position: None,
+            beacon_symbol: AprsSymbol::NODE,
+            beacon_compressed: false,
         }
     }
 }
@@ -436,6 +489,7 @@ pub struct AprsAdapter {
     rx: FrameReceiver,
     incoming_tx: mpsc::Sender<(Address, Frame)>,
     rx_task: Arc<RwLock<Option<JoinHandle<()>>>>,
+    heard: HeardStations,
 }
 
 impl AprsAdapter {
@@ -449,8 +503,7 @@ impl AprsAdapter {
             range_meters: 30000.0,       // 30 km typical
             power_consumption: PowerConsumption::Low,
             cost_per_mb: 0.0, // License-free for hams
-            supports_broadcast: true,
-            supports_multicast: true,
+            features: FeatureFlags::from_features(&[Feature::Broadcast, Feature::Multicast]),
         };
 
         // RESOURCE M3: Bounded channel to prevent memory exhaustion
@@ -494,6 +547,60 @@ impl AprsAdapter {
             rx: Arc::new(RwLock::new(Some(incoming_rx))),
             incoming_tx,
             rx_task: Arc::new(RwLock::new(None)),
+            heard: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Encode a position report for this station in TNC2 format
+    ///
+    /// Produces e.g. `N0CALL-1>APRS:!4903.50N/07201.75W#`, using the
+    /// compressed format when `beacon_compressed` is set. Altitude, if any,
+    /// is appended as the `/A=` comment extension.
+    pub fn encode_beacon(&self, coords: &GeoCoordinates, symbol: AprsSymbol) -> String {
+        format!(
+            "{}>APRS:{}",
+            self.config.callsign,
+            encode_position(coords, symbol, self.config.beacon_compressed)
+        )
+    }
+
+    /// Decode a TNC2-format position report into the sender and position
+    ///
+    /// Handles uncompressed and compressed positions, with or without a
+    /// timestamp. Returns None for other packet types (including Mic-E).
+    pub fn decode_beacon(packet: &str) -> Option<(Callsign, GeoCoordinates)> {
+        let (header, info) = packet.trim_end().split_once(':')?;
+        let source = header.split_once('>')?.0;
+        if !AprsConfig::is_valid_callsign(source) {
+            return None;
+        }
+
+        Some((source.to_string(), decode_position(info)?))
+    }
+
+    /// Stations heard beaconing a position, most recent report per callsign
+    pub async fn heard_positions(&self) -> Vec<(Callsign, GeoCoordinates)> {
+        self.heard
+            .read()
+            .await
+            .iter()
+            .filter_map(|(callsign, station)| Some((callsign.clone(), station.position?)))
+            .collect()
+    }
+
+    /// Record a position report; returns false if `info` is not one
+    async fn record_position(heard: &HeardStations, source: &str, info: &str) -> bool {
+        match decode_position(info) {
+            Some(position) => {
+                heard
+                    .write()
+                    .await
+                    .entry(source.to_string())
+                    .or_default()
+                    .position = Some(position);
+                true
+            }
+            None => false,
         }
     }
 
@@ -503,6 +610,7 @@ impl AprsAdapter {
         let aprs_is = self.aprs_is.clone();
         let incoming_tx = self.incoming_tx.clone();
         let state = self.state.clone();
+        let heard = self.heard.clone();
 
         let handle = tokio::spawn(async move {
             log::info!("APRS RX task started");
@@ -515,12 +623,26 @@ impl AprsAdapter {
                 if let Ok(Some(ax25_frame)) = tnc_guard.receive_frame() {
                     drop(tnc_guard);
 
+                    // Position beacons feed discovery rather than the router
+                    if let Ok(info) = std::str::from_utf8(&ax25_frame.info) {
+                        if Self::record_position(&heard, &ax25_frame.source, info).await {
+                            continue;
+                        }
+                    }
+
                     // Convert AX.25 to MyriadMesh frame
-                    if let Ok(frame) = bincode::deserialize(&ax25_frame.info) {
+                    if let Ok(frame) = bincode::deserialize::<Frame>(&ax25_frame.info) {
                         let mut state_guard = state.write().await;
                         state_guard.packets_received += 1;
                         state_guard.last_remote_heard = Some(ax25_frame.source.clone());
 
+                        heard
+                            .write()
+                            .await
+                            .entry(ax25_frame.source.clone())
+                            .or_default()
+                            .node_id = Some(frame.header.source);
+
                         // RESOURCE M3: Handle backpressure with try_send
                         let addr = Address::APRS(format!("aprs://{}", ax25_frame.source));
                         match incoming_tx.try_send((addr, frame)) {
@@ -539,8 +661,11 @@ impl AprsAdapter {
                 // Check APRS-IS
                 let mut aprs_is_guard = aprs_is.write().await;
                 if let Some(ref mut client) = *aprs_is_guard {
-                    if let Ok(Some(_packet)) = client.receive_packet().await {
-                        // TODO: Parse APRS-IS packet format
+                    if let Ok(Some(packet)) = client.receive_packet().await {
+                        if let Some((source, position)) = Self::decode_beacon(&packet) {
+                            heard.write().await.entry(source).or_default().position =
+                                Some(position);
+                        }
                     }
                 }
             }
@@ -700,7 +825,35 @@ impl NetworkAdapter for AprsAdapter {
 
     async fn discover_peers(&self) -> Result<Vec<PeerInfo>> {
         // APRS discovery: send beacon and listen
-        Ok(Vec::new()) // Simplified for now
+        if let Some(position) = self.config.beacon_position {
+            if !self.config.license_check || self.license_manager.can_transmit().await.is_ok() {
+                let info = encode_position(
+                    &position,
+                    self.config.beacon_symbol,
+                    self.config.beacon_compressed,
+                );
+                let beacon = Ax25Frame::new(
+                    "APRS".to_string(),
+                    self.config.callsign.clone(),
+                    info.into_bytes(),
+                );
+                self.tnc.write().await.send_frame(&beacon)?;
+            }
+        }
+
+        // Peers are stations we have heard that also speak MyriadMesh
+        Ok(self
+            .heard
+            .read()
+            .await
+            .iter()
+            .filter_map(|(callsign, station)| {
+                Some(PeerInfo {
+                    node_id: station.node_id?,
+                    address: Address::APRS(format!("aprs://{}", callsign)),
+                })
+            })
+            .collect())
     }
 
     fn get_status(&self) -> AdapterStatus {
@@ -741,6 +894,137 @@ impl NetworkAdapter for AprsAdapter {
     }
 }
 
+/// Encode the information field of a position report (no timestamp)
+fn encode_position(coords: &GeoCoordinates, symbol: AprsSymbol, compressed: bool) -> String {
+    let mut info = if compressed {
+        let lat = (380_926.0 * (90.0 - coords.latitude)).round() as u32;
+        let lon = (190_463.0 * (180.0 + coords.longitude)).round() as u32;
+        // Course/speed byte of ' ' means no csT data
+        format!(
+            "!{}{}{}{}  !",
+            symbol.table,
+            encode_base91(lat),
+            encode_base91(lon),
+            symbol.code
+        )
+    } else {
+        format!(
+            "!{}{}{}{}",
+            encode_degrees_minutes(coords.latitude, 2, 'N', 'S'),
+            symbol.table,
+            encode_degrees_minutes(coords.longitude, 3, 'E', 'W'),
+            symbol.code
+        )
+    };
+
+    if let Some(altitude) = coords.altitude {
+        let feet = (altitude / METERS_PER_FOOT).round() as i64;
+        info.push_str(&format!("/A={:06}", feet.clamp(-99_999, 999_999)));
+    }
+
+    info
+}
+
+/// Format an angle as APRS degrees and hundredths of minutes (e.g. 4903.50N)
+fn encode_degrees_minutes(
+    value: f64,
+    degree_digits: usize,
+    positive: char,
+    negative: char,
+) -> String {
+    let hemisphere = if value < 0.0 { negative } else { positive };
+    let hundredths = (value.abs() * 6000.0).round() as u64;
+    let degrees = hundredths / 6000;
+    let minutes = hundredths % 6000;
+    format!(
+        "{:0width$}{:02}.{:02}{}",
+        degrees,
+        minutes / 100,
+        minutes % 100,
+        hemisphere,
+        width = degree_digits
+    )
+}
+
+/// Encode a value as four base-91 characters
+fn encode_base91(mut value: u32) -> String {
+    let mut chars = [0u8; 4];
+    for c in chars.iter_mut().rev() {
+        *c = (value % 91) as u8 + 33;
+        value /= 91;
+    }
+    String::from_utf8_lossy(&chars).into_owned()
+}
+
+/// Decode four base-91 characters
+fn decode_base91(chars: &str) -> Option<u32> {
+    chars.bytes().try_fold(0u32, |acc, b| {
+        (33..124).contains(&b).then(|| acc * 91 + (b - 33) as u32)
+    })
+}
+
+/// Decode the information field of a position report
+fn decode_position(info: &str) -> Option<GeoCoordinates> {
+    let body = match info.chars().next()? {
+        '!' | '=' => info.get(1..)?,
+        // Timestamped reports carry a 7-character time (e.g. 092345z)
+        '/' | '@' => info.get(8..)?,
+        _ => return None,
+    };
+
+    let first = body.chars().next()?;
+    let (latitude, longitude, comment) = if first.is_ascii_digit() || first == ' ' {
+        let lat = decode_degrees_minutes(body.get(0..8)?, 2, 'N', 'S')?;
+        let lon = decode_degrees_minutes(body.get(9..18)?, 3, 'E', 'W')?;
+        body.get(18..19)?;
+        (lat, lon, body.get(19..).unwrap_or(""))
+    } else {
+        let lat = 90.0 - decode_base91(body.get(1..5)?)? as f64 / 380_926.0;
+        let lon = -180.0 + decode_base91(body.get(5..9)?)? as f64 / 190_463.0;
+        body.get(9..13)?;
+        (lat, lon, body.get(13..).unwrap_or(""))
+    };
+
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return None;
+    }
+
+    let altitude = comment.find("/A=").and_then(|i| {
+        let feet: f64 = comment.get(i + 3..i + 9)?.parse().ok()?;
+        Some(feet * METERS_PER_FOOT)
+    });
+
+    Some(GeoCoordinates {
+        latitude,
+        longitude,
+        altitude,
+    })
+}
+
+/// Parse APRS degrees and minutes (e.g. 07201.75W); spaces from position
+/// ambiguity are read as zeros
+fn decode_degrees_minutes(
+    field: &str,
+    degree_digits: usize,
+    positive: char,
+    negative: char,
+) -> Option<f64> {
+    let hemisphere = field.chars().last()?;
+    let digits = field.get(..field.len() - 1)?.replace(' ', "0");
+    let degrees: f64 = digits.get(..degree_digits)?.parse().ok()?;
+    let minutes: f64 = digits.get(degree_digits..)?.parse().ok()?;
+    if minutes >= 60.0 {
+        return None;
+    }
+
+    let value = degrees + minutes / 60.0;
+    match hemisphere {
+        c if c == positive => Some(value),
+        c if c == negative => Some(-value),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -831,7 +1115,7 @@ mod tests {
 
         assert_eq!(caps.adapter_type, AdapterType::APRS);
         assert_eq!(caps.max_message_size, 256);
-        assert!(caps.supports_broadcast);
+        assert!(caps.supports_broadcast());
     }
 
     #[tokio::test]
@@ -845,4 +1129,120 @@ mod tests {
 
         assert!(tnc.send_frame(&frame).is_ok());
     }
+
+    fn assert_close(actual: &GeoCoordinates, latitude: f64, longitude: f64, tolerance: f64) {
+        assert!(
+            (actual.latitude - latitude).abs() < tolerance,
+            "latitude {} != {}",
+            actual.latitude,
+            latitude
+        );
+        assert!(
+            (actual.longitude - longitude).abs() < tolerance,
+            "longitude {} != {}",
+            actual.longitude,
+            longitude
+        );
+    }
+
+    #[test]
+    fn test_decode_uncompressed_beacon() {
+        // Example from the APRS 1.01 specification
+        let (callsign, coords) =
+            AprsAdapter::decode_beacon("N0CALL-1>APRS,WIDE1-1:!4903.50N/07201.75W-Test 001234")
+                .unwrap();
+        assert_eq!(callsign, "N0CALL-1");
+        assert_close(&coords, 49.058333, -72.029167, 1e-5);
+        assert!(coords.altitude.is_none());
+
+        // Timestamped, southern/eastern hemispheres, with altitude
+        let (_, coords) =
+            AprsAdapter::decode_beacon("VK2ABC>APRS:@092345z3351.00S/15112.00E#/A=000328").unwrap();
+        assert_close(&coords, -33.85, 151.2, 1e-5);
+        assert!((coords.altitude.unwrap() - 99.97).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_decode_compressed_beacon() {
+        // Example from the APRS 1.01 specification: 49°30' N, 72°45' W
+        let (callsign, coords) =
+            AprsAdapter::decode_beacon("N0CALL>APRS:@092345z/5L!!<*e7>7P[").unwrap();
+        assert_eq!(callsign, "N0CALL");
+        assert_close(&coords, 49.5, -72.75, 1e-4);
+    }
+
+    #[test]
+    fn test_decode_rejects_non_position_packets() {
+        assert!(AprsAdapter::decode_beacon("N0CALL>APRS:>Status text").is_none());
+        assert!(AprsAdapter::decode_beacon("N0CALL>APRS::W1ABC    :hello").is_none());
+        assert!(AprsAdapter::decode_beacon("N0CALL>APRS:!9903.50N/07201.75W-").is_none());
+        assert!(AprsAdapter::decode_beacon("no header at all").is_none());
+    }
+
+    #[test]
+    fn test_beacon_round_trip() {
+        let coords = GeoCoordinates::with_altitude(37.7749, -122.4194, 52.0);
+
+        for compressed in [false, true] {
+            let adapter = AprsAdapter::new(AprsConfig {
+                beacon_compressed: compressed,
+                ..Default::default()
+            });
+            let beacon = adapter.encode_beacon(&coords, AprsSymbol::NODE);
+            let (callsign, decoded) = AprsAdapter::decode_beacon(&beacon).unwrap();
+
+            assert_eq!(callsign, "N0CALL-1");
+            // 0.01' uncompressed is ~18 m; compressed is finer
+            assert_close(&decoded, coords.latitude, coords.longitude, 2e-4);
+            assert!((decoded.altitude.unwrap() - 52.0).abs() < 0.5);
+        }
+
+        let adapter = AprsAdapter::new(AprsConfig::default());
+        assert_eq!(
+            adapter.encode_beacon(
+                &GeoCoordinates::new(49.058333, -72.029167),
+                AprsSymbol::NODE
+            ),
+            "N0CALL-1>APRS:!4903.50N/07201.75W#"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_heard_beacons_feed_discovery() {
+        use myriadmesh_protocol::types::NODE_ID_SIZE;
+
+        let adapter = AprsAdapter::new(AprsConfig {
+            license_check: false,
+            ..Default::default()
+        });
+
+        assert!(
+            AprsAdapter::record_position(&adapter.heard, "W1ABC-7", "!4903.50N/07201.75W#").await
+        );
+        assert!(!AprsAdapter::record_position(&adapter.heard, "W1ABC-7", ">status").await);
+
+        let positions = adapter.heard_positions().await;
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].0, "W1ABC-7");
+
+        // Not a peer until it has sent us a MyriadMesh frame
+        assert!(adapter.discover_peers().await.unwrap().is_empty());
+
+        let node_id = NodeId::from_bytes([7u8; NODE_ID_SIZE]);
+        adapter
+            .heard
+            .write()
+            .await
+            .get_mut("W1ABC-7")
+            .unwrap()
+            .node_id = Some(node_id);
+
+        let peers = adapter.discover_peers().await.unwrap();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].node_id, node_id);
+        assert_eq!(
+            peers[0].address,
+            Address::APRS("aprs://W1ABC-7".to_string())
+        );
+    }
 }
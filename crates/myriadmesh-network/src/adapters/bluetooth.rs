@@ -11,7 +11,7 @@
 
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
 use myriadmesh_protocol::{
     types::{AdapterType, NODE_ID_SIZE},
     Frame, NodeId,
@@ -102,8 +102,11 @@ impl BluetoothAdapter {
             range_meters: 100.0,              // Class 1 Bluetooth can reach 100m
             power_consumption: PowerConsumption::Low,
             cost_per_mb: 0.0, // No data cost
-            supports_broadcast: false,
-            supports_multicast: false,
+            // RFCOMM is a reliable byte stream
+            features: FeatureFlags::from_features(&[
+                Feature::ReliableDelivery,
+                Feature::OrderedDelivery,
+            ]),
         };
 
         // RESOURCE M3: Bounded channel to prevent memory exhaustion
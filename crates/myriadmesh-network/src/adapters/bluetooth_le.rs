@@ -8,15 +8,17 @@
 //! - Uses GATT characteristics for data transfer
 //! - Supports BLE advertising for discovery
 //! - Channel-based transport for send/receive
+//! - Frames larger than the negotiated ATT MTU are fragmented per connection
 //! - Can be integrated with platform BLE stacks (BlueZ, CoreBluetooth, WinRT)
 
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
 use myriadmesh_protocol::{
     types::{AdapterType, NODE_ID_SIZE},
     Frame, NodeId,
 };
+use myriadmesh_routing::{fragment_frame, FragmentReassembler};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -27,6 +29,24 @@ use tokio::time::timeout;
 /// Type alias for incoming frame receiver (bounded)
 type FrameReceiver = Arc<RwLock<Option<mpsc::Receiver<(Address, Frame)>>>>;
 
+/// Minimum ATT MTU every BLE device supports
+pub const BLE_MIN_ATT_MTU: usize = 23;
+
+/// Largest ATT MTU allowed by the Bluetooth spec
+pub const BLE_MAX_ATT_MTU: usize = 517;
+
+/// ATT opcode + attribute handle preceding each notification/write payload
+const ATT_HEADER_SIZE: usize = 3;
+
+/// Packet type prefix: a whole serialized frame follows
+const PACKET_WHOLE: u8 = 0x00;
+
+/// Packet type prefix: a routing-layer fragment follows
+const PACKET_FRAGMENT: u8 = 0x01;
+
+/// How long a partially received frame is kept
+const REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// BLE adapter configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BleConfig {
@@ -42,6 +62,13 @@ pub struct BleConfig {
     pub characteristic_uuid: String,
     /// Connection interval in milliseconds
     pub connection_interval_ms: u32,
+    /// ATT MTU requested during MTU exchange; the negotiated value may be lower
+    #[serde(default = "default_preferred_mtu")]
+    pub preferred_mtu: usize,
+}
+
+fn default_preferred_mtu() -> usize {
+    247 // BLE 4.2 data length extension
 }
 
 impl Default for BleConfig {
@@ -53,6 +80,7 @@ impl Default for BleConfig {
             service_uuid: "6E400001-B5A3-F393-E0A9-E50E24DCCA9E".to_string(),
             characteristic_uuid: "6E400002-B5A3-F393-E0A9-E50E24DCCA9E".to_string(),
             connection_interval_ms: 50,
+            preferred_mtu: default_preferred_mtu(),
         }
     }
 }
@@ -78,9 +106,38 @@ struct GattConnection {
     tx: mpsc::Sender<Vec<u8>>,
     #[allow(dead_code)]
     connected_at: u64,
+    /// Negotiated ATT MTU
     mtu: usize,
 }
 
+/// Split a frame into ATT payloads that fit `att_mtu`
+///
+/// Each payload starts with a packet type byte; frames that need splitting
+/// carry a routing `FragmentHeader` after it.
+fn packets_for_mtu(frame: &Frame, att_mtu: usize) -> Result<Vec<Vec<u8>>> {
+    let budget = att_mtu
+        .checked_sub(ATT_HEADER_SIZE + 1)
+        .ok_or_else(|| NetworkError::SendFailed(format!("ATT MTU {} too small", att_mtu)))?;
+
+    let chunks = fragment_frame(frame, budget)
+        .map_err(|e| NetworkError::SendFailed(format!("Fragmentation failed: {}", e)))?;
+    let packet_type = if chunks.len() == 1 {
+        PACKET_WHOLE
+    } else {
+        PACKET_FRAGMENT
+    };
+
+    Ok(chunks
+        .into_iter()
+        .map(|chunk| {
+            let mut packet = Vec::with_capacity(chunk.len() + 1);
+            packet.push(packet_type);
+            packet.extend_from_slice(&chunk);
+            packet
+        })
+        .collect())
+}
+
 /// Bluetooth Low Energy network adapter
 pub struct BleAdapter {
     config: BleConfig,
@@ -109,8 +166,8 @@ impl BleAdapter {
             range_meters: 50.0, // BLE 5.0 can reach up to 200m
             power_consumption: PowerConsumption::VeryLow,
             cost_per_mb: 0.0,
-            supports_broadcast: true, // BLE supports advertising broadcasts
-            supports_multicast: false,
+            // BLE supports advertising broadcasts
+            features: FeatureFlags::from(Feature::Broadcast),
         };
 
         // RESOURCE M3: Bounded channel to prevent memory exhaustion
@@ -210,7 +267,11 @@ impl BleAdapter {
                     remote_address: address.to_string(),
                     tx,
                     connected_at: now,
-                    mtu: 247, // BLE 4.2 MTU
+                    // MTU exchange: the platform stack reports the agreed value
+                    mtu: self
+                        .config
+                        .preferred_mtu
+                        .clamp(BLE_MIN_ATT_MTU, BLE_MAX_ATT_MTU),
                 },
             );
         }
@@ -219,7 +280,19 @@ impl BleAdapter {
         let addr = address.to_string();
         let incoming_tx = self.incoming_tx.clone();
         tokio::spawn(async move {
-            while let Some(data) = rx.recv().await {
+            let reassembler = FragmentReassembler::new(REASSEMBLY_TIMEOUT);
+            while let Some(packet) = rx.recv().await {
+                let data = match packet.split_first() {
+                    Some((&PACKET_WHOLE, data)) => data.to_vec(),
+                    Some((&PACKET_FRAGMENT, fragment)) => {
+                        match reassembler.add_fragment(fragment).await {
+                            Some(data) => data,
+                            None => continue, // Waiting for more fragments
+                        }
+                    }
+                    _ => continue,
+                };
+
                 match bincode::deserialize::<Frame>(&data) {
                     Ok(frame) => {
                         let source_addr = Address::BluetoothLE(addr.clone());
@@ -242,6 +315,26 @@ impl BleAdapter {
         Ok(())
     }
 
+    /// Negotiated ATT MTU for a connected peer
+    pub async fn negotiated_mtu(&self, address: &str) -> Option<usize> {
+        self.connections.read().await.get(address).map(|c| c.mtu)
+    }
+
+    /// Record the result of an ATT MTU exchange with a peer
+    ///
+    /// The value is clamped to the spec range and to our preferred MTU.
+    pub async fn update_negotiated_mtu(&self, address: &str, mtu: usize) -> Result<()> {
+        let mut connections = self.connections.write().await;
+        let connection = connections
+            .get_mut(address)
+            .ok_or_else(|| NetworkError::InvalidAddress(format!("Not connected: {}", address)))?;
+        connection.mtu = mtu.clamp(
+            BLE_MIN_ATT_MTU,
+            self.config.preferred_mtu.max(BLE_MIN_ATT_MTU),
+        );
+        Ok(())
+    }
+
     /// Ensure connection to peer exists
     async fn ensure_connection(&self, address: &str) -> Result<()> {
         {
@@ -332,29 +425,19 @@ impl NetworkAdapter for BleAdapter {
         // Ensure connection exists
         self.ensure_connection(ble_address).await?;
 
-        // Serialize frame
-        let frame_data = bincode::serialize(frame)
-            .map_err(|e| NetworkError::SendFailed(format!("Failed to serialize frame: {}", e)))?;
-
-        // Check size against BLE MTU
         let connections = self.connections.read().await;
         let connection = connections.get(ble_address).ok_or_else(|| {
             NetworkError::SendFailed("Connection lost after establishment".to_string())
         })?;
 
-        if frame_data.len() > connection.mtu {
-            // Fragment if needed (or return error for simplicity)
-            return Err(NetworkError::MessageTooLarge {
-                size: frame_data.len(),
-                max: connection.mtu,
-            });
+        // Split to this connection's negotiated MTU
+        for packet in packets_for_mtu(frame, connection.mtu)? {
+            // Async send for bounded channels
+            connection.tx.send(packet).await.map_err(|_| {
+                NetworkError::SendFailed("Failed to send to connection channel".to_string())
+            })?;
         }
 
-        // Async send for bounded channels
-        connection.tx.send(frame_data).await.map_err(|_| {
-            NetworkError::SendFailed("Failed to send to connection channel".to_string())
-        })?;
-
         Ok(())
     }
 
@@ -542,4 +625,71 @@ mod tests {
         assert!(adapter.supports_address(&Address::BluetoothLE("AA:BB:CC:DD:EE:FF".to_string())));
         assert!(!adapter.supports_address(&Address::Bluetooth("00:11:22:33:44:55".to_string())));
     }
+
+    fn test_frame(payload_len: usize) -> Frame {
+        use myriadmesh_protocol::{MessageId, MessageType};
+
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let payload: Vec<u8> = (0..payload_len).map(|i| i as u8).collect();
+        let msg_id = MessageId::generate(&source, &dest, &payload, 0, 0);
+        Frame::new(MessageType::Data, source, dest, payload, msg_id, 0).unwrap()
+    }
+
+    #[test]
+    fn test_fragment_count_for_mtu() {
+        use myriadmesh_routing::FragmentHeader;
+
+        let frame = test_frame(300);
+        let serialized_len = bincode::serialize(&frame).unwrap().len();
+
+        // Default MTU 23: 23 - 3 (ATT) - 1 (type) - 4 (fragment header) = 15 bytes
+        let packets = packets_for_mtu(&frame, BLE_MIN_ATT_MTU).unwrap();
+        assert_eq!(packets.len(), serialized_len.div_ceil(15));
+        assert!(packets
+            .iter()
+            .all(|p| p.len() + ATT_HEADER_SIZE <= BLE_MIN_ATT_MTU && p[0] == PACKET_FRAGMENT));
+
+        let header = FragmentHeader::from_bytes(&packets[0][1..]).unwrap();
+        assert_eq!(header.total_fragments as usize, packets.len());
+
+        // A frame that fits goes out whole
+        let packets = packets_for_mtu(&frame, BLE_MAX_ATT_MTU).unwrap();
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0][0], PACKET_WHOLE);
+
+        assert!(packets_for_mtu(&frame, 3).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fragmented_frame_reassembled() {
+        let config = BleConfig {
+            preferred_mtu: 64,
+            ..Default::default()
+        };
+        let mut adapter = BleAdapter::new(config);
+        adapter.initialize().await.unwrap();
+
+        let frame = test_frame(400);
+        let peer = "11:22:33:44:55:66";
+        let address = Address::BluetoothLE(peer.to_string());
+
+        adapter.send(&address, &frame).await.unwrap();
+        assert_eq!(adapter.negotiated_mtu(peer).await, Some(64));
+
+        let (from, received) = adapter.receive(1000).await.unwrap();
+        assert_eq!(from, address);
+        assert_eq!(received.payload, frame.payload);
+
+        // Peer negotiates down to the minimum; still delivered intact
+        adapter.update_negotiated_mtu(peer, 10).await.unwrap();
+        assert_eq!(adapter.negotiated_mtu(peer).await, Some(BLE_MIN_ATT_MTU));
+        adapter.send(&address, &frame).await.unwrap();
+        let (_, received) = adapter.receive(1000).await.unwrap();
+        assert_eq!(received.payload, frame.payload);
+
+        // Never above what we asked for
+        adapter.update_negotiated_mtu(peer, 500).await.unwrap();
+        assert_eq!(adapter.negotiated_mtu(peer).await, Some(64));
+    }
 }
@@ -8,10 +8,12 @@
 //! - Tracks data usage for cost management
 //! - Channel-based transport for send/receive
 //! - Can integrate with modem management APIs (ModemManager, AT commands)
+//! - Status stream for network-type and signal changes (feeds failover)
 
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
+use futures::Stream;
 use myriadmesh_protocol::{types::AdapterType, Frame};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -19,12 +21,15 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpStream;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio::time::timeout;
 
 /// Type alias for incoming frame receiver (bounded)
 type FrameReceiver = Arc<RwLock<Option<mpsc::Receiver<(Address, Frame)>>>>;
 
+/// Buffered status events per subscriber before the slowest one lags
+const STATUS_CHANNEL_CAPACITY: usize = 64;
+
 /// Cellular adapter configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CellularConfig {
@@ -35,6 +40,13 @@ pub struct CellularConfig {
     pub cost_per_mb: f64,
     pub data_cap_mb: u64,
     pub use_with_wifi: bool,
+    /// Signal strengths (0-100) whose crossing emits a status event
+    #[serde(default = "default_signal_thresholds")]
+    pub signal_thresholds: Vec<u8>,
+}
+
+fn default_signal_thresholds() -> Vec<u8> {
+    vec![20, 50]
 }
 
 impl Default for CellularConfig {
@@ -47,6 +59,7 @@ impl Default for CellularConfig {
             cost_per_mb: 0.10,
             data_cap_mb: 0,
             use_with_wifi: false,
+            signal_thresholds: default_signal_thresholds(),
         }
     }
 }
@@ -60,6 +73,26 @@ pub enum NetworkType {
     Auto,
 }
 
+/// Cellular radio status change, emitted by `CellularAdapter::subscribe_status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellularStatus {
+    /// Radio moved between network types (e.g. 5G to LTE)
+    NetworkTypeChanged {
+        previous: NetworkType,
+        current: NetworkType,
+    },
+    /// Radio lost service entirely
+    ServiceLost { previous: NetworkType },
+    /// Radio regained service after losing it
+    ServiceRestored { current: NetworkType },
+    /// Signal strength crossed a configured threshold
+    SignalThresholdCrossed {
+        threshold: u8,
+        signal_strength: u8,
+        rising: bool,
+    },
+}
+
 #[derive(Debug, Clone)]
 struct ConnectionState {
     connected: bool,
@@ -89,6 +122,8 @@ pub struct CellularAdapter {
     rx: FrameReceiver,
     /// Send channel for incoming frames (bounded to prevent memory exhaustion)
     incoming_tx: mpsc::Sender<(Address, Frame)>,
+    /// Status events for subscribers
+    status_tx: broadcast::Sender<CellularStatus>,
 }
 
 impl CellularAdapter {
@@ -102,13 +137,17 @@ impl CellularAdapter {
             range_meters: 0.0, // Wide area (not local range)
             power_consumption: PowerConsumption::High,
             cost_per_mb: config.cost_per_mb,
-            supports_broadcast: false,
-            supports_multicast: false,
+            // TCP/IP over the cellular link
+            features: FeatureFlags::from_features(&[
+                Feature::ReliableDelivery,
+                Feature::OrderedDelivery,
+            ]),
         };
 
         // RESOURCE M3: Bounded channel to prevent memory exhaustion
         // Cellular: 5,000 capacity (medium throughput)
         let (incoming_tx, incoming_rx) = mpsc::channel(5000);
+        let (status_tx, _) = broadcast::channel(STATUS_CHANNEL_CAPACITY);
 
         Self {
             config,
@@ -125,6 +164,72 @@ impl CellularAdapter {
             local_ip: None,
             rx: Arc::new(RwLock::new(Some(incoming_rx))),
             incoming_tx,
+            status_tx,
+        }
+    }
+
+    /// Subscribe to radio status changes
+    ///
+    /// Emits on network-type transitions, loss and return of service, and
+    /// signal strength crossing any of `CellularConfig::signal_thresholds`.
+    /// A subscriber that falls behind skips the events it missed.
+    pub fn subscribe_status(&self) -> impl Stream<Item = CellularStatus> {
+        futures::stream::unfold(self.status_tx.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(status) => return Some((status, rx)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// Apply a radio reading from the modem (network type, signal 0-100)
+    ///
+    /// `None` means no service. Emits status events for any changes.
+    pub async fn update_radio_status(
+        &self,
+        network_type: Option<NetworkType>,
+        signal_strength: u8,
+    ) {
+        let signal_strength = signal_strength.min(100);
+        let mut events = Vec::new();
+
+        {
+            let mut state = self.connection_state.write().await;
+
+            match (state.network_type, network_type) {
+                (Some(previous), Some(current)) if previous != current => {
+                    events.push(CellularStatus::NetworkTypeChanged { previous, current });
+                }
+                (Some(previous), None) => events.push(CellularStatus::ServiceLost { previous }),
+                (None, Some(current)) => events.push(CellularStatus::ServiceRestored { current }),
+                _ => {}
+            }
+
+            let old_strength = state.signal_strength;
+            for &threshold in &self.config.signal_thresholds {
+                let was_above = old_strength >= threshold;
+                let is_above = signal_strength >= threshold;
+                if was_above != is_above {
+                    events.push(CellularStatus::SignalThresholdCrossed {
+                        threshold,
+                        signal_strength,
+                        rising: is_above,
+                    });
+                }
+            }
+
+            state.network_type = network_type;
+            state.signal_strength = signal_strength;
+            state.connected = network_type.is_some();
+        }
+
+        for event in events {
+            log::debug!("Cellular status: {:?}", event);
+            // No subscribers is fine
+            let _ = self.status_tx.send(event);
         }
     }
 
@@ -139,10 +244,11 @@ impl CellularAdapter {
         // 4. Establish PPP/IP connection
         // 5. Configure routing
 
+        // Would be read from modem
+        self.update_radio_status(Some(self.config.preferred_network), 75)
+            .await;
+
         let mut state = self.connection_state.write().await;
-        state.connected = true;
-        state.network_type = Some(self.config.preferred_network);
-        state.signal_strength = 75; // Would be read from modem
         state.connection_time = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -155,9 +261,7 @@ impl CellularAdapter {
 
     /// Disconnect cellular data connection
     async fn disconnect(&self) -> Result<()> {
-        let mut state = self.connection_state.write().await;
-        state.connected = false;
-        state.network_type = None;
+        self.update_radio_status(None, 0).await;
         Ok(())
     }
 
@@ -530,4 +634,92 @@ mod tests {
         assert!(adapter.supports_address(&Address::Cellular("192.168.1.1".to_string())));
         assert!(!adapter.supports_address(&Address::Ethernet("192.168.1.1".to_string())));
     }
+
+    #[tokio::test]
+    async fn test_status_stream_reports_degradation() {
+        use futures::StreamExt;
+
+        let adapter = CellularAdapter::new(CellularConfig {
+            preferred_network: NetworkType::FiveG,
+            ..Default::default()
+        });
+        let mut status = Box::pin(adapter.subscribe_status());
+
+        // Mock radio: attach on 5G with a strong signal
+        adapter
+            .update_radio_status(Some(NetworkType::FiveG), 80)
+            .await;
+        assert_eq!(
+            status.next().await,
+            Some(CellularStatus::ServiceRestored {
+                current: NetworkType::FiveG
+            })
+        );
+        assert_eq!(
+            status.next().await,
+            Some(CellularStatus::SignalThresholdCrossed {
+                threshold: 20,
+                signal_strength: 80,
+                rising: true
+            })
+        );
+        assert_eq!(
+            status.next().await,
+            Some(CellularStatus::SignalThresholdCrossed {
+                threshold: 50,
+                signal_strength: 80,
+                rising: true
+            })
+        );
+
+        // Signal changes that cross no threshold are quiet
+        adapter
+            .update_radio_status(Some(NetworkType::FiveG), 70)
+            .await;
+
+        // 5G -> LTE, signal weakens below 50
+        adapter
+            .update_radio_status(Some(NetworkType::LTE), 40)
+            .await;
+        assert_eq!(
+            status.next().await,
+            Some(CellularStatus::NetworkTypeChanged {
+                previous: NetworkType::FiveG,
+                current: NetworkType::LTE
+            })
+        );
+        assert_eq!(
+            status.next().await,
+            Some(CellularStatus::SignalThresholdCrossed {
+                threshold: 50,
+                signal_strength: 40,
+                rising: false
+            })
+        );
+
+        // LTE -> no service
+        adapter.update_radio_status(None, 0).await;
+        assert_eq!(
+            status.next().await,
+            Some(CellularStatus::ServiceLost {
+                previous: NetworkType::LTE
+            })
+        );
+        assert_eq!(
+            status.next().await,
+            Some(CellularStatus::SignalThresholdCrossed {
+                threshold: 20,
+                signal_strength: 0,
+                rising: false
+            })
+        );
+        assert!(!adapter.connection_state.read().await.connected);
+
+        // Nothing further pending
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), status.next())
+                .await
+                .is_err()
+        );
+    }
 }
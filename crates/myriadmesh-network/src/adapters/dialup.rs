@@ -5,7 +5,7 @@
 
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
+use crate::types::{AdapterCapabilities, Address, FeatureFlags, PowerConsumption};
 use myriadmesh_protocol::{types::AdapterType, Frame};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -379,8 +379,7 @@ impl DialupAdapter {
             range_meters: 0.0, // Wide area
             power_consumption,
             cost_per_mb: 0.05, // Typically costs money
-            supports_broadcast: false,
-            supports_multicast: false,
+            features: FeatureFlags::empty(),
         };
 
         // RESOURCE M3: Bounded channel to prevent memory exhaustion
@@ -813,7 +812,7 @@ mod tests {
 
         assert_eq!(caps.adapter_type, AdapterType::Dialup);
         assert_eq!(caps.max_message_size, 1500);
-        assert!(!caps.supports_broadcast);
+        assert!(!caps.supports_broadcast());
     }
 
     #[tokio::test]
@@ -2,23 +2,27 @@
 //!
 //! Provides UDP-based communication over Ethernet networks with:
 //! - Unicast messaging
-//! - Multicast peer discovery
+//! - Multicast peer discovery (IPv4, IPv6 or dual-stack)
 //! - IPv4 and IPv6 support
 //! - SECURITY C3: Authenticated UDP frames with Ed25519 signatures
+//! - Anti-replay counters with a per-source sliding window
+//! - Configurable send MTU with per-destination path-MTU probing
 
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
 use myriadmesh_crypto::identity::NodeIdentity;
 use myriadmesh_crypto::signing::{sign_message, verify_signature};
 use myriadmesh_protocol::types::{AdapterType, NODE_ID_SIZE};
 use myriadmesh_protocol::{Frame, Message, MessageType, NodeId};
 use sodiumoxide::crypto::sign::ed25519;
-use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::collections::{BTreeSet, HashMap};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6, UdpSocket};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::net::UdpSocket as TokioUdpSocket;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{oneshot, Mutex, RwLock};
 use tokio::time::timeout;
 
 /// Default UDP port for MyriadMesh
@@ -28,17 +32,48 @@ pub const DEFAULT_PORT: u16 = 4001;
 pub const MULTICAST_ADDR: &str = "239.255.42.1";
 pub const MULTICAST_PORT: u16 = 4002;
 
+/// Multicast group for peer discovery (IPv6, link-local scope)
+pub const MULTICAST_ADDR_V6: &str = "ff02::4d4d:1";
+
 /// Maximum UDP packet size (typical MTU minus headers)
 pub const MAX_UDP_SIZE: usize = 1400;
 
+/// Smallest packet every path is assumed to carry unfragmented
+/// (the IPv4 minimum reassembly size)
+pub const MIN_PATH_MTU: usize = 576;
+
+/// Largest configurable send MTU (jumbo frames)
+pub const MAX_JUMBO_MTU: usize = 9000;
+
+/// Default time to wait for a peer to echo a path-MTU probe
+pub const DEFAULT_MTU_PROBE_TIMEOUT_MS: u64 = 500;
+
+/// Marks the payload of path-MTU probes and their echoes
+const MTU_PROBE_MAGIC: &[u8; 8] = b"MMPMTUv1";
+
+/// Probe payload header: magic, nonce (u64 BE), probed size (u32 BE)
+const MTU_PROBE_HEADER: usize = 20;
+
 /// SECURITY C3: Size of Ed25519 public key (32 bytes)
 const PUBLIC_KEY_SIZE: usize = 32;
 
 /// SECURITY C3: Size of Ed25519 signature (64 bytes)
 const SIGNATURE_SIZE: usize = 64;
 
-/// SECURITY C3: Overhead for authenticated UDP packet (public key + signature)
-const AUTH_OVERHEAD: usize = PUBLIC_KEY_SIZE + SIGNATURE_SIZE;
+/// Size of the anti-replay packet counter (8 bytes)
+const COUNTER_SIZE: usize = 8;
+
+/// SECURITY C3: Overhead for authenticated UDP packet (public key + counter + signature)
+const AUTH_OVERHEAD: usize = PUBLIC_KEY_SIZE + COUNTER_SIZE + SIGNATURE_SIZE;
+
+/// Default number of counters behind the highest seen that may still arrive
+pub const DEFAULT_REPLAY_WINDOW: u64 = 1024;
+
+/// Default age limit for counters from sources without a replay window
+pub const DEFAULT_REPLAY_MAX_AGE_MS: u64 = 300_000;
+
+/// Maximum number of sources tracked by the replay cache
+const MAX_REPLAY_SOURCES: usize = 10_000;
 
 /// Ethernet/UDP adapter configuration
 #[derive(Debug, Clone)]
@@ -52,14 +87,38 @@ pub struct EthernetConfig {
     /// Enable multicast peer discovery
     pub enable_multicast: bool,
 
-    /// Multicast address for discovery
+    /// Multicast group for discovery (IPv4 or IPv6; family is detected)
     pub multicast_addr: String,
 
+    /// Additional IPv6 multicast group for dual-stack discovery.
+    /// Announcements go out on both groups when set.
+    pub multicast_addr_v6: Option<String>,
+
+    /// Interface index used to join and send on IPv6 groups (0 = OS default)
+    pub multicast_interface_v6: u32,
+
     /// Multicast port
     pub multicast_port: u16,
 
     /// Discovery interval in seconds
     pub discovery_interval: u64,
+
+    /// Anti-replay window: how far behind the newest counter from a source
+    /// a reordered packet may arrive and still be accepted
+    pub replay_window: u64,
+
+    /// Oldest counter, relative to the local clock, accepted from a source
+    /// with no replay window (new, evicted, or seen before a restart).
+    /// Counters follow the sender's clock, so this must exceed the clock
+    /// skew between peers.
+    pub replay_max_age_ms: u64,
+
+    /// Largest UDP packet to send, authentication overhead included.
+    /// Clamped to `MIN_PATH_MTU..=MAX_JUMBO_MTU`.
+    pub mtu: usize,
+
+    /// How long to wait for a peer to echo each path-MTU probe
+    pub mtu_probe_timeout_ms: u64,
 }
 
 impl Default for EthernetConfig {
@@ -69,12 +128,212 @@ impl Default for EthernetConfig {
             port: DEFAULT_PORT,
             enable_multicast: true,
             multicast_addr: MULTICAST_ADDR.to_string(),
+            multicast_addr_v6: None,
+            multicast_interface_v6: 0,
             multicast_port: MULTICAST_PORT,
             discovery_interval: 60,
+            replay_window: DEFAULT_REPLAY_WINDOW,
+            replay_max_age_ms: DEFAULT_REPLAY_MAX_AGE_MS,
+            mtu: MAX_UDP_SIZE,
+            mtu_probe_timeout_ms: DEFAULT_MTU_PROBE_TIMEOUT_MS,
+        }
+    }
+}
+
+impl EthernetConfig {
+    /// Parse and validate every configured discovery group
+    pub fn multicast_groups(&self) -> Result<Vec<IpAddr>> {
+        let mut groups = vec![parse_multicast_group(&self.multicast_addr)?];
+
+        if let Some(v6) = &self.multicast_addr_v6 {
+            let group = parse_multicast_group(v6)?;
+            if !group.is_ipv6() {
+                return Err(NetworkError::InvalidAddress(format!(
+                    "multicast_addr_v6 is not an IPv6 group: {}",
+                    v6
+                )));
+            }
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+
+        Ok(groups)
+    }
+}
+
+/// Parse a multicast group address of either family
+pub fn parse_multicast_group(addr: &str) -> Result<IpAddr> {
+    let ip: IpAddr = addr
+        .trim()
+        .parse()
+        .map_err(|e| NetworkError::InvalidAddress(format!("Invalid multicast address: {}", e)))?;
+
+    if !ip.is_multicast() {
+        return Err(NetworkError::InvalidAddress(format!(
+            "Not a multicast address: {}",
+            ip
+        )));
+    }
+
+    Ok(ip)
+}
+
+/// Family-specific parameters for joining a discovery group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MulticastJoin {
+    V4 {
+        group: Ipv4Addr,
+        interface: Ipv4Addr,
+    },
+    V6 {
+        group: Ipv6Addr,
+        interface: u32,
+    },
+}
+
+impl MulticastJoin {
+    /// Select the join call for `group` based on its address family
+    fn for_group(group: IpAddr, v6_interface: u32) -> Self {
+        match group {
+            IpAddr::V4(group) => MulticastJoin::V4 {
+                group,
+                interface: Ipv4Addr::UNSPECIFIED,
+            },
+            IpAddr::V6(group) => MulticastJoin::V6 {
+                group,
+                interface: v6_interface,
+            },
+        }
+    }
+
+    /// Wildcard address of the matching family to bind on
+    fn bind_addr(&self, port: u16) -> SocketAddr {
+        match self {
+            MulticastJoin::V4 { .. } => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), port),
+            MulticastJoin::V6 { .. } => SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), port),
+        }
+    }
+
+    /// Destination for discovery announcements to this group
+    fn destination(&self, port: u16) -> SocketAddr {
+        match *self {
+            MulticastJoin::V4 { group, .. } => SocketAddr::new(IpAddr::V4(group), port),
+            MulticastJoin::V6 { group, interface } => {
+                SocketAddr::V6(SocketAddrV6::new(group, port, 0, interface))
+            }
+        }
+    }
+
+    /// Bind a socket for this group and join it
+    fn open(&self, port: u16) -> Result<UdpSocket> {
+        let socket = match self {
+            MulticastJoin::V4 { .. } => UdpSocket::bind(self.bind_addr(port)),
+            // IPv6-only so the IPv4 group can share the port in dual-stack mode
+            MulticastJoin::V6 { .. } => bind_v6_only(self.bind_addr(port)),
         }
+        .map_err(|e| {
+            NetworkError::InitializationFailed(format!("Failed to bind multicast socket: {}", e))
+        })?;
+
+        match self {
+            MulticastJoin::V4 { group, interface } => socket.join_multicast_v4(group, interface),
+            MulticastJoin::V6 { group, interface } => socket.join_multicast_v6(group, *interface),
+        }
+        .map_err(|e| {
+            NetworkError::InitializationFailed(format!("Failed to join multicast group: {}", e))
+        })?;
+
+        Ok(socket)
+    }
+}
+
+/// Set the don't-fragment bit on everything sent from `socket`
+///
+/// With `IP_PMTUDISC_DO` the kernel never fragments locally and fails
+/// oversized sends with EMSGSIZE, so path-MTU probes measure the path
+/// rather than the fragmentation.
+#[cfg(target_os = "linux")]
+fn set_dont_fragment(socket: &std::net::UdpSocket, ipv6: bool) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let (level, option, value) = if ipv6 {
+        (
+            libc::IPPROTO_IPV6,
+            libc::IPV6_MTU_DISCOVER,
+            libc::IPV6_PMTUDISC_DO,
+        )
+    } else {
+        (
+            libc::IPPROTO_IP,
+            libc::IP_MTU_DISCOVER,
+            libc::IP_PMTUDISC_DO,
+        )
+    };
+
+    // SAFETY: the descriptor is owned by `socket` and `value` outlives the call
+    let result = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            level,
+            option,
+            &value as *const libc::c_int as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
     }
 }
 
+/// Other platforms keep the OS default; probes still need a peer echo
+#[cfg(not(target_os = "linux"))]
+fn set_dont_fragment(_socket: &std::net::UdpSocket, _ipv6: bool) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Current Unix time in microseconds
+fn unix_micros() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+/// Payload of a path-MTU probe or echo
+fn mtu_probe_payload(nonce: u64, size: usize, padding: usize) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(MTU_PROBE_HEADER + padding);
+    payload.extend_from_slice(MTU_PROBE_MAGIC);
+    payload.extend_from_slice(&nonce.to_be_bytes());
+    payload.extend_from_slice(&(size as u32).to_be_bytes());
+    payload.resize(MTU_PROBE_HEADER + padding, 0);
+    payload
+}
+
+/// Nonce and probed size of a path-MTU probe or echo payload
+fn parse_mtu_probe(payload: &[u8]) -> Option<(u64, usize)> {
+    if payload.len() < MTU_PROBE_HEADER || &payload[..8] != MTU_PROBE_MAGIC {
+        return None;
+    }
+    let nonce = u64::from_be_bytes(payload[8..16].try_into().ok()?);
+    let size = u32::from_be_bytes(payload[16..20].try_into().ok()?) as usize;
+    Some((nonce, size))
+}
+
+/// Bind an IPv6 UDP socket with IPV6_V6ONLY set
+fn bind_v6_only(addr: SocketAddr) -> std::io::Result<UdpSocket> {
+    let socket = socket2::Socket::new(
+        socket2::Domain::IPV6,
+        socket2::Type::DGRAM,
+        Some(socket2::Protocol::UDP),
+    )?;
+    socket.set_only_v6(true)?;
+    socket.bind(&addr.into())?;
+    Ok(socket.into())
+}
+
 /// SECURITY H1: Authenticated discovery message
 /// Prevents multicast spoofing attacks where attackers claim to be arbitrary NodeIDs
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -89,6 +348,90 @@ struct DiscoveryMessage {
     signature: [u8; SIGNATURE_SIZE],
 }
 
+/// Sliding window of packet counters seen from one source
+#[derive(Debug)]
+struct ReplayWindow {
+    /// Highest counter accepted so far
+    highest: u64,
+    /// Counters accepted within the window
+    seen: BTreeSet<u64>,
+    /// When this source last sent an accepted packet
+    last_seen: Instant,
+}
+
+impl ReplayWindow {
+    fn new() -> Self {
+        ReplayWindow {
+            highest: 0,
+            seen: BTreeSet::new(),
+            last_seen: Instant::now(),
+        }
+    }
+
+    /// Accept `counter` if it is new and not older than the window
+    fn check_and_update(&mut self, counter: u64, window: u64) -> bool {
+        if counter.saturating_add(window) <= self.highest || self.seen.contains(&counter) {
+            return false;
+        }
+
+        self.seen.insert(counter);
+        if counter > self.highest {
+            self.highest = counter;
+            let floor = self.highest.saturating_sub(window);
+            self.seen = self.seen.split_off(&floor);
+        }
+        self.last_seen = Instant::now();
+        true
+    }
+}
+
+/// Binary search for the largest packet size a path carries
+///
+/// The floor is assumed to fit. The ceiling is probed first, so a path that
+/// carries the full MTU costs a single probe.
+#[derive(Debug)]
+struct MtuSearch {
+    ceiling: usize,
+    /// Largest size known to fit
+    fits: usize,
+    /// Smallest size known not to fit
+    too_big: usize,
+}
+
+impl MtuSearch {
+    fn new(floor: usize, ceiling: usize) -> Self {
+        MtuSearch {
+            ceiling,
+            fits: floor,
+            too_big: ceiling + 1,
+        }
+    }
+
+    /// Next size to probe, or None once the search has converged
+    fn next_probe(&self) -> Option<usize> {
+        if self.too_big - self.fits <= 1 {
+            None
+        } else if self.too_big > self.ceiling {
+            Some(self.ceiling)
+        } else {
+            Some(self.fits + (self.too_big - self.fits) / 2)
+        }
+    }
+
+    fn record(&mut self, size: usize, fit: bool) {
+        if fit {
+            self.fits = size;
+        } else {
+            self.too_big = size;
+        }
+    }
+
+    /// Largest size known to fit
+    fn result(&self) -> usize {
+        self.fits
+    }
+}
+
 /// Ethernet/UDP network adapter
 pub struct EthernetAdapter {
     /// Adapter status
@@ -103,11 +446,15 @@ pub struct EthernetAdapter {
     /// SECURITY C3: Node identity for signing UDP packets
     identity: Arc<NodeIdentity>,
 
-    /// UDP socket for messaging
-    socket: Arc<Mutex<Option<TokioUdpSocket>>>,
+    /// UDP socket for messaging; receivers hold the lock while waiting
+    socket: Arc<Mutex<Option<Arc<TokioUdpSocket>>>>,
+
+    /// Send handle to the same socket, never held across an await so
+    /// sends do not wait behind a pending receive
+    send_socket: std::sync::RwLock<Option<Arc<TokioUdpSocket>>>,
 
-    /// Multicast socket for discovery
-    multicast_socket: Arc<Mutex<Option<UdpSocket>>>,
+    /// Multicast sockets for discovery, one per joined group
+    multicast_sockets: Arc<Mutex<Vec<(MulticastJoin, UdpSocket)>>>,
 
     /// Local address
     local_addr: Arc<RwLock<Option<SocketAddr>>>,
@@ -117,6 +464,21 @@ pub struct EthernetAdapter {
 
     /// Adapter capabilities
     capabilities: AdapterCapabilities,
+
+    /// Lowest counter the next outgoing packet may carry
+    send_counter: AtomicU64,
+
+    /// Replay windows keyed by source NodeId
+    replay_cache: std::sync::Mutex<HashMap<NodeId, ReplayWindow>>,
+
+    /// Configured send MTU, clamped to the supported range
+    mtu: usize,
+
+    /// Probed path MTU per destination, where below `mtu`
+    path_mtu: std::sync::Mutex<HashMap<SocketAddr, usize>>,
+
+    /// Probes awaiting an echo, keyed by nonce
+    pending_probes: std::sync::Mutex<HashMap<u64, oneshot::Sender<()>>>,
 }
 
 impl EthernetAdapter {
@@ -125,18 +487,22 @@ impl EthernetAdapter {
     /// Requires a NodeIdentity for signing outgoing frames and verifying incoming frames.
     pub fn new(identity: Arc<NodeIdentity>, config: EthernetConfig) -> Self {
         let local_node_id = NodeId::from_bytes(*identity.node_id.as_bytes());
+        let mtu = config.mtu.clamp(MIN_PATH_MTU, MAX_JUMBO_MTU);
 
         let capabilities = AdapterCapabilities {
             adapter_type: AdapterType::Ethernet,
-            max_message_size: MAX_UDP_SIZE - AUTH_OVERHEAD, // Account for auth overhead
+            max_message_size: mtu - AUTH_OVERHEAD, // Account for auth overhead
             typical_latency_ms: 5.0,
             typical_bandwidth_bps: 100_000_000, // 100 Mbps
             reliability: 0.99,
             range_meters: 100.0,
             power_consumption: PowerConsumption::Medium,
             cost_per_mb: 0.0,
-            supports_broadcast: false,
-            supports_multicast: config.enable_multicast,
+            features: if config.enable_multicast {
+                FeatureFlags::from(Feature::Multicast)
+            } else {
+                FeatureFlags::empty()
+            },
         };
 
         EthernetAdapter {
@@ -145,10 +511,16 @@ impl EthernetAdapter {
             local_node_id,
             identity,
             socket: Arc::new(Mutex::new(None)),
-            multicast_socket: Arc::new(Mutex::new(None)),
+            send_socket: std::sync::RwLock::new(None),
+            multicast_sockets: Arc::new(Mutex::new(Vec::new())),
             local_addr: Arc::new(RwLock::new(None)),
             peers: Arc::new(RwLock::new(Vec::new())),
             capabilities,
+            send_counter: AtomicU64::new(unix_micros()),
+            replay_cache: std::sync::Mutex::new(HashMap::new()),
+            mtu,
+            path_mtu: std::sync::Mutex::new(HashMap::new()),
+            pending_probes: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
@@ -162,22 +534,221 @@ impl EthernetAdapter {
         *self.local_addr.try_read().ok()?
     }
 
+    /// Handle for sending on the UDP socket
+    fn send_handle(&self) -> Result<Arc<TokioUdpSocket>> {
+        self.send_socket
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .ok_or_else(|| NetworkError::SendFailed("Socket not initialized".to_string()))
+    }
+
+    /// Configured send MTU
+    pub fn mtu(&self) -> usize {
+        self.mtu
+    }
+
+    /// Largest packet sent to `destination`: its probed path MTU if known,
+    /// otherwise the configured MTU
+    pub fn path_mtu(&self, destination: &SocketAddr) -> usize {
+        self.path_mtu
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(destination)
+            .copied()
+            .unwrap_or(self.mtu)
+    }
+
+    /// Discover the path MTU to `peer` at `destination`
+    ///
+    /// Probes are authenticated `TestRequest` frames addressed to `peer`
+    /// and padded to the size under test, sent with the don't-fragment bit
+    /// set. A size fits only once the peer echoes the probe; probes the
+    /// kernel rejects (EMSGSIZE) or the peer does not echo within
+    /// `mtu_probe_timeout_ms` do not fit. Echoes are picked up by
+    /// `receive`, so a receive loop must be running.
+    ///
+    /// The result is cached for `destination` only; other destinations keep
+    /// the configured MTU. Fails with `Timeout`, leaving the cache
+    /// untouched, if the peer echoes no probe at all.
+    pub async fn probe_path_mtu(&self, peer: NodeId, destination: &Address) -> Result<usize> {
+        let dest_addr = Self::socket_addr(destination)?;
+        let frame_overhead = self.probe_packet(peer, 0, 0, 0)?.len();
+        let wait = Duration::from_millis(self.config.mtu_probe_timeout_ms);
+        let socket = self.send_handle()?;
+
+        let mut search = MtuSearch::new(MIN_PATH_MTU, self.mtu);
+        let mut confirmed = false;
+        while let Some(size) = search.next_probe() {
+            let nonce = rand::random::<u64>();
+            let packet =
+                self.probe_packet(peer, nonce, size, size.saturating_sub(frame_overhead))?;
+
+            let (tx, rx) = oneshot::channel();
+            self.pending_probes
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(nonce, tx);
+            let fit = socket.send_to(&packet, dest_addr).await.is_ok()
+                && matches!(timeout(wait, rx).await, Ok(Ok(())));
+            self.pending_probes
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&nonce);
+
+            confirmed |= fit;
+            search.record(size, fit);
+        }
+
+        if !confirmed {
+            return Err(NetworkError::Timeout);
+        }
+        Ok(self.set_path_mtu(dest_addr, search.result()))
+    }
+
+    /// Discover the path MTU to `destination` with a custom probe
+    ///
+    /// `fits(size)` reports whether a packet of `size` bytes reaches the
+    /// destination unfragmented. The largest fitting size between
+    /// `MIN_PATH_MTU` and the configured MTU is cached for `destination`.
+    pub fn probe_path_mtu_with<F>(&self, destination: SocketAddr, mut fits: F) -> usize
+    where
+        F: FnMut(usize) -> bool,
+    {
+        let mut search = MtuSearch::new(MIN_PATH_MTU, self.mtu);
+        while let Some(size) = search.next_probe() {
+            search.record(size, fits(size));
+        }
+        self.set_path_mtu(destination, search.result())
+    }
+
+    /// Cache a probed path MTU for one destination
+    fn set_path_mtu(&self, destination: SocketAddr, path_mtu: usize) -> usize {
+        let mut cache = self.path_mtu.lock().unwrap_or_else(|e| e.into_inner());
+        if path_mtu < self.mtu {
+            cache.insert(destination, path_mtu);
+        } else {
+            cache.remove(&destination);
+        }
+        path_mtu
+    }
+
+    /// Authenticated path-MTU probe for `peer` with `padding` bytes beyond
+    /// the probe header
+    fn probe_packet(
+        &self,
+        peer: NodeId,
+        nonce: u64,
+        size: usize,
+        padding: usize,
+    ) -> Result<Vec<u8>> {
+        self.mtu_probe_packet(peer, MessageType::TestRequest, nonce, size, padding)
+    }
+
+    /// Authenticated path-MTU probe or echo packet
+    fn mtu_probe_packet(
+        &self,
+        peer: NodeId,
+        message_type: MessageType,
+        nonce: u64,
+        size: usize,
+        padding: usize,
+    ) -> Result<Vec<u8>> {
+        let message = Message::new(
+            self.local_node_id,
+            peer,
+            message_type,
+            mtu_probe_payload(nonce, size, padding),
+        )
+        .map_err(|e| NetworkError::SendFailed(format!("Failed to create probe: {}", e)))?;
+        let frame = Frame::from_message(&message)
+            .map_err(|e| NetworkError::SendFailed(format!("Failed to create probe: {}", e)))?;
+        let frame_data = bincode::serialize(&frame)
+            .map_err(|e| NetworkError::SendFailed(format!("Failed to serialize frame: {}", e)))?;
+        self.create_authenticated_packet(&frame_data)
+    }
+
+    /// Answer or resolve a path-MTU probe; returns whether `frame` was one
+    ///
+    /// Probes addressed to this node are echoed when the whole packet
+    /// (`packet_len` bytes) arrived and fits the local MTU, so a peer with
+    /// a smaller link MTU never confirms jumbo sizes.
+    async fn handle_mtu_probe(&self, frame: &Frame, source: SocketAddr, packet_len: usize) -> bool {
+        let Some((nonce, size)) = parse_mtu_probe(&frame.payload) else {
+            return false;
+        };
+
+        match frame.header.message_type {
+            MessageType::TestRequest => {
+                if frame.header.destination == self.local_node_id
+                    && size == packet_len
+                    && size <= self.mtu
+                {
+                    let echo = self.mtu_probe_packet(
+                        frame.header.source,
+                        MessageType::TestResponse,
+                        nonce,
+                        size,
+                        0,
+                    );
+                    if let (Ok(echo), Ok(socket)) = (echo, self.send_handle()) {
+                        if let Err(e) = socket.send_to(&echo, source).await {
+                            log::debug!("Failed to echo MTU probe to {}: {}", source, e);
+                        }
+                    }
+                }
+                true
+            }
+            MessageType::TestResponse => {
+                let pending = self
+                    .pending_probes
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .remove(&nonce);
+                if let Some(tx) = pending {
+                    let _ = tx.send(());
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// SECURITY C3: Create authenticated UDP packet
     ///
-    /// Format: [public_key: 32 bytes][frame_data][signature: 64 bytes]
+    /// Format: [public_key: 32 bytes][counter: 8 bytes][frame_data][signature: 64 bytes]
+    ///
+    /// The counter is covered by the signature and increases with every
+    /// packet, so receivers can reject replays. It tracks the clock in
+    /// microseconds, so it keeps increasing across restarts and receivers
+    /// can bound its age.
     fn create_authenticated_packet(&self, frame_data: &[u8]) -> Result<Vec<u8>> {
         // Calculate total size
-        let total_size = PUBLIC_KEY_SIZE + frame_data.len() + SIGNATURE_SIZE;
+        let total_size = AUTH_OVERHEAD + frame_data.len();
         let mut packet = Vec::with_capacity(total_size);
 
         // Add public key
         packet.extend_from_slice(self.identity.public_key.as_ref());
 
+        // Add anti-replay counter
+        // Jump forward only once the counter lags the clock by half the age
+        // limit, so consecutive packets keep consecutive counters
+        let lag = self.config.replay_max_age_ms.saturating_mul(1000) / 2;
+        let floor = unix_micros().saturating_sub(lag);
+        let next = self
+            .send_counter
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |next| {
+                Some(next.max(floor).saturating_add(1))
+            })
+            .unwrap_or_else(|next| next);
+        let counter = next.max(floor);
+        packet.extend_from_slice(&counter.to_le_bytes());
+
         // Add frame data
         packet.extend_from_slice(frame_data);
 
-        // Sign: public_key + frame_data
-        let signable_data = &packet[..PUBLIC_KEY_SIZE + frame_data.len()];
+        // Sign: public_key + counter + frame_data
+        let signable_data = &packet[..PUBLIC_KEY_SIZE + COUNTER_SIZE + frame_data.len()];
         let signature = sign_message(&self.identity, signable_data)
             .map_err(|e| NetworkError::SendFailed(format!("Failed to sign packet: {}", e)))?;
 
@@ -189,10 +760,13 @@ impl EthernetAdapter {
 
     /// SECURITY C3: Verify and extract frame from authenticated UDP packet
     ///
+    /// Rejects packets whose counter was already seen from the same source,
+    /// or that fall behind the replay window, with `ReplayDetected`.
+    ///
     /// Returns: (source_public_key, frame_data)
     fn verify_authenticated_packet(&self, packet: &[u8]) -> Result<(ed25519::PublicKey, Vec<u8>)> {
         // Check minimum size
-        if packet.len() < PUBLIC_KEY_SIZE + SIGNATURE_SIZE {
+        if packet.len() < AUTH_OVERHEAD {
             return Err(NetworkError::ReceiveFailed(
                 "Packet too small for authentication".to_string(),
             ));
@@ -201,7 +775,10 @@ impl EthernetAdapter {
         // Extract components
         let public_key_bytes = &packet[..PUBLIC_KEY_SIZE];
         let signature_offset = packet.len() - SIGNATURE_SIZE;
-        let frame_data = &packet[PUBLIC_KEY_SIZE..signature_offset];
+        let mut counter_bytes = [0u8; COUNTER_SIZE];
+        counter_bytes.copy_from_slice(&packet[PUBLIC_KEY_SIZE..PUBLIC_KEY_SIZE + COUNTER_SIZE]);
+        let counter = u64::from_le_bytes(counter_bytes);
+        let frame_data = &packet[PUBLIC_KEY_SIZE + COUNTER_SIZE..signature_offset];
         let signature_bytes = &packet[signature_offset..];
 
         // Parse public key
@@ -214,28 +791,69 @@ impl EthernetAdapter {
         sig_array.copy_from_slice(signature_bytes);
         let signature = myriadmesh_crypto::signing::Signature::from_bytes(sig_array);
 
-        // Verify signature over public_key + frame_data
+        // Verify signature over public_key + counter + frame_data
         let signable_data = &packet[..signature_offset];
         verify_signature(&public_key, signable_data, &signature).map_err(|e| {
             NetworkError::ReceiveFailed(format!("Signature verification failed: {}", e))
         })?;
 
+        // Only authenticated counters may advance the replay window
+        let source = NodeId::from_bytes(*NodeIdentity::derive_node_id(&public_key).as_bytes());
+        self.check_replay(source, counter)?;
+
         // Verify that public key matches claimed source NodeId in frame
         // (This will be done after deserializing the frame)
 
         Ok((public_key, frame_data.to_vec()))
     }
 
+    /// Record `counter` from `source`, rejecting duplicates and stale counters
+    fn check_replay(&self, source: NodeId, counter: u64) -> Result<()> {
+        let mut cache = self
+            .replay_cache
+            .lock()
+            .map_err(|_| NetworkError::Other("Replay cache poisoned".to_string()))?;
+
+        // Bound memory: forget the longest-idle source when full
+        if !cache.contains_key(&source) && cache.len() >= MAX_REPLAY_SOURCES {
+            if let Some(idle) = cache
+                .iter()
+                .min_by_key(|(_, window)| window.last_seen)
+                .map(|(node_id, _)| *node_id)
+            {
+                cache.remove(&idle);
+            }
+        }
+
+        // SECURITY: Without a window, anything captured from this source
+        // would be accepted again, so only recent counters may open one
+        if !cache.contains_key(&source) {
+            let max_age = self.config.replay_max_age_ms.saturating_mul(1000);
+            if counter < unix_micros().saturating_sub(max_age) {
+                return Err(NetworkError::ReplayDetected(counter));
+            }
+        }
+
+        let window = cache.entry(source).or_insert_with(ReplayWindow::new);
+        if window.check_and_update(counter, self.config.replay_window) {
+            Ok(())
+        } else {
+            Err(NetworkError::ReplayDetected(counter))
+        }
+    }
+
     /// SECURITY H1: Send authenticated multicast discovery announcement
     async fn send_discovery_announcement(&self) -> Result<()> {
         if !self.config.enable_multicast {
             return Ok(());
         }
 
-        let multicast_guard = self.multicast_socket.lock().await;
-        let socket = multicast_guard.as_ref().ok_or_else(|| {
-            NetworkError::InitializationFailed("Multicast socket not initialized".to_string())
-        })?;
+        let multicast_guard = self.multicast_sockets.lock().await;
+        if multicast_guard.is_empty() {
+            return Err(NetworkError::InitializationFailed(
+                "Multicast socket not initialized".to_string(),
+            ));
+        }
 
         // SECURITY H1: Create signed discovery message
         let node_id_bytes = *self.local_node_id.as_bytes();
@@ -270,16 +888,24 @@ impl EthernetAdapter {
             NetworkError::SendFailed(format!("Failed to serialize discovery: {}", e))
         })?;
 
-        let dest = format!(
-            "{}:{}",
-            self.config.multicast_addr, self.config.multicast_port
-        );
-
-        socket
-            .send_to(&serialized, dest)
-            .map_err(|e| NetworkError::SendFailed(format!("Multicast send failed: {}", e)))?;
+        // Announce on every joined group; succeed if any family got through
+        let mut last_error = None;
+        let mut sent = false;
+        for (join, socket) in multicast_guard.iter() {
+            let dest = join.destination(self.config.multicast_port);
+            match socket.send_to(&serialized, dest) {
+                Ok(_) => sent = true,
+                Err(e) => last_error = Some(e),
+            }
+        }
 
-        Ok(())
+        match last_error {
+            Some(e) if !sent => Err(NetworkError::SendFailed(format!(
+                "Multicast send failed: {}",
+                e
+            ))),
+            _ => Ok(()),
+        }
     }
 
     /// SECURITY H1: Listen for authenticated multicast discovery messages (non-blocking)
@@ -288,42 +914,46 @@ impl EthernetAdapter {
             return Ok(Vec::new());
         }
 
-        let multicast_guard = self.multicast_socket.blocking_lock();
-        let socket = multicast_guard.as_ref().ok_or_else(|| {
-            NetworkError::InitializationFailed("Multicast socket not initialized".to_string())
-        })?;
+        let multicast_guard = self.multicast_sockets.blocking_lock();
+        if multicast_guard.is_empty() {
+            return Err(NetworkError::InitializationFailed(
+                "Multicast socket not initialized".to_string(),
+            ));
+        }
 
         let mut discovered = Vec::new();
         let mut buf = [0u8; 1024];
 
         // Non-blocking receive with timeout
-        loop {
-            match socket.recv_from(&mut buf) {
-                Ok((size, source_addr)) => {
-                    // SECURITY H1: Verify signed discovery message
-                    match self.verify_discovery_message(&buf[..size]) {
-                        Ok(node_id) => {
-                            // Don't add ourselves
-                            if node_id != self.local_node_id {
-                                discovered.push(PeerInfo {
-                                    node_id,
-                                    address: Address::Ethernet(source_addr.to_string()),
-                                });
+        for (_, socket) in multicast_guard.iter() {
+            loop {
+                match socket.recv_from(&mut buf) {
+                    Ok((size, source_addr)) => {
+                        // SECURITY H1: Verify signed discovery message
+                        match self.verify_discovery_message(&buf[..size]) {
+                            Ok(node_id) => {
+                                // Don't add ourselves
+                                if node_id != self.local_node_id {
+                                    discovered.push(PeerInfo {
+                                        node_id,
+                                        address: Address::Ethernet(source_addr.to_string()),
+                                    });
+                                }
+                            }
+                            Err(_) => {
+                                // Ignore invalid/unsigned discovery messages
+                                continue;
                             }
-                        }
-                        Err(_) => {
-                            // Ignore invalid/unsigned discovery messages
-                            continue;
                         }
                     }
-                }
-                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    // No more messages
-                    break;
-                }
-                Err(_) => {
-                    // Other errors, stop receiving
-                    break;
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        // No more messages
+                        break;
+                    }
+                    Err(_) => {
+                        // Other errors, stop receiving
+                        break;
+                    }
                 }
             }
         }
@@ -367,6 +997,46 @@ impl EthernetAdapter {
     }
 }
 
+impl EthernetAdapter {
+    /// Extract the socket address from an Ethernet destination
+    fn socket_addr(destination: &Address) -> Result<SocketAddr> {
+        match destination {
+            Address::Ethernet(addr_str) => addr_str.parse::<SocketAddr>().map_err(|e| {
+                NetworkError::InvalidAddress(format!("Invalid Ethernet address: {}", e))
+            }),
+            _ => Err(NetworkError::InvalidAddress(
+                "Ethernet adapter requires Ethernet address".to_string(),
+            )),
+        }
+    }
+
+    /// Resolve the destination and build the authenticated UDP payload for a frame
+    fn prepare_packet(
+        &self,
+        destination: &Address,
+        frame: &Frame,
+    ) -> Result<(SocketAddr, Vec<u8>)> {
+        let dest_addr = Self::socket_addr(destination)?;
+
+        // Serialize frame
+        let frame_data = bincode::serialize(frame)
+            .map_err(|e| NetworkError::SendFailed(format!("Failed to serialize frame: {}", e)))?;
+
+        // SECURITY C3: Create authenticated packet
+        let authenticated_packet = self.create_authenticated_packet(&frame_data)?;
+
+        let max = self.path_mtu(&dest_addr);
+        if authenticated_packet.len() > max {
+            return Err(NetworkError::MessageTooLarge {
+                size: authenticated_packet.len(),
+                max,
+            });
+        }
+
+        Ok((dest_addr, authenticated_packet))
+    }
+}
+
 #[async_trait::async_trait]
 impl NetworkAdapter for EthernetAdapter {
     async fn initialize(&mut self) -> Result<()> {
@@ -381,6 +1051,14 @@ impl NetworkAdapter for EthernetAdapter {
             NetworkError::InitializationFailed(format!("Failed to bind UDP socket: {}", e))
         })?;
 
+        let ipv6 = std_socket
+            .local_addr()
+            .map(|addr| addr.is_ipv6())
+            .unwrap_or(false);
+        set_dont_fragment(&std_socket, ipv6).map_err(|e| {
+            NetworkError::InitializationFailed(format!("Failed to set don't-fragment: {}", e))
+        })?;
+
         std_socket.set_nonblocking(true).map_err(|e| {
             NetworkError::InitializationFailed(format!("Failed to set non-blocking: {}", e))
         })?;
@@ -398,43 +1076,36 @@ impl NetworkAdapter for EthernetAdapter {
             *addr = Some(local_addr);
         }
 
+        let socket = Arc::new(socket);
+        *self.send_socket.write().unwrap_or_else(|e| e.into_inner()) = Some(socket.clone());
         *self.socket.lock().await = Some(socket);
 
         // Setup multicast (in blocking context to avoid blocking async runtime)
         if self.config.enable_multicast {
-            let multicast_addr = self.config.multicast_addr.clone();
+            let groups = self.config.multicast_groups()?;
+            let v6_interface = self.config.multicast_interface_v6;
             let multicast_port = self.config.multicast_port;
-            let multicast_socket = self.multicast_socket.clone();
+            let multicast_sockets = self.multicast_sockets.clone();
 
             tokio::task::spawn_blocking(move || -> Result<()> {
-                let addr: Ipv4Addr = multicast_addr.parse().map_err(|e| {
-                    NetworkError::InvalidAddress(format!("Invalid multicast address: {}", e))
-                })?;
-
-                let socket =
-                    UdpSocket::bind(format!("0.0.0.0:{}", multicast_port)).map_err(|e| {
-                        NetworkError::InitializationFailed(format!(
-                            "Failed to bind multicast socket: {}",
-                            e
-                        ))
-                    })?;
-
-                socket
-                    .join_multicast_v4(&addr, &Ipv4Addr::UNSPECIFIED)
-                    .map_err(|e| {
-                        NetworkError::InitializationFailed(format!(
-                            "Failed to join multicast group: {}",
-                            e
-                        ))
-                    })?;
-
-                socket
-                    .set_read_timeout(Some(Duration::from_secs(1)))
-                    .map_err(|e| {
-                        NetworkError::InitializationFailed(format!("Failed to set timeout: {}", e))
-                    })?;
-
-                *multicast_socket.blocking_lock() = Some(socket);
+                let mut sockets = Vec::with_capacity(groups.len());
+                for group in groups {
+                    let join = MulticastJoin::for_group(group, v6_interface);
+                    let socket = join.open(multicast_port)?;
+
+                    socket
+                        .set_read_timeout(Some(Duration::from_secs(1)))
+                        .map_err(|e| {
+                            NetworkError::InitializationFailed(format!(
+                                "Failed to set timeout: {}",
+                                e
+                            ))
+                        })?;
+
+                    sockets.push((join, socket));
+                }
+
+                *multicast_sockets.blocking_lock() = sockets;
                 Ok(())
             })
             .await
@@ -466,43 +1137,16 @@ impl NetworkAdapter for EthernetAdapter {
         }
 
         // Close sockets
+        *self.send_socket.write().unwrap_or_else(|e| e.into_inner()) = None;
         *self.socket.lock().await = None;
-        *self.multicast_socket.lock().await = None;
+        self.multicast_sockets.lock().await.clear();
 
         Ok(())
     }
 
     async fn send(&self, destination: &Address, frame: &Frame) -> Result<()> {
-        let socket_guard = self.socket.lock().await;
-        let socket = socket_guard
-            .as_ref()
-            .ok_or_else(|| NetworkError::SendFailed("Socket not initialized".to_string()))?;
-
-        // Extract socket address from destination
-        let dest_addr = match destination {
-            Address::Ethernet(addr_str) => addr_str.parse::<SocketAddr>().map_err(|e| {
-                NetworkError::InvalidAddress(format!("Invalid Ethernet address: {}", e))
-            })?,
-            _ => {
-                return Err(NetworkError::InvalidAddress(
-                    "Ethernet adapter requires Ethernet address".to_string(),
-                ))
-            }
-        };
-
-        // Serialize frame
-        let frame_data = bincode::serialize(frame)
-            .map_err(|e| NetworkError::SendFailed(format!("Failed to serialize frame: {}", e)))?;
-
-        // SECURITY C3: Create authenticated packet
-        let authenticated_packet = self.create_authenticated_packet(&frame_data)?;
-
-        if authenticated_packet.len() > MAX_UDP_SIZE {
-            return Err(NetworkError::MessageTooLarge {
-                size: authenticated_packet.len(),
-                max: MAX_UDP_SIZE,
-            });
-        }
+        let socket = self.send_handle()?;
+        let (dest_addr, authenticated_packet) = self.prepare_packet(destination, frame)?;
 
         // Send authenticated UDP packet
         socket
@@ -513,42 +1157,62 @@ impl NetworkAdapter for EthernetAdapter {
         Ok(())
     }
 
+    fn try_send(&self, destination: &Address, frame: &Frame) -> Result<bool> {
+        let socket = self.send_handle()?;
+        let (dest_addr, authenticated_packet) = self.prepare_packet(destination, frame)?;
+
+        // Issue the syscall directly: tokio's try_send_to reports WouldBlock
+        // until the reactor has observed writability, even on an idle socket
+        let sock_ref = socket2::SockRef::from(socket.as_ref());
+        match sock_ref.send_to(&authenticated_packet, &dest_addr.into()) {
+            Ok(_) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(false),
+            Err(e) => Err(NetworkError::SendFailed(format!("UDP send failed: {}", e))),
+        }
+    }
+
     async fn receive(&self, timeout_ms: u64) -> Result<(Address, Frame)> {
         let socket_guard = self.socket.lock().await;
         let socket = socket_guard
             .as_ref()
             .ok_or_else(|| NetworkError::ReceiveFailed("Socket not initialized".to_string()))?;
 
-        let mut buf = vec![0u8; MAX_UDP_SIZE + 1024];
+        // Sized for peers configured with jumbo frames
+        let mut buf = vec![0u8; MAX_JUMBO_MTU + 1024];
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout_ms);
 
-        let (size, source_addr) = timeout(
-            Duration::from_millis(timeout_ms),
-            socket.recv_from(&mut buf),
-        )
-        .await
-        .map_err(|_| NetworkError::ReceiveFailed("Receive timeout".to_string()))?
-        .map_err(|e| NetworkError::ReceiveFailed(format!("UDP receive failed: {}", e)))?;
+        loop {
+            let (size, source_addr) = tokio::time::timeout_at(deadline, socket.recv_from(&mut buf))
+                .await
+                .map_err(|_| NetworkError::ReceiveFailed("Receive timeout".to_string()))?
+                .map_err(|e| NetworkError::ReceiveFailed(format!("UDP receive failed: {}", e)))?;
+
+            // SECURITY C3: Verify authenticated packet
+            let (source_public_key, frame_data) = self.verify_authenticated_packet(&buf[..size])?;
+
+            // Deserialize frame
+            let frame: Frame = bincode::deserialize(&frame_data).map_err(|e| {
+                NetworkError::ReceiveFailed(format!("Failed to deserialize frame: {}", e))
+            })?;
+
+            // SECURITY C3: Verify that public key matches frame's source NodeId
+            let claimed_node_id = NodeIdentity::derive_node_id(&source_public_key);
+            let frame_source_id_bytes = frame.header.source.as_bytes();
+            if claimed_node_id.as_bytes() != frame_source_id_bytes {
+                return Err(NetworkError::ReceiveFailed(
+                    "Source public key does not match frame source NodeId".to_string(),
+                ));
+            }
 
-        // SECURITY C3: Verify authenticated packet
-        let (source_public_key, frame_data) = self.verify_authenticated_packet(&buf[..size])?;
+            // Path-MTU probes and echoes are handled here, not delivered
+            if self.handle_mtu_probe(&frame, source_addr, size).await {
+                continue;
+            }
 
-        // Deserialize frame
-        let frame: Frame = bincode::deserialize(&frame_data).map_err(|e| {
-            NetworkError::ReceiveFailed(format!("Failed to deserialize frame: {}", e))
-        })?;
+            let source_address = Address::Ethernet(source_addr.to_string());
 
-        // SECURITY C3: Verify that public key matches frame's source NodeId
-        let claimed_node_id = NodeIdentity::derive_node_id(&source_public_key);
-        let frame_source_id_bytes = frame.header.source.as_bytes();
-        if claimed_node_id.as_bytes() != frame_source_id_bytes {
-            return Err(NetworkError::ReceiveFailed(
-                "Source public key does not match frame source NodeId".to_string(),
-            ));
+            return Ok((source_address, frame));
         }
-
-        let source_address = Address::Ethernet(source_addr.to_string());
-
-        Ok((source_address, frame))
     }
 
     async fn discover_peers(&self) -> Result<Vec<PeerInfo>> {
@@ -653,6 +1317,79 @@ mod tests {
         assert!(config.enable_multicast);
     }
 
+    #[test]
+    fn test_parse_ipv6_multicast_group() {
+        let group = parse_multicast_group(MULTICAST_ADDR_V6).unwrap();
+        assert!(group.is_ipv6());
+        assert!(parse_multicast_group("ff05::1").unwrap().is_ipv6());
+        assert!(parse_multicast_group(MULTICAST_ADDR).unwrap().is_ipv4());
+
+        // Unicast and malformed addresses are rejected
+        assert!(parse_multicast_group("fe80::1").is_err());
+        assert!(parse_multicast_group("192.168.1.1").is_err());
+        assert!(parse_multicast_group("ff02::zz").is_err());
+    }
+
+    #[test]
+    fn test_dual_stack_multicast_groups() {
+        let config = EthernetConfig {
+            multicast_addr_v6: Some(MULTICAST_ADDR_V6.to_string()),
+            ..Default::default()
+        };
+        let groups = config.multicast_groups().unwrap();
+        assert_eq!(groups.len(), 2);
+        assert!(groups[0].is_ipv4());
+        assert!(groups[1].is_ipv6());
+
+        // IPv6-only: the primary group can itself be IPv6
+        let config = EthernetConfig {
+            multicast_addr: MULTICAST_ADDR_V6.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(config.multicast_groups().unwrap().len(), 1);
+
+        // The dual-stack group must actually be IPv6
+        let config = EthernetConfig {
+            multicast_addr_v6: Some("239.255.42.2".to_string()),
+            ..Default::default()
+        };
+        assert!(config.multicast_groups().is_err());
+    }
+
+    #[test]
+    fn test_multicast_join_selects_family() {
+        let v4 = MulticastJoin::for_group(parse_multicast_group(MULTICAST_ADDR).unwrap(), 3);
+        assert_eq!(
+            v4,
+            MulticastJoin::V4 {
+                group: Ipv4Addr::new(239, 255, 42, 1),
+                interface: Ipv4Addr::UNSPECIFIED,
+            }
+        );
+        assert!(v4.bind_addr(MULTICAST_PORT).is_ipv4());
+        assert_eq!(
+            v4.destination(MULTICAST_PORT).to_string(),
+            "239.255.42.1:4002"
+        );
+
+        let v6 = MulticastJoin::for_group(parse_multicast_group(MULTICAST_ADDR_V6).unwrap(), 3);
+        assert_eq!(
+            v6,
+            MulticastJoin::V6 {
+                group: MULTICAST_ADDR_V6.parse().unwrap(),
+                interface: 3,
+            }
+        );
+        assert!(v6.bind_addr(MULTICAST_PORT).is_ipv6());
+        match v6.destination(MULTICAST_PORT) {
+            SocketAddr::V6(dest) => {
+                assert_eq!(dest.scope_id(), 3);
+                assert_eq!(dest.port(), MULTICAST_PORT);
+            }
+            SocketAddr::V4(_) => panic!("IPv6 group must use an IPv6 destination"),
+        }
+    }
+
     #[test]
     fn test_ethernet_adapter_creation() {
         myriadmesh_crypto::init().unwrap();
@@ -708,10 +1445,7 @@ mod tests {
         let packet = adapter.create_authenticated_packet(frame_data).unwrap();
 
         // Verify it has the correct size
-        assert_eq!(
-            packet.len(),
-            PUBLIC_KEY_SIZE + frame_data.len() + SIGNATURE_SIZE
-        );
+        assert_eq!(packet.len(), AUTH_OVERHEAD + frame_data.len());
 
         // Verify the packet
         let (recovered_public_key, recovered_data) =
@@ -735,8 +1469,8 @@ mod tests {
         let mut packet = adapter.create_authenticated_packet(frame_data).unwrap();
 
         // Tamper with the data
-        if packet.len() > PUBLIC_KEY_SIZE + 10 {
-            packet[PUBLIC_KEY_SIZE + 5] ^= 0xFF;
+        if packet.len() > PUBLIC_KEY_SIZE + COUNTER_SIZE + 10 {
+            packet[PUBLIC_KEY_SIZE + COUNTER_SIZE + 5] ^= 0xFF;
         }
 
         // Verification should fail
@@ -761,6 +1495,113 @@ mod tests {
         assert!(adapter.verify_authenticated_packet(&packet).is_err());
     }
 
+    #[test]
+    fn test_reject_replayed_packet() {
+        myriadmesh_crypto::init().unwrap();
+        let identity = Arc::new(NodeIdentity::generate().unwrap());
+        let sender = EthernetAdapter::new_default(identity);
+        let receiver = EthernetAdapter::new_default(Arc::new(NodeIdentity::generate().unwrap()));
+
+        let packet = sender.create_authenticated_packet(b"once only").unwrap();
+        assert!(receiver.verify_authenticated_packet(&packet).is_ok());
+
+        // The same packet captured and resent is rejected
+        assert!(matches!(
+            receiver.verify_authenticated_packet(&packet),
+            Err(NetworkError::ReplayDetected(_))
+        ));
+
+        // Tampering with the counter breaks the signature instead
+        let mut bumped = packet.clone();
+        bumped[PUBLIC_KEY_SIZE] ^= 0x01;
+        assert!(matches!(
+            receiver.verify_authenticated_packet(&bumped),
+            Err(NetworkError::ReceiveFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_replay_after_eviction_rejected() {
+        myriadmesh_crypto::init().unwrap();
+        let config = EthernetConfig {
+            replay_max_age_ms: 50,
+            ..Default::default()
+        };
+        let victim =
+            EthernetAdapter::new(Arc::new(NodeIdentity::generate().unwrap()), config.clone());
+        let receiver = EthernetAdapter::new(Arc::new(NodeIdentity::generate().unwrap()), config);
+
+        let captured = victim.create_authenticated_packet(b"captured").unwrap();
+        assert!(receiver.verify_authenticated_packet(&captured).is_ok());
+        std::thread::sleep(Duration::from_millis(100));
+
+        // Throwaway sources fill the cache and push out the victim's window
+        {
+            let mut cache = receiver.replay_cache.lock().unwrap();
+            for i in 0..MAX_REPLAY_SOURCES - 1 {
+                let mut id = [0xEEu8; NODE_ID_SIZE];
+                id[..8].copy_from_slice(&(i as u64).to_le_bytes());
+                cache.insert(NodeId::from_bytes(id), ReplayWindow::new());
+            }
+        }
+        let attacker = EthernetAdapter::new_default(Arc::new(NodeIdentity::generate().unwrap()));
+        let packet = attacker.create_authenticated_packet(b"evict").unwrap();
+        assert!(receiver.verify_authenticated_packet(&packet).is_ok());
+        let victim_id = NodeId::from_bytes(*victim.identity.node_id.as_bytes());
+        assert!(!receiver
+            .replay_cache
+            .lock()
+            .unwrap()
+            .contains_key(&victim_id));
+
+        assert!(matches!(
+            receiver.verify_authenticated_packet(&captured),
+            Err(NetworkError::ReplayDetected(_))
+        ));
+
+        // The victim's new packets follow its clock and are still accepted
+        let fresh = victim.create_authenticated_packet(b"fresh").unwrap();
+        assert!(receiver.verify_authenticated_packet(&fresh).is_ok());
+    }
+
+    #[test]
+    fn test_reordered_packets_within_window_accepted() {
+        myriadmesh_crypto::init().unwrap();
+        let identity = Arc::new(NodeIdentity::generate().unwrap());
+        let sender = EthernetAdapter::new_default(identity);
+        let config = EthernetConfig {
+            replay_window: 4,
+            ..Default::default()
+        };
+        let receiver = EthernetAdapter::new(Arc::new(NodeIdentity::generate().unwrap()), config);
+
+        let packets: Vec<Vec<u8>> = (0..8)
+            .map(|i| sender.create_authenticated_packet(&[i]).unwrap())
+            .collect();
+
+        // Arrive out of order, but within the window of 4
+        for i in [2, 0, 1, 4, 3] {
+            assert!(
+                receiver.verify_authenticated_packet(&packets[i]).is_ok(),
+                "packet {} should be accepted",
+                i
+            );
+        }
+
+        // Newest is now 7; 3 is the oldest still inside the window
+        assert!(receiver.verify_authenticated_packet(&packets[7]).is_ok());
+        assert!(receiver.verify_authenticated_packet(&packets[5]).is_ok());
+        assert!(matches!(
+            receiver.verify_authenticated_packet(&packets[4]),
+            Err(NetworkError::ReplayDetected(_))
+        ));
+
+        // Counters from another sender are tracked independently
+        let other = EthernetAdapter::new_default(Arc::new(NodeIdentity::generate().unwrap()));
+        let other_packet = other.create_authenticated_packet(b"independent").unwrap();
+        assert!(receiver.verify_authenticated_packet(&other_packet).is_ok());
+    }
+
     // SECURITY H1: Test that valid signed discovery messages are accepted
     #[test]
     fn test_valid_discovery_message() {
@@ -911,4 +1752,252 @@ mod tests {
         // Verification should fail
         assert!(adapter.verify_discovery_message(&serialized).is_err());
     }
+
+    #[tokio::test]
+    async fn test_try_send_over_writable_socket() {
+        use myriadmesh_protocol::{types::NODE_ID_SIZE, MessageId, MessageType};
+
+        myriadmesh_crypto::init().unwrap();
+        let loopback = || EthernetConfig {
+            bind_addr: "127.0.0.1".to_string(),
+            port: 0,
+            enable_multicast: false,
+            ..Default::default()
+        };
+        let identity = Arc::new(NodeIdentity::generate().unwrap());
+        let mut sender = EthernetAdapter::new(identity.clone(), loopback());
+        let mut receiver =
+            EthernetAdapter::new(Arc::new(NodeIdentity::generate().unwrap()), loopback());
+        sender.initialize().await.unwrap();
+        receiver.initialize().await.unwrap();
+
+        let source = NodeId::from_bytes(*identity.node_id.as_bytes());
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let msg_id = MessageId::generate(&source, &dest, b"urgent", 0, 0);
+        let frame = Frame::new(
+            MessageType::Data,
+            source,
+            dest,
+            b"urgent".to_vec(),
+            msg_id,
+            0,
+        )
+        .unwrap();
+        let destination = receiver.get_local_address().unwrap();
+
+        assert!(sender.try_send(&destination, &frame).unwrap());
+        let (_, received) = receiver.receive(1000).await.unwrap();
+        assert_eq!(received.payload, frame.payload);
+
+        // A receive in progress does not hold up sending
+        {
+            let _receiving = sender.socket.lock().await;
+            assert!(sender.try_send(&destination, &frame).unwrap());
+        }
+        let (_, received) = receiver.receive(1000).await.unwrap();
+        assert_eq!(received.payload, frame.payload);
+
+        assert!(sender
+            .try_send(&Address::Bluetooth("00:11:22:33:44:55".to_string()), &frame)
+            .is_err());
+    }
+
+    fn frame_with_payload(identity: &NodeIdentity, size: usize) -> Frame {
+        use myriadmesh_protocol::{types::NODE_ID_SIZE, MessageId};
+
+        let source = NodeId::from_bytes(*identity.node_id.as_bytes());
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let payload = vec![0u8; size];
+        let msg_id = MessageId::generate(&source, &dest, &payload, 0, 0);
+        Frame::new(MessageType::Data, source, dest, payload, msg_id, 0).unwrap()
+    }
+
+    #[test]
+    fn test_configured_mtu_honored() {
+        myriadmesh_crypto::init().unwrap();
+        let identity = Arc::new(NodeIdentity::generate().unwrap());
+        let destination = Address::Ethernet("192.168.1.20:4001".to_string());
+        let jumbo_frame = frame_with_payload(&identity, 4000);
+
+        let standard = EthernetAdapter::new_default(identity.clone());
+        assert!(matches!(
+            standard.prepare_packet(&destination, &jumbo_frame),
+            Err(NetworkError::MessageTooLarge {
+                max: MAX_UDP_SIZE,
+                ..
+            })
+        ));
+
+        let jumbo = EthernetAdapter::new(
+            identity.clone(),
+            EthernetConfig {
+                mtu: MAX_JUMBO_MTU,
+                ..Default::default()
+            },
+        );
+        assert_eq!(jumbo.mtu(), MAX_JUMBO_MTU);
+        assert_eq!(
+            jumbo.get_capabilities().max_message_size,
+            MAX_JUMBO_MTU - AUTH_OVERHEAD
+        );
+        let (_, packet) = jumbo.prepare_packet(&destination, &jumbo_frame).unwrap();
+        assert!(packet.len() > MAX_UDP_SIZE && packet.len() <= MAX_JUMBO_MTU);
+
+        // Out-of-range settings are clamped
+        let tiny = EthernetAdapter::new(
+            identity,
+            EthernetConfig {
+                mtu: 100,
+                ..Default::default()
+            },
+        );
+        assert_eq!(tiny.mtu(), MIN_PATH_MTU);
+    }
+
+    #[test]
+    fn test_probe_lowers_effective_mtu() {
+        myriadmesh_crypto::init().unwrap();
+        let identity = Arc::new(NodeIdentity::generate().unwrap());
+        let adapter = EthernetAdapter::new(
+            identity.clone(),
+            EthernetConfig {
+                mtu: MAX_JUMBO_MTU,
+                ..Default::default()
+            },
+        );
+        let narrow: SocketAddr = "192.168.1.20:4001".parse().unwrap();
+        let wide: SocketAddr = "192.168.1.21:4001".parse().unwrap();
+
+        // Packets above 1500 bytes are dropped on the way to `narrow`
+        let mut probes = Vec::new();
+        let path_mtu = adapter.probe_path_mtu_with(narrow, |size| {
+            probes.push(size);
+            size <= 1500
+        });
+        assert_eq!(path_mtu, 1500);
+        assert_eq!(probes[0], MAX_JUMBO_MTU);
+        assert!(probes.len() <= 15);
+
+        // Only the probed destination is limited
+        assert_eq!(adapter.path_mtu(&narrow), 1500);
+        assert_eq!(adapter.path_mtu(&wide), MAX_JUMBO_MTU);
+        assert_eq!(
+            adapter.get_capabilities().max_message_size,
+            MAX_JUMBO_MTU - AUTH_OVERHEAD
+        );
+
+        let frame = frame_with_payload(&identity, 4000);
+        assert!(matches!(
+            adapter.prepare_packet(&Address::Ethernet(narrow.to_string()), &frame),
+            Err(NetworkError::MessageTooLarge { max: 1500, .. })
+        ));
+        assert!(adapter
+            .prepare_packet(&Address::Ethernet(wide.to_string()), &frame)
+            .is_ok());
+
+        // A path that carries the full MTU needs a single probe and clears the entry
+        let mut probes = 0;
+        assert_eq!(
+            adapter.probe_path_mtu_with(narrow, |_| {
+                probes += 1;
+                true
+            }),
+            MAX_JUMBO_MTU
+        );
+        assert_eq!(probes, 1);
+        assert_eq!(adapter.path_mtu(&narrow), MAX_JUMBO_MTU);
+    }
+
+    #[tokio::test]
+    async fn test_probe_path_mtu_over_loopback() {
+        myriadmesh_crypto::init().unwrap();
+        let loopback = || EthernetConfig {
+            bind_addr: "127.0.0.1".to_string(),
+            port: 0,
+            enable_multicast: false,
+            mtu: MAX_JUMBO_MTU,
+            mtu_probe_timeout_ms: 100,
+            ..Default::default()
+        };
+        let mut sender =
+            EthernetAdapter::new(Arc::new(NodeIdentity::generate().unwrap()), loopback());
+        // The peer's own link only carries standard frames
+        let receiver_identity = Arc::new(NodeIdentity::generate().unwrap());
+        let receiver_id = NodeId::from_bytes(*receiver_identity.node_id.as_bytes());
+        let mut receiver = EthernetAdapter::new(
+            receiver_identity,
+            EthernetConfig {
+                mtu: MAX_UDP_SIZE,
+                ..loopback()
+            },
+        );
+        sender.initialize().await.unwrap();
+        receiver.initialize().await.unwrap();
+
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::fd::AsRawFd;
+
+            let socket = sender.send_handle().unwrap();
+            let mut value: libc::c_int = 0;
+            let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+            // SAFETY: `value` and `len` are valid for the duration of the call
+            let result = unsafe {
+                libc::getsockopt(
+                    socket.as_raw_fd(),
+                    libc::IPPROTO_IP,
+                    libc::IP_MTU_DISCOVER,
+                    &mut value as *mut libc::c_int as *mut libc::c_void,
+                    &mut len,
+                )
+            };
+            assert_eq!(result, 0);
+            assert_eq!(value, libc::IP_PMTUDISC_DO);
+        }
+
+        // Echoes are handled by the regular receive path on both sides
+        let sender = Arc::new(sender);
+        let receiver = Arc::new(receiver);
+        let loops: Vec<_> = [sender.clone(), receiver.clone()]
+            .into_iter()
+            .map(|adapter| {
+                tokio::spawn(async move {
+                    loop {
+                        let _ = adapter.receive(50).await;
+                    }
+                })
+            })
+            .collect();
+
+        let destination = receiver.get_local_address().unwrap();
+        let dest_addr = EthernetAdapter::socket_addr(&destination).unwrap();
+        assert_eq!(
+            sender
+                .probe_path_mtu(receiver_id, &destination)
+                .await
+                .unwrap(),
+            MAX_UDP_SIZE
+        );
+        assert_eq!(sender.path_mtu(&dest_addr), MAX_UDP_SIZE);
+        assert_eq!(
+            sender.path_mtu(&"127.0.0.1:9".parse().unwrap()),
+            MAX_JUMBO_MTU
+        );
+        assert_eq!(
+            sender.get_capabilities().max_message_size,
+            MAX_JUMBO_MTU - AUTH_OVERHEAD
+        );
+
+        // Probes addressed to another node are never echoed
+        let stranger = NodeId::from_bytes([9u8; NODE_ID_SIZE]);
+        assert!(matches!(
+            sender.probe_path_mtu(stranger, &destination).await,
+            Err(NetworkError::Timeout)
+        ));
+        assert_eq!(sender.path_mtu(&dest_addr), MAX_UDP_SIZE);
+
+        for task in loops {
+            task.abort();
+        }
+    }
 }
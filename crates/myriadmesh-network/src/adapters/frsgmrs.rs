@@ -8,7 +8,8 @@
 //! - AFSK software modem (Bell 202: 1200 Hz = mark, 2200 Hz = space)
 //! - FreeDV/Codec2 digital voice mode simulation
 //! - PTT (Push-to-Talk) control via GPIO or serial
-//! - CTCSS (tone squelch) encoding and detection
+//! - CTCSS (tone) and DCS (digital code) squelch; transmissions without
+//!   the configured tone or code are discarded before demodulation
 //! - GMRS license verification (FRS is license-free)
 //! - FCC power limit enforcement (FRS: 0.5W, GMRS: 50W)
 //! - Mock radio interface for testing
@@ -16,7 +17,7 @@
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
 use crate::license::LicenseManager;
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
 use myriadmesh_protocol::{types::AdapterType, Frame};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -26,6 +27,17 @@ use tokio::task::JoinHandle;
 
 type FrameReceiver = Arc<RwLock<Option<mpsc::Receiver<(Address, Frame)>>>>;
 
+/// Length of the squelch tone/code sent ahead of each transmission
+///
+/// Long enough for two full DCS codewords so the receiver can lock on.
+const SQUELCH_PREAMBLE_MS: u32 = 350;
+
+/// DCS bit rate (bits per second)
+const DCS_BAUD: f32 = 134.4;
+
+/// Peak amplitude of the sub-audible squelch signal
+const SQUELCH_AMPLITUDE: f32 = 0.1;
+
 /// FRS/GMRS radio configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrsGmrsConfig {
@@ -39,6 +51,11 @@ pub struct FrsGmrsConfig {
     pub ctcss_enabled: bool,
     /// CTCSS tone frequency in Hz (67.0-254.1 Hz)
     pub ctcss_frequency_hz: Option<f32>,
+    /// DCS code as written on the radio, in octal (e.g. `0o023` for D023N)
+    ///
+    /// Used when CTCSS is disabled; the two are mutually exclusive.
+    #[serde(default)]
+    pub dcs_code: Option<u16>,
     /// Serial device path for radio module
     pub device_path: String,
     /// Baud rate for radio module
@@ -69,6 +86,7 @@ impl Default for FrsGmrsConfig {
             tx_power_watts: 0.5,
             ctcss_enabled: true,
             ctcss_frequency_hz: Some(67.0),
+            dcs_code: None,
             device_path: "/dev/ttyUSB0".to_string(),
             baud_rate: 9600,
             ptt_gpio_pin: None,
@@ -133,6 +151,21 @@ impl FrsGmrsConfig {
             }
         }
 
+        // DCS code range (three octal digits)
+        if let Some(code) = self.dcs_code {
+            if self.ctcss_enabled {
+                return Err(NetworkError::Other(
+                    "CTCSS and DCS squelch are mutually exclusive".to_string(),
+                ));
+            }
+            if code > 0o777 {
+                return Err(NetworkError::Other(format!(
+                    "DCS code {:o} must be three octal digits",
+                    code
+                )));
+            }
+        }
+
         Ok(())
     }
 }
@@ -250,13 +283,15 @@ impl CtcssCodec {
     }
 
     /// Detect CTCSS tone using Goertzel algorithm
+    ///
+    /// The tone must carry at least half of the signal's amplitude, so other
+    /// tones, DCS codes and silence do not open squelch.
     fn detect(&self, audio: &[f32]) -> bool {
         if audio.is_empty() {
             return false;
         }
 
-        let k = (0.5 + (audio.len() as f32 * self.tone_hz) / self.sample_rate as f32) as usize;
-        let omega = (2.0 * std::f32::consts::PI * k as f32) / audio.len() as f32;
+        let omega = 2.0 * std::f32::consts::PI * self.tone_hz / self.sample_rate as f32;
         let coeff = 2.0 * omega.cos();
 
         let mut s1 = 0.0;
@@ -268,11 +303,198 @@ impl CtcssCodec {
             s1 = s0;
         }
 
-        let magnitude = (s1 * s1 + s2 * s2 - coeff * s1 * s2).sqrt();
-        magnitude > 0.05 // Detection threshold
+        let magnitude = (s1 * s1 + s2 * s2 - coeff * s1 * s2).max(0.0).sqrt();
+        let tone_amplitude = 2.0 * magnitude / audio.len() as f32;
+
+        let energy: f32 = audio.iter().map(|s| s * s).sum();
+        let peak_amplitude = (2.0 * energy / audio.len() as f32).sqrt();
+
+        peak_amplitude > 0.0 && tone_amplitude >= 0.5 * peak_amplitude
     }
 }
 
+/// DCS (digital code squelch) codec
+///
+/// Sends a Golay (23,12) codeword of the 9-bit code as continuous NRZ at
+/// 134.4 bps. Receivers accept any rotation of the codeword, since they
+/// may join mid-word.
+struct DcsCodec {
+    sample_rate: u32,
+    codeword: u32,
+}
+
+impl DcsCodec {
+    /// Golay (23,12) generator polynomial
+    const GENERATOR: u32 = 0xC75;
+    const CODEWORD_BITS: usize = 23;
+    const CODEWORD_MASK: u32 = (1 << 23) - 1;
+
+    fn new(sample_rate: u32, code: u16) -> Self {
+        Self {
+            sample_rate,
+            codeword: Self::codeword(code),
+        }
+    }
+
+    /// Systematic Golay codeword: 9 code bits + fixed `100`, then 11 parity bits
+    fn codeword(code: u16) -> u32 {
+        let data = (code as u32 & 0x1FF) | 0x800;
+        let mut remainder = data << 11;
+        for bit in (11..23).rev() {
+            if remainder & (1 << bit) != 0 {
+                remainder ^= Self::GENERATOR << (bit - 11);
+            }
+        }
+        (data << 11) | remainder
+    }
+
+    fn samples_per_bit(&self) -> f32 {
+        self.sample_rate as f32 / DCS_BAUD
+    }
+
+    /// Add the repeating DCS codeword to audio
+    fn encode(&self, audio: &[f32]) -> Vec<f32> {
+        let samples_per_bit = self.samples_per_bit();
+        audio
+            .iter()
+            .enumerate()
+            .map(|(i, &sample)| {
+                let bit_index = (i as f32 / samples_per_bit) as usize % Self::CODEWORD_BITS;
+                let bit = (self.codeword >> (Self::CODEWORD_BITS - 1 - bit_index)) & 1;
+                let level = if bit == 1 {
+                    SQUELCH_AMPLITUDE
+                } else {
+                    -SQUELCH_AMPLITUDE
+                };
+                sample + level
+            })
+            .collect()
+    }
+
+    /// Slice bits from audio and look for a full codeword in any rotation
+    fn detect(&self, audio: &[f32]) -> bool {
+        let samples_per_bit = self.samples_per_bit();
+        let bit_count = (audio.len() as f32 / samples_per_bit) as usize;
+
+        let rotations: Vec<u32> = (0..Self::CODEWORD_BITS)
+            .map(|k| {
+                ((self.codeword << k) | (self.codeword >> (Self::CODEWORD_BITS - k)))
+                    & Self::CODEWORD_MASK
+            })
+            .collect();
+
+        let mut window = 0u32;
+        let mut valid_bits = 0;
+        for bit_index in 0..bit_count {
+            // Average the middle half of the bit to stay clear of transitions
+            let start = (bit_index as f32 * samples_per_bit + samples_per_bit / 4.0) as usize;
+            let end = (bit_index as f32 * samples_per_bit + samples_per_bit * 0.75) as usize;
+            let slice = &audio[start..end.min(audio.len())];
+            if slice.is_empty() {
+                break;
+            }
+            let mean = slice.iter().sum::<f32>() / slice.len() as f32;
+
+            if mean.abs() < SQUELCH_AMPLITUDE / 3.0 {
+                // No clear DCS level here
+                valid_bits = 0;
+                continue;
+            }
+
+            window = ((window << 1) | (mean > 0.0) as u32) & Self::CODEWORD_MASK;
+            valid_bits += 1;
+            if valid_bits >= Self::CODEWORD_BITS && rotations.contains(&window) {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// Sub-audible squelch signalling
+///
+/// The tone or code is sent as a preamble ahead of the data so the receiver
+/// can decide whether to open squelch before demodulating.
+enum Squelch {
+    Ctcss(CtcssCodec),
+    Dcs(DcsCodec),
+}
+
+impl Squelch {
+    fn from_config(config: &FrsGmrsConfig) -> Option<Self> {
+        match (
+            config.ctcss_enabled,
+            config.ctcss_frequency_hz,
+            config.dcs_code,
+        ) {
+            (true, Some(tone_hz), _) => Some(Squelch::Ctcss(CtcssCodec::new(
+                config.audio_sample_rate,
+                tone_hz,
+            ))),
+            (_, _, Some(code)) => Some(Squelch::Dcs(DcsCodec::new(config.audio_sample_rate, code))),
+            _ => None,
+        }
+    }
+
+    fn preamble_len(&self) -> usize {
+        let sample_rate = match self {
+            Squelch::Ctcss(c) => c.sample_rate,
+            Squelch::Dcs(d) => d.sample_rate,
+        };
+        (sample_rate as u64 * SQUELCH_PREAMBLE_MS as u64 / 1000) as usize
+    }
+
+    /// Squelch preamble to transmit ahead of the data
+    fn preamble(&self) -> Vec<f32> {
+        let silence = vec![0.0; self.preamble_len()];
+        match self {
+            Squelch::Ctcss(c) => c.encode(&silence),
+            Squelch::Dcs(d) => d.encode(&silence),
+        }
+    }
+
+    /// Check the preamble and return the data audio following it
+    fn open<'a>(&self, audio: &'a [f32]) -> Option<&'a [f32]> {
+        let len = self.preamble_len();
+        if audio.len() < len {
+            return None;
+        }
+        let (preamble, data) = audio.split_at(len);
+        let detected = match self {
+            Squelch::Ctcss(c) => c.detect(preamble),
+            Squelch::Dcs(d) => d.detect(preamble),
+        };
+        detected.then_some(data)
+    }
+}
+
+/// Demodulate received audio into a frame, applying squelch first
+fn decode_audio(
+    modulation: ModulationType,
+    modem: &AfskModem,
+    squelch: Option<&Squelch>,
+    audio: &[f32],
+) -> Result<Frame> {
+    let audio = match squelch {
+        Some(squelch) => squelch
+            .open(audio)
+            .ok_or_else(|| NetworkError::Other("Squelch closed: tone/code mismatch".to_string()))?,
+        None => audio,
+    };
+
+    let data = match modulation {
+        ModulationType::FM => audio
+            .iter()
+            .map(|s| ((s + 0.5) * 255.0).round().clamp(0.0, 255.0) as u8)
+            .collect(),
+        ModulationType::AFSK | ModulationType::FreeDV => modem.decode(audio)?,
+    };
+
+    bincode::deserialize(&data)
+        .map_err(|e| NetworkError::Other(format!("Deserialization failed: {}", e)))
+}
+
 /// Radio hardware interface trait
 trait RadioInterface: Send + Sync {
     fn transmit_audio(&mut self, audio: &[f32]) -> Result<()>;
@@ -338,7 +560,7 @@ pub struct FrsGmrsAdapter {
     rx_task: Arc<RwLock<Option<JoinHandle<()>>>>,
     radio: Arc<RwLock<Box<dyn RadioInterface>>>,
     modem: Arc<AfskModem>,
-    ctcss: Option<Arc<CtcssCodec>>,
+    squelch: Option<Arc<Squelch>>,
     license_manager: Option<Arc<LicenseManager>>,
 }
 
@@ -370,17 +592,14 @@ impl FrsGmrsAdapter {
             range_meters: 5000.0,
             power_consumption: PowerConsumption::Medium,
             cost_per_mb: 0.0,
-            supports_broadcast: true,
-            supports_multicast: false,
+            features: FeatureFlags::from(Feature::Broadcast),
         };
 
         // RESOURCE M3: Bounded channel to prevent memory exhaustion
         // LoRa/Radio: 1,000 capacity (low throughput)
         let (incoming_tx, incoming_rx) = mpsc::channel(1000);
         let modem = Arc::new(AfskModem::new(config.audio_sample_rate));
-        let ctcss = config
-            .ctcss_frequency_hz
-            .map(|f| Arc::new(CtcssCodec::new(config.audio_sample_rate, f)));
+        let squelch = Squelch::from_config(&config).map(Arc::new);
 
         Self {
             config: config.clone(),
@@ -398,7 +617,7 @@ impl FrsGmrsAdapter {
             rx_task: Arc::new(RwLock::new(None)),
             radio: Arc::new(RwLock::new(Box::new(MockRadio::new()))),
             modem,
-            ctcss,
+            squelch,
             license_manager,
         }
     }
@@ -413,9 +632,11 @@ impl FrsGmrsAdapter {
             ModulationType::AFSK | ModulationType::FreeDV => self.modem.encode(&data),
         };
 
-        // Add CTCSS if enabled
-        let audio = if let Some(ref ctcss) = self.ctcss {
-            ctcss.encode(&audio)
+        // Prefix the squelch tone/code if enabled
+        let audio = if let Some(ref squelch) = self.squelch {
+            let mut keyed = squelch.preamble();
+            keyed.extend(audio);
+            keyed
         } else {
             audio
         };
@@ -426,23 +647,12 @@ impl FrsGmrsAdapter {
     /// Decode audio to frame
     #[allow(dead_code)]
     async fn decode_frame(&self, audio: &[f32]) -> Result<Frame> {
-        // Check CTCSS
-        if let Some(ref ctcss) = self.ctcss {
-            if !ctcss.detect(audio) {
-                return Err(NetworkError::Other("CTCSS mismatch".to_string()));
-            }
-        }
-
-        let data = match self.config.modulation {
-            ModulationType::FM => audio
-                .iter()
-                .map(|s| ((s + 0.5) * 255.0).clamp(0.0, 255.0) as u8)
-                .collect(),
-            ModulationType::AFSK | ModulationType::FreeDV => self.modem.decode(audio)?,
-        };
-
-        bincode::deserialize(&data)
-            .map_err(|e| NetworkError::Other(format!("Deserialization failed: {}", e)))
+        decode_audio(
+            self.config.modulation,
+            &self.modem,
+            self.squelch.as_deref(),
+            audio,
+        )
     }
 }
 
@@ -488,7 +698,7 @@ impl NetworkAdapter for FrsGmrsAdapter {
         let incoming_tx = self.incoming_tx.clone();
         let config = self.config.clone();
         let modem = self.modem.clone();
-        let ctcss = self.ctcss.clone();
+        let squelch = self.squelch.clone();
 
         let rx_task = tokio::spawn(async move {
             loop {
@@ -499,36 +709,25 @@ impl NetworkAdapter for FrsGmrsAdapter {
                     _ => continue,
                 };
 
-                // Check CTCSS
-                if let Some(ref c) = ctcss {
-                    if !c.detect(&audio) {
-                        continue;
-                    }
-                }
-
-                // Decode
-                let data = match config.modulation {
-                    ModulationType::FM => audio
-                        .iter()
-                        .map(|s| ((s + 0.5) * 255.0).clamp(0.0, 255.0) as u8)
-                        .collect(),
-                    ModulationType::AFSK | ModulationType::FreeDV => match modem.decode(&audio) {
-                        Ok(d) => d,
-                        Err(_) => continue,
-                    },
-                };
-
-                if let Ok(frame) = bincode::deserialize::<Frame>(&data) {
-                    let addr = Address::FrsGmrs(config.frequency_hz.to_string());
-                    match incoming_tx.try_send((addr, frame)) {
-                        Ok(_) => {}
-                        Err(mpsc::error::TrySendError::Full(_)) => {
-                            log::warn!("FRS/GMRS incoming channel full, dropping frame");
-                        }
-                        Err(mpsc::error::TrySendError::Closed(_)) => {
-                            log::warn!("FRS/GMRS incoming channel closed, stopping RX task");
-                            break;
+                // Squelch filter, then demodulate
+                let frame =
+                    match decode_audio(config.modulation, &modem, squelch.as_deref(), &audio) {
+                        Ok(frame) => frame,
+                        Err(e) => {
+                            log::trace!("FRS/GMRS discarding transmission: {}", e);
+                            continue;
                         }
+                    };
+
+                let addr = Address::FrsGmrs(config.frequency_hz.to_string());
+                match incoming_tx.try_send((addr, frame)) {
+                    Ok(_) => {}
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        log::warn!("FRS/GMRS incoming channel full, dropping frame");
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => {
+                        log::warn!("FRS/GMRS incoming channel closed, stopping RX task");
+                        break;
                     }
                 }
             }
@@ -655,8 +854,8 @@ mod tests {
         let caps = adapter.get_capabilities();
 
         assert_eq!(caps.adapter_type, AdapterType::FRSGMRS);
-        assert!(caps.supports_broadcast);
-        assert!(!caps.supports_multicast);
+        assert!(caps.supports_broadcast());
+        assert!(!caps.supports_multicast());
     }
 
     #[test]
@@ -758,4 +957,99 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(adapter.get_status(), AdapterStatus::Ready);
     }
+
+    fn fm_config(ctcss_hz: Option<f32>, dcs_code: Option<u16>) -> FrsGmrsConfig {
+        FrsGmrsConfig {
+            modulation: ModulationType::FM,
+            ctcss_enabled: ctcss_hz.is_some(),
+            ctcss_frequency_hz: ctcss_hz,
+            dcs_code,
+            ..Default::default()
+        }
+    }
+
+    fn test_frame() -> Frame {
+        use myriadmesh_protocol::{types::NODE_ID_SIZE, MessageId, MessageType, NodeId};
+
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let msg_id = MessageId::generate(&source, &dest, b"squelch", 0, 0);
+        Frame::new(
+            MessageType::Data,
+            source,
+            dest,
+            b"squelch".to_vec(),
+            msg_id,
+            0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ctcss_rejects_other_tone() {
+        let ctcss = CtcssCodec::new(48000, 67.0);
+        let other = CtcssCodec::new(48000, 100.0);
+        let tone = other.encode(&vec![0.0; 16800]);
+        assert!(!ctcss.detect(&tone));
+        assert!(!ctcss.detect(&vec![0.0; 16800]));
+    }
+
+    #[test]
+    fn test_dcs_codeword() {
+        let dcs = DcsCodec::new(48000, 0o023);
+        // Data bits (code + fixed 100) are carried unchanged
+        assert_eq!(dcs.codeword >> 11, 0o023 | 0x800);
+        assert!(dcs.codeword <= DcsCodec::CODEWORD_MASK);
+
+        let audio = dcs.encode(&vec![0.0; 16800]);
+        assert!(dcs.detect(&audio));
+        assert!(!DcsCodec::new(48000, 0o411).detect(&audio));
+    }
+
+    #[test]
+    fn test_dcs_config_validation() {
+        assert!(fm_config(None, Some(0o023)).validate().is_ok());
+        assert!(fm_config(None, Some(0o1000)).validate().is_err());
+        assert!(fm_config(Some(67.0), Some(0o023)).validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ctcss_squelch_filter() {
+        let sender = FrsGmrsAdapter::new(fm_config(Some(67.0), None));
+        let matching = FrsGmrsAdapter::new(fm_config(Some(67.0), None));
+        let other_tone = FrsGmrsAdapter::new(fm_config(Some(100.0), None));
+        let dcs = FrsGmrsAdapter::new(fm_config(None, Some(0o023)));
+
+        let frame = test_frame();
+        let audio = sender.encode_frame(&frame).await.unwrap();
+
+        let decoded = matching.decode_frame(&audio).await.unwrap();
+        assert_eq!(decoded.payload, frame.payload);
+
+        assert!(other_tone.decode_frame(&audio).await.is_err());
+        assert!(dcs.decode_frame(&audio).await.is_err());
+
+        // Untoned transmissions never open squelch
+        let plain = FrsGmrsAdapter::new(fm_config(None, None));
+        let untoned = plain.encode_frame(&frame).await.unwrap();
+        assert!(plain.decode_frame(&untoned).await.is_ok());
+        assert!(matching.decode_frame(&untoned).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dcs_squelch_filter() {
+        let sender = FrsGmrsAdapter::new(fm_config(None, Some(0o023)));
+        let matching = FrsGmrsAdapter::new(fm_config(None, Some(0o023)));
+        let other_code = FrsGmrsAdapter::new(fm_config(None, Some(0o411)));
+        let ctcss = FrsGmrsAdapter::new(fm_config(Some(67.0), None));
+
+        let frame = test_frame();
+        let audio = sender.encode_frame(&frame).await.unwrap();
+
+        let decoded = matching.decode_frame(&audio).await.unwrap();
+        assert_eq!(decoded.payload, frame.payload);
+
+        assert!(other_code.decode_frame(&audio).await.is_err());
+        assert!(ctcss.decode_frame(&audio).await.is_err());
+    }
 }
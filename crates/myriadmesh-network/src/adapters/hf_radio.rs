@@ -10,20 +10,30 @@
 //! - Space weather integration (SFI, K-index) for propagation prediction
 //! - Automatic band selection based on conditions
 //! - License verification (General/Extra class required)
+//! - Stop-and-wait ARQ for unicast frames over noisy paths
 //! - Mock radio interface for testing
 
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
 use crate::license::LicenseManager;
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
-use myriadmesh_protocol::{types::AdapterType, Frame};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
+use myriadmesh_protocol::{types::AdapterType, Frame, NodeId};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, oneshot, RwLock};
 use tokio::task::JoinHandle;
 
 type FrameReceiver = Arc<RwLock<Option<mpsc::Receiver<(Address, Frame)>>>>;
+type PendingAckSlot = Arc<Mutex<Option<PendingAck>>>;
+
+/// Default number of transmissions per frame before ARQ gives up
+pub const DEFAULT_ARQ_MAX_ATTEMPTS: u32 = 3;
+
+/// How often the receive task polls the radio link (ms)
+const LINK_POLL_INTERVAL_MS: u64 = 20;
 
 /// HF radio configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +56,20 @@ pub struct HfRadioConfig {
     pub auto_band_switching: bool,
     /// Space weather API endpoint
     pub space_weather_api: String,
+    /// Require an ACK for each sent frame, retransmitting on timeout.
+    /// Both stations must have ARQ enabled.
+    #[serde(default)]
+    pub arq_enabled: bool,
+    /// Transmissions per frame (first attempt included) before failing
+    #[serde(default = "default_arq_max_attempts")]
+    pub arq_max_attempts: u32,
+    /// ACK timeout override in ms (None = digital mode default)
+    #[serde(default)]
+    pub arq_ack_timeout_ms: Option<u64>,
+}
+
+fn default_arq_max_attempts() -> u32 {
+    DEFAULT_ARQ_MAX_ATTEMPTS
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -60,6 +84,19 @@ pub enum DigitalMode {
     Packet,
 }
 
+impl DigitalMode {
+    /// Default time to wait for an ARQ acknowledgement in this mode.
+    /// Slow modes need room for the frame, the turnaround and the ACK.
+    pub fn default_ack_timeout_ms(&self) -> u64 {
+        match self {
+            DigitalMode::PSK31 => 20_000,
+            DigitalMode::RTTY => 15_000,
+            DigitalMode::FT8 => 30_000, // Two 15 s slots
+            DigitalMode::Packet => 5_000,
+        }
+    }
+}
+
 impl Default for HfRadioConfig {
     fn default() -> Self {
         Self {
@@ -72,6 +109,9 @@ impl Default for HfRadioConfig {
             tx_power_watts: 10,
             auto_band_switching: false,
             space_weather_api: "https://services.swpc.noaa.gov/json/".to_string(),
+            arq_enabled: false,
+            arq_max_attempts: DEFAULT_ARQ_MAX_ATTEMPTS,
+            arq_ack_timeout_ms: None,
         }
     }
 }
@@ -91,9 +131,23 @@ impl HfRadioConfig {
             return Err(NetworkError::Other("Power exceeds 100W limit".to_string()));
         }
 
+        if self.arq_enabled && self.arq_max_attempts == 0 {
+            return Err(NetworkError::Other(
+                "ARQ requires at least one attempt".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
+    /// ACK timeout for the configured digital mode
+    pub fn arq_ack_timeout(&self) -> Duration {
+        Duration::from_millis(
+            self.arq_ack_timeout_ms
+                .unwrap_or_else(|| self.digital_mode.default_ack_timeout_ms()),
+        )
+    }
+
     /// Get band name from frequency
     pub fn get_band(&self) -> &'static str {
         match self.frequency_hz as u32 {
@@ -175,19 +229,22 @@ trait CatControl: Send + Sync {
 }
 
 /// Mock CAT controller for testing
+///
+/// The PTT line is shared with the [`MockHfLink`] of the same radio, which
+/// only puts packets on the air while it is keyed.
 #[allow(dead_code)]
 struct MockCatControl {
     frequency: f32,
     mode: String,
-    ptt: bool,
+    ptt: Arc<AtomicBool>,
 }
 
 impl MockCatControl {
-    fn new() -> Self {
+    fn new(ptt: Arc<AtomicBool>) -> Self {
         Self {
             frequency: 7_040_000.0,
             mode: "USB".to_string(),
-            ptt: false,
+            ptt,
         }
     }
 }
@@ -208,7 +265,7 @@ impl CatControl for MockCatControl {
     }
 
     fn set_ptt(&mut self, active: bool) -> Result<()> {
-        self.ptt = active;
+        self.ptt.store(active, Ordering::SeqCst);
         Ok(())
     }
 
@@ -217,6 +274,84 @@ impl CatControl for MockCatControl {
     }
 }
 
+/// Data link carrying modem packets over the air
+trait HfLink: Send + Sync {
+    /// Transmit one packet
+    fn transmit(&mut self, data: &[u8]) -> Result<()>;
+    /// Next decoded packet, if any
+    fn receive(&mut self) -> Option<Vec<u8>>;
+}
+
+/// Mock radio link for testing
+///
+/// Packets written by one end are read by the end sharing its queues.
+/// `drop_outbound` simulates fades by discarding the next N transmissions.
+/// Nothing goes out unless the radio's PTT is keyed.
+struct MockHfLink {
+    outbound: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    inbound: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    drop_outbound: usize,
+    ptt: Arc<AtomicBool>,
+}
+
+impl MockHfLink {
+    fn new() -> Self {
+        Self {
+            outbound: Arc::new(Mutex::new(VecDeque::new())),
+            inbound: Arc::new(Mutex::new(VecDeque::new())),
+            drop_outbound: 0,
+            ptt: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl HfLink for MockHfLink {
+    fn transmit(&mut self, data: &[u8]) -> Result<()> {
+        if !self.ptt.load(Ordering::SeqCst) {
+            return Err(NetworkError::SendFailed(
+                "Transmit with PTT released".to_string(),
+            ));
+        }
+        if self.drop_outbound > 0 {
+            self.drop_outbound -= 1;
+            return Ok(());
+        }
+        self.outbound.lock().unwrap().push_back(data.to_vec());
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Option<Vec<u8>> {
+        self.inbound.lock().unwrap().pop_front()
+    }
+}
+
+/// On-air packet format with ARQ enabled
+///
+/// Without ARQ a serialized [`Frame`] goes on the air as is. Every ARQ
+/// transmission, ACKs included, identifies the sending station by callsign.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum HfPacket {
+    /// Frame requiring an ACK
+    Data {
+        callsign: String,
+        seq: u16,
+        frame: Box<Frame>,
+    },
+    /// Acknowledges `seq` from the station whose frames carry `source`
+    Ack {
+        callsign: String,
+        seq: u16,
+        source: NodeId,
+    },
+}
+
+/// The single outstanding stop-and-wait transmission
+struct PendingAck {
+    seq: u16,
+    source: NodeId,
+    notify: oneshot::Sender<()>,
+}
+
 /// PSK31 encoder/decoder (simplified)
 struct Psk31Codec {
     sample_rate: u32,
@@ -337,6 +472,12 @@ pub struct HfRadioAdapter {
     psk31_codec: Arc<Psk31Codec>,
     rtty_codec: Arc<RttyCodec>,
     license_manager: Option<Arc<LicenseManager>>,
+    link: Arc<RwLock<Box<dyn HfLink>>>,
+    /// Next ARQ sequence number
+    next_seq: AtomicU16,
+    /// Serializes ARQ sends (one frame in flight)
+    arq_lock: tokio::sync::Mutex<()>,
+    pending_ack: PendingAckSlot,
 }
 
 impl HfRadioAdapter {
@@ -348,6 +489,15 @@ impl HfRadioAdapter {
         config: HfRadioConfig,
         license_manager: Option<Arc<LicenseManager>>,
     ) -> Self {
+        Self::with_link(config, license_manager, MockHfLink::new())
+    }
+
+    fn with_link(
+        config: HfRadioConfig,
+        license_manager: Option<Arc<LicenseManager>>,
+        link: MockHfLink,
+    ) -> Self {
+        let cat = MockCatControl::new(link.ptt.clone());
         let max_message_size = match config.digital_mode {
             DigitalMode::PSK31 => 512,
             DigitalMode::RTTY => 256,
@@ -369,8 +519,8 @@ impl HfRadioAdapter {
             range_meters: 20_000_000.0, // Worldwide via ionosphere
             power_consumption: PowerConsumption::High,
             cost_per_mb: 0.0,
-            supports_broadcast: true,
-            supports_multicast: false,
+            // Stop-and-wait ARQ retransmits unicast frames
+            features: FeatureFlags::from_features(&[Feature::Broadcast, Feature::ReliableDelivery]),
         };
 
         // RESOURCE M3: Bounded channel to prevent memory exhaustion
@@ -392,10 +542,14 @@ impl HfRadioAdapter {
             rx: Arc::new(RwLock::new(Some(incoming_rx))),
             incoming_tx,
             rx_task: Arc::new(RwLock::new(None)),
-            cat: Arc::new(RwLock::new(Box::new(MockCatControl::new()))),
+            cat: Arc::new(RwLock::new(Box::new(cat))),
             psk31_codec: Arc::new(Psk31Codec::new(48000)),
             rtty_codec: Arc::new(RttyCodec::new(48000)),
             license_manager,
+            link: Arc::new(RwLock::new(Box::new(link))),
+            next_seq: AtomicU16::new(rand::random()),
+            arq_lock: tokio::sync::Mutex::new(()),
+            pending_ack: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -450,25 +604,135 @@ impl HfRadioAdapter {
         }
     }
 
-    /// Encode frame for digital mode
-    async fn encode_frame(&self, frame: &Frame) -> Result<Vec<f32>> {
-        let data = bincode::serialize(frame)
-            .map_err(|e| NetworkError::Other(format!("Serialization failed: {}", e)))?;
-
-        let audio = match self.config.digital_mode {
-            DigitalMode::PSK31 => self.psk31_codec.encode(&data),
-            DigitalMode::RTTY => self.rtty_codec.encode(&data),
+    /// Encode packet bytes for digital mode
+    fn encode_audio(&self, data: &[u8]) -> Vec<f32> {
+        match self.config.digital_mode {
+            DigitalMode::PSK31 => self.psk31_codec.encode(data),
+            DigitalMode::RTTY => self.rtty_codec.encode(data),
             DigitalMode::FT8 => {
                 // FT8 simulation - would need WSJT-X integration
-                self.psk31_codec.encode(&data) // Fallback to PSK31
+                self.psk31_codec.encode(data) // Fallback to PSK31
             }
             DigitalMode::Packet => {
                 // AX.25 packet - similar to APRS
-                self.psk31_codec.encode(&data) // Simplified
+                self.psk31_codec.encode(data) // Simplified
             }
+        }
+    }
+
+    /// Key the transmitter and send serialized packet bytes
+    async fn transmit_packet<T: Serialize>(&self, packet: &T) -> Result<()> {
+        let data = bincode::serialize(packet)
+            .map_err(|e| NetworkError::Other(format!("Serialization failed: {}", e)))?;
+
+        // Transmit audio (would send to soundcard)
+        let audio = self.encode_audio(&data);
+        log::debug!("Transmitting {} audio samples", audio.len());
+
+        Self::key_and_transmit(&self.cat, &self.link, &data).await
+    }
+
+    /// Key PTT around one transmission on the link
+    async fn key_and_transmit(
+        cat: &RwLock<Box<dyn CatControl>>,
+        link: &RwLock<Box<dyn HfLink>>,
+        data: &[u8],
+    ) -> Result<()> {
+        // Activate PTT
+        cat.write().await.set_ptt(true)?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let sent = link.write().await.transmit(data);
+
+        // Deactivate PTT
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        cat.write().await.set_ptt(false)?;
+
+        sent
+    }
+
+    /// Stop-and-wait: transmit until ACKed or out of attempts
+    async fn send_with_arq(&self, frame: &Frame) -> Result<()> {
+        let _in_flight = self.arq_lock.lock().await;
+
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let packet = HfPacket::Data {
+            callsign: self.config.callsign.clone(),
+            seq,
+            frame: Box::new(frame.clone()),
         };
+        let ack_timeout = self.config.arq_ack_timeout();
+
+        for attempt in 1..=self.config.arq_max_attempts {
+            let (notify, acked) = oneshot::channel();
+            *self.pending_ack.lock().unwrap() = Some(PendingAck {
+                seq,
+                source: frame.header.source,
+                notify,
+            });
+
+            self.transmit_packet(&packet).await?;
 
-        Ok(audio)
+            if let Ok(Ok(())) = tokio::time::timeout(ack_timeout, acked).await {
+                return Ok(());
+            }
+
+            log::debug!(
+                "HF ARQ: no ACK for seq {} (attempt {}/{})",
+                seq,
+                attempt,
+                self.config.arq_max_attempts
+            );
+        }
+
+        self.pending_ack.lock().unwrap().take();
+        Err(NetworkError::SendFailed(format!(
+            "No ACK for seq {} after {} attempts",
+            seq, self.config.arq_max_attempts
+        )))
+    }
+
+    /// Handle one ARQ packet decoded from the link
+    ///
+    /// Returns an ACK, identified by `own_callsign`, to transmit for ARQ data, and
+    /// delivers each ARQ frame once even when the sender retransmits because
+    /// our ACK was lost.
+    async fn handle_packet(
+        packet: HfPacket,
+        own_callsign: &str,
+        last_seq: &mut HashMap<NodeId, u16>,
+        pending_ack: &PendingAckSlot,
+        incoming_tx: &mpsc::Sender<(Address, Frame)>,
+    ) -> Option<HfPacket> {
+        match packet {
+            HfPacket::Data {
+                callsign,
+                seq,
+                frame,
+            } => {
+                let source = frame.header.source;
+                if last_seq.insert(source, seq) != Some(seq) {
+                    let _ = incoming_tx.send((Address::HfRadio(callsign), *frame)).await;
+                }
+                Some(HfPacket::Ack {
+                    callsign: own_callsign.to_string(),
+                    seq,
+                    source,
+                })
+            }
+            HfPacket::Ack { seq, source, .. } => {
+                let mut slot = pending_ack.lock().unwrap();
+                if slot
+                    .as_ref()
+                    .is_some_and(|p| p.seq == seq && p.source == source)
+                {
+                    if let Some(pending) = slot.take() {
+                        let _ = pending.notify.send(());
+                    }
+                }
+                None
+            }
+        }
     }
 }
 
@@ -522,17 +786,56 @@ impl NetworkAdapter for HfRadioAdapter {
             return Err(NetworkError::AdapterNotReady);
         }
 
-        // Spawn RX task (simplified)
+        // Spawn RX task: poll the link, deliver frames, answer ARQ data
         let incoming_tx = self.incoming_tx.clone();
-        let config = self.config.clone();
+        let link = self.link.clone();
+        let cat = self.cat.clone();
+        let pending_ack = self.pending_ack.clone();
+        let arq_enabled = self.config.arq_enabled;
+        let callsign = self.config.callsign.clone();
 
         let rx_task = tokio::spawn(async move {
+            let mut last_seq = HashMap::new();
             loop {
-                tokio::time::sleep(Duration::from_secs(1)).await;
-                // Mock RX - would decode audio from soundcard
-                // Send test frames occasionally for demo
-                let _ = incoming_tx; // Keep reference
-                let _ = config; // Keep reference
+                let received = link.write().await.receive();
+                let Some(data) = received else {
+                    tokio::time::sleep(Duration::from_millis(LINK_POLL_INTERVAL_MS)).await;
+                    continue;
+                };
+
+                if !arq_enabled {
+                    // Plain frames carry no callsign; the sender is unidentified
+                    match bincode::deserialize::<Frame>(&data) {
+                        Ok(frame) => {
+                            let _ = incoming_tx
+                                .send((Address::HfRadio(String::new()), frame))
+                                .await;
+                        }
+                        Err(_) => log::debug!("HF RX: dropping undecodable frame"),
+                    }
+                    continue;
+                }
+
+                let Ok(packet) = bincode::deserialize::<HfPacket>(&data) else {
+                    log::debug!("HF RX: dropping undecodable packet");
+                    continue;
+                };
+
+                let ack = Self::handle_packet(
+                    packet,
+                    &callsign,
+                    &mut last_seq,
+                    &pending_ack,
+                    &incoming_tx,
+                )
+                .await;
+                if let Some(ack) = ack {
+                    if let Ok(data) = bincode::serialize(&ack) {
+                        if let Err(e) = Self::key_and_transmit(&cat, &link, &data).await {
+                            log::debug!("HF ARQ: failed to send ACK: {}", e);
+                        }
+                    }
+                }
             }
         });
 
@@ -564,21 +867,11 @@ impl NetworkAdapter for HfRadioAdapter {
             self.cat.write().await.set_frequency(best_freq)?;
         }
 
-        // Encode frame
-        let audio = self.encode_frame(frame).await?;
-
-        // Activate PTT
-        self.cat.write().await.set_ptt(true)?;
-        tokio::time::sleep(Duration::from_millis(100)).await;
-
-        // Transmit audio (would send to soundcard)
-        log::debug!("Transmitting {} audio samples", audio.len());
-
-        // Deactivate PTT
-        tokio::time::sleep(Duration::from_millis(100)).await;
-        self.cat.write().await.set_ptt(false)?;
-
-        Ok(())
+        if self.config.arq_enabled {
+            self.send_with_arq(frame).await
+        } else {
+            self.transmit_packet(frame).await
+        }
     }
 
     async fn receive(&self, timeout_ms: u64) -> Result<(Address, Frame)> {
@@ -773,14 +1066,14 @@ mod tests {
 
     #[test]
     fn test_mock_cat_control() {
-        let mut cat = MockCatControl::new();
+        let mut cat = MockCatControl::new(Arc::new(AtomicBool::new(false)));
 
         cat.set_frequency(14_200_000.0).unwrap();
         assert_eq!(cat.get_frequency().unwrap(), 14_200_000.0);
 
         cat.set_mode("USB").unwrap();
         cat.set_ptt(true).unwrap();
-        assert!(cat.ptt);
+        assert!(cat.ptt.load(Ordering::SeqCst));
 
         let s_meter = cat.get_s_meter().unwrap();
         assert!(s_meter <= 9);
@@ -797,6 +1090,161 @@ mod tests {
         assert!(matches!(addr2, Address::HfRadio(_)));
     }
 
+    /// Two links sharing queues, like two stations on one frequency
+    fn link_pair() -> (MockHfLink, MockHfLink) {
+        let a = MockHfLink::new();
+        let b = MockHfLink {
+            outbound: a.inbound.clone(),
+            inbound: a.outbound.clone(),
+            drop_outbound: 0,
+            ptt: Arc::new(AtomicBool::new(false)),
+        };
+        (a, b)
+    }
+
+    fn arq_config() -> HfRadioConfig {
+        HfRadioConfig {
+            arq_enabled: true,
+            arq_ack_timeout_ms: Some(500),
+            ..Default::default()
+        }
+    }
+
+    fn arq_frame() -> Frame {
+        use myriadmesh_protocol::{types::NODE_ID_SIZE, MessageId, MessageType};
+
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let payload = b"hf arq".to_vec();
+        let msg_id = MessageId::generate(&source, &dest, &payload, 0, 0);
+        Frame::new(MessageType::Data, source, dest, payload, msg_id, 0).unwrap()
+    }
+
+    async fn started(config: HfRadioConfig, link: MockHfLink) -> HfRadioAdapter {
+        let mut adapter = HfRadioAdapter::with_link(config, None, link);
+        adapter.initialize().await.unwrap();
+        adapter.start().await.unwrap();
+        adapter
+    }
+
+    #[test]
+    fn test_arq_timeout_depends_on_mode() {
+        let config = HfRadioConfig {
+            digital_mode: DigitalMode::Packet,
+            ..Default::default()
+        };
+        assert_eq!(config.arq_ack_timeout(), Duration::from_millis(5_000));
+
+        let config = HfRadioConfig {
+            digital_mode: DigitalMode::FT8,
+            ..Default::default()
+        };
+        assert!(config.arq_ack_timeout() > Duration::from_millis(15_000));
+
+        assert_eq!(arq_config().arq_ack_timeout(), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_arq_retransmits_after_dropped_attempt() {
+        let (mut tx_link, rx_link) = link_pair();
+        tx_link.drop_outbound = 1; // First attempt lost to a fade
+
+        let sender = started(arq_config(), tx_link).await;
+        let receiver = started(arq_config(), rx_link).await;
+
+        let frame = arq_frame();
+        sender
+            .send(&Address::HfRadio("W1ABC".to_string()), &frame)
+            .await
+            .unwrap();
+
+        let (from, received) = receiver.receive(1000).await.unwrap();
+        assert_eq!(received.payload, frame.payload);
+        assert_eq!(from, Address::HfRadio("N0CALL".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_plain_frames_keep_the_non_arq_format() {
+        let (tx_link, rx_link) = link_pair();
+        let on_air = tx_link.outbound.clone();
+        let sender = started(HfRadioConfig::default(), tx_link).await;
+
+        let frame = arq_frame();
+        sender
+            .send(&Address::HfRadio("W1ABC".to_string()), &frame)
+            .await
+            .unwrap();
+        let data = on_air.lock().unwrap().front().cloned().unwrap();
+        assert_eq!(data, bincode::serialize(&frame).unwrap());
+
+        let receiver = started(HfRadioConfig::default(), rx_link).await;
+        let (_, received) = receiver.receive(1000).await.unwrap();
+        assert_eq!(received.payload, frame.payload);
+    }
+
+    #[tokio::test]
+    async fn test_arq_ack_identifies_station() {
+        let (incoming_tx, _incoming_rx) = mpsc::channel(1);
+        let pending_ack = Arc::new(Mutex::new(None));
+        let packet = HfPacket::Data {
+            callsign: "W1ABC".to_string(),
+            seq: 7,
+            frame: Box::new(arq_frame()),
+        };
+
+        let ack = HfRadioAdapter::handle_packet(
+            packet,
+            "N0CALL",
+            &mut HashMap::new(),
+            &pending_ack,
+            &incoming_tx,
+        )
+        .await;
+        assert!(matches!(
+            ack,
+            Some(HfPacket::Ack { callsign, seq: 7, .. }) if callsign == "N0CALL"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_arq_delivers_once_when_ack_lost() {
+        let (tx_link, mut rx_link) = link_pair();
+        rx_link.drop_outbound = 1; // First ACK lost, sender retransmits
+
+        let sender = started(arq_config(), tx_link).await;
+        let receiver = started(arq_config(), rx_link).await;
+
+        let frame = arq_frame();
+        sender
+            .send(&Address::HfRadio("W1ABC".to_string()), &frame)
+            .await
+            .unwrap();
+
+        assert!(receiver.receive(1000).await.is_ok());
+        assert!(receiver.receive(200).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_arq_fails_after_max_attempts() {
+        let (mut tx_link, rx_link) = link_pair();
+        tx_link.drop_outbound = 2;
+
+        let config = HfRadioConfig {
+            arq_max_attempts: 2,
+            arq_ack_timeout_ms: Some(200),
+            ..arq_config()
+        };
+        let sender = started(config, tx_link).await;
+        let _receiver = started(arq_config(), rx_link).await;
+
+        assert!(matches!(
+            sender
+                .send(&Address::HfRadio("W1ABC".to_string()), &arq_frame())
+                .await,
+            Err(NetworkError::SendFailed(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_adapter_initialization() {
         let mut adapter = HfRadioAdapter::new(HfRadioConfig::default());
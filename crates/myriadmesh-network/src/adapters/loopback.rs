@@ -0,0 +1,478 @@
+//! In-memory loopback adapter
+//!
+//! Two paired adapters exchange frames over `tokio::sync::mpsc` channels
+//! without touching the network, so router and node tests run fast and
+//! deterministically:
+//! - Frames sent on one side are received on the other
+//! - Artificial latency delays delivery without reordering
+//! - A drop rate discards frames silently, like a lossy radio link
+
+use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
+use crate::error::{NetworkError, Result};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
+use myriadmesh_protocol::frame::MAX_FRAME_SIZE;
+use myriadmesh_protocol::types::AdapterType;
+use myriadmesh_protocol::Frame;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::time::{sleep_until, timeout_at, Instant};
+
+/// Prefix of loopback address strings (e.g., "loopback:1")
+pub const LOOPBACK_ADDRESS_PREFIX: &str = "loopback:";
+
+/// Source of unique endpoint IDs, so addresses from different pairs never collide
+static NEXT_ENDPOINT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Loopback adapter configuration
+#[derive(Debug, Clone)]
+pub struct LoopbackConfig {
+    /// Delay between sending a frame and it becoming receivable (milliseconds)
+    pub latency_ms: u64,
+
+    /// Fraction of sent frames silently discarded (0.0 - 1.0)
+    pub drop_rate: f64,
+
+    /// Seed for the drop decision, for reproducible loss patterns
+    /// (None seeds from entropy)
+    pub seed: Option<u64>,
+}
+
+impl Default for LoopbackConfig {
+    fn default() -> Self {
+        LoopbackConfig {
+            latency_ms: 0,
+            drop_rate: 0.0,
+            seed: None,
+        }
+    }
+}
+
+/// Frame in flight to the paired adapter
+struct Delivery {
+    deliver_at: Instant,
+    source: Address,
+    frame: Frame,
+}
+
+/// Receive side of the channel
+struct Inbox {
+    rx: mpsc::UnboundedReceiver<Delivery>,
+
+    /// Frame taken off the channel whose latency had not elapsed when the
+    /// last receive timed out
+    pending: Option<Delivery>,
+}
+
+/// In-memory adapter wired to exactly one peer
+pub struct LoopbackAdapter {
+    /// Adapter status
+    status: AdapterStatus,
+
+    /// Configuration
+    config: LoopbackConfig,
+
+    /// Capabilities
+    capabilities: AdapterCapabilities,
+
+    /// This endpoint's address
+    local_address: Address,
+
+    /// The paired endpoint's address
+    peer_address: Address,
+
+    /// Queue into the paired endpoint's inbox
+    peer_tx: mpsc::UnboundedSender<Delivery>,
+
+    /// Frames sent to this endpoint
+    inbox: Mutex<Inbox>,
+
+    /// Drop decisions
+    rng: std::sync::Mutex<StdRng>,
+
+    /// Frames passed to `send` (including dropped ones)
+    frames_sent: AtomicU64,
+
+    /// Frames discarded by the drop rate
+    frames_dropped: AtomicU64,
+
+    /// Peers we have received frames from
+    peers: RwLock<Vec<PeerInfo>>,
+}
+
+impl LoopbackAdapter {
+    /// Create two adapters wired to each other
+    ///
+    /// Both sides use `config`; with a seed, the two sides draw from
+    /// different but reproducible streams.
+    pub fn pair(config: LoopbackConfig) -> (Self, Self) {
+        let id = NEXT_ENDPOINT_ID.fetch_add(2, Ordering::Relaxed);
+        let address_a = Address::Loopback(format!("{}{}", LOOPBACK_ADDRESS_PREFIX, id));
+        let address_b = Address::Loopback(format!("{}{}", LOOPBACK_ADDRESS_PREFIX, id + 1));
+
+        let (tx_to_a, rx_a) = mpsc::unbounded_channel();
+        let (tx_to_b, rx_b) = mpsc::unbounded_channel();
+
+        let a = Self::new(
+            config.clone(),
+            address_a.clone(),
+            address_b.clone(),
+            tx_to_b,
+            rx_a,
+            0,
+        );
+        let b = Self::new(config, address_b, address_a, tx_to_a, rx_b, 1);
+        (a, b)
+    }
+
+    fn new(
+        config: LoopbackConfig,
+        local_address: Address,
+        peer_address: Address,
+        peer_tx: mpsc::UnboundedSender<Delivery>,
+        rx: mpsc::UnboundedReceiver<Delivery>,
+        side: u64,
+    ) -> Self {
+        let drop_rate = config.drop_rate.clamp(0.0, 1.0);
+        let mut features = vec![Feature::OrderedDelivery];
+        if drop_rate == 0.0 {
+            features.push(Feature::ReliableDelivery);
+        }
+
+        let capabilities = AdapterCapabilities {
+            adapter_type: AdapterType::Unknown,
+            max_message_size: MAX_FRAME_SIZE,
+            typical_latency_ms: config.latency_ms as f64,
+            typical_bandwidth_bps: 1_000_000_000, // Bounded by memory, not a link
+            reliability: 1.0 - drop_rate,
+            range_meters: 0.0,
+            power_consumption: PowerConsumption::None,
+            cost_per_mb: 0.0,
+            features: FeatureFlags::from_features(&features),
+        };
+
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(side)),
+            None => StdRng::from_entropy(),
+        };
+
+        LoopbackAdapter {
+            status: AdapterStatus::Uninitialized,
+            config,
+            capabilities,
+            local_address,
+            peer_address,
+            peer_tx,
+            inbox: Mutex::new(Inbox { rx, pending: None }),
+            rng: std::sync::Mutex::new(rng),
+            frames_sent: AtomicU64::new(0),
+            frames_dropped: AtomicU64::new(0),
+            peers: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Address of the paired adapter
+    pub fn peer_address(&self) -> &Address {
+        &self.peer_address
+    }
+
+    /// Frames passed to `send`, including dropped ones
+    pub fn frames_sent(&self) -> u64 {
+        self.frames_sent.load(Ordering::Relaxed)
+    }
+
+    /// Frames discarded by the configured drop rate
+    pub fn frames_dropped(&self) -> u64 {
+        self.frames_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Queue a frame for the peer; never waits
+    fn enqueue(&self, destination: &Address, frame: &Frame) -> Result<()> {
+        if self.status != AdapterStatus::Ready {
+            return Err(NetworkError::AdapterNotReady);
+        }
+        if *destination != self.peer_address {
+            return Err(NetworkError::InvalidAddress(format!(
+                "{} is not paired with {}",
+                destination, self.local_address
+            )));
+        }
+
+        self.frames_sent.fetch_add(1, Ordering::Relaxed);
+        if self.should_drop() {
+            self.frames_dropped.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        let delivery = Delivery {
+            deliver_at: Instant::now() + Duration::from_millis(self.config.latency_ms),
+            source: self.local_address.clone(),
+            frame: frame.clone(),
+        };
+        self.peer_tx
+            .send(delivery)
+            .map_err(|_| NetworkError::SendFailed("Loopback peer dropped".to_string()))
+    }
+
+    /// Decide whether the next frame is lost
+    fn should_drop(&self) -> bool {
+        if self.config.drop_rate <= 0.0 {
+            return false;
+        }
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        rng.gen::<f64>() < self.config.drop_rate
+    }
+}
+
+#[async_trait::async_trait]
+impl NetworkAdapter for LoopbackAdapter {
+    async fn initialize(&mut self) -> Result<()> {
+        self.status = AdapterStatus::Ready;
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.status = AdapterStatus::ShuttingDown;
+        Ok(())
+    }
+
+    async fn send(&self, destination: &Address, frame: &Frame) -> Result<()> {
+        self.enqueue(destination, frame)
+    }
+
+    fn try_send(&self, destination: &Address, frame: &Frame) -> Result<bool> {
+        // The channel is unbounded, so sending never has to wait
+        self.enqueue(destination, frame)?;
+        Ok(true)
+    }
+
+    async fn receive(&self, timeout_ms: u64) -> Result<(Address, Frame)> {
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+        let mut inbox = self.inbox.lock().await;
+
+        let delivery = match inbox.pending.take() {
+            Some(delivery) => delivery,
+            None => timeout_at(deadline, inbox.rx.recv())
+                .await
+                .map_err(|_| NetworkError::ReceiveFailed("Receive timeout".to_string()))?
+                .ok_or_else(|| NetworkError::ReceiveFailed("Loopback peer dropped".to_string()))?,
+        };
+
+        // Park the frame first so a cancelled or timed-out receive keeps it
+        let deliver_at = delivery.deliver_at;
+        inbox.pending = Some(delivery);
+        if deliver_at > deadline {
+            sleep_until(deadline).await;
+            return Err(NetworkError::ReceiveFailed("Receive timeout".to_string()));
+        }
+        sleep_until(deliver_at).await;
+
+        let Delivery { source, frame, .. } = inbox
+            .pending
+            .take()
+            .ok_or_else(|| NetworkError::ReceiveFailed("Receive timeout".to_string()))?;
+        drop(inbox);
+
+        {
+            let mut peers = self.peers.write().await;
+            let node_id = frame.header.source;
+            if !peers.iter().any(|p| p.node_id == node_id) {
+                peers.push(PeerInfo {
+                    node_id,
+                    address: source.clone(),
+                });
+            }
+        }
+
+        Ok((source, frame))
+    }
+
+    async fn discover_peers(&self) -> Result<Vec<PeerInfo>> {
+        Ok(self.peers.read().await.clone())
+    }
+
+    fn get_status(&self) -> AdapterStatus {
+        self.status
+    }
+
+    fn get_capabilities(&self) -> &AdapterCapabilities {
+        &self.capabilities
+    }
+
+    async fn test_connection(&self, destination: &Address) -> Result<TestResults> {
+        let reachable = *destination == self.peer_address && !self.peer_tx.is_closed();
+        Ok(TestResults {
+            success: reachable,
+            rtt_ms: reachable.then_some(2.0 * self.config.latency_ms as f64),
+            error: (!reachable).then(|| format!("{} is not reachable", destination)),
+        })
+    }
+
+    fn get_local_address(&self) -> Option<Address> {
+        Some(self.local_address.clone())
+    }
+
+    fn parse_address(&self, addr_str: &str) -> Result<Address> {
+        match addr_str.strip_prefix(LOOPBACK_ADDRESS_PREFIX) {
+            Some(id) if id.parse::<u64>().is_ok() => Ok(Address::Loopback(addr_str.to_string())),
+            _ => Err(NetworkError::InvalidAddress(format!(
+                "Expected {}<id>, got {}",
+                LOOPBACK_ADDRESS_PREFIX, addr_str
+            ))),
+        }
+    }
+
+    fn supports_address(&self, address: &Address) -> bool {
+        matches!(address, Address::Loopback(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myriadmesh_protocol::message::MessageId;
+    use myriadmesh_protocol::types::NODE_ID_SIZE;
+    use myriadmesh_protocol::{MessageType, NodeId};
+
+    fn frame(source: u8, payload: Vec<u8>) -> Frame {
+        Frame::new(
+            MessageType::Data,
+            NodeId::from_bytes([source; NODE_ID_SIZE]),
+            NodeId::from_bytes([source ^ 0xFF; NODE_ID_SIZE]),
+            payload,
+            MessageId::from_bytes([0u8; 16]),
+            0,
+        )
+        .unwrap()
+    }
+
+    async fn ready_pair(config: LoopbackConfig) -> (LoopbackAdapter, LoopbackAdapter) {
+        let (mut a, mut b) = LoopbackAdapter::pair(config);
+        a.initialize().await.unwrap();
+        b.initialize().await.unwrap();
+        (a, b)
+    }
+
+    #[tokio::test]
+    async fn test_pair_exchanges_frames() {
+        let (a, b) = ready_pair(LoopbackConfig::default()).await;
+
+        a.send(
+            b.get_local_address().as_ref().unwrap(),
+            &frame(1, vec![1, 2, 3]),
+        )
+        .await
+        .unwrap();
+        let (source, received) = b.receive(100).await.unwrap();
+        assert_eq!(source, a.get_local_address().unwrap());
+        assert_eq!(received.payload, vec![1, 2, 3]);
+
+        b.send(b.peer_address(), &frame(2, vec![4, 5]))
+            .await
+            .unwrap();
+        let (source, received) = a.receive(100).await.unwrap();
+        assert_eq!(&source, a.peer_address());
+        assert_eq!(received.payload, vec![4, 5]);
+
+        // Frames arrive in send order
+        for i in 0..10u8 {
+            a.send(a.peer_address(), &frame(1, vec![i])).await.unwrap();
+        }
+        for i in 0..10u8 {
+            assert_eq!(b.receive(100).await.unwrap().1.payload, vec![i]);
+        }
+        assert!(b.receive(10).await.is_err());
+
+        let peers = b.discover_peers().await.unwrap();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].node_id, NodeId::from_bytes([1; NODE_ID_SIZE]));
+    }
+
+    #[tokio::test]
+    async fn test_drop_rate_loses_expected_fraction() {
+        let (a, b) = ready_pair(LoopbackConfig {
+            drop_rate: 0.25,
+            seed: Some(7),
+            ..Default::default()
+        })
+        .await;
+
+        const FRAMES: u64 = 2000;
+        for _ in 0..FRAMES {
+            a.send(a.peer_address(), &frame(1, vec![0])).await.unwrap();
+        }
+        let mut received = 0;
+        while b.receive(10).await.is_ok() {
+            received += 1;
+        }
+
+        assert_eq!(a.frames_sent(), FRAMES);
+        assert_eq!(received + a.frames_dropped(), FRAMES);
+        let lost = a.frames_dropped() as f64 / FRAMES as f64;
+        assert!((0.2..0.3).contains(&lost), "lost {}", lost);
+        assert!(!a.get_capabilities().supports(Feature::ReliableDelivery));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_latency_delays_delivery() {
+        let (a, b) = ready_pair(LoopbackConfig {
+            latency_ms: 200,
+            ..Default::default()
+        })
+        .await;
+
+        let start = Instant::now();
+        a.send(a.peer_address(), &frame(1, vec![9])).await.unwrap();
+
+        // Not yet deliverable; the frame is kept for the next receive
+        assert!(b.receive(50).await.is_err());
+        let (_, received) = b.receive(500).await.unwrap();
+        assert_eq!(received.payload, vec![9]);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_send_errors() {
+        let (mut a, b) = LoopbackAdapter::pair(LoopbackConfig::default());
+        let peer = a.peer_address().clone();
+        assert!(matches!(
+            a.send(&peer, &frame(1, vec![])).await,
+            Err(NetworkError::AdapterNotReady)
+        ));
+
+        a.initialize().await.unwrap();
+        let stranger = Address::Loopback("loopback:0".to_string());
+        assert!(matches!(
+            a.send(&stranger, &frame(1, vec![])).await,
+            Err(NetworkError::InvalidAddress(_))
+        ));
+        assert!(!a.test_connection(&stranger).await.unwrap().success);
+        assert!(a.test_connection(&peer).await.unwrap().success);
+
+        drop(b);
+        assert!(matches!(
+            a.send(&peer, &frame(1, vec![])).await,
+            Err(NetworkError::SendFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_address() {
+        let (a, _b) = LoopbackAdapter::pair(LoopbackConfig::default());
+
+        assert_eq!(
+            a.parse_address("loopback:42").unwrap(),
+            Address::Loopback("loopback:42".to_string())
+        );
+        assert!(a.parse_address("loopback:").is_err());
+        assert!(a.parse_address("127.0.0.1:4001").is_err());
+        assert!(a.supports_address(a.peer_address()));
+        assert!(!a.supports_address(&Address::Ethernet("127.0.0.1:4001".to_string())));
+    }
+}
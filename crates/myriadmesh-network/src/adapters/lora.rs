@@ -14,20 +14,62 @@
 
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
 use myriadmesh_protocol::{
     types::{AdapterType, NODE_ID_SIZE},
     Frame, MessageId, MessageType, NodeId,
 };
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::{mpsc, RwLock};
 use tokio::task::JoinHandle;
 
 type FrameReceiver = Arc<RwLock<Option<mpsc::Receiver<(Address, Frame)>>>>;
 
+/// Default duty-cycle observation window (1 hour, as in ETSI EN 300 220)
+pub const DEFAULT_DUTY_CYCLE_WINDOW_MS: u64 = 3_600_000;
+
+/// LoRa preamble length in symbols
+const PREAMBLE_SYMBOLS: f64 = 8.0;
+
+/// Regulatory region, which sets the maximum permitted duty cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LoRaRegion {
+    /// Europe 863-870 MHz: 1% duty cycle
+    #[default]
+    Eu868,
+    /// Europe 433 MHz: 10% duty cycle
+    Eu433,
+    /// North America 902-928 MHz: no duty-cycle limit (dwell time instead)
+    Us915,
+    /// Asia 923 MHz: 1% duty cycle
+    As923,
+}
+
+impl LoRaRegion {
+    /// Regulatory duty-cycle cap as a percentage
+    pub fn max_duty_cycle_percent(&self) -> f32 {
+        match self {
+            LoRaRegion::Eu868 | LoRaRegion::As923 => 1.0,
+            LoRaRegion::Eu433 => 10.0,
+            LoRaRegion::Us915 => 100.0,
+        }
+    }
+}
+
+/// What `send` does when the duty-cycle budget is exhausted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DutyCyclePolicy {
+    /// Fail immediately with `NetworkError::DutyCycleExceeded`
+    #[default]
+    Reject,
+    /// Wait until enough airtime has aged out of the window, then transmit
+    Queue,
+}
+
 /// LoRaWAN/Meshtastic adapter configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoRaConfig {
@@ -43,8 +85,18 @@ pub struct LoRaConfig {
     pub tx_power_dbm: i8,
     /// Enable Meshtastic protocol compatibility
     pub meshtastic_mode: bool,
-    /// Duty cycle limit as percentage (EU: 1%, US: unlimited)
+    /// Duty cycle limit as percentage (EU: 1%, US: unlimited).
+    /// The effective limit never exceeds the region's cap.
     pub duty_cycle_percent: f32,
+    /// Regulatory region
+    #[serde(default)]
+    pub region: LoRaRegion,
+    /// Rolling window over which airtime is budgeted (ms)
+    #[serde(default = "default_duty_cycle_window_ms")]
+    pub duty_cycle_window_ms: u64,
+    /// Reject or queue sends once the budget is exhausted
+    #[serde(default)]
+    pub duty_cycle_policy: DutyCyclePolicy,
     /// SPI device path for modem (e.g., "/dev/spidev0.0")
     pub spi_device: String,
     /// Use mock hardware (for testing without physical modem)
@@ -61,13 +113,26 @@ impl Default for LoRaConfig {
             tx_power_dbm: 14,
             meshtastic_mode: true,
             duty_cycle_percent: 1.0,
+            region: LoRaRegion::default(),
+            duty_cycle_window_ms: DEFAULT_DUTY_CYCLE_WINDOW_MS,
+            duty_cycle_policy: DutyCyclePolicy::default(),
             spi_device: "/dev/spidev0.0".to_string(),
             use_mock: true, // Default to mock for safety
         }
     }
 }
 
+fn default_duty_cycle_window_ms() -> u64 {
+    DEFAULT_DUTY_CYCLE_WINDOW_MS
+}
+
 impl LoRaConfig {
+    /// Duty cycle actually enforced: the configured limit, capped by region
+    pub fn effective_duty_cycle_percent(&self) -> f32 {
+        self.duty_cycle_percent
+            .min(self.region.max_duty_cycle_percent())
+    }
+
     /// Validate configuration parameters
     pub fn validate(&self) -> Result<()> {
         if !(7..=12).contains(&self.spreading_factor) {
@@ -100,23 +165,38 @@ impl LoRaConfig {
             ));
         }
 
+        if self.duty_cycle_window_ms == 0 {
+            return Err(NetworkError::InitializationFailed(
+                "Duty cycle window must be non-zero".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
     /// Calculate time-on-air for a given payload size in milliseconds
+    ///
+    /// Uses the Semtech SX127x/SX126x formula with explicit header and CRC
+    /// enabled, rounded up to whole milliseconds.
     pub fn calculate_time_on_air(&self, payload_bytes: usize) -> u64 {
-        // Simplified time-on-air calculation
-        // Real implementation would use proper LoRa formula
-        let symbol_time_ms =
-            (1000.0 * (1 << self.spreading_factor) as f64) / (self.bandwidth_khz as f64 * 1000.0);
+        let sf = self.spreading_factor as f64;
+        let symbol_time_ms = (1u64 << self.spreading_factor) as f64 / self.bandwidth_khz as f64;
+
+        // Coding rate index: 4/5 -> 1 ... 4/8 -> 4
+        let cr = ((4.0 / self.coding_rate as f64).round() - 4.0).clamp(1.0, 4.0);
 
-        // Preamble + header + payload symbols
-        let preamble_symbols = 8.0;
-        let header_symbols = 5.0;
-        let payload_symbols = ((payload_bytes as f64 * 8.0) / self.spreading_factor as f64).ceil();
+        // Low data rate optimisation is mandated above 16 ms symbols
+        let low_data_rate = if symbol_time_ms > 16.0 { 1.0 } else { 0.0 };
 
-        let total_symbols = preamble_symbols + header_symbols + payload_symbols;
-        (total_symbols * symbol_time_ms) as u64
+        let crc = 1.0;
+        let implicit_header = 0.0;
+        let numerator =
+            8.0 * payload_bytes as f64 - 4.0 * sf + 28.0 + 16.0 * crc - 20.0 * implicit_header;
+        let denominator = 4.0 * (sf - 2.0 * low_data_rate);
+        let payload_symbols = 8.0 + ((numerator / denominator).ceil() * (cr + 4.0)).max(0.0);
+
+        let preamble_ms = (PREAMBLE_SYMBOLS + 4.25) * symbol_time_ms;
+        (preamble_ms + payload_symbols * symbol_time_ms).ceil() as u64
     }
 }
 
@@ -135,61 +215,84 @@ struct LoRaState {
     packets_received: u64,
 }
 
-/// Duty cycle tracker
+/// Outcome of asking the duty-cycle tracker for airtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DutyCycleDecision {
+    /// Airtime reserved; transmit now
+    Granted,
+    /// Budget exhausted; enough airtime ages out after this many ms
+    Wait(u64),
+    /// The transmission alone exceeds the whole budget
+    Never,
+}
+
+/// Rolling-window duty cycle tracker
 struct DutyCycleTracker {
-    /// Cumulative TX time in current window (ms)
-    tx_time_ms: Arc<AtomicU64>,
-    /// Window start timestamp (Unix ms)
-    window_start_ms: Arc<AtomicU64>,
-    /// Duty cycle limit (0.0-1.0)
-    limit: f32,
+    /// (start time Unix ms, airtime ms) of transmissions still in the window
+    transmissions: Mutex<VecDeque<(u64, u64)>>,
+    /// Airtime allowed per window (ms)
+    budget_ms: u64,
     /// Window duration (ms) - typically 1 hour
     window_duration_ms: u64,
 }
 
 impl DutyCycleTracker {
-    fn new(limit_percent: f32) -> Self {
+    fn new(limit_percent: f32, window_duration_ms: u64) -> Self {
         Self {
-            tx_time_ms: Arc::new(AtomicU64::new(0)),
-            window_start_ms: Arc::new(AtomicU64::new(now_ms())),
-            limit: limit_percent / 100.0,
-            window_duration_ms: 3_600_000, // 1 hour
+            transmissions: Mutex::new(VecDeque::new()),
+            budget_ms: (window_duration_ms as f64 * limit_percent as f64 / 100.0) as u64,
+            window_duration_ms,
         }
     }
 
-    /// Check if transmission is allowed and record usage
-    fn check_and_record(&self, tx_duration_ms: u64) -> Result<()> {
-        let current_time = now_ms();
-        let window_start = self.window_start_ms.load(Ordering::Relaxed);
+    /// Reserve airtime for a transmission starting now
+    fn try_reserve(&self, tx_duration_ms: u64) -> DutyCycleDecision {
+        self.try_reserve_at(now_ms(), tx_duration_ms)
+    }
 
-        // Check if we need to reset the window
-        if current_time >= window_start + self.window_duration_ms {
-            self.tx_time_ms.store(0, Ordering::Relaxed);
-            self.window_start_ms.store(current_time, Ordering::Relaxed);
+    fn try_reserve_at(&self, now: u64, tx_duration_ms: u64) -> DutyCycleDecision {
+        if tx_duration_ms > self.budget_ms {
+            return DutyCycleDecision::Never;
         }
 
-        let current_tx_time = self.tx_time_ms.load(Ordering::Relaxed);
-        let projected_tx_time = current_tx_time + tx_duration_ms;
+        let mut transmissions = self.transmissions.lock().unwrap();
+        while transmissions
+            .front()
+            .is_some_and(|&(at, _)| at + self.window_duration_ms <= now)
+        {
+            transmissions.pop_front();
+        }
 
-        // Check if would exceed duty cycle
-        let max_tx_time = (self.window_duration_ms as f64 * self.limit as f64) as u64;
+        let used: u64 = transmissions.iter().map(|&(_, airtime)| airtime).sum();
+        if used + tx_duration_ms <= self.budget_ms {
+            transmissions.push_back((now, tx_duration_ms));
+            return DutyCycleDecision::Granted;
+        }
 
-        if projected_tx_time > max_tx_time {
-            return Err(NetworkError::SendFailed(format!(
-                "Duty cycle limit exceeded: {} ms used, {} ms max in window",
-                projected_tx_time, max_tx_time
-            )));
+        // Find the oldest transmission whose expiry frees enough budget
+        let mut freed = 0;
+        for &(at, airtime) in transmissions.iter() {
+            freed += airtime;
+            if used - freed + tx_duration_ms <= self.budget_ms {
+                return DutyCycleDecision::Wait(at + self.window_duration_ms - now);
+            }
         }
 
-        // Record the transmission
-        self.tx_time_ms.fetch_add(tx_duration_ms, Ordering::Relaxed);
-        Ok(())
+        DutyCycleDecision::Never
     }
 
     /// Get current duty cycle usage (0.0-1.0)
     #[allow(dead_code)]
     fn get_usage(&self) -> f32 {
-        let tx_time = self.tx_time_ms.load(Ordering::Relaxed);
+        let now = now_ms();
+        let tx_time: u64 = self
+            .transmissions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|&&(at, _)| at + self.window_duration_ms > now)
+            .map(|&(_, airtime)| airtime)
+            .sum();
         (tx_time as f32) / (self.window_duration_ms as f32)
     }
 }
@@ -402,9 +505,9 @@ impl LoRaAdapter {
             reliability: 0.95,          // High reliability, LOS
             range_meters: 15000.0,      // 15 km typical
             power_consumption: PowerConsumption::Low,
-            cost_per_mb: 0.0,         // License-free spectrum
-            supports_broadcast: true, // LoRa broadcast capable
-            supports_multicast: true, // Supports group addressing
+            cost_per_mb: 0.0, // License-free spectrum
+            // LoRa broadcast capable, supports group addressing
+            features: FeatureFlags::from_features(&[Feature::Broadcast, Feature::Multicast]),
         };
 
         // RESOURCE M3: Bounded channel to prevent memory exhaustion
@@ -414,7 +517,10 @@ impl LoRaAdapter {
         let modem: Box<dyn LoRaModem> = Box::new(MockLoRaModem::new());
 
         Self {
-            duty_cycle: Arc::new(DutyCycleTracker::new(config.duty_cycle_percent)),
+            duty_cycle: Arc::new(DutyCycleTracker::new(
+                config.effective_duty_cycle_percent(),
+                config.duty_cycle_window_ms,
+            )),
             config: config.clone(),
             status: Arc::new(RwLock::new(AdapterStatus::Uninitialized)),
             capabilities,
@@ -433,6 +539,35 @@ impl LoRaAdapter {
         }
     }
 
+    /// Reserve airtime under the duty-cycle budget, waiting if the policy
+    /// allows it
+    async fn reserve_airtime(&self, toa_ms: u64) -> Result<()> {
+        loop {
+            match self.duty_cycle.try_reserve(toa_ms) {
+                DutyCycleDecision::Granted => return Ok(()),
+                DutyCycleDecision::Wait(wait_ms)
+                    if self.config.duty_cycle_policy == DutyCyclePolicy::Queue =>
+                {
+                    log::debug!("LoRa duty cycle exhausted, delaying TX {} ms", wait_ms);
+                    tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+                }
+                DutyCycleDecision::Wait(wait_ms) => {
+                    return Err(NetworkError::DutyCycleExceeded(format!(
+                        "{} ms airtime needed, budget recovers in {} ms",
+                        toa_ms, wait_ms
+                    )))
+                }
+                DutyCycleDecision::Never => {
+                    return Err(NetworkError::DutyCycleExceeded(format!(
+                        "{} ms airtime exceeds the {:.2}% budget",
+                        toa_ms,
+                        self.config.effective_duty_cycle_percent()
+                    )))
+                }
+            }
+        }
+    }
+
     /// Start background receive task
     async fn start_rx_task(&self) -> Result<()> {
         let modem = self.modem.clone();
@@ -597,7 +732,7 @@ impl NetworkAdapter for LoRaAdapter {
         let toa_ms = self.config.calculate_time_on_air(data.len());
 
         // Check duty cycle
-        self.duty_cycle.check_and_record(toa_ms)?;
+        self.reserve_airtime(toa_ms).await?;
 
         // Transmit
         {
@@ -749,17 +884,152 @@ mod tests {
         assert!(toa < 5000); // Should be less than 5 seconds for small packet
     }
 
+    #[test]
+    fn test_lora_time_on_air_matches_semtech_formula() {
+        // SF7/125 kHz/CR 4/5, 10 bytes: 41.216 ms
+        let config = LoRaConfig::default();
+        assert_eq!(config.calculate_time_on_air(10), 42);
+
+        // SF12/125 kHz with low data rate optimisation, 51 bytes: 2465.792 ms
+        let config = LoRaConfig {
+            spreading_factor: 12,
+            ..Default::default()
+        };
+        assert_eq!(config.calculate_time_on_air(51), 2466);
+
+        // Wider bandwidth is faster, longer payload is slower
+        let wide = LoRaConfig {
+            bandwidth_khz: 500,
+            ..Default::default()
+        };
+        assert!(wide.calculate_time_on_air(10) < LoRaConfig::default().calculate_time_on_air(10));
+        assert!(
+            LoRaConfig::default().calculate_time_on_air(200)
+                > LoRaConfig::default().calculate_time_on_air(10)
+        );
+    }
+
+    #[test]
+    fn test_region_caps_duty_cycle() {
+        let config = LoRaConfig {
+            duty_cycle_percent: 50.0,
+            ..Default::default()
+        };
+        assert_eq!(config.effective_duty_cycle_percent(), 1.0);
+
+        let config = LoRaConfig {
+            region: LoRaRegion::Us915,
+            duty_cycle_percent: 50.0,
+            ..Default::default()
+        };
+        assert_eq!(config.effective_duty_cycle_percent(), 50.0);
+    }
+
     #[test]
     fn test_duty_cycle_tracker() {
-        let tracker = DutyCycleTracker::new(1.0); // 1% duty cycle
+        let tracker = DutyCycleTracker::new(1.0, DEFAULT_DUTY_CYCLE_WINDOW_MS); // 1% duty cycle
 
         // Should allow small transmission
-        assert!(tracker.check_and_record(100).is_ok());
+        assert_eq!(tracker.try_reserve(100), DutyCycleDecision::Granted);
 
         // Usage should be non-zero
         assert!(tracker.get_usage() > 0.0);
     }
 
+    #[test]
+    fn test_duty_cycle_budget_recovers_over_time() {
+        // 1% of 10 s = 100 ms of airtime
+        let tracker = DutyCycleTracker::new(1.0, 10_000);
+
+        assert_eq!(tracker.try_reserve_at(0, 40), DutyCycleDecision::Granted);
+        assert_eq!(
+            tracker.try_reserve_at(1_000, 40),
+            DutyCycleDecision::Granted
+        );
+
+        // Third send would exceed the budget until the first ages out
+        assert_eq!(
+            tracker.try_reserve_at(2_000, 40),
+            DutyCycleDecision::Wait(8_000)
+        );
+        assert_eq!(
+            tracker.try_reserve_at(9_999, 40),
+            DutyCycleDecision::Wait(1)
+        );
+        assert_eq!(
+            tracker.try_reserve_at(10_000, 40),
+            DutyCycleDecision::Granted
+        );
+
+        // A single transmission larger than the budget can never go out
+        assert_eq!(
+            tracker.try_reserve_at(10_000, 101),
+            DutyCycleDecision::Never
+        );
+    }
+
+    fn duty_cycle_frame() -> Frame {
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let payload = vec![0xAB; 16];
+        let msg_id = MessageId::generate(&source, &dest, &payload, 0, 0);
+        Frame::new(MessageType::Data, source, dest, payload, msg_id, 0).unwrap()
+    }
+
+    /// Config whose window fits exactly two transmissions of `duty_cycle_frame`
+    fn two_packet_config(policy: DutyCyclePolicy) -> LoRaConfig {
+        // 500 kHz keeps airtime short enough for a sub-second window
+        let base = LoRaConfig {
+            bandwidth_khz: 500,
+            ..Default::default()
+        };
+        let toa =
+            base.calculate_time_on_air(MeshtasticCodec::encode(&duty_cycle_frame()).unwrap().len());
+        let window_ms = 500;
+        LoRaConfig {
+            region: LoRaRegion::Us915,
+            duty_cycle_percent: (toa as f32 * 2.5) / window_ms as f32 * 100.0,
+            duty_cycle_window_ms: window_ms,
+            duty_cycle_policy: policy,
+            ..base
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rapid_sends_hit_duty_cycle_cap() {
+        let mut adapter = LoRaAdapter::new(two_packet_config(DutyCyclePolicy::Reject));
+        adapter.initialize().await.unwrap();
+        let dest = Address::LoRa("lora://test".to_string());
+        let frame = duty_cycle_frame();
+
+        assert!(adapter.send(&dest, &frame).await.is_ok());
+        assert!(adapter.send(&dest, &frame).await.is_ok());
+        assert!(matches!(
+            adapter.send(&dest, &frame).await,
+            Err(NetworkError::DutyCycleExceeded(_))
+        ));
+
+        // Budget recovers once the window rolls past the earlier sends
+        tokio::time::sleep(Duration::from_millis(550)).await;
+        assert!(adapter.send(&dest, &frame).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_queue_policy_delays_until_budget_recovers() {
+        let mut adapter = LoRaAdapter::new(two_packet_config(DutyCyclePolicy::Queue));
+        adapter.initialize().await.unwrap();
+        let dest = Address::LoRa("lora://test".to_string());
+        let frame = duty_cycle_frame();
+
+        let start = std::time::Instant::now();
+        adapter.send(&dest, &frame).await.unwrap();
+        adapter.send(&dest, &frame).await.unwrap();
+        adapter.send(&dest, &frame).await.unwrap();
+
+        // Third send had to wait for the first to leave the 500 ms window
+        assert!(start.elapsed() >= Duration::from_millis(400));
+    }
+
     #[test]
     fn test_meshtastic_codec() {
         let source = NodeId::from_bytes([0u8; NODE_ID_SIZE]);
@@ -815,7 +1085,7 @@ mod tests {
 
         assert_eq!(caps.adapter_type, AdapterType::LoRaWAN);
         assert_eq!(caps.max_message_size, 240);
-        assert!(caps.supports_broadcast);
+        assert!(caps.supports_broadcast());
         assert_eq!(caps.range_meters, 15000.0);
     }
 
@@ -4,6 +4,9 @@ pub mod bluetooth;
 pub mod bluetooth_le;
 pub mod cellular;
 pub mod ethernet;
+pub mod loopback;
+pub mod tor;
+pub mod websocket;
 
 // Phase 5: Specialized Adapters
 pub mod aprs;
@@ -15,13 +18,16 @@ pub mod wifi_halow;
 
 pub use bluetooth::{BluetoothAdapter, BluetoothConfig};
 pub use bluetooth_le::{BleAdapter, BleConfig};
-pub use cellular::{CellularAdapter, CellularConfig, NetworkType};
+pub use cellular::{CellularAdapter, CellularConfig, CellularStatus, NetworkType};
 pub use ethernet::{EthernetAdapter, EthernetConfig};
+pub use loopback::{LoopbackAdapter, LoopbackConfig};
+pub use tor::{TorAdapter, TorConfig};
+pub use websocket::{WebSocketAdapter, WebSocketConfig};
 
 // Phase 5 exports
-pub use aprs::{AprsAdapter, AprsConfig};
+pub use aprs::{AprsAdapter, AprsConfig, AprsSymbol, Callsign};
 pub use dialup::{DialupAdapter, DialupConfig, ModemType};
 pub use frsgmrs::{FrsGmrsAdapter, FrsGmrsConfig, ModulationType};
 pub use hf_radio::{DigitalMode, HfRadioAdapter, HfRadioConfig};
-pub use lora::{LoRaAdapter, LoRaConfig};
-pub use wifi_halow::{WifiHalowAdapter, WifiHalowConfig};
+pub use lora::{DutyCyclePolicy, LoRaAdapter, LoRaConfig, LoRaRegion};
+pub use wifi_halow::{RawSchedule, WifiHalowAdapter, WifiHalowConfig};
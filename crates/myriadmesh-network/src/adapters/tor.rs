@@ -0,0 +1,879 @@
+//! Tor network adapter
+//!
+//! Reaches peers' v3 onion services through Tor's SOCKS5 proxy and can
+//! publish an ephemeral onion service so peers can reach this node:
+//! - Outbound: SOCKS5 CONNECT by hostname, so resolution stays inside Tor
+//! - Inbound (optional): `ADD_ONION` on the control port maps a virtual port
+//!   to a local listener for as long as the control connection stays open
+//! - Frames are length-prefixed bincode, as on the i2p adapter
+
+use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
+use crate::error::{NetworkError, Result};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
+use myriadmesh_protocol::types::AdapterType;
+use myriadmesh_protocol::{Frame, NodeId};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tiny_keccak::{Hasher, Sha3};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+/// Maximum serialized frame size carried over an onion stream
+pub const MAX_TOR_FRAME_SIZE: usize = 64 * 1024;
+
+/// Port used when an onion address does not specify one
+pub const DEFAULT_ONION_PORT: u16 = 4001;
+
+/// Length of a v3 onion service ID (base32 of key, checksum and version)
+const ONION_V3_ID_LEN: usize = 56;
+
+/// Onion service version byte for v3 addresses
+const ONION_V3_VERSION: u8 = 3;
+
+/// Received frames queued for `receive` across all inbound streams
+///
+/// When full, stream readers stop reading and TCP pushes back on the peer.
+const INCOMING_QUEUE_SIZE: usize = 1000;
+
+/// Tor adapter configuration
+#[derive(Debug, Clone)]
+pub struct TorConfig {
+    /// Tor SOCKS5 proxy address
+    pub socks_addr: String,
+
+    /// Tor control port address; `None` disables the inbound onion service
+    pub control_addr: Option<String>,
+
+    /// Control port password (`HashedControlPassword`), if required
+    pub control_password: Option<String>,
+
+    /// Local address the onion service forwards to
+    pub listen_addr: String,
+
+    /// Port peers dial on this node's onion address
+    pub virtual_port: u16,
+
+    /// Timeout for establishing outbound circuits (milliseconds)
+    pub connect_timeout_ms: u64,
+}
+
+impl Default for TorConfig {
+    fn default() -> Self {
+        TorConfig {
+            socks_addr: "127.0.0.1:9050".to_string(),
+            control_addr: None,
+            control_password: None,
+            listen_addr: "127.0.0.1:0".to_string(),
+            virtual_port: DEFAULT_ONION_PORT,
+            // Onion circuits take far longer to build than direct connections
+            connect_timeout_ms: 60_000,
+        }
+    }
+}
+
+/// Open outbound streams, keyed by onion address
+type ConnectionMap = HashMap<String, Arc<Mutex<TcpStream>>>;
+
+/// Serialized frame received from a peer
+type IncomingFrame = (Address, Vec<u8>);
+
+/// Tor network adapter
+pub struct TorAdapter {
+    /// Adapter status
+    status: Arc<RwLock<AdapterStatus>>,
+
+    /// Configuration
+    config: TorConfig,
+
+    /// Local NodeId
+    local_node_id: NodeId,
+
+    /// Outbound streams through the SOCKS proxy
+    connections: Arc<Mutex<ConnectionMap>>,
+
+    /// Sender half of the incoming frame queue, shared by inbound streams
+    incoming_tx: mpsc::Sender<IncomingFrame>,
+
+    /// Receiver half of the incoming frame queue
+    incoming_rx: Arc<Mutex<mpsc::Receiver<IncomingFrame>>>,
+
+    /// Control connection; the ephemeral onion service lives while it is open
+    control: Mutex<Option<BufReader<TcpStream>>>,
+
+    /// Published onion address (`<id>.onion:<port>`)
+    onion_address: Arc<RwLock<Option<String>>>,
+
+    /// Bound local address the onion service forwards to
+    local_addr: Arc<RwLock<Option<SocketAddr>>>,
+
+    /// Background accept and connection tasks
+    tasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+
+    /// Adapter capabilities
+    capabilities: AdapterCapabilities,
+}
+
+impl TorAdapter {
+    /// Create new Tor adapter
+    pub fn new(local_node_id: NodeId, config: TorConfig) -> Self {
+        let capabilities = AdapterCapabilities {
+            adapter_type: AdapterType::Tor,
+            max_message_size: MAX_TOR_FRAME_SIZE,
+            typical_latency_ms: 2000.0, // Three-hop circuits each way
+            typical_bandwidth_bps: 1_000_000, // ~1 Mbps
+            reliability: 0.90,
+            range_meters: 0.0, // Global reach
+            power_consumption: PowerConsumption::Medium,
+            cost_per_mb: 0.0,
+            // Broadcasts would link the onion address to every recipient
+            features: FeatureFlags::from_features(&[
+                Feature::ReliableDelivery,
+                Feature::OrderedDelivery,
+                Feature::Encryption,
+                Feature::Anonymity,
+            ]),
+        };
+
+        let (incoming_tx, incoming_rx) = mpsc::channel(INCOMING_QUEUE_SIZE);
+
+        TorAdapter {
+            status: Arc::new(RwLock::new(AdapterStatus::Uninitialized)),
+            config,
+            local_node_id,
+            connections: Arc::new(Mutex::new(HashMap::new())),
+            incoming_tx,
+            incoming_rx: Arc::new(Mutex::new(incoming_rx)),
+            control: Mutex::new(None),
+            onion_address: Arc::new(RwLock::new(None)),
+            local_addr: Arc::new(RwLock::new(None)),
+            tasks: Arc::new(Mutex::new(Vec::new())),
+            capabilities,
+        }
+    }
+
+    /// Create with default configuration (outbound only)
+    pub fn new_default(local_node_id: NodeId) -> Self {
+        Self::new(local_node_id, TorConfig::default())
+    }
+
+    /// Local NodeId
+    pub fn local_node_id(&self) -> NodeId {
+        self.local_node_id
+    }
+
+    /// Published onion address, if an onion service is running
+    pub async fn onion_address(&self) -> Option<String> {
+        self.onion_address.read().await.clone()
+    }
+
+    /// Validate a v3 onion address, returning its host and port
+    ///
+    /// Accepts `<id>.onion` or `<id>.onion:<port>`; the ID's version byte and
+    /// checksum must match, so typos are caught before dialling.
+    pub fn validate_onion_address(address: &str) -> Result<(String, u16)> {
+        let invalid = |reason: &str| {
+            NetworkError::InvalidAddress(format!("Invalid onion address {}: {}", address, reason))
+        };
+
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>()
+                    .ok()
+                    .filter(|p| *p != 0)
+                    .ok_or_else(|| invalid("bad port"))?,
+            ),
+            None => (address, DEFAULT_ONION_PORT),
+        };
+
+        let host = host.to_ascii_lowercase();
+        let id = host
+            .strip_suffix(".onion")
+            .ok_or_else(|| invalid("missing .onion suffix"))?;
+        if id.len() != ONION_V3_ID_LEN {
+            return Err(invalid("not a v3 onion service ID"));
+        }
+
+        let decoded = data_encoding::BASE32_NOPAD
+            .decode(id.to_ascii_uppercase().as_bytes())
+            .map_err(|_| invalid("not base32"))?;
+        let (public_key, rest) = decoded.split_at(32);
+        let (checksum, version) = rest.split_at(2);
+
+        if version != [ONION_V3_VERSION] {
+            return Err(invalid("unsupported version"));
+        }
+        if checksum != onion_checksum(public_key) {
+            return Err(invalid("checksum mismatch"));
+        }
+
+        Ok((host, port))
+    }
+
+    /// Get the outbound stream for a peer, dialling it if not yet connected
+    async fn connection_for(&self, address: &str) -> Result<Arc<Mutex<TcpStream>>> {
+        if let Some(stream) = self.connections.lock().await.get(address) {
+            return Ok(stream.clone());
+        }
+
+        let (host, port) = Self::validate_onion_address(address)?;
+        let stream = timeout(
+            Duration::from_millis(self.config.connect_timeout_ms),
+            socks5_connect(&self.config.socks_addr, &host, port),
+        )
+        .await
+        .map_err(|_| NetworkError::Timeout)??;
+
+        let stream = Arc::new(Mutex::new(stream));
+        self.connections
+            .lock()
+            .await
+            .insert(address.to_string(), stream.clone());
+        Ok(stream)
+    }
+
+    /// Bind the local listener and publish it as an ephemeral onion service
+    async fn publish_onion_service(&self, control_addr: &str) -> Result<()> {
+        let listener = TcpListener::bind(&self.config.listen_addr)
+            .await
+            .map_err(|e| {
+                NetworkError::InitializationFailed(format!("Failed to bind Tor listener: {}", e))
+            })?;
+        let local_addr = listener.local_addr().map_err(|e| {
+            NetworkError::InitializationFailed(format!("Failed to get local address: {}", e))
+        })?;
+
+        let stream = TcpStream::connect(control_addr).await.map_err(|e| {
+            NetworkError::InitializationFailed(format!("Tor control connect failed: {}", e))
+        })?;
+        let mut control = BufReader::new(stream);
+
+        let auth = match &self.config.control_password {
+            Some(password) => format!("AUTHENTICATE {}\r\n", quote_control_string(password)),
+            None => "AUTHENTICATE\r\n".to_string(),
+        };
+        control_command(&mut control, &auth).await?;
+
+        // SECURITY: DiscardPK keeps the service key out of this process; the
+        // address is ephemeral and changes on every start
+        let reply = control_command(
+            &mut control,
+            &format!(
+                "ADD_ONION NEW:ED25519-V3 Flags=DiscardPK Port={},{}\r\n",
+                self.config.virtual_port, local_addr
+            ),
+        )
+        .await?;
+        let service_id = reply
+            .iter()
+            .find_map(|line| line.strip_prefix("250-ServiceID="))
+            .ok_or_else(|| {
+                NetworkError::InitializationFailed("ADD_ONION returned no ServiceID".to_string())
+            })?;
+
+        let onion_address = format!("{}.onion:{}", service_id, self.config.virtual_port);
+        log::info!("Tor onion service published at {}", onion_address);
+
+        *self.onion_address.write().await = Some(onion_address);
+        *self.local_addr.write().await = Some(local_addr);
+        *self.control.lock().await = Some(control);
+
+        let tasks = self.tasks.clone();
+        let incoming_tx = self.incoming_tx.clone();
+        let accept_loop = tokio::spawn(async move {
+            while let Ok((stream, peer_addr)) = listener.accept().await {
+                let incoming_tx = incoming_tx.clone();
+                let reader = tokio::spawn(async move {
+                    // Tor hides the dialler; the frame's source NodeId is the
+                    // only identity an inbound stream carries
+                    let address = Address::Unknown(format!("tor-inbound:{}", peer_addr));
+                    let mut stream = stream;
+                    while let Ok(Some(data)) = read_frame(&mut stream).await {
+                        if incoming_tx.send((address.clone(), data)).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+                let mut tasks = tasks.lock().await;
+                tasks.retain(|task| !task.is_finished());
+                tasks.push(reader);
+            }
+        });
+        self.tasks.lock().await.push(accept_loop);
+
+        Ok(())
+    }
+}
+
+/// SHA3-256 based checksum over a v3 onion service public key
+fn onion_checksum(public_key: &[u8]) -> [u8; 2] {
+    let mut hasher = Sha3::v256();
+    hasher.update(b".onion checksum");
+    hasher.update(public_key);
+    hasher.update(&[ONION_V3_VERSION]);
+    let mut digest = [0u8; 32];
+    hasher.finalize(&mut digest);
+    [digest[0], digest[1]]
+}
+
+/// Quote a string for the Tor control protocol
+fn quote_control_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Send a control port command and collect its reply lines
+///
+/// Returns the lines up to and including the final `250 ` line, or an error
+/// for any other status.
+async fn control_command(control: &mut BufReader<TcpStream>, command: &str) -> Result<Vec<String>> {
+    let failed = |e: String| NetworkError::InitializationFailed(format!("Tor control: {}", e));
+
+    control
+        .get_mut()
+        .write_all(command.as_bytes())
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        if control
+            .read_line(&mut line)
+            .await
+            .map_err(|e| failed(e.to_string()))?
+            == 0
+        {
+            return Err(failed("connection closed".to_string()));
+        }
+        let line = line.trim_end().to_string();
+
+        if !line.starts_with("250") {
+            return Err(failed(line));
+        }
+        // "250-" and "250+" continue the reply; "250 " ends it
+        let done = line.as_bytes().get(3) == Some(&b' ');
+        lines.push(line);
+        if done {
+            return Ok(lines);
+        }
+    }
+}
+
+/// Open a stream to `host:port` through a SOCKS5 proxy (RFC 1928)
+///
+/// The hostname is sent to the proxy unresolved so Tor resolves it.
+async fn socks5_connect(proxy: &str, host: &str, port: u16) -> Result<TcpStream> {
+    let failed = |e: String| NetworkError::SendFailed(format!("SOCKS5 connect failed: {}", e));
+
+    let host_len = u8::try_from(host.len()).map_err(|_| failed("hostname too long".to_string()))?;
+    let mut stream = TcpStream::connect(proxy)
+        .await
+        .map_err(|e| failed(format!("proxy unreachable: {}", e)))?;
+
+    // Greeting: version 5, one method, no authentication
+    stream
+        .write_all(&[0x05, 0x01, 0x00])
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+    let mut choice = [0u8; 2];
+    stream
+        .read_exact(&mut choice)
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+    if choice != [0x05, 0x00] {
+        return Err(failed("proxy requires authentication".to_string()));
+    }
+
+    // CONNECT by domain name
+    let mut request = vec![0x05, 0x01, 0x00, 0x03, host_len];
+    request.extend_from_slice(host.as_bytes());
+    request.extend_from_slice(&port.to_be_bytes());
+    stream
+        .write_all(&request)
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+
+    let mut reply = [0u8; 4];
+    stream
+        .read_exact(&mut reply)
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+    if reply[1] != 0x00 {
+        return Err(failed(format!("proxy replied with code {:#04x}", reply[1])));
+    }
+
+    // Skip the bound address that follows the reply header
+    let address_len = match reply[3] {
+        0x01 => 4,
+        0x04 => 16,
+        0x03 => {
+            let mut len = [0u8; 1];
+            stream
+                .read_exact(&mut len)
+                .await
+                .map_err(|e| failed(e.to_string()))?;
+            len[0] as usize
+        }
+        other => return Err(failed(format!("unknown address type {:#04x}", other))),
+    };
+    let mut bound = vec![0u8; address_len + 2];
+    stream
+        .read_exact(&mut bound)
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+
+    Ok(stream)
+}
+
+/// Read one length-prefixed frame, or `None` at end of stream
+async fn read_frame<R: AsyncRead + Unpin>(stream: &mut R) -> std::io::Result<Option<Vec<u8>>> {
+    let mut len_bytes = [0u8; 4];
+    match stream.read_exact(&mut len_bytes).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    // SECURITY: Reject oversized lengths before allocating
+    let len = u32::from_be_bytes(len_bytes) as usize;
+    if len > MAX_TOR_FRAME_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("frame of {} bytes exceeds limit", len),
+        ));
+    }
+
+    let mut data = vec![0u8; len];
+    stream.read_exact(&mut data).await?;
+    Ok(Some(data))
+}
+
+#[async_trait::async_trait]
+impl NetworkAdapter for TorAdapter {
+    async fn initialize(&mut self) -> Result<()> {
+        {
+            let mut status = self.status.write().await;
+            *status = AdapterStatus::Initializing;
+        }
+
+        if let Some(control_addr) = self.config.control_addr.clone() {
+            self.publish_onion_service(&control_addr).await?;
+        }
+
+        {
+            let mut status = self.status.write().await;
+            *status = AdapterStatus::Ready;
+        }
+
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        // Circuits are established lazily on first send
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        {
+            let mut status = self.status.write().await;
+            *status = AdapterStatus::ShuttingDown;
+        }
+
+        self.connections.lock().await.clear();
+        for task in self.tasks.lock().await.drain(..) {
+            task.abort();
+        }
+
+        // Closing the control connection removes the ephemeral service
+        *self.control.lock().await = None;
+        *self.onion_address.write().await = None;
+        *self.local_addr.write().await = None;
+
+        Ok(())
+    }
+
+    async fn send(&self, destination: &Address, frame: &Frame) -> Result<()> {
+        let address = match destination {
+            Address::Onion(address) => address,
+            _ => {
+                return Err(NetworkError::InvalidAddress(
+                    "Tor adapter requires onion address".to_string(),
+                ))
+            }
+        };
+
+        // Serialize frame
+        let frame_data = bincode::serialize(frame)
+            .map_err(|e| NetworkError::SendFailed(format!("Failed to serialize frame: {}", e)))?;
+
+        if frame_data.len() > MAX_TOR_FRAME_SIZE {
+            return Err(NetworkError::MessageTooLarge {
+                size: frame_data.len(),
+                max: MAX_TOR_FRAME_SIZE,
+            });
+        }
+
+        let connection = self.connection_for(address).await?;
+        let mut stream = connection.lock().await;
+        let result = async {
+            stream
+                .write_all(&(frame_data.len() as u32).to_be_bytes())
+                .await?;
+            stream.write_all(&frame_data).await?;
+            stream.flush().await
+        }
+        .await;
+
+        if let Err(e) = result {
+            // Circuit is gone; dial again on the next send
+            self.connections.lock().await.remove(address);
+            return Err(NetworkError::SendFailed(format!(
+                "Write to {} failed: {}",
+                address, e
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn receive(&self, timeout_ms: u64) -> Result<(Address, Frame)> {
+        let mut incoming = self.incoming_rx.lock().await;
+
+        let (source_address, frame_data) =
+            timeout(Duration::from_millis(timeout_ms), incoming.recv())
+                .await
+                .map_err(|_| NetworkError::ReceiveFailed("Receive timeout".to_string()))?
+                .ok_or_else(|| NetworkError::ReceiveFailed("Adapter stopped".to_string()))?;
+
+        // Deserialize frame
+        let frame: Frame = bincode::deserialize(&frame_data).map_err(|e| {
+            NetworkError::ReceiveFailed(format!("Failed to deserialize frame: {}", e))
+        })?;
+
+        Ok((source_address, frame))
+    }
+
+    async fn discover_peers(&self) -> Result<Vec<PeerInfo>> {
+        // Onion services are not discoverable; peers come from the DHT
+        Ok(Vec::new())
+    }
+
+    fn get_status(&self) -> AdapterStatus {
+        self.status
+            .try_read()
+            .map(|s| *s)
+            .unwrap_or(AdapterStatus::Error)
+    }
+
+    fn get_capabilities(&self) -> &AdapterCapabilities {
+        &self.capabilities
+    }
+
+    async fn test_connection(&self, destination: &Address) -> Result<TestResults> {
+        let address = match destination {
+            Address::Onion(address) => address,
+            _ => {
+                return Ok(TestResults {
+                    success: false,
+                    rtt_ms: None,
+                    error: Some("Not an onion address".to_string()),
+                })
+            }
+        };
+
+        // Building the circuit is the meaningful part of the round trip
+        let start = std::time::Instant::now();
+        match self.connection_for(address).await {
+            Ok(_) => Ok(TestResults {
+                success: true,
+                rtt_ms: Some(start.elapsed().as_secs_f64() * 1000.0),
+                error: None,
+            }),
+            Err(e) => Ok(TestResults {
+                success: false,
+                rtt_ms: None,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    fn get_local_address(&self) -> Option<Address> {
+        self.onion_address
+            .try_read()
+            .ok()?
+            .as_ref()
+            .map(|address| Address::Onion(address.clone()))
+    }
+
+    fn parse_address(&self, addr_str: &str) -> Result<Address> {
+        let (host, port) = Self::validate_onion_address(addr_str)?;
+        Ok(Address::Onion(format!("{}:{}", host, port)))
+    }
+
+    fn supports_address(&self, address: &Address) -> bool {
+        matches!(address, Address::Onion(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myriadmesh_protocol::types::NODE_ID_SIZE;
+    use myriadmesh_protocol::{MessageId, MessageType};
+
+    /// Build the v3 onion ID for a public key
+    fn onion_id(public_key: [u8; 32]) -> String {
+        let mut bytes = public_key.to_vec();
+        bytes.extend_from_slice(&onion_checksum(&public_key));
+        bytes.push(ONION_V3_VERSION);
+        data_encoding::BASE32_NOPAD
+            .encode(&bytes)
+            .to_ascii_lowercase()
+    }
+
+    fn create_adapter(config: TorConfig) -> TorAdapter {
+        TorAdapter::new(NodeId::from_bytes([1u8; NODE_ID_SIZE]), config)
+    }
+
+    fn test_frame() -> Frame {
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let payload = b"through tor".to_vec();
+        let msg_id = MessageId::generate(&source, &dest, &payload, 0, 0);
+        Frame::new(MessageType::Data, source, dest, payload, msg_id, 0).unwrap()
+    }
+
+    #[test]
+    fn test_tor_adapter_creation() {
+        let adapter = create_adapter(TorConfig::default());
+
+        assert_eq!(adapter.get_status(), AdapterStatus::Uninitialized);
+        let caps = adapter.get_capabilities();
+        assert_eq!(caps.adapter_type, AdapterType::Tor);
+        assert_eq!(caps.max_message_size, MAX_TOR_FRAME_SIZE);
+        assert!(!caps.supports_broadcast());
+        assert!(adapter.get_local_address().is_none());
+    }
+
+    #[test]
+    fn test_validate_onion_address() {
+        let id = onion_id([7u8; 32]);
+
+        assert_eq!(
+            TorAdapter::validate_onion_address(&format!("{}.onion:9000", id)).unwrap(),
+            (format!("{}.onion", id), 9000)
+        );
+        assert_eq!(
+            TorAdapter::validate_onion_address(&format!("{}.onion", id.to_uppercase())).unwrap(),
+            (format!("{}.onion", id), DEFAULT_ONION_PORT)
+        );
+
+        // A real v3 address validates too
+        assert!(TorAdapter::validate_onion_address(
+            "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_invalid_onion_addresses_rejected() {
+        let id = onion_id([7u8; 32]);
+
+        // Single-character typo breaks the checksum
+        let typo = id.replacen(&id[..1], if &id[..1] == "a" { "b" } else { "a" }, 1);
+        let invalid = [
+            format!("{}.onion:0", id),
+            format!("{}.onion:port", id),
+            format!("{}.com", id),
+            format!("{}.onion", typo),
+            "expyuzz4wqqyqhjn.onion".to_string(), // v2 address
+            format!("{}.onion", "1".repeat(ONION_V3_ID_LEN)),
+            "example.i2p".to_string(),
+        ];
+        for address in &invalid {
+            assert!(
+                matches!(
+                    TorAdapter::validate_onion_address(address),
+                    Err(NetworkError::InvalidAddress(_))
+                ),
+                "{} should be rejected",
+                address
+            );
+        }
+
+        // Wrong version byte with a matching-length ID
+        let mut bytes = [7u8; 32].to_vec();
+        bytes.extend_from_slice(&onion_checksum(&[7u8; 32]));
+        bytes.push(2);
+        let v2 = data_encoding::BASE32_NOPAD
+            .encode(&bytes)
+            .to_ascii_lowercase();
+        assert!(TorAdapter::validate_onion_address(&format!("{}.onion", v2)).is_err());
+    }
+
+    #[test]
+    fn test_parse_and_supports_address() {
+        let adapter = create_adapter(TorConfig::default());
+        let id = onion_id([9u8; 32]);
+
+        assert_eq!(
+            adapter.parse_address(&format!("{}.onion", id)).unwrap(),
+            Address::Onion(format!("{}.onion:{}", id, DEFAULT_ONION_PORT))
+        );
+        assert!(adapter.parse_address("example.onion").is_err());
+
+        assert!(adapter.supports_address(&Address::Onion(format!("{}.onion", id))));
+        assert!(!adapter.supports_address(&Address::I2P("test.i2p".to_string())));
+        assert_eq!(Address::Onion(id).adapter_type(), AdapterType::Tor);
+    }
+
+    /// Mock SOCKS5 proxy that accepts one CONNECT and forwards the first
+    /// frame, together with the requested host and port
+    async fn mock_socks_proxy(reply_code: u8) -> (String, mpsc::Receiver<(String, u16, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let (tx, rx) = mpsc::channel(1);
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).await.unwrap();
+            assert_eq!(greeting, [0x05, 0x01, 0x00]);
+            stream.write_all(&[0x05, 0x00]).await.unwrap();
+
+            let mut header = [0u8; 5];
+            stream.read_exact(&mut header).await.unwrap();
+            assert_eq!(header[..4], [0x05, 0x01, 0x00, 0x03]);
+            let mut host = vec![0u8; header[4] as usize];
+            stream.read_exact(&mut host).await.unwrap();
+            let mut port = [0u8; 2];
+            stream.read_exact(&mut port).await.unwrap();
+
+            stream
+                .write_all(&[0x05, reply_code, 0x00, 0x01, 0, 0, 0, 0, 0, 0])
+                .await
+                .unwrap();
+            if reply_code != 0x00 {
+                return;
+            }
+
+            let frame = read_frame(&mut stream).await.unwrap().unwrap();
+            let _ = tx
+                .send((
+                    String::from_utf8(host).unwrap(),
+                    u16::from_be_bytes(port),
+                    frame,
+                ))
+                .await;
+        });
+
+        (addr, rx)
+    }
+
+    #[tokio::test]
+    async fn test_send_dials_through_socks_proxy() {
+        let (socks_addr, mut received) = mock_socks_proxy(0x00).await;
+        let mut adapter = create_adapter(TorConfig {
+            socks_addr,
+            ..Default::default()
+        });
+        adapter.initialize().await.unwrap();
+        assert_eq!(adapter.get_status(), AdapterStatus::Ready);
+
+        let host = format!("{}.onion", onion_id([3u8; 32]));
+        let frame = test_frame();
+        adapter
+            .send(&Address::Onion(format!("{}:4001", host)), &frame)
+            .await
+            .unwrap();
+
+        let (requested_host, requested_port, data) =
+            timeout(Duration::from_secs(2), received.recv())
+                .await
+                .unwrap()
+                .unwrap();
+        // The onion hostname reaches the proxy unresolved
+        assert_eq!(requested_host, host);
+        assert_eq!(requested_port, 4001);
+        let delivered: Frame = bincode::deserialize(&data).unwrap();
+        assert_eq!(delivered.payload, frame.payload);
+    }
+
+    #[tokio::test]
+    async fn test_socks_failure_reported() {
+        // 0x04: host unreachable
+        let (socks_addr, _received) = mock_socks_proxy(0x04).await;
+        let adapter = create_adapter(TorConfig {
+            socks_addr,
+            ..Default::default()
+        });
+
+        let destination = Address::Onion(format!("{}.onion:4001", onion_id([3u8; 32])));
+        let result = adapter.send(&destination, &test_frame()).await;
+        assert!(matches!(result, Err(NetworkError::SendFailed(_))));
+
+        // Invalid addresses never reach the proxy
+        let result = adapter
+            .send(&Address::Onion("bogus.onion".to_string()), &test_frame())
+            .await;
+        assert!(matches!(result, Err(NetworkError::InvalidAddress(_))));
+    }
+
+    #[tokio::test]
+    async fn test_onion_service_published_and_receives() {
+        let service_id = onion_id([5u8; 32]);
+        let control = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let control_addr = control.local_addr().unwrap().to_string();
+
+        let reply_id = service_id.clone();
+        tokio::spawn(async move {
+            let (stream, _) = control.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            let mut line = String::new();
+            while stream.read_line(&mut line).await.unwrap() > 0 {
+                let reply = if line.starts_with("AUTHENTICATE \"secret\"") {
+                    "250 OK\r\n".to_string()
+                } else if line.starts_with("ADD_ONION NEW:ED25519-V3") {
+                    format!("250-ServiceID={}\r\n250 OK\r\n", reply_id)
+                } else {
+                    "510 Unrecognized command\r\n".to_string()
+                };
+                stream.get_mut().write_all(reply.as_bytes()).await.unwrap();
+                line.clear();
+            }
+        });
+
+        let mut adapter = create_adapter(TorConfig {
+            control_addr: Some(control_addr),
+            control_password: Some("secret".to_string()),
+            ..Default::default()
+        });
+        adapter.initialize().await.unwrap();
+
+        let expected = format!("{}.onion:{}", service_id, DEFAULT_ONION_PORT);
+        assert_eq!(adapter.onion_address().await, Some(expected.clone()));
+        assert_eq!(adapter.get_local_address(), Some(Address::Onion(expected)));
+
+        // Tor forwards inbound streams to the local listener
+        let local = adapter.local_addr.read().await.unwrap();
+        let mut stream = TcpStream::connect(local).await.unwrap();
+        let data = bincode::serialize(&test_frame()).unwrap();
+        stream
+            .write_all(&(data.len() as u32).to_be_bytes())
+            .await
+            .unwrap();
+        stream.write_all(&data).await.unwrap();
+
+        let (_, frame) = adapter.receive(2000).await.unwrap();
+        assert_eq!(frame.payload, test_frame().payload);
+
+        adapter.stop().await.unwrap();
+        assert!(adapter.onion_address().await.is_none());
+    }
+}
@@ -0,0 +1,562 @@
+//! WebSocket network adapter
+//!
+//! Carries frames as binary WebSocket messages, for deployments that can
+//! only reach the outside world through HTTP(S) ports:
+//! - Client mode: dials `ws://` or `wss://` URLs on demand
+//! - Listen mode: accepts inbound WebSocket connections
+//! - Frames use the same bincode serialization as the Ethernet adapter
+
+use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
+use crate::error::{NetworkError, Result};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
+use futures::{SinkExt, StreamExt};
+use myriadmesh_protocol::types::AdapterType;
+use myriadmesh_protocol::{Frame, Message, MessageType, NodeId};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_tungstenite::tungstenite::http::Uri;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig as WsProtocolConfig;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::WebSocketStream;
+
+/// Maximum serialized frame size carried in one WebSocket message
+pub const MAX_WS_MESSAGE_SIZE: usize = 128 * 1024;
+
+/// Received frames queued for `receive` across all connections
+const INCOMING_QUEUE_SIZE: usize = 1000;
+
+/// Frames queued for sending on one connection
+const OUTGOING_QUEUE_SIZE: usize = 256;
+
+/// Protocol limits for every connection, dialled or accepted
+///
+/// SECURITY: Caps WebSocket messages and frames at `MAX_WS_MESSAGE_SIZE`
+/// so a peer can't make us buffer tungstenite's 64 MiB default per message.
+fn ws_protocol_config() -> WsProtocolConfig {
+    WsProtocolConfig {
+        max_message_size: Some(MAX_WS_MESSAGE_SIZE),
+        max_frame_size: Some(MAX_WS_MESSAGE_SIZE),
+        ..Default::default()
+    }
+}
+
+/// WebSocket adapter configuration
+#[derive(Debug, Clone)]
+pub struct WebSocketConfig {
+    /// Address to accept inbound connections on (None for client-only mode)
+    pub listen_addr: Option<String>,
+
+    /// Timeout for establishing outbound connections (milliseconds)
+    pub connect_timeout_ms: u64,
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        WebSocketConfig {
+            listen_addr: None,
+            connect_timeout_ms: 10_000,
+        }
+    }
+}
+
+/// Outbound message queues for open connections, keyed by peer URL
+type ConnectionMap = HashMap<String, mpsc::Sender<WsMessage>>;
+
+/// Serialized frame received from a peer
+type IncomingFrame = (Address, Vec<u8>);
+
+/// WebSocket network adapter
+pub struct WebSocketAdapter {
+    /// Adapter status
+    status: Arc<RwLock<AdapterStatus>>,
+
+    /// Configuration
+    config: WebSocketConfig,
+
+    /// Local NodeId
+    local_node_id: NodeId,
+
+    /// Open connections (both dialled and accepted)
+    connections: Arc<RwLock<ConnectionMap>>,
+
+    /// Sender half of the incoming frame queue, shared by connection tasks
+    incoming_tx: mpsc::Sender<IncomingFrame>,
+
+    /// Receiver half of the incoming frame queue
+    incoming_rx: Arc<Mutex<mpsc::Receiver<IncomingFrame>>>,
+
+    /// Bound listen address
+    local_addr: Arc<RwLock<Option<SocketAddr>>>,
+
+    /// Peers we have received frames from
+    peers: Arc<RwLock<Vec<PeerInfo>>>,
+
+    /// Background accept and connection tasks
+    tasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+
+    /// Adapter capabilities
+    capabilities: AdapterCapabilities,
+}
+
+impl WebSocketAdapter {
+    /// Create new WebSocket adapter
+    pub fn new(local_node_id: NodeId, config: WebSocketConfig) -> Self {
+        let capabilities = AdapterCapabilities {
+            adapter_type: AdapterType::WebSocket,
+            max_message_size: MAX_WS_MESSAGE_SIZE,
+            typical_latency_ms: 50.0,
+            typical_bandwidth_bps: 10_000_000, // 10 Mbps
+            reliability: 0.98,
+            range_meters: 0.0, // Global (Internet)
+            power_consumption: PowerConsumption::Medium,
+            cost_per_mb: 0.0,
+            features: FeatureFlags::from_features(&[
+                Feature::ReliableDelivery,
+                Feature::OrderedDelivery,
+            ]),
+        };
+
+        let (incoming_tx, incoming_rx) = mpsc::channel(INCOMING_QUEUE_SIZE);
+
+        WebSocketAdapter {
+            status: Arc::new(RwLock::new(AdapterStatus::Uninitialized)),
+            config,
+            local_node_id,
+            connections: Arc::new(RwLock::new(HashMap::new())),
+            incoming_tx,
+            incoming_rx: Arc::new(Mutex::new(incoming_rx)),
+            local_addr: Arc::new(RwLock::new(None)),
+            peers: Arc::new(RwLock::new(Vec::new())),
+            tasks: Arc::new(Mutex::new(Vec::new())),
+            capabilities,
+        }
+    }
+
+    /// Create with default configuration (client-only)
+    pub fn new_default(local_node_id: NodeId) -> Self {
+        Self::new(local_node_id, WebSocketConfig::default())
+    }
+
+    /// Get bound listen address
+    pub fn local_address(&self) -> Option<SocketAddr> {
+        *self.local_addr.try_read().ok()?
+    }
+
+    /// Get number of open connections
+    pub async fn connection_count(&self) -> usize {
+        self.connections.read().await.len()
+    }
+
+    /// Validate a `ws://` or `wss://` URL
+    fn validate_url(url: &str) -> Result<()> {
+        let uri: Uri = url
+            .parse()
+            .map_err(|e| NetworkError::InvalidAddress(format!("Invalid WebSocket URL: {}", e)))?;
+
+        match uri.scheme_str() {
+            Some("ws") | Some("wss") => {}
+            _ => {
+                return Err(NetworkError::InvalidAddress(format!(
+                    "WebSocket URL must use ws:// or wss://: {}",
+                    url
+                )))
+            }
+        }
+
+        if uri.host().is_none_or(str::is_empty) {
+            return Err(NetworkError::InvalidAddress(format!(
+                "WebSocket URL has no host: {}",
+                url
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Spawn reader and writer tasks for an open WebSocket
+    ///
+    /// Inbound binary messages are queued for `receive`, and the reader
+    /// stops reading while that queue is full; the connection is forgotten
+    /// once either side closes it.
+    async fn spawn_connection<S>(
+        connections: Arc<RwLock<ConnectionMap>>,
+        tasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+        incoming_tx: mpsc::Sender<IncomingFrame>,
+        peer_url: String,
+        stream: WebSocketStream<S>,
+    ) -> mpsc::Sender<WsMessage>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (mut sink, mut source) = stream.split();
+        let (outgoing_tx, mut outgoing_rx) = mpsc::channel::<WsMessage>(OUTGOING_QUEUE_SIZE);
+
+        connections
+            .write()
+            .await
+            .insert(peer_url.clone(), outgoing_tx.clone());
+
+        let writer = tokio::spawn(async move {
+            while let Some(message) = outgoing_rx.recv().await {
+                if sink.send(message).await.is_err() {
+                    break;
+                }
+            }
+            let _ = sink.close().await;
+        });
+
+        let reader_connections = connections.clone();
+        let reader = tokio::spawn(async move {
+            let address = Address::WebSocket(peer_url.clone());
+            while let Some(Ok(message)) = source.next().await {
+                match message {
+                    WsMessage::Binary(data) => {
+                        if incoming_tx.send((address.clone(), data)).await.is_err() {
+                            break;
+                        }
+                    }
+                    WsMessage::Close(_) => break,
+                    // Ping/pong are answered by tungstenite; text is not a frame
+                    _ => continue,
+                }
+            }
+            reader_connections.write().await.remove(&peer_url);
+        });
+
+        let mut tasks = tasks.lock().await;
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(writer);
+        tasks.push(reader);
+
+        outgoing_tx
+    }
+
+    /// Get the outbound queue for a peer, dialling it if not yet connected
+    async fn connection_for(&self, url: &str) -> Result<mpsc::Sender<WsMessage>> {
+        if let Some(sender) = self.connections.read().await.get(url) {
+            if !sender.is_closed() {
+                return Ok(sender.clone());
+            }
+        }
+
+        Self::validate_url(url)?;
+
+        let (stream, _response) = timeout(
+            Duration::from_millis(self.config.connect_timeout_ms),
+            tokio_tungstenite::connect_async_with_config(url, Some(ws_protocol_config()), true),
+        )
+        .await
+        .map_err(|_| NetworkError::Timeout)?
+        .map_err(|e| NetworkError::SendFailed(format!("WebSocket connect failed: {}", e)))?;
+
+        Ok(Self::spawn_connection(
+            self.connections.clone(),
+            self.tasks.clone(),
+            self.incoming_tx.clone(),
+            url.to_string(),
+            stream,
+        )
+        .await)
+    }
+}
+
+#[async_trait::async_trait]
+impl NetworkAdapter for WebSocketAdapter {
+    async fn initialize(&mut self) -> Result<()> {
+        {
+            let mut status = self.status.write().await;
+            *status = AdapterStatus::Initializing;
+        }
+
+        if let Some(listen_addr) = &self.config.listen_addr {
+            let listener = TcpListener::bind(listen_addr).await.map_err(|e| {
+                NetworkError::InitializationFailed(format!(
+                    "Failed to bind WebSocket listener: {}",
+                    e
+                ))
+            })?;
+
+            let local_addr = listener.local_addr().map_err(|e| {
+                NetworkError::InitializationFailed(format!("Failed to get local address: {}", e))
+            })?;
+            *self.local_addr.write().await = Some(local_addr);
+
+            let connections = self.connections.clone();
+            let tasks = self.tasks.clone();
+            let incoming_tx = self.incoming_tx.clone();
+
+            let accept_loop = tokio::spawn(async move {
+                while let Ok((tcp, peer_addr)) = listener.accept().await {
+                    let connections = connections.clone();
+                    let tasks = tasks.clone();
+                    let incoming_tx = incoming_tx.clone();
+
+                    // Handshake off the accept loop so a slow client can't stall it
+                    tokio::spawn(async move {
+                        if let Ok(stream) = tokio_tungstenite::accept_async_with_config(
+                            tcp,
+                            Some(ws_protocol_config()),
+                        )
+                        .await
+                        {
+                            let peer_url = format!("ws://{}", peer_addr);
+                            Self::spawn_connection(
+                                connections,
+                                tasks,
+                                incoming_tx,
+                                peer_url,
+                                stream,
+                            )
+                            .await;
+                        }
+                    });
+                }
+            });
+            self.tasks.lock().await.push(accept_loop);
+        }
+
+        {
+            let mut status = self.status.write().await;
+            *status = AdapterStatus::Ready;
+        }
+
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        // Connections are established lazily on first send
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        {
+            let mut status = self.status.write().await;
+            *status = AdapterStatus::ShuttingDown;
+        }
+
+        // Dropping the outbound queues closes each connection
+        self.connections.write().await.clear();
+        for task in self.tasks.lock().await.drain(..) {
+            task.abort();
+        }
+        *self.local_addr.write().await = None;
+
+        Ok(())
+    }
+
+    async fn send(&self, destination: &Address, frame: &Frame) -> Result<()> {
+        let url = match destination {
+            Address::WebSocket(url) => url,
+            _ => {
+                return Err(NetworkError::InvalidAddress(
+                    "WebSocket adapter requires WebSocket address".to_string(),
+                ))
+            }
+        };
+
+        // Serialize frame
+        let frame_data = bincode::serialize(frame)
+            .map_err(|e| NetworkError::SendFailed(format!("Failed to serialize frame: {}", e)))?;
+
+        if frame_data.len() > MAX_WS_MESSAGE_SIZE {
+            return Err(NetworkError::MessageTooLarge {
+                size: frame_data.len(),
+                max: MAX_WS_MESSAGE_SIZE,
+            });
+        }
+
+        let connection = self.connection_for(url).await?;
+        connection
+            .try_send(WsMessage::Binary(frame_data))
+            .map_err(|e| match e {
+                mpsc::error::TrySendError::Full(_) => {
+                    NetworkError::SendFailed(format!("WebSocket to {} is backed up", url))
+                }
+                mpsc::error::TrySendError::Closed(_) => {
+                    NetworkError::SendFailed(format!("WebSocket to {} closed", url))
+                }
+            })?;
+
+        Ok(())
+    }
+
+    async fn receive(&self, timeout_ms: u64) -> Result<(Address, Frame)> {
+        let mut incoming = self.incoming_rx.lock().await;
+
+        let (source_address, frame_data) =
+            timeout(Duration::from_millis(timeout_ms), incoming.recv())
+                .await
+                .map_err(|_| NetworkError::ReceiveFailed("Receive timeout".to_string()))?
+                .ok_or_else(|| NetworkError::ReceiveFailed("Adapter stopped".to_string()))?;
+
+        // Deserialize frame
+        let frame: Frame = bincode::deserialize(&frame_data).map_err(|e| {
+            NetworkError::ReceiveFailed(format!("Failed to deserialize frame: {}", e))
+        })?;
+
+        // Remember who is reachable over this connection
+        {
+            let mut peers = self.peers.write().await;
+            let node_id = frame.header.source;
+            if !peers.iter().any(|p| p.node_id == node_id) {
+                peers.push(PeerInfo {
+                    node_id,
+                    address: source_address.clone(),
+                });
+            }
+        }
+
+        Ok((source_address, frame))
+    }
+
+    async fn discover_peers(&self) -> Result<Vec<PeerInfo>> {
+        // No broadcast medium; report peers seen on open connections
+        Ok(self.peers.read().await.clone())
+    }
+
+    fn get_status(&self) -> AdapterStatus {
+        self.status
+            .try_read()
+            .map(|s| *s)
+            .unwrap_or(AdapterStatus::Error)
+    }
+
+    fn get_capabilities(&self) -> &AdapterCapabilities {
+        &self.capabilities
+    }
+
+    async fn test_connection(&self, destination: &Address) -> Result<TestResults> {
+        // Simple ping test
+        let start = std::time::Instant::now();
+
+        // Create a test message
+        let test_message = Message::new(
+            self.local_node_id,
+            self.local_node_id,
+            MessageType::Data,
+            vec![0u8; 32],
+        )
+        .map_err(|e| NetworkError::SendFailed(format!("Failed to create test message: {}", e)))?;
+
+        // Create frame from message
+        let test_frame = Frame::from_message(&test_message)
+            .map_err(|e| NetworkError::SendFailed(format!("Failed to create test frame: {}", e)))?;
+
+        match self.send(destination, &test_frame).await {
+            Ok(_) => {
+                let rtt = start.elapsed().as_secs_f64() * 1000.0;
+                Ok(TestResults {
+                    success: true,
+                    rtt_ms: Some(rtt),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(TestResults {
+                success: false,
+                rtt_ms: None,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    fn get_local_address(&self) -> Option<Address> {
+        self.local_addr
+            .try_read()
+            .ok()?
+            .as_ref()
+            .map(|addr| Address::WebSocket(format!("ws://{}", addr)))
+    }
+
+    fn parse_address(&self, addr_str: &str) -> Result<Address> {
+        Self::validate_url(addr_str)?;
+        Ok(Address::WebSocket(addr_str.to_string()))
+    }
+
+    fn supports_address(&self, address: &Address) -> bool {
+        matches!(address, Address::WebSocket(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myriadmesh_protocol::types::NODE_ID_SIZE;
+
+    fn create_adapter() -> WebSocketAdapter {
+        WebSocketAdapter::new_default(NodeId::from_bytes([1u8; NODE_ID_SIZE]))
+    }
+
+    #[test]
+    fn test_websocket_config_default() {
+        let config = WebSocketConfig::default();
+        assert!(config.listen_addr.is_none());
+        assert_eq!(config.connect_timeout_ms, 10_000);
+    }
+
+    #[test]
+    fn test_websocket_adapter_creation() {
+        let adapter = create_adapter();
+
+        assert_eq!(adapter.get_status(), AdapterStatus::Uninitialized);
+        let caps = adapter.get_capabilities();
+        assert_eq!(caps.adapter_type, AdapterType::WebSocket);
+        assert_eq!(caps.max_message_size, MAX_WS_MESSAGE_SIZE);
+        assert_eq!(caps.range_meters, 0.0);
+    }
+
+    #[test]
+    fn test_parse_address() {
+        let adapter = create_adapter();
+
+        assert_eq!(
+            adapter.parse_address("ws://127.0.0.1:8080").unwrap(),
+            Address::WebSocket("ws://127.0.0.1:8080".to_string())
+        );
+        assert_eq!(
+            adapter
+                .parse_address("wss://relay.example.org/mesh")
+                .unwrap(),
+            Address::WebSocket("wss://relay.example.org/mesh".to_string())
+        );
+
+        assert!(adapter.parse_address("http://relay.example.org").is_err());
+        assert!(adapter.parse_address("relay.example.org:443").is_err());
+        assert!(adapter.parse_address("ws://").is_err());
+        assert!(adapter.parse_address("not a url").is_err());
+    }
+
+    #[test]
+    fn test_supports_address() {
+        let adapter = create_adapter();
+
+        assert!(adapter.supports_address(&Address::WebSocket("wss://example.org".to_string())));
+        assert!(!adapter.supports_address(&Address::Ethernet("192.168.1.1:4001".to_string())));
+        assert!(!adapter.supports_address(&Address::I2P("test.i2p".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_send_requires_websocket_address() {
+        let adapter = create_adapter();
+        let frame = Frame::new(
+            MessageType::Data,
+            NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+            NodeId::from_bytes([2u8; NODE_ID_SIZE]),
+            vec![1, 2, 3],
+            myriadmesh_protocol::message::MessageId::from_bytes([0u8; 16]),
+            0,
+        )
+        .unwrap();
+
+        let result = adapter
+            .send(&Address::Ethernet("127.0.0.1:4001".to_string()), &frame)
+            .await;
+        assert!(matches!(result, Err(NetworkError::InvalidAddress(_))));
+    }
+}
@@ -7,6 +7,7 @@
 //!
 //! - 802.11ah protocol (HaLoW = High efficiency, Long range, Low power)
 //! - TWT (Target Wake Time) for 80%+ power reduction
+//! - RAW (Restricted Access Window) slot scheduling for sends
 //! - Sub-1 GHz operation (900 MHz in US)
 //! - Range: 1-10 km (vs 100m for traditional WiFi)
 //! - Supports thousands of connected devices
@@ -14,7 +15,7 @@
 
 use crate::adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults};
 use crate::error::{NetworkError, Result};
-use crate::types::{AdapterCapabilities, Address, PowerConsumption};
+use crate::types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
 use myriadmesh_protocol::{types::AdapterType, Frame, NodeId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -44,6 +45,96 @@ pub struct WifiHalowConfig {
     pub mac_address: String,
     /// Operating bandwidth (1, 2, 4, 8, or 16 MHz)
     pub bandwidth_mhz: u8,
+    /// RAW slot assignment from the AP; sends wait for the assigned slot
+    #[serde(default)]
+    pub raw_schedule: Option<RawSchedule>,
+}
+
+/// RAW (Restricted Access Window) slot assignment
+///
+/// The AP opens a restricted window `raw_start_offset_ms` after each beacon
+/// and divides it into `slot_count` equal slots. A station may only contend
+/// for the medium during its assigned slot and can sleep the rest of the time.
+/// Beacons go out whenever the AP's TSF timer crosses a multiple of the
+/// beacon interval, so slot timing follows the TSF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawSchedule {
+    /// Beacon interval in milliseconds; the schedule repeats every beacon
+    pub beacon_interval_ms: u32,
+    /// Start of the RAW relative to the beacon
+    pub raw_start_offset_ms: u32,
+    /// Number of slots in the RAW
+    pub slot_count: u16,
+    /// Duration of each slot in milliseconds
+    pub slot_duration_ms: u32,
+    /// Slot assigned to this station (0-based)
+    pub assigned_slot: u16,
+}
+
+impl Default for RawSchedule {
+    fn default() -> Self {
+        Self {
+            beacon_interval_ms: 100,
+            raw_start_offset_ms: 0,
+            slot_count: 8,
+            slot_duration_ms: 10,
+            assigned_slot: 0,
+        }
+    }
+}
+
+impl RawSchedule {
+    /// Validate the slot layout
+    pub fn validate(&self) -> Result<()> {
+        if self.slot_count == 0 || self.slot_duration_ms == 0 {
+            return Err(NetworkError::Other(
+                "RAW must have at least one non-empty slot".to_string(),
+            ));
+        }
+
+        if self.assigned_slot >= self.slot_count {
+            return Err(NetworkError::Other(format!(
+                "Assigned RAW slot {} out of range (slot count {})",
+                self.assigned_slot, self.slot_count
+            )));
+        }
+
+        let raw_end =
+            self.raw_start_offset_ms as u64 + self.slot_count as u64 * self.slot_duration_ms as u64;
+        if raw_end > self.beacon_interval_ms as u64 {
+            return Err(NetworkError::Other(
+                "RAW does not fit within the beacon interval".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Offset of the assigned slot from the start of the beacon interval
+    fn slot_start_ms(&self) -> u64 {
+        self.raw_start_offset_ms as u64 + self.assigned_slot as u64 * self.slot_duration_ms as u64
+    }
+
+    /// Time until the assigned slot is open, given the AP's TSF timer (or
+    /// the time since any beacon)
+    ///
+    /// Returns zero when already inside the slot.
+    pub fn delay_until_slot(&self, elapsed: Duration) -> Duration {
+        let interval = self.beacon_interval_ms.max(1) as u64;
+        let position = (elapsed.as_millis() % interval as u128) as u64;
+        let start = self.slot_start_ms();
+        let end = start + self.slot_duration_ms as u64;
+
+        let wait_ms = if position < start {
+            start - position
+        } else if position < end {
+            0
+        } else {
+            interval - position + start
+        };
+
+        Duration::from_millis(wait_ms)
+    }
 }
 
 impl Default for WifiHalowConfig {
@@ -57,6 +148,7 @@ impl Default for WifiHalowConfig {
             interface: "wlan0".to_string(),
             mac_address: "00:11:22:33:44:55".to_string(),
             bandwidth_mhz: 2, // 2 MHz channel (compromise: range vs speed)
+            raw_schedule: None,
         }
     }
 }
@@ -83,6 +175,10 @@ impl WifiHalowConfig {
             ));
         }
 
+        if let Some(schedule) = &self.raw_schedule {
+            schedule.validate()?;
+        }
+
         Ok(())
     }
 
@@ -121,7 +217,8 @@ struct WifiHalowState {
     rssi_dbm: Option<i16>,
     link_rate_mbps: f32,
     power_save_active: bool,
-    last_beacon: Option<Instant>,
+    /// Association time, a beacon boundary when the stack reports no TSF
+    last_beacon: Option<tokio::time::Instant>,
     associated_peers: HashMap<String, Instant>, // MAC -> last seen
 }
 
@@ -171,6 +268,8 @@ trait NetworkStack: Send + Sync {
     fn receive_frame(&mut self) -> Result<Option<Vec<u8>>>;
     fn get_rssi(&self) -> Option<i16>;
     fn scan_networks(&self) -> Result<Vec<String>>;
+    /// AP TSF timer in microseconds, as last synchronized from a beacon
+    fn tsf_us(&self) -> Option<u64>;
 }
 
 /// Mock 802.11ah network stack
@@ -179,6 +278,8 @@ struct MockNetworkStack {
     ssid: String,
     tx_buffer: Vec<Vec<u8>>,
     rx_buffer: Vec<Vec<u8>>,
+    /// When the mock AP's TSF timer read zero
+    tsf_zero: Option<tokio::time::Instant>,
 }
 
 impl MockNetworkStack {
@@ -188,6 +289,7 @@ impl MockNetworkStack {
             ssid: String::new(),
             tx_buffer: Vec::new(),
             rx_buffer: Vec::new(),
+            tsf_zero: None,
         }
     }
 }
@@ -196,6 +298,7 @@ impl NetworkStack for MockNetworkStack {
     fn connect(&mut self, ssid: &str, _password: Option<&str>) -> Result<()> {
         self.ssid = ssid.to_string();
         self.connected = true;
+        self.tsf_zero.get_or_insert_with(tokio::time::Instant::now);
         Ok(())
     }
 
@@ -231,6 +334,11 @@ impl NetworkStack for MockNetworkStack {
     fn scan_networks(&self) -> Result<Vec<String>> {
         Ok(vec!["mesh-network".to_string(), "halow-test".to_string()])
     }
+
+    fn tsf_us(&self) -> Option<u64> {
+        let zero = self.tsf_zero.filter(|_| self.connected)?;
+        Some(zero.elapsed().as_micros() as u64)
+    }
 }
 
 /// WiFi HaLoW adapter
@@ -263,8 +371,7 @@ impl WifiHalowAdapter {
                 PowerConsumption::Low
             },
             cost_per_mb: 0.0,
-            supports_broadcast: true,
-            supports_multicast: true,
+            features: FeatureFlags::from_features(&[Feature::Broadcast, Feature::Multicast]),
         };
 
         // RESOURCE M3: Bounded channel to prevent memory exhaustion
@@ -291,6 +398,64 @@ impl WifiHalowAdapter {
         }
     }
 
+    /// Position in the beacon schedule, for RAW slot timing
+    ///
+    /// Uses the AP's TSF timer; stacks that do not report one fall back to
+    /// the beacon interval counted from association.
+    async fn beacon_clock(&self) -> Duration {
+        if let Some(tsf) = self.network_stack.read().await.tsf_us() {
+            return Duration::from_micros(tsf);
+        }
+        self.state
+            .read()
+            .await
+            .last_beacon
+            .map(|beacon| beacon.elapsed())
+            .unwrap_or_default()
+    }
+
+    /// Send a frame inside this station's RAW slot
+    ///
+    /// Sleeps until the assigned slot opens (if a schedule is configured)
+    /// and returns how long the send was deferred.
+    pub async fn send_scheduled(&self, destination: &Address, frame: &Frame) -> Result<Duration> {
+        let data = bincode::serialize(frame)
+            .map_err(|e| NetworkError::Other(format!("Serialization failed: {}", e)))?;
+
+        let waited = match &self.config.raw_schedule {
+            Some(schedule) => {
+                let delay = schedule.delay_until_slot(self.beacon_clock().await);
+                if !delay.is_zero() {
+                    log::debug!("Deferring HaLoW send {}ms to RAW slot", delay.as_millis());
+                    tokio::time::sleep(delay).await;
+                }
+                delay
+            }
+            None => Duration::ZERO,
+        };
+
+        // Check TWT wake schedule
+        if let Some(ref twt) = *self.twt_session.read().await {
+            if !twt.is_awake() {
+                // Wait for next wake window
+                return Err(NetworkError::Other("TWT sleep active".to_string()));
+            }
+        }
+
+        // Extract MAC address from destination
+        let dest_mac = match destination {
+            Address::WifiHaLow(mac) => mac.as_str(),
+            _ => "FF:FF:FF:FF:FF:FF", // Broadcast
+        };
+
+        self.network_stack
+            .write()
+            .await
+            .send_frame(dest_mac, &data)?;
+
+        Ok(waited)
+    }
+
     /// Connect to HaLoW network
     async fn connect_to_network(&self) -> Result<()> {
         let mut stack = self.network_stack.write().await;
@@ -299,6 +464,7 @@ impl WifiHalowAdapter {
         let mut state = self.state.write().await;
         state.connected = true;
         state.rssi_dbm = stack.get_rssi();
+        state.last_beacon = Some(tokio::time::Instant::now());
 
         log::info!("Connected to HaLoW network: {}", self.config.ssid);
         Ok(())
@@ -411,29 +577,7 @@ impl NetworkAdapter for WifiHalowAdapter {
     }
 
     async fn send(&self, destination: &Address, frame: &Frame) -> Result<()> {
-        let data = bincode::serialize(frame)
-            .map_err(|e| NetworkError::Other(format!("Serialization failed: {}", e)))?;
-
-        // Check TWT wake schedule
-        if let Some(ref twt) = *self.twt_session.read().await {
-            if !twt.is_awake() {
-                // Wait for next wake window
-                return Err(NetworkError::Other("TWT sleep active".to_string()));
-            }
-        }
-
-        // Extract MAC address from destination
-        let dest_mac = match destination {
-            Address::WifiHaLow(mac) => mac.as_str(),
-            _ => "FF:FF:FF:FF:FF:FF", // Broadcast
-        };
-
-        self.network_stack
-            .write()
-            .await
-            .send_frame(dest_mac, &data)?;
-
-        Ok(())
+        self.send_scheduled(destination, frame).await.map(|_| ())
     }
 
     async fn receive(&self, timeout_ms: u64) -> Result<(Address, Frame)> {
@@ -664,4 +808,113 @@ mod tests {
         assert!(adapter.configure_twt().await.is_ok());
         assert!(adapter.twt_session.read().await.is_some());
     }
+
+    #[test]
+    fn test_raw_schedule_delay() {
+        // RAW starts 20ms after the beacon, slot 2 covers 40..50ms
+        let schedule = RawSchedule {
+            beacon_interval_ms: 100,
+            raw_start_offset_ms: 20,
+            slot_count: 4,
+            slot_duration_ms: 10,
+            assigned_slot: 2,
+        };
+        assert!(schedule.validate().is_ok());
+
+        let ms = Duration::from_millis;
+        assert_eq!(schedule.delay_until_slot(ms(0)), ms(40));
+        assert_eq!(schedule.delay_until_slot(ms(45)), Duration::ZERO);
+        // Just missed it: wait for the next beacon interval
+        assert_eq!(schedule.delay_until_slot(ms(50)), ms(90));
+        assert_eq!(schedule.delay_until_slot(ms(1_030)), ms(10));
+
+        let bad = RawSchedule {
+            assigned_slot: 4,
+            ..schedule
+        };
+        assert!(bad.validate().is_err());
+
+        let too_long = RawSchedule {
+            slot_count: 9,
+            ..schedule
+        };
+        assert!(too_long.validate().is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_send_deferred_to_raw_slot() {
+        use myriadmesh_protocol::{types::NODE_ID_SIZE, MessageId, MessageType};
+
+        let config = WifiHalowConfig {
+            power_save: false,
+            raw_schedule: Some(RawSchedule {
+                beacon_interval_ms: 100,
+                raw_start_offset_ms: 0,
+                slot_count: 5,
+                slot_duration_ms: 10,
+                assigned_slot: 3,
+            }),
+            ..Default::default()
+        };
+        let mut adapter = WifiHalowAdapter::new(config);
+        adapter.initialize().await.unwrap();
+
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let msg_id = MessageId::generate(&source, &dest, b"raw", 0, 0);
+        let frame =
+            Frame::new(MessageType::Data, source, dest, b"raw".to_vec(), msg_id, 0).unwrap();
+        let address = Address::WifiHaLow("00:11:22:33:44:66".to_string());
+
+        // Outside the window: deferred until slot 3 opens at 30ms
+        let started = tokio::time::Instant::now();
+        let waited = adapter.send_scheduled(&address, &frame).await.unwrap();
+        assert_eq!(waited, Duration::from_millis(30));
+        assert!(started.elapsed() >= Duration::from_millis(30));
+
+        // Inside the window: goes out immediately
+        let waited = adapter.send_scheduled(&address, &frame).await.unwrap();
+        assert_eq!(waited, Duration::ZERO);
+
+        // Past the slot: wait for the next beacon interval
+        tokio::time::advance(Duration::from_millis(15)).await;
+        let waited = adapter.send_scheduled(&address, &frame).await.unwrap();
+        assert_eq!(waited, Duration::from_millis(85));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_raw_slot_follows_ap_tsf() {
+        use myriadmesh_protocol::{types::NODE_ID_SIZE, MessageId, MessageType};
+
+        let config = WifiHalowConfig {
+            power_save: false,
+            raw_schedule: Some(RawSchedule {
+                beacon_interval_ms: 100,
+                raw_start_offset_ms: 0,
+                slot_count: 5,
+                slot_duration_ms: 10,
+                assigned_slot: 3,
+            }),
+            ..Default::default()
+        };
+        let mut adapter = WifiHalowAdapter::new(config);
+
+        // The AP has been beaconing for a while: its TSF reads 1.225s on
+        // association, 25ms into a beacon interval
+        let mut stack = MockNetworkStack::new();
+        stack.tsf_zero = Some(tokio::time::Instant::now() - Duration::from_millis(1_225));
+        *adapter.network_stack.write().await = Box::new(stack);
+        adapter.initialize().await.unwrap();
+
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let msg_id = MessageId::generate(&source, &dest, b"raw", 0, 0);
+        let frame =
+            Frame::new(MessageType::Data, source, dest, b"raw".to_vec(), msg_id, 0).unwrap();
+        let address = Address::WifiHaLow("00:11:22:33:44:66".to_string());
+
+        // Slot 3 opens 30ms after the beacon, 5ms from now
+        let waited = adapter.send_scheduled(&address, &frame).await.unwrap();
+        assert_eq!(waited, Duration::from_millis(5));
+    }
 }
@@ -56,6 +56,12 @@ pub enum NetworkError {
     #[error("Invalid callsign: {0}")]
     InvalidCallsign(String),
 
+    #[error("Replay detected (packet counter {0})")]
+    ReplayDetected(u64),
+
+    #[error("Duty cycle exceeded: {0}")]
+    DutyCycleExceeded(String),
+
     #[error("Protocol error: {0}")]
     Protocol(#[from] myriadmesh_protocol::ProtocolError),
 
@@ -71,3 +77,145 @@ pub enum NetworkError {
 
 /// Result type for network operations
 pub type Result<T> = std::result::Result<T, NetworkError>;
+
+/// Broad category of a [`NetworkError`], for deciding how to react
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkErrorKind {
+    /// Temporary condition; retrying after a backoff may succeed
+    Transient,
+    /// Misconfiguration or invalid input; retrying will not help
+    Config,
+    /// The operation cannot succeed as requested
+    Fatal,
+    /// A rate, quota or duty-cycle limit was hit; retry once it resets
+    RateLimited,
+}
+
+impl NetworkError {
+    /// Categorize this error
+    pub fn kind(&self) -> NetworkErrorKind {
+        match self {
+            NetworkError::SendFailed(_)
+            | NetworkError::ReceiveFailed(_)
+            | NetworkError::AdapterNotReady
+            | NetworkError::Timeout
+            | NetworkError::NoAdaptersAvailable
+            | NetworkError::DiscoveryFailed(_)
+            | NetworkError::HealthCheckFailed(_) => NetworkErrorKind::Transient,
+
+            NetworkError::QuotaExceeded | NetworkError::DutyCycleExceeded(_) => {
+                NetworkErrorKind::RateLimited
+            }
+
+            NetworkError::AdapterNotFound(_)
+            | NetworkError::AdapterAlreadyRegistered(_)
+            | NetworkError::InvalidAddress(_)
+            | NetworkError::LicenseRequired
+            | NetworkError::LicenseExpired(_)
+            | NetworkError::InvalidCallsign(_) => NetworkErrorKind::Config,
+
+            NetworkError::InitializationFailed(_)
+            | NetworkError::NoCommonAdapter
+            | NetworkError::MessageTooLarge { .. }
+            | NetworkError::ReplayDetected(_)
+            | NetworkError::Protocol(_)
+            | NetworkError::Crypto(_) => NetworkErrorKind::Fatal,
+
+            NetworkError::Io(e) => match e.kind() {
+                std::io::ErrorKind::PermissionDenied
+                | std::io::ErrorKind::AddrInUse
+                | std::io::ErrorKind::AddrNotAvailable
+                | std::io::ErrorKind::InvalidInput
+                | std::io::ErrorKind::NotFound => NetworkErrorKind::Config,
+                std::io::ErrorKind::InvalidData | std::io::ErrorKind::Unsupported => {
+                    NetworkErrorKind::Fatal
+                }
+                _ => NetworkErrorKind::Transient,
+            },
+
+            // Unclassified failures keep the previous retry-everything behavior
+            NetworkError::Other(_) => NetworkErrorKind::Transient,
+        }
+    }
+
+    /// Whether retrying the operation later may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind(),
+            NetworkErrorKind::Transient | NetworkErrorKind::RateLimited
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_kinds() {
+        assert_eq!(NetworkError::Timeout.kind(), NetworkErrorKind::Transient);
+        assert_eq!(
+            NetworkError::SendFailed("link down".to_string()).kind(),
+            NetworkErrorKind::Transient
+        );
+        assert_eq!(
+            NetworkError::AdapterNotReady.kind(),
+            NetworkErrorKind::Transient
+        );
+        assert_eq!(
+            NetworkError::QuotaExceeded.kind(),
+            NetworkErrorKind::RateLimited
+        );
+        assert_eq!(
+            NetworkError::DutyCycleExceeded("1%".to_string()).kind(),
+            NetworkErrorKind::RateLimited
+        );
+        assert_eq!(
+            NetworkError::InvalidAddress("nowhere".to_string()).kind(),
+            NetworkErrorKind::Config
+        );
+        assert_eq!(
+            NetworkError::LicenseRequired.kind(),
+            NetworkErrorKind::Config
+        );
+        assert_eq!(
+            NetworkError::MessageTooLarge { size: 10, max: 5 }.kind(),
+            NetworkErrorKind::Fatal
+        );
+        assert_eq!(
+            NetworkError::ReplayDetected(7).kind(),
+            NetworkErrorKind::Fatal
+        );
+    }
+
+    #[test]
+    fn test_io_error_kinds() {
+        let io = |kind| NetworkError::Io(std::io::Error::from(kind));
+
+        assert_eq!(
+            io(std::io::ErrorKind::ConnectionReset).kind(),
+            NetworkErrorKind::Transient
+        );
+        assert_eq!(
+            io(std::io::ErrorKind::TimedOut).kind(),
+            NetworkErrorKind::Transient
+        );
+        assert_eq!(
+            io(std::io::ErrorKind::PermissionDenied).kind(),
+            NetworkErrorKind::Config
+        );
+        assert_eq!(
+            io(std::io::ErrorKind::InvalidData).kind(),
+            NetworkErrorKind::Fatal
+        );
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(NetworkError::Timeout.is_retryable());
+        assert!(NetworkError::QuotaExceeded.is_retryable());
+        assert!(NetworkError::Other("unknown".to_string()).is_retryable());
+        assert!(!NetworkError::InvalidAddress("nowhere".to_string()).is_retryable());
+        assert!(!NetworkError::MessageTooLarge { size: 10, max: 5 }.is_retryable());
+    }
+}
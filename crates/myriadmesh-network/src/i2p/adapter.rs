@@ -8,7 +8,7 @@ use super::sam_client::{SamSession, SessionStyle};
 use crate::{
     adapter::{AdapterStatus, NetworkAdapter, PeerInfo, TestResults},
     error::{NetworkError, Result},
-    types::{AdapterCapabilities, Address, PowerConsumption},
+    types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption},
 };
 use async_trait::async_trait;
 use myriadmesh_protocol::Frame;
@@ -21,6 +21,9 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+/// Interval between tunnel readiness checks in [`I2pAdapter::wait_ready`]
+const TUNNEL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 /// I2P network adapter with automatic router management
 pub struct I2pAdapter {
     /// I2P router (system or embedded)
@@ -70,8 +73,12 @@ impl I2pAdapter {
             range_meters: 0.0,                // global reach
             power_consumption: PowerConsumption::Medium,
             cost_per_mb: 0.0, // free
-            supports_broadcast: false,
-            supports_multicast: false,
+            features: FeatureFlags::from_features(&[
+                Feature::ReliableDelivery,
+                Feature::OrderedDelivery,
+                Feature::Encryption,
+                Feature::Anonymity,
+            ]),
         };
 
         I2pAdapter {
@@ -147,35 +154,85 @@ impl I2pAdapter {
         Ok(dest.destination)
     }
 
-    /// Initialize SAM session
-    async fn ensure_session(&self) -> Result<()> {
-        // Check if session already exists
-        {
-            let session = self.session.read().await;
-            if session.is_some() {
-                return Ok(());
-            }
+    /// Create the SAM session in the background
+    ///
+    /// The router only confirms `SESSION CREATE` once the session's tunnels
+    /// are built, so the adapter stays in `BuildingTunnels` until then and
+    /// becomes `Ready` when the session is established.
+    async fn start_session(&self) -> Result<()> {
+        if self.session.read().await.is_some() {
+            *self.status.write().await = AdapterStatus::Ready;
+            return Ok(());
         }
 
-        // Get destination
         let destination = self.ensure_destination().await?;
+        *self.status.write().await = AdapterStatus::BuildingTunnels;
 
-        // Create SAM session
         let sam_addr = self.sam_address();
-        let session = SamSession::create(
-            &sam_addr,
-            self.session_id.clone(),
-            SessionStyle::Stream,
-            Some(destination.clone()),
-        )
-        .map_err(|e| NetworkError::InitializationFailed(format!("SAM session failed: {}", e)))?;
+        let session_id = self.session_id.clone();
+        let session_slot = Arc::clone(&self.session);
+        let status = Arc::clone(&self.status);
+
+        tokio::spawn(async move {
+            let id = session_id.clone();
+            let created = tokio::task::spawn_blocking(move || {
+                SamSession::create(&sam_addr, id, SessionStyle::Stream, Some(destination))
+            })
+            .await;
+
+            // The adapter may have been stopped while tunnels were building
+            let mut status = status.write().await;
+            if *status != AdapterStatus::BuildingTunnels {
+                return;
+            }
+            match created {
+                Ok(Ok(session)) => {
+                    *session_slot.write().await = Some(session);
+                    *status = AdapterStatus::Ready;
+                    log::info!("I2P tunnels built, SAM session {} ready", session_id);
+                }
+                Ok(Err(e)) => {
+                    *status = AdapterStatus::Error;
+                    log::error!("I2P SAM session failed: {}", e);
+                }
+                Err(e) => {
+                    *status = AdapterStatus::Error;
+                    log::error!("I2P SAM session task failed: {}", e);
+                }
+            }
+        });
 
-        let mut session_lock = self.session.write().await;
-        *session_lock = Some(session);
+        Ok(())
+    }
 
-        log::info!("I2P SAM session established with ID: {}", self.session_id);
+    /// Whether the session is established and its tunnels built
+    async fn is_ready(&self) -> bool {
+        *self.status.read().await == AdapterStatus::Ready
+    }
 
-        Ok(())
+    /// Wait until the session's tunnels are built
+    ///
+    /// Returns `Timeout` if they are not ready within `timeout`, and fails
+    /// at once if the session could not be created.
+    pub async fn wait_ready(&self, timeout: Duration) -> Result<()> {
+        let start = Instant::now();
+
+        loop {
+            match *self.status.read().await {
+                AdapterStatus::Ready => return Ok(()),
+                AdapterStatus::BuildingTunnels => {}
+                status => {
+                    return Err(NetworkError::InitializationFailed(format!(
+                        "I2P session not building: {}",
+                        status
+                    )))
+                }
+            }
+            if start.elapsed() >= timeout {
+                return Err(NetworkError::Timeout);
+            }
+            tokio::time::sleep(TUNNEL_POLL_INTERVAL).await;
+        }
     }
 
     /// Get our i2p destination address
@@ -296,12 +353,8 @@ impl NetworkAdapter for I2pAdapter {
 
         *self.router.write().await = Some(router);
 
-        // Initialize session
-        self.ensure_session().await?;
-
-        *self.status.write().await = AdapterStatus::Ready;
-
-        Ok(())
+        // Sends are refused until the session's tunnels are built
+        self.start_session().await
     }
 
     async fn start(&mut self) -> Result<()> {
@@ -337,6 +390,11 @@ impl NetworkAdapter for I2pAdapter {
             }
         };
 
+        // There is no session to send on until the tunnels are built
+        if !self.is_ready().await {
+            return Err(NetworkError::AdapterNotReady);
+        }
+
         self.send_frame(dest_str, frame).await
     }
 
@@ -418,6 +476,142 @@ impl NetworkAdapter for I2pAdapter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Mock SAM bridge that confirms sessions once `ready` is set, as routers
+    /// do when the session's tunnels are built
+    struct MockSam {
+        port: u16,
+        ready: Arc<AtomicBool>,
+        /// Bytes received over STREAM CONNECT streams
+        received: Arc<AtomicUsize>,
+    }
+
+    impl MockSam {
+        fn start() -> Self {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let ready = Arc::new(AtomicBool::new(false));
+            let received = Arc::new(AtomicUsize::new(0));
+
+            let (ready_flag, received_count) = (Arc::clone(&ready), Arc::clone(&received));
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(stream) = stream else { break };
+                    let ready = Arc::clone(&ready_flag);
+                    let received = Arc::clone(&received_count);
+                    std::thread::spawn(move || Self::serve(stream, ready, received));
+                }
+            });
+
+            Self {
+                port,
+                ready,
+                received,
+            }
+        }
+
+        fn serve(stream: TcpStream, ready: Arc<AtomicBool>, received: Arc<AtomicUsize>) {
+            use std::io::{BufRead, BufReader};
+
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+
+            while reader.read_line(&mut line).unwrap_or(0) > 0 {
+                let reply = if line.starts_with("HELLO") {
+                    "HELLO REPLY RESULT=OK VERSION=3.1"
+                } else if line.starts_with("DEST GENERATE") {
+                    "DEST REPLY PUB=mock~dest PRIV=mock~keys"
+                } else if line.starts_with("SESSION CREATE") {
+                    while !ready.load(Ordering::SeqCst) {
+                        std::thread::sleep(Duration::from_millis(10));
+                    }
+                    "SESSION STATUS RESULT=OK DESTINATION=mock~dest"
+                } else if line.starts_with("STREAM CONNECT") {
+                    writer.write_all(b"STREAM STATUS RESULT=OK\n").unwrap();
+                    let mut buf = [0u8; 1024];
+                    while let Ok(n @ 1..) = reader.read(&mut buf) {
+                        received.fetch_add(n, Ordering::SeqCst);
+                    }
+                    return;
+                } else {
+                    "ERROR"
+                };
+                writer.write_all(format!("{}\n", reply).as_bytes()).unwrap();
+                line.clear();
+            }
+        }
+
+        fn adapter(&self, data_dir: &std::path::Path) -> I2pAdapter {
+            I2pAdapter::with_config(I2pRouterConfig {
+                data_dir: data_dir.to_path_buf(),
+                sam_port: self.port,
+                ..Default::default()
+            })
+        }
+    }
+
+    fn test_frame() -> Frame {
+        use myriadmesh_protocol::{types::NODE_ID_SIZE, MessageId, MessageType, NodeId};
+
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let payload = b"over i2p".to_vec();
+        let msg_id = MessageId::generate(&source, &dest, &payload, 0, 0);
+        Frame::new(MessageType::Data, source, dest, payload, msg_id, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_send_gated_on_tunnel_readiness() {
+        let sam = MockSam::start();
+        let dir = tempfile::tempdir().unwrap();
+        let mut adapter = sam.adapter(dir.path());
+        adapter.initialize().await.unwrap();
+        assert_eq!(adapter.get_status(), AdapterStatus::BuildingTunnels);
+
+        let peer = Address::I2P("peer.b32.i2p".to_string());
+        assert!(matches!(
+            adapter.send(&peer, &test_frame()).await,
+            Err(NetworkError::AdapterNotReady)
+        ));
+        assert_eq!(sam.received.load(Ordering::SeqCst), 0);
+
+        // Tunnels finish building and the router confirms the session
+        sam.ready.store(true, Ordering::SeqCst);
+        adapter.wait_ready(Duration::from_secs(5)).await.unwrap();
+        adapter.send(&peer, &test_frame()).await.unwrap();
+        assert_eq!(adapter.get_status(), AdapterStatus::Ready);
+
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while sam.received.load(Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("frame should reach the SAM stream");
+    }
+
+    #[tokio::test]
+    async fn test_wait_ready() {
+        let sam = MockSam::start();
+        let dir = tempfile::tempdir().unwrap();
+        let mut adapter = sam.adapter(dir.path());
+        adapter.initialize().await.unwrap();
+
+        assert!(matches!(
+            adapter.wait_ready(Duration::from_millis(100)).await,
+            Err(NetworkError::Timeout)
+        ));
+
+        let ready = Arc::clone(&sam.ready);
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            ready.store(true, Ordering::SeqCst);
+        });
+        adapter.wait_ready(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(adapter.get_status(), AdapterStatus::Ready);
+    }
 
     #[test]
     fn test_adapter_creation() {
@@ -455,6 +649,7 @@ mod tests {
         let mut adapter = I2pAdapter::new();
         let result = adapter.initialize().await;
         assert!(result.is_ok());
+        adapter.wait_ready(Duration::from_secs(300)).await.unwrap();
         assert_eq!(adapter.get_status(), AdapterStatus::Ready);
     }
 
@@ -11,6 +11,8 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
 
+use super::supervisor::{RouterController, RouterStatus, RouterSupervisor, SupervisorConfig};
+
 #[derive(Error, Debug)]
 pub enum I2pRouterError {
     #[error("Failed to start i2pd: {0}")]
@@ -25,6 +27,9 @@ pub enum I2pRouterError {
     #[error("Router not ready after {0:?}")]
     TimeoutError(Duration),
 
+    #[error("Router restarted {restarts} times within {window:?}, giving up")]
+    RestartLimitExceeded { restarts: usize, window: Duration },
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
@@ -150,6 +155,23 @@ impl std::fmt::Debug for EmbeddedI2pRouter {
 impl EmbeddedI2pRouter {
     /// Start an embedded i2pd router
     pub fn start(config: I2pRouterConfig) -> Result<Self> {
+        let process = Self::spawn_process(&config)?;
+        let ready = Arc::new(AtomicBool::new(false));
+
+        let mut router = EmbeddedI2pRouter {
+            process,
+            config,
+            ready,
+        };
+
+        // Monitor startup in background
+        router.monitor_startup();
+
+        Ok(router)
+    }
+
+    /// Write the configuration and spawn an i2pd process
+    fn spawn_process(config: &I2pRouterConfig) -> Result<Child> {
         // Create data directory
         fs::create_dir_all(&config.data_dir)?;
 
@@ -166,7 +188,7 @@ impl EmbeddedI2pRouter {
         };
 
         // Start i2pd process
-        let process = Command::new(&i2pd_binary)
+        Command::new(&i2pd_binary)
             .arg("--conf")
             .arg(&config_path)
             .arg("--datadir")
@@ -174,20 +196,7 @@ impl EmbeddedI2pRouter {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
-            .map_err(|e| I2pRouterError::StartupFailed(e.to_string()))?;
-
-        let ready = Arc::new(AtomicBool::new(false));
-
-        let mut router = EmbeddedI2pRouter {
-            process,
-            config,
-            ready,
-        };
-
-        // Monitor startup in background
-        router.monitor_startup();
-
-        Ok(router)
+            .map_err(|e| I2pRouterError::StartupFailed(e.to_string()))
     }
 
     /// Find i2pd binary in system PATH
@@ -273,6 +282,23 @@ impl EmbeddedI2pRouter {
     }
 }
 
+impl RouterController for EmbeddedI2pRouter {
+    fn is_healthy(&mut self) -> bool {
+        // The process must still be running and its SAM bridge accepting
+        matches!(self.process.try_wait(), Ok(None)) && Self::check_sam_available(self.sam_port())
+    }
+
+    fn restart(&mut self) -> Result<()> {
+        // The old process may already be gone; reap it either way
+        let _ = self.stop();
+
+        self.process = Self::spawn_process(&self.config)?;
+        self.ready.store(false, Ordering::SeqCst);
+        self.monitor_startup();
+        Ok(())
+    }
+}
+
 impl Drop for EmbeddedI2pRouter {
     fn drop(&mut self) {
         let _ = self.stop();
@@ -285,8 +311,11 @@ pub enum I2pRouterMode {
     /// Using system-installed i2p router
     System { sam_port: u16 },
 
-    /// Using embedded i2pd process
-    Embedded { router: EmbeddedI2pRouter },
+    /// Using embedded i2pd process, restarted by a supervisor if it dies
+    Embedded {
+        sam_port: u16,
+        supervisor: RouterSupervisor<EmbeddedI2pRouter>,
+    },
 }
 
 impl I2pRouterMode {
@@ -307,7 +336,14 @@ impl I2pRouterMode {
         // Wait for router to be ready
         router.wait_ready(Duration::from_secs(60)).await?;
 
-        Ok(I2pRouterMode::Embedded { router })
+        let sam_port = router.sam_port();
+        let supervisor = RouterSupervisor::new(router, SupervisorConfig::default());
+        supervisor.spawn();
+
+        Ok(I2pRouterMode::Embedded {
+            sam_port,
+            supervisor,
+        })
     }
 
     /// Check if system i2p router is available
@@ -321,7 +357,15 @@ impl I2pRouterMode {
     pub fn sam_port(&self) -> u16 {
         match self {
             I2pRouterMode::System { sam_port } => *sam_port,
-            I2pRouterMode::Embedded { router } => router.sam_port(),
+            I2pRouterMode::Embedded { sam_port, .. } => *sam_port,
+        }
+    }
+
+    /// Supervisor status of an embedded router, `None` for a system router
+    pub fn status(&self) -> Option<RouterStatus> {
+        match self {
+            I2pRouterMode::System { .. } => None,
+            I2pRouterMode::Embedded { supervisor, .. } => Some(supervisor.status()),
         }
     }
 }
@@ -2,14 +2,24 @@
 //!
 //! This module provides zero-configuration i2p integration by:
 //! - Automatically managing an embedded i2pd router process
+//! - Restarting the embedded router when it stops responding
 //! - Detecting and using existing system i2p routers
 //! - Persisting i2p destination keys across restarts
 //! - Providing SAM (Simple Anonymous Messaging) protocol client
+//! - Pooling SAM v3.3 streams over a persistent primary session
 
 pub mod adapter;
 pub mod embedded_router;
 pub mod sam_client;
+pub mod sam_pool;
+pub mod supervisor;
 
 pub use adapter::I2pAdapter;
 pub use embedded_router::{EmbeddedI2pRouter, I2pRouterConfig, I2pRouterError, I2pRouterMode};
-pub use sam_client::{SamConnection, SamDestination, SamError, SamSession, SessionStyle};
+pub use sam_client::{
+    SamConnection, SamDatagram, SamDestination, SamError, SamResult, SamSession, SessionStyle,
+};
+pub use sam_pool::{PooledStream, SamPoolConfig, SamPoolStats, SamSessionPool};
+pub use supervisor::{
+    RouterController, RouterHealth, RouterStatus, RouterSupervisor, SupervisorConfig,
+};
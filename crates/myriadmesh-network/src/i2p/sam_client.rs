@@ -1,9 +1,10 @@
 //! SAM (Simple Anonymous Messaging) protocol client for i2p
 //!
 //! Provides a client implementation for communicating with i2p routers
-//! using the SAM v3 protocol.
+//! using the SAM v3 protocol. PRIMARY sessions and subsessions require
+//! SAM v3.3; see [`super::sam_pool`] for pooled streaming.
 
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, Read, Write};
 use std::net::TcpStream;
 use std::time::Duration;
 use thiserror::Error;
@@ -22,15 +23,158 @@ pub enum SamError {
     #[error("Session error: {0}")]
     SessionError(String),
 
+    #[error("Datagram of {size} bytes exceeds the {max} byte limit")]
+    DatagramTooLarge { size: usize, max: usize },
+
+    /// The bridge answered a command with a non-OK `RESULT=`
+    #[error("{command} failed: {result}{}", message.as_ref().map(|m| format!(" ({})", m)).unwrap_or_default())]
+    Rejected {
+        /// Command the bridge rejected, e.g. `STREAM CONNECT`
+        command: String,
+        /// Result code from the reply
+        result: SamResult,
+        /// Human-readable `MESSAGE=` from the reply, if any
+        message: Option<String>,
+    },
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
 
 pub type Result<T> = std::result::Result<T, SamError>;
 
+impl SamError {
+    /// Result code of a rejected command
+    pub fn result(&self) -> Option<&SamResult> {
+        match self {
+            SamError::Rejected { result, .. } => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Whether the same command may succeed if retried later
+    ///
+    /// Unreachable peers, timeouts and router errors are transient; rejected
+    /// IDs, keys and versions need the session reconfigured instead.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SamError::Rejected { result, .. } => result.is_retryable(),
+            SamError::ConnectionFailed(_) | SamError::IoError(_) => true,
+            SamError::ProtocolError(_)
+            | SamError::InvalidDestination(_)
+            | SamError::SessionError(_)
+            | SamError::DatagramTooLarge { .. } => false,
+        }
+    }
+}
+
+/// `RESULT=` codes of SAM v3 replies, other than `OK`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SamResult {
+    /// The remote destination could not be reached
+    CantReachPeer,
+    /// The remote destination is not known to the router
+    PeerNotFound,
+    /// Tunnels or the remote peer did not answer in time
+    Timeout,
+    /// Generic router error, e.g. tunnels could not be built
+    I2pError,
+    /// The session ID is already in use
+    DuplicatedId,
+    /// The destination is already in use by another session
+    DuplicatedDest,
+    /// The session ID does not exist (e.g. the session was closed)
+    InvalidId,
+    /// The destination or private key is malformed
+    InvalidKey,
+    /// A name lookup found nothing
+    KeyNotFound,
+    /// The bridge does not support the requested version
+    NoVersion,
+    /// Another `STREAM ACCEPT` is already pending on the session
+    AlreadyAccepting,
+    /// A code this client does not know
+    Other(String),
+}
+
+impl SamResult {
+    /// Parse a `RESULT=` value; None for `OK`
+    pub fn parse(code: &str) -> Option<Self> {
+        Some(match code {
+            "OK" => return None,
+            "CANT_REACH_PEER" => SamResult::CantReachPeer,
+            "PEER_NOT_FOUND" => SamResult::PeerNotFound,
+            "TIMEOUT" => SamResult::Timeout,
+            "I2P_ERROR" => SamResult::I2pError,
+            "DUPLICATED_ID" => SamResult::DuplicatedId,
+            "DUPLICATED_DEST" => SamResult::DuplicatedDest,
+            "INVALID_ID" => SamResult::InvalidId,
+            "INVALID_KEY" => SamResult::InvalidKey,
+            "KEY_NOT_FOUND" => SamResult::KeyNotFound,
+            "NOVERSION" => SamResult::NoVersion,
+            "ALREADY_ACCEPTING" => SamResult::AlreadyAccepting,
+            other => SamResult::Other(other.to_string()),
+        })
+    }
+
+    /// The code as it appears on the wire
+    pub fn as_str(&self) -> &str {
+        match self {
+            SamResult::CantReachPeer => "CANT_REACH_PEER",
+            SamResult::PeerNotFound => "PEER_NOT_FOUND",
+            SamResult::Timeout => "TIMEOUT",
+            SamResult::I2pError => "I2P_ERROR",
+            SamResult::DuplicatedId => "DUPLICATED_ID",
+            SamResult::DuplicatedDest => "DUPLICATED_DEST",
+            SamResult::InvalidId => "INVALID_ID",
+            SamResult::InvalidKey => "INVALID_KEY",
+            SamResult::KeyNotFound => "KEY_NOT_FOUND",
+            SamResult::NoVersion => "NOVERSION",
+            SamResult::AlreadyAccepting => "ALREADY_ACCEPTING",
+            SamResult::Other(code) => code,
+        }
+    }
+
+    /// Whether a command failing with this code may succeed later
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            SamResult::CantReachPeer
+                | SamResult::PeerNotFound
+                | SamResult::Timeout
+                | SamResult::I2pError
+                | SamResult::AlreadyAccepting
+        )
+    }
+}
+
+impl std::fmt::Display for SamResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// SAM protocol version
 const SAM_VERSION: &str = "3.1";
 
+/// Timeout for ordinary SAM replies
+const SAM_REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait for `SESSION STATUS`
+///
+/// Routers only answer `SESSION CREATE` once the session's inbound and
+/// outbound tunnels are built, which can take minutes on a fresh router.
+pub const SESSION_CREATE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// SAM protocol version introducing PRIMARY sessions and subsessions
+pub const SAM_PRIMARY_VERSION: &str = "3.3";
+
+/// Maximum payload of a repliable datagram
+pub const MAX_DATAGRAM_SIZE: usize = 31744;
+
+/// Maximum payload of a raw (anonymous) datagram
+pub const MAX_RAW_DATAGRAM_SIZE: usize = 32768;
+
 /// SAM session types
 #[derive(Debug, Clone, Copy)]
 pub enum SessionStyle {
@@ -40,14 +184,27 @@ pub enum SessionStyle {
     Datagram,
     /// Raw data forwarding
     Raw,
+    /// Primary session hosting subsessions (SAM v3.3)
+    Primary,
 }
 
 impl SessionStyle {
+    /// Maximum datagram payload for this style, `None` if it does not
+    /// carry datagrams
+    pub fn max_datagram_size(&self) -> Option<usize> {
+        match self {
+            SessionStyle::Datagram => Some(MAX_DATAGRAM_SIZE),
+            SessionStyle::Raw => Some(MAX_RAW_DATAGRAM_SIZE),
+            SessionStyle::Stream | SessionStyle::Primary => None,
+        }
+    }
+
     fn as_str(&self) -> &str {
         match self {
             SessionStyle::Stream => "STREAM",
             SessionStyle::Datagram => "DATAGRAM",
             SessionStyle::Raw => "RAW",
+            SessionStyle::Primary => "PRIMARY",
         }
     }
 }
@@ -70,6 +227,15 @@ impl SamDestination {
     }
 }
 
+/// A datagram received on a DATAGRAM or RAW session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SamDatagram {
+    /// Sender, present for repliable datagrams only
+    pub source: Option<SamDestination>,
+    /// Datagram payload
+    pub payload: Vec<u8>,
+}
+
 /// SAM connection for communicating with i2p router
 pub struct SamConnection {
     stream: TcpStream,
@@ -79,11 +245,16 @@ pub struct SamConnection {
 impl SamConnection {
     /// Connect to SAM bridge
     pub fn connect(sam_addr: &str) -> Result<Self> {
+        Self::connect_version(sam_addr, SAM_VERSION)
+    }
+
+    /// Connect to SAM bridge, negotiating exactly `version`
+    pub fn connect_version(sam_addr: &str, version: &str) -> Result<Self> {
         let stream =
             TcpStream::connect(sam_addr).map_err(|e| SamError::ConnectionFailed(e.to_string()))?;
 
-        stream.set_read_timeout(Some(Duration::from_secs(30))).ok();
-        stream.set_write_timeout(Some(Duration::from_secs(30))).ok();
+        stream.set_read_timeout(Some(SAM_REPLY_TIMEOUT)).ok();
+        stream.set_write_timeout(Some(SAM_REPLY_TIMEOUT)).ok();
 
         let reader = BufReader::new(
             stream
@@ -94,18 +265,10 @@ impl SamConnection {
         let mut connection = SamConnection { stream, reader };
 
         // Send HELLO
-        connection.send_command(&format!(
-            "HELLO VERSION MIN={} MAX={}\n",
-            SAM_VERSION, SAM_VERSION
-        ))?;
+        connection.send_command(&format!("HELLO VERSION MIN={} MAX={}\n", version, version))?;
         let response = connection.read_response()?;
 
-        if !response.starts_with("HELLO REPLY") || !response.contains("RESULT=OK") {
-            return Err(SamError::ProtocolError(format!(
-                "HELLO failed: {}",
-                response
-            )));
-        }
+        Self::check_reply(&response, "HELLO REPLY", "HELLO")?;
 
         Ok(connection)
     }
@@ -131,6 +294,9 @@ impl SamConnection {
     }
 
     /// Create a SAM session
+    ///
+    /// Returns once the router reports the session's tunnels built, waiting
+    /// up to [`SESSION_CREATE_TIMEOUT`].
     pub fn create_session(
         &mut self,
         session_id: &str,
@@ -147,14 +313,14 @@ impl SamConnection {
         );
 
         self.send_command(&cmd)?;
-        let response = self.read_response()?;
+        self.stream
+            .set_read_timeout(Some(SESSION_CREATE_TIMEOUT))
+            .ok();
+        let response = self.read_response();
+        self.stream.set_read_timeout(Some(SAM_REPLY_TIMEOUT)).ok();
+        let response = response?;
 
-        if !response.starts_with("SESSION STATUS") || !response.contains("RESULT=OK") {
-            return Err(SamError::SessionError(format!(
-                "Session creation failed: {}",
-                response
-            )));
-        }
+        Self::check_reply(&response, "SESSION STATUS", "SESSION CREATE")?;
 
         // Extract destination from response
         let dest = Self::extract_value(&response, "DESTINATION=")
@@ -163,6 +329,40 @@ impl SamConnection {
         Ok(SamDestination::new(dest))
     }
 
+    /// Add a subsession to this connection's PRIMARY session (SAM v3.3)
+    ///
+    /// Subsessions of the same style must use distinct `from_port`s.
+    pub fn add_subsession(
+        &mut self,
+        subsession_id: &str,
+        style: SessionStyle,
+        from_port: u16,
+    ) -> Result<()> {
+        let cmd = format!(
+            "SESSION ADD STYLE={} ID={} FROM_PORT={}\n",
+            style.as_str(),
+            subsession_id,
+            from_port
+        );
+
+        self.send_command(&cmd)?;
+        let response = self.read_response()?;
+
+        Self::check_reply(&response, "SESSION STATUS", "SESSION ADD")?;
+
+        Ok(())
+    }
+
+    /// Remove a subsession from this connection's PRIMARY session (SAM v3.3)
+    pub fn remove_subsession(&mut self, subsession_id: &str) -> Result<()> {
+        self.send_command(&format!("SESSION REMOVE ID={}\n", subsession_id))?;
+        let response = self.read_response()?;
+
+        Self::check_reply(&response, "SESSION STATUS", "SESSION REMOVE")?;
+
+        Ok(())
+    }
+
     /// Connect to a remote i2p destination
     pub fn stream_connect(&mut self, session_id: &str, destination: &str) -> Result<TcpStream> {
         let cmd = format!(
@@ -173,12 +373,7 @@ impl SamConnection {
         self.send_command(&cmd)?;
         let response = self.read_response()?;
 
-        if !response.starts_with("STREAM STATUS") || !response.contains("RESULT=OK") {
-            return Err(SamError::ProtocolError(format!(
-                "STREAM CONNECT failed: {}",
-                response
-            )));
-        }
+        Self::check_reply(&response, "STREAM STATUS", "STREAM CONNECT")?;
 
         // Return the underlying stream for data transfer
         self.stream.try_clone().map_err(SamError::IoError)
@@ -191,12 +386,7 @@ impl SamConnection {
         self.send_command(&cmd)?;
         let response = self.read_response()?;
 
-        if !response.starts_with("STREAM STATUS") || !response.contains("RESULT=OK") {
-            return Err(SamError::ProtocolError(format!(
-                "STREAM ACCEPT failed: {}",
-                response
-            )));
-        }
+        Self::check_reply(&response, "STREAM STATUS", "STREAM ACCEPT")?;
 
         // Extract remote destination
         let remote_dest = Self::extract_value(&response, "DESTINATION=")
@@ -207,6 +397,80 @@ impl SamConnection {
         Ok((stream, SamDestination::new(remote_dest)))
     }
 
+    /// Send a datagram over this session's control socket
+    ///
+    /// `style` selects repliable (DATAGRAM) or anonymous (RAW) sending. The
+    /// bridge sends no reply, so delivery is not confirmed.
+    pub fn datagram_send(
+        &mut self,
+        style: SessionStyle,
+        destination: &str,
+        payload: &[u8],
+    ) -> Result<()> {
+        let max = style.max_datagram_size().ok_or_else(|| {
+            SamError::SessionError(format!(
+                "{} sessions do not carry datagrams",
+                style.as_str()
+            ))
+        })?;
+        if payload.len() > max {
+            return Err(SamError::DatagramTooLarge {
+                size: payload.len(),
+                max,
+            });
+        }
+
+        let header = format!(
+            "{} SEND DESTINATION={} SIZE={}\n",
+            style.as_str(),
+            destination,
+            payload.len()
+        );
+        self.stream
+            .write_all(header.as_bytes())
+            .and_then(|_| self.stream.write_all(payload))
+            .and_then(|_| self.stream.flush())
+            .map_err(SamError::IoError)
+    }
+
+    /// Wait for the next datagram delivered on this session's control socket
+    pub fn datagram_receive(&mut self) -> Result<SamDatagram> {
+        let header = self.read_response()?;
+
+        let source = if header.starts_with("DATAGRAM RECEIVED") {
+            let dest = Self::extract_value(&header, "DESTINATION=").ok_or_else(|| {
+                SamError::ProtocolError("No destination in datagram header".to_string())
+            })?;
+            Some(SamDestination::new(dest))
+        } else if header.starts_with("RAW RECEIVED") {
+            None
+        } else {
+            return Err(SamError::ProtocolError(format!(
+                "Unexpected message while receiving datagram: {}",
+                header
+            )));
+        };
+
+        let size: usize = Self::extract_value(&header, "SIZE=")
+            .and_then(|size| size.parse().ok())
+            .ok_or_else(|| SamError::ProtocolError(format!("Invalid datagram size: {}", header)))?;
+        // SECURITY: Bound the allocation by the protocol limit, not the
+        // size the bridge claims
+        if size > MAX_RAW_DATAGRAM_SIZE {
+            return Err(SamError::DatagramTooLarge {
+                size,
+                max: MAX_RAW_DATAGRAM_SIZE,
+            });
+        }
+
+        let mut payload = vec![0u8; size];
+        self.reader
+            .read_exact(&mut payload)
+            .map_err(SamError::IoError)?;
+
+        Ok(SamDatagram { source, payload })
+    }
+
     /// Send a command to SAM bridge
     fn send_command(&mut self, command: &str) -> Result<()> {
         self.stream
@@ -218,12 +482,55 @@ impl SamConnection {
     /// Read a response from SAM bridge
     fn read_response(&mut self) -> Result<String> {
         let mut line = String::new();
-        self.reader
+        let read = self
+            .reader
             .read_line(&mut line)
             .map_err(SamError::IoError)?;
+        if read == 0 {
+            // The bridge closed the socket, e.g. because the router restarted
+            return Err(SamError::ConnectionFailed(
+                "SAM bridge closed the connection".to_string(),
+            ));
+        }
         Ok(line.trim().to_string())
     }
 
+    /// Check a reply to `command` for a `prefix` line with `RESULT=OK`
+    ///
+    /// Other result codes become [`SamError::Rejected`], so callers can tell
+    /// a transient failure (e.g. `CANT_REACH_PEER`) from a misconfiguration
+    /// (e.g. `DUPLICATED_ID`).
+    fn check_reply(response: &str, prefix: &str, command: &str) -> Result<()> {
+        if !response.starts_with(prefix) {
+            return Err(SamError::ProtocolError(format!(
+                "{} failed: {}",
+                command, response
+            )));
+        }
+
+        let code = Self::extract_value(response, "RESULT=")
+            .ok_or_else(|| SamError::ProtocolError(format!("{} failed: {}", command, response)))?;
+        match SamResult::parse(&code) {
+            None => Ok(()),
+            Some(result) => Err(SamError::Rejected {
+                command: command.to_string(),
+                result,
+                message: Self::extract_message(response),
+            }),
+        }
+    }
+
+    /// Extract the `MESSAGE=` value, which may be quoted and contain spaces
+    fn extract_message(response: &str) -> Option<String> {
+        let start = response.find("MESSAGE=")? + "MESSAGE=".len();
+        let remaining = &response[start..];
+        let message = match remaining.strip_prefix('"') {
+            Some(quoted) => quoted.split('"').next().unwrap_or(quoted),
+            None => remaining.split(' ').next().unwrap_or(remaining),
+        };
+        Some(message.to_string()).filter(|m| !m.is_empty())
+    }
+
     /// Extract a value from SAM response
     fn extract_value(response: &str, key: &str) -> Option<String> {
         response.find(key).map(|start| {
@@ -301,6 +608,33 @@ impl SamSession {
 
         self.connection.stream_accept(&self.session_id)
     }
+
+    /// Send a datagram to a remote destination (for DATAGRAM and RAW sessions)
+    ///
+    /// Payloads over the style's size limit are rejected before sending.
+    pub fn send_datagram(&mut self, destination: &str, payload: &[u8]) -> Result<()> {
+        if self.style.max_datagram_size().is_none() {
+            return Err(SamError::SessionError(
+                "Datagrams only supported for DATAGRAM and RAW sessions".to_string(),
+            ));
+        }
+
+        self.connection
+            .datagram_send(self.style, destination, payload)
+    }
+
+    /// Receive the next datagram (for DATAGRAM and RAW sessions)
+    ///
+    /// The sender's destination is included for repliable datagrams.
+    pub fn receive_datagram(&mut self) -> Result<SamDatagram> {
+        if self.style.max_datagram_size().is_none() {
+            return Err(SamError::SessionError(
+                "Datagrams only supported for DATAGRAM and RAW sessions".to_string(),
+            ));
+        }
+
+        self.connection.datagram_receive()
+    }
 }
 
 #[cfg(test)]
@@ -329,6 +663,7 @@ mod tests {
         assert_eq!(SessionStyle::Stream.as_str(), "STREAM");
         assert_eq!(SessionStyle::Datagram.as_str(), "DATAGRAM");
         assert_eq!(SessionStyle::Raw.as_str(), "RAW");
+        assert_eq!(SessionStyle::Primary.as_str(), "PRIMARY");
     }
 
     #[test]
@@ -337,6 +672,139 @@ mod tests {
         assert_eq!(dest.as_str(), "test_destination");
     }
 
+    /// Mock SAM bridge routing DATAGRAM and RAW sends between its sessions
+    ///
+    /// Each session's destination is `dest-<session id>`.
+    fn mock_datagram_bridge() -> String {
+        use std::collections::HashMap;
+        use std::net::TcpListener;
+        use std::sync::{Arc, Mutex};
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let sessions: Arc<Mutex<HashMap<String, TcpStream>>> = Arc::default();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { break };
+                let sessions = Arc::clone(&sessions);
+                std::thread::spawn(move || {
+                    let mut writer = stream.try_clone().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let mut own_dest = String::new();
+                    let mut line = String::new();
+
+                    while reader.read_line(&mut line).unwrap_or(0) > 0 {
+                        let field = |key| SamConnection::extract_value(line.trim(), key);
+                        if line.starts_with("HELLO") {
+                            writer
+                                .write_all(b"HELLO REPLY RESULT=OK VERSION=3.1\n")
+                                .unwrap();
+                        } else if line.starts_with("SESSION CREATE") {
+                            own_dest = format!("dest-{}", field("ID=").unwrap());
+                            sessions
+                                .lock()
+                                .unwrap()
+                                .insert(own_dest.clone(), writer.try_clone().unwrap());
+                            let reply =
+                                format!("SESSION STATUS RESULT=OK DESTINATION={}\n", own_dest);
+                            writer.write_all(reply.as_bytes()).unwrap();
+                        } else if line.contains(" SEND ") {
+                            let target = field("DESTINATION=").unwrap();
+                            let size: usize = field("SIZE=").unwrap().parse().unwrap();
+                            let mut payload = vec![0u8; size];
+                            reader.read_exact(&mut payload).unwrap();
+
+                            let header = if line.starts_with("DATAGRAM") {
+                                format!(
+                                    "DATAGRAM RECEIVED DESTINATION={} SIZE={}\n",
+                                    own_dest, size
+                                )
+                            } else {
+                                format!("RAW RECEIVED SIZE={}\n", size)
+                            };
+                            if let Some(peer) = sessions.lock().unwrap().get_mut(&target) {
+                                peer.write_all(header.as_bytes()).unwrap();
+                                peer.write_all(&payload).unwrap();
+                            }
+                        }
+                        line.clear();
+                    }
+                });
+            }
+        });
+
+        addr
+    }
+
+    #[test]
+    fn test_repliable_datagram_round_trip() {
+        let bridge = mock_datagram_bridge();
+        let mut alice =
+            SamSession::create(&bridge, "alice".to_string(), SessionStyle::Datagram, None).unwrap();
+        let mut bob =
+            SamSession::create(&bridge, "bob".to_string(), SessionStyle::Datagram, None).unwrap();
+
+        alice.send_datagram("dest-bob", b"beacon").unwrap();
+        let received = bob.receive_datagram().unwrap();
+        assert_eq!(received.payload, b"beacon");
+
+        // The sender's destination is recovered and can be replied to
+        let reply_to = received.source.expect("repliable datagram has a source");
+        assert_eq!(&reply_to, alice.destination());
+        bob.send_datagram(reply_to.as_str(), b"ack").unwrap();
+
+        let reply = alice.receive_datagram().unwrap();
+        assert_eq!(reply.payload, b"ack");
+        assert_eq!(reply.source.as_ref(), Some(bob.destination()));
+    }
+
+    #[test]
+    fn test_raw_datagram_is_anonymous() {
+        let bridge = mock_datagram_bridge();
+        let mut alice =
+            SamSession::create(&bridge, "alice".to_string(), SessionStyle::Raw, None).unwrap();
+        let mut bob =
+            SamSession::create(&bridge, "bob".to_string(), SessionStyle::Raw, None).unwrap();
+
+        alice.send_datagram("dest-bob", b"anonymous").unwrap();
+        let received = bob.receive_datagram().unwrap();
+        assert_eq!(received.payload, b"anonymous");
+        assert_eq!(received.source, None);
+    }
+
+    #[test]
+    fn test_datagram_size_limits() {
+        let bridge = mock_datagram_bridge();
+        let mut datagram =
+            SamSession::create(&bridge, "dg".to_string(), SessionStyle::Datagram, None).unwrap();
+        let mut raw =
+            SamSession::create(&bridge, "raw".to_string(), SessionStyle::Raw, None).unwrap();
+        let mut stream =
+            SamSession::create(&bridge, "st".to_string(), SessionStyle::Stream, None).unwrap();
+
+        let oversized = vec![0u8; MAX_DATAGRAM_SIZE + 1];
+        assert!(matches!(
+            datagram.send_datagram("dest-raw", &oversized),
+            Err(SamError::DatagramTooLarge {
+                max: MAX_DATAGRAM_SIZE,
+                ..
+            })
+        ));
+
+        // Raw datagrams have no sender header, leaving room for more payload
+        raw.send_datagram("dest-dg", &oversized).unwrap();
+        assert!(matches!(
+            raw.send_datagram("dest-dg", &vec![0u8; MAX_RAW_DATAGRAM_SIZE + 1]),
+            Err(SamError::DatagramTooLarge { .. })
+        ));
+
+        assert!(matches!(
+            stream.send_datagram("dest-dg", b"x"),
+            Err(SamError::SessionError(_))
+        ));
+    }
+
     // Integration tests require a running i2p router
     #[test]
     #[ignore]
@@ -367,4 +835,72 @@ mod tests {
         );
         assert!(result.is_ok());
     }
+
+    fn rejected(response: &str) -> SamError {
+        SamConnection::check_reply(response, "STREAM STATUS", "STREAM CONNECT").unwrap_err()
+    }
+
+    #[test]
+    fn test_reply_ok_accepted() {
+        assert!(SamConnection::check_reply(
+            "STREAM STATUS RESULT=OK",
+            "STREAM STATUS",
+            "STREAM CONNECT"
+        )
+        .is_ok());
+        assert!(matches!(
+            SamConnection::check_reply("HELLO REPLY RESULT=OK", "STREAM STATUS", "STREAM CONNECT"),
+            Err(SamError::ProtocolError(_))
+        ));
+    }
+
+    #[test]
+    fn test_error_replies_parse_to_result_codes() {
+        let error =
+            rejected("STREAM STATUS RESULT=CANT_REACH_PEER MESSAGE=\"Connection refused by peer\"");
+        match &error {
+            SamError::Rejected {
+                command,
+                result,
+                message,
+            } => {
+                assert_eq!(command, "STREAM CONNECT");
+                assert_eq!(*result, SamResult::CantReachPeer);
+                assert_eq!(message.as_deref(), Some("Connection refused by peer"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(error.is_retryable());
+        assert_eq!(
+            error.to_string(),
+            "STREAM CONNECT failed: CANT_REACH_PEER (Connection refused by peer)"
+        );
+
+        let error = rejected("STREAM STATUS RESULT=I2P_ERROR MESSAGE=tunnel_build_failed");
+        assert_eq!(error.result(), Some(&SamResult::I2pError));
+        assert!(error.is_retryable());
+
+        let error = SamConnection::check_reply(
+            "SESSION STATUS RESULT=DUPLICATED_ID",
+            "SESSION STATUS",
+            "SESSION CREATE",
+        )
+        .unwrap_err();
+        assert_eq!(error.result(), Some(&SamResult::DuplicatedId));
+        assert!(!error.is_retryable());
+        assert_eq!(error.to_string(), "SESSION CREATE failed: DUPLICATED_ID");
+
+        assert_eq!(
+            rejected("STREAM STATUS RESULT=INVALID_ID").result(),
+            Some(&SamResult::InvalidId)
+        );
+        assert_eq!(
+            rejected("STREAM STATUS RESULT=TIMEOUT").result(),
+            Some(&SamResult::Timeout)
+        );
+        assert_eq!(
+            rejected("STREAM STATUS RESULT=SOMETHING_NEW").result(),
+            Some(&SamResult::Other("SOMETHING_NEW".to_string()))
+        );
+    }
 }
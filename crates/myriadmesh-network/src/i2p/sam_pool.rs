@@ -0,0 +1,577 @@
+//! Pooled SAM v3.3 streaming
+//!
+//! Opening a SAM STREAM session builds fresh tunnels, which takes seconds to
+//! minutes. [`SamSessionPool`] instead keeps one persistent PRIMARY session
+//! and multiplexes outgoing streams over a bounded set of STREAM subsessions,
+//! all sharing the primary's destination and tunnels. Idle subsessions are
+//! reaped, and if the router restarts the primary is transparently rebuilt.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::sam_client::{
+    Result, SamConnection, SamDestination, SamError, SamResult, SessionStyle, SAM_PRIMARY_VERSION,
+};
+
+/// Pool configuration
+#[derive(Debug, Clone)]
+pub struct SamPoolConfig {
+    /// SAM bridge address
+    pub sam_addr: String,
+    /// Prefix for primary and subsession IDs
+    pub session_prefix: String,
+    /// Private keys for the primary session, or `None` for a transient one
+    pub destination: Option<String>,
+    /// Maximum number of STREAM subsessions
+    pub max_subsessions: usize,
+    /// Idle time after which an unused subsession is removed
+    pub idle_timeout: Duration,
+}
+
+impl Default for SamPoolConfig {
+    fn default() -> Self {
+        Self {
+            sam_addr: "127.0.0.1:7656".to_string(),
+            session_prefix: "myriadmesh".to_string(),
+            destination: None,
+            max_subsessions: 8,
+            idle_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Pool counters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SamPoolStats {
+    /// PRIMARY sessions created, including after recoveries
+    pub sessions_created: u64,
+    /// Times the primary was lost and rebuilt
+    pub session_recoveries: u64,
+    /// STREAM subsessions added
+    pub subsessions_added: u64,
+    /// Subsessions removed after going idle
+    pub subsessions_reaped: u64,
+    /// Streams successfully opened
+    pub streams_opened: u64,
+}
+
+/// The persistent PRIMARY session
+struct Primary {
+    /// Control connection; closing it ends the session on the router
+    control: SamConnection,
+    session_id: String,
+    destination: SamDestination,
+}
+
+/// A STREAM subsession of the primary
+struct Subsession {
+    id: String,
+    last_used: Instant,
+    /// Streams currently open through this subsession
+    active: Arc<AtomicUsize>,
+}
+
+impl Subsession {
+    fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Default)]
+struct PoolState {
+    primary: Option<Primary>,
+    subsessions: Vec<Subsession>,
+    /// Incremented per primary so IDs never collide with a stale session
+    generation: u64,
+    /// Subsessions added under the current primary
+    added: u64,
+    stats: SamPoolStats,
+}
+
+/// Pool of SAM streams sharing one PRIMARY session
+pub struct SamSessionPool {
+    config: SamPoolConfig,
+    state: Mutex<PoolState>,
+}
+
+impl SamSessionPool {
+    /// Create a pool; the primary session is created on first use
+    pub fn new(config: SamPoolConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(PoolState::default()),
+        }
+    }
+
+    /// Open a stream to `destination`
+    ///
+    /// If the SAM bridge no longer knows the session (e.g. the router
+    /// restarted), the primary is recreated and the connect retried once.
+    pub fn open_stream(&self, destination: &str) -> Result<PooledStream> {
+        match self.try_open_stream(destination) {
+            Err(e) if is_session_lost(&e) => {
+                log::warn!("SAM session lost ({}), recreating primary session", e);
+                {
+                    let mut state = self.lock();
+                    Self::reset(&mut state);
+                    state.stats.session_recoveries += 1;
+                }
+                self.try_open_stream(destination)
+            }
+            result => result,
+        }
+    }
+
+    /// Remove subsessions with no open streams that have been idle longer
+    /// than the configured timeout, returning how many were removed
+    pub fn reap_idle(&self) -> usize {
+        let mut state = self.lock();
+        self.reap_locked(&mut state)
+    }
+
+    /// Destination of the primary session, if one is established
+    pub fn destination(&self) -> Option<SamDestination> {
+        self.lock()
+            .primary
+            .as_ref()
+            .map(|primary| primary.destination.clone())
+    }
+
+    /// Number of live subsessions
+    pub fn subsession_count(&self) -> usize {
+        self.lock().subsessions.len()
+    }
+
+    /// Pool counters
+    pub fn stats(&self) -> SamPoolStats {
+        self.lock().stats.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn try_open_stream(&self, destination: &str) -> Result<PooledStream> {
+        let (subsession_id, active) = {
+            let mut state = self.lock();
+            self.reap_locked(&mut state);
+            self.ensure_primary(&mut state)?;
+
+            let index = self.select_subsession(&mut state)?;
+            let subsession = &mut state.subsessions[index];
+            subsession.last_used = Instant::now();
+            subsession.active.fetch_add(1, Ordering::SeqCst);
+            (subsession.id.clone(), Arc::clone(&subsession.active))
+        };
+
+        // Each stream needs its own SAM socket; the lock is not held while
+        // the router builds the connection
+        let connected = SamConnection::connect_version(&self.config.sam_addr, SAM_PRIMARY_VERSION)
+            .and_then(|mut connection| connection.stream_connect(&subsession_id, destination));
+
+        match connected {
+            Ok(stream) => {
+                self.lock().stats.streams_opened += 1;
+                Ok(PooledStream { stream, active })
+            }
+            Err(e) => {
+                active.fetch_sub(1, Ordering::SeqCst);
+                Err(e)
+            }
+        }
+    }
+
+    /// Create the PRIMARY session if there is none
+    fn ensure_primary(&self, state: &mut PoolState) -> Result<()> {
+        if state.primary.is_some() {
+            return Ok(());
+        }
+
+        state.generation += 1;
+        state.added = 0;
+        let session_id = format!("{}-{}", self.config.session_prefix, state.generation);
+
+        let mut control =
+            SamConnection::connect_version(&self.config.sam_addr, SAM_PRIMARY_VERSION)?;
+        let destination = control.create_session(
+            &session_id,
+            SessionStyle::Primary,
+            self.config.destination.as_deref(),
+        )?;
+
+        log::info!("SAM primary session established with ID: {}", session_id);
+        state.primary = Some(Primary {
+            control,
+            session_id,
+            destination,
+        });
+        state.stats.sessions_created += 1;
+        Ok(())
+    }
+
+    /// Pick the subsession for a new stream, adding one if needed
+    ///
+    /// Prefers an idle subsession, then a new one while under the limit,
+    /// then the least loaded.
+    fn select_subsession(&self, state: &mut PoolState) -> Result<usize> {
+        if let Some(index) = state.subsessions.iter().position(|s| s.active() == 0) {
+            return Ok(index);
+        }
+
+        if state.subsessions.len() < self.config.max_subsessions.max(1) {
+            let primary = state
+                .primary
+                .as_mut()
+                .ok_or_else(|| SamError::SessionError("No primary session".to_string()))?;
+
+            state.added += 1;
+            let id = format!("{}-sub{}", primary.session_id, state.added);
+            // Distinct ports let the router tell STREAM subsessions apart
+            let from_port = (state.added % u64::from(u16::MAX)) as u16 + 1;
+            primary
+                .control
+                .add_subsession(&id, SessionStyle::Stream, from_port)?;
+
+            state.subsessions.push(Subsession {
+                id,
+                last_used: Instant::now(),
+                active: Arc::new(AtomicUsize::new(0)),
+            });
+            state.stats.subsessions_added += 1;
+            return Ok(state.subsessions.len() - 1);
+        }
+
+        Ok(state
+            .subsessions
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, s)| s.active())
+            .map(|(index, _)| index)
+            .unwrap_or(0))
+    }
+
+    fn reap_locked(&self, state: &mut PoolState) -> usize {
+        let timeout = self.config.idle_timeout;
+        let (idle, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut state.subsessions)
+            .into_iter()
+            .partition(|s| s.active() == 0 && s.last_used.elapsed() >= timeout);
+        state.subsessions = kept;
+
+        let mut reaped = 0;
+        for subsession in idle {
+            let Some(primary) = state.primary.as_mut() else {
+                break;
+            };
+            match primary.control.remove_subsession(&subsession.id) {
+                Ok(()) => reaped += 1,
+                Err(e) if is_session_lost(&e) => {
+                    // The whole session is gone; rebuild on next use
+                    Self::reset(state);
+                    break;
+                }
+                Err(e) => {
+                    log::warn!("Failed to remove SAM subsession {}: {}", subsession.id, e);
+                    reaped += 1;
+                }
+            }
+        }
+
+        state.stats.subsessions_reaped += reaped as u64;
+        reaped
+    }
+
+    /// Drop the primary and its subsessions; streams already open keep working
+    /// until the router closes them
+    fn reset(state: &mut PoolState) {
+        state.primary = None;
+        state.subsessions.clear();
+    }
+}
+
+/// Whether an error means the primary session no longer exists
+fn is_session_lost(error: &SamError) -> bool {
+    match error {
+        SamError::ConnectionFailed(_) | SamError::IoError(_) => true,
+        SamError::Rejected { result, .. } => *result == SamResult::InvalidId,
+        SamError::ProtocolError(_)
+        | SamError::SessionError(_)
+        | SamError::InvalidDestination(_)
+        | SamError::DatagramTooLarge { .. } => false,
+    }
+}
+
+/// A stream opened through a [`SamSessionPool`]
+///
+/// Dropping it closes the stream and releases its subsession slot.
+pub struct PooledStream {
+    stream: TcpStream,
+    active: Arc<AtomicUsize>,
+}
+
+impl PooledStream {
+    /// The underlying socket, e.g. to set timeouts
+    pub fn get_ref(&self) -> &TcpStream {
+        &self.stream
+    }
+}
+
+impl Read for PooledStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.stream.read(buf)
+    }
+}
+
+impl Write for PooledStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+impl Drop for PooledStream {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader};
+    use std::net::{Shutdown, TcpListener};
+    use std::thread;
+
+    #[derive(Default)]
+    struct MockState {
+        primaries: usize,
+        adds: usize,
+        removes: usize,
+        /// Live subsession ID -> owning primary ID
+        subsessions: HashMap<String, String>,
+        /// Control sockets of live primaries
+        controls: Vec<TcpStream>,
+    }
+
+    /// Minimal SAM v3.3 bridge: PRIMARY sessions, subsessions and echoing
+    /// STREAM CONNECTs
+    struct MockSam {
+        addr: String,
+        state: Arc<Mutex<MockState>>,
+    }
+
+    impl MockSam {
+        fn start() -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap().to_string();
+            let state = Arc::new(Mutex::new(MockState::default()));
+
+            let server_state = Arc::clone(&state);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(stream) = stream else { break };
+                    let state = Arc::clone(&server_state);
+                    thread::spawn(move || handle_client(stream, state));
+                }
+            });
+
+            Self { addr, state }
+        }
+
+        /// Simulate a router restart: all sessions are forgotten
+        fn restart(&self) {
+            let mut state = self.state.lock().unwrap();
+            for control in state.controls.drain(..) {
+                let _ = control.shutdown(Shutdown::Both);
+            }
+            state.subsessions.clear();
+        }
+
+        fn primaries(&self) -> usize {
+            self.state.lock().unwrap().primaries
+        }
+
+        fn adds(&self) -> usize {
+            self.state.lock().unwrap().adds
+        }
+
+        fn removes(&self) -> usize {
+            self.state.lock().unwrap().removes
+        }
+    }
+
+    fn value<'a>(line: &'a str, key: &str) -> &'a str {
+        line.split_whitespace()
+            .find_map(|part| part.strip_prefix(key))
+            .unwrap_or("")
+    }
+
+    fn handle_client(stream: TcpStream, state: Arc<Mutex<MockState>>) {
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut primary: Option<String> = None;
+
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap_or(0) > 0 {
+            let reply = if line.starts_with("HELLO") {
+                "HELLO REPLY RESULT=OK VERSION=3.3".to_string()
+            } else if line.starts_with("SESSION CREATE") {
+                let mut state = state.lock().unwrap();
+                state.primaries += 1;
+                state.controls.push(stream.try_clone().unwrap());
+                primary = Some(value(&line, "ID=").to_string());
+                "SESSION STATUS RESULT=OK DESTINATION=mockdest".to_string()
+            } else if line.starts_with("SESSION ADD") {
+                let mut state = state.lock().unwrap();
+                state.adds += 1;
+                let owner = primary.clone().unwrap_or_default();
+                state
+                    .subsessions
+                    .insert(value(&line, "ID=").to_string(), owner);
+                "SESSION STATUS RESULT=OK".to_string()
+            } else if line.starts_with("SESSION REMOVE") {
+                let mut state = state.lock().unwrap();
+                state.removes += 1;
+                state.subsessions.remove(value(&line, "ID="));
+                "SESSION STATUS RESULT=OK".to_string()
+            } else if line.starts_with("STREAM CONNECT") {
+                let known = state
+                    .lock()
+                    .unwrap()
+                    .subsessions
+                    .contains_key(value(&line, "ID="));
+                if !known {
+                    let _ = writer.write_all(b"STREAM STATUS RESULT=INVALID_ID\n");
+                    return;
+                }
+                let _ = writer.write_all(b"STREAM STATUS RESULT=OK\n");
+                // Echo everything sent over the stream
+                let mut buf = [0u8; 1024];
+                while let Ok(n @ 1..) = reader.read(&mut buf) {
+                    if writer.write_all(&buf[..n]).is_err() {
+                        break;
+                    }
+                }
+                return;
+            } else {
+                "ERROR".to_string()
+            };
+
+            if writer.write_all(format!("{}\n", reply).as_bytes()).is_err() {
+                break;
+            }
+            line.clear();
+        }
+
+        // Closing the control socket ends the primary and its subsessions
+        if let Some(primary) = primary {
+            state
+                .lock()
+                .unwrap()
+                .subsessions
+                .retain(|_, owner| *owner != primary);
+        }
+    }
+
+    fn pool(server: &MockSam, max_subsessions: usize, idle_timeout: Duration) -> SamSessionPool {
+        SamSessionPool::new(SamPoolConfig {
+            sam_addr: server.addr.clone(),
+            max_subsessions,
+            idle_timeout,
+            ..Default::default()
+        })
+    }
+
+    fn assert_echo(stream: &mut PooledStream, data: &[u8]) {
+        stream.write_all(data).unwrap();
+        let mut buf = vec![0u8; data.len()];
+        stream.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, data);
+    }
+
+    #[test]
+    fn test_streams_reuse_primary_session() {
+        let server = MockSam::start();
+        let pool = pool(&server, 4, Duration::from_secs(300));
+
+        let mut streams: Vec<_> = (0..3)
+            .map(|_| pool.open_stream("peer.b32.i2p").unwrap())
+            .collect();
+        for (i, stream) in streams.iter_mut().enumerate() {
+            assert_echo(stream, format!("stream {}", i).as_bytes());
+        }
+        assert_eq!(server.primaries(), 1);
+        assert_eq!(server.adds(), 3);
+        assert_eq!(pool.destination().unwrap().as_str(), "mockdest");
+
+        // Idle subsessions are reused rather than added
+        drop(streams);
+        let mut stream = pool.open_stream("other.b32.i2p").unwrap();
+        assert_echo(&mut stream, b"again");
+        assert_eq!(server.primaries(), 1);
+        assert_eq!(server.adds(), 3);
+        assert_eq!(pool.stats().streams_opened, 4);
+    }
+
+    #[test]
+    fn test_subsessions_bounded() {
+        let server = MockSam::start();
+        let pool = pool(&server, 2, Duration::from_secs(300));
+
+        let mut streams: Vec<_> = (0..5)
+            .map(|_| pool.open_stream("peer.b32.i2p").unwrap())
+            .collect();
+        assert_echo(&mut streams[4], b"shared");
+
+        assert_eq!(pool.subsession_count(), 2);
+        assert_eq!(server.adds(), 2);
+        assert_eq!(server.primaries(), 1);
+    }
+
+    #[test]
+    fn test_idle_subsessions_reaped() {
+        let server = MockSam::start();
+        let pool = pool(&server, 4, Duration::ZERO);
+
+        let busy = pool.open_stream("peer.b32.i2p").unwrap();
+        let idle = pool.open_stream("peer.b32.i2p").unwrap();
+        drop(idle);
+
+        // Only the subsession without open streams goes
+        assert_eq!(pool.reap_idle(), 1);
+        assert_eq!(server.removes(), 1);
+        assert_eq!(pool.subsession_count(), 1);
+
+        drop(busy);
+        assert_eq!(pool.reap_idle(), 1);
+        assert_eq!(pool.subsession_count(), 0);
+        assert_eq!(pool.stats().subsessions_reaped, 2);
+    }
+
+    #[test]
+    fn test_dropped_session_recreated() {
+        let server = MockSam::start();
+        let pool = pool(&server, 4, Duration::from_secs(300));
+
+        let mut stream = pool.open_stream("peer.b32.i2p").unwrap();
+        assert_echo(&mut stream, b"before");
+        drop(stream);
+
+        server.restart();
+
+        let mut stream = pool.open_stream("peer.b32.i2p").unwrap();
+        assert_echo(&mut stream, b"after");
+
+        assert_eq!(server.primaries(), 2);
+        let stats = pool.stats();
+        assert_eq!(stats.sessions_created, 2);
+        assert_eq!(stats.session_recoveries, 1);
+        assert_eq!(pool.subsession_count(), 1);
+    }
+}
@@ -0,0 +1,411 @@
+//! Health checking and automatic restart for a managed i2p router
+//!
+//! [`RouterSupervisor`] periodically probes the router and restarts it when
+//! the probe fails. A restarted router gets a startup grace period to come
+//! up before it is judged again. Consecutive restarts back off
+//! exponentially, and the supervisor gives up once too many restarts happen
+//! within a window so a router that cannot stay up does not restart forever.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use super::embedded_router::{I2pRouterError, Result};
+
+/// Process control used by the supervisor
+///
+/// Both methods may block (probing a socket, killing and reaping a process),
+/// so the supervisor calls them on the blocking thread pool.
+pub trait RouterController: Send + 'static {
+    /// Whether the router process is running and answering
+    fn is_healthy(&mut self) -> bool;
+
+    /// Stop whatever is left of the router and start it again
+    fn restart(&mut self) -> Result<()>;
+}
+
+/// Supervisor timing and restart limits
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// Interval between health checks while the router is healthy
+    pub check_interval: Duration,
+    /// How long a restarted router has to become healthy before the
+    /// restart counts as failed
+    pub startup_grace: Duration,
+    /// Interval between readiness checks during the startup grace period
+    pub startup_poll: Duration,
+    /// Delay before the second restart in a row
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between consecutive restarts
+    pub max_backoff: Duration,
+    /// Restarts allowed within `restart_window` before giving up
+    pub max_restarts: usize,
+    /// Window over which restarts are counted
+    pub restart_window: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            check_interval: Duration::from_secs(10),
+            startup_grace: Duration::from_secs(60),
+            startup_poll: Duration::from_millis(500),
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(300),
+            max_restarts: 10,
+            restart_window: Duration::from_secs(3600),
+        }
+    }
+}
+
+impl SupervisorConfig {
+    /// Delay before the restart following `failures` consecutive failures
+    fn backoff_for(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Router health as seen by the supervisor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RouterHealth {
+    /// Last health check passed
+    #[default]
+    Healthy,
+    /// Health checks are failing and the router is being restarted
+    Restarting,
+    /// Restart limit reached; the supervisor has stopped
+    Failed,
+}
+
+/// Supervisor status snapshot
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouterStatus {
+    /// Current health
+    pub health: RouterHealth,
+    /// Restarts performed since the supervisor started
+    pub restarts: u64,
+    /// Failed health checks since the last passing one
+    pub consecutive_failures: u32,
+    /// Most recent restart or supervision error
+    pub last_error: Option<String>,
+}
+
+/// Watches a router and restarts it when it stops responding
+///
+/// Dropping the supervisor stops the background task, which then drops the
+/// controller (stopping an embedded router).
+pub struct RouterSupervisor<C: RouterController> {
+    controller: Arc<Mutex<C>>,
+    status: Arc<RwLock<RouterStatus>>,
+    config: SupervisorConfig,
+    shutdown_tx: broadcast::Sender<()>,
+}
+
+impl<C: RouterController> std::fmt::Debug for RouterSupervisor<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouterSupervisor")
+            .field("config", &self.config)
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+impl<C: RouterController> RouterSupervisor<C> {
+    /// Create a supervisor for a router that is already running
+    pub fn new(controller: C, config: SupervisorConfig) -> Self {
+        Self {
+            controller: Arc::new(Mutex::new(controller)),
+            status: Arc::new(RwLock::new(RouterStatus::default())),
+            config,
+            shutdown_tx: broadcast::channel(1).0,
+        }
+    }
+
+    /// Start supervising in a background task
+    ///
+    /// The task ends with `RestartLimitExceeded` if the router keeps failing,
+    /// or `Ok` once the supervisor is shut down.
+    pub fn spawn(&self) -> JoinHandle<Result<()>> {
+        tokio::spawn(supervise(
+            Arc::clone(&self.controller),
+            Arc::clone(&self.status),
+            self.config.clone(),
+            self.shutdown_tx.subscribe(),
+        ))
+    }
+
+    /// Current status
+    pub fn status(&self) -> RouterStatus {
+        self.status
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Stop supervising; the router is left as it is
+    pub fn shutdown(&self) {
+        let _ = self.shutdown_tx.send(());
+    }
+}
+
+impl<C: RouterController> Drop for RouterSupervisor<C> {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Supervision loop: check, restart with backoff, give up past the limit
+async fn supervise<C: RouterController>(
+    controller: Arc<Mutex<C>>,
+    status: Arc<RwLock<RouterStatus>>,
+    config: SupervisorConfig,
+    mut shutdown: broadcast::Receiver<()>,
+) -> Result<()> {
+    let mut restart_times: VecDeque<Instant> = VecDeque::new();
+    let mut failures = 0u32;
+    let mut delay = config.check_interval;
+    // Deadline for a restarted router to come up
+    let mut starting_until: Option<Instant> = None;
+
+    loop {
+        tokio::select! {
+            _ = shutdown.recv() => return Ok(()),
+            _ = tokio::time::sleep(delay) => {}
+        }
+
+        if blocking(&controller, |c| c.is_healthy()).await? {
+            if failures > 0 {
+                log::info!("i2p router healthy again after {} failures", failures);
+            }
+            failures = 0;
+            starting_until = None;
+            delay = config.check_interval;
+            update_status(&status, |s| {
+                s.health = RouterHealth::Healthy;
+                s.consecutive_failures = 0;
+            });
+            continue;
+        }
+
+        // A restarted router isn't judged until its grace period is over
+        let now = Instant::now();
+        if let Some(deadline) = starting_until {
+            if now < deadline {
+                delay = config.startup_poll.min(deadline - now);
+                continue;
+            }
+        }
+
+        failures += 1;
+
+        // Back off before restarting a router that failed to come up
+        if failures > 1 {
+            tokio::select! {
+                _ = shutdown.recv() => return Ok(()),
+                _ = tokio::time::sleep(config.backoff_for(failures - 1)) => {}
+            }
+        }
+
+        // Bound restart storms: only restarts inside the window count
+        let now = Instant::now();
+        while restart_times
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= config.restart_window)
+        {
+            restart_times.pop_front();
+        }
+        if restart_times.len() >= config.max_restarts {
+            let error = I2pRouterError::RestartLimitExceeded {
+                restarts: restart_times.len(),
+                window: config.restart_window,
+            };
+            log::error!("i2p router supervisor stopping: {}", error);
+            let message = error.to_string();
+            update_status(&status, |s| {
+                s.health = RouterHealth::Failed;
+                s.consecutive_failures = failures;
+                s.last_error = Some(message);
+            });
+            return Err(error);
+        }
+
+        log::warn!(
+            "i2p router health check failed ({} in a row), restarting",
+            failures
+        );
+        let result = blocking(&controller, |c| c.restart()).await?;
+        restart_times.push_back(now);
+        starting_until = Some(Instant::now() + config.startup_grace);
+        delay = config.startup_poll.min(config.startup_grace);
+
+        let error = result.err().map(|e| e.to_string());
+        if let Some(e) = &error {
+            log::error!("Failed to restart i2p router: {}", e);
+        }
+        update_status(&status, |s| {
+            s.health = RouterHealth::Restarting;
+            s.restarts += 1;
+            s.consecutive_failures = failures;
+            if error.is_some() {
+                s.last_error = error;
+            }
+        });
+    }
+}
+
+/// Run a controller call on the blocking thread pool
+async fn blocking<C: RouterController, T: Send + 'static>(
+    controller: &Arc<Mutex<C>>,
+    f: impl FnOnce(&mut C) -> T + Send + 'static,
+) -> Result<T> {
+    let controller = Arc::clone(controller);
+    tokio::task::spawn_blocking(move || {
+        f(&mut controller.lock().unwrap_or_else(|e| e.into_inner()))
+    })
+    .await
+    .map_err(|e| I2pRouterError::StartupFailed(format!("supervisor task failed: {}", e)))
+}
+
+fn update_status(status: &RwLock<RouterStatus>, f: impl FnOnce(&mut RouterStatus)) {
+    f(&mut status.write().unwrap_or_else(|e| e.into_inner()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Controller whose health is set by the test
+    #[derive(Default)]
+    struct MockState {
+        healthy: AtomicBool,
+        /// Whether a restart brings the router back
+        restart_heals: AtomicBool,
+        restarts: Mutex<Vec<Instant>>,
+    }
+
+    struct MockController(Arc<MockState>);
+
+    impl RouterController for MockController {
+        fn is_healthy(&mut self) -> bool {
+            self.0.healthy.load(Ordering::SeqCst)
+        }
+
+        fn restart(&mut self) -> Result<()> {
+            self.0.restarts.lock().unwrap().push(Instant::now());
+            if self.0.restart_heals.load(Ordering::SeqCst) {
+                self.0.healthy.store(true, Ordering::SeqCst);
+            }
+            Ok(())
+        }
+    }
+
+    fn mock(restart_heals: bool) -> (MockController, Arc<MockState>) {
+        let state = Arc::new(MockState {
+            healthy: AtomicBool::new(true),
+            restart_heals: AtomicBool::new(restart_heals),
+            restarts: Mutex::new(Vec::new()),
+        });
+        (MockController(Arc::clone(&state)), state)
+    }
+
+    fn test_config() -> SupervisorConfig {
+        SupervisorConfig {
+            check_interval: Duration::from_secs(10),
+            startup_grace: Duration::from_secs(5),
+            startup_poll: Duration::from_secs(1),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(4),
+            max_restarts: 5,
+            restart_window: Duration::from_secs(3600),
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let config = test_config();
+        assert_eq!(config.backoff_for(1), Duration::from_secs(1));
+        assert_eq!(config.backoff_for(2), Duration::from_secs(2));
+        assert_eq!(config.backoff_for(3), Duration::from_secs(4));
+        assert_eq!(config.backoff_for(40), Duration::from_secs(4));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_crash_triggers_one_restart() {
+        let (controller, state) = mock(true);
+        let supervisor = RouterSupervisor::new(controller, test_config());
+        let task = supervisor.spawn();
+
+        tokio::time::sleep(Duration::from_secs(25)).await;
+        assert!(state.restarts.lock().unwrap().is_empty());
+
+        // Simulated crash; the restart brings the router back
+        state.healthy.store(false, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_secs(120)).await;
+
+        assert_eq!(state.restarts.lock().unwrap().len(), 1);
+        let status = supervisor.status();
+        assert_eq!(status.health, RouterHealth::Healthy);
+        assert_eq!(status.restarts, 1);
+        assert_eq!(status.consecutive_failures, 0);
+
+        supervisor.shutdown();
+        assert!(task.await.unwrap().is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_startup_within_grace_is_not_restarted() {
+        let (controller, state) = mock(false);
+        let supervisor = RouterSupervisor::new(controller, test_config());
+        let task = supervisor.spawn();
+
+        // Crash; the restart at 10s takes 4s to come up
+        state.healthy.store(false, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_secs(14)).await;
+        assert_eq!(state.restarts.lock().unwrap().len(), 1);
+        assert_eq!(supervisor.status().health, RouterHealth::Restarting);
+        state.healthy.store(true, Ordering::SeqCst);
+
+        tokio::time::sleep(Duration::from_secs(120)).await;
+        assert_eq!(state.restarts.lock().unwrap().len(), 1);
+        assert_eq!(supervisor.status().health, RouterHealth::Healthy);
+
+        supervisor.shutdown();
+        assert!(task.await.unwrap().is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_repeated_crashes_back_off_and_give_up() {
+        let (controller, state) = mock(false);
+        state.healthy.store(false, Ordering::SeqCst);
+        let start = Instant::now();
+        let supervisor = RouterSupervisor::new(controller, test_config());
+
+        let result = supervisor.spawn().await.unwrap();
+        assert!(matches!(
+            result,
+            Err(I2pRouterError::RestartLimitExceeded { restarts: 5, .. })
+        ));
+
+        // First restart after one check interval; each later one after the
+        // 5s startup grace plus a backoff of 1s, 2s, 4s, 4s
+        let restarts = state.restarts.lock().unwrap();
+        let offsets: Vec<u64> = restarts
+            .iter()
+            .map(|t| t.duration_since(start).as_secs())
+            .collect();
+        assert_eq!(offsets, vec![10, 16, 23, 32, 41]);
+
+        let status = supervisor.status();
+        assert_eq!(status.health, RouterHealth::Failed);
+        assert_eq!(status.restarts, 5);
+        assert!(status.last_error.is_some());
+    }
+}
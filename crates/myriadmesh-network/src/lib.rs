@@ -2,11 +2,12 @@
 //!
 //! This module provides a unified interface for multiple network transport types:
 //! - Ethernet/IP (UDP)
+//! - WebSocket (ws:// and wss://)
 //! - Bluetooth (Classic and LE)
 //! - Cellular (4G/5G)
 //! - LoRaWAN
 //! - Radio (APRS, CB, Shortwave, FRS/GMRS)
-//! - Overlay networks (i2p)
+//! - Overlay networks (i2p, Tor)
 //!
 //! ## Adaptive Security
 //!
@@ -26,31 +27,34 @@ pub mod metrics;
 pub mod plugin;
 pub mod reload;
 pub mod types;
+pub mod update_coordinator;
 pub mod version_tracking;
 
 pub use adapter::{AdapterStatus, NetworkAdapter};
 pub use adapters::{
     BleAdapter, BleConfig, BluetoothAdapter, BluetoothConfig, CellularAdapter, CellularConfig,
-    EthernetAdapter, EthernetConfig, NetworkType,
+    CellularStatus, EthernetAdapter, EthernetConfig, LoopbackAdapter, LoopbackConfig, NetworkType,
+    TorAdapter, TorConfig, WebSocketAdapter, WebSocketConfig,
 };
-pub use error::{NetworkError, Result};
+pub use error::{NetworkError, NetworkErrorKind, Result};
 pub use i2p::{I2pAdapter, I2pRouterConfig};
 pub use license::{AmateurClass, FccClient, LicenseClass, LicenseManager, LicenseState};
-pub use manager::AdapterManager;
-pub use metrics::AdapterMetrics;
+pub use manager::{AdapterHealth, AdapterManager, QuarantineConfig};
+pub use metrics::{AdapterMetrics, LatencyHistogram, LatencyWindow, LATENCY_BUCKETS_MS};
 pub use plugin::{
     AdapterPlugin, ApplicationPlugin, BridgePlugin, ComponentType, HttpMethod, MessageHandler,
     MyriadMeshPlugin, PluginConfig, PluginDependency, PluginRegistry, RestEndpoint, UiComponent,
 };
 pub use reload::{
-    AdapterHealthMonitor, AdapterLoadStatus, AdapterMetadata, AdapterRegistry,
-    DegradationThresholds, HealthMetrics, HistoricalVersion, RollbackHistory,
-    RollbackHistoryConfig,
+    AdapterHandle, AdapterHealthMonitor, AdapterLoadStatus, AdapterLoader, AdapterMetadata,
+    AdapterRegistry, DegradationThresholds, DetectionMode, HealthMetrics, HistoricalVersion,
+    RollbackHistory, RollbackHistoryConfig,
 };
-pub use types::{AdapterCapabilities, Address, PowerConsumption};
+pub use types::{AdapterCapabilities, Address, Feature, FeatureFlags, PowerConsumption};
+pub use update_coordinator::{ReloadWindow, UpdateCoordinator, UpdateCoordinatorConfig};
 pub use version_tracking::{
-    calculate_version_penalty, AdapterComponentStatus, AdapterVersionInfo, ComponentManifest,
-    CveInfo, CveSeverity, SemanticVersion,
+    calculate_version_penalty, reports_version_penalty, AdapterComponentStatus, AdapterVersionInfo,
+    ComponentManifest, CveInfo, CveSeverity, SemanticVersion,
 };
 
 #[cfg(test)]
@@ -3,26 +3,95 @@
 use crate::adapter::{AdapterStatus, NetworkAdapter};
 use crate::error::{NetworkError, Result};
 use crate::metrics::AdapterMetrics;
-use crate::types::AdapterCapabilities;
+use crate::types::{AdapterCapabilities, Address, FeatureFlags};
 use myriadmesh_protocol::types::AdapterType;
 use myriadmesh_protocol::Frame;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, RwLock};
 
 /// Unique identifier for an adapter instance
 pub type AdapterId = String;
 
+/// Adapter handle shared between the manager and its callers
+pub type SharedAdapter = Arc<RwLock<Box<dyn NetworkAdapter>>>;
+
+/// How long each `receive` call waits before re-checking for shutdown.
+/// Also bounds how long the adapter's read lock is held per call.
+const RECEIVE_POLL_MS: u64 = 100;
+
+/// Pause after a receive error so a failing adapter does not spin
+const RECEIVE_ERROR_BACKOFF_MS: u64 = 50;
+
+/// Default consecutive send failures before an adapter is quarantined
+pub const DEFAULT_QUARANTINE_THRESHOLD: u32 = 5;
+
+/// Default time between re-probes of a quarantined adapter
+pub const DEFAULT_REPROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// When failing adapters are taken out of selection
+#[derive(Debug, Clone)]
+pub struct QuarantineConfig {
+    /// Consecutive send failures that quarantine an adapter (0 disables)
+    pub failure_threshold: u32,
+    /// Minimum time between re-probes of a quarantined adapter
+    pub reprobe_interval: Duration,
+}
+
+impl Default for QuarantineConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_QUARANTINE_THRESHOLD,
+            reprobe_interval: DEFAULT_REPROBE_INTERVAL,
+        }
+    }
+}
+
+/// Quarantine state of one adapter
+///
+/// The failure streak itself is `AdapterMetrics::consecutive_failures`.
+#[derive(Debug, Clone, Default)]
+pub struct AdapterHealth {
+    /// When the adapter was quarantined (None while in service)
+    pub quarantined_since: Option<Instant>,
+    /// Last re-probe while quarantined
+    pub last_probe: Option<Instant>,
+    /// Destination of the last failed send, used as the re-probe target
+    last_failed_destination: Option<Address>,
+}
+
+impl AdapterHealth {
+    /// Check if the adapter is excluded from selection
+    pub fn is_quarantined(&self) -> bool {
+        self.quarantined_since.is_some()
+    }
+
+    /// Whether a quarantined adapter is due for another probe
+    fn probe_due(&self, interval: Duration, now: Instant) -> bool {
+        match self.last_probe.or(self.quarantined_since) {
+            Some(since) => now.duration_since(since) >= interval,
+            None => false,
+        }
+    }
+}
+
 /// Adapter manager for managing multiple network adapters
 pub struct AdapterManager {
     /// Registered adapters
-    adapters: HashMap<AdapterId, Arc<RwLock<Box<dyn NetworkAdapter>>>>,
+    adapters: HashMap<AdapterId, SharedAdapter>,
 
     /// Adapter performance metrics
     metrics: HashMap<AdapterId, AdapterMetrics>,
 
     /// Adapter capabilities cache
     capabilities: HashMap<AdapterId, AdapterCapabilities>,
+
+    /// Send failure streaks and quarantine state
+    health: HashMap<AdapterId, AdapterHealth>,
+
+    /// Quarantine thresholds
+    quarantine: QuarantineConfig,
 }
 
 impl AdapterManager {
@@ -32,9 +101,16 @@ impl AdapterManager {
             adapters: HashMap::new(),
             metrics: HashMap::new(),
             capabilities: HashMap::new(),
+            health: HashMap::new(),
+            quarantine: QuarantineConfig::default(),
         }
     }
 
+    /// Change when failing adapters are quarantined
+    pub fn set_quarantine_config(&mut self, config: QuarantineConfig) {
+        self.quarantine = config;
+    }
+
     /// Register a new adapter
     pub async fn register_adapter(
         &mut self,
@@ -63,6 +139,7 @@ impl AdapterManager {
 
         // Initialize metrics
         self.metrics.insert(id.clone(), AdapterMetrics::new());
+        self.health.insert(id.clone(), AdapterHealth::default());
 
         // Store adapter
         self.adapters.insert(id, Arc::new(RwLock::new(adapter)));
@@ -77,6 +154,7 @@ impl AdapterManager {
             adapter.stop().await?;
             self.metrics.remove(id);
             self.capabilities.remove(id);
+            self.health.remove(id);
             Ok(())
         } else {
             Err(NetworkError::AdapterNotFound(id.to_string()))
@@ -84,7 +162,7 @@ impl AdapterManager {
     }
 
     /// Get adapter by ID
-    pub fn get_adapter(&self, id: &str) -> Option<Arc<RwLock<Box<dyn NetworkAdapter>>>> {
+    pub fn get_adapter(&self, id: &str) -> Option<SharedAdapter> {
         self.adapters.get(id).cloned()
     }
 
@@ -103,6 +181,158 @@ impl AdapterManager {
         self.metrics.get_mut(id)
     }
 
+    /// Get adapter send health
+    pub fn get_health(&self, id: &str) -> Option<&AdapterHealth> {
+        self.health.get(id)
+    }
+
+    /// Check if an adapter is quarantined
+    pub fn is_quarantined(&self, id: &str) -> bool {
+        self.health
+            .get(id)
+            .is_some_and(AdapterHealth::is_quarantined)
+    }
+
+    /// IDs of quarantined adapters
+    pub fn quarantined_adapters(&self) -> Vec<AdapterId> {
+        self.health
+            .iter()
+            .filter(|(_, health)| health.is_quarantined())
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Record a successful send of `bytes` taking `latency`
+    ///
+    /// Updates the adapter's metrics, which clears its failure streak.
+    pub fn record_send_success(&mut self, id: &str, bytes: usize, latency: Duration) {
+        if let Some(metrics) = self.metrics.get_mut(id) {
+            metrics.record_send(bytes, latency);
+        }
+        if let Some(health) = self.health.get_mut(id) {
+            *health = AdapterHealth::default();
+        }
+    }
+
+    /// Record a failed send to `destination`
+    ///
+    /// Once the adapter's consecutive failures reach the quarantine
+    /// threshold it is excluded from selection until a re-probe succeeds.
+    /// Returns whether the adapter is quarantined.
+    pub fn record_send_failure(&mut self, id: &str, destination: &Address) -> bool {
+        let threshold = u64::from(self.quarantine.failure_threshold);
+        let (Some(metrics), Some(health)) = (self.metrics.get_mut(id), self.health.get_mut(id))
+        else {
+            return false;
+        };
+
+        metrics.record_send_failure();
+        health.last_failed_destination = Some(destination.clone());
+        if threshold > 0 && metrics.consecutive_failures >= threshold && !health.is_quarantined() {
+            health.quarantined_since = Some(Instant::now());
+        }
+        health.is_quarantined()
+    }
+
+    /// Quarantined adapters due for a re-probe, with their probe targets
+    ///
+    /// Marks each as probed now, so concurrent callers do not probe the
+    /// same adapter twice. Probe them without holding the manager lock and
+    /// report each outcome through [`AdapterManager::record_reprobe`].
+    pub fn take_due_reprobes(&mut self) -> Vec<(AdapterId, SharedAdapter, Address)> {
+        let now = Instant::now();
+        let interval = self.quarantine.reprobe_interval;
+        let mut due = Vec::new();
+        for (id, health) in &mut self.health {
+            if !health.is_quarantined() || !health.probe_due(interval, now) {
+                continue;
+            }
+            let (Some(destination), Some(adapter)) = (
+                health.last_failed_destination.clone(),
+                self.adapters.get(id),
+            ) else {
+                continue;
+            };
+            health.last_probe = Some(now);
+            due.push((id.clone(), Arc::clone(adapter), destination));
+        }
+        due
+    }
+
+    /// Record the outcome of a re-probe; a success reinstates the adapter
+    ///
+    /// Returns whether the adapter was reinstated.
+    pub fn record_reprobe(&mut self, id: &str, healthy: bool) -> bool {
+        if !healthy || !self.is_quarantined(id) {
+            return false;
+        }
+        if let Some(health) = self.health.get_mut(id) {
+            *health = AdapterHealth::default();
+        }
+        if let Some(metrics) = self.metrics.get_mut(id) {
+            metrics.consecutive_failures = 0;
+        }
+        true
+    }
+
+    /// Probe a quarantined adapter with `test_connection`
+    async fn reprobe(adapter: &RwLock<Box<dyn NetworkAdapter>>, destination: &Address) -> bool {
+        match adapter.read().await.test_connection(destination).await {
+            Ok(results) => results.success,
+            Err(_) => false,
+        }
+    }
+
+    /// Re-probe quarantined adapters whose probe interval has elapsed
+    ///
+    /// Each is probed with `test_connection` to the destination of its last
+    /// failed send and reinstated if the test succeeds. Returns the IDs of
+    /// reinstated adapters.
+    pub async fn reprobe_quarantined(&mut self) -> Vec<AdapterId> {
+        let mut reinstated = Vec::new();
+        for (id, adapter, destination) in self.take_due_reprobes() {
+            let healthy = Self::reprobe(&adapter, &destination).await;
+            if self.record_reprobe(&id, healthy) {
+                reinstated.push(id);
+            }
+        }
+        reinstated
+    }
+
+    /// Periodically re-probe quarantined adapters of a shared manager
+    ///
+    /// Probes run without holding the manager lock, so sends and selection
+    /// are not held up by a slow probe. Runs until `shutdown` fires (or its
+    /// sender is dropped).
+    pub async fn run_reprobe_loop(
+        manager: Arc<RwLock<AdapterManager>>,
+        mut shutdown: broadcast::Receiver<()>,
+    ) {
+        let period = manager
+            .read()
+            .await
+            .quarantine
+            .reprobe_interval
+            .max(Duration::from_secs(1));
+        let mut ticker = tokio::time::interval(period);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                _ = ticker.tick() => {}
+            }
+
+            let due = manager.write().await.take_due_reprobes();
+            for (id, adapter, destination) in due {
+                let healthy = Self::reprobe(&adapter, &destination).await;
+                if manager.write().await.record_reprobe(&id, healthy) {
+                    log::info!("Adapter {} passed its re-probe and is back in service", id);
+                }
+            }
+        }
+    }
+
     /// Get all adapter IDs
     pub fn adapter_ids(&self) -> Vec<AdapterId> {
         self.adapters.keys().cloned().collect()
@@ -120,6 +350,17 @@ impl AdapterManager {
 
     /// Select best adapter for sending a frame
     pub fn select_best_adapter(&self, frame: &Frame, priority: u8) -> Option<AdapterId> {
+        self.select_best_adapter_with_features(frame, priority, FeatureFlags::empty())
+    }
+
+    /// Select best adapter for sending a frame among those offering every
+    /// feature in `required`
+    pub fn select_best_adapter_with_features(
+        &self,
+        frame: &Frame,
+        priority: u8,
+        required: FeatureFlags,
+    ) -> Option<AdapterId> {
         if self.adapters.is_empty() {
             return None;
         }
@@ -128,12 +369,20 @@ impl AdapterManager {
         let mut best_score = f64::MIN;
 
         for id in self.adapters.keys() {
+            if self.is_quarantined(id) {
+                continue;
+            }
+
             if let Some(caps) = self.capabilities.get(id) {
                 // Check if adapter can handle message size
                 if frame.size() > caps.max_message_size {
                     continue;
                 }
 
+                if !caps.supports_all(required) {
+                    continue;
+                }
+
                 // Calculate score
                 let score = caps.calculate_score(frame.size(), priority);
 
@@ -152,10 +401,10 @@ impl AdapterManager {
         best_adapter
     }
 
-    /// Find adapter by type
+    /// Find adapter by type, skipping quarantined adapters
     pub fn find_adapter_by_type(&self, adapter_type: AdapterType) -> Option<AdapterId> {
         for (id, caps) in &self.capabilities {
-            if caps.adapter_type == adapter_type {
+            if caps.adapter_type == adapter_type && !self.is_quarantined(id) {
                 return Some(id.clone());
             }
         }
@@ -189,6 +438,79 @@ impl AdapterManager {
         statuses
     }
 
+    /// Drive `receive` on every registered adapter and forward frames to `sink`
+    ///
+    /// Each adapter gets its own task that waits for room in the bounded
+    /// `sink` before pulling its next frame, so a fast adapter cannot run
+    /// ahead of the consumer or buffer without limit. Waiting senders are
+    /// served in order, so a busy adapter does not starve the others.
+    ///
+    /// Runs until `shutdown` fires (or its sender is dropped) or the sink is
+    /// closed. Adapters registered after the loop starts are not picked up.
+    pub async fn run_receive_loop(
+        &self,
+        sink: mpsc::Sender<(Address, Frame)>,
+        shutdown: broadcast::Receiver<()>,
+    ) -> Result<()> {
+        if self.adapters.is_empty() {
+            return Err(NetworkError::NoAdaptersAvailable);
+        }
+
+        let tasks: Vec<_> = self
+            .adapters
+            .values()
+            .map(|adapter| {
+                let adapter = Arc::clone(adapter);
+                let sink = sink.clone();
+                let shutdown = shutdown.resubscribe();
+                tokio::spawn(Self::receive_from_adapter(adapter, sink, shutdown))
+            })
+            .collect();
+        drop(sink);
+
+        for task in futures::future::join_all(tasks).await {
+            task.map_err(|e| NetworkError::Other(format!("Receive task failed: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Receive loop for a single adapter
+    async fn receive_from_adapter(
+        adapter: SharedAdapter,
+        sink: mpsc::Sender<(Address, Frame)>,
+        mut shutdown: broadcast::Receiver<()>,
+    ) {
+        loop {
+            let received = tokio::select! {
+                _ = shutdown.recv() => break,
+                received = async { adapter.read().await.receive(RECEIVE_POLL_MS).await } => received,
+            };
+
+            match received {
+                Ok(item) => {
+                    // Backpressure: block here until the consumer makes room
+                    tokio::select! {
+                        _ = shutdown.recv() => break,
+                        sent = sink.send(item) => {
+                            if sent.is_err() {
+                                // Consumer went away
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(_) => {
+                    // Timeouts and transient errors: back off and poll again
+                    tokio::select! {
+                        _ = shutdown.recv() => break,
+                        _ = tokio::time::sleep(Duration::from_millis(RECEIVE_ERROR_BACKOFF_MS)) => {}
+                    }
+                }
+            }
+        }
+    }
+
     /// Stop all adapters
     pub async fn stop_all(&mut self) -> Result<()> {
         let ids: Vec<_> = self.adapters.keys().cloned().collect();
@@ -212,12 +534,20 @@ impl Default for AdapterManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::Address;
+    use myriadmesh_protocol::message::MessageId;
+    use myriadmesh_protocol::types::NODE_ID_SIZE;
+    use myriadmesh_protocol::{MessageType, NodeId};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
     // Mock adapter for testing
     struct MockAdapter {
         status: AdapterStatus,
         capabilities: AdapterCapabilities,
+        /// When set, `receive` immediately yields a frame from this source
+        frame_source: Option<u8>,
+        receive_count: Arc<AtomicUsize>,
+        /// Result reported by `test_connection`
+        probe_ok: Arc<AtomicBool>,
     }
 
     #[async_trait::async_trait]
@@ -241,7 +571,20 @@ mod tests {
         }
 
         async fn receive(&self, _timeout_ms: u64) -> Result<(Address, Frame)> {
-            Err(NetworkError::ReceiveFailed("Not implemented".to_string()))
+            let source = self
+                .frame_source
+                .ok_or_else(|| NetworkError::ReceiveFailed("Not implemented".to_string()))?;
+
+            self.receive_count.fetch_add(1, Ordering::SeqCst);
+            let frame = Frame::new(
+                MessageType::Data,
+                NodeId::from_bytes([source; NODE_ID_SIZE]),
+                NodeId::from_bytes([0; NODE_ID_SIZE]),
+                vec![source],
+                MessageId::from_bytes([source; 16]),
+                0,
+            )?;
+            Ok((Address::Unknown(format!("mock-{}", source)), frame))
         }
 
         async fn discover_peers(&self) -> Result<Vec<crate::adapter::PeerInfo>> {
@@ -261,7 +604,7 @@ mod tests {
             _destination: &Address,
         ) -> Result<crate::adapter::TestResults> {
             Ok(crate::adapter::TestResults {
-                success: true,
+                success: self.probe_ok.load(Ordering::SeqCst),
                 rtt_ms: Some(10.0),
                 error: None,
             })
@@ -281,7 +624,7 @@ mod tests {
     }
 
     fn create_mock_adapter() -> MockAdapter {
-        use crate::types::PowerConsumption;
+        use crate::types::{Feature, FeatureFlags, PowerConsumption};
 
         MockAdapter {
             status: AdapterStatus::Uninitialized,
@@ -294,9 +637,18 @@ mod tests {
                 range_meters: 100.0,
                 power_consumption: PowerConsumption::None,
                 cost_per_mb: 0.0,
-                supports_broadcast: true,
-                supports_multicast: true,
+                features: FeatureFlags::from_features(&[Feature::Broadcast, Feature::Multicast]),
             },
+            frame_source: None,
+            receive_count: Arc::new(AtomicUsize::new(0)),
+            probe_ok: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    fn create_receiving_adapter(source: u8) -> MockAdapter {
+        MockAdapter {
+            frame_source: Some(source),
+            ..create_mock_adapter()
         }
     }
 
@@ -342,4 +694,272 @@ mod tests {
         assert!(found.is_some());
         assert_eq!(found.unwrap(), "test");
     }
+
+    #[tokio::test]
+    async fn test_receive_loop_multiplexes_adapters() {
+        let mut manager = AdapterManager::new();
+        let first = create_receiving_adapter(1);
+        let second = create_receiving_adapter(2);
+        let counts = [first.receive_count.clone(), second.receive_count.clone()];
+
+        manager
+            .register_adapter("first".to_string(), Box::new(first))
+            .await
+            .unwrap();
+        manager
+            .register_adapter("second".to_string(), Box::new(second))
+            .await
+            .unwrap();
+
+        let manager = Arc::new(manager);
+        let (sink, mut frames) = mpsc::channel(4);
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+
+        let loop_manager = Arc::clone(&manager);
+        let handle =
+            tokio::spawn(async move { loop_manager.run_receive_loop(sink, shutdown_rx).await });
+
+        // Consumer is idle: both adapters must stall once the channel is full
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let pulled: usize = counts.iter().map(|c| c.load(Ordering::SeqCst)).sum();
+        assert!(
+            pulled <= 4 + 2,
+            "adapters ran ahead of a full channel: {}",
+            pulled
+        );
+
+        // Frames from both adapters arrive
+        let mut sources = std::collections::HashSet::new();
+        for _ in 0..32 {
+            let (_, frame) = frames.recv().await.unwrap();
+            sources.insert(frame.payload[0]);
+        }
+        assert!(sources.contains(&1));
+        assert!(sources.contains(&2));
+
+        // Loop stops on cancel
+        shutdown_tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("receive loop did not stop on cancel")
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_receive_loop_requires_adapters() {
+        let manager = AdapterManager::new();
+        let (sink, _frames) = mpsc::channel(1);
+        let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+
+        assert!(matches!(
+            manager.run_receive_loop(sink, shutdown_rx).await,
+            Err(NetworkError::NoAdaptersAvailable)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_select_best_adapter_by_features() {
+        use crate::types::Feature;
+
+        let mut manager = AdapterManager::new();
+        manager
+            .register_adapter("radio".to_string(), Box::new(create_mock_adapter()))
+            .await
+            .unwrap();
+
+        let mut reliable = create_mock_adapter();
+        reliable.capabilities.reliability = 0.5;
+        reliable.capabilities.features =
+            FeatureFlags::from_features(&[Feature::ReliableDelivery, Feature::OrderedDelivery]);
+        manager
+            .register_adapter("stream".to_string(), Box::new(reliable))
+            .await
+            .unwrap();
+
+        let frame = Frame::new(
+            MessageType::Data,
+            NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+            NodeId::from_bytes([2u8; NODE_ID_SIZE]),
+            vec![0u8; 64],
+            MessageId::from_bytes([3u8; 16]),
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manager.select_best_adapter(&frame, 128),
+            Some("radio".to_string())
+        );
+        assert_eq!(
+            manager.select_best_adapter_with_features(
+                &frame,
+                128,
+                FeatureFlags::from(Feature::ReliableDelivery)
+            ),
+            Some("stream".to_string())
+        );
+        assert_eq!(
+            manager.select_best_adapter_with_features(
+                &frame,
+                128,
+                FeatureFlags::from_features(&[Feature::ReliableDelivery, Feature::Broadcast])
+            ),
+            None
+        );
+    }
+
+    fn quarantine_after(failure_threshold: u32) -> QuarantineConfig {
+        QuarantineConfig {
+            failure_threshold,
+            reprobe_interval: Duration::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_adapter_quarantined() {
+        let mut manager = AdapterManager::new();
+        manager.set_quarantine_config(quarantine_after(3));
+        manager
+            .register_adapter("flaky".to_string(), Box::new(create_mock_adapter()))
+            .await
+            .unwrap();
+        let peer = Address::Unknown("peer".to_string());
+        let frame = Frame::new(
+            MessageType::Data,
+            NodeId::from_bytes([1; NODE_ID_SIZE]),
+            NodeId::from_bytes([2; NODE_ID_SIZE]),
+            vec![0; 16],
+            MessageId::from_bytes([1; 16]),
+            0,
+        )
+        .unwrap();
+
+        assert!(!manager.record_send_failure("flaky", &peer));
+        assert!(!manager.record_send_failure("flaky", &peer));
+        // A success breaks the streak
+        manager.record_send_success("flaky", 16, Duration::from_millis(5));
+        let metrics = manager.get_metrics("flaky").unwrap();
+        assert_eq!(metrics.consecutive_failures, 0);
+        assert_eq!(metrics.send_failures, 2);
+        assert_eq!(metrics.messages_sent, 1);
+
+        for _ in 0..2 {
+            assert!(!manager.record_send_failure("flaky", &peer));
+        }
+        assert!(manager.record_send_failure("flaky", &peer));
+
+        assert!(manager.is_quarantined("flaky"));
+        assert_eq!(manager.quarantined_adapters(), vec!["flaky".to_string()]);
+        assert_eq!(manager.select_best_adapter(&frame, 128), None);
+        assert_eq!(manager.find_adapter_by_type(AdapterType::Ethernet), None);
+        // Still registered, only out of selection
+        assert!(manager.get_adapter("flaky").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_successful_reprobe_reinstates_adapter() {
+        let mut manager = AdapterManager::new();
+        manager.set_quarantine_config(quarantine_after(2));
+        let adapter = create_mock_adapter();
+        let probe_ok = Arc::clone(&adapter.probe_ok);
+        probe_ok.store(false, Ordering::SeqCst);
+        manager
+            .register_adapter("flaky".to_string(), Box::new(adapter))
+            .await
+            .unwrap();
+
+        let peer = Address::Unknown("peer".to_string());
+        manager.record_send_failure("flaky", &peer);
+        assert!(manager.record_send_failure("flaky", &peer));
+
+        // Still failing: stays quarantined and the probe is recorded
+        assert!(manager.reprobe_quarantined().await.is_empty());
+        let health = manager.get_health("flaky").unwrap();
+        assert!(health.is_quarantined());
+        assert!(health.last_probe.is_some());
+
+        probe_ok.store(true, Ordering::SeqCst);
+        assert_eq!(
+            manager.reprobe_quarantined().await,
+            vec!["flaky".to_string()]
+        );
+        assert!(!manager.is_quarantined("flaky"));
+        assert_eq!(
+            manager.get_metrics("flaky").unwrap().consecutive_failures,
+            0
+        );
+        assert_eq!(
+            manager.find_adapter_by_type(AdapterType::Ethernet),
+            Some("flaky".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reprobe_waits_for_interval() {
+        let mut manager = AdapterManager::new();
+        manager.set_quarantine_config(QuarantineConfig {
+            failure_threshold: 1,
+            reprobe_interval: Duration::from_secs(3600),
+        });
+        manager
+            .register_adapter("flaky".to_string(), Box::new(create_mock_adapter()))
+            .await
+            .unwrap();
+
+        assert!(manager.record_send_failure("flaky", &Address::Unknown("peer".to_string())));
+        assert!(manager.reprobe_quarantined().await.is_empty());
+        assert!(manager.is_quarantined("flaky"));
+    }
+
+    #[tokio::test]
+    async fn test_zero_threshold_never_quarantines() {
+        let mut manager = AdapterManager::new();
+        manager.set_quarantine_config(quarantine_after(0));
+        manager
+            .register_adapter("flaky".to_string(), Box::new(create_mock_adapter()))
+            .await
+            .unwrap();
+
+        let peer = Address::Unknown("peer".to_string());
+        for _ in 0..100 {
+            assert!(!manager.record_send_failure("flaky", &peer));
+        }
+        assert_eq!(
+            manager.get_metrics("flaky").unwrap().consecutive_failures,
+            100
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reprobe_loop_reinstates_shared_manager() {
+        let mut manager = AdapterManager::new();
+        // Probes are due on every tick, which is at least a second apart
+        manager.set_quarantine_config(quarantine_after(1));
+        let adapter = create_mock_adapter();
+        let probe_ok = Arc::clone(&adapter.probe_ok);
+        probe_ok.store(false, Ordering::SeqCst);
+        manager
+            .register_adapter("flaky".to_string(), Box::new(adapter))
+            .await
+            .unwrap();
+        assert!(manager.record_send_failure("flaky", &Address::Unknown("peer".to_string())));
+
+        let manager = Arc::new(RwLock::new(manager));
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let task = tokio::spawn(AdapterManager::run_reprobe_loop(
+            Arc::clone(&manager),
+            shutdown_rx,
+        ));
+
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+        assert!(manager.read().await.is_quarantined("flaky"));
+
+        probe_ok.store(true, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        assert!(!manager.read().await.is_quarantined("flaky"));
+
+        shutdown_tx.send(()).unwrap();
+        task.await.unwrap();
+    }
 }
@@ -1,7 +1,126 @@
 //! Network adapter performance metrics
 
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+/// Number of recent latency samples kept for percentile queries
+pub const LATENCY_WINDOW: usize = 1024;
+
+/// Rolling window of the most recent latency samples
+///
+/// Bounded to `capacity` samples; the oldest sample is evicted when full, so
+/// percentiles reflect recent behaviour and memory stays constant.
+#[derive(Debug, Clone)]
+pub struct LatencyWindow {
+    samples: VecDeque<f64>,
+    capacity: usize,
+}
+
+impl LatencyWindow {
+    /// Create a window holding at most `capacity` samples
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Add a latency sample in milliseconds
+    pub fn record(&mut self, latency_ms: f64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency_ms);
+    }
+
+    /// Number of samples currently held
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether no samples have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Latency at quantile `q` (0.0-1.0) using the nearest-rank method
+    pub fn percentile(&self, q: f64) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let rank = (q.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1)])
+    }
+}
+
+impl Default for LatencyWindow {
+    fn default() -> Self {
+        Self::new(LATENCY_WINDOW)
+    }
+}
+
+/// Upper bounds in milliseconds of the send latency histogram buckets
+///
+/// Spans sub-millisecond Ethernet through multi-second HF and LoRa links.
+pub const LATENCY_BUCKETS_MS: [f64; 12] = [
+    1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0,
+];
+
+/// Lifetime send latency distribution over [`LATENCY_BUCKETS_MS`]
+///
+/// Unlike [`LatencyWindow`] this never forgets samples, which is what
+/// monitoring systems expect when computing rates over scrape intervals.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencyHistogram {
+    /// Samples per bucket (not cumulative); index `i` holds samples in
+    /// `(LATENCY_BUCKETS_MS[i - 1], LATENCY_BUCKETS_MS[i]]`
+    buckets: [u64; LATENCY_BUCKETS_MS.len()],
+    /// Total samples, including those above the last bound
+    count: u64,
+    /// Sum of all samples in milliseconds
+    sum_ms: f64,
+}
+
+impl LatencyHistogram {
+    /// Add a latency sample in milliseconds
+    pub fn record(&mut self, latency_ms: f64) {
+        if let Some(i) = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| latency_ms <= bound)
+        {
+            self.buckets[i] += 1;
+        }
+        self.count += 1;
+        self.sum_ms += latency_ms;
+    }
+
+    /// Samples at or below each bound in [`LATENCY_BUCKETS_MS`]
+    pub fn cumulative(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        LATENCY_BUCKETS_MS
+            .iter()
+            .zip(&self.buckets)
+            .scan(0, |total, (&bound, &n)| {
+                *total += n;
+                Some((bound, *total))
+            })
+    }
+
+    /// Total samples recorded
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Sum of all samples in milliseconds
+    pub fn sum_ms(&self) -> f64 {
+        self.sum_ms
+    }
+}
+
 /// Performance metrics for a network adapter
 #[derive(Debug, Clone)]
 pub struct AdapterMetrics {
@@ -29,6 +148,9 @@ pub struct AdapterMetrics {
     /// Total send failures
     pub send_failures: u64,
 
+    /// Send failures since the last successful send
+    pub consecutive_failures: u64,
+
     /// Total bytes sent
     pub bytes_sent: u64,
 
@@ -37,6 +159,12 @@ pub struct AdapterMetrics {
 
     /// Last update timestamp
     pub last_updated: Instant,
+
+    /// Recent latency samples for tail latency (p95/p99)
+    pub latency_window: LatencyWindow,
+
+    /// Send latency distribution since the adapter started
+    pub latency_histogram: LatencyHistogram,
 }
 
 impl AdapterMetrics {
@@ -51,15 +179,19 @@ impl AdapterMetrics {
             messages_sent: 0,
             messages_received: 0,
             send_failures: 0,
+            consecutive_failures: 0,
             bytes_sent: 0,
             bytes_received: 0,
             last_updated: Instant::now(),
+            latency_window: LatencyWindow::default(),
+            latency_histogram: LatencyHistogram::default(),
         }
     }
 
     /// Record a successful send
     pub fn record_send(&mut self, bytes: usize, latency: Duration) {
         self.messages_sent += 1;
+        self.consecutive_failures = 0;
         self.bytes_sent += bytes as u64;
 
         // Update moving average for latency
@@ -70,6 +202,8 @@ impl AdapterMetrics {
             // Exponential moving average (alpha = 0.2)
             self.latency_ms = self.latency_ms * 0.8 + new_latency * 0.2;
         }
+        self.latency_window.record(new_latency);
+        self.latency_histogram.record(new_latency);
 
         // Update bandwidth estimate
         let total_time = self.last_updated.elapsed().as_secs_f64();
@@ -88,6 +222,7 @@ impl AdapterMetrics {
     /// Record a failed send
     pub fn record_send_failure(&mut self) {
         self.send_failures += 1;
+        self.consecutive_failures += 1;
 
         // Update reliability
         let total_attempts = self.messages_sent + self.send_failures;
@@ -121,6 +256,13 @@ impl AdapterMetrics {
         }
     }
 
+    /// Send latency in milliseconds at quantile `q`, e.g. `percentile(0.99)`
+    ///
+    /// Computed over the last `LATENCY_WINDOW` sends; `None` before any send.
+    pub fn percentile(&self, q: f64) -> Option<f64> {
+        self.latency_window.percentile(q)
+    }
+
     /// Get send success rate
     pub fn send_success_rate(&self) -> f64 {
         let total = self.messages_sent + self.send_failures;
@@ -192,4 +334,62 @@ mod tests {
 
         assert_eq!(metrics.send_success_rate(), 2.0 / 3.0);
     }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let mut metrics = AdapterMetrics::new();
+        assert_eq!(metrics.percentile(0.95), None);
+
+        // 1..=1000ms in a scrambled order: p95 = 950ms, p99 = 990ms
+        for i in 0..1000u64 {
+            let ms = (i * 7919) % 1000 + 1;
+            metrics.record_send(100, Duration::from_millis(ms));
+        }
+
+        let p95 = metrics.percentile(0.95).unwrap();
+        let p99 = metrics.percentile(0.99).unwrap();
+        assert!((p95 - 950.0).abs() <= 5.0, "p95 = {}", p95);
+        assert!((p99 - 990.0).abs() <= 5.0, "p99 = {}", p99);
+        assert!(metrics.latency_ms > 0.0);
+    }
+
+    #[test]
+    fn test_latency_window_bounded_and_rolling() {
+        let mut metrics = AdapterMetrics::new();
+
+        // An old burst of slow sends followed by a long run of fast ones
+        for _ in 0..5_000 {
+            metrics.record_send(10, Duration::from_millis(500));
+        }
+        for i in 0..50_000u64 {
+            // 90% at 10ms, 10% at 100ms
+            let ms = if i % 10 == 0 { 100 } else { 10 };
+            metrics.record_send(10, Duration::from_millis(ms));
+        }
+
+        assert_eq!(metrics.latency_window.len(), LATENCY_WINDOW);
+        assert_eq!(metrics.percentile(0.5), Some(10.0));
+        assert_eq!(metrics.percentile(0.95), Some(100.0));
+        assert_eq!(metrics.percentile(0.99), Some(100.0));
+    }
+
+    #[test]
+    fn test_latency_histogram_buckets() {
+        let mut metrics = AdapterMetrics::new();
+        for ms in [1, 3, 40, 40, 20_000] {
+            metrics.record_send(10, Duration::from_millis(ms));
+        }
+
+        let histogram = &metrics.latency_histogram;
+        assert_eq!(histogram.count(), 5);
+        assert!((histogram.sum_ms() - 20_084.0).abs() < 1e-6);
+
+        let cumulative: Vec<(f64, u64)> = histogram.cumulative().collect();
+        assert_eq!(cumulative.len(), LATENCY_BUCKETS_MS.len());
+        assert_eq!(cumulative[0], (1.0, 1));
+        assert_eq!(cumulative[1], (5.0, 2));
+        assert_eq!(cumulative[4], (50.0, 4));
+        // The 20s sample only shows up in the overall count
+        assert_eq!(cumulative.last(), Some(&(10000.0, 4)));
+    }
 }
@@ -6,9 +6,11 @@
 
 use crate::adapter::NetworkAdapter;
 use crate::error::{NetworkError, Result};
+use crate::update_coordinator::UpdateCoordinator;
 use crate::version_tracking::SemanticVersion;
 use myriadmesh_protocol::types::AdapterType;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -123,27 +125,220 @@ pub struct DegradationThresholds {
 
     /// Minimum operations before evaluation
     pub min_operations: u64,
+
+    /// Only operations this recent are evaluated against the baseline
+    pub evaluation_window: Duration,
+
+    /// Upper bound on samples kept per adapter within the window
+    pub max_window_samples: usize,
+
+    /// Crashes this recent count towards degradation
+    ///
+    /// Kept longer than `evaluation_window` so an adapter that crashes only
+    /// occasionally is still caught.
+    pub crash_window: Duration,
+
+    /// How current behaviour is compared against the baseline
+    pub detection_mode: DetectionMode,
 }
 
 impl Default for DegradationThresholds {
     fn default() -> Self {
         Self {
-            max_success_rate_drop: 0.10,    // 10% drop triggers rollback
-            max_latency_increase: 0.50,     // 50% increase triggers rollback
-            max_error_rate_multiplier: 2.0, // 2x errors triggers rollback
-            crash_triggers_rollback: true,  // Any crash triggers rollback
-            min_operations: 10,             // Need at least 10 ops to evaluate
+            max_success_rate_drop: 0.10,                 // 10% drop triggers rollback
+            max_latency_increase: 0.50,                  // 50% increase triggers rollback
+            max_error_rate_multiplier: 2.0,              // 2x errors triggers rollback
+            crash_triggers_rollback: true,               // Any crash triggers rollback
+            min_operations: 10,                          // Need at least 10 ops to evaluate
+            evaluation_window: Duration::from_secs(300), // Last 5 minutes
+            max_window_samples: 10_000,
+            crash_window: Duration::from_secs(24 * 3600), // Last day
+            detection_mode: DetectionMode::Threshold,
         }
     }
 }
 
+/// Degradation detection strategy
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DetectionMode {
+    /// Compare raw rates over the evaluation window against the thresholds
+    #[default]
+    Threshold,
+
+    /// Compare exponentially weighted moving averages against the thresholds
+    ///
+    /// A deviation only counts once the average is also `z_threshold`
+    /// standard errors away from the baseline, so a short spike is absorbed
+    /// while sustained drift still trips. The error rate multiplier is not
+    /// used in this mode.
+    Ewma {
+        /// Smoothing factor in (0, 1]; higher reacts faster
+        alpha: f64,
+
+        /// Standard errors of deviation required to trigger
+        z_threshold: f64,
+    },
+}
+
+/// Exponentially weighted mean and variance of one signal
+#[derive(Debug, Clone, Copy)]
+struct EwmaStat {
+    mean: f64,
+    variance: f64,
+}
+
+impl EwmaStat {
+    fn update(stat: &mut Option<Self>, value: f64, alpha: f64) {
+        match stat {
+            Some(stat) => {
+                let diff = value - stat.mean;
+                let increment = alpha * diff;
+                stat.mean += increment;
+                stat.variance = (1.0 - alpha) * (stat.variance + diff * increment);
+            }
+            None => {
+                *stat = Some(Self {
+                    mean: value,
+                    variance: 0.0,
+                })
+            }
+        }
+    }
+
+    /// Deviation of the average from `reference`, in standard errors
+    fn z_score(&self, reference: f64, alpha: f64) -> f64 {
+        let deviation = self.mean - reference;
+        let std_error = (self.variance * alpha / (2.0 - alpha)).sqrt();
+        if std_error > 0.0 {
+            deviation / std_error
+        } else if deviation == 0.0 {
+            0.0
+        } else {
+            deviation.signum() * f64::INFINITY
+        }
+    }
+}
+
+/// Smoothed success rate and latency since monitoring started
+#[derive(Debug, Clone, Copy, Default)]
+struct EwmaHealth {
+    success: Option<EwmaStat>,
+    latency: Option<EwmaStat>,
+}
+
+/// A single observed operation outcome
+#[derive(Debug, Clone, Copy)]
+enum HealthSample {
+    Success { latency_ms: u64 },
+    Failure,
+    Crash,
+}
+
+/// Ring buffer of timestamped samples since monitoring started
+///
+/// Crash times are kept apart from operation samples and age out over the
+/// longer `crash_window`.
+#[derive(Debug)]
+struct HealthWindow {
+    started_at: tokio::time::Instant,
+    samples: VecDeque<(tokio::time::Instant, HealthSample)>,
+    crashes: VecDeque<tokio::time::Instant>,
+    ewma: EwmaHealth,
+}
+
+impl HealthWindow {
+    fn new() -> Self {
+        Self {
+            started_at: tokio::time::Instant::now(),
+            samples: VecDeque::new(),
+            crashes: VecDeque::new(),
+            ewma: EwmaHealth::default(),
+        }
+    }
+
+    fn record(&mut self, sample: HealthSample, thresholds: &DegradationThresholds) {
+        if let DetectionMode::Ewma { alpha, .. } = thresholds.detection_mode {
+            match sample {
+                HealthSample::Success { latency_ms } => {
+                    EwmaStat::update(&mut self.ewma.success, 1.0, alpha);
+                    EwmaStat::update(&mut self.ewma.latency, latency_ms as f64, alpha);
+                }
+                HealthSample::Failure | HealthSample::Crash => {
+                    EwmaStat::update(&mut self.ewma.success, 0.0, alpha);
+                }
+            }
+        }
+
+        let now = tokio::time::Instant::now();
+        if let HealthSample::Crash = sample {
+            self.crashes.push_back(now);
+        } else {
+            self.samples.push_back((now, sample));
+        }
+        self.prune(now, thresholds);
+    }
+
+    /// Drop samples that fell out of their window or exceed the sample cap
+    fn prune(&mut self, now: tokio::time::Instant, thresholds: &DegradationThresholds) {
+        while let Some(at) = self.crashes.front() {
+            if now.duration_since(*at) > thresholds.crash_window {
+                self.crashes.pop_front();
+            } else {
+                break;
+            }
+        }
+        while let Some((at, _)) = self.samples.front() {
+            if now.duration_since(*at) > thresholds.evaluation_window {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+        while self.samples.len() > thresholds.max_window_samples.max(1) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Aggregate the operations inside the evaluation window and the
+    /// crashes inside the crash window
+    fn metrics(&self, thresholds: &DegradationThresholds) -> HealthMetrics {
+        let now = tokio::time::Instant::now();
+        let window_start = now
+            .checked_sub(thresholds.evaluation_window)
+            .map_or(self.started_at, |start| start.max(self.started_at));
+        let crash_start = now
+            .checked_sub(thresholds.crash_window)
+            .map_or(self.started_at, |start| start.max(self.started_at));
+
+        let mut metrics = HealthMetrics {
+            started_at: window_start.into_std(),
+            ..HealthMetrics::new()
+        };
+        for _ in self.crashes.iter().filter(|at| **at >= crash_start) {
+            metrics.record_crash();
+        }
+        for (_, sample) in self.samples.iter().filter(|(at, _)| *at >= window_start) {
+            match *sample {
+                HealthSample::Success { latency_ms } => metrics.record_success(latency_ms),
+                HealthSample::Failure => metrics.record_failure(),
+                HealthSample::Crash => {}
+            }
+        }
+        metrics
+    }
+}
+
 /// Health monitor for detecting adapter degradation after updates
+///
+/// Post-update behaviour is judged over `evaluation_window` only, so a
+/// regression that starts long after the update is not diluted by hours of
+/// healthy history.
 pub struct AdapterHealthMonitor {
     /// Baseline metrics (before update)
     baseline: Arc<RwLock<HashMap<AdapterType, HealthMetrics>>>,
 
-    /// Current metrics (after update)
-    current: Arc<RwLock<HashMap<AdapterType, HealthMetrics>>>,
+    /// Recent samples (after update)
+    current: Arc<RwLock<HashMap<AdapterType, HealthWindow>>>,
 
     /// Degradation thresholds
     thresholds: DegradationThresholds,
@@ -172,7 +367,7 @@ impl AdapterHealthMonitor {
     /// Start monitoring after update
     pub async fn start_monitoring(&self, adapter_type: AdapterType) {
         let mut current = self.current.write().await;
-        current.insert(adapter_type, HealthMetrics::new());
+        current.insert(adapter_type, HealthWindow::new());
 
         let mut enabled = self.monitoring_enabled.write().await;
         enabled.insert(adapter_type, true);
@@ -186,36 +381,22 @@ impl AdapterHealthMonitor {
 
     /// Record a successful operation
     pub async fn record_success(&self, adapter_type: AdapterType, latency_ms: u64) {
-        let enabled = {
-            let monitoring = self.monitoring_enabled.read().await;
-            monitoring.get(&adapter_type).copied().unwrap_or(false)
-        };
-
-        if enabled {
-            let mut current = self.current.write().await;
-            if let Some(metrics) = current.get_mut(&adapter_type) {
-                metrics.record_success(latency_ms);
-            }
-        }
+        self.record_sample(adapter_type, HealthSample::Success { latency_ms })
+            .await;
     }
 
     /// Record a failed operation
     pub async fn record_failure(&self, adapter_type: AdapterType) {
-        let enabled = {
-            let monitoring = self.monitoring_enabled.read().await;
-            monitoring.get(&adapter_type).copied().unwrap_or(false)
-        };
-
-        if enabled {
-            let mut current = self.current.write().await;
-            if let Some(metrics) = current.get_mut(&adapter_type) {
-                metrics.record_failure();
-            }
-        }
+        self.record_sample(adapter_type, HealthSample::Failure)
+            .await;
     }
 
     /// Record a crash
     pub async fn record_crash(&self, adapter_type: AdapterType) {
+        self.record_sample(adapter_type, HealthSample::Crash).await;
+    }
+
+    async fn record_sample(&self, adapter_type: AdapterType, sample: HealthSample) {
         let enabled = {
             let monitoring = self.monitoring_enabled.read().await;
             monitoring.get(&adapter_type).copied().unwrap_or(false)
@@ -223,8 +404,8 @@ impl AdapterHealthMonitor {
 
         if enabled {
             let mut current = self.current.write().await;
-            if let Some(metrics) = current.get_mut(&adapter_type) {
-                metrics.record_crash();
+            if let Some(window) = current.get_mut(&adapter_type) {
+                window.record(sample, &self.thresholds);
             }
         }
     }
@@ -239,10 +420,11 @@ impl AdapterHealthMonitor {
             None => return (false, "No baseline metrics".to_string()),
         };
 
-        let current_metrics = match current.get(&adapter_type) {
-            Some(m) => m,
+        let window = match current.get(&adapter_type) {
+            Some(window) => window,
             None => return (false, "No current metrics".to_string()),
         };
+        let current_metrics = window.metrics(&self.thresholds);
 
         // Need minimum operations for valid comparison
         if current_metrics.total_operations < self.thresholds.min_operations {
@@ -257,6 +439,10 @@ impl AdapterHealthMonitor {
             );
         }
 
+        if let DetectionMode::Ewma { alpha, z_threshold } = self.thresholds.detection_mode {
+            return self.ewma_degraded(baseline_metrics, &window.ewma, alpha, z_threshold);
+        }
+
         // Check success rate drop
         let baseline_success = baseline_metrics.success_rate();
         let current_success = current_metrics.success_rate();
@@ -315,10 +501,61 @@ impl AdapterHealthMonitor {
         (false, "No degradation detected".to_string())
     }
 
-    /// Get current metrics for an adapter
+    /// Compare smoothed success rate and latency against the baseline
+    fn ewma_degraded(
+        &self,
+        baseline: &HealthMetrics,
+        ewma: &EwmaHealth,
+        alpha: f64,
+        z_threshold: f64,
+    ) -> (bool, String) {
+        if let Some(success) = ewma.success {
+            let baseline_success = baseline.success_rate();
+            let success_drop = baseline_success - success.mean;
+            let z = -success.z_score(baseline_success, alpha);
+
+            if success_drop > self.thresholds.max_success_rate_drop && z > z_threshold {
+                return (
+                    true,
+                    format!(
+                        "Success rate dropped {:.1}% (baseline: {:.1}%, EWMA: {:.1}%, z={:.1})",
+                        success_drop * 100.0,
+                        baseline_success * 100.0,
+                        success.mean * 100.0,
+                        z
+                    ),
+                );
+            }
+        }
+
+        let baseline_latency = baseline.average_latency_ms();
+        if let Some(latency) = ewma.latency.filter(|_| baseline_latency > 0.0) {
+            let latency_increase = (latency.mean - baseline_latency) / baseline_latency;
+            let z = latency.z_score(baseline_latency, alpha);
+
+            if latency_increase > self.thresholds.max_latency_increase && z > z_threshold {
+                return (
+                    true,
+                    format!(
+                        "Latency increased {:.1}% (baseline: {:.1}ms, EWMA: {:.1}ms, z={:.1})",
+                        latency_increase * 100.0,
+                        baseline_latency,
+                        latency.mean,
+                        z
+                    ),
+                );
+            }
+        }
+
+        (false, "No degradation detected".to_string())
+    }
+
+    /// Get current metrics for an adapter, aggregated over the evaluation window
     pub async fn get_current_metrics(&self, adapter_type: AdapterType) -> Option<HealthMetrics> {
         let current = self.current.read().await;
-        current.get(&adapter_type).cloned()
+        current
+            .get(&adapter_type)
+            .map(|window| window.metrics(&self.thresholds))
     }
 
     /// Get baseline metrics for an adapter
@@ -370,6 +607,9 @@ pub struct AdapterMetadata {
 
     /// Number of active connections
     pub active_connections: u32,
+
+    /// Version the adapter is frozen at, if pinned by an operator
+    pub pinned_version: Option<SemanticVersion>,
 }
 
 /// Historical version entry for rollback
@@ -390,8 +630,10 @@ pub struct HistoricalVersion {
     /// Metadata from when it was running
     pub metadata: AdapterMetadata,
 
-    /// Path to preserved binary (for real rollback)
-    /// In production, this would point to a preserved .so/.dll file
+    /// Path to the preserved copy of this version's shared library
+    ///
+    /// Set only when `preserve_binaries` is enabled and the library was
+    /// found on disk when the version was archived.
     pub binary_path: Option<String>,
 }
 
@@ -437,7 +679,16 @@ impl RollbackHistory {
     }
 
     /// Add a version to history when it's being replaced
+    ///
+    /// With `preserve_binaries` enabled the adapter's library is copied into
+    /// `binary_storage_path` so the version can be restored later.
     pub async fn archive_version(&self, adapter_type: AdapterType, metadata: AdapterMetadata) {
+        let binary_path = if self.config.preserve_binaries {
+            self.preserve_binary(&metadata).await
+        } else {
+            None
+        };
+
         let mut history = self.history.write().await;
         let versions = history.entry(adapter_type).or_insert_with(Vec::new);
 
@@ -450,7 +701,7 @@ impl RollbackHistory {
                 .unwrap()
                 .as_secs(),
             metadata: metadata.clone(),
-            binary_path: None, // TODO: Implement binary preservation
+            binary_path,
         };
 
         versions.push(historical);
@@ -464,9 +715,8 @@ impl RollbackHistory {
                 adapter_type
             );
 
-            // TODO: Clean up preserved binary if it exists
             if let Some(path) = removed.binary_path {
-                log::debug!("Would delete preserved binary at: {}", path);
+                remove_preserved_binary(&path).await;
             }
         }
     }
@@ -528,10 +778,9 @@ impl RollbackHistory {
                 adapter_type
             );
 
-            // TODO: Clean up preserved binaries
             for version in versions {
                 if let Some(path) = version.binary_path {
-                    log::debug!("Would delete preserved binary at: {}", path);
+                    remove_preserved_binary(&path).await;
                 }
             }
         }
@@ -547,12 +796,98 @@ impl RollbackHistory {
     pub fn config(&self) -> &RollbackHistoryConfig {
         &self.config
     }
+
+    /// Copy an adapter's library into binary storage
+    ///
+    /// Failures are logged rather than returned: losing the ability to roll
+    /// back must not block the reload that triggered archiving.
+    async fn preserve_binary(&self, metadata: &AdapterMetadata) -> Option<String> {
+        let storage = match &self.config.binary_storage_path {
+            Some(path) => PathBuf::from(path),
+            None => {
+                log::warn!("preserve_binaries is enabled but no binary_storage_path is set");
+                return None;
+            }
+        };
+
+        let source = Path::new(&metadata.library);
+        if !source.is_file() {
+            log::debug!(
+                "Library {} for {:?} not found on disk, not preserving",
+                metadata.library,
+                metadata.adapter_type
+            );
+            return None;
+        }
+
+        if let Err(e) = tokio::fs::create_dir_all(&storage).await {
+            log::warn!(
+                "Failed to create binary storage {}: {}",
+                storage.display(),
+                e
+            );
+            return None;
+        }
+
+        // Unique per archive so repeated reloads of one version never collide
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let mut file_name = format!("{:?}-{}-{}", metadata.adapter_type, metadata.version, nanos);
+        if let Some(ext) = source.extension() {
+            file_name.push('.');
+            file_name.push_str(&ext.to_string_lossy());
+        }
+        let destination = storage.join(file_name);
+
+        match tokio::fs::copy(source, &destination).await {
+            Ok(_) => {
+                log::debug!(
+                    "Preserved {} as {}",
+                    metadata.library,
+                    destination.display()
+                );
+                Some(destination.to_string_lossy().into_owned())
+            }
+            Err(e) => {
+                log::warn!("Failed to preserve {}: {}", metadata.library, e);
+                None
+            }
+        }
+    }
+}
+
+/// Delete a preserved binary that has left the history
+async fn remove_preserved_binary(path: &str) {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => log::debug!("Deleted preserved binary at: {}", path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => log::warn!("Failed to delete preserved binary at {}: {}", path, e),
+    }
 }
 
+/// Instantiates an adapter from a shared library on disk
+///
+/// The plugin ABI belongs to the host application, so the registry only
+/// tracks where each version's library lives and delegates loading here.
+pub trait AdapterLoader: Send + Sync {
+    /// Load the library at `library_path` and construct its adapter
+    fn load_adapter(
+        &self,
+        adapter_type: AdapterType,
+        library_path: &Path,
+        version: &SemanticVersion,
+    ) -> Result<Box<dyn NetworkAdapter>>;
+}
+
+/// Shared handle to a registered adapter
+pub type AdapterHandle = Arc<RwLock<Box<dyn NetworkAdapter>>>;
+
 /// Registry for managing hot-reloadable adapters
 pub struct AdapterRegistry {
     /// Loaded adapters
-    adapters: Arc<RwLock<HashMap<AdapterType, Box<dyn NetworkAdapter>>>>,
+    adapters: Arc<RwLock<HashMap<AdapterType, AdapterHandle>>>,
 
     /// Adapter metadata
     metadata: Arc<RwLock<HashMap<AdapterType, AdapterMetadata>>>,
@@ -571,6 +906,12 @@ pub struct AdapterRegistry {
 
     /// Rollback history manager
     rollback_history: Option<Arc<RollbackHistory>>,
+
+    /// Loader for restoring preserved adapter libraries
+    adapter_loader: Option<Arc<dyn AdapterLoader>>,
+
+    /// Staggers reloads across neighbors
+    update_coordinator: Option<Arc<UpdateCoordinator>>,
 }
 
 impl AdapterRegistry {
@@ -584,6 +925,8 @@ impl AdapterRegistry {
             health_monitor: None,
             auto_rollback_enabled: Arc::new(RwLock::new(HashMap::new())),
             rollback_history: None,
+            adapter_loader: None,
+            update_coordinator: None,
         }
     }
 
@@ -597,6 +940,8 @@ impl AdapterRegistry {
             health_monitor: Some(Arc::new(AdapterHealthMonitor::new(thresholds))),
             auto_rollback_enabled: Arc::new(RwLock::new(HashMap::new())),
             rollback_history: None,
+            adapter_loader: None,
+            update_coordinator: None,
         }
     }
 
@@ -613,9 +958,23 @@ impl AdapterRegistry {
             health_monitor: Some(Arc::new(AdapterHealthMonitor::new(thresholds))),
             auto_rollback_enabled: Arc::new(RwLock::new(HashMap::new())),
             rollback_history: Some(Arc::new(RollbackHistory::new(history_config))),
+            adapter_loader: None,
+            update_coordinator: None,
         }
     }
 
+    /// Set the loader used to restore preserved libraries on rollback
+    pub fn with_adapter_loader(mut self, loader: Arc<dyn AdapterLoader>) -> Self {
+        self.adapter_loader = Some(loader);
+        self
+    }
+
+    /// Set the coordinator used by `scheduled_hot_reload`
+    pub fn with_update_coordinator(mut self, coordinator: Arc<UpdateCoordinator>) -> Self {
+        self.update_coordinator = Some(coordinator);
+        self
+    }
+
     /// Enable automatic rollback for an adapter
     pub async fn enable_auto_rollback(&self, adapter_type: AdapterType) {
         let mut enabled = self.auto_rollback_enabled.write().await;
@@ -634,6 +993,43 @@ impl AdapterRegistry {
         enabled.get(&adapter_type).copied().unwrap_or(false)
     }
 
+    /// Pin an adapter to a version
+    ///
+    /// While pinned, reloads to any other version are rejected and
+    /// automatic rollback is skipped.
+    pub async fn pin_version(
+        &self,
+        adapter_type: AdapterType,
+        version: SemanticVersion,
+    ) -> Result<()> {
+        let mut metadata = self.metadata.write().await;
+        let meta = metadata
+            .get_mut(&adapter_type)
+            .ok_or_else(|| NetworkError::AdapterNotFound(format!("{:?}", adapter_type)))?;
+
+        log::warn!("Pinning {:?} to version {}", adapter_type, version);
+        meta.pinned_version = Some(version);
+        Ok(())
+    }
+
+    /// Remove an adapter's version pin
+    pub async fn unpin(&self, adapter_type: AdapterType) {
+        let mut metadata = self.metadata.write().await;
+        if let Some(meta) = metadata.get_mut(&adapter_type) {
+            if meta.pinned_version.take().is_some() {
+                log::info!("Unpinned {:?}", adapter_type);
+            }
+        }
+    }
+
+    /// Version an adapter is pinned to, if any
+    pub async fn pinned_version(&self, adapter_type: AdapterType) -> Option<SemanticVersion> {
+        let metadata = self.metadata.read().await;
+        metadata
+            .get(&adapter_type)
+            .and_then(|meta| meta.pinned_version.clone())
+    }
+
     /// Register a new adapter
     pub async fn register_adapter(
         &self,
@@ -656,9 +1052,10 @@ impl AdapterRegistry {
             reload_count: 0,
             status: AdapterLoadStatus::Active,
             active_connections: 0,
+            pinned_version: None,
         };
 
-        adapters.insert(adapter_type, adapter);
+        adapters.insert(adapter_type, Arc::new(RwLock::new(adapter)));
         metadata.insert(adapter_type, meta);
 
         Ok(())
@@ -684,6 +1081,22 @@ impl AdapterRegistry {
             new_version
         );
 
+        // Refuse to move a pinned adapter off its pinned version
+        if let Some(pinned) = self.pinned_version(adapter_type).await {
+            if pinned != new_version {
+                log::warn!(
+                    "Refusing reload of {:?} to {}: pinned to {}",
+                    adapter_type,
+                    new_version,
+                    pinned
+                );
+                return Err(NetworkError::InitializationFailed(format!(
+                    "{:?} is pinned to version {}",
+                    adapter_type, pinned
+                )));
+            }
+        }
+
         // Archive current version to rollback history
         {
             let metadata = self.metadata.read().await;
@@ -731,35 +1144,35 @@ impl AdapterRegistry {
 
         // Stop old adapter
         {
-            let mut adapters = self.adapters.write().await;
-            if let Some(mut old_adapter) = adapters.remove(&adapter_type) {
-                // Gracefully stop old adapter
-                if let Err(e) = old_adapter.stop().await {
+            let old_adapter = self.adapters.write().await.remove(&adapter_type);
+            if let Some(old_adapter) = old_adapter {
+                // Gracefully stop old adapter; waits for callers holding its handle
+                if let Err(e) = old_adapter.write().await.stop().await {
                     log::error!("Error stopping old adapter {:?}: {}", adapter_type, e);
                 }
             }
         }
 
         // Insert new adapter
+        let new_adapter: AdapterHandle = Arc::new(RwLock::new(new_adapter));
         {
             let mut adapters = self.adapters.write().await;
-            adapters.insert(adapter_type, new_adapter);
+            adapters.insert(adapter_type, new_adapter.clone());
         }
 
         // Initialize new adapter
         {
-            let mut adapters = self.adapters.write().await;
-            if let Some(adapter) = adapters.get_mut(&adapter_type) {
-                adapter.initialize().await.map_err(|e| {
-                    log::error!("Failed to initialize new adapter {:?}: {}", adapter_type, e);
-                    NetworkError::InitializationFailed(e.to_string())
-                })?;
-
-                adapter.start().await.map_err(|e| {
-                    log::error!("Failed to start new adapter {:?}: {}", adapter_type, e);
-                    NetworkError::InitializationFailed(e.to_string())
-                })?;
-            }
+            // Callers fetching the handle meanwhile wait until it is started
+            let mut adapter = new_adapter.write().await;
+            adapter.initialize().await.map_err(|e| {
+                log::error!("Failed to initialize new adapter {:?}: {}", adapter_type, e);
+                NetworkError::InitializationFailed(e.to_string())
+            })?;
+
+            adapter.start().await.map_err(|e| {
+                log::error!("Failed to start new adapter {:?}: {}", adapter_type, e);
+                NetworkError::InitializationFailed(e.to_string())
+            })?;
         }
 
         // Update metadata
@@ -781,6 +1194,41 @@ impl AdapterRegistry {
         Ok(())
     }
 
+    /// Hot reload an adapter in this node's coordinated reload window
+    ///
+    /// Waits for the window assigned by the update coordinator so neighbors
+    /// on the same adapter do not all reload at once, then performs
+    /// `hot_reload_adapter`. Without a coordinator it reloads immediately.
+    /// Rollbacks bypass this and reload at once.
+    pub async fn scheduled_hot_reload(
+        &self,
+        adapter_type: AdapterType,
+        new_adapter: Box<dyn NetworkAdapter>,
+        new_version: SemanticVersion,
+    ) -> Result<()> {
+        if let Some(coordinator) = &self.update_coordinator {
+            let window = coordinator.next_window(adapter_type).await;
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+
+            if now < window.start {
+                log::info!(
+                    "Deferring reload of {:?} to window {}/{} in {}s",
+                    adapter_type,
+                    window.slot + 1,
+                    window.slot_count,
+                    window.start - now
+                );
+                tokio::time::sleep(Duration::from_secs(window.start - now)).await;
+            }
+        }
+
+        self.hot_reload_adapter(adapter_type, new_adapter, new_version)
+            .await
+    }
+
     /// Drain connections from an adapter
     async fn drain_adapter(&self, adapter_type: AdapterType, timeout: Duration) -> Result<()> {
         let start = Instant::now();
@@ -856,6 +1304,11 @@ impl AdapterRegistry {
     }
 
     /// Rollback to previous version
+    ///
+    /// The preserved library is first copied next to the version's original
+    /// library, then moved into place once the reload succeeds. The running
+    /// adapter never depends on a history copy that eviction or
+    /// `clear_history` may delete.
     pub async fn rollback_adapter(&self, adapter_type: AdapterType) -> Result<()> {
         let previous_version = {
             let previous = self.previous_versions.read().await;
@@ -870,15 +1323,49 @@ impl AdapterRegistry {
             previous_version
         );
 
-        // Note: In a real implementation, we would need to:
-        // 1. Load the previous version's binary/library
-        // 2. Create an adapter instance
-        // 3. Call hot_reload_adapter with the old version
+        let history = self.rollback_history.as_ref().ok_or_else(|| {
+            NetworkError::InitializationFailed("Rollback history not enabled".to_string())
+        })?;
+        let historical = history
+            .get_version(adapter_type, &previous_version)
+            .await
+            .filter(|v| v.binary_path.is_some())
+            .ok_or_else(|| {
+                NetworkError::InitializationFailed(format!(
+                    "No preserved binary for version {}",
+                    previous_version
+                ))
+            })?;
+        let binary_path = historical.binary_path.unwrap_or_default();
+        let loader = self.adapter_loader.as_ref().ok_or_else(|| {
+            NetworkError::InitializationFailed("No adapter loader configured".to_string())
+        })?;
+
+        // Stage the restored library outside the history: reloading archives
+        // the current version, which may evict the copy being restored, and
+        // the current library must be archived before it is overwritten
+        let staged = format!("{}.rollback", historical.library);
+        tokio::fs::copy(&binary_path, &staged).await?;
+
+        let reloaded =
+            match loader.load_adapter(adapter_type, Path::new(&staged), &previous_version) {
+                Ok(adapter) => {
+                    self.hot_reload_adapter(adapter_type, adapter, previous_version)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+        if let Err(e) = reloaded {
+            remove_preserved_binary(&staged).await;
+            return Err(e);
+        }
+
+        tokio::fs::rename(&staged, &historical.library).await?;
+        if let Some(meta) = self.metadata.write().await.get_mut(&adapter_type) {
+            meta.library = historical.library;
+        }
 
-        // For now, this is a placeholder showing the interface
-        Err(NetworkError::InitializationFailed(
-            "Rollback not yet implemented - requires binary/library loading".to_string(),
-        ))
+        Ok(())
     }
 
     /// Check for degradation and trigger automatic rollback if needed
@@ -891,6 +1378,16 @@ impl AdapterRegistry {
             return Ok(false);
         }
 
+        // Pinned adapters stay put until an operator unpins them
+        if let Some(pinned) = self.pinned_version(adapter_type).await {
+            log::warn!(
+                "Skipping automatic rollback of {:?}: pinned to {}",
+                adapter_type,
+                pinned
+            );
+            return Ok(false);
+        }
+
         // Check if we have a health monitor
         let monitor = match &self.health_monitor {
             Some(m) => m,
@@ -974,14 +1471,13 @@ impl AdapterRegistry {
             .unwrap_or(false)
     }
 
-    /// Get adapter reference
-    pub async fn get_adapter(
-        &self,
-        _adapter_type: AdapterType,
-    ) -> Option<Arc<RwLock<Box<dyn NetworkAdapter>>>> {
-        // This would need adjustment to the actual implementation
-        // For now, returning None as we can't easily clone trait objects
-        None
+    /// Get a shared handle to the active adapter
+    ///
+    /// Handles are not redirected by a hot reload: the old handle refers to
+    /// the stopped adapter afterwards, so fetch a fresh one per operation
+    /// rather than caching it.
+    pub async fn get_adapter(&self, adapter_type: AdapterType) -> Option<AdapterHandle> {
+        self.adapters.read().await.get(&adapter_type).cloned()
     }
 }
 
@@ -994,6 +1490,9 @@ impl Default for AdapterRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapter::{AdapterStatus, PeerInfo, TestResults};
+    use crate::types::{AdapterCapabilities, Address, FeatureFlags, PowerConsumption};
+    use myriadmesh_protocol::Frame;
 
     #[tokio::test]
     async fn test_connection_counting() {
@@ -1057,6 +1556,7 @@ mod tests {
                     reload_count: 0,
                     status: AdapterLoadStatus::Active,
                     active_connections: 0,
+                    pinned_version: None,
                 },
             );
         }
@@ -1334,6 +1834,7 @@ mod tests {
             reload_count: 0,
             status: AdapterLoadStatus::Active,
             active_connections: 0,
+            pinned_version: None,
         };
 
         history
@@ -1368,6 +1869,7 @@ mod tests {
                 reload_count: i,
                 status: AdapterLoadStatus::Active,
                 active_connections: 0,
+                pinned_version: None,
             };
 
             history
@@ -1399,6 +1901,7 @@ mod tests {
                 reload_count: i,
                 status: AdapterLoadStatus::Active,
                 active_connections: 0,
+                pinned_version: None,
             };
 
             history
@@ -1434,6 +1937,7 @@ mod tests {
                 reload_count: i,
                 status: AdapterLoadStatus::Active,
                 active_connections: 0,
+                pinned_version: None,
             };
 
             history
@@ -1480,6 +1984,7 @@ mod tests {
                 reload_count: i,
                 status: AdapterLoadStatus::Active,
                 active_connections: 0,
+                pinned_version: None,
             };
 
             history
@@ -1505,4 +2010,650 @@ mod tests {
         assert!(registry.get_health_monitor().is_some());
         assert!(registry.get_rollback_history().is_some());
     }
+
+    struct StubAdapter {
+        status: AdapterStatus,
+        capabilities: AdapterCapabilities,
+    }
+
+    impl StubAdapter {
+        fn boxed() -> Box<dyn NetworkAdapter> {
+            Box::new(Self {
+                status: AdapterStatus::Uninitialized,
+                capabilities: AdapterCapabilities {
+                    adapter_type: AdapterType::Ethernet,
+                    max_message_size: 1400,
+                    typical_latency_ms: 1.0,
+                    typical_bandwidth_bps: 1_000_000,
+                    reliability: 1.0,
+                    range_meters: 0.0,
+                    power_consumption: PowerConsumption::None,
+                    cost_per_mb: 0.0,
+                    features: FeatureFlags::empty(),
+                },
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl NetworkAdapter for StubAdapter {
+        async fn initialize(&mut self) -> Result<()> {
+            self.status = AdapterStatus::Ready;
+            Ok(())
+        }
+
+        async fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            self.status = AdapterStatus::ShuttingDown;
+            Ok(())
+        }
+
+        async fn send(&self, _destination: &Address, _frame: &Frame) -> Result<()> {
+            Ok(())
+        }
+
+        async fn receive(&self, _timeout_ms: u64) -> Result<(Address, Frame)> {
+            Err(NetworkError::Timeout)
+        }
+
+        async fn discover_peers(&self) -> Result<Vec<PeerInfo>> {
+            Ok(Vec::new())
+        }
+
+        fn get_status(&self) -> AdapterStatus {
+            self.status
+        }
+
+        fn get_capabilities(&self) -> &AdapterCapabilities {
+            &self.capabilities
+        }
+
+        async fn test_connection(&self, _destination: &Address) -> Result<TestResults> {
+            Err(NetworkError::AdapterNotReady)
+        }
+
+        fn get_local_address(&self) -> Option<Address> {
+            None
+        }
+
+        fn parse_address(&self, addr_str: &str) -> Result<Address> {
+            Err(NetworkError::InvalidAddress(addr_str.to_string()))
+        }
+
+        fn supports_address(&self, _address: &Address) -> bool {
+            false
+        }
+    }
+
+    /// Records which library paths it was asked to load
+    #[derive(Default)]
+    struct RecordingLoader {
+        loaded: std::sync::Mutex<Vec<(PathBuf, Vec<u8>)>>,
+    }
+
+    impl AdapterLoader for RecordingLoader {
+        fn load_adapter(
+            &self,
+            _adapter_type: AdapterType,
+            library_path: &Path,
+            _version: &SemanticVersion,
+        ) -> Result<Box<dyn NetworkAdapter>> {
+            let contents = std::fs::read(library_path)?;
+            self.loaded
+                .lock()
+                .unwrap()
+                .push((library_path.to_path_buf(), contents));
+            Ok(StubAdapter::boxed())
+        }
+    }
+
+    fn preserving_config(storage: &Path, depth: usize) -> RollbackHistoryConfig {
+        RollbackHistoryConfig {
+            max_history_depth: depth,
+            preserve_binaries: true,
+            binary_storage_path: Some(storage.to_string_lossy().into_owned()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_preserves_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = dir.path().join("libmyriad_ethernet.so");
+        std::fs::write(&library, b"ethernet v1").unwrap();
+
+        let storage = dir.path().join("history");
+        let history = RollbackHistory::new(preserving_config(&storage, 1));
+
+        let metadata = AdapterMetadata {
+            adapter_type: AdapterType::Ethernet,
+            version: SemanticVersion::new(1, 0, 0),
+            library: library.to_string_lossy().into_owned(),
+            loaded_at: 1000,
+            reload_count: 0,
+            status: AdapterLoadStatus::Active,
+            active_connections: 0,
+            pinned_version: None,
+        };
+        history
+            .archive_version(AdapterType::Ethernet, metadata.clone())
+            .await;
+
+        let first = history
+            .get_previous_version(AdapterType::Ethernet)
+            .await
+            .unwrap()
+            .binary_path
+            .unwrap();
+        assert!(first.starts_with(storage.to_str().unwrap()));
+        assert!(first.ends_with(".so"));
+        assert_eq!(std::fs::read(&first).unwrap(), b"ethernet v1");
+
+        // Depth eviction deletes the evicted version's copy
+        history
+            .archive_version(
+                AdapterType::Ethernet,
+                AdapterMetadata {
+                    version: SemanticVersion::new(1, 1, 0),
+                    ..metadata.clone()
+                },
+            )
+            .await;
+        assert!(!Path::new(&first).exists());
+        assert_eq!(std::fs::read_dir(&storage).unwrap().count(), 1);
+
+        // Missing libraries are archived without a binary
+        history
+            .archive_version(
+                AdapterType::Ethernet,
+                AdapterMetadata {
+                    library: "not-on-disk".to_string(),
+                    ..metadata
+                },
+            )
+            .await;
+        let latest = history.get_previous_version(AdapterType::Ethernet).await;
+        assert!(latest.unwrap().binary_path.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_loads_preserved_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = dir.path().join("libmyriad_ethernet.so");
+        std::fs::write(&library, b"ethernet v1").unwrap();
+        let storage = dir.path().join("history");
+
+        let loader = Arc::new(RecordingLoader::default());
+        let registry = AdapterRegistry::with_full_features(
+            DegradationThresholds::default(),
+            preserving_config(&storage, 5),
+        )
+        .with_adapter_loader(loader.clone());
+
+        registry
+            .register_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 0, 0),
+                library.to_string_lossy().into_owned(),
+            )
+            .await
+            .unwrap();
+        registry
+            .hot_reload_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(2, 0, 0),
+            )
+            .await
+            .unwrap();
+
+        // The deployed library may be replaced on disk after the upgrade
+        std::fs::write(&library, b"ethernet v2").unwrap();
+
+        registry
+            .rollback_adapter(AdapterType::Ethernet)
+            .await
+            .unwrap();
+
+        let loaded = loader.loaded.lock().unwrap().clone();
+        assert_eq!(loaded.len(), 1);
+        assert!(!loaded[0].0.starts_with(&storage));
+        assert_eq!(loaded[0].1, b"ethernet v1");
+
+        // The preserved library is restored to its original location
+        let meta = registry.get_metadata(AdapterType::Ethernet).await.unwrap();
+        assert_eq!(meta.version, SemanticVersion::new(1, 0, 0));
+        assert_eq!(Path::new(&meta.library), library.as_path());
+        assert_eq!(std::fs::read(&library).unwrap(), b"ethernet v1");
+
+        // Version 2 was archived from its own bytes before the restore
+        let history = registry.get_rollback_history().unwrap();
+        let v2 = history
+            .get_version(AdapterType::Ethernet, &SemanticVersion::new(2, 0, 0))
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read(v2.binary_path.unwrap()).unwrap(),
+            b"ethernet v2"
+        );
+
+        // Clearing history removes every preserved copy but not the library
+        // the adapter now runs from
+        history.clear_history(AdapterType::Ethernet).await;
+        assert_eq!(std::fs::read_dir(&storage).unwrap().count(), 0);
+        assert!(library.exists());
+        assert_eq!(std::fs::read(&library).unwrap(), b"ethernet v1");
+    }
+
+    #[tokio::test]
+    async fn test_rollback_survives_history_eviction() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = dir.path().join("libmyriad_ethernet.so");
+        std::fs::write(&library, b"ethernet v1").unwrap();
+        let storage = dir.path().join("history");
+
+        // Depth 1: archiving the current version on rollback evicts the
+        // copy being restored
+        let loader = Arc::new(RecordingLoader::default());
+        let registry = AdapterRegistry::with_full_features(
+            DegradationThresholds::default(),
+            preserving_config(&storage, 1),
+        )
+        .with_adapter_loader(loader.clone());
+
+        registry
+            .register_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 0, 0),
+                library.to_string_lossy().into_owned(),
+            )
+            .await
+            .unwrap();
+        registry
+            .hot_reload_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(2, 0, 0),
+            )
+            .await
+            .unwrap();
+        std::fs::write(&library, b"ethernet v2").unwrap();
+
+        registry
+            .rollback_adapter(AdapterType::Ethernet)
+            .await
+            .unwrap();
+
+        let meta = registry.get_metadata(AdapterType::Ethernet).await.unwrap();
+        assert_eq!(meta.version, SemanticVersion::new(1, 0, 0));
+        assert!(Path::new(&meta.library).exists());
+        assert_eq!(std::fs::read(&meta.library).unwrap(), b"ethernet v1");
+        assert!(!Path::new(&format!("{}.rollback", meta.library)).exists());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_without_preserved_binary_fails() {
+        let registry = AdapterRegistry::with_full_features(
+            DegradationThresholds::default(),
+            RollbackHistoryConfig::default(),
+        )
+        .with_adapter_loader(Arc::new(RecordingLoader::default()));
+
+        registry
+            .register_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 0, 0),
+                "libmyriad_ethernet.so".to_string(),
+            )
+            .await
+            .unwrap();
+        registry
+            .hot_reload_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(2, 0, 0),
+            )
+            .await
+            .unwrap();
+
+        assert!(registry
+            .rollback_adapter(AdapterType::Ethernet)
+            .await
+            .is_err());
+        let meta = registry.get_metadata(AdapterType::Ethernet).await.unwrap();
+        assert_eq!(meta.version, SemanticVersion::new(2, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_get_adapter_returns_usable_handle() {
+        let registry = AdapterRegistry::new();
+        assert!(registry.get_adapter(AdapterType::Ethernet).await.is_none());
+
+        registry
+            .register_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 0, 0),
+                "libmyriad_ethernet.so".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let handle = registry.get_adapter(AdapterType::Ethernet).await.unwrap();
+        assert_eq!(
+            handle.read().await.get_status(),
+            AdapterStatus::Uninitialized
+        );
+        handle.write().await.initialize().await.unwrap();
+        assert_eq!(handle.read().await.get_status(), AdapterStatus::Ready);
+
+        // After a hot reload the old handle sees the stopped adapter
+        registry
+            .hot_reload_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 1, 0),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            handle.read().await.get_status(),
+            AdapterStatus::ShuttingDown
+        );
+
+        let reloaded = registry.get_adapter(AdapterType::Ethernet).await.unwrap();
+        assert_eq!(reloaded.read().await.get_status(), AdapterStatus::Ready);
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_hot_reload_in_open_window() {
+        use crate::update_coordinator::UpdateCoordinatorConfig;
+        use myriadmesh_protocol::{types::NODE_ID_SIZE, NodeId};
+
+        // A lone node owns every window, so the reload proceeds at once
+        let coordinator = Arc::new(UpdateCoordinator::new(
+            NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+            UpdateCoordinatorConfig::default(),
+        ));
+        let registry = AdapterRegistry::new().with_update_coordinator(coordinator);
+
+        registry
+            .register_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 0, 0),
+                "libmyriad_ethernet.so".to_string(),
+            )
+            .await
+            .unwrap();
+        registry
+            .scheduled_hot_reload(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 1, 0),
+            )
+            .await
+            .unwrap();
+
+        let meta = registry.get_metadata(AdapterType::Ethernet).await.unwrap();
+        assert_eq!(meta.version, SemanticVersion::new(1, 1, 0));
+        assert_eq!(meta.reload_count, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_health_monitor_detects_recent_regression() {
+        let thresholds = DegradationThresholds {
+            evaluation_window: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let monitor = AdapterHealthMonitor::new(thresholds);
+
+        let mut baseline = HealthMetrics::new();
+        for _ in 0..100 {
+            baseline.record_success(10);
+        }
+        monitor
+            .capture_baseline(AdapterType::Ethernet, baseline)
+            .await;
+        monitor.start_monitoring(AdapterType::Ethernet).await;
+
+        // An hour of healthy traffic, one operation per second
+        for _ in 0..3600 {
+            monitor.record_success(AdapterType::Ethernet, 10).await;
+            tokio::time::advance(Duration::from_secs(1)).await;
+        }
+        let (degraded, reason) = monitor.is_degraded(AdapterType::Ethernet).await;
+        assert!(!degraded, "Should not be degraded: {}", reason);
+
+        // Then 20 seconds of failures; lifetime success rate would still be ~99%
+        for _ in 0..20 {
+            monitor.record_failure(AdapterType::Ethernet).await;
+            tokio::time::advance(Duration::from_secs(1)).await;
+        }
+
+        let (degraded, reason) = monitor.is_degraded(AdapterType::Ethernet).await;
+        assert!(degraded, "Recent regression should be detected");
+        assert!(reason.contains("Success rate dropped"));
+
+        // Only the last minute is retained
+        let current = monitor
+            .get_current_metrics(AdapterType::Ethernet)
+            .await
+            .unwrap();
+        assert!(current.total_operations <= 61);
+        assert_eq!(current.failed_operations, 20);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_crash_outlives_evaluation_window() {
+        let thresholds = DegradationThresholds {
+            evaluation_window: Duration::from_secs(60),
+            crash_window: Duration::from_secs(3600),
+            ..Default::default()
+        };
+        let monitor = AdapterHealthMonitor::new(thresholds);
+        monitor
+            .capture_baseline(AdapterType::Ethernet, HealthMetrics::new())
+            .await;
+        monitor.start_monitoring(AdapterType::Ethernet).await;
+
+        monitor.record_crash(AdapterType::Ethernet).await;
+
+        // Ten minutes of healthy traffic pushes the crash out of the
+        // evaluation window but not out of the crash window
+        for _ in 0..600 {
+            monitor.record_success(AdapterType::Ethernet, 10).await;
+            tokio::time::advance(Duration::from_secs(1)).await;
+        }
+        let (degraded, reason) = monitor.is_degraded(AdapterType::Ethernet).await;
+        assert!(degraded, "Earlier crash should still count");
+        assert!(reason.contains("Crashes detected"));
+
+        // Once the crash window has passed the adapter is healthy again
+        for _ in 0..3600 {
+            monitor.record_success(AdapterType::Ethernet, 10).await;
+            tokio::time::advance(Duration::from_secs(1)).await;
+        }
+        let (degraded, reason) = monitor.is_degraded(AdapterType::Ethernet).await;
+        assert!(!degraded, "Should not be degraded: {}", reason);
+    }
+
+    fn ewma_monitor() -> AdapterHealthMonitor {
+        AdapterHealthMonitor::new(DegradationThresholds {
+            detection_mode: DetectionMode::Ewma {
+                alpha: 0.1,
+                z_threshold: 3.0,
+            },
+            ..Default::default()
+        })
+    }
+
+    async fn start_ewma_monitoring(monitor: &AdapterHealthMonitor) {
+        let mut baseline = HealthMetrics::new();
+        for _ in 0..100 {
+            baseline.record_success(10);
+        }
+        monitor
+            .capture_baseline(AdapterType::Ethernet, baseline)
+            .await;
+        monitor.start_monitoring(AdapterType::Ethernet).await;
+    }
+
+    #[tokio::test]
+    async fn test_ewma_ignores_brief_latency_spike() {
+        let monitor = ewma_monitor();
+        start_ewma_monitoring(&monitor).await;
+
+        for _ in 0..20 {
+            monitor.record_success(AdapterType::Ethernet, 10).await;
+        }
+        // A single slow operation averages 10ms over the window (+90%),
+        // which would trip the raw threshold check
+        monitor.record_success(AdapterType::Ethernet, 200).await;
+
+        for _ in 0..20 {
+            let (degraded, reason) = monitor.is_degraded(AdapterType::Ethernet).await;
+            assert!(!degraded, "Spike should be absorbed: {}", reason);
+            monitor.record_success(AdapterType::Ethernet, 10).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ewma_detects_sustained_latency_increase() {
+        let monitor = ewma_monitor();
+        start_ewma_monitoring(&monitor).await;
+
+        for _ in 0..20 {
+            monitor.record_success(AdapterType::Ethernet, 10).await;
+        }
+        for _ in 0..30 {
+            monitor.record_success(AdapterType::Ethernet, 30).await;
+        }
+
+        let (degraded, reason) = monitor.is_degraded(AdapterType::Ethernet).await;
+        assert!(degraded, "Sustained increase should be detected");
+        assert!(reason.contains("Latency increased"));
+    }
+    #[tokio::test]
+    async fn test_pinned_adapter_refuses_upgrade() {
+        let registry = AdapterRegistry::new();
+        assert!(registry
+            .pin_version(AdapterType::Ethernet, SemanticVersion::new(1, 0, 0))
+            .await
+            .is_err());
+
+        registry
+            .register_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 0, 0),
+                "libmyriad_ethernet.so".to_string(),
+            )
+            .await
+            .unwrap();
+        registry
+            .pin_version(AdapterType::Ethernet, SemanticVersion::new(1, 0, 0))
+            .await
+            .unwrap();
+
+        let meta = registry.get_metadata(AdapterType::Ethernet).await.unwrap();
+        assert_eq!(meta.pinned_version, Some(SemanticVersion::new(1, 0, 0)));
+
+        assert!(registry
+            .hot_reload_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(2, 0, 0),
+            )
+            .await
+            .is_err());
+        let meta = registry.get_metadata(AdapterType::Ethernet).await.unwrap();
+        assert_eq!(meta.version, SemanticVersion::new(1, 0, 0));
+        assert_eq!(meta.status, AdapterLoadStatus::Active);
+
+        // Reloading the pinned version itself is still allowed
+        registry
+            .hot_reload_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 0, 0),
+            )
+            .await
+            .unwrap();
+
+        registry.unpin(AdapterType::Ethernet).await;
+        assert!(registry
+            .pinned_version(AdapterType::Ethernet)
+            .await
+            .is_none());
+        registry
+            .hot_reload_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(2, 0, 0),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pinned_adapter_skips_auto_rollback() {
+        let registry = AdapterRegistry::with_health_monitoring(DegradationThresholds::default());
+        registry
+            .register_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(1, 0, 0),
+                "libmyriad_ethernet.so".to_string(),
+            )
+            .await
+            .unwrap();
+        registry
+            .hot_reload_adapter(
+                AdapterType::Ethernet,
+                StubAdapter::boxed(),
+                SemanticVersion::new(2, 0, 0),
+            )
+            .await
+            .unwrap();
+        registry
+            .pin_version(AdapterType::Ethernet, SemanticVersion::new(2, 0, 0))
+            .await
+            .unwrap();
+        registry.enable_auto_rollback(AdapterType::Ethernet).await;
+
+        let monitor = registry.get_health_monitor().unwrap();
+        let mut baseline = HealthMetrics::new();
+        for _ in 0..100 {
+            baseline.record_success(10);
+        }
+        monitor
+            .capture_baseline(AdapterType::Ethernet, baseline)
+            .await;
+        monitor.start_monitoring(AdapterType::Ethernet).await;
+        for _ in 0..20 {
+            registry.record_success(AdapterType::Ethernet, 10).await;
+        }
+        registry.record_crash(AdapterType::Ethernet).await;
+        assert!(monitor.is_degraded(AdapterType::Ethernet).await.0);
+
+        let rolled_back = registry
+            .check_and_rollback(AdapterType::Ethernet)
+            .await
+            .unwrap();
+        assert!(!rolled_back);
+
+        let meta = registry.get_metadata(AdapterType::Ethernet).await.unwrap();
+        assert_eq!(meta.version, SemanticVersion::new(2, 0, 0));
+        assert!(
+            registry
+                .is_auto_rollback_enabled(AdapterType::Ethernet)
+                .await
+        );
+    }
 }
@@ -1,5 +1,6 @@
 //! Network type definitions
 
+use myriadmesh_routing::LinkMtu;
 use serde::{Deserialize, Serialize};
 
 // Re-export commonly used types from protocol
@@ -43,6 +44,16 @@ pub enum Address {
 
     /// Unknown/custom address
     Unknown(String),
+
+    // New variants go last: serialized addresses encode the variant index
+    /// WebSocket URL (e.g., "wss://relay.example.org/mesh")
+    WebSocket(String),
+
+    /// Tor v3 onion service and port (e.g., "<56 chars>.onion:4001")
+    Onion(String),
+
+    /// In-memory loopback endpoint (e.g., "loopback:1"), for tests
+    Loopback(String),
 }
 
 impl Address {
@@ -60,6 +71,9 @@ impl Address {
             Address::HfRadio(s) => s,
             Address::Dialup(s) => s,
             Address::I2P(s) => s,
+            Address::WebSocket(s) => s,
+            Address::Onion(s) => s,
+            Address::Loopback(s) => s,
             Address::Unknown(s) => s,
         }
     }
@@ -78,7 +92,10 @@ impl Address {
             Address::HfRadio(_) => AdapterType::Shortwave,
             Address::Dialup(_) => AdapterType::Dialup,
             Address::I2P(_) => AdapterType::I2P,
-            Address::Unknown(_) => AdapterType::Unknown,
+            Address::WebSocket(_) => AdapterType::WebSocket,
+            Address::Onion(_) => AdapterType::Tor,
+            // Test-only transport; not worth a wire identifier
+            Address::Loopback(_) | Address::Unknown(_) => AdapterType::Unknown,
         }
     }
 }
@@ -106,6 +123,128 @@ pub enum PowerConsumption {
     VeryHigh,
 }
 
+/// Transport feature an adapter may offer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Feature {
+    /// One send reaches every peer in range
+    Broadcast,
+    /// One send reaches a group of peers
+    Multicast,
+    /// Lost frames are retransmitted by the transport
+    ReliableDelivery,
+    /// Frames arrive in the order they were sent
+    OrderedDelivery,
+    /// Frames are encrypted on the wire by the transport itself
+    Encryption,
+    /// Peers cannot learn each other's network location
+    Anonymity,
+}
+
+impl Feature {
+    /// Every feature, in bit order
+    pub const ALL: [Feature; 6] = [
+        Feature::Broadcast,
+        Feature::Multicast,
+        Feature::ReliableDelivery,
+        Feature::OrderedDelivery,
+        Feature::Encryption,
+        Feature::Anonymity,
+    ];
+
+    /// Bit representing this feature in [`FeatureFlags`]
+    pub const fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// Set of [`Feature`]s, exchanged as a `u32` bitset
+///
+/// Bits this version does not know are kept, so flags received from a newer
+/// peer survive a round trip.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FeatureFlags(u32);
+
+impl FeatureFlags {
+    /// No features
+    pub const fn empty() -> Self {
+        FeatureFlags(0)
+    }
+
+    /// Flags from a raw bitset
+    pub const fn from_bits(bits: u32) -> Self {
+        FeatureFlags(bits)
+    }
+
+    /// Flags with exactly the given features set
+    pub const fn from_features(features: &[Feature]) -> Self {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < features.len() {
+            bits |= features[i].bit();
+            i += 1;
+        }
+        FeatureFlags(bits)
+    }
+
+    /// Raw bitset
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Whether no features are set
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether `feature` is set
+    pub const fn contains(self, feature: Feature) -> bool {
+        self.0 & feature.bit() != 0
+    }
+
+    /// Whether every feature in `other` is set
+    pub const fn contains_all(self, other: FeatureFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Features set in both, e.g. what two peers can agree on
+    pub const fn intersection(self, other: FeatureFlags) -> Self {
+        FeatureFlags(self.0 & other.0)
+    }
+
+    /// Set `feature`
+    pub fn insert(&mut self, feature: Feature) {
+        self.0 |= feature.bit();
+    }
+
+    /// Clear `feature`
+    pub fn remove(&mut self, feature: Feature) {
+        self.0 &= !feature.bit();
+    }
+
+    /// Known features that are set
+    pub fn iter(self) -> impl Iterator<Item = Feature> {
+        Feature::ALL
+            .into_iter()
+            .filter(move |feature| self.contains(*feature))
+    }
+}
+
+impl From<Feature> for FeatureFlags {
+    fn from(feature: Feature) -> Self {
+        FeatureFlags(feature.bit())
+    }
+}
+
+impl FromIterator<Feature> for FeatureFlags {
+    fn from_iter<I: IntoIterator<Item = Feature>>(iter: I) -> Self {
+        let mut flags = FeatureFlags::empty();
+        for feature in iter {
+            flags.insert(feature);
+        }
+        flags
+    }
+}
+
 /// Adapter capabilities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdapterCapabilities {
@@ -133,14 +272,38 @@ pub struct AdapterCapabilities {
     /// Cost per megabyte (USD)
     pub cost_per_mb: f64,
 
-    /// Supports broadcast
-    pub supports_broadcast: bool,
+    /// Transport features offered
+    pub features: FeatureFlags,
+}
 
-    /// Supports multicast
-    pub supports_multicast: bool,
+/// Fragment frames to the adapter's maximum message size
+impl From<&AdapterCapabilities> for LinkMtu {
+    fn from(capabilities: &AdapterCapabilities) -> Self {
+        LinkMtu::new(capabilities.max_message_size)
+    }
 }
 
 impl AdapterCapabilities {
+    /// Whether the adapter offers `feature`
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.features.contains(feature)
+    }
+
+    /// Whether the adapter offers every feature in `required`
+    pub fn supports_all(&self, required: FeatureFlags) -> bool {
+        self.features.contains_all(required)
+    }
+
+    /// Supports broadcast
+    pub fn supports_broadcast(&self) -> bool {
+        self.supports(Feature::Broadcast)
+    }
+
+    /// Supports multicast
+    pub fn supports_multicast(&self) -> bool {
+        self.supports(Feature::Multicast)
+    }
+
     /// Calculate adapter score for message routing
     ///
     /// Higher score = better adapter for this use case
@@ -222,6 +385,20 @@ mod tests {
         assert_eq!(addr.adapter_type(), AdapterType::I2P);
     }
 
+    #[test]
+    fn test_address_variant_indices_are_stable() {
+        let index = |addr: &Address| {
+            let encoded = bincode::serialize(addr).unwrap();
+            u32::from_le_bytes(encoded[..4].try_into().unwrap())
+        };
+
+        assert_eq!(index(&Address::I2P(String::new())), 10);
+        assert_eq!(index(&Address::Unknown(String::new())), 11);
+        assert_eq!(index(&Address::WebSocket(String::new())), 12);
+        assert_eq!(index(&Address::Onion(String::new())), 13);
+        assert_eq!(index(&Address::Loopback(String::new())), 14);
+    }
+
     #[test]
     fn test_adapter_score_emergency() {
         let caps = AdapterCapabilities {
@@ -233,14 +410,52 @@ mod tests {
             range_meters: 100.0,
             power_consumption: PowerConsumption::None,
             cost_per_mb: 0.0,
-            supports_broadcast: true,
-            supports_multicast: true,
+            features: FeatureFlags::from_features(&[Feature::Broadcast, Feature::Multicast]),
         };
 
         let score = caps.calculate_score(100, 255); // Emergency priority
         assert!(score > 0.5); // Should have high score for reliable adapter
     }
 
+    #[test]
+    fn test_fragmentation_fits_adapter_mtu() {
+        use crate::adapter::NetworkAdapter;
+        use crate::adapters::{LoRaAdapter, LoRaConfig};
+        use myriadmesh_protocol::{message::MessageId, Frame, MessageType, NodeId};
+        use myriadmesh_routing::fragment_frame;
+
+        let frame = Frame::new(
+            MessageType::Data,
+            NodeId::from_bytes([1u8; 64]),
+            NodeId::from_bytes([2u8; 64]),
+            vec![7u8; 2000],
+            MessageId::from_bytes([0u8; 16]),
+            0,
+        )
+        .unwrap();
+
+        let lora = LoRaAdapter::new(LoRaConfig::default());
+        let lora_caps = lora.get_capabilities();
+        let ethernet_caps = AdapterCapabilities {
+            adapter_type: AdapterType::Ethernet,
+            max_message_size: 1400,
+            typical_latency_ms: 5.0,
+            typical_bandwidth_bps: 100_000_000,
+            reliability: 0.99,
+            range_meters: 100.0,
+            power_consumption: PowerConsumption::None,
+            cost_per_mb: 0.0,
+            features: FeatureFlags::empty(),
+        };
+
+        let over_lora = fragment_frame(&frame, lora_caps).unwrap();
+        let over_ethernet = fragment_frame(&frame, &ethernet_caps).unwrap();
+        assert!(over_lora.len() > over_ethernet.len());
+        assert!(over_lora
+            .iter()
+            .all(|f| f.len() <= lora_caps.max_message_size));
+    }
+
     #[test]
     fn test_power_consumption_levels() {
         assert_eq!(PowerConsumption::None as u8, 0);
@@ -249,4 +464,43 @@ mod tests {
             PowerConsumption::VeryLow
         ));
     }
+
+    #[test]
+    fn test_feature_flags_set_operations() {
+        let mut flags = FeatureFlags::from(Feature::ReliableDelivery);
+        flags.insert(Feature::OrderedDelivery);
+        assert!(flags.contains(Feature::ReliableDelivery));
+        assert!(!flags.contains(Feature::Broadcast));
+
+        let required: FeatureFlags = [Feature::ReliableDelivery, Feature::OrderedDelivery]
+            .into_iter()
+            .collect();
+        assert_eq!(flags, required);
+        assert!(flags.contains_all(required));
+        assert!(flags.contains_all(FeatureFlags::empty()));
+
+        flags.remove(Feature::OrderedDelivery);
+        assert!(!flags.contains_all(required));
+        assert_eq!(
+            flags.iter().collect::<Vec<_>>(),
+            vec![Feature::ReliableDelivery]
+        );
+    }
+
+    #[test]
+    fn test_feature_flags_keep_unknown_bits() {
+        let from_newer_peer = FeatureFlags::from_bits(Feature::Broadcast.bit() | 1 << 31);
+        assert!(from_newer_peer.contains(Feature::Broadcast));
+        assert_eq!(from_newer_peer.iter().count(), 1);
+
+        let json = serde_json::to_string(&from_newer_peer).unwrap();
+        let decoded: FeatureFlags = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.bits(), from_newer_peer.bits());
+
+        let ours = FeatureFlags::from_features(&[Feature::Broadcast, Feature::Encryption]);
+        assert_eq!(
+            ours.intersection(from_newer_peer),
+            FeatureFlags::from(Feature::Broadcast)
+        );
+    }
 }
@@ -0,0 +1,363 @@
+//! Coordinated adapter reload scheduling across neighbors
+//!
+//! When a new adapter version ships, every node running it wants to reload
+//! at once. If all neighbors on a link reload together the link goes dark.
+//! The `UpdateCoordinator` staggers reloads: nodes that need the update are
+//! spread over fixed-length windows so that at most a configured fraction of
+//! the neighbors using an adapter reload in the same window.
+//!
+//! Assignment is deterministic (ordered by NodeId, windows aligned to the
+//! Unix epoch), so every node derives the same schedule from the same
+//! version reports without a negotiation round. Per-node scheduling around
+//! peer downtime lives in `myriadmesh-updates`.
+
+use crate::version_tracking::{
+    reports_version_penalty, AdapterComponentStatus, AdapterVersionInfo, ComponentManifest,
+};
+use myriadmesh_protocol::{types::AdapterType, NodeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Configuration for staggered reloads
+#[derive(Debug, Clone)]
+pub struct UpdateCoordinatorConfig {
+    /// Maximum fraction of an adapter's neighbors reloading in one window
+    pub max_concurrent_fraction: f64,
+
+    /// Length of each reload window
+    pub window_duration: Duration,
+}
+
+impl Default for UpdateCoordinatorConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_fraction: 0.25,
+            window_duration: Duration::from_secs(600), // 10 minutes
+        }
+    }
+}
+
+/// A reload window assigned to a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReloadWindow {
+    /// Slot index within the rotation (0-based)
+    pub slot: usize,
+
+    /// Number of slots in the rotation
+    pub slot_count: usize,
+
+    /// Window start (Unix timestamp, seconds)
+    pub start: u64,
+
+    /// Window end (Unix timestamp, seconds, exclusive)
+    pub end: u64,
+}
+
+impl ReloadWindow {
+    /// Check if a timestamp falls inside this window
+    pub fn contains(&self, now: u64) -> bool {
+        (self.start..self.end).contains(&now)
+    }
+}
+
+/// Staggers adapter reloads across neighbors
+pub struct UpdateCoordinator {
+    /// This node
+    local_node: NodeId,
+
+    /// Configuration
+    config: UpdateCoordinatorConfig,
+
+    /// Latest version report per neighbor and adapter
+    reports: Arc<RwLock<HashMap<AdapterType, HashMap<NodeId, AdapterVersionInfo>>>>,
+}
+
+impl UpdateCoordinator {
+    /// Create a coordinator for the local node
+    pub fn new(local_node: NodeId, config: UpdateCoordinatorConfig) -> Self {
+        Self {
+            local_node,
+            config,
+            reports: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Record a neighbor's version report for one adapter
+    pub async fn record_neighbor_report(&self, neighbor: NodeId, info: AdapterVersionInfo) {
+        if neighbor == self.local_node {
+            return;
+        }
+        let mut reports = self.reports.write().await;
+        reports
+            .entry(info.adapter_type)
+            .or_default()
+            .insert(neighbor, info);
+    }
+
+    /// Record every adapter in a neighbor's component manifest
+    pub async fn record_manifest(&self, manifest: &ComponentManifest) {
+        for info in manifest.adapters.values() {
+            self.record_neighbor_report(manifest.node_id, info.clone())
+                .await;
+        }
+    }
+
+    /// Forget a neighbor that is no longer reachable
+    pub async fn remove_neighbor(&self, neighbor: &NodeId) {
+        let mut reports = self.reports.write().await;
+        for by_node in reports.values_mut() {
+            by_node.remove(neighbor);
+        }
+    }
+
+    /// Version penalty of every neighbor with reports on file
+    ///
+    /// Feed these into the DHT's `RoutingTable::set_version_penalty` (and
+    /// `ReputationManager::set_version_penalty`) so neighbors running
+    /// vulnerable adapters are trusted less for routing.
+    pub async fn version_penalties(&self) -> HashMap<NodeId, f64> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        self.version_penalties_at(now).await
+    }
+
+    /// Version penalty of every neighbor as of `now` (Unix seconds)
+    pub async fn version_penalties_at(&self, now: u64) -> HashMap<NodeId, f64> {
+        let reports = self.reports.read().await;
+        let mut by_neighbor: HashMap<NodeId, Vec<&AdapterVersionInfo>> = HashMap::new();
+        for (node_id, info) in reports.values().flatten() {
+            by_neighbor.entry(*node_id).or_default().push(info);
+        }
+        by_neighbor
+            .into_iter()
+            .map(|(node_id, infos)| (node_id, reports_version_penalty(infos, now)))
+            .collect()
+    }
+
+    /// Slot assignment for every node that will reload an adapter
+    ///
+    /// Includes the local node, which is assumed to be reloading. Neighbors
+    /// already on the current version are counted towards the total but are
+    /// not assigned a slot. Returns the assignment and the slot count.
+    pub async fn assignments(&self, adapter_type: AdapterType) -> (HashMap<NodeId, usize>, usize) {
+        let reports = self.reports.read().await;
+        let by_node = reports.get(&adapter_type);
+
+        let neighbor_count = by_node.map(|n| n.len()).unwrap_or(0);
+        let total = neighbor_count + 1;
+
+        let mut reloading: Vec<NodeId> = by_node
+            .into_iter()
+            .flatten()
+            .filter(|(_, info)| info.status != AdapterComponentStatus::Current)
+            .map(|(node_id, _)| *node_id)
+            .collect();
+        reloading.push(self.local_node);
+        reloading.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+
+        let per_slot =
+            ((self.config.max_concurrent_fraction * total as f64).floor() as usize).max(1);
+        let slot_count = reloading.len().div_ceil(per_slot);
+
+        let assignments = reloading
+            .into_iter()
+            .enumerate()
+            .map(|(i, node_id)| (node_id, i / per_slot))
+            .collect();
+
+        (assignments, slot_count)
+    }
+
+    /// Next window in which this node may reload an adapter
+    ///
+    /// Returns the current window if it is already open.
+    pub async fn next_window(&self, adapter_type: AdapterType) -> ReloadWindow {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.next_window_at(adapter_type, now).await
+    }
+
+    /// Next window for this node at or after `now` (Unix seconds)
+    pub async fn next_window_at(&self, adapter_type: AdapterType, now: u64) -> ReloadWindow {
+        let (assignments, slot_count) = self.assignments(adapter_type).await;
+        let slot = assignments.get(&self.local_node).copied().unwrap_or(0);
+        window_for_slot(slot, slot_count, self.config.window_duration, now)
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &UpdateCoordinatorConfig {
+        &self.config
+    }
+}
+
+/// First occurrence of `slot` in the epoch-aligned rotation that ends after `now`
+fn window_for_slot(slot: usize, slot_count: usize, window: Duration, now: u64) -> ReloadWindow {
+    let length = window.as_secs().max(1);
+    let slot_count = slot_count.max(1) as u64;
+    let slot = slot as u64 % slot_count;
+
+    let current = now / length;
+    let offset = (slot + slot_count - current % slot_count) % slot_count;
+    let index = current + offset;
+
+    ReloadWindow {
+        slot: slot as usize,
+        slot_count: slot_count as usize,
+        start: index * length,
+        end: (index + 1) * length,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version_tracking::SemanticVersion;
+    use myriadmesh_protocol::types::NODE_ID_SIZE;
+    use std::collections::HashSet;
+
+    fn node(byte: u8) -> NodeId {
+        NodeId::from_bytes([byte; NODE_ID_SIZE])
+    }
+
+    fn report(status: AdapterComponentStatus) -> AdapterVersionInfo {
+        AdapterVersionInfo {
+            adapter_type: AdapterType::LoRaWAN,
+            library: "sx127x".to_string(),
+            version: SemanticVersion::new(1, 0, 0),
+            latest_version: Some(SemanticVersion::new(1, 1, 0)),
+            days_since_update: 0,
+            known_cves: Vec::new(),
+            status,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reloads_staggered_under_cap() {
+        let coordinator = UpdateCoordinator::new(node(0), UpdateCoordinatorConfig::default());
+
+        let neighbors = 15;
+        for i in 1..=neighbors {
+            coordinator
+                .record_neighbor_report(node(i), report(AdapterComponentStatus::SecurityUpdate))
+                .await;
+        }
+
+        let (assignments, slot_count) = coordinator.assignments(AdapterType::LoRaWAN).await;
+        assert_eq!(assignments.len(), neighbors as usize + 1);
+
+        let total = neighbors as usize + 1;
+        let mut per_slot: HashMap<usize, usize> = HashMap::new();
+        for slot in assignments.values() {
+            *per_slot.entry(*slot).or_default() += 1;
+        }
+        assert!(per_slot.len() >= 4);
+        assert_eq!(per_slot.len(), slot_count);
+        assert!(per_slot
+            .values()
+            .all(|&count| count as f64 <= 0.25 * total as f64));
+
+        // Each slot's next occurrence is a distinct, non-overlapping window
+        let length = coordinator.config().window_duration.as_secs();
+        let now = 1_700_000_000;
+        let windows: Vec<ReloadWindow> = (0..slot_count)
+            .map(|slot| window_for_slot(slot, slot_count, Duration::from_secs(length), now))
+            .collect();
+        let starts: HashSet<u64> = windows.iter().map(|w| w.start).collect();
+        assert_eq!(starts.len(), slot_count);
+        for a in &windows {
+            assert_eq!(a.end - a.start, length);
+            for b in &windows {
+                if a.slot != b.slot {
+                    assert!(a.end <= b.start || b.end <= a.start);
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_current_neighbors_not_scheduled() {
+        let coordinator = UpdateCoordinator::new(node(0), UpdateCoordinatorConfig::default());
+        for i in 1..=7 {
+            coordinator
+                .record_neighbor_report(node(i), report(AdapterComponentStatus::Current))
+                .await;
+        }
+        coordinator
+            .record_neighbor_report(node(8), report(AdapterComponentStatus::CriticalUpdate))
+            .await;
+
+        // 9 nodes in total allow 2 per window; only 2 need reloading
+        let (assignments, slot_count) = coordinator.assignments(AdapterType::LoRaWAN).await;
+        assert_eq!(assignments.len(), 2);
+        assert_eq!(slot_count, 1);
+
+        // Other adapters are scheduled independently
+        let (ethernet, _) = coordinator.assignments(AdapterType::Ethernet).await;
+        assert_eq!(ethernet.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_next_window_rotation() {
+        let config = UpdateCoordinatorConfig {
+            max_concurrent_fraction: 0.25,
+            window_duration: Duration::from_secs(100),
+        };
+        // Highest NodeId sorts last, so the local node gets the final slot
+        let coordinator = UpdateCoordinator::new(node(0xFF), config);
+        for i in 1..=3 {
+            coordinator
+                .record_neighbor_report(node(i), report(AdapterComponentStatus::MinorUpdate))
+                .await;
+        }
+
+        // 4 nodes, 1 per window: slot 3 of 4
+        let window = coordinator
+            .next_window_at(AdapterType::LoRaWAN, 1_000)
+            .await;
+        assert_eq!((window.slot, window.slot_count), (3, 4));
+        assert_eq!((window.start, window.end), (1_100, 1_200));
+
+        // Inside the window it is returned as-is
+        let inside = coordinator
+            .next_window_at(AdapterType::LoRaWAN, 1_150)
+            .await;
+        assert_eq!(inside, window);
+        assert!(inside.contains(1_150));
+
+        // Just after it closes, the next rotation
+        let after = coordinator
+            .next_window_at(AdapterType::LoRaWAN, 1_200)
+            .await;
+        assert_eq!(after.start, 1_500);
+
+        coordinator.remove_neighbor(&node(1)).await;
+        let (assignments, _) = coordinator.assignments(AdapterType::LoRaWAN).await;
+        assert!(!assignments.contains_key(&node(1)));
+    }
+
+    #[tokio::test]
+    async fn test_version_penalties_per_neighbor() {
+        let coordinator = UpdateCoordinator::new(node(0), UpdateCoordinatorConfig::default());
+        coordinator
+            .record_neighbor_report(node(1), report(AdapterComponentStatus::Current))
+            .await;
+        coordinator
+            .record_neighbor_report(node(2), report(AdapterComponentStatus::Deprecated))
+            .await;
+        let mut ethernet = report(AdapterComponentStatus::Unsupported);
+        ethernet.adapter_type = AdapterType::Ethernet;
+        coordinator.record_neighbor_report(node(2), ethernet).await;
+
+        let penalties = coordinator.version_penalties().await;
+        assert_eq!(penalties.len(), 2);
+        assert_eq!(penalties[&node(1)], 0.0);
+        // 0.5 + 1.0, capped
+        assert_eq!(penalties[&node(2)], 0.95);
+    }
+}
@@ -135,6 +135,21 @@ pub struct CveInfo {
 
     /// Description
     pub description: String,
+
+    /// Public disclosure time (Unix timestamp, seconds)
+    ///
+    /// Penalties grow with time since disclosure. When unknown, the adapter's
+    /// `days_since_update` is used instead.
+    #[serde(default)]
+    pub disclosed_at: Option<u64>,
+}
+
+impl CveInfo {
+    /// Whole days the CVE has been public as of `now` (Unix seconds)
+    pub fn days_since_disclosure(&self, now: u64) -> Option<u32> {
+        self.disclosed_at
+            .map(|disclosed| (now.saturating_sub(disclosed) / 86400) as u32)
+    }
 }
 
 /// CVE severity levels
@@ -166,51 +181,14 @@ pub struct AdvisoryCompliance {
 ///
 /// Returns a penalty factor between 0.0 (no penalty) and 0.95 (maximum penalty)
 pub fn calculate_version_penalty(manifest: &ComponentManifest) -> f64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
     let mut penalty = 0.0;
 
     for info in manifest.adapters.values() {
-        // Base penalty by status
-        let status_penalty = match info.status {
-            AdapterComponentStatus::Current => 0.0,
-            AdapterComponentStatus::MinorUpdate => {
-                // Light penalty that increases with age
-                0.01 * (info.days_since_update as f64 / 30.0).min(5.0)
-            }
-            AdapterComponentStatus::SecurityUpdate => {
-                // Moderate penalty that increases with time
-                0.10 * (1.0 + info.days_since_update as f64 / 7.0)
-            }
-            AdapterComponentStatus::CriticalUpdate => {
-                // Heavy penalty that increases rapidly
-                0.30 * (1.0 + info.days_since_update as f64 / 3.0)
-            }
-            AdapterComponentStatus::Deprecated => {
-                // Severe fixed penalty
-                0.50
-            }
-            AdapterComponentStatus::Unsupported => {
-                // Maximum fixed penalty
-                1.00
-            }
-        };
-
-        penalty += status_penalty;
-
-        // Additional penalty for known CVEs
-        for cve in &info.known_cves {
-            let cve_base_penalty = match cve.severity {
-                CveSeverity::Low => 0.05,
-                CveSeverity::Medium => 0.15,
-                CveSeverity::High => 0.40,
-                CveSeverity::Critical => 0.80,
-            };
-
-            // Penalty increases with time unpatched
-            let days_unpatched = info.days_since_update;
-            let time_multiplier = 1.0 + (days_unpatched as f64 / 7.0).min(10.0);
-
-            penalty += cve_base_penalty * time_multiplier;
-        }
+        penalty += adapter_version_penalty(info, now);
     }
 
     // Security advisory non-compliance
@@ -218,11 +196,6 @@ pub fn calculate_version_penalty(manifest: &ComponentManifest) -> f64 {
         if !advisory.compliant {
             // Check if past deadline
             if let Some(deadline) = advisory.deadline {
-                let now = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs();
-
                 if now > deadline {
                     // Heavy penalty for missing deadline
                     let days_overdue = ((now - deadline) / 86400) as f64;
@@ -241,6 +214,72 @@ pub fn calculate_version_penalty(manifest: &ComponentManifest) -> f64 {
     penalty.min(0.95)
 }
 
+/// Penalty for a single adapter, without the overall cap
+fn adapter_version_penalty(info: &AdapterVersionInfo, now: u64) -> f64 {
+    // Base penalty by status
+    let status_penalty = match info.status {
+        AdapterComponentStatus::Current => 0.0,
+        AdapterComponentStatus::MinorUpdate => {
+            // Light penalty that increases with age
+            0.01 * (info.days_since_update as f64 / 30.0).min(5.0)
+        }
+        AdapterComponentStatus::SecurityUpdate => {
+            // Moderate penalty that increases with time
+            0.10 * (1.0 + info.days_since_update as f64 / 7.0)
+        }
+        AdapterComponentStatus::CriticalUpdate => {
+            // Heavy penalty that increases rapidly
+            0.30 * (1.0 + info.days_since_update as f64 / 3.0)
+        }
+        AdapterComponentStatus::Deprecated => {
+            // Severe fixed penalty
+            0.50
+        }
+        AdapterComponentStatus::Unsupported => {
+            // Maximum fixed penalty
+            1.00
+        }
+    };
+
+    let mut penalty = status_penalty;
+
+    // Additional penalty for known CVEs
+    for cve in &info.known_cves {
+        let cve_base_penalty = match cve.severity {
+            CveSeverity::Low => 0.05,
+            CveSeverity::Medium => 0.15,
+            CveSeverity::High => 0.40,
+            CveSeverity::Critical => 0.80,
+        };
+
+        // Penalty increases with time unpatched since public disclosure
+        let days_unpatched = cve
+            .days_since_disclosure(now)
+            .unwrap_or(info.days_since_update);
+        let time_multiplier = 1.0 + (days_unpatched as f64 / 7.0).min(10.0);
+
+        penalty += cve_base_penalty * time_multiplier;
+    }
+
+    penalty
+}
+
+/// Calculate reputation penalty from a neighbor's adapter version reports
+///
+/// Same scale as [`calculate_version_penalty`] for nodes known only through
+/// `AdapterVersionInfo` reports rather than a full manifest, so advisory
+/// compliance is not considered. `now` is Unix seconds, used to age CVEs.
+pub fn reports_version_penalty<'a, I>(reports: I, now: u64) -> f64
+where
+    I: IntoIterator<Item = &'a AdapterVersionInfo>,
+{
+    let penalty: f64 = reports
+        .into_iter()
+        .map(|info| adapter_version_penalty(info, now))
+        .sum();
+    penalty.min(0.95)
+}
+
 impl ComponentManifest {
     /// Create a new manifest for this node
     pub fn new(node_id: NodeId, core_version: SemanticVersion) -> Self {
@@ -584,6 +623,7 @@ mod tests {
                 cvss_score: 9.8,
                 patched_in: SemanticVersion::new(0, 11, 0),
                 description: "Critical RCE vulnerability".to_string(),
+                disclosed_at: None,
             }],
             status: AdapterComponentStatus::CriticalUpdate,
         });
@@ -681,6 +721,7 @@ mod tests {
                 cvss_score: 8.5,
                 patched_in: SemanticVersion::new(0, 11, 0),
                 description: "High severity issue".to_string(),
+                disclosed_at: None,
             }],
             status: AdapterComponentStatus::CriticalUpdate,
         });
@@ -728,6 +769,7 @@ mod tests {
                 cvss_score: 9.8,
                 patched_in: SemanticVersion::new(0, 11, 0),
                 description: "Critical issue".to_string(),
+                disclosed_at: None,
             }],
             status: AdapterComponentStatus::CriticalUpdate,
         });
@@ -746,4 +788,55 @@ mod tests {
             .await;
         assert!(!medium_plus.is_empty());
     }
+
+    #[test]
+    fn test_cve_penalty_grows_with_disclosure_age() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let penalty_after_days = |days: u64| {
+            let mut manifest = ComponentManifest::new(
+                NodeId::from_bytes([0u8; 64]),
+                SemanticVersion::new(1, 0, 0),
+            );
+            manifest.add_adapter(AdapterVersionInfo {
+                adapter_type: AdapterType::Bluetooth,
+                library: "btleplug".to_string(),
+                version: SemanticVersion::new(0, 10, 0),
+                latest_version: Some(SemanticVersion::new(0, 10, 1)),
+                // Same for both: only the CVE's age differs
+                days_since_update: 0,
+                known_cves: vec![CveInfo {
+                    cve_id: "CVE-2024-5678".to_string(),
+                    severity: CveSeverity::Low,
+                    cvss_score: 3.1,
+                    patched_in: SemanticVersion::new(0, 10, 1),
+                    description: "Information disclosure".to_string(),
+                    disclosed_at: Some(now - days * 86400 - 60),
+                }],
+                status: AdapterComponentStatus::Current,
+            });
+            calculate_version_penalty(&manifest)
+        };
+
+        let fresh = penalty_after_days(1);
+        let stale = penalty_after_days(60);
+
+        // 0.05 * (1 + 1/7) vs 0.05 * (1 + 60/7)
+        assert!((fresh - 0.05 * (1.0 + 1.0 / 7.0)).abs() < 1e-9);
+        assert!((stale - 0.05 * (1.0 + 60.0 / 7.0)).abs() < 1e-9);
+        assert!(stale > fresh * 5.0);
+
+        let cve = CveInfo {
+            cve_id: "CVE-2024-0001".to_string(),
+            severity: CveSeverity::Low,
+            cvss_score: 1.0,
+            patched_in: SemanticVersion::new(1, 0, 0),
+            description: String::new(),
+            disclosed_at: None,
+        };
+        assert_eq!(cve.days_since_disclosure(now), None);
+    }
 }
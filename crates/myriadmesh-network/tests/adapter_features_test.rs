@@ -0,0 +1,153 @@
+//! Feature flags reported by each built-in adapter
+//!
+//! The router selects adapters by required feature set, so the flags each
+//! adapter advertises are part of its contract.
+
+use std::sync::Arc;
+
+use myriadmesh_crypto::identity::NodeIdentity;
+use myriadmesh_network::adapters::{
+    AprsAdapter, AprsConfig, DialupAdapter, DialupConfig, FrsGmrsAdapter, FrsGmrsConfig,
+    HfRadioAdapter, HfRadioConfig, LoRaAdapter, LoRaConfig, WifiHalowAdapter, WifiHalowConfig,
+};
+use myriadmesh_network::{
+    AdapterCapabilities, BleAdapter, BleConfig, BluetoothAdapter, BluetoothConfig, CellularAdapter,
+    CellularConfig, EthernetAdapter, EthernetConfig, Feature, FeatureFlags, I2pAdapter,
+    LoopbackAdapter, LoopbackConfig, NetworkAdapter, TorAdapter, WebSocketAdapter,
+};
+use myriadmesh_protocol::types::NODE_ID_SIZE;
+use myriadmesh_protocol::NodeId;
+
+use Feature::*;
+
+fn node_id() -> NodeId {
+    NodeId::from_bytes([1u8; NODE_ID_SIZE])
+}
+
+/// Capabilities of every built-in adapter with its default configuration
+fn builtin_capabilities() -> Vec<(&'static str, AdapterCapabilities)> {
+    myriadmesh_crypto::init().unwrap();
+    let identity = Arc::new(NodeIdentity::generate().unwrap());
+
+    fn caps(adapter: &dyn NetworkAdapter) -> AdapterCapabilities {
+        adapter.get_capabilities().clone()
+    }
+
+    vec![
+        (
+            "ethernet",
+            caps(&EthernetAdapter::new(identity, EthernetConfig::default())),
+        ),
+        ("websocket", caps(&WebSocketAdapter::new_default(node_id()))),
+        ("tor", caps(&TorAdapter::new_default(node_id()))),
+        ("i2p", caps(&I2pAdapter::new())),
+        (
+            "bluetooth",
+            caps(&BluetoothAdapter::new(BluetoothConfig::default())),
+        ),
+        ("ble", caps(&BleAdapter::new(BleConfig::default()))),
+        (
+            "cellular",
+            caps(&CellularAdapter::new(CellularConfig::default())),
+        ),
+        ("dialup", caps(&DialupAdapter::new(DialupConfig::default()))),
+        ("lora", caps(&LoRaAdapter::new(LoRaConfig::default()))),
+        (
+            "wifi_halow",
+            caps(&WifiHalowAdapter::new(WifiHalowConfig::default())),
+        ),
+        ("aprs", caps(&AprsAdapter::new(AprsConfig::default()))),
+        (
+            "frsgmrs",
+            caps(&FrsGmrsAdapter::new(FrsGmrsConfig::default())),
+        ),
+        (
+            "hf_radio",
+            caps(&HfRadioAdapter::new(HfRadioConfig::default())),
+        ),
+        (
+            "loopback",
+            caps(&LoopbackAdapter::pair(LoopbackConfig::default()).0),
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn test_builtin_adapters_report_expected_features() {
+    let expected: &[(&str, &[Feature])] = &[
+        ("ethernet", &[Multicast]),
+        ("websocket", &[ReliableDelivery, OrderedDelivery]),
+        (
+            "tor",
+            &[ReliableDelivery, OrderedDelivery, Encryption, Anonymity],
+        ),
+        (
+            "i2p",
+            &[ReliableDelivery, OrderedDelivery, Encryption, Anonymity],
+        ),
+        ("bluetooth", &[ReliableDelivery, OrderedDelivery]),
+        ("ble", &[Broadcast]),
+        ("cellular", &[ReliableDelivery, OrderedDelivery]),
+        ("dialup", &[]),
+        ("lora", &[Broadcast, Multicast]),
+        ("wifi_halow", &[Broadcast, Multicast]),
+        ("aprs", &[Broadcast, Multicast]),
+        ("frsgmrs", &[Broadcast]),
+        ("hf_radio", &[Broadcast, ReliableDelivery]),
+        ("loopback", &[ReliableDelivery, OrderedDelivery]),
+    ];
+
+    let actual = builtin_capabilities();
+    assert_eq!(actual.len(), expected.len());
+    for ((name, caps), (expected_name, features)) in actual.iter().zip(expected) {
+        assert_eq!(name, expected_name);
+        assert_eq!(
+            caps.features,
+            FeatureFlags::from_features(features),
+            "unexpected features for {}",
+            name
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_supports_matches_legacy_accessors() {
+    for (name, caps) in builtin_capabilities() {
+        assert_eq!(
+            caps.supports(Broadcast),
+            caps.supports_broadcast(),
+            "{}",
+            name
+        );
+        assert_eq!(
+            caps.supports(Multicast),
+            caps.supports_multicast(),
+            "{}",
+            name
+        );
+        for feature in Feature::ALL {
+            assert_eq!(caps.supports(feature), caps.features.contains(feature));
+            assert_eq!(
+                caps.supports_all(FeatureFlags::from(feature)),
+                caps.supports(feature)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_multicast_follows_ethernet_config() {
+    myriadmesh_crypto::init().unwrap();
+    let identity = Arc::new(NodeIdentity::generate().unwrap());
+    let adapter = EthernetAdapter::new(
+        identity,
+        EthernetConfig {
+            enable_multicast: false,
+            ..Default::default()
+        },
+    );
+
+    let caps = adapter.get_capabilities();
+    assert!(!caps.supports_multicast());
+    assert!(caps.features.is_empty());
+}
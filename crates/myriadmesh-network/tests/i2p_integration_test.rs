@@ -81,8 +81,8 @@ fn test_i2p_adapter_capabilities() {
     assert!(caps.reliability > 0.9); // High reliability
     assert!(caps.typical_latency_ms > 1000.0); // High latency
     assert_eq!(caps.cost_per_mb, 0.0); // Free
-    assert!(!caps.supports_broadcast); // No broadcast support
-    assert!(!caps.supports_multicast); // No multicast support
+    assert!(!caps.supports_broadcast()); // No broadcast support
+    assert!(!caps.supports_multicast()); // No multicast support
 }
 
 /// Test I2P adapter address handling
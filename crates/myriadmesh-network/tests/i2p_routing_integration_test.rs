@@ -57,6 +57,7 @@ fn test_i2p_route_cost_calculation() {
         loss: 1.0,        // Packet loss is important
         jitter: 0.5,      // Jitter less important
         utilization: 0.5, // Utilization less important
+        ..CostWeights::default()
     };
 
     // Ethernet link metrics (fast, low loss)
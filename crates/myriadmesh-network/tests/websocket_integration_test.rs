@@ -0,0 +1,156 @@
+//! Integration tests for the WebSocket network adapter
+//!
+//! These run a listening adapter in-process and exchange frames with a
+//! client-mode adapter over a real loopback WebSocket connection.
+
+use myriadmesh_network::adapters::websocket::MAX_WS_MESSAGE_SIZE;
+use myriadmesh_network::{
+    AdapterManager, Address, NetworkAdapter, WebSocketAdapter, WebSocketConfig,
+};
+use myriadmesh_protocol::message::MessageId;
+use myriadmesh_protocol::types::{AdapterType, NODE_ID_SIZE};
+use myriadmesh_protocol::{Frame, MessageType, NodeId};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_bytes([byte; NODE_ID_SIZE])
+}
+
+fn test_frame(source: NodeId, destination: NodeId, payload: &[u8]) -> Frame {
+    Frame::new(
+        MessageType::Data,
+        source,
+        destination,
+        payload.to_vec(),
+        MessageId::from_bytes([7u8; 16]),
+        1_700_000_000_000,
+    )
+    .unwrap()
+}
+
+async fn listening_adapter(id: NodeId) -> WebSocketAdapter {
+    let config = WebSocketConfig {
+        listen_addr: Some("127.0.0.1:0".to_string()),
+        ..Default::default()
+    };
+    let mut adapter = WebSocketAdapter::new(id, config);
+    adapter.initialize().await.unwrap();
+    adapter.start().await.unwrap();
+    adapter
+}
+
+/// Test connect, send and receive in both directions over one connection
+#[tokio::test]
+async fn test_websocket_connect_send_receive() {
+    let server_id = node_id(1);
+    let client_id = node_id(2);
+
+    let server = listening_adapter(server_id).await;
+    let server_address = server.get_local_address().unwrap();
+    assert!(server_address.as_str().starts_with("ws://127.0.0.1:"));
+
+    let mut client = WebSocketAdapter::new_default(client_id);
+    client.initialize().await.unwrap();
+    assert!(client.get_local_address().is_none());
+
+    // Client dials the server on first send
+    let outbound = test_frame(client_id, server_id, b"hello over websocket");
+    client.send(&server_address, &outbound).await.unwrap();
+
+    let (from, received) = server.receive(2000).await.unwrap();
+    assert_eq!(received.payload, outbound.payload);
+    assert_eq!(received.header.source, client_id);
+    assert!(matches!(from, Address::WebSocket(_)));
+
+    // Server replies over the accepted connection
+    let reply = test_frame(server_id, client_id, b"hello back");
+    server.send(&from, &reply).await.unwrap();
+
+    let (reply_from, received_reply) = client.receive(2000).await.unwrap();
+    assert_eq!(received_reply.payload, reply.payload);
+    assert_eq!(reply_from, server_address);
+    assert_eq!(client.connection_count().await, 1);
+
+    // Both sides learned the other as a peer
+    let server_peers = server.discover_peers().await.unwrap();
+    assert_eq!(server_peers.len(), 1);
+    assert_eq!(server_peers[0].node_id, client_id);
+    let client_peers = client.discover_peers().await.unwrap();
+    assert_eq!(client_peers[0].node_id, server_id);
+}
+
+/// Test that receive times out cleanly with nothing queued
+#[tokio::test]
+async fn test_websocket_receive_timeout() {
+    let server = listening_adapter(node_id(1)).await;
+    assert!(server.receive(50).await.is_err());
+}
+
+/// Test that connecting to a closed port fails rather than hanging
+#[tokio::test]
+async fn test_websocket_connect_refused() {
+    let mut server = listening_adapter(node_id(1)).await;
+    let address = server.get_local_address().unwrap();
+    server.stop().await.unwrap();
+
+    let client = WebSocketAdapter::new_default(node_id(2));
+    let frame = test_frame(node_id(2), node_id(1), b"nobody home");
+    assert!(client.send(&address, &frame).await.is_err());
+}
+
+/// Test that the WebSocket adapter registers with the manager
+#[tokio::test]
+async fn test_register_websocket_adapter_with_manager() {
+    let mut manager = AdapterManager::new();
+
+    let adapter = Box::new(WebSocketAdapter::new(
+        node_id(1),
+        WebSocketConfig {
+            listen_addr: Some("127.0.0.1:0".to_string()),
+            ..Default::default()
+        },
+    )) as Box<dyn NetworkAdapter>;
+
+    manager
+        .register_adapter("websocket".to_string(), adapter)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        manager.find_adapter_by_type(AdapterType::WebSocket),
+        Some("websocket".to_string())
+    );
+
+    let caps = manager.get_capabilities("websocket").unwrap();
+    assert_eq!(caps.adapter_type, AdapterType::WebSocket);
+    assert!(!caps.supports_broadcast());
+
+    manager.stop_all().await.unwrap();
+}
+
+/// Test that a listening adapter drops peers sending oversized messages
+#[tokio::test]
+async fn test_websocket_rejects_oversized_message() {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+    let server = listening_adapter(node_id(1)).await;
+    let url = server.get_local_address().unwrap().as_str().to_string();
+
+    let (mut raw, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .unwrap();
+    let oversized = vec![0u8; MAX_WS_MESSAGE_SIZE + 1];
+    let _ = raw.send(WsMessage::Binary(oversized)).await;
+
+    // The server closes the connection instead of buffering the message
+    let closed = tokio::time::timeout(std::time::Duration::from_secs(2), async {
+        while let Some(Ok(message)) = raw.next().await {
+            if message.is_close() {
+                break;
+            }
+        }
+    })
+    .await;
+    assert!(closed.is_ok());
+    assert!(server.receive(100).await.is_err());
+}
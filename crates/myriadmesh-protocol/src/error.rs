@@ -33,6 +33,9 @@ pub enum ProtocolError {
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
 
+    #[error("Unsupported protocol version: {0}")]
+    UnsupportedVersion(u8),
+
     #[error("TTL exceeded")]
     TtlExceeded,
 
@@ -3,9 +3,9 @@
 //! Frames are the on-wire representation of messages, following the MyriadMesh
 //! Protocol Specification (docs/protocol/specification.md).
 //!
-//! Frame Structure (163-byte header + payload + 64-byte signature):
+//! Frame Structure (163-byte fixed header + extensions + payload + 64-byte signature):
 //! - Magic (4 bytes): 0x4D594D53 ("MYMS")
-//! - Version (1 byte): Protocol version (0x01)
+//! - Version (1 byte): Protocol version (0x02)
 //! - Flags (1 byte): Message flags bitfield
 //! - Message Type (1 byte): Type of message
 //! - Priority (1 byte): Message priority (0-255)
@@ -15,22 +15,49 @@
 //! - Source Node ID (64 bytes): Sender's node ID (SECURITY C6: increased for collision resistance)
 //! - Dest Node ID (64 bytes): Recipient's node ID (SECURITY C6: increased for collision resistance)
 //! - Timestamp (8 bytes): Unix timestamp in milliseconds (big-endian)
+//! - Extensions Length (2 bytes): Length of the extension block (big-endian)
+//! - Extensions (variable): Entries of type (1 byte), length (2 bytes,
+//!   big-endian) and value; unknown types are skipped
 //! - Payload (variable): Encrypted message payload
 //! - Signature (64 bytes): Ed25519 signature of header+payload
+//!
+//! For transport, `Frame::encode` wraps a frame in a versioned envelope:
+//! - Magic (4 bytes): "MYMS"
+//! - Wire Version (1 byte): `WIRE_VERSION`
+//! - Length (4 bytes): Length of the frame body (big-endian)
+//! - Body: header + payload + signature (0 or 64 bytes)
+//!
+//! Wire version 1 was the unversioned bincode encoding of `Frame`, which
+//! starts with the same magic and the header's protocol version (1), so
+//! legacy frames are recognised by the same version byte. `Frame`'s serde
+//! implementation writes the `encode` bytes, so serde formats carry the
+//! versioned wire format rather than the struct layout.
+//!
+//! Protocol version 1 headers have no extension block; they are still
+//! accepted and decode with default extension fields.
+
+use std::fmt;
 
-use serde::{Deserialize, Serialize};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::error::{ProtocolError, Result};
-use crate::message::{Message, MessageId, MessageType};
+use crate::message::{
+    is_channel_permitted, validate_source_route, Message, MessageId, MessageType, DEFAULT_CHANNEL,
+};
+use crate::routing::{ContentTag, MAX_CONTENT_TAGS};
 use crate::types::{NodeId, Priority, NODE_ID_SIZE};
 
 /// Protocol version
-pub const PROTOCOL_VERSION: u8 = 1;
+pub const PROTOCOL_VERSION: u8 = 2;
+
+/// Protocol version of headers without an extension block
+pub const LEGACY_PROTOCOL_VERSION: u8 = 1;
 
 /// Magic bytes to identify MyriadMesh frames: "MYMS"
 pub const MAGIC_BYTES: [u8; 4] = [0x4D, 0x59, 0x4D, 0x53];
 
-/// Total header size: 4 + 1 + 1 + 1 + 1 + 1 + 2 + 16 + 64 + 64 + 8 = 163 bytes
+/// Fixed header size: 4 + 1 + 1 + 1 + 1 + 1 + 2 + 16 + 64 + 64 + 8 = 163 bytes
 ///
 /// SECURITY C6: Increased from 99 to 163 bytes due to NodeID expansion (32→64 bytes each)
 /// for collision resistance against birthday attacks.
@@ -39,12 +66,43 @@ pub const HEADER_SIZE: usize = 163;
 /// Signature size (64 bytes for Ed25519)
 pub const SIGNATURE_SIZE: usize = 64;
 
-/// Maximum frame size (1 MB + header + signature)
-pub const MAX_FRAME_SIZE: usize = 1024 * 1024 + HEADER_SIZE + SIGNATURE_SIZE;
+/// Maximum size of the header extension block, excluding its length
+pub const MAX_EXTENSIONS_SIZE: usize = 2048;
+
+/// Maximum frame size (1 MB + header + extensions + signature)
+pub const MAX_FRAME_SIZE: usize =
+    1024 * 1024 + HEADER_SIZE + 2 + MAX_EXTENSIONS_SIZE + SIGNATURE_SIZE;
 
 /// Maximum payload size
 pub const MAX_PAYLOAD_SIZE: usize = 65535;
 
+/// Wire envelope version produced by `Frame::encode`
+pub const WIRE_VERSION: u8 = 2;
+
+/// Wire version of legacy bincode-encoded frames
+pub const LEGACY_WIRE_VERSION: u8 = 1;
+
+/// Envelope size before the frame body: magic + version + length
+pub const WIRE_PREFIX_SIZE: usize = 4 + 1 + 4;
+
+/// Header extension: application channel (u16, big-endian)
+const EXT_CHANNEL: u8 = 0x01;
+
+/// Header extension: content tags, each a length byte and UTF-8 text
+const EXT_TAGS: u8 = 0x02;
+
+/// Header extension: per-flow sequence number (u32, big-endian)
+const EXT_SEQUENCE: u8 = 0x03;
+
+/// Header extension: expiry in Unix milliseconds (u64, big-endian)
+const EXT_EXPIRES_AT: u8 = 0x04;
+
+/// Header extension: source route, the relay node IDs back to back
+const EXT_SOURCE_ROUTE: u8 = 0x05;
+
+/// Size of an extension entry's type and length
+const EXT_ENTRY_PREFIX_SIZE: usize = 1 + 2;
+
 /// Frame flags bitfield (per specification.md:77-87)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FrameFlags(u8);
@@ -71,8 +129,12 @@ impl FrameFlags {
     /// Message to all nodes (Bit 6)
     pub const BROADCAST: u8 = 0b0100_0000;
 
+    /// A relay on the path is congested; adaptive senders should back off (Bit 7)
+    pub const CONGESTION_EXPERIENCED: u8 = 0b1000_0000;
+
     /// Reserved (Bit 7)
-    pub const RESERVED: u8 = 0b1000_0000;
+    #[deprecated(note = "bit 7 is now assigned to CONGESTION_EXPERIENCED")]
+    pub const RESERVED: u8 = Self::CONGESTION_EXPERIENCED;
 
     /// Create new frame flags
     pub fn new(flags: u8) -> Self {
@@ -142,6 +204,21 @@ pub struct FrameHeader {
 
     /// Unix timestamp in milliseconds (8 bytes)
     pub timestamp: u64,
+
+    /// Application channel (extension, omitted when default)
+    pub channel: u16,
+
+    /// Content tags (extension, omitted when empty)
+    pub tags: Vec<ContentTag>,
+
+    /// Sequence number for ordering and dedup (extension, omitted when 0)
+    pub sequence: u32,
+
+    /// Expiry in Unix milliseconds (extension, omitted when None)
+    pub expires_at: Option<u64>,
+
+    /// Relays to traverse in order (extension, omitted when empty)
+    pub source_route: Vec<NodeId>,
 }
 
 impl FrameHeader {
@@ -166,6 +243,11 @@ impl FrameHeader {
             source,
             destination,
             timestamp,
+            channel: DEFAULT_CHANNEL,
+            tags: Vec::new(),
+            sequence: 0,
+            expires_at: None,
+            source_route: Vec::new(),
         }
     }
 
@@ -175,11 +257,8 @@ impl FrameHeader {
             return Err(ProtocolError::InvalidFrameFormat);
         }
 
-        if self.version != PROTOCOL_VERSION {
-            return Err(ProtocolError::ValidationFailed(format!(
-                "Unsupported protocol version: {}",
-                self.version
-            )));
+        if self.version != PROTOCOL_VERSION && self.version != LEGACY_PROTOCOL_VERSION {
+            return Err(ProtocolError::UnsupportedVersion(self.version));
         }
 
         if self.payload_length as usize > MAX_PAYLOAD_SIZE {
@@ -193,10 +272,69 @@ impl FrameHeader {
             return Err(ProtocolError::TtlExceeded);
         }
 
+        if !is_channel_permitted(self.message_type, self.channel) {
+            return Err(ProtocolError::ValidationFailed(format!(
+                "{:?} frame on reserved channel {:#06x}",
+                self.message_type, self.channel
+            )));
+        }
+
+        if self.tags.len() > MAX_CONTENT_TAGS {
+            return Err(ProtocolError::ValidationFailed(format!(
+                "Too many content tags: {} (max {})",
+                self.tags.len(),
+                MAX_CONTENT_TAGS
+            )));
+        }
+
+        validate_source_route(&self.source, &self.destination, &self.source_route)?;
+
         Ok(())
     }
 
-    /// Serialize header to bytes (99 bytes)
+    /// Size of the encoded header, including the extension block
+    pub fn encoded_len(&self) -> usize {
+        if self.version == LEGACY_PROTOCOL_VERSION {
+            HEADER_SIZE
+        } else {
+            HEADER_SIZE + 2 + self.extensions().len()
+        }
+    }
+
+    /// Encode the extension entries, omitting fields at their default
+    fn extensions(&self) -> Vec<u8> {
+        let mut ext = Vec::new();
+        if self.channel != DEFAULT_CHANNEL {
+            push_extension(&mut ext, EXT_CHANNEL, &self.channel.to_be_bytes());
+        }
+        if !self.tags.is_empty() {
+            let mut value = Vec::new();
+            for tag in &self.tags {
+                value.push(tag.as_str().len() as u8);
+                value.extend_from_slice(tag.as_str().as_bytes());
+            }
+            push_extension(&mut ext, EXT_TAGS, &value);
+        }
+        if self.sequence != 0 {
+            push_extension(&mut ext, EXT_SEQUENCE, &self.sequence.to_be_bytes());
+        }
+        if let Some(expires_at) = self.expires_at {
+            push_extension(&mut ext, EXT_EXPIRES_AT, &expires_at.to_be_bytes());
+        }
+        if !self.source_route.is_empty() {
+            let value: Vec<u8> = self
+                .source_route
+                .iter()
+                .flat_map(|hop| hop.as_bytes().iter().copied())
+                .collect();
+            push_extension(&mut ext, EXT_SOURCE_ROUTE, &value);
+        }
+        ext
+    }
+
+    /// Serialize header to bytes (fixed header, then the extension block)
+    ///
+    /// Version 1 headers have no extension block.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::with_capacity(HEADER_SIZE);
 
@@ -235,11 +373,25 @@ impl FrameHeader {
 
         debug_assert_eq!(bytes.len(), HEADER_SIZE, "Header size mismatch");
 
+        // Extension block (2 bytes length, big-endian)
+        if self.version != LEGACY_PROTOCOL_VERSION {
+            let extensions = self.extensions();
+            bytes.extend_from_slice(&(extensions.len() as u16).to_be_bytes());
+            bytes.extend_from_slice(&extensions);
+        }
+
         bytes
     }
 
     /// Deserialize header from bytes
+    ///
+    /// Bytes after the header are ignored.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::parse(bytes).map(|(header, _)| header)
+    }
+
+    /// Parse a header, returning it with the number of bytes it occupies
+    fn parse(bytes: &[u8]) -> Result<(Self, usize)> {
         if bytes.len() < HEADER_SIZE {
             return Err(ProtocolError::InvalidFrameFormat);
         }
@@ -304,8 +456,9 @@ impl FrameHeader {
             bytes[offset + 6],
             bytes[offset + 7],
         ]);
+        offset += 8;
 
-        let header = FrameHeader {
+        let mut header = FrameHeader {
             magic,
             version,
             flags,
@@ -317,18 +470,118 @@ impl FrameHeader {
             source,
             destination,
             timestamp,
+            channel: DEFAULT_CHANNEL,
+            tags: Vec::new(),
+            sequence: 0,
+            expires_at: None,
+            source_route: Vec::new(),
         };
 
+        if version == PROTOCOL_VERSION {
+            offset = header.parse_extensions(bytes, offset)?;
+        }
+
         header.validate()?;
 
-        Ok(header)
+        Ok((header, offset))
+    }
+
+    /// Read the extension block at `offset`, returning the offset after it
+    ///
+    /// Unknown extension types are skipped so later versions can add fields.
+    fn parse_extensions(&mut self, bytes: &[u8], offset: usize) -> Result<usize> {
+        let length = bytes
+            .get(offset..offset + 2)
+            .ok_or(ProtocolError::InvalidFrameFormat)?;
+        let length = u16::from_be_bytes([length[0], length[1]]) as usize;
+        if length > MAX_EXTENSIONS_SIZE {
+            return Err(ProtocolError::MessageTooLarge {
+                size: length,
+                max: MAX_EXTENSIONS_SIZE,
+            });
+        }
+
+        let start = offset + 2;
+        let mut block = bytes
+            .get(start..start + length)
+            .ok_or(ProtocolError::InvalidFrameFormat)?;
+        while !block.is_empty() {
+            if block.len() < EXT_ENTRY_PREFIX_SIZE {
+                return Err(ProtocolError::InvalidFrameFormat);
+            }
+            let kind = block[0];
+            let value_len = u16::from_be_bytes([block[1], block[2]]) as usize;
+            let end = EXT_ENTRY_PREFIX_SIZE + value_len;
+            let value = block
+                .get(EXT_ENTRY_PREFIX_SIZE..end)
+                .ok_or(ProtocolError::InvalidFrameFormat)?;
+
+            match kind {
+                EXT_CHANNEL => self.channel = u16::from_be_bytes(fixed_value(value)?),
+                EXT_TAGS => self.tags = parse_tags(value)?,
+                EXT_SEQUENCE => self.sequence = u32::from_be_bytes(fixed_value(value)?),
+                EXT_EXPIRES_AT => self.expires_at = Some(u64::from_be_bytes(fixed_value(value)?)),
+                EXT_SOURCE_ROUTE => self.source_route = parse_source_route(value)?,
+                _ => {}
+            }
+            block = &block[end..];
+        }
+
+        Ok(start + length)
+    }
+}
+
+/// Append an extension entry: type, length (big-endian), value
+fn push_extension(ext: &mut Vec<u8>, kind: u8, value: &[u8]) {
+    ext.push(kind);
+    ext.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    ext.extend_from_slice(value);
+}
+
+/// Read the content tags extension
+fn parse_tags(mut value: &[u8]) -> Result<Vec<ContentTag>> {
+    let mut tags = Vec::new();
+    while let Some((&len, rest)) = value.split_first() {
+        let text = rest
+            .get(..len as usize)
+            .ok_or(ProtocolError::InvalidFrameFormat)?;
+        let text = std::str::from_utf8(text).map_err(|_| ProtocolError::InvalidFrameFormat)?;
+        tags.push(ContentTag::new(text).map_err(ProtocolError::ValidationFailed)?);
+        if tags.len() > MAX_CONTENT_TAGS {
+            return Err(ProtocolError::ValidationFailed(format!(
+                "Too many content tags (max {})",
+                MAX_CONTENT_TAGS
+            )));
+        }
+        value = &rest[len as usize..];
+    }
+    Ok(tags)
+}
+
+/// Read the source route extension
+///
+/// Length and loops are checked by `FrameHeader::validate`.
+fn parse_source_route(value: &[u8]) -> Result<Vec<NodeId>> {
+    if !value.len().is_multiple_of(NODE_ID_SIZE) {
+        return Err(ProtocolError::InvalidFrameFormat);
     }
+    value
+        .chunks_exact(NODE_ID_SIZE)
+        .map(|hop| fixed_value(hop).map(NodeId::from_bytes))
+        .collect()
+}
+
+/// Read a fixed-size extension value
+fn fixed_value<const N: usize>(value: &[u8]) -> Result<[u8; N]> {
+    value
+        .try_into()
+        .map_err(|_| ProtocolError::InvalidFrameFormat)
 }
 
 /// A complete frame with header, payload, and signature
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
 pub struct Frame {
-    /// Frame header (99 bytes)
+    /// Frame header (fixed fields and extensions)
     pub header: FrameHeader,
 
     /// Message payload (variable, 0-65535 bytes)
@@ -373,14 +626,26 @@ impl Frame {
 
     /// Create a frame from a Message (compatibility helper)
     pub fn from_message(message: &Message) -> Result<Self> {
-        Self::new(
+        let mut frame = Self::new(
             message.message_type,
             message.source,
             message.destination,
             message.payload.clone(),
             message.id,
             message.timestamp,
-        )
+        )?;
+        if message.compressed {
+            frame.header.flags.set(FrameFlags::COMPRESSED);
+        }
+        frame.header.priority = message.priority;
+        frame.header.ttl = message.ttl;
+        frame.header.channel = message.channel;
+        frame.header.tags = message.tags.clone();
+        frame.header.sequence = message.sequence;
+        frame.header.expires_at = message.expires_at;
+        frame.header.source_route = message.source_route.clone();
+        frame.header.validate()?;
+        Ok(frame)
     }
 
     /// Convert frame to Message (compatibility helper)
@@ -390,11 +655,16 @@ impl Frame {
             source: self.header.source,
             destination: self.header.destination,
             message_type: self.header.message_type,
+            channel: self.header.channel,
             priority: self.header.priority,
             ttl: self.header.ttl,
             timestamp: self.header.timestamp,
-            sequence: 0, // Not stored in frame
+            sequence: self.header.sequence,
             payload: self.payload.clone(),
+            compressed: self.header.flags.contains(FrameFlags::COMPRESSED),
+            tags: self.header.tags.clone(),
+            source_route: self.header.source_route.clone(),
+            expires_at: self.header.expires_at,
         })
     }
 
@@ -440,10 +710,10 @@ impl Frame {
         }
 
         // Parse header
-        let header = FrameHeader::from_bytes(&bytes[0..HEADER_SIZE])?;
+        let (header, header_len) = FrameHeader::parse(bytes)?;
 
         // Calculate expected frame size
-        let expected_size = HEADER_SIZE + header.payload_length as usize + SIGNATURE_SIZE;
+        let expected_size = header_len + header.payload_length as usize + SIGNATURE_SIZE;
         if bytes.len() != expected_size {
             return Err(ProtocolError::ValidationFailed(format!(
                 "Frame size mismatch: expected {}, got {}",
@@ -453,7 +723,7 @@ impl Frame {
         }
 
         // Extract payload
-        let payload_start = HEADER_SIZE;
+        let payload_start = header_len;
         let payload_end = payload_start + header.payload_length as usize;
         let payload = bytes[payload_start..payload_end].to_vec();
 
@@ -467,9 +737,93 @@ impl Frame {
         })
     }
 
+    /// Encode frame in the versioned, length-prefixed wire format
+    pub fn encode(&self) -> Vec<u8> {
+        let header = self.header.to_bytes();
+        let body_len = header.len() + self.payload.len() + self.signature.len();
+        let mut bytes = Vec::with_capacity(WIRE_PREFIX_SIZE + body_len);
+        bytes.extend_from_slice(&MAGIC_BYTES);
+        bytes.push(WIRE_VERSION);
+        bytes.extend_from_slice(&(body_len as u32).to_be_bytes());
+        bytes.extend_from_slice(&header);
+        bytes.extend_from_slice(&self.payload);
+        bytes.extend_from_slice(&self.signature);
+        bytes
+    }
+
+    /// Decode a frame produced by `encode`
+    ///
+    /// Legacy bincode frames are still accepted; that fallback will be
+    /// removed in the next release.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 5 || bytes[0..4] != MAGIC_BYTES {
+            return Err(ProtocolError::InvalidFrameFormat);
+        }
+
+        match bytes[4] {
+            WIRE_VERSION => Self::decode_v2(bytes),
+            LEGACY_WIRE_VERSION => Self::decode_legacy(bytes),
+            version => Err(ProtocolError::UnsupportedVersion(version)),
+        }
+    }
+
+    fn decode_v2(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < WIRE_PREFIX_SIZE {
+            return Err(ProtocolError::InvalidFrameFormat);
+        }
+
+        let body_len = u32::from_be_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]) as usize;
+        if body_len > MAX_FRAME_SIZE {
+            return Err(ProtocolError::MessageTooLarge {
+                size: body_len,
+                max: MAX_FRAME_SIZE,
+            });
+        }
+
+        let body = &bytes[WIRE_PREFIX_SIZE..];
+        if body.len() != body_len || body_len < HEADER_SIZE {
+            return Err(ProtocolError::ValidationFailed(format!(
+                "Frame length mismatch: prefix {}, got {}",
+                body_len,
+                body.len()
+            )));
+        }
+
+        let (header, header_len) = FrameHeader::parse(body)?;
+        let payload_end = header_len + header.payload_length as usize;
+        if payload_end > body_len {
+            return Err(ProtocolError::InvalidFrameFormat);
+        }
+
+        let payload = body[header_len..payload_end].to_vec();
+        let signature = body[payload_end..].to_vec();
+        if !signature.is_empty() && signature.len() != SIGNATURE_SIZE {
+            return Err(ProtocolError::ValidationFailed(format!(
+                "Invalid signature size: expected {}, got {}",
+                SIGNATURE_SIZE,
+                signature.len()
+            )));
+        }
+
+        Ok(Frame {
+            header,
+            payload,
+            signature,
+        })
+    }
+
+    /// Compatibility shim for frames sent before the versioned wire format
+    fn decode_legacy(bytes: &[u8]) -> Result<Self> {
+        let legacy: LegacyFrame = bincode::deserialize(bytes)
+            .map_err(|e| ProtocolError::DeserializationFailed(e.to_string()))?;
+        let frame = legacy.into_frame();
+        frame.header.validate()?;
+        Ok(frame)
+    }
+
     /// Get the total size of the frame
     pub fn size(&self) -> usize {
-        HEADER_SIZE + self.payload.len() + SIGNATURE_SIZE
+        self.header.encoded_len() + self.payload.len() + SIGNATURE_SIZE
     }
 
     /// Validate the frame
@@ -495,6 +849,97 @@ impl Frame {
     }
 }
 
+impl Serialize for Frame {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.encode())
+    }
+}
+
+impl<'de> Deserialize<'de> for Frame {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_bytes(FrameVisitor)
+    }
+}
+
+/// Decodes the `encode` bytes written by `Frame`'s `Serialize` impl
+struct FrameVisitor;
+
+impl<'de> Visitor<'de> for FrameVisitor {
+    type Value = Frame;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an encoded MyriadMesh frame")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> std::result::Result<Frame, E> {
+        Frame::decode(bytes).map_err(E::custom)
+    }
+
+    // Formats without a native byte type (e.g. JSON) write a sequence
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Frame, A::Error> {
+        let mut bytes = Vec::new();
+        while let Some(byte) = seq.next_element::<u8>()? {
+            if bytes.len() == WIRE_PREFIX_SIZE + MAX_FRAME_SIZE {
+                return Err(de::Error::custom("encoded frame too large"));
+            }
+            bytes.push(byte);
+        }
+        Frame::decode(&bytes).map_err(de::Error::custom)
+    }
+}
+
+/// Frame as encoded by wire version 1 (bincode of the struct layout)
+#[derive(Serialize, Deserialize)]
+struct LegacyFrame {
+    header: LegacyFrameHeader,
+    payload: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+/// Header layout of wire version 1, without extension fields
+#[derive(Serialize, Deserialize)]
+struct LegacyFrameHeader {
+    magic: [u8; 4],
+    version: u8,
+    flags: FrameFlags,
+    message_type: MessageType,
+    priority: Priority,
+    ttl: u8,
+    payload_length: u16,
+    message_id: MessageId,
+    source: NodeId,
+    destination: NodeId,
+    timestamp: u64,
+}
+
+impl LegacyFrame {
+    fn into_frame(self) -> Frame {
+        let h = self.header;
+        Frame {
+            header: FrameHeader {
+                magic: h.magic,
+                version: h.version,
+                flags: h.flags,
+                message_type: h.message_type,
+                priority: h.priority,
+                ttl: h.ttl,
+                payload_length: h.payload_length,
+                message_id: h.message_id,
+                source: h.source,
+                destination: h.destination,
+                timestamp: h.timestamp,
+                channel: DEFAULT_CHANNEL,
+                tags: Vec::new(),
+                sequence: 0,
+                expires_at: None,
+                source_route: Vec::new(),
+            },
+            payload: self.payload,
+            signature: self.signature,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -559,7 +1004,8 @@ mod tests {
         let frame = create_test_frame();
         let header_bytes = frame.header.to_bytes();
 
-        assert_eq!(header_bytes.len(), HEADER_SIZE);
+        assert_eq!(header_bytes.len(), frame.header.encoded_len());
+        assert_eq!(header_bytes.len(), HEADER_SIZE + 2);
 
         let deserialized = FrameHeader::from_bytes(&header_bytes).unwrap();
         assert_eq!(frame.header, deserialized);
@@ -609,8 +1055,9 @@ mod tests {
         let mut frame = create_test_frame();
         frame.set_signature(vec![0u8; SIGNATURE_SIZE]).unwrap();
 
-        let expected_size = HEADER_SIZE + frame.payload.len() + SIGNATURE_SIZE;
+        let expected_size = frame.header.encoded_len() + frame.payload.len() + SIGNATURE_SIZE;
         assert_eq!(frame.size(), expected_size);
+        assert_eq!(frame.size(), frame.serialize().len());
     }
 
     #[test]
@@ -619,7 +1066,10 @@ mod tests {
         let signable = frame.signable_bytes();
 
         // Should be header + payload
-        assert_eq!(signable.len(), HEADER_SIZE + frame.payload.len());
+        assert_eq!(
+            signable.len(),
+            frame.header.encoded_len() + frame.payload.len()
+        );
     }
 
     #[test]
@@ -630,4 +1080,245 @@ mod tests {
         let result = frame.set_signature(vec![0u8; 32]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_wire_format_roundtrip() {
+        let unsigned = create_test_frame();
+        let mut signed = create_test_frame();
+        signed.set_signature(vec![7u8; SIGNATURE_SIZE]).unwrap();
+
+        for frame in [unsigned, signed] {
+            let encoded = frame.encode();
+            assert_eq!(&encoded[0..4], &MAGIC_BYTES);
+            assert_eq!(encoded[4], WIRE_VERSION);
+            let body_len = u32::from_be_bytes(encoded[5..9].try_into().unwrap()) as usize;
+            assert_eq!(body_len, encoded.len() - WIRE_PREFIX_SIZE);
+
+            let decoded = Frame::decode(&encoded).unwrap();
+            assert_eq!(decoded.header, frame.header);
+            assert_eq!(decoded.payload, frame.payload);
+            assert_eq!(decoded.signature, frame.signature);
+        }
+
+        // Truncated input is rejected
+        let encoded = create_test_frame().encode();
+        assert!(Frame::decode(&encoded[..encoded.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_wire_format_rejects_future_version() {
+        let mut encoded = create_test_frame().encode();
+        encoded[4] = WIRE_VERSION + 1;
+
+        assert_eq!(
+            Frame::decode(&encoded).unwrap_err(),
+            ProtocolError::UnsupportedVersion(WIRE_VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn test_wire_format_decodes_legacy_bincode() {
+        let frame = create_test_frame();
+        let h = &frame.header;
+        let legacy = LegacyFrame {
+            header: LegacyFrameHeader {
+                magic: h.magic,
+                version: LEGACY_PROTOCOL_VERSION,
+                flags: h.flags,
+                message_type: h.message_type,
+                priority: h.priority,
+                ttl: h.ttl,
+                payload_length: h.payload_length,
+                message_id: h.message_id,
+                source: h.source,
+                destination: h.destination,
+                timestamp: h.timestamp,
+            },
+            payload: frame.payload.clone(),
+            signature: Vec::new(),
+        };
+        let legacy = bincode::serialize(&legacy).unwrap();
+        assert_eq!(legacy[4], LEGACY_WIRE_VERSION);
+
+        let decoded = Frame::decode(&legacy).unwrap();
+        assert_eq!(decoded.header.version, LEGACY_PROTOCOL_VERSION);
+        assert_eq!(decoded.header.message_id, frame.header.message_id);
+        assert_eq!(decoded.header.channel, DEFAULT_CHANNEL);
+        assert_eq!(decoded.payload, frame.payload);
+        // Re-encoding a legacy header reproduces the bytes its signature covers
+        assert_eq!(decoded.header.to_bytes().len(), HEADER_SIZE);
+    }
+
+    #[test]
+    fn test_channel_carried_in_header() {
+        let message = Message::new(
+            NodeId::from_bytes([2u8; NODE_ID_SIZE]),
+            NodeId::from_bytes([3u8; NODE_ID_SIZE]),
+            MessageType::Data,
+            b"chat".to_vec(),
+        )
+        .unwrap()
+        .with_channel(7)
+        .unwrap();
+
+        let mut frame = Frame::from_message(&message).unwrap();
+        frame.set_signature(vec![0xAAu8; SIGNATURE_SIZE]).unwrap();
+        let decoded = Frame::decode(&frame.encode()).unwrap();
+        assert_eq!(decoded.header.channel, 7);
+        assert_eq!(decoded.to_message().unwrap().channel, 7);
+        assert_eq!(decoded.payload, b"chat");
+
+        let deserialized = Frame::deserialize(&frame.serialize()).unwrap();
+        assert_eq!(deserialized.header, frame.header);
+    }
+
+    #[test]
+    fn test_tags_carried_in_header() {
+        let tags = vec![
+            ContentTag::new("media:image").unwrap(),
+            ContentTag::new("educational").unwrap(),
+        ];
+        let message = Message::new(
+            NodeId::from_bytes([2u8; NODE_ID_SIZE]),
+            NodeId::from_bytes([3u8; NODE_ID_SIZE]),
+            MessageType::Data,
+            b"picture".to_vec(),
+        )
+        .unwrap()
+        .with_tags(tags.clone())
+        .unwrap();
+
+        let frame = Frame::from_message(&message).unwrap();
+        let decoded = Frame::decode(&frame.encode()).unwrap();
+        assert_eq!(decoded.header.tags, tags);
+        assert_eq!(decoded.to_message().unwrap().tags, tags);
+
+        // Tags are covered by the signature
+        let untagged = Frame::from_message(&message.clone().with_tags(Vec::new()).unwrap());
+        assert_ne!(frame.signable_bytes(), untagged.unwrap().signable_bytes());
+    }
+
+    #[test]
+    fn test_sequence_carried_in_header() {
+        let mut frame = create_test_frame();
+        frame.header.sequence = 0x0102_0304;
+
+        let decoded = Frame::decode(&frame.encode()).unwrap();
+        assert_eq!(decoded.header.sequence, 0x0102_0304);
+        assert_eq!(decoded.to_message().unwrap().sequence, 0x0102_0304);
+
+        // Sequence zero is the default and costs no extension bytes
+        assert_eq!(create_test_frame().header.encoded_len(), HEADER_SIZE + 2);
+    }
+
+    #[test]
+    fn test_expiry_carried_in_header() {
+        let mut frame = create_test_frame();
+        frame.header.expires_at = Some(1_704_067_260_000);
+
+        let decoded = Frame::decode(&frame.encode()).unwrap();
+        assert_eq!(decoded.header.expires_at, Some(1_704_067_260_000));
+        assert_eq!(
+            decoded.to_message().unwrap().expires_at,
+            Some(1_704_067_260_000)
+        );
+
+        // A truncated value is malformed, not silently dropped
+        let mut ext = Vec::new();
+        push_extension(&mut ext, EXT_EXPIRES_AT, &[0u8; 4]);
+        let mut bytes = create_test_frame().header.to_bytes();
+        bytes.truncate(HEADER_SIZE);
+        bytes.extend_from_slice(&(ext.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(&ext);
+        assert!(FrameHeader::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_source_route_carried_in_header() {
+        let hops: Vec<_> = (4..8u8)
+            .map(|b| NodeId::from_bytes([b; NODE_ID_SIZE]))
+            .collect();
+        let mut frame = create_test_frame();
+        frame.header.source_route = hops.clone();
+
+        let decoded = Frame::decode(&frame.encode()).unwrap();
+        assert_eq!(decoded.header.source_route, hops);
+        let message = decoded.to_message().unwrap();
+        assert_eq!(message.next_source_hop(&hops[1]), Some(hops[2]));
+
+        // A looping route is rejected on receive
+        frame.header.source_route = vec![hops[0], hops[0]];
+        assert!(Frame::decode(&frame.encode()).is_err());
+    }
+
+    #[test]
+    fn test_malformed_tags_rejected() {
+        let mut ext = Vec::new();
+        push_extension(&mut ext, EXT_TAGS, &[5, b'a', b'b']);
+        let mut bytes = create_test_frame().header.to_bytes();
+        bytes.truncate(HEADER_SIZE);
+        bytes.extend_from_slice(&(ext.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(&ext);
+
+        assert!(FrameHeader::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_data_on_control_channel_rejected() {
+        let mut frame = create_test_frame();
+        frame.header.channel = crate::message::CONTROL_CHANNEL_START;
+        assert!(frame.header.validate().is_err());
+        assert!(Frame::decode(&frame.encode()).is_err());
+
+        frame.header.message_type = MessageType::Control;
+        assert!(Frame::decode(&frame.encode()).is_ok());
+    }
+
+    #[test]
+    fn test_version_one_header_has_no_extensions() {
+        let mut frame = create_test_frame();
+        frame.header.version = LEGACY_PROTOCOL_VERSION;
+
+        let bytes = frame.header.to_bytes();
+        assert_eq!(bytes.len(), HEADER_SIZE);
+        let decoded = FrameHeader::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, frame.header);
+        assert_eq!(
+            Frame::decode(&frame.encode()).unwrap().payload,
+            frame.payload
+        );
+    }
+
+    #[test]
+    fn test_unknown_extension_skipped() {
+        let mut frame = create_test_frame();
+        frame.header.channel = 9;
+        let mut bytes = frame.header.to_bytes();
+
+        // Append an entry of an unassigned type and rewrite the block length
+        let mut ext = bytes.split_off(HEADER_SIZE + 2);
+        push_extension(&mut ext, 0xEE, b"future");
+        bytes.truncate(HEADER_SIZE);
+        bytes.extend_from_slice(&(ext.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(&ext);
+
+        let decoded = FrameHeader::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.channel, 9);
+
+        // A block running past the input is rejected
+        assert!(FrameHeader::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_serde_uses_wire_format() {
+        let mut frame = create_test_frame();
+        frame.header.channel = 3;
+
+        let bytes = bincode::serialize(&frame).unwrap();
+        assert_eq!(&bytes[8..], frame.encode().as_slice());
+
+        let decoded: Frame = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded.header, frame.header);
+        assert_eq!(decoded.payload, frame.payload);
+    }
 }
@@ -11,7 +11,7 @@ pub mod types;
 
 pub use error::{ProtocolError, Result};
 pub use frame::{Frame, FrameHeader};
-pub use message::{Message, MessageId, MessageType};
+pub use message::{Message, MessageId, MessageType, CONTROL_CHANNEL_START, DEFAULT_CHANNEL};
 pub use routing::{ContentTag, RelayPolicy, RoutingFlags};
 pub use types::NodeId;
 
@@ -1,10 +1,14 @@
 //! Message types and structures
 
 use blake2::{Blake2b512, Digest};
-use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::io::Read;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::error::{ProtocolError, Result};
+use crate::routing::{ContentTag, MAX_CONTENT_TAGS};
 use crate::types::{NodeId, Priority, NODE_ID_SIZE};
 
 /// Size of a message ID in bytes (per specification.md:128-131)
@@ -13,6 +17,59 @@ pub const MESSAGE_ID_SIZE: usize = 16;
 /// Maximum message payload size (1 MB)
 pub const MAX_PAYLOAD_SIZE: usize = 1024 * 1024;
 
+/// Channel used by messages that do not select one
+pub const DEFAULT_CHANNEL: u16 = 0;
+
+/// First channel of the range reserved for protocol control traffic
+///
+/// Channels `CONTROL_CHANNEL_START..=u16::MAX` cannot be used by applications.
+pub const CONTROL_CHANNEL_START: u16 = 0xFF00;
+
+/// Check if a channel is in the reserved control range
+pub fn is_control_channel(channel: u16) -> bool {
+    channel >= CONTROL_CHANNEL_START
+}
+
+/// Check if messages of `message_type` may use `channel`
+///
+/// Application data cannot be sent on the reserved control range.
+pub fn is_channel_permitted(message_type: MessageType, channel: u16) -> bool {
+    message_type != MessageType::Data || !is_control_channel(channel)
+}
+
+/// Check a source route's length and that no hop repeats or names an endpoint
+pub(crate) fn validate_source_route(
+    source: &NodeId,
+    destination: &NodeId,
+    hops: &[NodeId],
+) -> Result<()> {
+    if hops.len() > MAX_SOURCE_ROUTE_HOPS {
+        return Err(ProtocolError::ValidationFailed(format!(
+            "Source route too long: {} hops (max {})",
+            hops.len(),
+            MAX_SOURCE_ROUTE_HOPS
+        )));
+    }
+    for (i, hop) in hops.iter().enumerate() {
+        if hop == source || hop == destination || hops[..i].contains(hop) {
+            return Err(ProtocolError::ValidationFailed(format!(
+                "Source route hop {} would loop",
+                i
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Domain separator for canonical message IDs
+const CANONICAL_ID_DOMAIN: &[u8] = b"myriadmesh-canonical-id-v1";
+
+/// zstd compression level for message payloads
+pub const COMPRESSION_LEVEL: i32 = 3;
+
+/// Maximum relays in an explicit source route
+pub const MAX_SOURCE_ROUTE_HOPS: usize = 16;
+
 /// A unique identifier for a message
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct MessageId([u8; MESSAGE_ID_SIZE]);
@@ -44,6 +101,38 @@ impl MessageId {
         MessageId(id)
     }
 
+    /// Derive the canonical ID of a logical message
+    ///
+    /// Unlike `generate`, the timestamp is not hashed, so retransmissions of
+    /// the same message (same sequence number) share an ID. Lengths are
+    /// hashed alongside the payload to keep field boundaries unambiguous.
+    pub fn canonical(
+        source: &NodeId,
+        destination: &NodeId,
+        message_type: MessageType,
+        channel: u16,
+        payload: &[u8],
+        sequence: u32,
+    ) -> Self {
+        let mut hasher = Blake2b512::new();
+
+        hasher.update(CANONICAL_ID_DOMAIN);
+        hasher.update(source.as_bytes());
+        hasher.update(destination.as_bytes());
+        hasher.update([message_type.to_u8()]);
+        hasher.update(channel.to_le_bytes());
+        hasher.update(sequence.to_le_bytes());
+        hasher.update((payload.len() as u64).to_le_bytes());
+        hasher.update(payload);
+
+        let hash = hasher.finalize();
+
+        let mut id = [0u8; MESSAGE_ID_SIZE];
+        id.copy_from_slice(&hash[..MESSAGE_ID_SIZE]);
+
+        MessageId(id)
+    }
+
     /// Create from bytes
     pub fn from_bytes(bytes: [u8; MESSAGE_ID_SIZE]) -> Self {
         MessageId(bytes)
@@ -188,7 +277,10 @@ impl MessageType {
 }
 
 /// A message in the MyriadMesh protocol
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Binary serialization starts with a magic and layout version, so messages
+/// stored before the current field set still decode.
+#[derive(Debug, Clone)]
 pub struct Message {
     /// Unique message identifier
     pub id: MessageId,
@@ -202,6 +294,9 @@ pub struct Message {
     /// Message type
     pub message_type: MessageType,
 
+    /// Application channel, used by receivers to demultiplex messages
+    pub channel: u16,
+
     /// Message priority
     pub priority: Priority,
 
@@ -216,6 +311,25 @@ pub struct Message {
 
     /// Message payload
     pub payload: Vec<u8>,
+
+    /// Payload is zstd-compressed
+    pub compressed: bool,
+
+    /// Content tags relays and subscribers may filter on
+    pub tags: Vec<ContentTag>,
+
+    /// Relays to traverse in order (strict source routing)
+    ///
+    /// Empty lets each router choose the next hop. The destination is not
+    /// listed; it follows the last relay.
+    pub source_route: Vec<NodeId>,
+
+    /// Wall-clock deadline (Unix time in milliseconds) after which the
+    /// message is useless and relays drop it
+    ///
+    /// Complements `ttl`, which bounds hops rather than time. None never
+    /// expires.
+    pub expires_at: Option<u64>,
 }
 
 impl Message {
@@ -255,11 +369,16 @@ impl Message {
             source,
             destination,
             message_type,
+            channel: DEFAULT_CHANNEL,
             priority: Priority::default(),
             ttl: 32, // Default TTL (per specification.md:122)
             timestamp,
             sequence,
             payload,
+            compressed: false,
+            tags: Vec::new(),
+            source_route: Vec::new(),
+            expires_at: None,
         })
     }
 
@@ -281,6 +400,184 @@ impl Message {
         self
     }
 
+    /// Set the application channel
+    ///
+    /// Fails if the channel is in the reserved control range.
+    pub fn with_channel(mut self, channel: u16) -> Result<Self> {
+        if is_control_channel(channel) {
+            return Err(ProtocolError::ValidationFailed(format!(
+                "Channel {:#06x} is reserved for control traffic",
+                channel
+            )));
+        }
+        self.channel = channel;
+        Ok(self)
+    }
+
+    /// Set a channel in the reserved control range
+    pub fn with_control_channel(mut self, channel: u16) -> Result<Self> {
+        if !is_control_channel(channel) {
+            return Err(ProtocolError::ValidationFailed(format!(
+                "Channel {:#06x} is not a control channel",
+                channel
+            )));
+        }
+        self.channel = channel;
+        Ok(self)
+    }
+
+    /// Set the content tags
+    ///
+    /// Fails if there are more than [`MAX_CONTENT_TAGS`].
+    pub fn with_tags(mut self, tags: Vec<ContentTag>) -> Result<Self> {
+        if tags.len() > MAX_CONTENT_TAGS {
+            return Err(ProtocolError::ValidationFailed(format!(
+                "Too many content tags: {} (max {})",
+                tags.len(),
+                MAX_CONTENT_TAGS
+            )));
+        }
+        self.tags = tags;
+        Ok(self)
+    }
+
+    /// Pin the message to an explicit list of relays
+    ///
+    /// Routers forward it through `hops` in order and then to the
+    /// destination, instead of choosing a path themselves. This is plaintext
+    /// path pinning for diagnostics and out-of-band negotiated routes; every
+    /// relay sees the full route. Fails if there are more than
+    /// [`MAX_SOURCE_ROUTE_HOPS`], or if a hop repeats or names the source or
+    /// destination.
+    pub fn with_source_route(mut self, hops: Vec<NodeId>) -> Result<Self> {
+        validate_source_route(&self.source, &self.destination, &hops)?;
+        self.source_route = hops;
+        Ok(self)
+    }
+
+    /// Check if this message follows an explicit source route
+    pub fn is_source_routed(&self) -> bool {
+        !self.source_route.is_empty()
+    }
+
+    /// Next hop after `current` on the message's source route
+    ///
+    /// Returns None if the message is not source routed or `current` is
+    /// neither its source nor one of the listed relays.
+    pub fn next_source_hop(&self, current: &NodeId) -> Option<NodeId> {
+        let first = *self.source_route.first()?;
+        if *current == self.source {
+            return Some(first);
+        }
+        let position = self.source_route.iter().position(|hop| hop == current)?;
+        Some(
+            self.source_route
+                .get(position + 1)
+                .copied()
+                .unwrap_or(self.destination),
+        )
+    }
+
+    /// Expire the message at `expires_at` (Unix time in milliseconds)
+    pub fn with_expires_at(mut self, expires_at: u64) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Expire the message `lifetime` after its timestamp
+    pub fn with_lifetime(self, lifetime: Duration) -> Self {
+        let expires_at = self
+            .timestamp
+            .saturating_add(lifetime.as_millis().min(u64::MAX as u128) as u64);
+        self.with_expires_at(expires_at)
+    }
+
+    /// Check if the message has expired at `now_ms` (Unix time in milliseconds)
+    pub fn is_expired_at(&self, now_ms: u64) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| now_ms >= expires_at)
+    }
+
+    /// Check if the message has expired by the system clock
+    pub fn is_expired(&self) -> bool {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        self.is_expired_at(now_ms)
+    }
+
+    /// Check if this message is on a control channel
+    pub fn is_control(&self) -> bool {
+        is_control_channel(self.channel)
+    }
+
+    /// Canonical ID of this message, stable across retransmissions
+    ///
+    /// Covers source, destination, type, channel, payload and sequence number, but
+    /// not the timestamp, TTL or priority, which may change between sends.
+    /// Callers must assign distinct sequence numbers to distinct messages
+    /// with the same content.
+    pub fn canonical_id(&self) -> MessageId {
+        MessageId::canonical(
+            &self.source,
+            &self.destination,
+            self.message_type,
+            self.channel,
+            &self.payload,
+            self.sequence,
+        )
+    }
+
+    /// Compress the payload with zstd
+    ///
+    /// The compressed form is kept only if it is smaller than the original;
+    /// otherwise the payload is left stored. Returns whether the payload is
+    /// now compressed.
+    pub fn compress(&mut self) -> Result<bool> {
+        if self.compressed {
+            return Ok(true);
+        }
+
+        let compressed = zstd::bulk::compress(&self.payload, COMPRESSION_LEVEL)
+            .map_err(|e| ProtocolError::SerializationFailed(e.to_string()))?;
+        if compressed.len() < self.payload.len() {
+            self.payload = compressed;
+            self.compressed = true;
+        }
+
+        Ok(self.compressed)
+    }
+
+    /// Decompress the payload if it is compressed
+    ///
+    /// SECURITY: Output is capped at MAX_PAYLOAD_SIZE so a small malicious
+    /// payload cannot expand without bound (decompression bomb).
+    pub fn decompress(&mut self) -> Result<()> {
+        if !self.compressed {
+            return Ok(());
+        }
+
+        let mut payload = Vec::new();
+        let decoder = zstd::stream::read::Decoder::new(self.payload.as_slice())
+            .map_err(|e| ProtocolError::DeserializationFailed(e.to_string()))?;
+        decoder
+            .take(MAX_PAYLOAD_SIZE as u64 + 1)
+            .read_to_end(&mut payload)
+            .map_err(|e| ProtocolError::DeserializationFailed(e.to_string()))?;
+
+        if payload.len() > MAX_PAYLOAD_SIZE {
+            return Err(ProtocolError::MessageTooLarge {
+                size: payload.len(),
+                max: MAX_PAYLOAD_SIZE,
+            });
+        }
+
+        self.payload = payload;
+        self.compressed = false;
+        Ok(())
+    }
+
     /// Decrement TTL (returns false if TTL reaches 0)
     pub fn decrement_ttl(&mut self) -> bool {
         if self.ttl > 0 {
@@ -313,6 +610,7 @@ impl Message {
             + NODE_ID_SIZE // source
             + NODE_ID_SIZE // destination
             + 1  // message_type
+            + 2  // channel
             + 1  // priority
             + 1  // ttl
             + 8  // timestamp
@@ -341,6 +639,208 @@ impl Message {
     }
 }
 
+/// Leading bytes of the binary layout of [`Message`], followed by a layout
+/// version
+///
+/// Messages written before channels, tags, source routes and expiry existed
+/// are bare fields starting with the random message id, so binary data that
+/// does not start with this magic is decoded as that legacy layout. A legacy
+/// id that happens to start with the magic fails to decode.
+const MESSAGE_MAGIC: [u8; 4] = *b"MMSG";
+
+/// Binary layout version following [`MESSAGE_MAGIC`]
+const MESSAGE_LAYOUT_VERSION: u8 = 2;
+
+/// Fields of [`Message`] as serialized
+///
+/// Self-describing formats such as JSON use this struct directly, keeping
+/// the original shape; fields added since default when absent.
+#[derive(Deserialize)]
+struct MessageFields {
+    id: MessageId,
+    source: NodeId,
+    destination: NodeId,
+    message_type: MessageType,
+    #[serde(default)]
+    channel: u16,
+    priority: Priority,
+    ttl: u8,
+    timestamp: u64,
+    sequence: u32,
+    payload: Vec<u8>,
+    #[serde(default)]
+    compressed: bool,
+    #[serde(default)]
+    tags: Vec<ContentTag>,
+    #[serde(default)]
+    source_route: Vec<NodeId>,
+    #[serde(default)]
+    expires_at: Option<u64>,
+}
+
+/// Borrowed [`MessageFields`], so serializing does not copy the payload
+#[derive(Serialize)]
+#[serde(rename = "Message")]
+struct MessageFieldsRef<'a> {
+    id: &'a MessageId,
+    source: &'a NodeId,
+    destination: &'a NodeId,
+    message_type: &'a MessageType,
+    channel: u16,
+    priority: &'a Priority,
+    ttl: u8,
+    timestamp: u64,
+    sequence: u32,
+    payload: &'a [u8],
+    compressed: bool,
+    tags: &'a [ContentTag],
+    source_route: &'a [NodeId],
+    expires_at: Option<u64>,
+}
+
+/// Fields following the id in the legacy binary layout
+#[derive(Deserialize)]
+struct LegacyMessageTail {
+    source: NodeId,
+    destination: NodeId,
+    message_type: MessageType,
+    priority: Priority,
+    ttl: u8,
+    timestamp: u64,
+    sequence: u32,
+    payload: Vec<u8>,
+}
+
+impl From<MessageFields> for Message {
+    fn from(m: MessageFields) -> Self {
+        Message {
+            id: m.id,
+            source: m.source,
+            destination: m.destination,
+            message_type: m.message_type,
+            channel: m.channel,
+            priority: m.priority,
+            ttl: m.ttl,
+            timestamp: m.timestamp,
+            sequence: m.sequence,
+            payload: m.payload,
+            compressed: m.compressed,
+            tags: m.tags,
+            source_route: m.source_route,
+            expires_at: m.expires_at,
+        }
+    }
+}
+
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let fields = MessageFieldsRef {
+            id: &self.id,
+            source: &self.source,
+            destination: &self.destination,
+            message_type: &self.message_type,
+            channel: self.channel,
+            priority: &self.priority,
+            ttl: self.ttl,
+            timestamp: self.timestamp,
+            sequence: self.sequence,
+            payload: &self.payload,
+            compressed: self.compressed,
+            tags: &self.tags,
+            source_route: &self.source_route,
+            expires_at: self.expires_at,
+        };
+        if serializer.is_human_readable() {
+            return fields.serialize(serializer);
+        }
+
+        let mut tuple = serializer.serialize_tuple(MESSAGE_MAGIC.len() + 2)?;
+        for byte in &MESSAGE_MAGIC {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.serialize_element(&MESSAGE_LAYOUT_VERSION)?;
+        tuple.serialize_element(&fields)?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return MessageFields::deserialize(deserializer).map(Message::from);
+        }
+
+        struct MessageVisitor;
+
+        impl<'de> Visitor<'de> for MessageVisitor {
+            type Value = Message;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a versioned or legacy message")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<Message, A::Error> {
+                let mut lead = [0u8; MESSAGE_MAGIC.len()];
+                for (i, byte) in lead.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+
+                if lead != MESSAGE_MAGIC {
+                    // Legacy layout: the bytes read so far start the id
+                    let mut id = [0u8; MESSAGE_ID_SIZE];
+                    id[..lead.len()].copy_from_slice(&lead);
+                    for (i, byte) in id.iter_mut().enumerate().skip(lead.len()) {
+                        *byte = seq
+                            .next_element()?
+                            .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                    }
+                    let m: LegacyMessageTail = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(MESSAGE_ID_SIZE, &self))?;
+                    return Ok(Message {
+                        id: MessageId(id),
+                        source: m.source,
+                        destination: m.destination,
+                        message_type: m.message_type,
+                        channel: DEFAULT_CHANNEL,
+                        priority: m.priority,
+                        ttl: m.ttl,
+                        timestamp: m.timestamp,
+                        sequence: m.sequence,
+                        payload: m.payload,
+                        compressed: false,
+                        tags: Vec::new(),
+                        source_route: Vec::new(),
+                        expires_at: None,
+                    });
+                }
+
+                let version: u8 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(lead.len(), &self))?;
+                if version != MESSAGE_LAYOUT_VERSION {
+                    return Err(de::Error::custom(format!(
+                        "Unsupported message layout version {}",
+                        version
+                    )));
+                }
+                let fields: MessageFields = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(lead.len() + 1, &self))?;
+                Ok(fields.into())
+            }
+        }
+
+        // Long enough for either layout; the legacy id is read bytewise
+        deserializer.deserialize_tuple(MESSAGE_ID_SIZE + 1, MessageVisitor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -491,9 +991,279 @@ mod tests {
 
         // Message size should be:
         // MESSAGE_ID_SIZE (16) + NODE_ID_SIZE (64) + NODE_ID_SIZE (64)
-        // + 1 (type) + 1 (priority) + 1 (ttl) + 8 (timestamp) + 4 (sequence) + payload (100)
-        let expected_size = 16 + 64 + 64 + 1 + 1 + 1 + 8 + 4 + 100;
+        // + 1 (type) + 2 (channel) + 1 (priority) + 1 (ttl) + 8 (timestamp) + 4 (sequence)
+        // + payload (100)
+        let expected_size = 16 + 64 + 64 + 1 + 2 + 1 + 1 + 8 + 4 + 100;
         assert_eq!(msg.size(), expected_size);
-        assert_eq!(msg.size(), 259); // Explicit check
+        assert_eq!(msg.size(), 261); // Explicit check
+    }
+
+    fn test_message(payload: Vec<u8>) -> Message {
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        Message::new(source, dest, MessageType::Data, payload).unwrap()
+    }
+
+    #[test]
+    fn test_compressible_payload_shrinks() {
+        let payload = b"level=info adapter=lora status=ok\n".repeat(200);
+        let mut msg = test_message(payload.clone());
+
+        assert!(msg.compress().unwrap());
+        assert!(msg.compressed);
+        assert!(msg.payload.len() < payload.len());
+
+        msg.decompress().unwrap();
+        assert!(!msg.compressed);
+        assert_eq!(msg.payload, payload);
+    }
+
+    #[test]
+    fn test_incompressible_payload_stays_stored() {
+        // Hash output is effectively random
+        let payload: Vec<u8> = (0u32..64)
+            .flat_map(|i| Blake2b512::digest(i.to_be_bytes()).to_vec())
+            .collect();
+        let mut msg = test_message(payload.clone());
+
+        assert!(!msg.compress().unwrap());
+        assert!(!msg.compressed);
+        assert_eq!(msg.payload, payload);
+
+        // Decompressing a stored payload is a no-op
+        msg.decompress().unwrap();
+        assert_eq!(msg.payload, payload);
+    }
+
+    #[test]
+    fn test_decompression_bomb_rejected() {
+        let bomb =
+            zstd::bulk::compress(&vec![0u8; MAX_PAYLOAD_SIZE * 4], COMPRESSION_LEVEL).unwrap();
+        let mut msg = test_message(bomb.clone());
+        msg.compressed = true;
+
+        assert!(matches!(
+            msg.decompress(),
+            Err(ProtocolError::MessageTooLarge { .. })
+        ));
+        // The message is left untouched
+        assert!(msg.compressed);
+        assert_eq!(msg.payload, bomb);
+    }
+    #[test]
+    fn test_canonical_id_stable_across_builds() {
+        let payload = b"config blob".to_vec();
+        let first = test_message(payload.clone()).with_sequence(7);
+        let mut second = test_message(payload).with_sequence(7).with_ttl(3);
+        second.timestamp += 10_000;
+
+        assert_eq!(first.canonical_id(), second.canonical_id());
+    }
+
+    #[test]
+    fn test_canonical_id_distinguishes_messages() {
+        let base = test_message(b"hello".to_vec()).with_sequence(1);
+        let other_payload = test_message(b"hellp".to_vec()).with_sequence(1);
+        let other_sequence = test_message(b"hello".to_vec()).with_sequence(2);
+        let mut other_type = base.clone();
+        other_type.message_type = MessageType::Heartbeat;
+
+        assert_ne!(base.canonical_id(), other_payload.canonical_id());
+        assert_ne!(base.canonical_id(), other_sequence.canonical_id());
+        assert_ne!(base.canonical_id(), other_type.canonical_id());
+    }
+    #[test]
+    fn test_channels_distinguish_messages() {
+        let chat = test_message(b"hi".to_vec()).with_channel(1).unwrap();
+        let logs = test_message(b"hi".to_vec()).with_channel(2).unwrap();
+
+        assert_eq!(test_message(Vec::new()).channel, DEFAULT_CHANNEL);
+        assert_ne!(chat.channel, logs.channel);
+        assert_ne!(chat.canonical_id(), logs.canonical_id());
+        assert!(!chat.is_control());
+    }
+
+    #[test]
+    fn test_control_channels_reserved() {
+        for channel in [CONTROL_CHANNEL_START, 0xFFAB, u16::MAX] {
+            assert!(matches!(
+                test_message(Vec::new()).with_channel(channel),
+                Err(ProtocolError::ValidationFailed(_))
+            ));
+            assert!(test_message(Vec::new())
+                .with_control_channel(channel)
+                .unwrap()
+                .is_control());
+        }
+
+        assert!(test_message(Vec::new())
+            .with_channel(CONTROL_CHANNEL_START - 1)
+            .is_ok());
+        assert!(test_message(Vec::new()).with_control_channel(42).is_err());
+    }
+
+    #[test]
+    fn test_content_tags_bounded() {
+        let source = NodeId::from_bytes([1u8; NODE_ID_SIZE]);
+        let dest = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+        let message = Message::new(source, dest, MessageType::Data, vec![1, 2, 3]).unwrap();
+        assert!(message.tags.is_empty());
+
+        let tag = |name: &str| ContentTag::new(name).unwrap();
+        let tagged = message
+            .clone()
+            .with_tags(vec![tag("media:image"), tag("size:small")])
+            .unwrap();
+        assert_eq!(tagged.tags.len(), 2);
+
+        let too_many = vec![tag("spam"); MAX_CONTENT_TAGS + 1];
+        assert!(message.with_tags(too_many).is_err());
+    }
+
+    #[test]
+    fn test_source_route_next_hops() {
+        let node = |byte: u8| NodeId::from_bytes([byte; NODE_ID_SIZE]);
+        let message = Message::new(node(1), node(9), MessageType::Data, vec![1, 2, 3])
+            .unwrap()
+            .with_source_route(vec![node(2), node(3)])
+            .unwrap();
+
+        assert!(message.is_source_routed());
+        assert_eq!(message.next_source_hop(&node(1)), Some(node(2)));
+        assert_eq!(message.next_source_hop(&node(2)), Some(node(3)));
+        assert_eq!(message.next_source_hop(&node(3)), Some(node(9)));
+        assert_eq!(message.next_source_hop(&node(4)), None);
+
+        let unrouted = Message::new(node(1), node(9), MessageType::Data, vec![1]).unwrap();
+        assert!(!unrouted.is_source_routed());
+        assert_eq!(unrouted.next_source_hop(&node(1)), None);
+    }
+
+    #[test]
+    fn test_source_route_validation() {
+        let node = |byte: u8| NodeId::from_bytes([byte; NODE_ID_SIZE]);
+        let message = Message::new(node(1), node(9), MessageType::Data, vec![1]).unwrap();
+
+        assert!(message
+            .clone()
+            .with_source_route(vec![node(2), node(2)])
+            .is_err());
+        assert!(message.clone().with_source_route(vec![node(1)]).is_err());
+        assert!(message.clone().with_source_route(vec![node(9)]).is_err());
+
+        let too_long = (10..10 + MAX_SOURCE_ROUTE_HOPS as u8 + 1)
+            .map(node)
+            .collect();
+        assert!(message.clone().with_source_route(too_long).is_err());
+
+        let max = (10..10 + MAX_SOURCE_ROUTE_HOPS as u8).map(node).collect();
+        assert!(message.with_source_route(max).is_ok());
+    }
+
+    #[test]
+    fn test_message_expiry() {
+        let msg = test_message(b"reading".to_vec());
+        assert_eq!(msg.expires_at, None);
+        assert!(!msg.is_expired());
+        assert!(!msg.is_expired_at(u64::MAX));
+
+        let msg = msg.with_lifetime(Duration::from_secs(60));
+        assert_eq!(msg.expires_at, Some(msg.timestamp + 60_000));
+        assert!(!msg.is_expired());
+        assert!(!msg.is_expired_at(msg.timestamp + 59_999));
+        assert!(msg.is_expired_at(msg.timestamp + 60_000));
+
+        let stale = test_message(b"reading".to_vec()).with_expires_at(1);
+        assert!(stale.is_expired());
+    }
+
+    #[test]
+    fn test_serialization_is_versioned() {
+        let message = test_message(b"tagged".to_vec())
+            .with_channel(4)
+            .unwrap()
+            .with_tags(vec![ContentTag::new("media:image").unwrap()])
+            .unwrap()
+            .with_expires_at(42);
+
+        let bytes = bincode::serialize(&message).unwrap();
+        assert_eq!(bytes[..4], MESSAGE_MAGIC);
+        assert_eq!(bytes[4], MESSAGE_LAYOUT_VERSION);
+        let decoded: Message = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded.channel, 4);
+        assert_eq!(decoded.tags, message.tags);
+        assert_eq!(decoded.expires_at, Some(42));
+        assert_eq!(decoded.payload, message.payload);
+
+        // Unknown versions are rejected instead of misread
+        let mut future = bytes.clone();
+        future[4] = MESSAGE_LAYOUT_VERSION + 1;
+        assert!(bincode::deserialize::<Message>(&future).is_err());
+    }
+
+    /// `Message` as derived before the versioned layout
+    #[derive(Serialize)]
+    struct BaselineMessage {
+        id: MessageId,
+        source: NodeId,
+        destination: NodeId,
+        message_type: MessageType,
+        priority: Priority,
+        ttl: u8,
+        timestamp: u64,
+        sequence: u32,
+        payload: Vec<u8>,
+    }
+
+    fn baseline(message: &Message) -> BaselineMessage {
+        BaselineMessage {
+            id: message.id,
+            source: message.source,
+            destination: message.destination,
+            message_type: message.message_type,
+            priority: message.priority,
+            ttl: message.ttl,
+            timestamp: message.timestamp,
+            sequence: message.sequence,
+            payload: message.payload.clone(),
+        }
+    }
+
+    #[test]
+    fn test_legacy_messages_decode_with_defaults() {
+        let mut message = test_message(b"old".to_vec());
+        // Ids that read as small integers are not mistaken for a tag
+        for lead in [[0u8; 4], [1, 0, 0, 0], [0xAB; 4]] {
+            let mut id = [7u8; MESSAGE_ID_SIZE];
+            id[..4].copy_from_slice(&lead);
+            message.id = MessageId::from_bytes(id);
+
+            let bytes = bincode::serialize(&baseline(&message)).unwrap();
+            let decoded: Message = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(decoded.id, message.id);
+            assert_eq!(decoded.source, message.source);
+            assert_eq!(decoded.sequence, message.sequence);
+            assert_eq!(decoded.payload, message.payload);
+            assert_eq!(decoded.channel, DEFAULT_CHANNEL);
+            assert!(decoded.tags.is_empty());
+            assert_eq!(decoded.expires_at, None);
+        }
+    }
+
+    #[test]
+    fn test_json_keeps_baseline_shape() {
+        let message = test_message(b"json".to_vec()).with_expires_at(9);
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert!(json.get("id").is_some());
+        assert_eq!(json["expires_at"], 9);
+        let decoded: Message = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.expires_at, Some(9));
+
+        let legacy = serde_json::to_string(&baseline(&message)).unwrap();
+        let decoded: Message = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(decoded.id, message.id);
+        assert_eq!(decoded.payload, message.payload);
+        assert_eq!(decoded.expires_at, None);
     }
 }
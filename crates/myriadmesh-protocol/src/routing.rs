@@ -69,7 +69,7 @@ impl Default for RoutingFlags {
 }
 
 /// Content tag for optional relay filtering
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ContentTag(String);
 
 impl ContentTag {
@@ -1,7 +1,9 @@
 //! Core protocol types
 
+use bech32::{FromBase32, ToBase32, Variant};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
 /// Size of a node ID in bytes (64 bytes / 512 bits)
 ///
@@ -9,6 +11,20 @@ use std::fmt;
 /// 512-bit NodeIDs provide ~2^256 collision resistance, secure against quantum computers.
 pub const NODE_ID_SIZE: usize = 64;
 
+/// Human-readable prefix of bech32-encoded node IDs
+pub const NODE_ID_HRP: &str = "mm";
+
+/// Length of a bech32m-encoded node ID: prefix, separator, 103 data
+/// characters for 512 bits and the 6-character checksum
+///
+/// This is an extended-length bech32m, like BOLT 11 invoices: BIP-350 caps
+/// strings at 90 characters, which a 64-byte ID cannot fit, so decoders
+/// that enforce the cap reject these IDs. In exchange the length is fixed
+/// and checked exactly. The checksum still detects any error with
+/// probability 1 - 2^-30, though the guarantee of catching every error of
+/// up to 4 characters only holds within 89 characters.
+pub const NODE_ID_BECH32_LEN: usize = NODE_ID_HRP.len() + 1 + (NODE_ID_SIZE * 8).div_ceil(5) + 6;
+
 /// A unique identifier for a node in the MyriadMesh network
 ///
 /// SECURITY C6: Uses custom serde implementation for 64-byte array support
@@ -48,6 +64,54 @@ impl NodeId {
         Ok(NodeId(arr))
     }
 
+    /// Convert to an extended-length bech32m string (e.g. `mm1...`)
+    ///
+    /// The checksum catches typos when IDs are copied by hand. Always
+    /// [`NODE_ID_BECH32_LEN`] characters.
+    pub fn to_bech32(&self) -> String {
+        bech32::encode(NODE_ID_HRP, self.0.to_base32(), Variant::Bech32m)
+            .expect("NODE_ID_HRP is a valid human-readable part")
+    }
+
+    /// Parse from an extended-length bech32m string
+    ///
+    /// Only strings of exactly [`NODE_ID_BECH32_LEN`] characters are accepted.
+    pub fn from_bech32(s: &str) -> Result<Self, String> {
+        if s.len() != NODE_ID_BECH32_LEN {
+            return Err(format!(
+                "Invalid NodeId length: expected {} characters, got {}",
+                NODE_ID_BECH32_LEN,
+                s.len()
+            ));
+        }
+        let (hrp, data, variant) =
+            bech32::decode(s).map_err(|e| format!("Invalid NodeId encoding: {}", e))?;
+
+        if hrp != NODE_ID_HRP {
+            return Err(format!(
+                "Invalid NodeId prefix: expected {}, got {}",
+                NODE_ID_HRP, hrp
+            ));
+        }
+        if variant != Variant::Bech32m {
+            return Err("Invalid NodeId encoding: expected bech32m checksum".to_string());
+        }
+
+        let bytes =
+            Vec::<u8>::from_base32(&data).map_err(|e| format!("Invalid NodeId encoding: {}", e))?;
+        if bytes.len() != NODE_ID_SIZE {
+            return Err(format!(
+                "Invalid NodeId length: expected {}, got {}",
+                NODE_ID_SIZE,
+                bytes.len()
+            ));
+        }
+
+        let mut arr = [0u8; NODE_ID_SIZE];
+        arr.copy_from_slice(&bytes);
+        Ok(NodeId(arr))
+    }
+
     /// Calculate XOR distance between two node IDs (for Kademlia DHT)
     pub fn distance(&self, other: &NodeId) -> [u8; NODE_ID_SIZE] {
         let mut result = [0u8; NODE_ID_SIZE];
@@ -66,7 +130,19 @@ impl fmt::Debug for NodeId {
 
 impl fmt::Display for NodeId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", &self.to_hex()[..16])
+        write!(f, "{}", self.to_bech32())
+    }
+}
+
+impl FromStr for NodeId {
+    type Err = String;
+
+    /// Parse a bech32m node ID, or a full hex node ID
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() == NODE_ID_SIZE * 2 && s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Self::from_hex(s);
+        }
+        Self::from_bech32(s)
     }
 }
 
@@ -277,6 +353,10 @@ pub enum AdapterType {
     PPPoE = 0x0D,
     /// i2p overlay network
     I2P = 0x0E,
+    /// WebSocket (ws:// or wss://)
+    WebSocket = 0x0F,
+    /// Tor onion services
+    Tor = 0x10,
     /// Unknown/Custom adapter
     Unknown = 0xFF,
 }
@@ -299,6 +379,8 @@ impl AdapterType {
             0x0C => AdapterType::Dialup,
             0x0D => AdapterType::PPPoE,
             0x0E => AdapterType::I2P,
+            0x0F => AdapterType::WebSocket,
+            0x10 => AdapterType::Tor,
             _ => AdapterType::Unknown,
         }
     }
@@ -325,15 +407,87 @@ impl AdapterType {
             AdapterType::Dialup => "Dial-up",
             AdapterType::PPPoE => "PPPoE",
             AdapterType::I2P => "i2p",
+            AdapterType::WebSocket => "WebSocket",
+            AdapterType::Tor => "Tor",
             AdapterType::Unknown => "Unknown",
         }
     }
+
+    /// Stable lowercase identifier, the canonical spelling accepted by `from_str`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdapterType::Ethernet => "ethernet",
+            AdapterType::Bluetooth => "bluetooth",
+            AdapterType::BluetoothLE => "bluetooth_le",
+            AdapterType::Cellular => "cellular",
+            AdapterType::WiFiHaLoW => "wifi_halow",
+            AdapterType::LoRaWAN => "lorawan",
+            AdapterType::Meshtastic => "meshtastic",
+            AdapterType::FRSGMRS => "frsgmrs",
+            AdapterType::CBRadio => "cbradio",
+            AdapterType::Shortwave => "shortwave",
+            AdapterType::APRS => "aprs",
+            AdapterType::Dialup => "dialup",
+            AdapterType::PPPoE => "pppoe",
+            AdapterType::I2P => "i2p",
+            AdapterType::WebSocket => "websocket",
+            AdapterType::Tor => "tor",
+            AdapterType::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for AdapterType {
+    type Err = String;
+
+    /// Parse an adapter type name (case-insensitive), e.g. `cellular` or `ble`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ethernet" => Ok(AdapterType::Ethernet),
+            "bluetooth" => Ok(AdapterType::Bluetooth),
+            "bluetooth_le" | "bluetoothle" | "ble" => Ok(AdapterType::BluetoothLE),
+            "cellular" => Ok(AdapterType::Cellular),
+            "wifi_halow" | "wifihalow" | "halow" => Ok(AdapterType::WiFiHaLoW),
+            "lorawan" | "lora" => Ok(AdapterType::LoRaWAN),
+            "meshtastic" => Ok(AdapterType::Meshtastic),
+            "frsgmrs" | "frs" | "gmrs" => Ok(AdapterType::FRSGMRS),
+            "cbradio" | "cb" => Ok(AdapterType::CBRadio),
+            "shortwave" | "sw" => Ok(AdapterType::Shortwave),
+            "aprs" => Ok(AdapterType::APRS),
+            "dialup" => Ok(AdapterType::Dialup),
+            "pppoe" => Ok(AdapterType::PPPoE),
+            "i2p" => Ok(AdapterType::I2P),
+            "websocket" | "ws" => Ok(AdapterType::WebSocket),
+            "tor" | "onion" => Ok(AdapterType::Tor),
+            _ => Err(format!("Unknown adapter type: {}", s)),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_adapter_type_from_str() {
+        assert_eq!("Cellular".parse(), Ok(AdapterType::Cellular));
+        assert_eq!("ble".parse(), Ok(AdapterType::BluetoothLE));
+        assert_eq!("LoRa".parse(), Ok(AdapterType::LoRaWAN));
+        assert_eq!(
+            "carrier-pigeon".parse::<AdapterType>(),
+            Err("Unknown adapter type: carrier-pigeon".to_string())
+        );
+    }
+
+    #[test]
+    fn test_adapter_type_as_str_roundtrip() {
+        for value in 0x01..=0x10 {
+            let adapter_type = AdapterType::from_u8(value);
+            assert_ne!(adapter_type, AdapterType::Unknown);
+            assert_eq!(adapter_type.as_str().parse(), Ok(adapter_type));
+        }
+    }
+
     #[test]
     fn test_node_id_hex() {
         let bytes = [42u8; NODE_ID_SIZE];
@@ -345,6 +499,57 @@ mod tests {
         assert_eq!(node_id, parsed);
     }
 
+    fn pseudo_random_node_id(seed: u32) -> NodeId {
+        use blake2::{Blake2b512, Digest};
+        let mut bytes = [0u8; NODE_ID_SIZE];
+        bytes.copy_from_slice(&Blake2b512::digest(seed.to_be_bytes()));
+        NodeId::from_bytes(bytes)
+    }
+
+    #[test]
+    fn test_node_id_bech32_roundtrip() {
+        for seed in 0..32 {
+            let node_id = pseudo_random_node_id(seed);
+            let encoded = node_id.to_bech32();
+
+            assert!(encoded.starts_with("mm1"));
+            assert_eq!(encoded.len(), NODE_ID_BECH32_LEN);
+            assert_eq!(NodeId::from_bech32(&encoded).unwrap(), node_id);
+            assert_eq!(node_id.to_string(), encoded);
+            assert_eq!(encoded.parse::<NodeId>().unwrap(), node_id);
+            assert_eq!(encoded.to_uppercase().parse::<NodeId>().unwrap(), node_id);
+        }
+
+        // Full hex is accepted too
+        let node_id = pseudo_random_node_id(99);
+        assert_eq!(node_id.to_hex().parse::<NodeId>().unwrap(), node_id);
+    }
+
+    #[test]
+    fn test_node_id_bech32_rejects_corruption() {
+        let encoded = pseudo_random_node_id(7).to_bech32();
+
+        // Flip the last checksum character
+        let mut chars: Vec<char> = encoded.chars().collect();
+        let last = chars.len() - 1;
+        chars[last] = if chars[last] == 'q' { 'p' } else { 'q' };
+        let flipped: String = chars.into_iter().collect();
+        let err = NodeId::from_bech32(&flipped).unwrap_err();
+        assert!(err.contains("checksum"), "{}", err);
+
+        // Truncated strings fail the length check
+        let err = NodeId::from_bech32(&encoded[..encoded.len() - 10]).unwrap_err();
+        assert!(err.contains("length"), "{}", err);
+        assert!("mm1".parse::<NodeId>().is_err());
+
+        // Valid bech32m with the wrong prefix
+        let foreign =
+            bech32::encode("xx", [1u8; NODE_ID_SIZE].to_base32(), Variant::Bech32m).unwrap();
+        assert!(NodeId::from_bech32(&foreign)
+            .unwrap_err()
+            .contains("prefix"));
+    }
+
     #[test]
     fn test_node_id_distance() {
         let id1 = NodeId::from_bytes([0xFF; NODE_ID_SIZE]);
@@ -430,6 +635,7 @@ mod tests {
     fn test_adapter_type_conversion() {
         assert_eq!(AdapterType::from_u8(0x01), AdapterType::Ethernet);
         assert_eq!(AdapterType::from_u8(0x0E), AdapterType::I2P);
+        assert_eq!(AdapterType::from_u8(0x10), AdapterType::Tor);
         assert_eq!(AdapterType::from_u8(0xFF), AdapterType::Unknown);
 
         assert_eq!(AdapterType::Ethernet.to_u8(), 0x01);
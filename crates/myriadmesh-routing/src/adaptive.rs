@@ -122,6 +122,9 @@ impl Default for LinkMetrics {
     }
 }
 
+/// Default hysteresis margin: a new next hop must be 10% cheaper to replace the current one
+pub const DEFAULT_HYSTERESIS_MARGIN: f64 = 0.1;
+
 /// Cost calculation weights
 #[derive(Debug, Clone)]
 pub struct CostWeights {
@@ -129,6 +132,9 @@ pub struct CostWeights {
     pub loss: f64,
     pub jitter: f64,
     pub utilization: f64,
+    /// Fraction by which a candidate must undercut the current next hop's
+    /// cost before routing switches to it (0.0 disables hysteresis)
+    pub hysteresis_margin: f64,
 }
 
 impl Default for CostWeights {
@@ -138,10 +144,34 @@ impl Default for CostWeights {
             loss: 10.0,
             jitter: 0.5,
             utilization: 2.0,
+            hysteresis_margin: DEFAULT_HYSTERESIS_MARGIN,
         }
     }
 }
 
+/// Pluggable link cost function (lower is better)
+///
+/// Lets operators inject custom cost logic such as time-of-day or
+/// battery-aware penalties. Closures `Fn(&LinkMetrics) -> f64` implement it.
+pub trait RouteCostFn: Send + Sync {
+    fn cost(&self, metrics: &LinkMetrics) -> f64;
+}
+
+impl RouteCostFn for CostWeights {
+    fn cost(&self, metrics: &LinkMetrics) -> f64 {
+        metrics.calculate_cost(self)
+    }
+}
+
+impl<F> RouteCostFn for F
+where
+    F: Fn(&LinkMetrics) -> f64 + Send + Sync,
+{
+    fn cost(&self, metrics: &LinkMetrics) -> f64 {
+        self(metrics)
+    }
+}
+
 /// Routing policy
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RoutingPolicy {
@@ -166,12 +196,14 @@ impl RoutingPolicy {
                 loss: 1.0,
                 jitter: 5.0,
                 utilization: 0.5,
+                hysteresis_margin: DEFAULT_HYSTERESIS_MARGIN,
             },
             RoutingPolicy::HighReliability => CostWeights {
                 latency: 0.5,
                 loss: 20.0,
                 jitter: 1.0,
                 utilization: 1.0,
+                hysteresis_margin: DEFAULT_HYSTERESIS_MARGIN,
             },
             RoutingPolicy::Balanced => CostWeights::default(),
             RoutingPolicy::LoadBalanced => CostWeights {
@@ -179,6 +211,7 @@ impl RoutingPolicy {
                 loss: 5.0,
                 jitter: 0.5,
                 utilization: 15.0,
+                hysteresis_margin: DEFAULT_HYSTERESIS_MARGIN,
             },
             RoutingPolicy::Custom => CostWeights::default(),
         }
@@ -193,8 +226,16 @@ pub struct AdaptiveRoutingTable {
     policy: RoutingPolicy,
     /// Custom cost weights (used when policy is Custom)
     custom_weights: Option<CostWeights>,
+    /// Custom cost function (overrides the policy's weighted sum when set)
+    cost_fn: Option<Box<dyn RouteCostFn>>,
     /// Metrics time-to-live
     metrics_ttl: Duration,
+    /// Currently selected next hop for each source node
+    selected: HashMap<NodeId, NodeId>,
+    /// Number of times a selected next hop was replaced
+    route_flaps: u64,
+    /// Number of switches suppressed by the hysteresis margin
+    flaps_suppressed: u64,
 }
 
 impl AdaptiveRoutingTable {
@@ -204,10 +245,32 @@ impl AdaptiveRoutingTable {
             metrics: HashMap::new(),
             policy,
             custom_weights: None,
+            cost_fn: None,
             metrics_ttl,
+            selected: HashMap::new(),
+            route_flaps: 0,
+            flaps_suppressed: 0,
         }
     }
 
+    /// Create an adaptive routing table with a custom cost function
+    ///
+    /// The policy's weights still supply the hysteresis margin.
+    pub fn with_cost_fn(
+        policy: RoutingPolicy,
+        metrics_ttl: Duration,
+        cost_fn: impl RouteCostFn + 'static,
+    ) -> Self {
+        let mut table = Self::new(policy, metrics_ttl);
+        table.cost_fn = Some(Box::new(cost_fn));
+        table
+    }
+
+    /// Replace the cost function, or restore the policy's weighted sum with `None`
+    pub fn set_cost_fn(&mut self, cost_fn: Option<Box<dyn RouteCostFn>>) {
+        self.cost_fn = cost_fn;
+    }
+
     /// Set custom cost weights
     pub fn set_custom_weights(&mut self, weights: CostWeights) {
         self.custom_weights = Some(weights);
@@ -232,19 +295,61 @@ impl AdaptiveRoutingTable {
         self.metrics.get(&(*from, *to))
     }
 
+    /// Get the cost weights in effect for the current policy
+    pub fn weights(&self) -> CostWeights {
+        match &self.custom_weights {
+            Some(w) if self.policy == RoutingPolicy::Custom => w.clone(),
+            _ => self.policy.weights(),
+        }
+    }
+
     /// Calculate link cost
     pub fn link_cost(&self, from: &NodeId, to: &NodeId) -> Option<f64> {
-        self.get_link_metrics(from, to).map(|metrics| {
-            let weights = match &self.custom_weights {
-                Some(w) if self.policy == RoutingPolicy::Custom => w.clone(),
-                _ => self.policy.weights(),
-            };
-            metrics.calculate_cost(&weights)
-        })
+        self.get_link_metrics(from, to)
+            .map(|metrics| match &self.cost_fn {
+                Some(cost_fn) => cost_fn.cost(metrics),
+                None => self.weights().cost(metrics),
+            })
     }
 
     /// Select best next hop from neighbors
+    ///
+    /// Once a next hop is selected for `current`, it is kept until another
+    /// neighbor beats its cost by more than the policy's hysteresis margin,
+    /// preventing flapping between near-equal paths.
     pub fn select_best_neighbor(
+        &mut self,
+        current: &NodeId,
+        neighbors: &[NodeId],
+    ) -> Option<(NodeId, f64)> {
+        let best = self.lowest_cost_neighbor(current, neighbors)?;
+
+        let previous = self
+            .selected
+            .get(current)
+            .filter(|prev| neighbors.contains(prev))
+            .and_then(|prev| self.link_cost(current, prev).map(|cost| (*prev, cost)));
+
+        let chosen = match previous {
+            Some((prev, prev_cost)) if prev != best.0 => {
+                let margin = self.weights().hysteresis_margin.max(0.0);
+                if best.1 < prev_cost * (1.0 - margin) {
+                    self.route_flaps += 1;
+                    best
+                } else {
+                    self.flaps_suppressed += 1;
+                    (prev, prev_cost)
+                }
+            }
+            _ => best,
+        };
+
+        self.selected.insert(*current, chosen.0);
+        Some(chosen)
+    }
+
+    /// Find the neighbor with the lowest link cost, ignoring hysteresis
+    fn lowest_cost_neighbor(
         &self,
         current: &NodeId,
         neighbors: &[NodeId],
@@ -271,6 +376,9 @@ impl AdaptiveRoutingTable {
     pub fn cleanup_stale(&mut self) {
         self.metrics
             .retain(|_, metrics| !metrics.is_stale(self.metrics_ttl));
+        let metrics = &self.metrics;
+        self.selected
+            .retain(|from, to| metrics.contains_key(&(*from, *to)));
     }
 
     /// Get total number of tracked links
@@ -314,6 +422,8 @@ impl AdaptiveRoutingTable {
             avg_latency_ms: avg_latency,
             avg_loss_rate,
             avg_quality_score: avg_quality,
+            route_flaps: self.route_flaps,
+            flaps_suppressed: self.flaps_suppressed,
         }
     }
 }
@@ -325,6 +435,10 @@ pub struct AdaptiveRoutingStats {
     pub avg_latency_ms: f64,
     pub avg_loss_rate: f64,
     pub avg_quality_score: f64,
+    /// Number of times a selected next hop was replaced
+    pub route_flaps: u64,
+    /// Number of next-hop switches suppressed by hysteresis
+    pub flaps_suppressed: u64,
 }
 
 #[cfg(test)]
@@ -417,4 +531,146 @@ mod tests {
         // Should select neighbor1 (lower latency)
         assert_eq!(best_neighbor, neighbor1);
     }
+
+    #[test]
+    fn test_hysteresis_prevents_flapping() {
+        let mut table = AdaptiveRoutingTable::new(RoutingPolicy::Custom, Duration::from_secs(60));
+        table.set_custom_weights(CostWeights {
+            latency: 1.0,
+            loss: 0.0,
+            jitter: 0.0,
+            utilization: 0.0,
+            hysteresis_margin: 0.2,
+        });
+
+        let current = create_test_node_id(1);
+        let neighbor1 = create_test_node_id(2);
+        let neighbor2 = create_test_node_id(3);
+        let neighbors = vec![neighbor1, neighbor2];
+
+        // Seed both links with a single sample so costs follow the inputs exactly
+        table.update_link(current, neighbor1, 100.0, false, 1_000_000, 0.0);
+        table.update_link(current, neighbor2, 101.0, false, 1_000_000, 0.0);
+        let (initial, _) = table.select_best_neighbor(&current, &neighbors).unwrap();
+        assert_eq!(initial, neighbor1);
+
+        // Alternate near-equal metrics; the chosen next hop must not move
+        for round in 0..20 {
+            let (a, b) = if round % 2 == 0 {
+                (105.0, 95.0)
+            } else {
+                (95.0, 105.0)
+            };
+            table
+                .metrics
+                .get_mut(&(current, neighbor1))
+                .unwrap()
+                .latency_ms = a;
+            table
+                .metrics
+                .get_mut(&(current, neighbor2))
+                .unwrap()
+                .latency_ms = b;
+
+            let (chosen, _) = table.select_best_neighbor(&current, &neighbors).unwrap();
+            assert_eq!(chosen, neighbor1, "flapped on round {}", round);
+        }
+
+        let stats = table.stats();
+        assert_eq!(stats.route_flaps, 0);
+        assert_eq!(stats.flaps_suppressed, 10);
+
+        // Beat the current next hop by more than the 20% margin
+        table
+            .metrics
+            .get_mut(&(current, neighbor1))
+            .unwrap()
+            .latency_ms = 100.0;
+        table
+            .metrics
+            .get_mut(&(current, neighbor2))
+            .unwrap()
+            .latency_ms = 70.0;
+        let (chosen, cost) = table.select_best_neighbor(&current, &neighbors).unwrap();
+        assert_eq!(chosen, neighbor2);
+        assert_eq!(cost, 70.0);
+        assert_eq!(table.stats().route_flaps, 1);
+
+        // And the new choice is sticky in turn
+        table
+            .metrics
+            .get_mut(&(current, neighbor1))
+            .unwrap()
+            .latency_ms = 65.0;
+        let (chosen, _) = table.select_best_neighbor(&current, &neighbors).unwrap();
+        assert_eq!(chosen, neighbor2);
+    }
+
+    #[test]
+    fn test_zero_hysteresis_always_picks_cheapest() {
+        let mut table = AdaptiveRoutingTable::new(RoutingPolicy::Custom, Duration::from_secs(60));
+        table.set_custom_weights(CostWeights {
+            hysteresis_margin: 0.0,
+            ..CostWeights::default()
+        });
+
+        let current = create_test_node_id(1);
+        let neighbor1 = create_test_node_id(2);
+        let neighbor2 = create_test_node_id(3);
+        let neighbors = vec![neighbor1, neighbor2];
+
+        table.update_link(current, neighbor1, 100.0, false, 1_000_000, 0.0);
+        table.update_link(current, neighbor2, 101.0, false, 1_000_000, 0.0);
+        assert_eq!(
+            table.select_best_neighbor(&current, &neighbors).unwrap().0,
+            neighbor1
+        );
+
+        table
+            .metrics
+            .get_mut(&(current, neighbor2))
+            .unwrap()
+            .latency_ms = 99.0;
+        assert_eq!(
+            table.select_best_neighbor(&current, &neighbors).unwrap().0,
+            neighbor2
+        );
+        assert_eq!(table.stats().route_flaps, 1);
+    }
+
+    #[test]
+    fn test_custom_cost_fn_inverts_preference() {
+        let current = create_test_node_id(1);
+        let fast = create_test_node_id(2);
+        let slow = create_test_node_id(3);
+        let neighbors = vec![fast, slow];
+
+        let mut table =
+            AdaptiveRoutingTable::new(RoutingPolicy::LowLatency, Duration::from_secs(60));
+        table.update_link(current, fast, 10.0, false, 1_000_000, 0.2);
+        table.update_link(current, slow, 200.0, false, 1_000_000, 0.2);
+        assert_eq!(
+            table.select_best_neighbor(&current, &neighbors).unwrap().0,
+            fast
+        );
+
+        // Prefer high-latency links, e.g. to keep traffic off a metered fast link
+        let mut table = AdaptiveRoutingTable::with_cost_fn(
+            RoutingPolicy::LowLatency,
+            Duration::from_secs(60),
+            |metrics: &LinkMetrics| 1000.0 / (metrics.latency_ms + 1.0),
+        );
+        table.update_link(current, fast, 10.0, false, 1_000_000, 0.2);
+        table.update_link(current, slow, 200.0, false, 1_000_000, 0.2);
+
+        let (chosen, cost) = table.select_best_neighbor(&current, &neighbors).unwrap();
+        assert_eq!(chosen, slow);
+        assert!((cost - 1000.0 / 201.0).abs() < 1e-9);
+
+        // Removing the custom function restores the weighted sum
+        table.set_cost_fn(None);
+        let weighted = table.link_cost(&current, &fast).unwrap();
+        let metrics = table.get_link_metrics(&current, &fast).unwrap();
+        assert_eq!(weighted, RoutingPolicy::LowLatency.weights().cost(metrics));
+    }
 }
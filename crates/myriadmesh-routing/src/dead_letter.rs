@@ -0,0 +1,224 @@
+//! Dead-letter queue for undeliverable messages
+//!
+//! Messages the router gives up on (TTL exhausted, send attempts used up, or
+//! a send error that retrying cannot fix) are kept here with the reason, so
+//! delivery failures can be inspected after the fact. The queue is bounded;
+//! when full the oldest entry is evicted.
+
+use myriadmesh_protocol::message::Message;
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default number of dead letters kept
+pub const DEFAULT_DEAD_LETTER_CAPACITY: usize = 256;
+
+/// Why a message was given up on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeadLetterReason {
+    /// TTL ran out before the message could be forwarded
+    TtlExpired,
+    /// The message's wall-clock expiry passed before it was forwarded
+    Expired,
+    /// Every send attempt allowed by the retry policy failed
+    RetriesExhausted {
+        /// Send attempts made
+        attempts: u32,
+        /// Error from the final attempt
+        last_error: String,
+    },
+    /// A send failed with an error retrying cannot fix
+    NonRetryable {
+        /// The send error
+        error: String,
+    },
+    /// A send failed and no queue capacity was left to hold the retry
+    RetryQueueFull,
+}
+
+impl fmt::Display for DeadLetterReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeadLetterReason::TtlExpired => write!(f, "TTL expired"),
+            DeadLetterReason::Expired => write!(f, "expired"),
+            DeadLetterReason::RetriesExhausted {
+                attempts,
+                last_error,
+            } => write!(f, "gave up after {} attempts: {}", attempts, last_error),
+            DeadLetterReason::NonRetryable { error } => write!(f, "non-retryable: {}", error),
+            DeadLetterReason::RetryQueueFull => write!(f, "no capacity left to retry"),
+        }
+    }
+}
+
+/// An undeliverable message and why it failed
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub message: Message,
+    pub reason: DeadLetterReason,
+    /// Wall-clock failure time in seconds since the Unix epoch
+    pub failed_at: u64,
+}
+
+/// Bounded queue of dead letters, oldest first
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    entries: VecDeque<DeadLetter>,
+    capacity: usize,
+    /// Dead letters evicted to make room
+    evicted: u64,
+}
+
+impl DeadLetterQueue {
+    /// Create a queue holding at most `capacity` dead letters
+    ///
+    /// A capacity of zero disables the queue: nothing is kept.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity.min(DEFAULT_DEAD_LETTER_CAPACITY)),
+            capacity,
+            evicted: 0,
+        }
+    }
+
+    /// Record a failed message, evicting the oldest entry if full
+    pub fn push(&mut self, message: Message, reason: DeadLetterReason) {
+        if self.capacity == 0 {
+            self.evicted += 1;
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+            self.evicted += 1;
+        }
+        let failed_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.entries.push_back(DeadLetter {
+            message,
+            reason,
+            failed_at,
+        });
+    }
+
+    /// Change the capacity, evicting the oldest entries that no longer fit
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            self.entries.pop_front();
+            self.evicted += 1;
+        }
+    }
+
+    /// Maximum dead letters kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Dead letters currently held
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no dead letters are held
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Dead letters, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &DeadLetter> {
+        self.entries.iter()
+    }
+
+    /// Remove and return all dead letters, oldest first
+    pub fn drain(&mut self) -> Vec<DeadLetter> {
+        self.entries.drain(..).collect()
+    }
+
+    /// Dead letters evicted (or not kept) because the queue was full
+    pub fn evicted(&self) -> u64 {
+        self.evicted
+    }
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEAD_LETTER_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myriadmesh_protocol::{message::MessageType, types::NODE_ID_SIZE, NodeId};
+
+    fn message(sequence: u32) -> Message {
+        Message::new(
+            NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+            NodeId::from_bytes([2u8; NODE_ID_SIZE]),
+            MessageType::Data,
+            vec![0u8; 16],
+        )
+        .unwrap()
+        .with_sequence(sequence)
+    }
+
+    fn sequences(queue: &DeadLetterQueue) -> Vec<u32> {
+        queue.iter().map(|d| d.message.sequence).collect()
+    }
+
+    #[test]
+    fn test_cap_drops_oldest() {
+        let mut queue = DeadLetterQueue::new(3);
+        for sequence in 0..5 {
+            queue.push(message(sequence), DeadLetterReason::TtlExpired);
+        }
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(sequences(&queue), vec![2, 3, 4]);
+        assert_eq!(queue.evicted(), 2);
+    }
+
+    #[test]
+    fn test_drain_empties_queue() {
+        let mut queue = DeadLetterQueue::default();
+        queue.push(
+            message(7),
+            DeadLetterReason::NonRetryable {
+                error: "bad address".to_string(),
+            },
+        );
+
+        let drained = queue.drain();
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].message.sequence, 7);
+        assert!(drained[0].failed_at > 0);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_shrinking_capacity_evicts() {
+        let mut queue = DeadLetterQueue::new(4);
+        for sequence in 0..4 {
+            queue.push(message(sequence), DeadLetterReason::TtlExpired);
+        }
+        queue.set_capacity(1);
+        assert_eq!(sequences(&queue), vec![3]);
+
+        queue.set_capacity(0);
+        queue.push(message(9), DeadLetterReason::TtlExpired);
+        assert!(queue.is_empty());
+        assert_eq!(queue.evicted(), 5);
+    }
+
+    #[test]
+    fn test_reason_display() {
+        let reason = DeadLetterReason::RetriesExhausted {
+            attempts: 3,
+            last_error: "timeout".to_string(),
+        };
+        assert_eq!(reason.to_string(), "gave up after 3 attempts: timeout");
+        assert_eq!(DeadLetterReason::TtlExpired.to_string(), "TTL expired");
+    }
+}
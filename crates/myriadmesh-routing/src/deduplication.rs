@@ -1,9 +1,26 @@
 //! Message deduplication cache
-
+//!
+//! Two modes are supported:
+//! - **Exact**: every message id is kept in an LRU map (default)
+//! - **Hybrid**: a counting Bloom filter over a bounded window of 64-bit
+//!   fingerprints answers negative lookups, backed by a small LRU of recent
+//!   ids for confirmation. Trades a bounded false-positive rate for a much
+//!   smaller memory footprint.
+
+use blake2::digest::consts::U8;
+use blake2::digest::{KeyInit, Mac};
+use blake2::Blake2bMac;
 use myriadmesh_protocol::MessageId;
 use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// In hybrid mode, the confirmation LRU holds `max_size / HYBRID_LRU_DIVISOR` ids
+const HYBRID_LRU_DIVISOR: usize = 16;
+
+/// Maximum value of a 4-bit counter; saturated counters are never decremented
+const COUNTER_MAX: u8 = 0x0f;
+
 /// Get current timestamp
 fn now() -> u64 {
     SystemTime::now()
@@ -12,6 +29,162 @@ fn now() -> u64 {
         .as_secs()
 }
 
+/// Counting Bloom filter over a bounded FIFO window of fingerprints
+#[derive(Debug)]
+struct BloomWindow {
+    /// 4-bit counters, two per byte
+    counters: Vec<u8>,
+
+    /// Number of counters
+    num_counters: u64,
+
+    /// Number of hash functions
+    num_hashes: u32,
+
+    /// Fingerprints in insertion order, with the time they were inserted
+    window: VecDeque<(u64, u64)>,
+
+    /// Maximum number of fingerprints in the window
+    capacity: usize,
+
+    /// Target false-positive rate at full capacity
+    false_positive_rate: f64,
+
+    /// Secret key for [`BloomWindow::fingerprint`]
+    key: [u8; 32],
+}
+
+impl BloomWindow {
+    fn new(capacity: usize, false_positive_rate: f64) -> Self {
+        let capacity = capacity.max(1);
+        let p = false_positive_rate.clamp(1e-9, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+
+        // Standard Bloom sizing: m = -n ln(p) / ln(2)^2, k = (m / n) ln(2)
+        let num_counters = ((-(capacity as f64) * p.ln()) / (ln2 * ln2)).ceil() as u64;
+        let num_counters = num_counters.max(8);
+        let num_hashes = ((num_counters as f64 / capacity as f64) * ln2).round() as u32;
+
+        BloomWindow {
+            counters: vec![0; num_counters.div_ceil(2) as usize],
+            num_counters,
+            num_hashes: num_hashes.max(1),
+            window: VecDeque::with_capacity(capacity),
+            capacity,
+            false_positive_rate: p,
+            key: rand::random(),
+        }
+    }
+
+    /// Keyed 64-bit fingerprint of a message id, from which Bloom indices
+    /// are derived
+    ///
+    /// SECURITY: Message ids are chosen by peers. Keyed BLAKE2b with a secret
+    /// per-cache key keeps them from crafting ids that collide in the filter
+    /// and get legitimate messages dropped as duplicates.
+    fn fingerprint(&self, message_id: &MessageId) -> u64 {
+        let mut mac = <Blake2bMac<U8> as KeyInit>::new_from_slice(&self.key)
+            .expect("32-byte keys are within BLAKE2b's 64-byte limit");
+        mac.update(message_id.as_bytes());
+        u64::from_le_bytes(mac.finalize().into_bytes().into())
+    }
+
+    /// Counter indices for a fingerprint (Kirsch-Mitzenmacher double hashing)
+    fn indices(&self, fp: u64) -> impl Iterator<Item = usize> + '_ {
+        let h1 = fp & 0xffff_ffff;
+        let h2 = (fp >> 32) | 1;
+        (0..self.num_hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % self.num_counters) as usize)
+    }
+
+    fn counter(&self, index: usize) -> u8 {
+        (self.counters[index / 2] >> ((index % 2) * 4)) & COUNTER_MAX
+    }
+
+    fn set_counter(&mut self, index: usize, value: u8) {
+        let shift = (index % 2) * 4;
+        let byte = &mut self.counters[index / 2];
+        *byte = (*byte & !(COUNTER_MAX << shift)) | (value << shift);
+    }
+
+    fn contains(&self, fp: u64) -> bool {
+        self.indices(fp).all(|i| self.counter(i) > 0)
+    }
+
+    fn insert(&mut self, fp: u64, seen_at: u64) {
+        if self.window.len() >= self.capacity {
+            self.pop_oldest();
+        }
+        let indices: Vec<usize> = self.indices(fp).collect();
+        for i in indices {
+            let c = self.counter(i);
+            if c < COUNTER_MAX {
+                self.set_counter(i, c + 1);
+            }
+        }
+        self.window.push_back((fp, seen_at));
+    }
+
+    fn pop_oldest(&mut self) -> bool {
+        let Some((fp, _)) = self.window.pop_front() else {
+            return false;
+        };
+        let indices: Vec<usize> = self.indices(fp).collect();
+        for i in indices {
+            let c = self.counter(i);
+            if c > 0 && c < COUNTER_MAX {
+                self.set_counter(i, c - 1);
+            }
+        }
+        true
+    }
+
+    /// Drop fingerprints older than the TTL; the window is in insertion order
+    fn expire(&mut self, current_time: u64, ttl_secs: u64) -> usize {
+        let mut removed = 0;
+        while let Some(&(_, seen_at)) = self.window.front() {
+            if current_time.saturating_sub(seen_at) < ttl_secs {
+                break;
+            }
+            self.pop_oldest();
+            removed += 1;
+        }
+        removed
+    }
+
+    /// Expected false-positive rate at the current fill level
+    fn estimated_false_positive_rate(&self) -> f64 {
+        let k = self.num_hashes as f64;
+        let n = self.window.len() as f64;
+        let m = self.num_counters as f64;
+        (1.0 - (-k * n / m).exp()).powf(k)
+    }
+
+    fn clear(&mut self) {
+        self.counters.iter_mut().for_each(|c| *c = 0);
+        self.window.clear();
+    }
+}
+
+/// Deduplication cache statistics
+#[derive(Debug, Clone, Default)]
+pub struct DeduplicationStats {
+    /// Lookups that reported the message as already seen
+    pub hits: u64,
+    /// Lookups that reported the message as new
+    pub misses: u64,
+    /// Hits confirmed by an exact id match
+    pub confirmed_hits: u64,
+    /// Hits reported by the Bloom filter alone (old duplicates or false positives)
+    pub unconfirmed_hits: u64,
+    /// Expected false-positive rate at the current fill level (0.0 in exact mode)
+    pub estimated_false_positive_rate: f64,
+    /// Estimated number of lookups that were false positives
+    pub estimated_false_positives: u64,
+    /// Target false-positive rate (None in exact mode)
+    pub target_false_positive_rate: Option<f64>,
+}
+
 /// LRU cache for message deduplication
 #[derive(Debug)]
 pub struct DeduplicationCache {
@@ -21,26 +194,81 @@ pub struct DeduplicationCache {
     /// LRU queue for eviction
     lru_queue: VecDeque<MessageId>,
 
-    /// Maximum cache size
+    /// Maximum number of exact entries
     max_size: usize,
 
     /// TTL for entries (seconds)
     ttl_secs: u64,
+
+    /// Bloom filter window (hybrid mode only)
+    bloom: Option<BloomWindow>,
+
+    hits: AtomicU64,
+    misses: AtomicU64,
+    unconfirmed_hits: AtomicU64,
 }
 
 impl DeduplicationCache {
-    /// Create a new deduplication cache
+    /// Create a new deduplication cache holding exact message ids
     pub fn new(max_size: usize, ttl_secs: u64) -> Self {
         DeduplicationCache {
             entries: HashMap::with_capacity(max_size),
             lru_queue: VecDeque::with_capacity(max_size),
             max_size,
             ttl_secs,
+            bloom: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            unconfirmed_hits: AtomicU64::new(0),
         }
     }
 
+    /// Create a hybrid Bloom+LRU cache
+    ///
+    /// The Bloom filter covers the `max_size` most recent ids with the given
+    /// target false-positive rate; an exact LRU of `max_size / 16` ids
+    /// confirms recent duplicates. There are no false negatives for ids
+    /// within the window and TTL.
+    pub fn hybrid(max_size: usize, ttl_secs: u64, false_positive_rate: f64) -> Self {
+        let lru_size = (max_size / HYBRID_LRU_DIVISOR).max(1);
+        let mut cache = Self::new(lru_size, ttl_secs);
+        cache.bloom = Some(BloomWindow::new(max_size, false_positive_rate));
+        cache
+    }
+
+    /// Check whether this cache is in hybrid Bloom+LRU mode
+    pub fn is_hybrid(&self) -> bool {
+        self.bloom.is_some()
+    }
+
     /// Check if a message has been seen
+    ///
+    /// In hybrid mode, expired fingerprints are only dropped by `mark_seen`
+    /// and `cleanup_expired`.
     pub fn has_seen(&self, message_id: &MessageId) -> bool {
+        let seen = match &self.bloom {
+            None => self.lookup_exact(message_id),
+            Some(bloom) => {
+                if !bloom.contains(bloom.fingerprint(message_id)) {
+                    false
+                } else if self.lookup_exact(message_id) {
+                    true
+                } else {
+                    self.unconfirmed_hits.fetch_add(1, Ordering::Relaxed);
+                    true
+                }
+            }
+        };
+
+        if seen {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        seen
+    }
+
+    fn lookup_exact(&self, message_id: &MessageId) -> bool {
         if let Some(&seen_at) = self.entries.get(message_id) {
             // Check if entry is still valid (not expired)
             let age = now().saturating_sub(seen_at);
@@ -54,6 +282,14 @@ impl DeduplicationCache {
     pub fn mark_seen(&mut self, message_id: MessageId) {
         let current_time = now();
 
+        if let Some(bloom) = &mut self.bloom {
+            bloom.expire(current_time, self.ttl_secs);
+            if !self.entries.contains_key(&message_id) {
+                let fp = bloom.fingerprint(&message_id);
+                bloom.insert(fp, current_time);
+            }
+        }
+
         // If already exists, update timestamp and move to back of LRU
         if let std::collections::hash_map::Entry::Occupied(mut e) = self.entries.entry(message_id) {
             e.insert(current_time);
@@ -99,6 +335,10 @@ impl DeduplicationCache {
             })
             .collect();
 
+        if let Some(bloom) = &mut self.bloom {
+            bloom.expire(current_time, self.ttl_secs);
+        }
+
         // Remove expired entries
         for id in expired {
             self.entries.remove(&id);
@@ -115,19 +355,57 @@ impl DeduplicationCache {
     }
 
     /// Get number of entries in cache
+    ///
+    /// In hybrid mode this counts fingerprints in the Bloom window.
     pub fn len(&self) -> usize {
-        self.entries.len()
+        match &self.bloom {
+            Some(bloom) => bloom.window.len(),
+            None => self.entries.len(),
+        }
     }
 
     /// Check if cache is empty
     pub fn is_empty(&self) -> bool {
-        self.entries.is_empty()
+        self.len() == 0
     }
 
     /// Clear all entries
     pub fn clear(&mut self) {
         self.entries.clear();
         self.lru_queue.clear();
+        if let Some(bloom) = &mut self.bloom {
+            bloom.clear();
+        }
+    }
+
+    /// Get lookup statistics
+    pub fn stats(&self) -> DeduplicationStats {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let unconfirmed_hits = self.unconfirmed_hits.load(Ordering::Relaxed);
+
+        let (estimated_false_positive_rate, target_false_positive_rate) = match &self.bloom {
+            Some(bloom) => (
+                bloom.estimated_false_positive_rate(),
+                Some(bloom.false_positive_rate),
+            ),
+            None => (0.0, None),
+        };
+
+        // Only unconfirmed hits can be false positives
+        let estimated_false_positives = (((hits + misses) as f64 * estimated_false_positive_rate)
+            .round() as u64)
+            .min(unconfirmed_hits);
+
+        DeduplicationStats {
+            hits,
+            misses,
+            confirmed_hits: hits - unconfirmed_hits,
+            unconfirmed_hits,
+            estimated_false_positive_rate,
+            estimated_false_positives,
+            target_false_positive_rate,
+        }
     }
 }
 
@@ -247,4 +525,117 @@ mod tests {
         assert!(!cache.has_seen(&id2)); // Evicted
         assert!(cache.has_seen(&id3));
     }
+
+    fn sequential_message_id(tag: u8, n: u64) -> MessageId {
+        let mut bytes = [tag; 16];
+        bytes[..8].copy_from_slice(&n.to_le_bytes());
+        MessageId::from_bytes(bytes)
+    }
+
+    #[test]
+    fn test_hybrid_no_false_negatives() {
+        let mut cache = DeduplicationCache::hybrid(5_000, 3600, 0.01);
+        assert!(cache.is_hybrid());
+
+        for n in 0..5_000 {
+            cache.mark_seen(sequential_message_id(0xaa, n));
+        }
+        assert_eq!(cache.len(), 5_000);
+
+        // Every id in the window is reported, even those long gone from the LRU
+        for n in 0..5_000 {
+            assert!(cache.has_seen(&sequential_message_id(0xaa, n)));
+        }
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 5_000);
+        assert_eq!(stats.misses, 0);
+        assert!(stats.confirmed_hits > 0);
+        assert!(stats.unconfirmed_hits > 0);
+    }
+
+    #[test]
+    fn test_hybrid_false_positive_rate_within_bound() {
+        let target = 0.01;
+        let mut cache = DeduplicationCache::hybrid(10_000, 3600, target);
+
+        for n in 0..10_000 {
+            cache.mark_seen(sequential_message_id(0x11, n));
+        }
+
+        let probes = 20_000;
+        let false_positives = (0..probes)
+            .filter(|&n| cache.has_seen(&sequential_message_id(0x22, n)))
+            .count();
+
+        let observed = false_positives as f64 / probes as f64;
+        assert!(
+            observed <= target * 1.5,
+            "observed false-positive rate {} exceeds bound",
+            observed
+        );
+
+        let stats = cache.stats();
+        assert_eq!(stats.target_false_positive_rate, Some(target));
+        assert!(stats.estimated_false_positive_rate <= target * 1.5);
+        assert!(stats.estimated_false_positives <= stats.unconfirmed_hits);
+    }
+
+    #[test]
+    fn test_fingerprints_are_keyed_per_cache() {
+        let a = BloomWindow::new(100, 0.01);
+        let b = BloomWindow::new(100, 0.01);
+        let id = create_test_message_id(7);
+
+        assert_eq!(a.fingerprint(&id), a.fingerprint(&id));
+        assert_ne!(a.fingerprint(&id), b.fingerprint(&id));
+    }
+
+    #[test]
+    fn test_hybrid_window_eviction() {
+        let mut cache = DeduplicationCache::hybrid(100, 3600, 0.001);
+
+        for n in 0..200 {
+            cache.mark_seen(sequential_message_id(0x33, n));
+        }
+        assert_eq!(cache.len(), 100);
+
+        // Most of the evicted half should no longer match
+        let still_matching = (0..100)
+            .filter(|&n| cache.has_seen(&sequential_message_id(0x33, n)))
+            .count();
+        assert!(still_matching < 10);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(!cache.has_seen(&sequential_message_id(0x33, 150)));
+    }
+
+    #[test]
+    fn test_hybrid_expired_entries() {
+        let mut cache = DeduplicationCache::hybrid(100, 0, 0.01);
+        cache.mark_seen(create_test_message_id(1));
+        cache.mark_seen(create_test_message_id(2));
+
+        cache.cleanup_expired();
+        assert!(cache.is_empty());
+        assert!(!cache.has_seen(&create_test_message_id(1)));
+    }
+
+    #[test]
+    fn test_exact_mode_stats() {
+        let mut cache = DeduplicationCache::new(100, 3600);
+        let id = create_test_message_id(1);
+
+        assert!(!cache.has_seen(&id));
+        cache.mark_seen(id);
+        assert!(cache.has_seen(&id));
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.unconfirmed_hits, 0);
+        assert_eq!(stats.estimated_false_positives, 0);
+        assert_eq!(stats.target_false_positive_rate, None);
+    }
 }
@@ -1,6 +1,6 @@
 //! Routing error types
 
-use myriadmesh_protocol::message::MessageId;
+use myriadmesh_protocol::{message::MessageId, NodeId};
 use thiserror::Error;
 
 /// Routing-specific errors
@@ -39,6 +39,12 @@ pub enum RoutingError {
     #[error("Insufficient relays for onion routing")]
     InsufficientRelays,
 
+    #[error("No path can meet the {deadline_ms}ms deadline (best estimate: {best_estimate_ms}ms)")]
+    DeadlineUnreachable {
+        deadline_ms: u64,
+        best_estimate_ms: u64,
+    },
+
     // SECURITY M1: DOS protection error types
     #[error("Invalid message: {0}")]
     InvalidMessage(String),
@@ -55,6 +61,18 @@ pub enum RoutingError {
     #[error("Policy violation: {0}")]
     PolicyViolation(String),
 
+    #[error("Source route hop unreachable: {0}")]
+    HopUnreachable(NodeId),
+
+    #[error("Node is not on the message's source route")]
+    NotOnSourceRoute,
+
+    #[error("Message expired")]
+    MessageExpired,
+
+    #[error("Invalid tag pattern: {0}")]
+    InvalidTagPattern(String),
+
     #[error("Protocol error: {0}")]
     Protocol(#[from] myriadmesh_protocol::ProtocolError),
 
@@ -67,9 +85,52 @@ pub enum RoutingError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("Send failed: {message}")]
+    SendFailed { message: String, retryable: bool },
+
     #[error("Other error: {0}")]
     Other(String),
 }
 
+impl RoutingError {
+    /// Whether a failed send may succeed if retried later
+    ///
+    /// Missing routes, rate limits and transport errors flagged retryable
+    /// are worth retrying; rejected or malformed messages are not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RoutingError::SendFailed { retryable, .. } => *retryable,
+
+            RoutingError::DestinationNotFound(_)
+            | RoutingError::NoRoute
+            | RoutingError::RateLimitExceeded
+            | RoutingError::GlobalRateLimitExceeded
+            | RoutingError::RateLimited(_)
+            | RoutingError::QueueFull(_)
+            | RoutingError::CacheFull
+            | RoutingError::InsufficientRelays
+            | RoutingError::HopUnreachable(_)
+            | RoutingError::Dht(_)
+            | RoutingError::Io(_)
+            | RoutingError::Other(_) => true,
+
+            RoutingError::TtlExceeded
+            | RoutingError::ReplayDetected
+            | RoutingError::InvalidSignature
+            | RoutingError::InvalidTimestamp { .. }
+            | RoutingError::MessageFiltered
+            | RoutingError::DeadlineUnreachable { .. }
+            | RoutingError::InvalidMessage(_)
+            | RoutingError::DuplicateMessage(_)
+            | RoutingError::PolicyViolation(_)
+            | RoutingError::NotOnSourceRoute
+            | RoutingError::MessageExpired
+            | RoutingError::InvalidTagPattern(_)
+            | RoutingError::Protocol(_)
+            | RoutingError::Crypto(_) => false,
+        }
+    }
+}
+
 /// Result type for routing operations
 pub type Result<T> = std::result::Result<T, RoutingError>;
@@ -4,13 +4,17 @@
 //! routing system to decide whether to fragment at the router or adapter level.
 
 use myriadmesh_protocol::Frame;
+use reed_solomon_erasure::galois_8::ReedSolomon;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
-use crate::error::Result;
+use crate::error::{Result, RoutingError};
+
+/// Default maximum number of concurrent in-flight reassemblies
+pub const DEFAULT_MAX_PENDING_REASSEMBLIES: usize = 256;
 
 /// Fragmentation decision
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -36,29 +40,201 @@ pub enum FragmentationReason {
     AdapterHandled,
 }
 
+impl FragmentationDecision {
+    /// Decide whether a serialized message of `size` bytes must be split for `link`
+    pub fn for_size(size: usize, link: LinkMtu) -> Self {
+        let mtu = link.budget();
+        if size <= mtu {
+            Self {
+                should_fragment: false,
+                reason: FragmentationReason::WithinMtu,
+                mtu,
+            }
+        } else {
+            Self {
+                should_fragment: true,
+                reason: FragmentationReason::ExceedsMtu,
+                mtu,
+            }
+        }
+    }
+
+    /// Decide whether `frame` must be split to be sent over `link`
+    pub fn for_frame(frame: &Frame, link: impl Into<LinkMtu>) -> Result<Self> {
+        let size = bincode::serialized_size(frame)
+            .map_err(|e| RoutingError::Other(format!("Serialization failed: {}", e)))?;
+        Ok(Self::for_size(size as usize, link.into()))
+    }
+}
+
+/// Size limit of the link a frame is about to be sent over
+///
+/// Built from the chosen adapter's capabilities, or from a bare MTU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkMtu {
+    /// Largest message the adapter carries in one transmission
+    pub max_message_size: usize,
+    /// Bytes of each transmission the adapter needs for its own framing
+    pub overhead: usize,
+}
+
+impl LinkMtu {
+    /// Link carrying up to `max_message_size` bytes with no extra framing
+    pub fn new(max_message_size: usize) -> Self {
+        Self {
+            max_message_size,
+            overhead: 0,
+        }
+    }
+
+    /// Reserve `overhead` bytes of each transmission for adapter framing
+    pub fn with_overhead(mut self, overhead: usize) -> Self {
+        self.overhead = overhead;
+        self
+    }
+
+    /// Bytes available to a fragment, including its `FragmentHeader`
+    pub fn budget(&self) -> usize {
+        self.max_message_size.saturating_sub(self.overhead)
+    }
+}
+
+impl From<usize> for LinkMtu {
+    fn from(max_message_size: usize) -> Self {
+        Self::new(max_message_size)
+    }
+}
+
+/// Kind of shard carried by a fragment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShardKind {
+    /// Fragment carries a slice of the original message
+    Data,
+    /// Fragment carries Reed-Solomon parity for its group
+    Parity,
+}
+
+/// Forward error correction settings for fragmentation
+///
+/// Data fragments are split into groups of `data_shards`, and each group gets
+/// `parity_shards` Reed-Solomon parity fragments. A group can be reconstructed
+/// as long as no more than `parity_shards` of its fragments are lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FecConfig {
+    /// Data fragments per FEC group
+    pub data_shards: u8,
+    /// Parity fragments per FEC group
+    pub parity_shards: u8,
+}
+
+impl FecConfig {
+    /// Create a new FEC configuration
+    pub fn new(data_shards: u8, parity_shards: u8) -> Self {
+        Self {
+            data_shards,
+            parity_shards,
+        }
+    }
+}
+
+impl Default for FecConfig {
+    fn default() -> Self {
+        Self::new(4, 2)
+    }
+}
+
 /// Fragment header
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FragmentHeader {
     /// Message ID (same across all fragments of a message)
     pub message_id: u16,
     /// Fragment number (0-indexed)
+    ///
+    /// For data shards this is the index among all data fragments; for parity
+    /// shards it is the index of the parity shard within its group.
     pub fragment_num: u8,
-    /// Total number of fragments
+    /// Total number of data fragments
     pub total_fragments: u8,
+    /// Data or parity shard
+    pub kind: ShardKind,
+    /// FEC group this fragment belongs to
+    pub group_id: u8,
+    /// Data fragments per FEC group (0 = FEC disabled)
+    pub data_shards: u8,
+    /// Parity fragments per FEC group
+    pub parity_shards: u8,
 }
 
 impl FragmentHeader {
-    /// Size of fragment header in bytes
+    /// Size of a plain fragment header in bytes
     pub const SIZE: usize = 4; // 2 bytes message_id + 1 byte fragment_num + 1 byte total
 
+    /// Size of an FEC fragment header in bytes
+    ///
+    /// The plain layout with `FEC_MARKER` as its total, followed by 1 byte
+    /// total + 1 byte kind + 1 byte group_id + 1 byte data_shards + 1 byte
+    /// parity_shards
+    pub const FEC_SIZE: usize = 9;
+
+    /// Total of a plain header announcing the FEC layout
+    ///
+    /// A plain message always has at least one fragment, so peers that only
+    /// know the plain layout never send it.
+    const FEC_MARKER: u8 = 0;
+
+    /// Create a header for a plain data fragment without FEC
+    pub fn new(message_id: u16, fragment_num: u8, total_fragments: u8) -> Self {
+        Self {
+            message_id,
+            fragment_num,
+            total_fragments,
+            kind: ShardKind::Data,
+            group_id: 0,
+            data_shards: 0,
+            parity_shards: 0,
+        }
+    }
+
+    /// Whether this fragment belongs to an FEC-protected message
+    pub fn has_fec(&self) -> bool {
+        self.data_shards > 0
+    }
+
+    fn completed_key(&self) -> CompletedKey {
+        (self.message_id, self.total_fragments, self.data_shards)
+    }
+
+    /// Size of this header once serialized
+    pub fn encoded_len(&self) -> usize {
+        if self.has_fec() {
+            Self::FEC_SIZE
+        } else {
+            Self::SIZE
+        }
+    }
+
     /// Serialize to bytes
     pub fn to_bytes(&self) -> Vec<u8> {
-        vec![
+        let mut bytes = vec![
             (self.message_id >> 8) as u8,
             (self.message_id & 0xFF) as u8,
             self.fragment_num,
             self.total_fragments,
-        ]
+        ];
+        if self.has_fec() {
+            bytes[3] = Self::FEC_MARKER;
+            bytes.extend_from_slice(&[
+                self.total_fragments,
+                match self.kind {
+                    ShardKind::Data => 0,
+                    ShardKind::Parity => 1,
+                },
+                self.group_id,
+                self.data_shards,
+                self.parity_shards,
+            ]);
+        }
+        bytes
     }
 
     /// Deserialize from bytes
@@ -67,37 +243,159 @@ impl FragmentHeader {
             return None;
         }
 
+        let message_id = ((data[0] as u16) << 8) | (data[1] as u16);
+        if data[3] != Self::FEC_MARKER {
+            return Some(Self::new(message_id, data[2], data[3]));
+        }
+
+        if data.len() < Self::FEC_SIZE || data[7] == 0 {
+            return None;
+        }
+        let kind = match data[5] {
+            0 => ShardKind::Data,
+            1 => ShardKind::Parity,
+            _ => return None,
+        };
+
         Some(Self {
-            message_id: ((data[0] as u16) << 8) | (data[1] as u16),
+            message_id,
             fragment_num: data[2],
-            total_fragments: data[3],
+            total_fragments: data[4],
+            kind,
+            group_id: data[6],
+            data_shards: data[7],
+            parity_shards: data[8],
         })
     }
 }
 
-/// Fragment a frame into multiple smaller frames
-pub fn fragment_frame(frame: &Frame, mtu: usize) -> Result<Vec<Vec<u8>>> {
+/// Number of data fragments in a given FEC group
+fn group_data_shards(total_fragments: usize, data_shards: usize, group_id: usize) -> usize {
+    data_shards.min(total_fragments.saturating_sub(group_id * data_shards))
+}
+
+/// Fragment a frame to fit the link it will be sent over
+///
+/// Fragments are sized to the link's MTU minus its overhead. A frame that
+/// already fits is returned whole, without a fragment header.
+pub fn fragment_frame(frame: &Frame, link: impl Into<LinkMtu>) -> Result<Vec<Vec<u8>>> {
+    fragment_frame_with_fec(frame, link, None)
+}
+
+/// Fragment a frame, optionally adding Reed-Solomon parity fragments
+///
+/// With FEC enabled the message is prefixed with its length, split into
+/// equally sized data shards and every group of `data_shards` fragments is
+/// followed by `parity_shards` parity fragments.
+pub fn fragment_frame_with_fec(
+    frame: &Frame,
+    link: impl Into<LinkMtu>,
+    fec: Option<FecConfig>,
+) -> Result<Vec<Vec<u8>>> {
     let serialized = bincode::serialize(frame)
-        .map_err(|e| crate::error::RoutingError::Other(format!("Serialization failed: {}", e)))?;
+        .map_err(|e| RoutingError::Other(format!("Serialization failed: {}", e)))?;
 
-    if serialized.len() <= mtu {
+    let decision = FragmentationDecision::for_size(serialized.len(), link.into());
+    if !decision.should_fragment {
         return Ok(vec![serialized]);
     }
 
-    let header_size = FragmentHeader::SIZE;
-    let payload_size = mtu.saturating_sub(header_size);
+    let header_size = match fec {
+        Some(_) => FragmentHeader::FEC_SIZE,
+        None => FragmentHeader::SIZE,
+    };
+    let payload_size = decision.mtu.saturating_sub(header_size);
 
     if payload_size == 0 {
-        return Err(crate::error::RoutingError::Other(
+        return Err(RoutingError::Other(
             "MTU too small for fragmentation".to_string(),
         ));
     }
 
     let message_id = rand::random::<u16>();
+
+    let fec = match fec {
+        Some(fec) => fec,
+        None => return fragment_plain(&serialized, message_id, payload_size),
+    };
+
+    if fec.data_shards == 0 || fec.data_shards as usize + fec.parity_shards as usize > 255 {
+        return Err(RoutingError::Other(format!(
+            "Invalid FEC configuration: {} data + {} parity shards",
+            fec.data_shards, fec.parity_shards
+        )));
+    }
+
+    // Length prefix lets the reassembler strip the zero padding of the last shard
+    let mut data = Vec::with_capacity(serialized.len() + 4);
+    data.extend_from_slice(&(serialized.len() as u32).to_be_bytes());
+    data.extend_from_slice(&serialized);
+
+    let total_frags = data.len().div_ceil(payload_size);
+    if total_frags > 255 {
+        return Err(RoutingError::Other(
+            "Message too large: exceeds maximum fragments (255)".to_string(),
+        ));
+    }
+
+    let data_shards = fec.data_shards as usize;
+    let total_groups = total_frags.div_ceil(data_shards);
+    let mut fragments = Vec::new();
+
+    for group_id in 0..total_groups {
+        let group_data = group_data_shards(total_frags, data_shards, group_id);
+        let first = group_id * data_shards;
+
+        let mut shards: Vec<Vec<u8>> = (first..first + group_data)
+            .map(|frag_num| {
+                let start = frag_num * payload_size;
+                let end = std::cmp::min(start + payload_size, data.len());
+                let mut shard = data[start..end].to_vec();
+                shard.resize(payload_size, 0);
+                shard
+            })
+            .collect();
+
+        if fec.parity_shards > 0 {
+            shards.extend((0..fec.parity_shards).map(|_| vec![0u8; payload_size]));
+            let rs = ReedSolomon::new(group_data, fec.parity_shards as usize)
+                .map_err(|e| RoutingError::Other(format!("FEC setup failed: {:?}", e)))?;
+            rs.encode(&mut shards)
+                .map_err(|e| RoutingError::Other(format!("FEC encoding failed: {:?}", e)))?;
+        }
+
+        for (index, shard) in shards.iter().enumerate() {
+            let (kind, fragment_num) = if index < group_data {
+                (ShardKind::Data, first + index)
+            } else {
+                (ShardKind::Parity, index - group_data)
+            };
+
+            let header = FragmentHeader {
+                message_id,
+                fragment_num: fragment_num as u8,
+                total_fragments: total_frags as u8,
+                kind,
+                group_id: group_id as u8,
+                data_shards: fec.data_shards,
+                parity_shards: fec.parity_shards,
+            };
+
+            let mut fragment = header.to_bytes();
+            fragment.extend_from_slice(shard);
+            fragments.push(fragment);
+        }
+    }
+
+    Ok(fragments)
+}
+
+/// Split serialized data into plain data fragments without FEC
+fn fragment_plain(serialized: &[u8], message_id: u16, payload_size: usize) -> Result<Vec<Vec<u8>>> {
     let total_frags = serialized.len().div_ceil(payload_size);
 
     if total_frags > 255 {
-        return Err(crate::error::RoutingError::Other(
+        return Err(RoutingError::Other(
             "Message too large: exceeds maximum fragments (255)".to_string(),
         ));
     }
@@ -108,11 +406,7 @@ pub fn fragment_frame(frame: &Frame, mtu: usize) -> Result<Vec<Vec<u8>>> {
         let start = frag_num * payload_size;
         let end = std::cmp::min(start + payload_size, serialized.len());
 
-        let header = FragmentHeader {
-            message_id,
-            fragment_num: frag_num as u8,
-            total_fragments: total_frags as u8,
-        };
+        let header = FragmentHeader::new(message_id, frag_num as u8, total_frags as u8);
 
         let mut fragment = header.to_bytes();
         fragment.extend_from_slice(&serialized[start..end]);
@@ -127,79 +421,258 @@ pub fn fragment_frame(frame: &Frame, mtu: usize) -> Result<Vec<Vec<u8>>> {
 struct ReassemblyState {
     /// Fragment storage
     fragments: Vec<Option<Vec<u8>>>,
+    /// Parity shard storage, indexed by `group_id * parity_shards + fragment_num`
+    parity: Vec<Option<Vec<u8>>>,
     /// Total expected fragments
-    #[allow(dead_code)]
     total_fragments: u8,
+    /// Data fragments per FEC group (0 = FEC disabled)
+    data_shards: u8,
+    /// Parity fragments per FEC group
+    parity_shards: u8,
     /// Timestamp when first fragment received
     started_at: Instant,
 }
 
+impl ReassemblyState {
+    fn new(header: &FragmentHeader) -> Self {
+        let total_groups = if header.has_fec() {
+            (header.total_fragments as usize).div_ceil(header.data_shards as usize)
+        } else {
+            0
+        };
+
+        Self {
+            fragments: vec![None; header.total_fragments as usize],
+            parity: vec![None; total_groups * header.parity_shards as usize],
+            total_fragments: header.total_fragments,
+            data_shards: header.data_shards,
+            parity_shards: header.parity_shards,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Whether this reassembly has been pending longer than `timeout`
+    fn is_stale(&self, now: Instant, timeout: Duration) -> bool {
+        now.saturating_duration_since(self.started_at) > timeout
+    }
+
+    /// Whether a fragment header is consistent with this reassembly
+    fn matches(&self, header: &FragmentHeader) -> bool {
+        header.total_fragments == self.total_fragments
+            && header.data_shards == self.data_shards
+            && header.parity_shards == self.parity_shards
+    }
+
+    /// Store a fragment payload, returning false if it is out of range
+    fn store(&mut self, header: &FragmentHeader, payload: &[u8]) -> bool {
+        let slot = match header.kind {
+            ShardKind::Data => {
+                if header.has_fec()
+                    && header.group_id as usize
+                        != header.fragment_num as usize / header.data_shards as usize
+                {
+                    return false;
+                }
+                self.fragments.get_mut(header.fragment_num as usize)
+            }
+            ShardKind::Parity => {
+                if header.fragment_num >= self.parity_shards {
+                    return false;
+                }
+                let index = header.group_id as usize * self.parity_shards as usize
+                    + header.fragment_num as usize;
+                self.parity.get_mut(index)
+            }
+        };
+
+        match slot {
+            Some(slot) => {
+                *slot = Some(payload.to_vec());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Recover missing data shards from parity where possible
+    fn recover(&mut self) {
+        if self.data_shards == 0 || self.parity_shards == 0 {
+            return;
+        }
+
+        let data_shards = self.data_shards as usize;
+        let parity_shards = self.parity_shards as usize;
+        let total = self.total_fragments as usize;
+
+        for group_id in 0..total.div_ceil(data_shards) {
+            let group_data = group_data_shards(total, data_shards, group_id);
+            let first = group_id * data_shards;
+            let data_range = first..first + group_data;
+            let parity_range = group_id * parity_shards..(group_id + 1) * parity_shards;
+
+            if self.fragments[data_range.clone()]
+                .iter()
+                .all(Option::is_some)
+            {
+                continue;
+            }
+
+            let mut shards: Vec<Option<Vec<u8>>> = self.fragments[data_range.clone()]
+                .iter()
+                .chain(self.parity[parity_range].iter())
+                .cloned()
+                .collect();
+
+            if shards.iter().filter(|s| s.is_some()).count() < group_data {
+                continue;
+            }
+
+            let rs = match ReedSolomon::new(group_data, parity_shards) {
+                Ok(rs) => rs,
+                Err(_) => continue,
+            };
+            if rs.reconstruct_data(&mut shards).is_ok() {
+                for (slot, shard) in self.fragments[data_range].iter_mut().zip(shards) {
+                    *slot = shard;
+                }
+            }
+        }
+    }
+
+    /// Concatenate the data shards into the original message
+    fn assemble(&self) -> Option<Vec<u8>> {
+        let mut result = Vec::new();
+        for data in self.fragments.iter().flatten() {
+            result.extend_from_slice(data);
+        }
+
+        if self.data_shards == 0 {
+            return Some(result);
+        }
+
+        // FEC messages carry a length prefix and zero-padded shards
+        if result.len() < 4 {
+            return None;
+        }
+        let len = u32::from_be_bytes([result[0], result[1], result[2], result[3]]) as usize;
+        if len > result.len() - 4 {
+            return None;
+        }
+        result.drain(..4);
+        result.truncate(len);
+        Some(result)
+    }
+}
+
+/// Message id, total fragments and data shards of a completed FEC message
+type CompletedKey = (u16, u8, u8);
+
 /// Fragment reassembler
 pub struct FragmentReassembler {
     /// Pending fragment reassembly states
     pending: Arc<RwLock<HashMap<u16, ReassemblyState>>>,
-    /// Fragment timeout
-    timeout: Duration,
+    /// Recently completed FEC messages, so surplus parity fragments arriving
+    /// after reconstruction don't start a new reassembly
+    ///
+    /// Keyed by the whole message shape, not just the random 16-bit id, so a
+    /// new message reusing the id is less likely to be dropped.
+    completed: Arc<RwLock<HashMap<CompletedKey, Instant>>>,
+    /// How long an incomplete reassembly may stay pending
+    reassembly_timeout: Duration,
+    /// SECURITY: Hard cap on concurrent in-flight reassemblies
+    max_pending: usize,
 }
 
 impl FragmentReassembler {
     /// Create a new fragment reassembler
-    pub fn new(timeout: Duration) -> Self {
+    pub fn new(reassembly_timeout: Duration) -> Self {
+        Self::with_limits(reassembly_timeout, DEFAULT_MAX_PENDING_REASSEMBLIES)
+    }
+
+    /// Create a fragment reassembler with a custom cap on in-flight reassemblies
+    pub fn with_limits(reassembly_timeout: Duration, max_pending: usize) -> Self {
         Self {
             pending: Arc::new(RwLock::new(HashMap::new())),
-            timeout,
+            completed: Arc::new(RwLock::new(HashMap::new())),
+            reassembly_timeout,
+            max_pending,
         }
     }
 
+    /// Get the reassembly timeout
+    pub fn reassembly_timeout(&self) -> Duration {
+        self.reassembly_timeout
+    }
+
+    /// Get the maximum number of concurrent in-flight reassemblies
+    pub fn max_pending(&self) -> usize {
+        self.max_pending
+    }
+
     /// Add a fragment and try to reassemble
     ///
     /// Returns Some(data) if all fragments are received, None otherwise
     pub async fn add_fragment(&self, fragment_data: &[u8]) -> Option<Vec<u8>> {
         // Parse header
         let header = FragmentHeader::from_bytes(fragment_data)?;
+        let payload = &fragment_data[header.encoded_len()..];
 
-        if fragment_data.len() < FragmentHeader::SIZE {
-            return None;
-        }
+        let mut pending = self.pending.write().await;
 
-        let payload = &fragment_data[FragmentHeader::SIZE..];
+        if header.has_fec() {
+            let completed = self.completed.read().await;
+            if let Some(completed_at) = completed.get(&header.completed_key()) {
+                if completed_at.elapsed() < self.reassembly_timeout {
+                    return None;
+                }
+            }
+        }
 
-        let mut pending = self.pending.write().await;
+        // SECURITY: Reject fragments starting a new reassembly once the cap is
+        // reached, so hostile peers can't exhaust memory with partial messages
+        if !pending.contains_key(&header.message_id) && pending.len() >= self.max_pending {
+            let now = Instant::now();
+            pending.retain(|_, state| !state.is_stale(now, self.reassembly_timeout));
+            if pending.len() >= self.max_pending {
+                return None;
+            }
+        }
 
         // Initialize state if first fragment and get mutable reference
         let state = pending
             .entry(header.message_id)
-            .or_insert_with(|| ReassemblyState {
-                fragments: vec![None; header.total_fragments as usize],
-                total_fragments: header.total_fragments,
-                started_at: Instant::now(),
-            });
+            .or_insert_with(|| ReassemblyState::new(&header));
 
         // Check timeout
-        if state.started_at.elapsed() > self.timeout {
+        if state.started_at.elapsed() > self.reassembly_timeout {
             pending.remove(&header.message_id);
             return None;
         }
 
         // Store fragment
-        if (header.fragment_num as usize) < state.fragments.len() {
-            state.fragments[header.fragment_num as usize] = Some(payload.to_vec());
-        } else {
+        if !state.matches(&header) || !state.store(&header, payload) {
             return None;
         }
 
+        // Fill in lost data fragments from parity
+        state.recover();
+
         // Check if complete
         if state.fragments.iter().all(|f| f.is_some()) {
             // Reassemble
-            let mut result = Vec::new();
-            for data in state.fragments.iter().flatten() {
-                result.extend_from_slice(data);
-            }
+            let result = state.assemble();
 
             // Remove from pending
             pending.remove(&header.message_id);
 
-            Some(result)
+            if header.has_fec() {
+                self.completed
+                    .write()
+                    .await
+                    .insert(header.completed_key(), Instant::now());
+            }
+
+            result
         } else {
             None
         }
@@ -207,8 +680,24 @@ impl FragmentReassembler {
 
     /// Clean up expired fragment reassembly states
     pub async fn cleanup_expired(&self) {
+        self.evict_stale(Instant::now()).await;
+    }
+
+    /// Drop incomplete reassemblies older than the reassembly timeout
+    ///
+    /// Returns the number of evicted reassemblies.
+    pub async fn evict_stale(&self, now: Instant) -> usize {
         let mut pending = self.pending.write().await;
-        pending.retain(|_, state| state.started_at.elapsed() < self.timeout);
+        let before = pending.len();
+        pending.retain(|_, state| !state.is_stale(now, self.reassembly_timeout));
+        let evicted = before - pending.len();
+
+        let mut completed = self.completed.write().await;
+        completed.retain(|_, completed_at| {
+            now.saturating_duration_since(*completed_at) <= self.reassembly_timeout
+        });
+
+        evicted
     }
 
     /// Get number of pending reassembly states
@@ -233,15 +722,36 @@ mod tests {
             message_id: 0x1234,
             fragment_num: 5,
             total_fragments: 10,
+            kind: ShardKind::Parity,
+            group_id: 1,
+            data_shards: 4,
+            parity_shards: 2,
         };
 
         let bytes = header.to_bytes();
-        assert_eq!(bytes.len(), FragmentHeader::SIZE);
+        assert_eq!(bytes.len(), FragmentHeader::FEC_SIZE);
 
         let deserialized = FragmentHeader::from_bytes(&bytes).unwrap();
         assert_eq!(deserialized.message_id, header.message_id);
         assert_eq!(deserialized.fragment_num, header.fragment_num);
         assert_eq!(deserialized.total_fragments, header.total_fragments);
+        assert_eq!(deserialized.kind, header.kind);
+        assert_eq!(deserialized.group_id, header.group_id);
+        assert_eq!(deserialized.data_shards, header.data_shards);
+        assert_eq!(deserialized.parity_shards, header.parity_shards);
+    }
+
+    #[test]
+    fn test_plain_header_keeps_four_byte_layout() {
+        let header = FragmentHeader::new(0x1234, 2, 7);
+        assert_eq!(header.to_bytes(), vec![0x12, 0x34, 2, 7]);
+
+        // Fragments from peers without FEC support parse unchanged
+        let parsed = FragmentHeader::from_bytes(&[0xAB, 0xCD, 0, 3, 0xFF]).unwrap();
+        assert_eq!(parsed.message_id, 0xABCD);
+        assert_eq!(parsed.total_fragments, 3);
+        assert!(!parsed.has_fec());
+        assert_eq!(parsed.encoded_len(), FragmentHeader::SIZE);
     }
 
     #[test]
@@ -256,6 +766,56 @@ mod tests {
         assert!(test_data.len() <= mtu); // Small message doesn't need fragmentation
     }
 
+    fn frame_with_payload(len: usize) -> Frame {
+        use myriadmesh_protocol::{MessageId, MessageType, NodeId};
+
+        Frame::new(
+            MessageType::Data,
+            NodeId::from_bytes([2u8; 64]),
+            NodeId::from_bytes([3u8; 64]),
+            (0..len).map(|i| i as u8).collect(),
+            MessageId::from_bytes([1u8; 16]),
+            1704067200000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fragment_size_follows_link_mtu() {
+        let frame = frame_with_payload(4000);
+        let lora = LinkMtu::new(240);
+        let ethernet = LinkMtu::new(1472);
+
+        let lora_fragments = fragment_frame(&frame, lora).unwrap();
+        let ethernet_fragments = fragment_frame(&frame, ethernet).unwrap();
+
+        assert!(lora_fragments.len() > ethernet_fragments.len());
+        assert!(ethernet_fragments.len() > 1);
+        assert!(lora_fragments.iter().all(|f| f.len() <= 240));
+        assert!(ethernet_fragments.iter().all(|f| f.len() <= 1472));
+
+        // Adapter overhead shrinks the fragments
+        let framed = fragment_frame(&frame, lora.with_overhead(40)).unwrap();
+        assert!(framed.iter().all(|f| f.len() <= 200));
+        assert!(framed.len() > lora_fragments.len());
+    }
+
+    #[test]
+    fn test_small_frame_not_fragmented_on_large_mtu() {
+        let frame = frame_with_payload(100);
+        let serialized = bincode::serialize(&frame).unwrap();
+
+        let decision = FragmentationDecision::for_frame(&frame, LinkMtu::new(1472)).unwrap();
+        assert!(!decision.should_fragment);
+        assert_eq!(decision.reason, FragmentationReason::WithinMtu);
+        assert_eq!(decision.mtu, 1472);
+        assert_eq!(fragment_frame(&frame, 1472).unwrap(), vec![serialized]);
+
+        let decision = FragmentationDecision::for_frame(&frame, LinkMtu::new(240)).unwrap();
+        assert!(decision.should_fragment);
+        assert_eq!(decision.reason, FragmentationReason::ExceedsMtu);
+    }
+
     #[tokio::test]
     async fn test_fragment_reassembly() {
         let reassembler = FragmentReassembler::default();
@@ -265,17 +825,8 @@ mod tests {
         let payload1 = vec![1, 2, 3, 4];
         let payload2 = vec![5, 6, 7, 8];
 
-        let header1 = FragmentHeader {
-            message_id,
-            fragment_num: 0,
-            total_fragments: 2,
-        };
-
-        let header2 = FragmentHeader {
-            message_id,
-            fragment_num: 1,
-            total_fragments: 2,
-        };
+        let header1 = FragmentHeader::new(message_id, 0, 2);
+        let header2 = FragmentHeader::new(message_id, 1, 2);
 
         let mut frag1 = header1.to_bytes();
         frag1.extend_from_slice(&payload1);
@@ -302,11 +853,7 @@ mod tests {
         let reassembler = FragmentReassembler::new(Duration::from_millis(100));
 
         let message_id = 0x5678;
-        let header = FragmentHeader {
-            message_id,
-            fragment_num: 0,
-            total_fragments: 2,
-        };
+        let header = FragmentHeader::new(message_id, 0, 2);
 
         let mut frag = header.to_bytes();
         frag.extend_from_slice(&[1, 2, 3, 4]);
@@ -323,4 +870,185 @@ mod tests {
         // Should have been removed
         assert_eq!(reassembler.pending_count().await, 0);
     }
+
+    /// Fragment a test frame with 4+2 FEC so it forms exactly one group
+    fn fec_test_fragments() -> (Vec<u8>, Vec<Vec<u8>>) {
+        use myriadmesh_protocol::{MessageId, MessageType, NodeId};
+
+        let frame = Frame::new(
+            MessageType::Data,
+            NodeId::from_bytes([2u8; 64]),
+            NodeId::from_bytes([3u8; 64]),
+            (0..600).map(|i| i as u8).collect(),
+            MessageId::from_bytes([1u8; 16]),
+            1704067200000,
+        )
+        .unwrap();
+
+        let serialized = bincode::serialize(&frame).unwrap();
+        let mtu = (serialized.len() + 4).div_ceil(4) + FragmentHeader::FEC_SIZE;
+        let fragments = fragment_frame_with_fec(&frame, mtu, Some(FecConfig::new(4, 2))).unwrap();
+
+        (serialized, fragments)
+    }
+
+    #[tokio::test]
+    async fn test_fec_fragment_layout() {
+        let (_, fragments) = fec_test_fragments();
+        assert_eq!(fragments.len(), 6);
+
+        let headers: Vec<_> = fragments
+            .iter()
+            .map(|f| FragmentHeader::from_bytes(f).unwrap())
+            .collect();
+        assert_eq!(
+            headers.iter().filter(|h| h.kind == ShardKind::Data).count(),
+            4
+        );
+        assert_eq!(
+            headers
+                .iter()
+                .filter(|h| h.kind == ShardKind::Parity)
+                .count(),
+            2
+        );
+        assert!(headers.iter().all(|h| h.group_id == 0 && h.has_fec()));
+    }
+
+    #[tokio::test]
+    async fn test_fec_reassembly_with_one_loss() {
+        let (serialized, fragments) = fec_test_fragments();
+        let reassembler = FragmentReassembler::default();
+
+        let mut result = None;
+        for (i, fragment) in fragments.iter().enumerate() {
+            if i == 1 {
+                continue; // Lost data fragment
+            }
+            if let Some(data) = reassembler.add_fragment(fragment).await {
+                result = Some(data);
+            }
+        }
+
+        assert_eq!(result, Some(serialized));
+        assert_eq!(reassembler.pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fec_reassembly_with_two_losses() {
+        let (serialized, fragments) = fec_test_fragments();
+        let reassembler = FragmentReassembler::default();
+
+        let mut result = None;
+        for (i, fragment) in fragments.iter().enumerate() {
+            if i == 0 || i == 3 {
+                continue; // Lost data fragments
+            }
+            if let Some(data) = reassembler.add_fragment(fragment).await {
+                result = Some(data);
+            }
+        }
+
+        assert_eq!(result, Some(serialized));
+    }
+
+    #[tokio::test]
+    async fn test_fec_reassembly_fails_with_three_losses() {
+        let (_, fragments) = fec_test_fragments();
+        let reassembler = FragmentReassembler::default();
+
+        for (i, fragment) in fragments.iter().enumerate() {
+            if i == 0 || i == 2 || i == 4 {
+                continue; // Two data fragments and one parity fragment lost
+            }
+            assert!(reassembler.add_fragment(fragment).await.is_none());
+        }
+
+        assert_eq!(reassembler.pending_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_completed_id_reused_by_different_message() {
+        let (_, fragments) = fec_test_fragments();
+        let reassembler = FragmentReassembler::default();
+        for fragment in &fragments {
+            reassembler.add_fragment(fragment).await;
+        }
+
+        // Surplus fragments of the completed message are ignored
+        assert!(reassembler.add_fragment(&fragments[5]).await.is_none());
+        assert_eq!(reassembler.pending_count().await, 0);
+
+        // A different message that drew the same id still reassembles
+        let id = FragmentHeader::from_bytes(&fragments[0])
+            .unwrap()
+            .message_id;
+        // Length-prefixed two-byte message split over two data shards
+        let shards = [[0, 0, 0, 2], [9, 9, 0, 0]];
+        let mut result = None;
+        for (fragment_num, shard) in (0u8..).zip(shards) {
+            let header = FragmentHeader {
+                message_id: id,
+                fragment_num,
+                total_fragments: 2,
+                kind: ShardKind::Data,
+                group_id: 0,
+                data_shards: 2,
+                parity_shards: 0,
+            };
+            let mut frag = header.to_bytes();
+            frag.extend_from_slice(&shard);
+            result = reassembler.add_fragment(&frag).await;
+        }
+        assert_eq!(result, Some(vec![9, 9]));
+    }
+
+    #[tokio::test]
+    async fn test_evict_stale_reassemblies() {
+        let reassembler = FragmentReassembler::new(Duration::from_secs(30));
+
+        for message_id in 0..3u16 {
+            let mut frag = FragmentHeader::new(message_id, 0, 2).to_bytes();
+            frag.extend_from_slice(&[1, 2, 3, 4]);
+            assert!(reassembler.add_fragment(&frag).await.is_none());
+        }
+        assert_eq!(reassembler.pending_count().await, 3);
+
+        // Nothing is older than the timeout yet
+        assert_eq!(reassembler.evict_stale(Instant::now()).await, 0);
+        assert_eq!(reassembler.pending_count().await, 3);
+
+        // All incomplete groups are evicted once the timeout has passed
+        let later = Instant::now() + Duration::from_secs(31);
+        assert_eq!(reassembler.evict_stale(later).await, 3);
+        assert_eq!(reassembler.pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pending_reassembly_cap() {
+        let reassembler = FragmentReassembler::with_limits(Duration::from_secs(30), 2);
+
+        let fragment = |message_id: u16, fragment_num: u8| {
+            let mut frag = FragmentHeader::new(message_id, fragment_num, 2).to_bytes();
+            frag.extend_from_slice(&[fragment_num; 4]);
+            frag
+        };
+
+        assert!(reassembler.add_fragment(&fragment(1, 0)).await.is_none());
+        assert!(reassembler.add_fragment(&fragment(2, 0)).await.is_none());
+        assert_eq!(reassembler.pending_count().await, 2);
+
+        // A third concurrent reassembly is rejected
+        assert!(reassembler.add_fragment(&fragment(3, 0)).await.is_none());
+        assert!(reassembler.add_fragment(&fragment(3, 1)).await.is_none());
+        assert_eq!(reassembler.pending_count().await, 2);
+
+        // Fragments for in-flight reassemblies are still accepted
+        let result = reassembler.add_fragment(&fragment(1, 1)).await;
+        assert_eq!(result, Some(vec![0, 0, 0, 0, 1, 1, 1, 1]));
+
+        // Completing one frees a slot
+        assert!(reassembler.add_fragment(&fragment(3, 0)).await.is_none());
+        assert_eq!(reassembler.pending_count().await, 2);
+    }
 }
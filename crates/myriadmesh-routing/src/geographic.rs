@@ -3,11 +3,115 @@
 //! Implements geographic routing algorithms that use node location data
 //! to make intelligent routing decisions. Nodes closer to the destination
 //! are preferred to minimize hops and latency.
+//!
+//! Greedy forwarding stalls at local minima, where a void leaves no neighbor
+//! closer to the destination. [`GeoRoutingTable::gpsr_next_hop`] then falls
+//! back to GPSR perimeter routing: the packet walks the faces of the
+//! planarized (Gabriel graph) neighbor graph by the right-hand rule until it
+//! reaches a node closer to the destination than where greedy forwarding
+//! failed.
 
 use myriadmesh_protocol::NodeId;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Mean Earth radius in kilometers
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Normalize a longitude difference into the range [-180, 180)
+///
+/// Differences across the antimeridian (e.g. 179° to -179°) become the short
+/// way around (2°) instead of the long way (-358°).
+fn wrap_longitude_delta(delta: f64) -> f64 {
+    (delta + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Kilometers per degree of latitude
+const KM_PER_DEGREE: f64 = EARTH_RADIUS_KM * std::f64::consts::PI / 180.0;
+
+/// Point in a local plane around a node: (km east, km north)
+type PlanePoint = (f64, f64);
+
+/// Project `point` onto the plane tangent at `origin` (equirectangular)
+///
+/// Neighbor distances are short, so the distortion is negligible for the
+/// angle and intersection tests of perimeter routing.
+fn project(origin: &GeoCoordinates, point: &GeoCoordinates) -> PlanePoint {
+    let x = wrap_longitude_delta(point.longitude - origin.longitude)
+        * origin.latitude.to_radians().cos()
+        * KM_PER_DEGREE;
+    let y = (point.latitude - origin.latitude) * KM_PER_DEGREE;
+    (x, y)
+}
+
+/// Inverse of [`project`]
+fn unproject(origin: &GeoCoordinates, (x, y): PlanePoint) -> GeoCoordinates {
+    let latitude = origin.latitude + y / KM_PER_DEGREE;
+    let longitude = origin.longitude + x / (KM_PER_DEGREE * origin.latitude.to_radians().cos());
+    GeoCoordinates::new(latitude, wrap_longitude_delta(longitude))
+}
+
+/// Counterclockwise angle of `to` seen from `from`, in radians
+fn plane_angle(from: PlanePoint, to: PlanePoint) -> f64 {
+    (to.1 - from.1).atan2(to.0 - from.0)
+}
+
+/// Intersection of segments `a1`-`a2` and `b1`-`b2`, if they cross
+fn segment_intersection(
+    a1: PlanePoint,
+    a2: PlanePoint,
+    b1: PlanePoint,
+    b2: PlanePoint,
+) -> Option<PlanePoint> {
+    let cross = |u: PlanePoint, v: PlanePoint| u.0 * v.1 - u.1 * v.0;
+    let r = (a2.0 - a1.0, a2.1 - a1.1);
+    let s = (b2.0 - b1.0, b2.1 - b1.1);
+    let denom = cross(r, s);
+    if denom.abs() < f64::EPSILON {
+        return None; // Parallel or degenerate
+    }
+
+    let q = (b1.0 - a1.0, b1.1 - a1.1);
+    let t = cross(q, s) / denom;
+    let u = cross(q, r) / denom;
+    if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+        Some((a1.0 + t * r.0, a1.1 + t * r.1))
+    } else {
+        None
+    }
+}
+
+/// GPSR forwarding mode, carried with the packet between hops
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GpsrMode {
+    /// Forward to the neighbor closest to the destination
+    Greedy,
+    /// Walk the planar graph around a void
+    Perimeter(Box<PerimeterState>),
+}
+
+/// Perimeter-mode packet state (GPSR's Lp, Lf and e0)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerimeterState {
+    /// Where greedy forwarding failed; greedy resumes at any node closer
+    /// to the destination than this
+    pub entry: GeoCoordinates,
+    /// Where the packet entered the current face
+    pub face_entry: GeoCoordinates,
+    /// First edge traversed on the current face; crossing it again means
+    /// the destination is unreachable
+    pub first_edge: (NodeId, NodeId),
+    /// Node that forwarded the packet, the reference for the right-hand rule
+    pub last_hop: NodeId,
+}
+
+/// Next hop chosen by GPSR and the mode to send the packet in
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpsrHop {
+    pub next_hop: NodeId,
+    pub mode: GpsrMode,
+}
+
 /// Geographic coordinates (latitude, longitude)
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct GeoCoordinates {
@@ -38,27 +142,38 @@ impl GeoCoordinates {
         }
     }
 
-    /// Calculate Haversine distance to another point (in kilometers)
-    pub fn distance_to(&self, other: &GeoCoordinates) -> f64 {
-        const EARTH_RADIUS_KM: f64 = 6371.0;
-
+    /// Calculate great-circle distance to another point (in kilometers)
+    ///
+    /// Uses the haversine formula, which stays accurate for short distances,
+    /// near the poles and for paths crossing the ±180° antimeridian.
+    pub fn haversine_distance(&self, other: &GeoCoordinates) -> f64 {
         let lat1 = self.latitude.to_radians();
         let lat2 = other.latitude.to_radians();
         let delta_lat = (other.latitude - self.latitude).to_radians();
-        let delta_lon = (other.longitude - self.longitude).to_radians();
+        let delta_lon = wrap_longitude_delta(other.longitude - self.longitude).to_radians();
 
         let a = (delta_lat / 2.0).sin().powi(2)
             + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+        // Rounding can push `a` slightly outside [0, 1] for (near-)antipodal points
+        let a = a.clamp(0.0, 1.0);
         let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
 
         EARTH_RADIUS_KM * c
     }
 
-    /// Calculate bearing to another point (in degrees, 0-360)
+    /// Calculate Haversine distance to another point (in kilometers)
+    pub fn distance_to(&self, other: &GeoCoordinates) -> f64 {
+        self.haversine_distance(other)
+    }
+
+    /// Calculate initial great-circle bearing to another point (in degrees, 0-360)
+    ///
+    /// 0° is north, 90° east. Paths crossing the antimeridian take the short
+    /// way around.
     pub fn bearing_to(&self, other: &GeoCoordinates) -> f64 {
         let lat1 = self.latitude.to_radians();
         let lat2 = other.latitude.to_radians();
-        let delta_lon = (other.longitude - self.longitude).to_radians();
+        let delta_lon = wrap_longitude_delta(other.longitude - self.longitude).to_radians();
 
         let y = delta_lon.sin() * lat2.cos();
         let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * delta_lon.cos();
@@ -129,7 +244,7 @@ impl GeoRoutingTable {
         let mut distances: Vec<(NodeId, f64)> = self
             .locations
             .values()
-            .map(|loc| (loc.node_id, loc.coordinates.distance_to(target)))
+            .map(|loc| (loc.node_id, loc.coordinates.haversine_distance(target)))
             .collect();
 
         // Sort by distance (NaN-safe)
@@ -156,7 +271,7 @@ impl GeoRoutingTable {
             .values()
             .filter_map(|loc| {
                 let bearing = from.bearing_to(&loc.coordinates);
-                let distance = from.distance_to(&loc.coordinates);
+                let distance = from.haversine_distance(&loc.coordinates);
 
                 // Calculate angular difference
                 let mut diff = (bearing - target_bearing).abs();
@@ -190,11 +305,11 @@ impl GeoRoutingTable {
         neighbors: &[NodeId],
     ) -> Option<(NodeId, f64)> {
         let mut best: Option<(NodeId, f64)> = None;
-        let current_dist = current_pos.distance_to(dest_pos);
+        let current_dist = current_pos.haversine_distance(dest_pos);
 
         for neighbor in neighbors {
             if let Some(neighbor_loc) = self.get_location(neighbor) {
-                let neighbor_dist = neighbor_loc.coordinates.distance_to(dest_pos);
+                let neighbor_dist = neighbor_loc.coordinates.haversine_distance(dest_pos);
 
                 // Greedy: only consider neighbors closer to destination
                 if neighbor_dist < current_dist {
@@ -213,6 +328,145 @@ impl GeoRoutingTable {
         best
     }
 
+    /// Neighbors kept in the Gabriel graph around `current_pos`
+    ///
+    /// The edge to a neighbor is dropped if another neighbor lies inside the
+    /// circle whose diameter is that edge. The result is planar (no crossing
+    /// edges) yet stays connected if the full neighbor graph is. Neighbors
+    /// without a known location are left out.
+    pub fn planar_neighbors(
+        &self,
+        current_pos: &GeoCoordinates,
+        neighbors: &[NodeId],
+    ) -> Vec<(NodeId, GeoCoordinates)> {
+        let located: Vec<(NodeId, GeoCoordinates, PlanePoint)> = neighbors
+            .iter()
+            .filter_map(|id| self.get_location(id))
+            .map(|loc| {
+                let point = project(current_pos, &loc.coordinates);
+                (loc.node_id, loc.coordinates, point)
+            })
+            .collect();
+
+        let dist2 = |a: PlanePoint, b: PlanePoint| (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2);
+        located
+            .iter()
+            .filter(|(id, _, v)| {
+                let midpoint = (v.0 / 2.0, v.1 / 2.0);
+                let radius2 = dist2((0.0, 0.0), *v) / 4.0;
+                !located
+                    .iter()
+                    .any(|(other, _, w)| other != id && dist2(midpoint, *w) < radius2)
+            })
+            .map(|(id, coordinates, _)| (*id, *coordinates))
+            .collect()
+    }
+
+    /// Calculate next hop with GPSR: greedy forwarding, falling back to
+    /// perimeter routing around voids
+    ///
+    /// `mode` is the mode the packet arrived in (`Greedy` at the source), and
+    /// the returned hop carries the mode to forward it in. Returns `None` if
+    /// there is no neighbor to forward to, or if the perimeter walk looped
+    /// back to its first edge, meaning the destination is unreachable.
+    pub fn gpsr_next_hop(
+        &self,
+        current: NodeId,
+        current_pos: &GeoCoordinates,
+        dest_pos: &GeoCoordinates,
+        neighbors: &[NodeId],
+        mode: GpsrMode,
+    ) -> Option<GpsrHop> {
+        let state = match mode {
+            GpsrMode::Perimeter(state)
+                if current_pos.haversine_distance(dest_pos)
+                    >= state.entry.haversine_distance(dest_pos) =>
+            {
+                state
+            }
+            // Greedy, or perimeter mode that got past the void
+            _ => {
+                if let Some((next_hop, _)) = self.greedy_next_hop(current_pos, dest_pos, neighbors)
+                {
+                    return Some(GpsrHop {
+                        next_hop,
+                        mode: GpsrMode::Greedy,
+                    });
+                }
+                return self.enter_perimeter(current, current_pos, dest_pos, neighbors);
+            }
+        };
+
+        let planar = self.planar_neighbors(current_pos, neighbors);
+        let last_hop_pos = self.get_location(&state.last_hop)?.coordinates;
+        let reference = plane_angle((0.0, 0.0), project(current_pos, &last_hop_pos));
+        let mut next = right_hand_next(current_pos, &planar, reference)?;
+
+        // Face change: if the edge crosses the line from the entry point to
+        // the destination closer to it than where this face was entered,
+        // continue on the adjacent face instead
+        let entry = project(current_pos, &state.entry);
+        let dest = project(current_pos, dest_pos);
+        let mut face_entry = state.face_entry;
+        let mut first_edge = state.first_edge;
+        for _ in 0..planar.len() {
+            let crossing =
+                segment_intersection((0.0, 0.0), project(current_pos, &next.1), entry, dest)
+                    .map(|point| unproject(current_pos, point))
+                    .filter(|point| {
+                        point.haversine_distance(dest_pos) < face_entry.haversine_distance(dest_pos)
+                    });
+            let Some(point) = crossing else {
+                break;
+            };
+
+            face_entry = point;
+            let crossed = plane_angle((0.0, 0.0), project(current_pos, &next.1));
+            next = right_hand_next(current_pos, &planar, crossed)?;
+            first_edge = (current, next.0);
+        }
+
+        if first_edge == state.first_edge && (current, next.0) == state.first_edge {
+            return None;
+        }
+
+        Some(GpsrHop {
+            next_hop: next.0,
+            mode: GpsrMode::Perimeter(Box::new(PerimeterState {
+                entry: state.entry,
+                face_entry,
+                first_edge,
+                last_hop: current,
+            })),
+        })
+    }
+
+    /// Start perimeter routing at a local minimum
+    ///
+    /// The first edge is the first one counterclockwise from the line to the
+    /// destination.
+    fn enter_perimeter(
+        &self,
+        current: NodeId,
+        current_pos: &GeoCoordinates,
+        dest_pos: &GeoCoordinates,
+        neighbors: &[NodeId],
+    ) -> Option<GpsrHop> {
+        let planar = self.planar_neighbors(current_pos, neighbors);
+        let reference = plane_angle((0.0, 0.0), project(current_pos, dest_pos));
+        let (next_hop, _) = right_hand_next(current_pos, &planar, reference)?;
+
+        Some(GpsrHop {
+            next_hop,
+            mode: GpsrMode::Perimeter(Box::new(PerimeterState {
+                entry: *current_pos,
+                face_entry: *current_pos,
+                first_edge: (current, next_hop),
+                last_hop: current,
+            })),
+        })
+    }
+
     /// Get total number of known locations
     pub fn location_count(&self) -> usize {
         self.locations.len()
@@ -224,6 +478,31 @@ impl GeoRoutingTable {
     }
 }
 
+/// Right-hand rule: the first planar neighbor counterclockwise from
+/// `reference` (an angle around the current node)
+///
+/// A neighbor exactly at `reference` comes last, so the node a packet
+/// arrived from is only chosen when it is the only neighbor.
+fn right_hand_next(
+    current_pos: &GeoCoordinates,
+    planar: &[(NodeId, GeoCoordinates)],
+    reference: f64,
+) -> Option<(NodeId, GeoCoordinates)> {
+    let tau = std::f64::consts::TAU;
+    planar
+        .iter()
+        .map(|(id, coordinates)| {
+            let angle = plane_angle((0.0, 0.0), project(current_pos, coordinates));
+            let mut delta = (angle - reference).rem_euclid(tau);
+            if delta <= f64::EPSILON {
+                delta = tau;
+            }
+            (delta, *id, *coordinates)
+        })
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, id, coordinates)| (id, coordinates))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,4 +641,283 @@ mod tests {
         // Should return results without panicking
         assert!(!result.is_empty());
     }
+
+    fn assert_within_one_percent(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= expected * 0.01,
+            "distance {} km not within 1% of {} km",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_haversine_known_city_pairs() {
+        let london = GeoCoordinates::new(51.5074, -0.1278);
+        let paris = GeoCoordinates::new(48.8566, 2.3522);
+        let nyc = GeoCoordinates::new(40.7128, -74.0060);
+        let sydney = GeoCoordinates::new(-33.8688, 151.2093);
+        let melbourne = GeoCoordinates::new(-37.8136, 144.9631);
+        let tokyo = GeoCoordinates::new(35.6762, 139.6503);
+
+        assert_within_one_percent(london.haversine_distance(&paris), 344.0);
+        assert_within_one_percent(nyc.haversine_distance(&london), 5570.0);
+        assert_within_one_percent(sydney.haversine_distance(&melbourne), 713.0);
+        assert_within_one_percent(tokyo.haversine_distance(&sydney), 7823.0);
+
+        // Symmetric
+        assert!(
+            (paris.haversine_distance(&london) - london.haversine_distance(&paris)).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_haversine_antimeridian_and_poles() {
+        // Suva, Fiji and Apia, Samoa straddle the antimeridian (~1150 km apart)
+        let suva = GeoCoordinates::new(-18.1416, 178.4419);
+        let apia = GeoCoordinates::new(-13.8333, -171.7500);
+        assert_within_one_percent(suva.haversine_distance(&apia), 1152.0);
+
+        // Samoa lies east-northeast of Fiji, not west
+        let bearing = suva.bearing_to(&apia);
+        assert!(bearing > 45.0 && bearing < 90.0, "bearing {}", bearing);
+
+        // Points near the pole are close regardless of longitude
+        let a = GeoCoordinates::new(89.9, 0.0);
+        let b = GeoCoordinates::new(89.9, 180.0);
+        assert!(a.haversine_distance(&b) < 25.0);
+
+        // Antipodal points don't produce NaN
+        let north = GeoCoordinates::new(90.0, 0.0);
+        let south = GeoCoordinates::new(-90.0, 0.0);
+        let half_circumference = std::f64::consts::PI * EARTH_RADIUS_KM;
+        assert!((north.haversine_distance(&south) - half_circumference).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_greedy_next_hop_across_antimeridian() {
+        let mut table = GeoRoutingTable::new(3600);
+
+        // Just east of Fiji across the antimeridian, ~930 km from Apia
+        let mut east_id = [0u8; 64];
+        east_id[0] = 1;
+        let east = NodeId::from_bytes(east_id);
+        table.update_location(NodeLocation {
+            node_id: east,
+            coordinates: GeoCoordinates::new(-16.0, 179.9),
+            last_updated: 1000,
+            confidence: 0.95,
+        });
+
+        // Far west of Fiji, ~4100 km from Apia; a naive longitude difference
+        // would rank this one closer
+        let mut west_id = [0u8; 64];
+        west_id[0] = 2;
+        let west = NodeId::from_bytes(west_id);
+        table.update_location(NodeLocation {
+            node_id: west,
+            coordinates: GeoCoordinates::new(-14.0, 150.0),
+            last_updated: 1000,
+            confidence: 0.95,
+        });
+
+        let suva = GeoCoordinates::new(-18.1416, 178.4419);
+        let apia = GeoCoordinates::new(-13.8333, -171.7500);
+
+        let (next_hop, distance) = table.greedy_next_hop(&suva, &apia, &[east, west]).unwrap();
+        assert_eq!(next_hop, east);
+        assert!(distance < 1000.0);
+
+        let nearest = table.find_nearest_nodes(&apia, 2);
+        assert_eq!(nearest[0].0, east);
+        assert_eq!(nearest[1].0, west);
+    }
+
+    /// Synthetic topology near the equator; positions are in units of
+    /// 0.01° (about 1.1 km) as (north, east)
+    struct Topology {
+        table: GeoRoutingTable,
+        positions: HashMap<NodeId, GeoCoordinates>,
+        links: HashMap<NodeId, Vec<NodeId>>,
+    }
+
+    impl Topology {
+        fn new(nodes: &[(u8, f64, f64)], links: &[(u8, u8)]) -> Self {
+            let mut table = GeoRoutingTable::new(3600);
+            let mut positions = HashMap::new();
+            for &(id, north, east) in nodes {
+                let coordinates = GeoCoordinates::new(north * 0.01, east * 0.01);
+                table.update_location(NodeLocation {
+                    node_id: node(id),
+                    coordinates,
+                    last_updated: 1000,
+                    confidence: 1.0,
+                });
+                positions.insert(node(id), coordinates);
+            }
+
+            let mut adjacency: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+            for &(a, b) in links {
+                adjacency.entry(node(a)).or_default().push(node(b));
+                adjacency.entry(node(b)).or_default().push(node(a));
+            }
+
+            Self {
+                table,
+                positions,
+                links: adjacency,
+            }
+        }
+
+        /// Forward hop by hop with GPSR; returns the path, or `None` if a
+        /// node finds no next hop
+        fn route(&self, source: u8, destination: u8, greedy_only: bool) -> Option<Vec<NodeId>> {
+            let dest = node(destination);
+            let dest_pos = self.positions[&dest];
+            let mut current = node(source);
+            let mut mode = GpsrMode::Greedy;
+            let mut path = vec![current];
+
+            while current != dest {
+                assert!(path.len() < 50, "routing loop: {:?}", path);
+                let neighbors = self.links.get(&current).cloned().unwrap_or_default();
+                let current_pos = self.positions[&current];
+                let next = if greedy_only {
+                    let (next_hop, _) =
+                        self.table
+                            .greedy_next_hop(&current_pos, &dest_pos, &neighbors)?;
+                    next_hop
+                } else {
+                    let hop = self.table.gpsr_next_hop(
+                        current,
+                        &current_pos,
+                        &dest_pos,
+                        &neighbors,
+                        mode,
+                    )?;
+                    mode = hop.mode;
+                    hop.next_hop
+                };
+                path.push(next);
+                current = next;
+            }
+            Some(path)
+        }
+    }
+
+    fn node(id: u8) -> NodeId {
+        let mut bytes = [0u8; 64];
+        bytes[0] = id;
+        NodeId::from_bytes(bytes)
+    }
+
+    /// Source 1 sits at the edge of a void facing destination 9; both its
+    /// neighbors are farther from 9, and the only way around goes north
+    fn void_topology() -> Topology {
+        Topology::new(
+            &[
+                (1, 0.0, 0.0),
+                (2, 2.0, -1.0),
+                (3, -2.0, -1.0),
+                (4, 4.0, 2.0),
+                (5, 3.0, 5.0),
+                (9, 0.0, 6.0),
+            ],
+            &[(1, 2), (1, 3), (2, 3), (2, 4), (4, 5), (5, 9)],
+        )
+    }
+
+    #[test]
+    fn test_greedy_stuck_in_void() {
+        let topology = void_topology();
+        assert!(topology.route(1, 9, true).is_none());
+    }
+
+    #[test]
+    fn test_perimeter_mode_routes_around_void() {
+        let topology = void_topology();
+        let path = topology.route(1, 9, false).unwrap();
+        assert_eq!(path, vec![node(1), node(2), node(4), node(5), node(9)]);
+    }
+
+    #[test]
+    fn test_perimeter_mode_hands_back_to_greedy() {
+        let topology = void_topology();
+        let source = topology.positions[&node(1)];
+        let dest = topology.positions[&node(9)];
+
+        let first = topology
+            .table
+            .gpsr_next_hop(
+                node(1),
+                &source,
+                &dest,
+                &[node(2), node(3)],
+                GpsrMode::Greedy,
+            )
+            .unwrap();
+        assert_eq!(first.next_hop, node(2));
+        assert!(matches!(first.mode, GpsrMode::Perimeter(_)));
+
+        // Node 2 is still farther than the entry point: stays in perimeter
+        let second = topology
+            .table
+            .gpsr_next_hop(
+                node(2),
+                &topology.positions[&node(2)],
+                &dest,
+                &[node(1), node(3), node(4)],
+                first.mode,
+            )
+            .unwrap();
+        assert_eq!(second.next_hop, node(4));
+        assert!(matches!(second.mode, GpsrMode::Perimeter(_)));
+
+        // Node 4 is closer than the entry point: greedy again
+        let third = topology
+            .table
+            .gpsr_next_hop(
+                node(4),
+                &topology.positions[&node(4)],
+                &dest,
+                &[node(2), node(5)],
+                second.mode,
+            )
+            .unwrap();
+        assert_eq!(third.next_hop, node(5));
+        assert_eq!(third.mode, GpsrMode::Greedy);
+    }
+
+    #[test]
+    fn test_unreachable_destination_detected() {
+        // Same void, but nothing links the far side to the destination
+        let topology = Topology::new(
+            &[
+                (1, 0.0, 0.0),
+                (2, 2.0, -1.0),
+                (3, -2.0, -1.0),
+                (9, 0.0, 6.0),
+            ],
+            &[(1, 2), (1, 3), (2, 3)],
+        );
+        assert!(topology.route(1, 9, false).is_none());
+    }
+
+    #[test]
+    fn test_gabriel_graph_drops_witnessed_edge() {
+        let topology = Topology::new(
+            &[(1, 0.0, 0.0), (2, 0.0, 2.0), (3, 0.2, 1.0), (4, 3.0, 0.0)],
+            &[(1, 2), (1, 3), (1, 4)],
+        );
+        let origin = topology.positions[&node(1)];
+
+        // Node 3 lies inside the circle over the 1-2 edge
+        let planar: Vec<NodeId> = topology
+            .table
+            .planar_neighbors(&origin, &[node(2), node(3), node(4)])
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(planar, vec![node(3), node(4)]);
+    }
 }
@@ -8,6 +8,8 @@
 //! - Store-and-forward for offline nodes
 //! - Message deduplication
 //! - Content tag filtering (optional)
+//! - Per-flow in-order local delivery (optional)
+//! - Dead-letter queue for undeliverable messages
 //!
 //! ## Phase 4 (Advanced Routing)
 //! - Geographic routing with location-based path selection
@@ -16,6 +18,7 @@
 //! - Quality of Service (QoS) with bandwidth reservation
 
 pub mod adaptive;
+pub mod dead_letter;
 pub mod deduplication;
 pub mod error;
 pub mod fragmentation;
@@ -25,23 +28,35 @@ pub mod offline_cache;
 pub mod priority_queue;
 pub mod qos;
 pub mod rate_limiter;
+pub mod reorder;
 pub mod router;
+pub mod subscription;
 
 pub use adaptive::{
-    AdaptiveRoutingStats, AdaptiveRoutingTable, CostWeights, LinkMetrics, RoutingPolicy,
+    AdaptiveRoutingStats, AdaptiveRoutingTable, CostWeights, LinkMetrics, RouteCostFn,
+    RoutingPolicy,
 };
-pub use deduplication::DeduplicationCache;
+pub use dead_letter::{DeadLetter, DeadLetterQueue, DeadLetterReason};
+pub use deduplication::{DeduplicationCache, DeduplicationStats};
 pub use error::{Result, RoutingError};
 pub use fragmentation::{
-    fragment_frame, FragmentHeader, FragmentReassembler, FragmentationDecision, FragmentationReason,
+    fragment_frame, fragment_frame_with_fec, FecConfig, FragmentHeader, FragmentReassembler,
+    FragmentationDecision, FragmentationReason, LinkMtu, ShardKind,
+};
+pub use geographic::{
+    GeoCoordinates, GeoRoutingTable, GpsrHop, GpsrMode, NodeLocation, PerimeterState,
+    EARTH_RADIUS_KM,
 };
-pub use geographic::{GeoCoordinates, GeoRoutingTable, NodeLocation};
 pub use multipath::{MultiPathRouter, MultiPathStats, MultiPathStrategy, NetworkPath};
-pub use offline_cache::{CacheStats, OfflineMessageCache};
-pub use priority_queue::{PriorityLevel, PriorityQueue};
-pub use qos::{FlowId, FlowStats, QosClass, QosError, QosManager, QosStats};
-pub use rate_limiter::RateLimiter;
-pub use router::{Router, RouterStats};
+pub use offline_cache::{CacheStats, OfflineCacheConfig, OfflineMessageCache};
+pub use priority_queue::{FairnessPolicy, PriorityLevel, PriorityQueue, PriorityQueueStats};
+pub use qos::{
+    FlowId, FlowStats, PreemptionPolicy, QosClass, QosError, QosEvent, QosManager, QosStats,
+};
+pub use rate_limiter::{PriorityBucketConfig, PriorityRateLimits, RateLimitError, RateLimiter};
+pub use reorder::{ReorderBuffer, ReorderConfig};
+pub use router::{CongestionState, RetryPolicy, Router, RouterStats};
+pub use subscription::{TagSubscriptions, MAX_TAG_WILDCARDS, TAG_WILDCARD};
 
 /// Maximum cached messages per destination
 pub const MAX_CACHED_MESSAGES_PER_DEST: usize = 100;
@@ -61,6 +76,9 @@ pub const MESSAGE_DEDUP_CACHE_SIZE: usize = 10_000;
 /// Message deduplication TTL (seconds)
 pub const MESSAGE_DEDUP_TTL_SECS: u64 = 3600; // 1 hour
 
+/// Target false-positive rate of the hybrid deduplication cache
+pub const MESSAGE_DEDUP_FALSE_POSITIVE_RATE: f64 = 0.0001;
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -4,8 +4,11 @@
 //! along multiple disjoint paths to improve reliability and reduce latency.
 //! Useful for high-priority messages or unreliable network conditions.
 
+use crate::RoutingError;
 use myriadmesh_protocol::NodeId;
 use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
 
 /// Path through the network
 #[derive(Debug, Clone)]
@@ -16,6 +19,12 @@ pub struct NetworkPath {
     pub cost: u32,
     /// Path quality metric (0.0-1.0, higher is better)
     pub quality: f32,
+    /// Measured path bandwidth in bits per second (0 = unknown/unusable)
+    pub bandwidth_bps: u64,
+    /// One-way propagation latency in milliseconds
+    pub latency_ms: u32,
+    /// Bytes currently queued for transmission on this path
+    pub backlog_bytes: u64,
 }
 
 impl PartialEq for NetworkPath {
@@ -40,6 +49,9 @@ impl NetworkPath {
             hops,
             cost: 0,
             quality: 1.0,
+            bandwidth_bps: 0,
+            latency_ms: 0,
+            backlog_bytes: 0,
         }
     }
 
@@ -49,9 +61,38 @@ impl NetworkPath {
             hops,
             cost,
             quality,
+            bandwidth_bps: 0,
+            latency_ms: 0,
+            backlog_bytes: 0,
         }
     }
 
+    /// Set the measured bandwidth of the path
+    pub fn with_bandwidth(mut self, bandwidth_bps: u64) -> Self {
+        self.bandwidth_bps = bandwidth_bps;
+        self
+    }
+
+    /// Set the one-way propagation latency of the path
+    pub fn with_latency(mut self, latency_ms: u32) -> Self {
+        self.latency_ms = latency_ms;
+        self
+    }
+
+    /// Estimate when a message of `message_bytes` would arrive over this path
+    ///
+    /// Sums the time to drain the current backlog plus the message at the
+    /// path's bandwidth, and the propagation latency. Returns `None` if the
+    /// path has no known bandwidth.
+    pub fn estimated_delivery(&self, message_bytes: usize) -> Option<Duration> {
+        if self.bandwidth_bps == 0 {
+            return None;
+        }
+        let queued_bits = (self.backlog_bytes + message_bytes as u64) as f64 * 8.0;
+        let queue_delay = queued_bits / self.bandwidth_bps as f64;
+        Some(Duration::from_secs_f64(queue_delay) + Duration::from_millis(self.latency_ms as u64))
+    }
+
     /// Get path length (number of hops)
     pub fn length(&self) -> usize {
         if !self.hops.is_empty() {
@@ -104,6 +145,11 @@ pub enum MultiPathStrategy {
     DisjointOnly,
     /// Adaptive based on message priority
     Adaptive,
+    /// Send on one path at a time, proportionally to measured path bandwidth
+    WeightedRoundRobin,
+    /// Send on the path with the earliest estimated delivery (queue delay +
+    /// propagation); use `select_path_with_deadline` to enforce a deadline
+    DeadlineAware,
 }
 
 /// Multi-path router
@@ -114,6 +160,12 @@ pub struct MultiPathRouter {
     strategy: MultiPathStrategy,
     /// Maximum paths to maintain per destination
     max_paths_per_dest: usize,
+    /// Smooth weighted round-robin state (current weight per path index)
+    ///
+    /// Behind a lock so path selection can stay `&self`.
+    wrr_current: Mutex<HashMap<NodeId, Vec<i128>>>,
+    /// Number of sends selected per path (keyed by path hops)
+    send_counts: Mutex<HashMap<Vec<NodeId>, u64>>,
 }
 
 impl MultiPathRouter {
@@ -123,6 +175,8 @@ impl MultiPathRouter {
             paths: HashMap::new(),
             strategy,
             max_paths_per_dest,
+            wrr_current: Mutex::new(HashMap::new()),
+            send_counts: Mutex::new(HashMap::new()),
         }
     }
 
@@ -141,6 +195,12 @@ impl MultiPathRouter {
             if paths.len() > self.max_paths_per_dest {
                 paths.truncate(self.max_paths_per_dest);
             }
+
+            // Path indices changed, restart weighted round-robin
+            self.wrr_current
+                .get_mut()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&destination);
         }
     }
 
@@ -149,6 +209,10 @@ impl MultiPathRouter {
         if let Some(paths) = self.paths.get_mut(destination) {
             paths.retain(|p| p != path);
         }
+        self.wrr_current
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(destination);
     }
 
     /// Get all paths to a destination
@@ -157,18 +221,41 @@ impl MultiPathRouter {
     }
 
     /// Select paths to use for a message based on strategy
+    ///
+    /// Every selected path is counted as one send in `MultiPathStats`.
     pub fn select_paths(
         &self,
         destination: &NodeId,
         priority: u8, // 0-255, higher = more important
     ) -> Vec<NetworkPath> {
-        let available_paths = match self.paths.get(destination) {
-            Some(paths) => paths,
-            None => return Vec::new(),
-        };
+        let selected = self.select_paths_for_strategy(destination, priority);
+
+        for path in &selected {
+            self.record_send(path);
+        }
+
+        selected
+    }
+
+    /// Count one send on `path` for `MultiPathStats`
+    fn record_send(&self, path: &NetworkPath) {
+        *self
+            .send_counts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(path.hops.clone())
+            .or_insert(0) += 1;
+    }
+
+    fn select_paths_for_strategy(&self, destination: &NodeId, priority: u8) -> Vec<NetworkPath> {
+        let available_paths = self
+            .paths
+            .get(destination)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
 
         match self.strategy {
-            MultiPathStrategy::AllPaths => available_paths.clone(),
+            MultiPathStrategy::AllPaths => available_paths.to_vec(),
 
             MultiPathStrategy::BestN(n) => available_paths.iter().take(n).cloned().collect(),
 
@@ -192,7 +279,94 @@ impl MultiPathRouter {
                 };
                 self.select_disjoint_paths_n(available_paths, num_paths)
             }
+
+            MultiPathStrategy::WeightedRoundRobin => {
+                self.select_weighted_path(destination).into_iter().collect()
+            }
+
+            MultiPathStrategy::DeadlineAware => self
+                .fastest_path(destination, 0)
+                .map(|(path, _)| path.clone())
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Pick the next path using smooth weighted round-robin
+    ///
+    /// Each path's weight is its measured bandwidth, read at selection time so
+    /// metric updates take effect immediately. Paths with zero bandwidth are
+    /// excluded; returns `None` if no path has usable bandwidth.
+    fn select_weighted_path(&self, destination: &NodeId) -> Option<NetworkPath> {
+        let paths = self.paths.get(destination)?;
+        let total: i128 = paths.iter().map(|p| p.bandwidth_bps as i128).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut wrr_current = self.wrr_current.lock().unwrap_or_else(|e| e.into_inner());
+        let current = wrr_current
+            .entry(*destination)
+            .or_insert_with(|| vec![0; paths.len()]);
+        current.resize(paths.len(), 0);
+
+        let mut best: Option<usize> = None;
+        for (i, path) in paths.iter().enumerate() {
+            if path.bandwidth_bps == 0 {
+                current[i] = 0;
+                continue;
+            }
+            current[i] += path.bandwidth_bps as i128;
+            if best.is_none_or(|b| current[i] > current[b]) {
+                best = Some(i);
+            }
         }
+
+        let best = best?;
+        current[best] -= total;
+        Some(paths[best].clone())
+    }
+
+    /// Select the path that best meets a delivery deadline
+    ///
+    /// Picks the path with the earliest estimated delivery time for a message
+    /// of `message_bytes`, based on each path's latency and current backlog.
+    /// Fails with `RoutingError::DeadlineUnreachable` if even the fastest path
+    /// is estimated to miss `deadline`, and with `RoutingError::NoRoute` if no
+    /// path has a usable estimate.
+    pub fn select_path_with_deadline(
+        &self,
+        destination: &NodeId,
+        message_bytes: usize,
+        deadline: Duration,
+    ) -> Result<NetworkPath, RoutingError> {
+        let (path, estimate) = self
+            .fastest_path(destination, message_bytes)
+            .ok_or(RoutingError::NoRoute)?;
+
+        if estimate > deadline {
+            return Err(RoutingError::DeadlineUnreachable {
+                deadline_ms: deadline.as_millis() as u64,
+                best_estimate_ms: estimate.as_millis() as u64,
+            });
+        }
+
+        let path = path.clone();
+        self.record_send(&path);
+        Ok(path)
+    }
+
+    /// Find the path with the earliest estimated delivery
+    fn fastest_path(
+        &self,
+        destination: &NodeId,
+        message_bytes: usize,
+    ) -> Option<(&NetworkPath, Duration)> {
+        self.paths
+            .get(destination)?
+            .iter()
+            .filter_map(|p| p.estimated_delivery(message_bytes).map(|d| (p, d)))
+            .min_by_key(|(_, d)| *d)
     }
 
     /// Select node-disjoint paths
@@ -256,6 +430,40 @@ impl MultiPathRouter {
         }
     }
 
+    /// Update measured path bandwidth
+    ///
+    /// Weighted round-robin weights are derived from bandwidth, so the new
+    /// value is used from the next selection on. A path reporting zero
+    /// bandwidth is excluded until it recovers.
+    pub fn update_path_bandwidth(
+        &mut self,
+        destination: &NodeId,
+        path: &NetworkPath,
+        bandwidth_bps: u64,
+    ) {
+        if let Some(paths) = self.paths.get_mut(destination) {
+            if let Some(stored_path) = paths.iter_mut().find(|p| p.hops == path.hops) {
+                stored_path.bandwidth_bps = bandwidth_bps;
+            }
+        }
+    }
+
+    /// Update the number of bytes queued on a path
+    ///
+    /// Deadline-aware scheduling uses the backlog to estimate queueing delay.
+    pub fn update_path_backlog(
+        &mut self,
+        destination: &NodeId,
+        path: &NetworkPath,
+        backlog_bytes: u64,
+    ) {
+        if let Some(paths) = self.paths.get_mut(destination) {
+            if let Some(stored_path) = paths.iter_mut().find(|p| p.hops == path.hops) {
+                stored_path.backlog_bytes = backlog_bytes;
+            }
+        }
+    }
+
     /// Calculate path diversity score (higher = more diverse)
     pub fn path_diversity_score(&self, paths: &[NetworkPath]) -> f32 {
         if paths.len() < 2 {
@@ -295,6 +503,11 @@ impl MultiPathRouter {
             total_destinations,
             total_paths,
             avg_paths_per_dest,
+            path_send_counts: self
+                .send_counts
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
         }
     }
 }
@@ -305,6 +518,8 @@ pub struct MultiPathStats {
     pub total_destinations: usize,
     pub total_paths: usize,
     pub avg_paths_per_dest: f32,
+    /// Number of sends selected per path (keyed by path hops)
+    pub path_send_counts: HashMap<Vec<NodeId>, u64>,
 }
 
 #[cfg(test)]
@@ -430,4 +645,109 @@ mod tests {
         let updated = &router.get_paths(&dest).unwrap()[0];
         assert!(updated.quality < 0.5);
     }
+
+    #[test]
+    fn test_weighted_round_robin_distribution() {
+        let mut router = MultiPathRouter::new(MultiPathStrategy::WeightedRoundRobin, 5);
+        let dest = create_test_node_id(10);
+
+        let slow = NetworkPath::with_metrics(vec![create_test_node_id(1), dest], 10, 0.9)
+            .with_bandwidth(100_000);
+        let medium = NetworkPath::with_metrics(vec![create_test_node_id(2), dest], 10, 0.9)
+            .with_bandwidth(200_000);
+        let fast = NetworkPath::with_metrics(vec![create_test_node_id(3), dest], 10, 0.9)
+            .with_bandwidth(700_000);
+
+        router.add_path(dest, slow.clone());
+        router.add_path(dest, medium.clone());
+        router.add_path(dest, fast.clone());
+
+        for _ in 0..1000 {
+            assert_eq!(router.select_paths(&dest, 100).len(), 1);
+        }
+
+        let stats = router.stats();
+        let count = |p: &NetworkPath| stats.path_send_counts.get(&p.hops).copied().unwrap_or(0);
+        assert!((90..=110).contains(&count(&slow)));
+        assert!((190..=210).contains(&count(&medium)));
+        assert!((690..=710).contains(&count(&fast)));
+    }
+
+    #[test]
+    fn test_weighted_round_robin_zero_bandwidth() {
+        let mut router = MultiPathRouter::new(MultiPathStrategy::WeightedRoundRobin, 5);
+        let dest = create_test_node_id(10);
+
+        let path1 = NetworkPath::with_metrics(vec![create_test_node_id(1), dest], 10, 0.9)
+            .with_bandwidth(500_000);
+        let path2 = NetworkPath::with_metrics(vec![create_test_node_id(2), dest], 10, 0.9)
+            .with_bandwidth(500_000);
+        router.add_path(dest, path1.clone());
+        router.add_path(dest, path2.clone());
+
+        // Path 2 drops to zero bandwidth and must be excluded
+        router.update_path_bandwidth(&dest, &path2, 0);
+        for _ in 0..10 {
+            let selected = router.select_paths(&dest, 100);
+            assert_eq!(selected[0].hops, path1.hops);
+        }
+
+        // No usable bandwidth at all: nothing selected, no panic
+        router.update_path_bandwidth(&dest, &path1, 0);
+        assert!(router.select_paths(&dest, 100).is_empty());
+
+        // Recovery restores weighting
+        router.update_path_bandwidth(&dest, &path2, 100_000);
+        assert_eq!(router.select_paths(&dest, 100)[0].hops, path2.hops);
+    }
+
+    #[test]
+    fn test_deadline_aware_scheduling() {
+        let mut router = MultiPathRouter::new(MultiPathStrategy::DeadlineAware, 5);
+        let dest = create_test_node_id(10);
+
+        // 10 Mbps with 20ms latency, but 500 KB queued (~400ms backlog)
+        let fast_busy = NetworkPath::with_metrics(vec![create_test_node_id(1), dest], 10, 0.9)
+            .with_bandwidth(10_000_000)
+            .with_latency(20);
+        // 1 Mbps with 150ms latency, nothing queued
+        let slow_idle = NetworkPath::with_metrics(vec![create_test_node_id(2), dest], 10, 0.9)
+            .with_bandwidth(1_000_000)
+            .with_latency(150);
+
+        router.add_path(dest, fast_busy.clone());
+        router.add_path(dest, slow_idle.clone());
+        router.update_path_backlog(&dest, &fast_busy, 500_000);
+
+        // 1 KB message: fast path ~421ms, slow path ~158ms
+        let chosen = router
+            .select_path_with_deadline(&dest, 1000, Duration::from_millis(200))
+            .unwrap();
+        assert_eq!(chosen.hops, slow_idle.hops);
+
+        // Neither path can make 100ms
+        let result = router.select_path_with_deadline(&dest, 1000, Duration::from_millis(100));
+        assert!(matches!(
+            result,
+            Err(RoutingError::DeadlineUnreachable {
+                deadline_ms: 100,
+                best_estimate_ms: 158,
+            })
+        ));
+
+        // Once the fast path drains it wins, and now makes a tight deadline
+        router.update_path_backlog(&dest, &fast_busy, 0);
+        let chosen = router
+            .select_path_with_deadline(&dest, 1000, Duration::from_millis(50))
+            .unwrap();
+        assert_eq!(chosen.hops, fast_busy.hops);
+        assert_eq!(router.select_paths(&dest, 100)[0].hops, fast_busy.hops);
+
+        // No paths at all
+        let unknown = create_test_node_id(99);
+        assert!(matches!(
+            router.select_path_with_deadline(&unknown, 1000, Duration::from_secs(1)),
+            Err(RoutingError::NoRoute)
+        ));
+    }
 }
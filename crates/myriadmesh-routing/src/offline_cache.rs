@@ -2,11 +2,25 @@
 //!
 //! Implements message caching for nodes that are temporarily unreachable.
 //! Messages are stored with TTL-based expiration and priority-based eviction.
-
-use crate::RoutingError;
-use myriadmesh_protocol::{message::Message, types::Priority, NodeId};
+//! An optional on-disk snapshot lets cached messages survive a node restart.
+//! Changes are written behind: at most once per flush interval, on
+//! [`OfflineMessageCache::cleanup_expired`] and when the cache is dropped.
+//!
+//! Delivery is receipt-based: when a destination comes back online its
+//! messages are handed out but stay cached until the peer acknowledges them,
+//! so anything lost in transit is sent again on the next reconnect.
+
+use crate::{RoutingError, MAX_CACHED_MESSAGE_AGE_SECS};
+use myriadmesh_protocol::{
+    message::{Message, MessageId},
+    types::Priority,
+    NodeId,
+};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
-use std::time::{Duration, Instant};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Maximum messages to cache per destination node
 const DEFAULT_PER_NODE_LIMIT: usize = 100;
@@ -14,6 +28,9 @@ const DEFAULT_PER_NODE_LIMIT: usize = 100;
 /// Maximum total cached messages across all destinations
 const DEFAULT_TOTAL_LIMIT: usize = 10_000;
 
+/// Minimum time between snapshot writes
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Default TTL for cached messages based on priority
 fn default_ttl_for_priority(priority: Priority) -> Duration {
     let value = priority.as_u8();
@@ -30,13 +47,27 @@ fn default_ttl_for_priority(priority: Priority) -> Duration {
     }
 }
 
+/// Current wall-clock time in seconds since the Unix epoch
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 /// A cached message with metadata
 #[derive(Debug, Clone)]
 struct CachedMessage {
     message: Message,
     cached_at: Instant,
+    /// Wall-clock caching time, used to age messages across restarts
+    cached_at_unix: u64,
     ttl: Duration,
     priority: Priority,
+    /// Handed out for delivery and not yet acknowledged
+    awaiting_receipt: bool,
+    /// Times handed out for delivery
+    delivery_attempts: u32,
 }
 
 impl CachedMessage {
@@ -44,13 +75,17 @@ impl CachedMessage {
         Self {
             message,
             cached_at: Instant::now(),
+            cached_at_unix: unix_now(),
             ttl: default_ttl_for_priority(priority),
             priority,
+            awaiting_receipt: false,
+            delivery_attempts: 0,
         }
     }
 
+    /// Expired by the cache TTL or by the message's own `expires_at`
     fn is_expired(&self) -> bool {
-        self.cached_at.elapsed() > self.ttl
+        self.cached_at.elapsed() > self.ttl || self.message.is_expired()
     }
 
     #[allow(dead_code)]
@@ -112,9 +147,55 @@ impl DestinationQueue {
             .collect()
     }
 
-    /// Remove expired messages
-    fn evict_expired(&mut self) {
+    /// Hand out every unacknowledged message for delivery
+    ///
+    /// Returns the messages and how many of them were handed out before.
+    fn begin_delivery(&mut self) -> (Vec<Message>, usize) {
+        self.evict_expired();
+        let mut retried = 0;
+        let messages = self
+            .messages
+            .iter_mut()
+            .map(|cached| {
+                if cached.delivery_attempts > 0 {
+                    retried += 1;
+                }
+                cached.delivery_attempts += 1;
+                cached.awaiting_receipt = true;
+                cached.message.clone()
+            })
+            .collect();
+        (messages, retried)
+    }
+
+    /// Remove an acknowledged message, returning whether it was cached
+    fn acknowledge(&mut self, id: &MessageId) -> bool {
+        match self.messages.iter().position(|m| m.message.id == *id) {
+            Some(pos) => {
+                self.messages.remove(pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Return handed-out messages to pending
+    fn reset_delivery(&mut self) {
+        for cached in &mut self.messages {
+            cached.awaiting_receipt = false;
+        }
+    }
+
+    /// Number of messages handed out and not yet acknowledged
+    fn awaiting_receipt(&self) -> usize {
+        self.messages.iter().filter(|m| m.awaiting_receipt).count()
+    }
+
+    /// Remove expired messages, returning how many were removed
+    fn evict_expired(&mut self) -> usize {
+        let before = self.messages.len();
         self.messages.retain(|msg| !msg.is_expired());
+        before - self.messages.len()
     }
 
     /// Remove the lowest priority message
@@ -141,6 +222,50 @@ impl DestinationQueue {
     }
 }
 
+/// On-disk representation of a cached message
+#[derive(Serialize, Deserialize)]
+struct PersistedMessage {
+    destination: NodeId,
+    message: Message,
+    priority: Priority,
+    cached_at_unix: u64,
+}
+
+/// Configuration for the offline message cache
+#[derive(Debug, Clone)]
+pub struct OfflineCacheConfig {
+    /// Maximum messages per destination
+    pub per_node_limit: usize,
+    /// Maximum total cached messages
+    pub total_limit: usize,
+    /// Snapshot file for persisting cached messages (None = memory only)
+    pub persistence_path: Option<PathBuf>,
+    /// Minimum time between snapshot writes; changes in between are
+    /// written by the next flush
+    pub flush_interval: Duration,
+}
+
+impl Default for OfflineCacheConfig {
+    fn default() -> Self {
+        Self {
+            per_node_limit: DEFAULT_PER_NODE_LIMIT,
+            total_limit: DEFAULT_TOTAL_LIMIT,
+            persistence_path: None,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+        }
+    }
+}
+
+impl OfflineCacheConfig {
+    /// Create a configuration that persists cached messages to `path`
+    pub fn persistent<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            persistence_path: Some(path.into()),
+            ..Default::default()
+        }
+    }
+}
+
 /// Offline message cache
 ///
 /// Stores messages for offline or unreachable nodes with:
@@ -148,6 +273,8 @@ impl DestinationQueue {
 /// - Per-destination capacity limits
 /// - Global capacity limits
 /// - Priority-based eviction
+/// - Optional on-disk persistence, written behind at most once per
+///   flush interval
 pub struct OfflineMessageCache {
     /// Messages indexed by destination NodeId
     queues: HashMap<NodeId, DestinationQueue>,
@@ -158,6 +285,18 @@ pub struct OfflineMessageCache {
     /// Maximum total cached messages
     total_limit: usize,
 
+    /// Snapshot file for persistence (None = memory only)
+    persistence_path: Option<PathBuf>,
+
+    /// Minimum time between snapshot writes
+    flush_interval: Duration,
+
+    /// When the snapshot was last written
+    last_flush: Instant,
+
+    /// Changed since the snapshot was last written
+    dirty: bool,
+
     /// Statistics
     stats: CacheStats,
 }
@@ -166,11 +305,21 @@ pub struct OfflineMessageCache {
 #[derive(Debug, Default, Clone)]
 pub struct CacheStats {
     pub total_cached: u64,
+    /// Messages acknowledged by their destination (or retrieved without a
+    /// receipt) and purged
     pub total_delivered: u64,
+    /// Unacknowledged messages handed out again on a later reconnect
+    pub total_redelivered: u64,
     pub total_expired: u64,
     pub total_evicted: u64,
     pub current_size: usize,
+    /// Cached messages not yet handed out for delivery
+    pub pending_delivery: usize,
+    /// Messages handed out for delivery and waiting for a receipt
+    pub awaiting_receipt: usize,
     pub destinations_count: usize,
+    /// Messages reloaded from the on-disk snapshot at startup
+    pub recovered_from_disk: u64,
 }
 
 impl OfflineMessageCache {
@@ -185,10 +334,140 @@ impl OfflineMessageCache {
             queues: HashMap::new(),
             per_node_limit,
             total_limit,
+            persistence_path: None,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            last_flush: Instant::now(),
+            dirty: false,
             stats: CacheStats::default(),
         }
     }
 
+    /// Create a cache from a configuration
+    ///
+    /// If a persistence path is configured and a snapshot exists there, the
+    /// cached messages are reloaded. Messages older than
+    /// `MAX_CACHED_MESSAGE_AGE_SECS` or past their TTL are pruned at load time.
+    pub fn with_config(config: OfflineCacheConfig) -> Result<Self, RoutingError> {
+        let mut cache = Self::with_limits(config.per_node_limit, config.total_limit);
+        cache.persistence_path = config.persistence_path;
+        cache.flush_interval = config.flush_interval;
+        cache.load()?;
+        Ok(cache)
+    }
+
+    /// Reload cached messages from the snapshot file
+    fn load(&mut self) -> Result<(), RoutingError> {
+        let path = match &self.persistence_path {
+            Some(path) if path.exists() => path,
+            _ => return Ok(()),
+        };
+
+        let bytes = fs::read(path)?;
+        let persisted: Vec<PersistedMessage> = bincode::deserialize(&bytes).map_err(|e| {
+            RoutingError::Other(format!("Failed to decode offline cache snapshot: {}", e))
+        })?;
+
+        let now = unix_now();
+        for entry in persisted {
+            let age = Duration::from_secs(now.saturating_sub(entry.cached_at_unix));
+            let ttl = default_ttl_for_priority(entry.priority);
+
+            if age.as_secs() > MAX_CACHED_MESSAGE_AGE_SECS
+                || age > ttl
+                || entry.message.is_expired()
+            {
+                self.stats.total_expired += 1;
+                continue;
+            }
+
+            if self.current_size() >= self.total_limit {
+                self.stats.total_evicted += 1;
+                continue;
+            }
+
+            let cached = CachedMessage {
+                message: entry.message,
+                cached_at: Instant::now().checked_sub(age).unwrap_or_else(Instant::now),
+                cached_at_unix: entry.cached_at_unix,
+                ttl,
+                priority: entry.priority,
+                awaiting_receipt: false,
+                delivery_attempts: 0,
+            };
+
+            let queue = self
+                .queues
+                .entry(entry.destination)
+                .or_insert_with(|| DestinationQueue::new(self.per_node_limit));
+            if queue.push(cached).is_ok() {
+                self.stats.recovered_from_disk += 1;
+            } else {
+                self.stats.total_evicted += 1;
+            }
+        }
+
+        self.update_stats();
+        Ok(())
+    }
+
+    /// Write all cached messages to the snapshot file if anything changed
+    ///
+    /// Does nothing for a memory-only cache. The snapshot is written to a
+    /// temporary file and renamed into place so a crash never leaves a
+    /// truncated snapshot behind.
+    pub fn flush(&mut self) -> Result<(), RoutingError> {
+        let path = match &self.persistence_path {
+            Some(path) if self.dirty => path,
+            _ => return Ok(()),
+        };
+
+        let persisted: Vec<PersistedMessage> = self
+            .queues
+            .iter()
+            .flat_map(|(destination, queue)| {
+                queue.messages.iter().map(|cached| PersistedMessage {
+                    destination: *destination,
+                    message: cached.message.clone(),
+                    priority: cached.priority,
+                    cached_at_unix: cached.cached_at_unix,
+                })
+            })
+            .collect();
+
+        let bytes = bincode::serialize(&persisted).map_err(|e| {
+            RoutingError::Other(format!("Failed to encode offline cache snapshot: {}", e))
+        })?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, path)?;
+
+        self.dirty = false;
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+
+    /// Flush where the caller has no way to surface errors
+    ///
+    /// A failed write leaves the cache dirty, so the next flush retries it.
+    fn flush_or_warn(&mut self) {
+        if let Err(e) = self.flush() {
+            log::warn!("Failed to persist offline message cache: {}", e);
+        }
+    }
+
+    /// Record a change, writing the snapshot if the flush interval has passed
+    fn mark_changed(&mut self) {
+        self.update_stats();
+        self.dirty = true;
+        if self.last_flush.elapsed() >= self.flush_interval {
+            self.flush_or_warn();
+        }
+    }
+
     /// Cache a message for an offline destination
     ///
     /// # Arguments
@@ -197,13 +476,21 @@ impl OfflineMessageCache {
     /// * `priority` - Message priority for TTL and eviction
     ///
     /// # Errors
-    /// Returns error if cache is full or destination queue is at capacity
+    /// Returns error if cache is full or destination queue is at capacity.
+    /// Persistence failures are logged, never returned, since the message
+    /// is cached either way.
     pub fn cache_message(
         &mut self,
         destination: NodeId,
         message: Message,
         priority: Priority,
     ) -> Result<(), RoutingError> {
+        // Not worth storing a message that is already past its deadline
+        if message.is_expired() {
+            self.stats.total_expired += 1;
+            return Err(RoutingError::MessageExpired);
+        }
+
         // Check global capacity
         if self.current_size() >= self.total_limit {
             // Try cleanup first
@@ -226,13 +513,16 @@ impl OfflineMessageCache {
         queue.push(cached_msg)?;
 
         self.stats.total_cached += 1;
-        self.update_stats();
+        self.mark_changed();
 
         Ok(())
     }
 
     /// Retrieve all cached messages for a destination (node came online)
     ///
+    /// The messages are purged at once, without waiting for a receipt; see
+    /// [`Self::deliver_messages`] for acknowledged delivery.
+    ///
     /// # Arguments
     /// * `destination` - The node that came online
     ///
@@ -240,15 +530,73 @@ impl OfflineMessageCache {
     /// Vector of cached messages, or empty vec if none cached
     pub fn retrieve_messages(&mut self, destination: &NodeId) -> Vec<Message> {
         if let Some(mut queue) = self.queues.remove(destination) {
+            self.stats.total_expired += queue.evict_expired() as u64;
             let messages = queue.drain_all();
             self.stats.total_delivered += messages.len() as u64;
-            self.update_stats();
+            self.mark_changed();
             messages
         } else {
             Vec::new()
         }
     }
 
+    /// Hand out cached messages for a destination that came online
+    ///
+    /// The messages stay cached until acknowledged with
+    /// [`Self::acknowledge`]. Whatever is still unacknowledged is handed out
+    /// again by the next call, so call this on every reconnect.
+    pub fn deliver_messages(&mut self, destination: &NodeId) -> Vec<Message> {
+        let Some(queue) = self.queues.get_mut(destination) else {
+            return Vec::new();
+        };
+
+        let expired = queue.evict_expired();
+        self.stats.total_expired += expired as u64;
+        let (messages, retried) = queue.begin_delivery();
+        let emptied = queue.is_empty();
+        if emptied {
+            self.queues.remove(destination);
+        }
+        self.stats.total_redelivered += retried as u64;
+        if expired > 0 || emptied {
+            self.mark_changed();
+        } else {
+            self.update_stats();
+        }
+        messages
+    }
+
+    /// Purge a message its destination has acknowledged
+    ///
+    /// Returns false if the message is not cached (already acknowledged or
+    /// expired).
+    pub fn acknowledge(&mut self, destination: &NodeId, message_id: &MessageId) -> bool {
+        let Some(queue) = self.queues.get_mut(destination) else {
+            return false;
+        };
+        if !queue.acknowledge(message_id) {
+            return false;
+        }
+
+        if queue.is_empty() {
+            self.queues.remove(destination);
+        }
+        self.stats.total_delivered += 1;
+        self.mark_changed();
+        true
+    }
+
+    /// Note that a destination went offline before acknowledging everything
+    ///
+    /// Its unacknowledged messages count as pending again until the next
+    /// [`Self::deliver_messages`].
+    pub fn mark_offline(&mut self, destination: &NodeId) {
+        if let Some(queue) = self.queues.get_mut(destination) {
+            queue.reset_delivery();
+            self.update_stats();
+        }
+    }
+
     /// Check if any messages are cached for a destination
     pub fn has_messages(&self, destination: &NodeId) -> bool {
         self.queues
@@ -263,19 +611,24 @@ impl OfflineMessageCache {
     }
 
     /// Clean up expired messages across all destinations
+    ///
+    /// Also writes any changes not yet persisted, so calling this
+    /// periodically bounds how much a crash can lose.
     pub fn cleanup_expired(&mut self) -> usize {
         let mut expired_count = 0;
 
         // Clean each queue
         self.queues.retain(|_, queue| {
-            let before = queue.len();
-            queue.evict_expired();
-            expired_count += before - queue.len();
+            expired_count += queue.evict_expired();
             !queue.is_empty()
         });
 
         self.stats.total_expired += expired_count as u64;
         self.update_stats();
+        if expired_count > 0 {
+            self.dirty = true;
+        }
+        self.flush_or_warn();
 
         expired_count
     }
@@ -293,14 +646,15 @@ impl OfflineMessageCache {
     /// Update statistics
     fn update_stats(&mut self) {
         self.stats.current_size = self.current_size();
+        self.stats.awaiting_receipt = self.queues.values().map(|q| q.awaiting_receipt()).sum();
+        self.stats.pending_delivery = self.stats.current_size - self.stats.awaiting_receipt;
         self.stats.destinations_count = self.queues.len();
     }
 
     /// Clear all cached messages (for testing/shutdown)
     pub fn clear(&mut self) {
         self.queues.clear();
-        self.stats.current_size = 0;
-        self.stats.destinations_count = 0;
+        self.mark_changed();
     }
 }
 
@@ -310,10 +664,16 @@ impl Default for OfflineMessageCache {
     }
 }
 
+impl Drop for OfflineMessageCache {
+    fn drop(&mut self) {
+        self.flush_or_warn();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use myriadmesh_protocol::{message::MessageId, types::NODE_ID_SIZE};
+    use myriadmesh_protocol::types::NODE_ID_SIZE;
 
     fn create_test_node_id(value: u8) -> NodeId {
         let mut bytes = [value; NODE_ID_SIZE];
@@ -331,11 +691,16 @@ mod tests {
             source,
             destination,
             payload: payload.to_vec(),
+            compressed: false,
+            tags: Vec::new(),
+            source_route: Vec::new(),
+            expires_at: None,
             timestamp: 0,
             sequence: 0,
             ttl: 10,
             priority: Priority::normal(),
             message_type: myriadmesh_protocol::MessageType::Data,
+            channel: 0,
         }
     }
 
@@ -490,4 +855,195 @@ mod tests {
         assert_eq!(stats.current_size, 0);
         assert_eq!(stats.destinations_count, 0);
     }
+
+    #[test]
+    fn test_delivery_purged_only_after_receipt() {
+        let mut cache = OfflineMessageCache::new();
+        let destination = create_test_node_id(2);
+
+        // Destination offline: messages are cached
+        let first = create_test_message(b"first");
+        let second = create_test_message(b"second");
+        cache
+            .cache_message(destination, first.clone(), Priority::normal())
+            .unwrap();
+        cache
+            .cache_message(destination, second.clone(), Priority::normal())
+            .unwrap();
+        assert_eq!(cache.stats().pending_delivery, 2);
+
+        // Online: both are handed out but stay cached
+        let delivered = cache.deliver_messages(&destination);
+        assert_eq!(delivered.len(), 2);
+        assert_eq!(cache.message_count(&destination), 2);
+        let stats = cache.stats();
+        assert_eq!(stats.awaiting_receipt, 2);
+        assert_eq!(stats.pending_delivery, 0);
+        assert_eq!(stats.total_delivered, 0);
+
+        // Only the acknowledged message is purged
+        assert!(cache.acknowledge(&destination, &first.id));
+        assert!(!cache.acknowledge(&destination, &first.id));
+        assert_eq!(cache.message_count(&destination), 1);
+        assert_eq!(cache.stats().total_delivered, 1);
+
+        // Peer drops before acknowledging the second
+        cache.mark_offline(&destination);
+        let stats = cache.stats();
+        assert_eq!(stats.pending_delivery, 1);
+        assert_eq!(stats.awaiting_receipt, 0);
+
+        // Reconnect: the unacknowledged message is retried
+        let retried = cache.deliver_messages(&destination);
+        assert_eq!(retried.len(), 1);
+        assert_eq!(retried[0].id, second.id);
+        assert_eq!(cache.stats().total_redelivered, 1);
+
+        assert!(cache.acknowledge(&destination, &second.id));
+        assert!(!cache.has_messages(&destination));
+        let stats = cache.stats();
+        assert_eq!(stats.total_delivered, 2);
+        assert_eq!(stats.current_size, 0);
+        assert_eq!(stats.destinations_count, 0);
+        assert!(cache.deliver_messages(&destination).is_empty());
+    }
+
+    #[test]
+    fn test_unacknowledged_delivery_not_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("offline.bin");
+        let destination = create_test_node_id(2);
+        let message = create_test_message(b"persisted");
+
+        {
+            let mut cache =
+                OfflineMessageCache::with_config(OfflineCacheConfig::persistent(&path)).unwrap();
+            cache
+                .cache_message(destination, message.clone(), Priority::normal())
+                .unwrap();
+            cache.deliver_messages(&destination);
+        }
+
+        // A restart before the receipt keeps the message for redelivery
+        let mut cache =
+            OfflineMessageCache::with_config(OfflineCacheConfig::persistent(&path)).unwrap();
+        assert_eq!(cache.stats().pending_delivery, 1);
+        let delivered = cache.deliver_messages(&destination);
+        assert_eq!(delivered[0].id, message.id);
+
+        assert!(cache.acknowledge(&destination, &message.id));
+        drop(cache);
+        let cache =
+            OfflineMessageCache::with_config(OfflineCacheConfig::persistent(&path)).unwrap();
+        assert_eq!(cache.message_count(&destination), 0);
+    }
+
+    #[test]
+    fn test_snapshot_written_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("offline.bin");
+        let destination = create_test_node_id(2);
+        let config = OfflineCacheConfig {
+            flush_interval: Duration::from_secs(3600),
+            ..OfflineCacheConfig::persistent(&path)
+        };
+
+        let mut cache = OfflineMessageCache::with_config(config).unwrap();
+        for payload in [b"one", b"two"] {
+            cache
+                .cache_message(
+                    destination,
+                    create_test_message(payload),
+                    Priority::normal(),
+                )
+                .unwrap();
+        }
+        // Inserts inside the interval are not written one by one
+        assert!(!path.exists());
+
+        // Periodic cleanup writes them
+        cache.cleanup_expired();
+        let written = fs::read(&path).unwrap();
+
+        // Nothing changed, nothing rewritten
+        fs::remove_file(&path).unwrap();
+        cache.cleanup_expired();
+        assert!(!path.exists());
+
+        cache.acknowledge(&destination, &create_test_message(b"one").id);
+        drop(cache);
+        assert_ne!(fs::read(&path).unwrap(), written);
+        let cache =
+            OfflineMessageCache::with_config(OfflineCacheConfig::persistent(&path)).unwrap();
+        assert_eq!(cache.message_count(&destination), 1);
+    }
+
+    #[test]
+    fn test_expiry_on_delivery_reaches_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("offline.bin");
+        let destination = create_test_node_id(2);
+        let config = OfflineCacheConfig {
+            flush_interval: Duration::from_secs(3600),
+            ..OfflineCacheConfig::persistent(&path)
+        };
+
+        let mut cache = OfflineMessageCache::with_config(config).unwrap();
+        let mut message = create_test_message(b"expiring");
+        message.expires_at = Some(u64::MAX);
+        cache
+            .cache_message(destination, message, Priority::normal())
+            .unwrap();
+        cache.cleanup_expired();
+        let written = fs::read(&path).unwrap();
+
+        for queue in cache.queues.values_mut() {
+            for cached in queue.messages.iter_mut() {
+                cached.message.expires_at = Some(1);
+            }
+        }
+        assert!(cache.deliver_messages(&destination).is_empty());
+
+        // The emptied queue is written out with the next flush
+        drop(cache);
+        assert_ne!(fs::read(&path).unwrap(), written);
+    }
+
+    #[test]
+    fn test_message_expiry_honored() {
+        let mut cache = OfflineMessageCache::new();
+        let dest = create_test_node_id(2);
+
+        // Already past its deadline: never cached
+        let stale = create_test_message(b"stale").with_expires_at(1);
+        assert!(matches!(
+            cache.cache_message(dest, stale, Priority::normal()),
+            Err(RoutingError::MessageExpired)
+        ));
+        assert_eq!(cache.stats().total_expired, 1);
+        assert!(!cache.has_messages(&dest));
+
+        // Expires while cached: dropped instead of delivered
+        let mut expiring = create_test_message(b"expiring");
+        expiring.expires_at = Some(u64::MAX);
+        let fresh = create_test_message(b"fresh");
+        cache
+            .cache_message(dest, expiring.clone(), Priority::normal())
+            .unwrap();
+        cache
+            .cache_message(dest, fresh.clone(), Priority::normal())
+            .unwrap();
+        for queue in cache.queues.values_mut() {
+            for cached in queue.messages.iter_mut() {
+                if cached.message.id == expiring.id {
+                    cached.message.expires_at = Some(1);
+                }
+            }
+        }
+
+        let delivered = cache.deliver_messages(&dest);
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].id, fresh.id);
+        assert_eq!(cache.stats().total_expired, 2);
+    }
 }
@@ -81,6 +81,35 @@ impl QueuedMessage {
     }
 }
 
+/// Dequeue fairness policy across priority levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FairnessPolicy {
+    /// Always dequeue from the highest non-empty priority level
+    ///
+    /// A steady stream of high priority messages can starve lower levels.
+    #[default]
+    StrictPriority,
+
+    /// Weighted fair queuing with aging
+    ///
+    /// Weights are indexed by `PriorityLevel::queue_index()` (Background first,
+    /// Emergency last). Every non-empty level accumulates credit equal to its
+    /// weight on each dequeue, so waiting levels gain effective priority over
+    /// time. A level with weight `w` is served at least once every
+    /// `sum(weights) / w` dequeues, bounding the wait of the lowest level.
+    /// Levels with weight 0 are only served when no weighted level has messages.
+    WeightedFair { weights: [u32; 5] },
+}
+
+impl FairnessPolicy {
+    /// Weighted fair queuing where each level gets twice the share of the one below
+    pub fn default_weighted() -> Self {
+        FairnessPolicy::WeightedFair {
+            weights: [1, 2, 4, 8, 16],
+        }
+    }
+}
+
 /// Priority queue system with 5 priority levels
 #[derive(Debug)]
 pub struct PriorityQueue {
@@ -92,11 +121,22 @@ pub struct PriorityQueue {
 
     /// Total messages across all queues
     total_messages: usize,
+
+    /// Dequeue fairness policy
+    fairness: FairnessPolicy,
+
+    /// Accumulated weighted-fair credit per priority level
+    credits: [i64; 5],
 }
 
 impl PriorityQueue {
     /// Create a new priority queue
     pub fn new(max_per_queue: usize) -> Self {
+        Self::with_fairness(max_per_queue, FairnessPolicy::StrictPriority)
+    }
+
+    /// Create a new priority queue with a fairness policy
+    pub fn with_fairness(max_per_queue: usize, fairness: FairnessPolicy) -> Self {
         PriorityQueue {
             queues: [
                 VecDeque::new(),
@@ -107,9 +147,22 @@ impl PriorityQueue {
             ],
             max_per_queue,
             total_messages: 0,
+            fairness,
+            credits: [0; 5],
         }
     }
 
+    /// Get the fairness policy
+    pub fn fairness(&self) -> FairnessPolicy {
+        self.fairness
+    }
+
+    /// Change the fairness policy
+    pub fn set_fairness(&mut self, fairness: FairnessPolicy) {
+        self.fairness = fairness;
+        self.credits = [0; 5];
+    }
+
     /// Enqueue a message with automatic priority detection
     pub fn enqueue(&mut self, message: Message) -> Result<(), String> {
         let priority = PriorityLevel::from(message.priority);
@@ -139,26 +192,62 @@ impl PriorityQueue {
         Ok(())
     }
 
-    /// Dequeue the highest priority message
-    pub fn dequeue(&mut self) -> Option<QueuedMessage> {
-        // Check queues from highest to lowest priority
-        for queue in self.queues.iter_mut().rev() {
-            if let Some(msg) = queue.pop_front() {
-                self.total_messages -= 1;
-                return Some(msg);
+    /// Select the queue to dequeue from next according to the fairness policy
+    fn next_queue_index(&self) -> Option<usize> {
+        // Highest non-empty priority level
+        let highest = (0..self.queues.len())
+            .rev()
+            .find(|&idx| !self.queues[idx].is_empty())?;
+
+        match self.fairness {
+            FairnessPolicy::StrictPriority => Some(highest),
+            FairnessPolicy::WeightedFair { weights } => {
+                // Smooth weighted round-robin: pick the level with the most
+                // credit after this round's accrual, ties going to higher priority
+                let mut best: Option<(usize, i64)> = None;
+                for idx in (0..self.queues.len()).rev() {
+                    if self.queues[idx].is_empty() || weights[idx] == 0 {
+                        continue;
+                    }
+                    let credit = self.credits[idx] + weights[idx] as i64;
+                    if best.is_none_or(|(_, best_credit)| credit > best_credit) {
+                        best = Some((idx, credit));
+                    }
+                }
+                Some(best.map_or(highest, |(idx, _)| idx))
             }
         }
-        None
     }
 
-    /// Peek at the highest priority message without removing it
-    pub fn peek(&self) -> Option<&QueuedMessage> {
-        for queue in self.queues.iter().rev() {
-            if let Some(msg) = queue.front() {
-                return Some(msg);
+    /// Dequeue the next message
+    ///
+    /// Under `StrictPriority` this is always the highest priority message;
+    /// under `WeightedFair` lower levels are interleaved by weight.
+    pub fn dequeue(&mut self) -> Option<QueuedMessage> {
+        let idx = self.next_queue_index()?;
+
+        if let FairnessPolicy::WeightedFair { weights } = self.fairness {
+            let mut total = 0i64;
+            for (level, queue) in self.queues.iter().enumerate() {
+                if queue.is_empty() || weights[level] == 0 {
+                    // Idle levels don't bank credit while they have nothing to send
+                    self.credits[level] = 0;
+                    continue;
+                }
+                self.credits[level] += weights[level] as i64;
+                total += weights[level] as i64;
             }
+            self.credits[idx] -= total;
         }
-        None
+
+        let msg = self.queues[idx].pop_front()?;
+        self.total_messages -= 1;
+        Some(msg)
+    }
+
+    /// Peek at the message `dequeue` would return, without removing it
+    pub fn peek(&self) -> Option<&QueuedMessage> {
+        self.queues[self.next_queue_index()?].front()
     }
 
     /// Get total number of messages across all queues
@@ -171,6 +260,11 @@ impl PriorityQueue {
         self.total_messages == 0
     }
 
+    /// Get the maximum number of messages per priority level
+    pub fn max_per_queue(&self) -> usize {
+        self.max_per_queue
+    }
+
     /// Get number of messages in a specific priority queue
     pub fn len_for_priority(&self, priority: PriorityLevel) -> usize {
         self.queues[priority.queue_index()].len()
@@ -182,6 +276,7 @@ impl PriorityQueue {
             queue.clear();
         }
         self.total_messages = 0;
+        self.credits = [0; 5];
     }
 
     /// Get statistics for all queues
@@ -204,7 +299,7 @@ impl Default for PriorityQueue {
 }
 
 /// Statistics for priority queues
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct PriorityQueueStats {
     pub emergency: usize,
     pub high: usize,
@@ -352,4 +447,80 @@ mod tests {
         assert_eq!(queue.len(), 0);
         assert!(queue.is_empty());
     }
+
+    #[test]
+    fn test_strict_priority_starves_lower_levels() {
+        let mut queue = PriorityQueue::new(1000);
+
+        for _ in 0..3 {
+            queue
+                .enqueue(create_test_message(Priority::background()))
+                .unwrap();
+        }
+
+        for _ in 0..100 {
+            queue
+                .enqueue(create_test_message(Priority::emergency()))
+                .unwrap();
+            let msg = queue.dequeue().unwrap();
+            assert_eq!(msg.message.priority, Priority::emergency());
+        }
+
+        assert_eq!(queue.len_for_priority(PriorityLevel::Background), 3);
+    }
+
+    #[test]
+    fn test_weighted_fair_bounded_wait() {
+        let mut queue = PriorityQueue::with_fairness(1000, FairnessPolicy::default_weighted());
+
+        for _ in 0..10 {
+            queue
+                .enqueue(create_test_message(Priority::emergency()))
+                .unwrap();
+        }
+        for _ in 0..3 {
+            queue
+                .enqueue(create_test_message(Priority::background()))
+                .unwrap();
+        }
+
+        // Steady stream of Emergency messages: one in, one out
+        let mut emergency = 0;
+        let mut pops = 0;
+        while queue.len_for_priority(PriorityLevel::Background) > 0 {
+            queue
+                .enqueue(create_test_message(Priority::emergency()))
+                .unwrap();
+            let msg = queue.dequeue().unwrap();
+            if msg.message.priority == Priority::emergency() {
+                emergency += 1;
+            }
+            pops += 1;
+
+            // Background weight 1 of 17: served at least once every 17 pops
+            assert!(pops <= 3 * 17, "Background starved after {} pops", pops);
+        }
+
+        // Emergency traffic still dominates: Background only got its share
+        assert_eq!(emergency, pops - 3);
+        assert!(pops >= 2 * 17, "Background over-served: {} pops", pops);
+    }
+
+    #[test]
+    fn test_weighted_fair_peek_matches_dequeue() {
+        let mut queue = PriorityQueue::with_fairness(1000, FairnessPolicy::default_weighted());
+
+        for _ in 0..20 {
+            queue
+                .enqueue(create_test_message(Priority::high()))
+                .unwrap();
+            queue.enqueue(create_test_message(Priority::low())).unwrap();
+        }
+
+        while !queue.is_empty() {
+            let peeked = queue.peek().unwrap().message.priority;
+            let dequeued = queue.dequeue().unwrap().message.priority;
+            assert_eq!(peeked, dequeued);
+        }
+    }
 }
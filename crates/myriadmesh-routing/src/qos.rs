@@ -3,13 +3,28 @@
 //! Implements advanced QoS mechanisms including:
 //! - Priority-based scheduling with multiple queues
 //! - Bandwidth reservation and admission control
+//! - Preemption of lower-class reservations by higher-class requests
 //! - Traffic shaping and policing
 //! - Service Level Agreements (SLA) enforcement
+//! - Work-conserving borrowing of idle reserved bandwidth
 
 use crate::priority_queue::PriorityLevel;
 use myriadmesh_protocol::NodeId;
 use std::collections::HashMap;
-use std::time::{Duration, Instant};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+
+/// Default scheduling quantum: a reservation unused for this long is idle
+/// and its capacity may be lent to other flows
+pub const DEFAULT_SCHEDULING_QUANTUM: Duration = Duration::from_millis(100);
+
+/// Default time without packets after which an unreserved flow's statistics
+/// are evicted
+pub const DEFAULT_FLOW_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Buffered QoS events per subscriber before old ones are dropped
+const EVENT_CHANNEL_CAPACITY: usize = 64;
 
 /// QoS class for different traffic types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -72,6 +87,33 @@ impl QosClass {
     }
 }
 
+/// What admission control may do when a reservation does not fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreemptionPolicy {
+    /// Reject reservations that do not fit
+    #[default]
+    Disabled,
+    /// Release reservations of strictly lower classes, lowest class first,
+    /// until the new reservation fits
+    PreemptLowerClasses,
+}
+
+/// Reservation lifecycle events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QosEvent {
+    /// A reservation was released to admit a higher-class one
+    Preempted {
+        /// Flow that lost its reservation
+        flow_id: FlowId,
+        /// Class of the preempted reservation
+        qos_class: QosClass,
+        /// Bandwidth freed (bytes per second)
+        released_bps: u64,
+        /// Flow whose reservation caused the preemption
+        preempted_by: FlowId,
+    },
+}
+
 /// Bandwidth reservation
 #[derive(Debug, Clone)]
 pub struct BandwidthReservation {
@@ -89,6 +131,8 @@ pub struct BandwidthReservation {
     pub duration: Duration,
     /// Bytes transmitted in this reservation
     pub bytes_transmitted: u64,
+    /// Last time the owning flow tried to send
+    pub last_active: Instant,
 }
 
 /// Flow identifier
@@ -108,6 +152,10 @@ pub struct FlowStats {
     pub avg_latency_ms: f64,
     pub avg_jitter_ms: f64,
     pub last_packet_time: Instant,
+    /// Bytes admitted from the flow's own reservation or the unreserved share
+    pub bytes_owned: u64,
+    /// Bytes admitted on capacity borrowed from idle reservations
+    pub bytes_borrowed: u64,
 }
 
 impl Default for FlowStats {
@@ -120,6 +168,8 @@ impl Default for FlowStats {
             avg_latency_ms: 0.0,
             avg_jitter_ms: 0.0,
             last_packet_time: Instant::now(),
+            bytes_owned: 0,
+            bytes_borrowed: 0,
         }
     }
 }
@@ -138,6 +188,22 @@ pub struct QosManager {
     admission_control: bool,
     /// Token buckets for rate limiting
     token_buckets: HashMap<FlowId, TokenBucket>,
+    /// Lend capacity of each reservation, usable by other flows while idle
+    lend_buckets: HashMap<FlowId, TokenBucket>,
+    /// Shared bucket for unreserved bandwidth (enforced when borrowing)
+    best_effort_bucket: TokenBucket,
+    /// Bandwidth borrowing enabled
+    borrowing: bool,
+    /// Idle threshold for lending reserved capacity
+    scheduling_quantum: Duration,
+    /// Idle time after which unreserved flow statistics are evicted
+    flow_idle_timeout: Duration,
+    /// Admission behavior when a reservation does not fit
+    preemption_policy: PreemptionPolicy,
+    /// Reservations released by preemption
+    total_preemptions: u64,
+    /// Reservation lifecycle events
+    event_tx: broadcast::Sender<QosEvent>,
 }
 
 impl QosManager {
@@ -150,10 +216,143 @@ impl QosManager {
             reserved_bandwidth_bps: 0,
             admission_control,
             token_buckets: HashMap::new(),
+            lend_buckets: HashMap::new(),
+            best_effort_bucket: TokenBucket::new(total_bandwidth_bps, total_bandwidth_bps / 10),
+            borrowing: false,
+            scheduling_quantum: DEFAULT_SCHEDULING_QUANTUM,
+            flow_idle_timeout: DEFAULT_FLOW_IDLE_TIMEOUT,
+            preemption_policy: PreemptionPolicy::default(),
+            total_preemptions: 0,
+            event_tx: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Set what admission control does when a reservation does not fit
+    pub fn set_preemption_policy(&mut self, policy: PreemptionPolicy) {
+        self.preemption_policy = policy;
+    }
+
+    /// Current preemption policy
+    pub fn preemption_policy(&self) -> PreemptionPolicy {
+        self.preemption_policy
+    }
+
+    /// Subscribe to reservation events such as preemptions
+    pub fn subscribe_events(&self) -> broadcast::Receiver<QosEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Enable or disable bandwidth borrowing
+    ///
+    /// When enabled, capacity of a reservation whose flow has been idle for a
+    /// scheduling quantum is lent to other flows, and reclaimed as soon as the
+    /// owning flow sends again. Unreserved flows are shaped to the unreserved
+    /// share of the link plus whatever they can borrow.
+    pub fn enable_borrowing(&mut self, enabled: bool) {
+        self.borrowing = enabled;
+    }
+
+    /// Check if bandwidth borrowing is enabled
+    pub fn is_borrowing_enabled(&self) -> bool {
+        self.borrowing
+    }
+
+    /// Set how long a reservation must be unused before it is lent out
+    pub fn set_scheduling_quantum(&mut self, quantum: Duration) {
+        self.scheduling_quantum = quantum;
+    }
+
+    /// Set how long a flow without a reservation may go without packets
+    /// before [`Self::cleanup_expired`] evicts its statistics
+    pub fn set_flow_idle_timeout(&mut self, timeout: Duration) {
+        self.flow_idle_timeout = timeout;
+    }
+
+    /// Resize the unreserved bucket after reservations change
+    fn update_best_effort_rate(&mut self) {
+        let unreserved = self
+            .total_bandwidth_bps
+            .saturating_sub(self.reserved_bandwidth_bps);
+        self.best_effort_bucket
+            .set_rate(unreserved, unreserved / 10);
+    }
+
+    /// Record bytes admitted for a flow
+    fn record_admitted(&mut self, flow_id: FlowId, bytes: u64, borrowed: bool) {
+        let stats = self.flow_stats.entry(flow_id).or_default();
+        if borrowed {
+            stats.bytes_borrowed += bytes;
+        } else {
+            stats.bytes_owned += bytes;
+        }
+        stats.last_packet_time = Instant::now();
+    }
+
+    /// Try to borrow capacity from another flow's idle reservation
+    fn try_borrow(&mut self, flow_id: &FlowId, packet_size: u64) -> bool {
+        let now = Instant::now();
+        let quantum = self.scheduling_quantum;
+
+        let lenders: Vec<FlowId> = self
+            .reservations
+            .iter()
+            .filter(|(id, res)| *id != flow_id && now.duration_since(res.last_active) >= quantum)
+            .map(|(id, _)| *id)
+            .collect();
+
+        lenders.iter().any(|lender| {
+            self.lend_buckets
+                .get_mut(lender)
+                .is_some_and(|bucket| bucket.consume(packet_size))
+        })
+    }
+
+    /// Pick reservations to release so `requested_bps` fits, lowest class
+    /// first and newest first within a class
+    ///
+    /// Fails with the total lower-class bandwidth if releasing all of it would
+    /// still not make room.
+    fn select_preemption_victims(
+        &self,
+        qos_class: QosClass,
+        requested_bps: u64,
+        available: u64,
+    ) -> Result<Vec<FlowId>, u64> {
+        let mut candidates: Vec<&BandwidthReservation> = self
+            .reservations
+            .values()
+            .filter(|res| res.qos_class.priority() < qos_class.priority())
+            .collect();
+        candidates.sort_by(|a, b| {
+            a.qos_class
+                .priority()
+                .cmp(&b.qos_class.priority())
+                .then(b.start_time.cmp(&a.start_time))
+        });
+
+        let mut freed = available;
+        let mut victims = Vec::new();
+        for res in candidates {
+            if freed >= requested_bps {
+                break;
+            }
+            freed += res.reserved_bps;
+            victims.push(res.flow_id);
+        }
+
+        if freed >= requested_bps {
+            Ok(victims)
+        } else {
+            Err(freed - available)
         }
     }
 
     /// Request bandwidth reservation
+    ///
+    /// With admission control, a request that does not fit fails unless the
+    /// preemption policy allows releasing lower-class reservations; in that
+    /// case nothing is released unless the request can then be admitted,
+    /// and each release is announced as a [`QosEvent::Preempted`].
     pub fn reserve_bandwidth(
         &mut self,
         flow_id: FlowId,
@@ -165,10 +364,23 @@ impl QosManager {
         if self.admission_control {
             let available = self.total_bandwidth_bps - self.reserved_bandwidth_bps;
             if requested_bps > available {
-                return Err(QosError::InsufficientBandwidth {
-                    requested: requested_bps,
-                    available,
-                });
+                if self.preemption_policy == PreemptionPolicy::Disabled {
+                    return Err(QosError::InsufficientBandwidth {
+                        requested: requested_bps,
+                        available,
+                    });
+                }
+
+                let victims = self
+                    .select_preemption_victims(qos_class, requested_bps, available)
+                    .map_err(|preemptible| QosError::PreemptionInsufficient {
+                        requested: requested_bps,
+                        available,
+                        preemptible,
+                    })?;
+                for victim in victims {
+                    self.preempt(&victim, flow_id);
+                }
             }
         }
 
@@ -181,6 +393,7 @@ impl QosManager {
             start_time: Instant::now(),
             duration,
             bytes_transmitted: 0,
+            last_active: Instant::now(),
         };
 
         // Update reserved bandwidth
@@ -190,26 +403,71 @@ impl QosManager {
         // Create token bucket for this flow
         let bucket = TokenBucket::new(requested_bps, requested_bps / 10);
         self.token_buckets.insert(flow_id, bucket);
+        let lend_bucket = TokenBucket::new(requested_bps, requested_bps / 10);
+        self.lend_buckets.insert(flow_id, lend_bucket);
+        self.update_best_effort_rate();
 
         Ok(())
     }
 
+    /// Release a reservation on behalf of `preempted_by` and announce it
+    fn preempt(&mut self, flow_id: &FlowId, preempted_by: FlowId) {
+        let Some(reservation) = self.reservations.get(flow_id) else {
+            return;
+        };
+        let event = QosEvent::Preempted {
+            flow_id: *flow_id,
+            qos_class: reservation.qos_class,
+            released_bps: reservation.reserved_bps,
+            preempted_by,
+        };
+
+        self.release_reservation(flow_id);
+        self.total_preemptions += 1;
+        // No subscribers is fine
+        let _ = self.event_tx.send(event);
+    }
+
     /// Release bandwidth reservation
     pub fn release_reservation(&mut self, flow_id: &FlowId) {
         if let Some(reservation) = self.reservations.remove(flow_id) {
             self.reserved_bandwidth_bps -= reservation.reserved_bps;
             self.token_buckets.remove(flow_id);
+            self.lend_buckets.remove(flow_id);
+            self.update_best_effort_rate();
         }
     }
 
     /// Check if a packet can be sent (admission control)
+    ///
+    /// With borrowing enabled, a flow that exhausts its own share may use
+    /// capacity of idle reservations; any send attempt by a reservation's
+    /// owner marks it active and immediately stops it from being lent out.
     pub fn can_send(&mut self, flow_id: &FlowId, packet_size: u64) -> bool {
-        if let Some(bucket) = self.token_buckets.get_mut(flow_id) {
-            bucket.consume(packet_size)
+        let admitted = if let Some(bucket) = self.token_buckets.get_mut(flow_id) {
+            let admitted = bucket.consume(packet_size);
+            if let Some(reservation) = self.reservations.get_mut(flow_id) {
+                reservation.last_active = Instant::now();
+            }
+            admitted
+        } else if self.borrowing {
+            self.best_effort_bucket.consume(packet_size)
         } else {
             // No reservation, use best effort
             true
+        };
+
+        if admitted {
+            self.record_admitted(*flow_id, packet_size, false);
+            return true;
+        }
+
+        if self.borrowing && self.try_borrow(flow_id, packet_size) {
+            self.record_admitted(*flow_id, packet_size, true);
+            return true;
         }
+
+        false
     }
 
     /// Update flow statistics
@@ -241,7 +499,10 @@ impl QosManager {
         stats.last_packet_time = Instant::now();
     }
 
-    /// Cleanup expired reservations
+    /// Cleanup expired reservations and evict statistics of idle flows
+    ///
+    /// Statistics of a flow that holds a reservation are kept for as long as
+    /// the reservation lasts.
     pub fn cleanup_expired(&mut self) {
         let now = Instant::now();
         let expired: Vec<FlowId> = self
@@ -254,6 +515,13 @@ impl QosManager {
         for flow_id in expired {
             self.release_reservation(&flow_id);
         }
+
+        let idle_timeout = self.flow_idle_timeout;
+        let reservations = &self.reservations;
+        self.flow_stats.retain(|flow_id, stats| {
+            reservations.contains_key(flow_id)
+                || now.duration_since(stats.last_packet_time) <= idle_timeout
+        });
     }
 
     /// Get QoS statistics
@@ -273,6 +541,7 @@ impl QosManager {
             reserved_bandwidth_bps: self.reserved_bandwidth_bps,
             available_bandwidth_bps: self.total_bandwidth_bps - self.reserved_bandwidth_bps,
             bandwidth_utilization,
+            total_preemptions: self.total_preemptions,
         }
     }
 
@@ -315,6 +584,14 @@ impl TokenBucket {
         self.last_refill = now;
     }
 
+    /// Change the refill rate and burst capacity
+    fn set_rate(&mut self, rate_bps: u64, burst_bytes: u64) {
+        self.refill();
+        self.refill_rate = rate_bps;
+        self.capacity = burst_bytes;
+        self.tokens = self.tokens.min(burst_bytes);
+    }
+
     /// Try to consume tokens
     fn consume(&mut self, amount: u64) -> bool {
         self.refill();
@@ -336,6 +613,8 @@ pub struct QosStats {
     pub reserved_bandwidth_bps: u64,
     pub available_bandwidth_bps: u64,
     pub bandwidth_utilization: f64,
+    /// Reservations released to admit higher-class ones
+    pub total_preemptions: u64,
 }
 
 /// QoS errors
@@ -344,6 +623,15 @@ pub enum QosError {
     #[error("Insufficient bandwidth: requested {requested} bps, available {available} bps")]
     InsufficientBandwidth { requested: u64, available: u64 },
 
+    #[error(
+        "Insufficient bandwidth even with preemption: requested {requested} bps, available {available} bps, preemptible {preemptible} bps"
+    )]
+    PreemptionInsufficient {
+        requested: u64,
+        available: u64,
+        preemptible: u64,
+    },
+
     #[error("Reservation not found")]
     ReservationNotFound,
 
@@ -444,4 +732,227 @@ mod tests {
         assert_eq!(stats.packets_sent, 2);
         assert!(stats.avg_latency_ms > 0.0);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_flow_stats_evicted() {
+        let mut manager = QosManager::new(1_000_000, false);
+        manager.set_flow_idle_timeout(Duration::from_secs(60));
+        let reserved = flow(1);
+        let active = flow(2);
+        let idle = flow(3);
+        manager
+            .reserve_bandwidth(
+                reserved,
+                QosClass::Streaming,
+                100_000,
+                Duration::from_secs(3600),
+            )
+            .unwrap();
+
+        manager.update_stats(reserved, 1000, 10.0, false);
+        manager.update_stats(idle, 1000, 10.0, false);
+        tokio::time::advance(Duration::from_secs(45)).await;
+        assert!(manager.can_send(&active, 1000));
+        tokio::time::advance(Duration::from_secs(30)).await;
+
+        manager.cleanup_expired();
+        assert!(manager.get_flow_stats(&idle).is_none());
+        assert!(manager.get_flow_stats(&active).is_some());
+        // Reserved flows keep their statistics while the reservation lasts
+        assert!(manager.get_flow_stats(&reserved).is_some());
+        assert_eq!(manager.stats().total_flows, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_best_effort_borrows_idle_reservation() {
+        // Link fully reserved: best effort has no share of its own
+        let mut manager = QosManager::new(100_000, true);
+        manager.enable_borrowing(true);
+        manager.set_scheduling_quantum(Duration::from_millis(20));
+
+        let reserved = create_test_flow();
+        let best_effort = FlowId {
+            source: create_test_node_id(3),
+            destination: create_test_node_id(4),
+        };
+        manager
+            .reserve_bandwidth(
+                reserved,
+                QosClass::Streaming,
+                100_000,
+                Duration::from_secs(60),
+            )
+            .unwrap();
+
+        // Reservation hasn't been idle for a quantum yet
+        assert!(!manager.can_send(&best_effort, 5_000));
+
+        tokio::time::advance(Duration::from_millis(30)).await;
+
+        // Idle reservation capacity is lent to the best-effort flow
+        assert!(manager.can_send(&best_effort, 5_000));
+        let stats = manager.get_flow_stats(&best_effort).unwrap();
+        assert_eq!(stats.bytes_borrowed, 5_000);
+        assert_eq!(stats.bytes_owned, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reserved_flow_preempts_borrower() {
+        let mut manager = QosManager::new(100_000, true);
+        manager.enable_borrowing(true);
+        manager.set_scheduling_quantum(Duration::from_millis(20));
+
+        let reserved = create_test_flow();
+        let best_effort = FlowId {
+            source: create_test_node_id(3),
+            destination: create_test_node_id(4),
+        };
+        manager
+            .reserve_bandwidth(
+                reserved,
+                QosClass::Streaming,
+                100_000,
+                Duration::from_secs(60),
+            )
+            .unwrap();
+
+        tokio::time::advance(Duration::from_millis(30)).await;
+        assert!(manager.can_send(&best_effort, 2_000));
+
+        // Owner becomes active: its full reservation is available and lending stops
+        assert!(manager.can_send(&reserved, 10_000));
+        assert!(!manager.can_send(&best_effort, 2_000));
+
+        let owner_stats = manager.get_flow_stats(&reserved).unwrap();
+        assert_eq!(owner_stats.bytes_owned, 10_000);
+        assert_eq!(owner_stats.bytes_borrowed, 0);
+        assert_eq!(
+            manager.get_flow_stats(&best_effort).unwrap().bytes_borrowed,
+            2_000
+        );
+    }
+
+    #[test]
+    fn test_borrowing_disabled_keeps_best_effort_unshaped() {
+        let mut manager = QosManager::new(100_000, true);
+        let flow = create_test_flow();
+
+        assert!(!manager.is_borrowing_enabled());
+        assert!(manager.can_send(&flow, 1_000_000));
+        assert_eq!(manager.get_flow_stats(&flow).unwrap().bytes_borrowed, 0);
+    }
+
+    fn flow(value: u8) -> FlowId {
+        FlowId {
+            source: create_test_node_id(value),
+            destination: create_test_node_id(value.wrapping_add(100)),
+        }
+    }
+
+    #[test]
+    fn test_preemption_admits_higher_class() {
+        let mut manager = QosManager::new(300_000, true);
+        manager.set_preemption_policy(PreemptionPolicy::PreemptLowerClasses);
+        let mut events = manager.subscribe_events();
+
+        let (bulk, stream, interactive, realtime) = (flow(1), flow(2), flow(3), flow(4));
+        let minute = Duration::from_secs(60);
+        manager
+            .reserve_bandwidth(bulk, QosClass::BulkData, 100_000, minute)
+            .unwrap();
+        manager
+            .reserve_bandwidth(stream, QosClass::Streaming, 100_000, minute)
+            .unwrap();
+        manager
+            .reserve_bandwidth(interactive, QosClass::Interactive, 100_000, minute)
+            .unwrap();
+
+        // Needs 150 KB/s on a full link: bulk goes first, then streaming
+        manager
+            .reserve_bandwidth(realtime, QosClass::RealTime, 150_000, minute)
+            .unwrap();
+
+        let preempted: Vec<_> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+        assert_eq!(
+            preempted,
+            vec![
+                QosEvent::Preempted {
+                    flow_id: bulk,
+                    qos_class: QosClass::BulkData,
+                    released_bps: 100_000,
+                    preempted_by: realtime,
+                },
+                QosEvent::Preempted {
+                    flow_id: stream,
+                    qos_class: QosClass::Streaming,
+                    released_bps: 100_000,
+                    preempted_by: realtime,
+                },
+            ]
+        );
+
+        let stats = manager.stats();
+        assert_eq!(stats.total_reservations, 2);
+        assert_eq!(stats.reserved_bandwidth_bps, 250_000);
+        assert_eq!(stats.total_preemptions, 2);
+        assert!(manager.reservations.contains_key(&interactive));
+        assert!(manager.reservations.contains_key(&realtime));
+    }
+
+    #[test]
+    fn test_preemption_rejected_when_insufficient() {
+        let mut manager = QosManager::new(200_000, true);
+        manager.set_preemption_policy(PreemptionPolicy::PreemptLowerClasses);
+        let mut events = manager.subscribe_events();
+        let minute = Duration::from_secs(60);
+
+        manager
+            .reserve_bandwidth(flow(1), QosClass::BulkData, 50_000, minute)
+            .unwrap();
+        manager
+            .reserve_bandwidth(flow(2), QosClass::RealTime, 150_000, minute)
+            .unwrap();
+
+        // Only the bulk reservation is lower class; freeing it is not enough
+        let result = manager.reserve_bandwidth(flow(3), QosClass::Interactive, 100_000, minute);
+        assert!(matches!(
+            result,
+            Err(QosError::PreemptionInsufficient {
+                requested: 100_000,
+                available: 0,
+                preemptible: 50_000,
+            })
+        ));
+
+        // Nothing was released
+        assert!(events.try_recv().is_err());
+        let stats = manager.stats();
+        assert_eq!(stats.total_reservations, 2);
+        assert_eq!(stats.reserved_bandwidth_bps, 200_000);
+        assert_eq!(stats.total_preemptions, 0);
+
+        // Same-class reservations are never preempted
+        let result = manager.reserve_bandwidth(flow(4), QosClass::BulkData, 10_000, minute);
+        assert!(matches!(
+            result,
+            Err(QosError::PreemptionInsufficient { preemptible: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_preemption_disabled_by_default() {
+        let mut manager = QosManager::new(100_000, true);
+        assert_eq!(manager.preemption_policy(), PreemptionPolicy::Disabled);
+        let minute = Duration::from_secs(60);
+
+        manager
+            .reserve_bandwidth(flow(1), QosClass::BulkData, 100_000, minute)
+            .unwrap();
+        let result = manager.reserve_bandwidth(flow(2), QosClass::RealTime, 50_000, minute);
+        assert!(matches!(
+            result,
+            Err(QosError::InsufficientBandwidth { .. })
+        ));
+        assert_eq!(manager.stats().total_reservations, 1);
+    }
 }
@@ -1,9 +1,92 @@
 //! Rate limiting for message routing
 
+use crate::priority_queue::PriorityLevel;
 use myriadmesh_protocol::NodeId;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+/// Token bucket configuration for one priority level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriorityBucketConfig {
+    /// Maximum tokens (burst size, in messages)
+    pub capacity: f64,
+    /// Refill rate (messages per second)
+    pub refill_per_sec: f64,
+}
+
+impl PriorityBucketConfig {
+    pub fn new(capacity: f64, refill_per_sec: f64) -> Self {
+        PriorityBucketConfig {
+            capacity,
+            refill_per_sec,
+        }
+    }
+
+    /// Default bucket configuration, indexed by `PriorityLevel::queue_index()`
+    ///
+    /// Emergency traffic gets the largest budget so it is never starved by
+    /// bulk senders.
+    pub fn defaults() -> [PriorityBucketConfig; 5] {
+        [
+            PriorityBucketConfig::new(25.0, 2.0),   // Background
+            PriorityBucketConfig::new(50.0, 5.0),   // Low
+            PriorityBucketConfig::new(100.0, 10.0), // Normal
+            PriorityBucketConfig::new(100.0, 10.0), // High
+            PriorityBucketConfig::new(200.0, 20.0), // Emergency
+        ]
+    }
+}
+
+/// Opt-in per-priority rate limits
+///
+/// Each priority level draws from its own token bucket, and a share of the
+/// per-node and global windows is held back for Emergency traffic, so a
+/// flood of low-priority messages cannot lock emergencies out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriorityRateLimits {
+    /// Token buckets indexed by `PriorityLevel::queue_index()`
+    pub buckets: [PriorityBucketConfig; 5],
+    /// Fraction (0.0-1.0) of the per-node and global limits only
+    /// Emergency messages may use
+    pub emergency_reserve: f64,
+}
+
+impl Default for PriorityRateLimits {
+    fn default() -> Self {
+        PriorityRateLimits {
+            buckets: PriorityBucketConfig::defaults(),
+            emergency_reserve: 0.1,
+        }
+    }
+}
+
+/// Token bucket with fractional refill
+#[derive(Debug)]
+struct PriorityBucket {
+    config: PriorityBucketConfig,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl PriorityBucket {
+    fn new(config: PriorityBucketConfig) -> Self {
+        PriorityBucket {
+            config,
+            tokens: config.capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.config.refill_per_sec).min(self.config.capacity);
+        self.last_refill = now;
+    }
+}
+
 /// Rate limiter for message routing
 #[derive(Debug)]
 pub struct RateLimiter {
@@ -21,6 +104,12 @@ pub struct RateLimiter {
 
     /// Window duration
     window: Duration,
+
+    /// Token buckets indexed by `PriorityLevel::queue_index()`, if enabled
+    priority_buckets: Option<[PriorityBucket; 5]>,
+
+    /// Share of each window reserved for Emergency traffic
+    emergency_reserve: f64,
 }
 
 impl RateLimiter {
@@ -32,14 +121,115 @@ impl RateLimiter {
             node_counters: HashMap::new(),
             global_counter: (0, Instant::now()),
             window: Duration::from_secs(60), // 1 minute window
+            priority_buckets: None,
+            emergency_reserve: 0.0,
+        }
+    }
+
+    /// Create a rate limiter with per-priority limits enabled
+    pub fn with_priority_limits(
+        per_node_limit: u32,
+        global_limit: u32,
+        limits: PriorityRateLimits,
+    ) -> Self {
+        let mut limiter = Self::new(per_node_limit, global_limit);
+        limiter.set_priority_limits(Some(limits));
+        limiter
+    }
+
+    /// Enable (or with `None`, disable) per-priority limits
+    ///
+    /// Buckets start full.
+    pub fn set_priority_limits(&mut self, limits: Option<PriorityRateLimits>) {
+        match limits {
+            Some(limits) => {
+                self.priority_buckets = Some(limits.buckets.map(PriorityBucket::new));
+                self.emergency_reserve = limits.emergency_reserve.clamp(0.0, 1.0);
+            }
+            None => {
+                self.priority_buckets = None;
+                self.emergency_reserve = 0.0;
+            }
         }
     }
 
+    /// Check if a message from a node at the given priority should be accepted
+    ///
+    /// Without per-priority limits this is [`Self::check_rate`]. With them,
+    /// each priority level draws from its own token bucket, so exhausting a
+    /// low-priority bucket never blocks emergency traffic, and only
+    /// Emergency messages may use the reserved share of the per-node and
+    /// global windows. The bucket token is only consumed if the per-node and
+    /// global limits also pass.
+    pub fn check(
+        &mut self,
+        node_id: &NodeId,
+        priority: PriorityLevel,
+    ) -> Result<(), RateLimitError> {
+        let Some(buckets) = self.priority_buckets.as_mut() else {
+            return self.check_rate(node_id);
+        };
+
+        let bucket = &mut buckets[priority.queue_index()];
+        bucket.refill(Instant::now());
+        if bucket.tokens < 1.0 {
+            return Err(RateLimitError::PriorityLimitExceeded {
+                priority,
+                capacity: bucket.config.capacity,
+                refill_per_sec: bucket.config.refill_per_sec,
+            });
+        }
+
+        if priority == PriorityLevel::Emergency {
+            self.check_within(node_id, self.per_node_limit, self.global_limit)?;
+        } else {
+            self.check_within(
+                node_id,
+                self.unreserved(self.per_node_limit),
+                self.unreserved(self.global_limit),
+            )?;
+        }
+
+        if let Some(buckets) = self.priority_buckets.as_mut() {
+            buckets[priority.queue_index()].tokens -= 1.0;
+        }
+        Ok(())
+    }
+
+    /// Part of `limit` non-emergency traffic may use
+    fn unreserved(&self, limit: u32) -> u32 {
+        let reserved = (limit as f64 * self.emergency_reserve).ceil() as u32;
+        limit.saturating_sub(reserved)
+    }
+
+    /// Get remaining tokens in a priority bucket (None: buckets disabled)
+    pub fn remaining_tokens(&mut self, priority: PriorityLevel) -> Option<f64> {
+        let bucket = &mut self.priority_buckets.as_mut()?[priority.queue_index()];
+        bucket.refill(Instant::now());
+        Some(bucket.tokens)
+    }
+
+    /// Get the bucket configuration for a priority level (None: buckets disabled)
+    pub fn priority_bucket_config(&self, priority: PriorityLevel) -> Option<PriorityBucketConfig> {
+        self.priority_buckets
+            .as_ref()
+            .map(|buckets| buckets[priority.queue_index()].config)
+    }
+
     /// Check if a message from a node should be accepted
     ///
     /// SECURITY H12: Global limit is checked FIRST to prevent resource exhaustion
     /// from per-node tracking when global capacity is exhausted
     pub fn check_rate(&mut self, node_id: &NodeId) -> Result<(), RateLimitError> {
+        self.check_within(node_id, self.per_node_limit, self.global_limit)
+    }
+
+    fn check_within(
+        &mut self,
+        node_id: &NodeId,
+        per_node_limit: u32,
+        global_limit: u32,
+    ) -> Result<(), RateLimitError> {
         let now = Instant::now();
 
         // SECURITY H12: Check global limit FIRST before per-node tracking
@@ -50,9 +240,9 @@ impl RateLimiter {
         }
 
         // Check if incrementing would exceed global limit
-        if self.global_counter.0 + 1 > self.global_limit {
+        if self.global_counter.0 + 1 > global_limit {
             return Err(RateLimitError::GlobalLimitExceeded {
-                limit: self.global_limit,
+                limit: global_limit,
                 current: self.global_counter.0 + 1, // What it would be
             });
         }
@@ -67,10 +257,10 @@ impl RateLimiter {
         }
 
         // Check if incrementing would exceed per-node limit
-        if entry.0 + 1 > self.per_node_limit {
+        if entry.0 + 1 > per_node_limit {
             return Err(RateLimitError::PerNodeLimitExceeded {
                 node_id: *node_id,
-                limit: self.per_node_limit,
+                limit: per_node_limit,
                 current: entry.0 + 1, // What it would be
             });
         }
@@ -105,10 +295,15 @@ impl RateLimiter {
         }
     }
 
-    /// Clear all rate limit counters
+    /// Clear all rate limit counters and refill priority buckets
     pub fn clear(&mut self) {
         self.node_counters.clear();
         self.global_counter = (0, Instant::now());
+        if let Some(buckets) = self.priority_buckets.as_mut() {
+            for bucket in buckets {
+                *bucket = PriorityBucket::new(bucket.config);
+            }
+        }
     }
 
     /// Cleanup expired node counters
@@ -131,6 +326,13 @@ pub enum RateLimitError {
 
     /// Global limit exceeded
     GlobalLimitExceeded { limit: u32, current: u32 },
+
+    /// Token bucket for a priority level is empty
+    PriorityLimitExceeded {
+        priority: PriorityLevel,
+        capacity: f64,
+        refill_per_sec: f64,
+    },
 }
 
 impl std::fmt::Display for RateLimitError {
@@ -150,6 +352,15 @@ impl std::fmt::Display for RateLimitError {
                 "Global rate limit exceeded: {}/{} messages/min",
                 current, limit
             ),
+            RateLimitError::PriorityLimitExceeded {
+                priority,
+                capacity,
+                refill_per_sec,
+            } => write!(
+                f,
+                "Rate limit exceeded for {:?} priority: bucket of {} empty, refilling at {}/s",
+                priority, capacity, refill_per_sec
+            ),
         }
     }
 }
@@ -438,4 +649,165 @@ mod tests {
             _ => panic!("Expected GlobalLimitExceeded"),
         }
     }
+
+    fn priority_limits(
+        overrides: &[(PriorityLevel, PriorityBucketConfig)],
+        emergency_reserve: f64,
+    ) -> PriorityRateLimits {
+        let mut limits = PriorityRateLimits {
+            emergency_reserve,
+            ..Default::default()
+        };
+        for (priority, config) in overrides {
+            limits.buckets[priority.queue_index()] = *config;
+        }
+        limits
+    }
+
+    #[test]
+    fn test_priority_limits_are_opt_in() {
+        let mut limiter = RateLimiter::new(2, 100);
+        let node = create_test_node_id(1);
+        assert_eq!(limiter.remaining_tokens(PriorityLevel::Normal), None);
+        assert_eq!(limiter.priority_bucket_config(PriorityLevel::Normal), None);
+
+        // Without buckets every priority shares the plain per-node limit
+        limiter.check(&node, PriorityLevel::Background).unwrap();
+        limiter.check(&node, PriorityLevel::Background).unwrap();
+        assert!(matches!(
+            limiter.check(&node, PriorityLevel::Emergency),
+            Err(RateLimitError::PerNodeLimitExceeded { .. })
+        ));
+
+        limiter.set_priority_limits(Some(PriorityRateLimits::default()));
+        assert!(limiter.remaining_tokens(PriorityLevel::Normal).is_some());
+        limiter.set_priority_limits(None);
+        assert_eq!(limiter.remaining_tokens(PriorityLevel::Normal), None);
+    }
+
+    #[test]
+    fn test_exhausted_background_bucket_still_permits_emergency() {
+        let limits = priority_limits(
+            &[(
+                PriorityLevel::Background,
+                PriorityBucketConfig::new(3.0, 0.0),
+            )],
+            0.0,
+        );
+        let mut limiter = RateLimiter::with_priority_limits(100, 100, limits);
+        let node = create_test_node_id(1);
+
+        for _ in 0..3 {
+            assert!(limiter.check(&node, PriorityLevel::Background).is_ok());
+        }
+
+        let result = limiter.check(&node, PriorityLevel::Background);
+        assert!(matches!(
+            result,
+            Err(RateLimitError::PriorityLimitExceeded {
+                priority: PriorityLevel::Background,
+                ..
+            })
+        ));
+        assert!(limiter.remaining_tokens(PriorityLevel::Background).unwrap() < 1.0);
+
+        // Emergency traffic has its own budget
+        assert!(limiter.check(&node, PriorityLevel::Emergency).is_ok());
+
+        // Rejected bulk messages did not count against the node's window
+        assert_eq!(limiter.get_node_rate(&node), 4);
+    }
+
+    #[test]
+    fn test_emergency_reserve() {
+        let limits = priority_limits(&[], 0.2);
+        let mut limiter = RateLimiter::with_priority_limits(10, 100, limits);
+        let node = create_test_node_id(1);
+
+        // Non-emergency traffic stops short of the reserved 20%
+        for _ in 0..8 {
+            limiter.check(&node, PriorityLevel::Normal).unwrap();
+        }
+        assert!(matches!(
+            limiter.check(&node, PriorityLevel::High),
+            Err(RateLimitError::PerNodeLimitExceeded { limit: 8, .. })
+        ));
+
+        // which Emergency can still use
+        limiter.check(&node, PriorityLevel::Emergency).unwrap();
+        limiter.check(&node, PriorityLevel::Emergency).unwrap();
+        assert!(limiter.check(&node, PriorityLevel::Emergency).is_err());
+
+        // The reserve also applies to the global window
+        let mut limiter = RateLimiter::with_priority_limits(100, 10, limits);
+        for i in 0..8 {
+            limiter
+                .check(&create_test_node_id(i), PriorityLevel::Low)
+                .unwrap();
+        }
+        assert!(matches!(
+            limiter.check(&create_test_node_id(8), PriorityLevel::Low),
+            Err(RateLimitError::GlobalLimitExceeded { limit: 8, .. })
+        ));
+        limiter
+            .check(&create_test_node_id(8), PriorityLevel::Emergency)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_priority_bucket_refill_rates() {
+        let limits = priority_limits(
+            &[
+                (PriorityLevel::Low, PriorityBucketConfig::new(10.0, 1.0)),
+                (PriorityLevel::High, PriorityBucketConfig::new(10.0, 4.0)),
+            ],
+            0.0,
+        );
+        let mut limiter = RateLimiter::with_priority_limits(1000, 1000, limits);
+        let node = create_test_node_id(1);
+
+        for _ in 0..10 {
+            limiter.check(&node, PriorityLevel::Low).unwrap();
+            limiter.check(&node, PriorityLevel::High).unwrap();
+        }
+        assert!(limiter.check(&node, PriorityLevel::Low).is_err());
+        assert!(limiter.check(&node, PriorityLevel::High).is_err());
+
+        // Pretend two seconds have passed
+        let two_secs_ago = Instant::now() - Duration::from_secs(2);
+        let buckets = limiter.priority_buckets.as_mut().unwrap();
+        buckets[PriorityLevel::Low.queue_index()].last_refill = two_secs_ago;
+        buckets[PriorityLevel::High.queue_index()].last_refill = two_secs_ago;
+
+        let low = limiter.remaining_tokens(PriorityLevel::Low).unwrap();
+        let high = limiter.remaining_tokens(PriorityLevel::High).unwrap();
+        assert!((2.0..2.1).contains(&low), "low bucket refilled to {}", low);
+        assert!(
+            (8.0..8.1).contains(&high),
+            "high bucket refilled to {}",
+            high
+        );
+
+        // Refill is capped at capacity
+        limiter.priority_buckets.as_mut().unwrap()[PriorityLevel::High.queue_index()].last_refill =
+            Instant::now() - Duration::from_secs(60);
+        assert_eq!(limiter.remaining_tokens(PriorityLevel::High), Some(10.0));
+    }
+
+    #[test]
+    fn test_priority_bucket_not_consumed_on_node_limit() {
+        let mut limiter = RateLimiter::with_priority_limits(2, 100, priority_limits(&[], 0.0));
+        let node = create_test_node_id(1);
+        let before = limiter.remaining_tokens(PriorityLevel::Normal).unwrap();
+
+        limiter.check(&node, PriorityLevel::Normal).unwrap();
+        limiter.check(&node, PriorityLevel::Normal).unwrap();
+        assert!(matches!(
+            limiter.check(&node, PriorityLevel::Normal),
+            Err(RateLimitError::PerNodeLimitExceeded { .. })
+        ));
+
+        let after = limiter.remaining_tokens(PriorityLevel::Normal).unwrap();
+        assert!((before - after - 2.0).abs() < 0.1);
+    }
 }
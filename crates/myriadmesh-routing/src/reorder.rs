@@ -0,0 +1,332 @@
+//! Per-flow in-order delivery
+//!
+//! Multipath forwarding and retries can deliver a flow's messages out of
+//! order. The reorder buffer holds early arrivals until the gap before them
+//! fills, then releases them in sequence order. A flow is one
+//! (source, destination) pair, ordered by the message sequence number.
+//!
+//! A gap is given up on, and the messages behind it released, when it has
+//! been open for `gap_timeout` or when the flow's buffer is full. Messages
+//! arriving after their sequence was skipped are released immediately.
+
+use myriadmesh_protocol::{message::Message, NodeId};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Default messages held per flow while waiting for a gap to fill
+pub const DEFAULT_MAX_BUFFERED_PER_FLOW: usize = 64;
+
+/// Default time a gap may stay open before later messages are released
+pub const DEFAULT_GAP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default time after which a flow with nothing buffered is forgotten
+pub const DEFAULT_FLOW_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Ordered delivery settings
+#[derive(Debug, Clone)]
+pub struct ReorderConfig {
+    /// Messages held per flow; when full the oldest gap is skipped
+    pub max_buffered_per_flow: usize,
+    /// Time a gap may stay open before it is skipped
+    pub gap_timeout: Duration,
+    /// Time after which an idle flow's state is dropped
+    pub flow_idle_timeout: Duration,
+}
+
+impl Default for ReorderConfig {
+    fn default() -> Self {
+        Self {
+            max_buffered_per_flow: DEFAULT_MAX_BUFFERED_PER_FLOW,
+            gap_timeout: DEFAULT_GAP_TIMEOUT,
+            flow_idle_timeout: DEFAULT_FLOW_IDLE_TIMEOUT,
+        }
+    }
+}
+
+/// Ordering state of one (source, destination) flow
+#[derive(Debug)]
+struct FlowState {
+    /// Next sequence number to release
+    next_sequence: u32,
+    /// Early arrivals keyed by sequence number
+    buffered: HashMap<u32, Message>,
+    /// When the current gap opened (None while nothing is buffered)
+    gap_since: Option<Instant>,
+    /// Last arrival on this flow
+    last_activity: Instant,
+}
+
+impl FlowState {
+    /// Move `buffered` messages from `next_sequence` onward into `released`
+    fn release_run(&mut self, released: &mut Vec<Message>) {
+        while let Some(message) = self.buffered.remove(&self.next_sequence) {
+            released.push(message);
+            self.next_sequence = self.next_sequence.wrapping_add(1);
+        }
+    }
+
+    /// Give up on the current gap, returning how many sequence numbers were skipped
+    fn skip_gap(&mut self, released: &mut Vec<Message>) -> u64 {
+        let next = self.next_sequence;
+        let Some(nearest) = self
+            .buffered
+            .keys()
+            .copied()
+            .min_by_key(|sequence| sequence.wrapping_sub(next))
+        else {
+            return 0;
+        };
+
+        let skipped = nearest.wrapping_sub(next) as u64;
+        self.next_sequence = nearest;
+        self.release_run(released);
+        skipped
+    }
+
+    /// Restart or clear the gap timer after a release
+    fn update_gap(&mut self, now: Instant) {
+        if self.buffered.is_empty() {
+            self.gap_since = None;
+        } else if self.gap_since.is_none() {
+            self.gap_since = Some(now);
+        }
+    }
+}
+
+/// Reorder buffers for all flows
+#[derive(Debug)]
+pub struct ReorderBuffer {
+    config: ReorderConfig,
+    flows: HashMap<(NodeId, NodeId), FlowState>,
+    /// Sequence numbers given up on
+    skipped: u64,
+    /// Messages released immediately because their sequence was already passed
+    late: u64,
+}
+
+impl ReorderBuffer {
+    /// Create an empty buffer
+    pub fn new(config: ReorderConfig) -> Self {
+        Self {
+            config,
+            flows: HashMap::new(),
+            skipped: 0,
+            late: 0,
+        }
+    }
+
+    /// Accept a message, returning the messages now releasable in order
+    ///
+    /// The first message seen on a flow sets its starting sequence number.
+    pub fn push(&mut self, message: Message, now: Instant) -> Vec<Message> {
+        let key = (message.source, message.destination);
+        let sequence = message.sequence;
+        let mut released = Vec::new();
+
+        let flow = self.flows.entry(key).or_insert_with(|| FlowState {
+            next_sequence: sequence,
+            buffered: HashMap::new(),
+            gap_since: None,
+            last_activity: now,
+        });
+        flow.last_activity = now;
+
+        // Serial number arithmetic, so ordering survives sequence wraparound
+        let offset = sequence.wrapping_sub(flow.next_sequence) as i32;
+        if offset < 0 {
+            // Its place in the order was given up; holding it would not help
+            self.late += 1;
+            released.push(message);
+            return released;
+        }
+
+        flow.buffered.entry(sequence).or_insert(message);
+        flow.release_run(&mut released);
+
+        while flow.buffered.len() > self.config.max_buffered_per_flow {
+            // Full: give up on the oldest gap rather than grow without bound
+            flow.gap_since = None;
+            self.skipped += flow.skip_gap(&mut released);
+        }
+        flow.update_gap(now);
+
+        released
+    }
+
+    /// Release messages stuck behind gaps open longer than the gap timeout
+    pub fn release_stalled(&mut self, now: Instant) -> Vec<Message> {
+        let mut released = Vec::new();
+        for flow in self.flows.values_mut() {
+            let Some(since) = flow.gap_since else {
+                continue;
+            };
+            if now.duration_since(since) < self.config.gap_timeout {
+                continue;
+            }
+
+            // A later gap in the same flow gets a full timeout of its own
+            flow.gap_since = None;
+            self.skipped += flow.skip_gap(&mut released);
+            flow.update_gap(now);
+        }
+        released
+    }
+
+    /// Forget flows with nothing buffered and no recent arrivals
+    pub fn prune_idle(&mut self, now: Instant) {
+        let idle_timeout = self.config.flow_idle_timeout;
+        self.flows.retain(|_, flow| {
+            !flow.buffered.is_empty() || now.duration_since(flow.last_activity) < idle_timeout
+        });
+    }
+
+    /// Messages currently held back
+    pub fn buffered_len(&self) -> usize {
+        self.flows.values().map(|flow| flow.buffered.len()).sum()
+    }
+
+    /// Flows being tracked
+    pub fn flow_count(&self) -> usize {
+        self.flows.len()
+    }
+
+    /// Sequence numbers given up on so far
+    pub fn skipped_sequences(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Messages that arrived after their sequence number was skipped
+    pub fn late_messages(&self) -> u64 {
+        self.late
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myriadmesh_protocol::{message::MessageType, types::NODE_ID_SIZE};
+
+    fn node(byte: u8) -> NodeId {
+        NodeId::from_bytes([byte; NODE_ID_SIZE])
+    }
+
+    fn message(source: u8, sequence: u32) -> Message {
+        Message::new(node(source), node(9), MessageType::Data, vec![source])
+            .unwrap()
+            .with_sequence(sequence)
+    }
+
+    fn sequences(messages: &[Message]) -> Vec<u32> {
+        messages.iter().map(|m| m.sequence).collect()
+    }
+
+    fn buffer(max_buffered_per_flow: usize) -> ReorderBuffer {
+        ReorderBuffer::new(ReorderConfig {
+            max_buffered_per_flow,
+            gap_timeout: Duration::from_secs(5),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_out_of_order_released_in_order() {
+        let mut reorder = buffer(16);
+        let now = Instant::now();
+
+        assert_eq!(sequences(&reorder.push(message(1, 10), now)), vec![10]);
+        assert!(reorder.push(message(1, 13), now).is_empty());
+        assert!(reorder.push(message(1, 12), now).is_empty());
+        assert_eq!(reorder.buffered_len(), 2);
+        assert_eq!(
+            sequences(&reorder.push(message(1, 11), now)),
+            vec![11, 12, 13]
+        );
+        assert_eq!(reorder.buffered_len(), 0);
+        assert_eq!(reorder.skipped_sequences(), 0);
+    }
+
+    #[test]
+    fn test_flows_are_independent() {
+        let mut reorder = buffer(16);
+        let now = Instant::now();
+
+        reorder.push(message(1, 0), now);
+        reorder.push(message(2, 0), now);
+        assert!(reorder.push(message(1, 2), now).is_empty());
+
+        // A gap in flow 1 does not hold back flow 2
+        assert_eq!(sequences(&reorder.push(message(2, 1), now)), vec![1]);
+        assert_eq!(reorder.flow_count(), 2);
+    }
+
+    #[test]
+    fn test_gap_timeout_releases_later_messages() {
+        let mut reorder = buffer(16);
+        let start = Instant::now();
+
+        reorder.push(message(1, 0), start);
+        reorder.push(message(1, 2), start);
+        reorder.push(message(1, 3), start + Duration::from_secs(1));
+        reorder.push(message(1, 5), start + Duration::from_secs(1));
+
+        assert!(reorder
+            .release_stalled(start + Duration::from_secs(4))
+            .is_empty());
+
+        // Sequence 1 never arrives: 2 and 3 are released, 5 still waits on 4
+        let released = reorder.release_stalled(start + Duration::from_secs(5));
+        assert_eq!(sequences(&released), vec![2, 3]);
+        assert_eq!(reorder.skipped_sequences(), 1);
+
+        // The gap at 4 gets its own timeout
+        let later = start + Duration::from_secs(9);
+        assert!(reorder.release_stalled(later).is_empty());
+        let released = reorder.release_stalled(start + Duration::from_secs(10));
+        assert_eq!(sequences(&released), vec![5]);
+        assert_eq!(reorder.skipped_sequences(), 2);
+
+        // The missing message shows up after all and is passed straight through
+        assert_eq!(sequences(&reorder.push(message(1, 1), later)), vec![1]);
+        assert_eq!(reorder.late_messages(), 1);
+    }
+
+    #[test]
+    fn test_full_buffer_skips_gap() {
+        let mut reorder = buffer(2);
+        let now = Instant::now();
+
+        reorder.push(message(1, 0), now);
+        assert!(reorder.push(message(1, 2), now).is_empty());
+        assert!(reorder.push(message(1, 3), now).is_empty());
+        assert_eq!(sequences(&reorder.push(message(1, 4), now)), vec![2, 3, 4]);
+        assert_eq!(reorder.skipped_sequences(), 1);
+        assert_eq!(reorder.buffered_len(), 0);
+    }
+
+    #[test]
+    fn test_sequence_wraparound() {
+        let mut reorder = buffer(16);
+        let now = Instant::now();
+
+        reorder.push(message(1, u32::MAX - 1), now);
+        assert!(reorder.push(message(1, 0), now).is_empty());
+        assert_eq!(
+            sequences(&reorder.push(message(1, u32::MAX), now)),
+            vec![u32::MAX, 0]
+        );
+    }
+
+    #[test]
+    fn test_prune_idle_flows() {
+        let mut reorder = buffer(16);
+        let start = Instant::now();
+
+        reorder.push(message(1, 0), start);
+        reorder.push(message(2, 0), start);
+        reorder.push(message(2, 2), start);
+
+        reorder.prune_idle(start + DEFAULT_FLOW_IDLE_TIMEOUT);
+        // Flow 2 still holds a message
+        assert_eq!(reorder.flow_count(), 1);
+    }
+}
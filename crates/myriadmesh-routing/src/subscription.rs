@@ -0,0 +1,255 @@
+//! Content-tag subscriptions
+//!
+//! A node registers the tags it is interested in, optionally with `*`
+//! wildcards (`media:*`, `*:emergency`, `*`), and the router drops tagged
+//! messages that match none of them before spending bandwidth on delivery
+//! or forwarding.
+//!
+//! Patterns are stored in a character trie where `*` is a dedicated edge
+//! matching any run of characters, so a tag is checked against every
+//! pattern in one walk instead of one comparison per pattern.
+//!
+//! Each wildcard makes matching try every split of the remaining tag, so
+//! the cost grows with the tag length raised to the number of wildcards.
+//! Patterns are limited to [`MAX_TAG_WILDCARDS`] to keep that bounded.
+
+use std::collections::{BTreeSet, HashMap};
+
+use myriadmesh_protocol::ContentTag;
+
+use crate::RoutingError;
+
+/// Wildcard matching any (possibly empty) run of characters
+pub const TAG_WILDCARD: char = '*';
+
+/// Maximum wildcards in one pattern (`**` counts once)
+pub const MAX_TAG_WILDCARDS: usize = 2;
+
+/// Number of wildcards in `pattern`, counting each run of `*` once
+fn wildcard_count(pattern: &str) -> usize {
+    let mut count = 0;
+    let mut previous = None;
+    for c in pattern.chars() {
+        if c == TAG_WILDCARD && previous != Some(TAG_WILDCARD) {
+            count += 1;
+        }
+        previous = Some(c);
+    }
+    count
+}
+
+#[derive(Debug, Default, Clone)]
+struct TrieNode {
+    children: HashMap<char, TrieNode>,
+    wildcard: Option<Box<TrieNode>>,
+    /// A pattern ends here
+    terminal: bool,
+}
+
+impl TrieNode {
+    fn insert(&mut self, pattern: &str) {
+        let mut node = self;
+        let mut previous = None;
+        for c in pattern.chars() {
+            node = if c == TAG_WILDCARD {
+                // `**` matches the same tags as `*`
+                if previous == Some(TAG_WILDCARD) {
+                    continue;
+                }
+                node.wildcard.get_or_insert_with(Default::default)
+            } else {
+                node.children.entry(c).or_default()
+            };
+            previous = Some(c);
+        }
+        node.terminal = true;
+    }
+
+    fn matches(&self, tag: &str) -> bool {
+        if let Some(wildcard) = &self.wildcard {
+            // Let the wildcard consume every possible prefix of the rest
+            let consumed = tag.char_indices().map(|(i, _)| i).chain([tag.len()]);
+            for i in consumed {
+                if wildcard.matches(&tag[i..]) {
+                    return true;
+                }
+            }
+        }
+
+        let mut chars = tag.chars();
+        match chars.next() {
+            None => self.terminal,
+            Some(c) => self
+                .children
+                .get(&c)
+                .is_some_and(|child| child.matches(chars.as_str())),
+        }
+    }
+}
+
+/// Set of tag patterns a node is subscribed to
+///
+/// With no subscriptions nothing is filtered. Untagged messages always pass,
+/// since there is nothing to match them on.
+#[derive(Debug, Default, Clone)]
+pub struct TagSubscriptions {
+    patterns: BTreeSet<String>,
+    trie: TrieNode,
+}
+
+impl TagSubscriptions {
+    /// Create an empty subscription set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe to tags matching `pattern`
+    ///
+    /// Returns false if already subscribed. Fails if the pattern has more
+    /// than [`MAX_TAG_WILDCARDS`] wildcards.
+    pub fn subscribe(&mut self, pattern: ContentTag) -> Result<bool, RoutingError> {
+        let wildcards = wildcard_count(pattern.as_str());
+        if wildcards > MAX_TAG_WILDCARDS {
+            return Err(RoutingError::InvalidTagPattern(format!(
+                "{} has {} wildcards (max {})",
+                pattern, wildcards, MAX_TAG_WILDCARDS
+            )));
+        }
+        if !self.patterns.insert(pattern.as_str().to_string()) {
+            return Ok(false);
+        }
+        self.trie.insert(pattern.as_str());
+        Ok(true)
+    }
+
+    /// Remove a subscription, returning whether it existed
+    pub fn unsubscribe(&mut self, pattern: &ContentTag) -> bool {
+        if !self.patterns.remove(pattern.as_str()) {
+            return false;
+        }
+        // Unsubscribing is rare; rebuilding keeps the trie free of dead branches
+        self.trie = TrieNode::default();
+        for pattern in &self.patterns {
+            self.trie.insert(pattern);
+        }
+        true
+    }
+
+    /// Subscribed patterns, sorted
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.patterns.iter().map(String::as_str)
+    }
+
+    /// Number of subscribed patterns
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Whether there are no subscriptions (filtering disabled)
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether `tag` matches any subscribed pattern
+    pub fn matches(&self, tag: &ContentTag) -> bool {
+        self.trie.matches(tag.as_str())
+    }
+
+    /// Whether a message carrying `tags` should be delivered or forwarded
+    pub fn accepts(&self, tags: &[ContentTag]) -> bool {
+        self.is_empty() || tags.is_empty() || tags.iter().any(|tag| self.matches(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(name: &str) -> ContentTag {
+        ContentTag::new(name).unwrap()
+    }
+
+    fn subscriptions(patterns: &[&str]) -> TagSubscriptions {
+        let mut subscriptions = TagSubscriptions::new();
+        for pattern in patterns {
+            subscriptions.subscribe(tag(pattern)).unwrap();
+        }
+        subscriptions
+    }
+
+    #[test]
+    fn test_exact_match() {
+        let subs = subscriptions(&["educational", "media:image"]);
+
+        assert!(subs.matches(&tag("educational")));
+        assert!(subs.matches(&tag("media:image")));
+        assert!(!subs.matches(&tag("media:video")));
+        assert!(!subs.matches(&tag("media")));
+        assert!(!subs.matches(&tag("educational:extra")));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        let subs = subscriptions(&["media:*", "*:emergency", "size:*ll"]);
+
+        assert!(subs.matches(&tag("media:image")));
+        assert!(subs.matches(&tag("media:")));
+        assert!(subs.matches(&tag("priority:emergency")));
+        assert!(subs.matches(&tag(":emergency")));
+        assert!(subs.matches(&tag("size:small")));
+        assert!(!subs.matches(&tag("size:large")));
+        assert!(!subs.matches(&tag("mediaimage")));
+        assert!(!subs.matches(&tag("priority:high")));
+
+        let everything = subscriptions(&["**"]);
+        assert!(everything.matches(&tag("anything:at-all")));
+    }
+
+    #[test]
+    fn test_delivery_decisions() {
+        let subs = subscriptions(&["media:*", "educational"]);
+
+        // Exact and wildcard matches are accepted; one matching tag suffices
+        assert!(subs.accepts(&[tag("educational")]));
+        assert!(subs.accepts(&[tag("commercial"), tag("media:audio")]));
+
+        // Non-matching tags are dropped
+        assert!(!subs.accepts(&[tag("commercial")]));
+        assert!(!subs.accepts(&[tag("nsfw"), tag("political")]));
+
+        // Untagged messages cannot be filtered
+        assert!(subs.accepts(&[]));
+
+        // No subscriptions: no filtering
+        assert!(TagSubscriptions::new().accepts(&[tag("commercial")]));
+    }
+
+    #[test]
+    fn test_wildcards_limited() {
+        let mut subs = TagSubscriptions::new();
+        assert!(subs.subscribe(tag("*:*")).unwrap());
+        assert!(subs.subscribe(tag("a**b*")).unwrap());
+        assert!(matches!(
+            subs.subscribe(tag("*a*b*")),
+            Err(RoutingError::InvalidTagPattern(_))
+        ));
+        assert_eq!(subs.len(), 2);
+
+        // A worst-case tag against the allowed patterns stays cheap
+        let long = tag(&"a".repeat(32));
+        assert!(!subs.matches(&long));
+    }
+
+    #[test]
+    fn test_unsubscribe() {
+        let mut subs = subscriptions(&["media:*", "media:image"]);
+        assert!(!subs.subscribe(tag("media:*")).unwrap());
+        assert_eq!(subs.len(), 2);
+
+        assert!(subs.unsubscribe(&tag("media:*")));
+        assert!(!subs.unsubscribe(&tag("media:*")));
+        assert!(subs.matches(&tag("media:image")));
+        assert!(!subs.matches(&tag("media:video")));
+        assert_eq!(subs.patterns().collect::<Vec<_>>(), vec!["media:image"]);
+    }
+}
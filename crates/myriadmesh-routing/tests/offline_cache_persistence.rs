@@ -0,0 +1,79 @@
+//! Integration tests for the persistent offline message cache
+
+use myriadmesh_protocol::types::{Priority, NODE_ID_SIZE};
+use myriadmesh_protocol::{Message, MessageType, NodeId};
+use myriadmesh_routing::{OfflineCacheConfig, OfflineMessageCache};
+use tempfile::TempDir;
+
+fn create_test_message(destination: NodeId, payload: &[u8]) -> Message {
+    Message::new(
+        NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+        destination,
+        MessageType::Data,
+        payload.to_vec(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_cached_messages_survive_restart() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("offline_cache.bin");
+    let alice = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+    let bob = NodeId::from_bytes([3u8; NODE_ID_SIZE]);
+
+    {
+        let mut cache =
+            OfflineMessageCache::with_config(OfflineCacheConfig::persistent(&path)).unwrap();
+        cache
+            .cache_message(
+                alice,
+                create_test_message(alice, b"hello alice"),
+                Priority::normal(),
+            )
+            .unwrap();
+        cache
+            .cache_message(
+                alice,
+                create_test_message(alice, b"urgent"),
+                Priority::emergency(),
+            )
+            .unwrap();
+        cache
+            .cache_message(bob, create_test_message(bob, b"hello bob"), Priority::low())
+            .unwrap();
+
+        // Bob comes online before the restart
+        assert_eq!(cache.retrieve_messages(&bob).len(), 1);
+    }
+
+    // Recreate the cache pointing at the same snapshot
+    let mut cache =
+        OfflineMessageCache::with_config(OfflineCacheConfig::persistent(&path)).unwrap();
+    assert_eq!(cache.stats().recovered_from_disk, 2);
+    assert_eq!(cache.message_count(&alice), 2);
+    assert!(!cache.has_messages(&bob));
+
+    // Delivery still works, in priority order
+    let messages = cache.retrieve_messages(&alice);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].payload, b"urgent");
+    assert_eq!(messages[1].payload, b"hello alice");
+
+    // Delivered messages are not recovered again
+    drop(cache);
+    let cache = OfflineMessageCache::with_config(OfflineCacheConfig::persistent(&path)).unwrap();
+    assert_eq!(cache.stats().recovered_from_disk, 0);
+}
+
+#[test]
+fn test_memory_only_cache_has_no_snapshot() {
+    let mut cache = OfflineMessageCache::with_config(OfflineCacheConfig::default()).unwrap();
+    let alice = NodeId::from_bytes([2u8; NODE_ID_SIZE]);
+
+    cache
+        .cache_message(alice, create_test_message(alice, b"hi"), Priority::normal())
+        .unwrap();
+    assert!(cache.flush().is_ok());
+    assert_eq!(cache.stats().recovered_from_disk, 0);
+}
@@ -21,8 +21,12 @@ impl ApiClient {
         }
     }
 
+    /// API base URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Check node health
-    #[allow(dead_code)]
     pub async fn health(&self) -> Result<HealthResponse> {
         let url = format!("{}/health", self.base_url);
         let response = self
@@ -98,9 +102,8 @@ impl ApiClient {
     }
 
     /// Send a message
-    #[allow(dead_code)]
     pub async fn send_message(&self, request: SendMessageRequest) -> Result<SendMessageResponse> {
-        let url = format!("{}/api/v1/messages/send", self.base_url);
+        let url = format!("{}/api/v1/messages", self.base_url);
         let response = self
             .client
             .post(&url)
@@ -108,6 +111,8 @@ impl ApiClient {
             .send()
             .await
             .context("Failed to send message")?
+            .error_for_status()
+            .context("Node rejected message")?
             .json()
             .await
             .context("Failed to parse send response")?;
@@ -188,11 +193,25 @@ impl ApiClient {
             .context("Failed to parse i2p tunnels")?;
         Ok(response)
     }
+
+    /// Get active onion routes
+    pub async fn onion_routes(&self) -> Result<Vec<OnionRouteInfo>> {
+        let url = format!("{}/api/i2p/routes", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to get onion routes")?
+            .json()
+            .await
+            .context("Failed to parse onion routes")?;
+        Ok(response)
+    }
 }
 
 // Response types
 
-#[allow(dead_code)]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct HealthResponse {
     pub status: String,
@@ -257,15 +276,13 @@ pub struct Message {
     pub status: String,
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct SendMessageRequest {
     pub destination: String,
-    pub content: String,
-    pub priority: String,
+    pub payload: String,
+    pub priority: Option<u8>,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SendMessageResponse {
     pub message_id: String,
@@ -318,3 +335,13 @@ pub struct I2pTunnelInfo {
     pub bandwidth_bps: u64,
     pub status: String,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OnionRouteInfo {
+    pub route_id: u64,
+    pub destination: String,
+    pub hops: Vec<String>,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub use_count: u64,
+}
@@ -2,8 +2,11 @@
 
 use crate::api_client::{
     AdapterInfo, ApiClient, DhtNode, HeartbeatStats, I2pDestination, I2pStatus, I2pTunnels,
-    Message, NodeInfo, NodeStatus,
+    Message, NodeInfo, NodeStatus, OnionRouteInfo, SendMessageRequest,
 };
+use crate::compose::ComposeForm;
+use crate::log_filter::LogView;
+use crate::settings::SettingsForm;
 use anyhow::Result;
 
 /// Active view in the TUI
@@ -12,6 +15,7 @@ pub enum View {
     Dashboard,
     Messages,
     I2p,
+    Routes,
     Logs,
     Help,
 }
@@ -22,7 +26,8 @@ impl View {
         match self {
             View::Dashboard => View::Messages,
             View::Messages => View::I2p,
-            View::I2p => View::Logs,
+            View::I2p => View::Routes,
+            View::Routes => View::Logs,
             View::Logs => View::Dashboard,
             View::Help => View::Dashboard,
         }
@@ -34,7 +39,8 @@ impl View {
             View::Dashboard => View::Logs,
             View::Messages => View::Dashboard,
             View::I2p => View::Messages,
-            View::Logs => View::I2p,
+            View::Routes => View::I2p,
+            View::Logs => View::Routes,
             View::Help => View::Dashboard,
         }
     }
@@ -45,6 +51,7 @@ impl View {
             View::Dashboard => "Dashboard",
             View::Messages => "Messages",
             View::I2p => "I2P Network",
+            View::Routes => "Routes",
             View::Logs => "Logs",
             View::Help => "Help",
         }
@@ -77,22 +84,30 @@ pub struct App {
     pub i2p_destination: Option<I2pDestination>,
     /// I2P tunnels
     pub i2p_tunnels: Option<I2pTunnels>,
+    /// Active onion routes
+    pub onion_routes: Vec<OnionRouteInfo>,
     /// Error message
     pub error: Option<String>,
+    /// Status notice (e.g. message sent)
+    pub notice: Option<String>,
     /// Loading state
     pub is_loading: bool,
-    /// Message input buffer
-    pub message_input: String,
-    /// Message destination input
-    pub message_destination: String,
+    /// Message composition form
+    pub compose: ComposeForm,
+    /// Settings overlay
+    pub settings: SettingsForm,
     /// Selected message index
     pub selected_message: usize,
     /// Selected adapter index
     pub selected_adapter: usize,
+    /// Selected onion route index
+    pub selected_route: usize,
     /// Log buffer
     pub logs: Vec<LogEntry>,
     /// Log follow mode
     pub log_follow: bool,
+    /// Log filter and search state
+    pub log_view: LogView,
 }
 
 #[derive(Debug, Clone)]
@@ -118,14 +133,18 @@ impl App {
             i2p_status: None,
             i2p_destination: None,
             i2p_tunnels: None,
+            onion_routes: Vec::new(),
             error: None,
+            notice: None,
             is_loading: false,
-            message_input: String::new(),
-            message_destination: String::new(),
+            compose: ComposeForm::default(),
+            settings: SettingsForm::default(),
             selected_message: 0,
             selected_adapter: 0,
+            selected_route: 0,
             logs: Vec::new(),
             log_follow: true,
+            log_view: LogView::default(),
         }
     }
 
@@ -145,6 +164,7 @@ impl App {
             i2p_status,
             i2p_destination,
             i2p_tunnels,
+            onion_routes,
         ) = tokio::join!(
             self.api_client.node_info(),
             self.api_client.node_status(),
@@ -155,6 +175,7 @@ impl App {
             self.api_client.i2p_status(),
             self.api_client.i2p_destination(),
             self.api_client.i2p_tunnels(),
+            self.api_client.onion_routes(),
         );
 
         // Update state
@@ -167,11 +188,42 @@ impl App {
         self.i2p_status = i2p_status.ok();
         self.i2p_destination = i2p_destination.ok();
         self.i2p_tunnels = i2p_tunnels.ok();
+        self.onion_routes = onion_routes.unwrap_or_default();
+        if self.selected_route >= self.onion_routes.len() {
+            self.selected_route = self.onion_routes.len().saturating_sub(1);
+        }
 
         self.is_loading = false;
         Ok(())
     }
 
+    /// Switch to a different node API
+    ///
+    /// The new node must answer a health check; otherwise the current
+    /// connection is kept and the failure is reported. Returns whether the
+    /// switch happened.
+    pub async fn reconnect(&mut self, api_url: String) -> bool {
+        let client = ApiClient::new(api_url.clone());
+        if let Err(e) = client.health().await {
+            let error = format!("Failed to connect to {}: {:#}", api_url, e);
+            self.add_log("ERROR".to_string(), error.clone());
+            self.error = Some(error);
+            return false;
+        }
+
+        self.api_client = client;
+        self.selected_message = 0;
+        self.selected_adapter = 0;
+        self.selected_route = 0;
+        let notice = format!("Connected to {}", api_url);
+        self.add_log("INFO".to_string(), notice.clone());
+        if let Err(e) = self.refresh().await {
+            self.error = Some(e.to_string());
+        }
+        self.notice = Some(notice);
+        true
+    }
+
     /// Navigate to next view
     pub fn next_view(&mut self) {
         self.current_view = self.current_view.next();
@@ -210,6 +262,24 @@ impl App {
         }
     }
 
+    /// Send a composed message and report the outcome in the footer
+    pub async fn send_message(&mut self, request: SendMessageRequest) {
+        match self.api_client.send_message(request).await {
+            Ok(response) => {
+                let notice = format!("Message {} {}", response.message_id, response.status);
+                self.error = None;
+                self.add_log("INFO".to_string(), notice.clone());
+                self.notice = Some(notice);
+            }
+            Err(e) => {
+                let error = format!("Send failed: {:#}", e);
+                self.notice = None;
+                self.add_log("ERROR".to_string(), error.clone());
+                self.error = Some(error);
+            }
+        }
+    }
+
     /// Select next adapter
     pub fn next_adapter(&mut self) {
         if !self.adapters.is_empty() {
@@ -228,6 +298,24 @@ impl App {
         }
     }
 
+    /// Select next onion route
+    pub fn next_route(&mut self) {
+        if !self.onion_routes.is_empty() {
+            self.selected_route = (self.selected_route + 1) % self.onion_routes.len();
+        }
+    }
+
+    /// Select previous onion route
+    pub fn previous_route(&mut self) {
+        if !self.onion_routes.is_empty() {
+            self.selected_route = if self.selected_route > 0 {
+                self.selected_route - 1
+            } else {
+                self.onion_routes.len() - 1
+            };
+        }
+    }
+
     /// Toggle log follow mode
     pub fn toggle_log_follow(&mut self) {
         self.log_follow = !self.log_follow;
@@ -252,3 +340,23 @@ impl App {
         self.logs.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reconnect_failure_keeps_connection() {
+        let mut app = App::new("http://localhost:4000".to_string());
+
+        // Nothing listens on port 1
+        assert!(!app.reconnect("http://127.0.0.1:1".to_string()).await);
+        assert_eq!(app.api_client.base_url(), "http://localhost:4000");
+        assert!(app
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("Failed to connect to http://127.0.0.1:1"));
+        assert!(app.notice.is_none());
+    }
+}
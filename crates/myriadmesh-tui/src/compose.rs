@@ -0,0 +1,318 @@
+//! Message composition form
+
+use crate::api_client::SendMessageRequest;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Length of a hex-encoded NodeId (64 bytes)
+const NODE_ID_HEX_LEN: usize = 128;
+
+/// Form field with input focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComposeField {
+    Destination,
+    Priority,
+    Payload,
+}
+
+impl ComposeField {
+    fn next(self) -> Self {
+        match self {
+            ComposeField::Destination => ComposeField::Priority,
+            ComposeField::Priority => ComposeField::Payload,
+            ComposeField::Payload => ComposeField::Destination,
+        }
+    }
+
+    fn previous(self) -> Self {
+        match self {
+            ComposeField::Destination => ComposeField::Payload,
+            ComposeField::Priority => ComposeField::Destination,
+            ComposeField::Payload => ComposeField::Priority,
+        }
+    }
+}
+
+/// Message priority offered by the form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComposePriority {
+    Background,
+    Low,
+    Normal,
+    High,
+    Emergency,
+}
+
+impl ComposePriority {
+    const ALL: [ComposePriority; 5] = [
+        ComposePriority::Background,
+        ComposePriority::Low,
+        ComposePriority::Normal,
+        ComposePriority::High,
+        ComposePriority::Emergency,
+    ];
+
+    /// Priority level understood by the node API
+    pub fn level(self) -> u8 {
+        self as u8
+    }
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            ComposePriority::Background => "Background",
+            ComposePriority::Low => "Low",
+            ComposePriority::Normal => "Normal",
+            ComposePriority::High => "High",
+            ComposePriority::Emergency => "Emergency",
+        }
+    }
+
+    fn raise(self) -> Self {
+        Self::ALL[(self.level() as usize + 1).min(Self::ALL.len() - 1)]
+    }
+
+    fn lower(self) -> Self {
+        Self::ALL[(self.level() as usize).saturating_sub(1)]
+    }
+}
+
+/// Result of feeding a key to the form
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComposeAction {
+    /// Key consumed, nothing to do
+    None,
+    /// Form closed without sending
+    Cancelled,
+    /// Form validated; send this request
+    Submit(SendMessageRequest),
+}
+
+/// Message composition form state
+#[derive(Debug, Clone)]
+pub struct ComposeForm {
+    /// Form is open and receives key input
+    pub active: bool,
+    /// Focused field
+    pub focus: ComposeField,
+    /// Destination NodeId (hex)
+    pub destination: String,
+    /// Selected priority
+    pub priority: ComposePriority,
+    /// Message payload
+    pub payload: String,
+    /// Validation error from the last submit attempt
+    pub error: Option<String>,
+}
+
+impl Default for ComposeForm {
+    fn default() -> Self {
+        Self {
+            active: false,
+            focus: ComposeField::Destination,
+            destination: String::new(),
+            priority: ComposePriority::Normal,
+            payload: String::new(),
+            error: None,
+        }
+    }
+}
+
+impl ComposeForm {
+    /// Open an empty form
+    pub fn open(&mut self) {
+        *self = Self {
+            active: true,
+            ..Self::default()
+        };
+    }
+
+    /// Close the form, keeping its contents
+    pub fn close(&mut self) {
+        self.active = false;
+    }
+
+    /// Handle a key press while the form is open
+    pub fn handle_key(&mut self, key: KeyEvent) -> ComposeAction {
+        match key.code {
+            KeyCode::Esc => {
+                self.close();
+                return ComposeAction::Cancelled;
+            }
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Tab | KeyCode::Down => self.focus = self.focus.next(),
+            KeyCode::BackTab | KeyCode::Up => self.focus = self.focus.previous(),
+            KeyCode::Left if self.focus == ComposeField::Priority => {
+                self.priority = self.priority.lower()
+            }
+            KeyCode::Right if self.focus == ComposeField::Priority => {
+                self.priority = self.priority.raise()
+            }
+            KeyCode::Backspace => {
+                if let Some(field) = self.focused_text() {
+                    field.pop();
+                }
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(field) = self.focused_text() {
+                    field.push(c);
+                }
+            }
+            _ => {}
+        }
+        ComposeAction::None
+    }
+
+    /// Validate the form and build the send request
+    pub fn validate(&self) -> Result<SendMessageRequest, String> {
+        let destination = self.destination.trim();
+        if destination.len() != NODE_ID_HEX_LEN {
+            return Err(format!(
+                "Destination must be a {}-character hex NodeId (got {} characters)",
+                NODE_ID_HEX_LEN,
+                destination.len()
+            ));
+        }
+        if !destination.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("Destination must contain only hex digits".to_string());
+        }
+        if self.payload.is_empty() {
+            return Err("Message is empty".to_string());
+        }
+
+        Ok(SendMessageRequest {
+            destination: destination.to_ascii_lowercase(),
+            payload: self.payload.clone(),
+            priority: Some(self.priority.level()),
+        })
+    }
+
+    fn submit(&mut self) -> ComposeAction {
+        match self.validate() {
+            Ok(request) => {
+                self.error = None;
+                self.close();
+                ComposeAction::Submit(request)
+            }
+            Err(e) => {
+                self.error = Some(e);
+                ComposeAction::None
+            }
+        }
+    }
+
+    fn focused_text(&mut self) -> Option<&mut String> {
+        match self.focus {
+            ComposeField::Destination => Some(&mut self.destination),
+            ComposeField::Payload => Some(&mut self.payload),
+            ComposeField::Priority => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(form: &mut ComposeForm, code: KeyCode) -> ComposeAction {
+        form.handle_key(KeyEvent::from(code))
+    }
+
+    fn type_text(form: &mut ComposeForm, text: &str) {
+        for c in text.chars() {
+            press(form, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_field_focus_cycles() {
+        let mut form = ComposeForm::default();
+        form.open();
+        assert_eq!(form.focus, ComposeField::Destination);
+
+        press(&mut form, KeyCode::Tab);
+        assert_eq!(form.focus, ComposeField::Priority);
+        press(&mut form, KeyCode::Tab);
+        assert_eq!(form.focus, ComposeField::Payload);
+        press(&mut form, KeyCode::Tab);
+        assert_eq!(form.focus, ComposeField::Destination);
+        press(&mut form, KeyCode::BackTab);
+        assert_eq!(form.focus, ComposeField::Payload);
+
+        // Typing goes to the focused field only
+        type_text(&mut form, "hi!");
+        press(&mut form, KeyCode::Backspace);
+        assert_eq!(form.payload, "hi");
+        assert!(form.destination.is_empty());
+
+        // Arrows change priority only while it is focused
+        press(&mut form, KeyCode::Up);
+        press(&mut form, KeyCode::Right);
+        press(&mut form, KeyCode::Right);
+        press(&mut form, KeyCode::Right);
+        assert_eq!(form.priority, ComposePriority::Emergency);
+        press(&mut form, KeyCode::Left);
+        assert_eq!(form.priority, ComposePriority::High);
+        type_text(&mut form, "x");
+        assert_eq!(form.payload, "hi");
+    }
+
+    #[test]
+    fn test_malformed_destination_rejected() {
+        let mut form = ComposeForm::default();
+        form.open();
+        type_text(&mut form, "abc123");
+        press(&mut form, KeyCode::Tab);
+        press(&mut form, KeyCode::Tab);
+        type_text(&mut form, "hello");
+
+        assert_eq!(press(&mut form, KeyCode::Enter), ComposeAction::None);
+        assert!(form.active);
+        assert!(form.error.as_ref().unwrap().contains("128-character"));
+
+        // Right length, but not hex
+        form.destination = "z".repeat(NODE_ID_HEX_LEN);
+        assert_eq!(press(&mut form, KeyCode::Enter), ComposeAction::None);
+        assert!(form.error.as_ref().unwrap().contains("hex digits"));
+    }
+
+    #[test]
+    fn test_submit_builds_request() {
+        let mut form = ComposeForm::default();
+        form.open();
+        type_text(&mut form, &"AB".repeat(NODE_ID_HEX_LEN / 2));
+        press(&mut form, KeyCode::Tab);
+        press(&mut form, KeyCode::Right);
+        press(&mut form, KeyCode::Tab);
+
+        // Empty payload is rejected
+        assert_eq!(press(&mut form, KeyCode::Enter), ComposeAction::None);
+        assert_eq!(form.error.as_deref(), Some("Message is empty"));
+
+        type_text(&mut form, "status?");
+        let action = press(&mut form, KeyCode::Enter);
+        assert_eq!(
+            action,
+            ComposeAction::Submit(SendMessageRequest {
+                destination: "ab".repeat(NODE_ID_HEX_LEN / 2),
+                payload: "status?".to_string(),
+                priority: Some(ComposePriority::High.level()),
+            })
+        );
+        assert!(!form.active);
+        assert!(form.error.is_none());
+    }
+
+    #[test]
+    fn test_escape_cancels() {
+        let mut form = ComposeForm::default();
+        form.open();
+        type_text(&mut form, "abc");
+
+        assert_eq!(press(&mut form, KeyCode::Esc), ComposeAction::Cancelled);
+        assert!(!form.active);
+
+        // Reopening starts from a clean form
+        form.open();
+        assert!(form.destination.is_empty());
+    }
+}
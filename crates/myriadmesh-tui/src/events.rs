@@ -2,7 +2,7 @@
 
 use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
 use std::time::Duration;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, watch};
 use tokio::task::JoinHandle;
 
 /// TUI events
@@ -27,6 +27,8 @@ pub struct EventHandler {
     // RESOURCE M4: Task handle management for graceful shutdown
     #[allow(dead_code)]
     shutdown_tx: broadcast::Sender<()>,
+    /// Tick rate, watched by the tick task
+    tick_rate_tx: watch::Sender<Duration>,
     #[allow(dead_code)]
     keyboard_task: JoinHandle<()>,
     #[allow(dead_code)]
@@ -71,6 +73,7 @@ impl EventHandler {
         });
 
         // RESOURCE M4: Spawn tick event generator with shutdown handling
+        let (tick_rate_tx, mut tick_rate_rx) = watch::channel(tick_rate);
         let tick_tx = tx.clone();
         let tick_task = tokio::spawn(async move {
             let mut interval = tokio::time::interval(tick_rate);
@@ -79,6 +82,14 @@ impl EventHandler {
                     _ = shutdown_rx2.recv() => {
                         break;
                     }
+                    Ok(()) = tick_rate_rx.changed() => {
+                        // Restart the interval so the new rate applies immediately
+                        let rate = *tick_rate_rx.borrow_and_update();
+                        interval = tokio::time::interval_at(
+                            tokio::time::Instant::now() + rate,
+                            rate,
+                        );
+                    }
                     _ = interval.tick() => {
                         let _ = tick_tx.send(Event::Tick);
                     }
@@ -90,6 +101,7 @@ impl EventHandler {
             tx,
             rx,
             shutdown_tx,
+            tick_rate_tx,
             keyboard_task,
             tick_task,
         }
@@ -108,12 +120,30 @@ impl EventHandler {
         let _ = self.tick_task.await;
     }
 
+    /// Current tick rate
+    pub fn tick_rate(&self) -> Duration {
+        *self.tick_rate_tx.borrow()
+    }
+
+    /// Change the tick rate without restarting the handler
+    pub fn set_tick_rate(&self, tick_rate: Duration) {
+        self.tick_rate_tx.send_replace(tick_rate);
+    }
+
     /// Get the next event
     pub async fn next(&mut self) -> Option<Event> {
         self.rx.recv().await
     }
 }
 
+/// Check if key event interrupts the application (Ctrl+C)
+///
+/// Unlike [`should_quit`], this never matches a printable key, so it is the
+/// only way to quit while a text field is taking input.
+pub fn is_interrupt(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
 /// Check if key event should quit the application
 pub fn should_quit(key: &KeyEvent) -> bool {
     matches!(
@@ -129,3 +159,50 @@ pub fn should_quit(key: &KeyEvent) -> bool {
         }
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wait for the next tick, skipping other events
+    async fn next_tick(events: &mut EventHandler) {
+        while !matches!(events.next().await, Some(Event::Tick)) {}
+    }
+
+    #[test]
+    fn test_only_ctrl_c_interrupts() {
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+
+        assert!(should_quit(&q));
+        assert!(should_quit(&ctrl_c));
+        assert!(!is_interrupt(&q));
+        assert!(is_interrupt(&ctrl_c));
+        assert!(!is_interrupt(&KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::NONE
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_set_tick_rate_updates_handler() {
+        let mut events = EventHandler::new(Duration::from_secs(3600));
+        assert_eq!(events.tick_rate(), Duration::from_secs(3600));
+
+        // The first tick of an interval fires immediately
+        next_tick(&mut events).await;
+
+        events.set_tick_rate(Duration::from_millis(20));
+        assert_eq!(events.tick_rate(), Duration::from_millis(20));
+
+        // Without the change the next tick would be an hour away
+        tokio::time::timeout(Duration::from_secs(5), async {
+            next_tick(&mut events).await;
+            next_tick(&mut events).await;
+        })
+        .await
+        .expect("ticks at the new rate");
+
+        events.shutdown().await;
+    }
+}
@@ -0,0 +1,285 @@
+//! Log filtering and search
+
+use crate::app::LogEntry;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Log levels recognised in filter input
+const LEVELS: [&str; 5] = ["ERROR", "WARN", "INFO", "DEBUG", "TRACE"];
+
+/// Filter applied to the log buffer
+///
+/// Parsed from input such as `error timeout`: a word naming a log level
+/// selects that level, the remaining words must appear in the message.
+/// Both parts are case-insensitive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Required level (upper case)
+    pub level: Option<String>,
+    /// Required message substring (lower case)
+    pub text: String,
+}
+
+impl LogFilter {
+    /// Parse filter input
+    pub fn parse(input: &str) -> Self {
+        let mut level = None;
+        let mut words = Vec::new();
+
+        for word in input.split_whitespace() {
+            let upper = word.to_ascii_uppercase();
+            if level.is_none() && LEVELS.contains(&upper.as_str()) {
+                level = Some(upper);
+            } else {
+                words.push(word.to_lowercase());
+            }
+        }
+
+        Self {
+            level,
+            text: words.join(" "),
+        }
+    }
+
+    /// Check if the filter is empty
+    pub fn is_empty(&self) -> bool {
+        self.level.is_none() && self.text.is_empty()
+    }
+
+    /// Check if a log entry passes the filter
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(level) = &self.level {
+            if !entry.level.eq_ignore_ascii_case(level) {
+                return false;
+            }
+        }
+        self.text.is_empty() || entry.message.to_lowercase().contains(&self.text)
+    }
+}
+
+/// Input line being edited in the Logs view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogInput {
+    Filter,
+    Search,
+}
+
+/// Filter and search state of the Logs view
+#[derive(Debug, Clone, Default)]
+pub struct LogView {
+    /// Input line being edited, if any
+    pub input: Option<LogInput>,
+    /// Filter input text
+    pub filter_input: String,
+    /// Active filter
+    pub filter: LogFilter,
+    /// Search query (case-insensitive)
+    pub search: String,
+    /// Index into the search matches of the current match
+    pub current_match: usize,
+}
+
+impl LogView {
+    /// Start editing the filter
+    pub fn start_filter(&mut self) {
+        self.input = Some(LogInput::Filter);
+    }
+
+    /// Start a new search
+    pub fn start_search(&mut self) {
+        self.input = Some(LogInput::Search);
+        self.search.clear();
+        self.current_match = 0;
+    }
+
+    /// Check if an input line is being edited
+    pub fn editing(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// Handle a key while an input line is being edited
+    ///
+    /// Filters and searches apply as they are typed. Enter keeps the
+    /// result, Esc clears it.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let Some(input) = self.input else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Enter => self.input = None,
+            KeyCode::Esc => {
+                self.input = None;
+                match input {
+                    LogInput::Filter => self.filter_input.clear(),
+                    LogInput::Search => self.search.clear(),
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_text(input).pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.input_text(input).push(c);
+            }
+            _ => return,
+        }
+
+        match input {
+            LogInput::Filter => self.filter = LogFilter::parse(&self.filter_input),
+            LogInput::Search => self.current_match = 0,
+        }
+    }
+
+    /// Log entries passing the filter
+    pub fn visible<'a>(&self, logs: &'a [LogEntry]) -> Vec<&'a LogEntry> {
+        logs.iter().filter(|log| self.filter.matches(log)).collect()
+    }
+
+    /// Positions within the visible entries that match the search
+    pub fn search_matches(&self, visible: &[&LogEntry]) -> Vec<usize> {
+        if self.search.is_empty() {
+            return Vec::new();
+        }
+        let query = self.search.to_lowercase();
+        visible
+            .iter()
+            .enumerate()
+            .filter(|(_, log)| log.message.to_lowercase().contains(&query))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Position of the current search match within the visible entries
+    pub fn current_position(&self, logs: &[LogEntry]) -> Option<usize> {
+        let matches = self.search_matches(&self.visible(logs));
+        if matches.is_empty() {
+            return None;
+        }
+        Some(matches[self.current_match % matches.len()])
+    }
+
+    /// Jump to the next search match, wrapping to the first
+    pub fn next_match(&mut self, logs: &[LogEntry]) {
+        let count = self.search_matches(&self.visible(logs)).len();
+        if count > 0 {
+            self.current_match = (self.current_match % count + 1) % count;
+        }
+    }
+
+    /// Jump to the previous search match, wrapping to the last
+    pub fn previous_match(&mut self, logs: &[LogEntry]) {
+        let count = self.search_matches(&self.visible(logs)).len();
+        if count > 0 {
+            self.current_match = (self.current_match % count + count - 1) % count;
+        }
+    }
+
+    fn input_text(&mut self, input: LogInput) -> &mut String {
+        match input {
+            LogInput::Filter => &mut self.filter_input,
+            LogInput::Search => &mut self.search,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: chrono::Utc::now(),
+            level: level.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    fn sample_logs() -> Vec<LogEntry> {
+        vec![
+            entry("INFO", "Connected to MyriadNode"),
+            entry("ERROR", "Send failed: connection timeout"),
+            entry("INFO", "Refreshing data..."),
+            entry("ERROR", "Failed to connect: timeout"),
+            entry("ERROR", "Adapter lora crashed"),
+            entry("WARN", "Slow response: timeout approaching"),
+        ]
+    }
+
+    fn type_text(view: &mut LogView, text: &str) {
+        for c in text.chars() {
+            view.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_level_and_substring_filter() {
+        let logs = sample_logs();
+        let mut view = LogView::default();
+        assert_eq!(view.visible(&logs).len(), logs.len());
+
+        view.start_filter();
+        type_text(&mut view, "error");
+        assert_eq!(view.visible(&logs).len(), 3);
+
+        type_text(&mut view, " TimeOut");
+        view.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(!view.editing());
+        assert_eq!(
+            view.filter,
+            LogFilter {
+                level: Some("ERROR".to_string()),
+                text: "timeout".to_string(),
+            }
+        );
+
+        let visible = view.visible(&logs);
+        let messages: Vec<&str> = visible.iter().map(|l| l.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Send failed: connection timeout",
+                "Failed to connect: timeout"
+            ]
+        );
+
+        // Substring alone matches across levels
+        assert_eq!(LogFilter::parse("timeout").text, "timeout");
+        view.filter = LogFilter::parse("timeout");
+        assert_eq!(view.visible(&logs).len(), 3);
+
+        // Esc while editing clears the filter
+        view.start_filter();
+        view.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert!(view.filter.is_empty());
+        assert_eq!(view.visible(&logs).len(), logs.len());
+    }
+
+    #[test]
+    fn test_search_navigation_wraps() {
+        let logs = sample_logs();
+        let mut view = LogView::default();
+
+        view.start_search();
+        type_text(&mut view, "TIMEOUT");
+        view.handle_key(KeyEvent::from(KeyCode::Enter));
+
+        let visible = view.visible(&logs);
+        assert_eq!(view.search_matches(&visible), vec![1, 3, 5]);
+        assert_eq!(view.current_position(&logs), Some(1));
+
+        view.next_match(&logs);
+        assert_eq!(view.current_position(&logs), Some(3));
+        view.next_match(&logs);
+        assert_eq!(view.current_position(&logs), Some(5));
+        view.next_match(&logs);
+        assert_eq!(view.current_position(&logs), Some(1));
+
+        view.previous_match(&logs);
+        assert_eq!(view.current_position(&logs), Some(5));
+
+        // Search runs within the filtered set
+        view.filter = LogFilter::parse("warn");
+        assert_eq!(view.current_position(&logs), Some(0));
+        view.next_match(&logs);
+        assert_eq!(view.current_position(&logs), Some(0));
+    }
+}
@@ -4,12 +4,16 @@
 
 mod api_client;
 mod app;
+mod compose;
 mod events;
+mod log_filter;
+mod settings;
 mod ui;
 
 use anyhow::Result;
 use app::{App, View};
 use clap::Parser;
+use compose::ComposeAction;
 use crossterm::{
     event::KeyCode,
     execute,
@@ -17,6 +21,7 @@ use crossterm::{
 };
 use events::{Event, EventHandler};
 use ratatui::{backend::CrosstermBackend, Terminal};
+use settings::SettingsAction;
 use std::{io, time::Duration};
 use tracing::info;
 
@@ -101,11 +106,48 @@ async fn run_app<B: ratatui::backend::Backend>(
         if let Some(event) = events.next().await {
             match event {
                 Event::Key(key) => {
+                    // The settings overlay takes all input while open; only
+                    // Ctrl+C quits
+                    if app.settings.active && !events::is_interrupt(&key) {
+                        if let SettingsAction::Apply(change) = app.settings.handle_key(key) {
+                            if let Some(interval) = change.refresh_interval {
+                                events.set_tick_rate(interval);
+                                app.add_log(
+                                    "INFO".to_string(),
+                                    format!("Refresh interval set to {}s", interval.as_secs()),
+                                );
+                            }
+                            if let Some(api_url) = change.api_url {
+                                app.reconnect(api_url).await;
+                            }
+                        }
+                        continue;
+                    }
+
+                    // The log filter/search line takes all input while edited;
+                    // only Ctrl+C quits
+                    if app.log_view.editing() && !events::is_interrupt(&key) {
+                        app.log_view.handle_key(key);
+                        continue;
+                    }
+
+                    // The compose form takes all input while open, so a
+                    // typed 'q' is text; only Ctrl+C quits
+                    if app.compose.active && !events::is_interrupt(&key) {
+                        if let ComposeAction::Submit(request) = app.compose.handle_key(key) {
+                            app.send_message(request).await;
+                        }
+                        continue;
+                    }
+
                     // Global shortcuts
                     if events::should_quit(&key) {
                         app.quit();
                     } else if key.code == KeyCode::Char('?') {
                         app.show_help();
+                    } else if key.code == KeyCode::Char('o') {
+                        let api_url = app.api_client.base_url().to_string();
+                        app.settings.open(&api_url, events.tick_rate());
                     } else if key.code == KeyCode::Char('r') {
                         app.add_log("INFO".to_string(), "Refreshing data...".to_string());
                         if let Err(e) = app.refresh().await {
@@ -155,6 +197,12 @@ async fn handle_view_input(app: &mut App, key: crossterm::event::KeyEvent) {
         View::Messages => match key.code {
             KeyCode::Up => app.previous_message(),
             KeyCode::Down => app.next_message(),
+            KeyCode::Char('s') => app.compose.open(),
+            _ => {}
+        },
+        View::Routes => match key.code {
+            KeyCode::Up => app.previous_route(),
+            KeyCode::Down => app.next_route(),
             _ => {}
         },
         View::Logs => match key.code {
@@ -172,6 +220,10 @@ async fn handle_view_input(app: &mut App, key: crossterm::event::KeyEvent) {
                 app.clear_logs();
                 app.add_log("INFO".to_string(), "Logs cleared".to_string());
             }
+            KeyCode::Char('F') => app.log_view.start_filter(),
+            KeyCode::Char('/') => app.log_view.start_search(),
+            KeyCode::Char('n') => app.log_view.next_match(&app.logs),
+            KeyCode::Char('N') => app.log_view.previous_match(&app.logs),
             _ => {}
         },
         View::Help => {
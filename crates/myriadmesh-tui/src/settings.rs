@@ -0,0 +1,227 @@
+//! Runtime settings overlay
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::Duration;
+
+/// Longest refresh interval accepted (1 hour)
+const MAX_REFRESH_SECS: u64 = 3600;
+
+/// Settings field with input focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    ApiUrl,
+    RefreshInterval,
+}
+
+/// Changes to apply after the overlay is confirmed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingsChange {
+    /// New API URL, if it changed
+    pub api_url: Option<String>,
+    /// New refresh interval, if it changed
+    pub refresh_interval: Option<Duration>,
+}
+
+/// Result of feeding a key to the overlay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsAction {
+    /// Key consumed, nothing to do
+    None,
+    /// Overlay closed without changes
+    Cancelled,
+    /// Overlay confirmed with valid settings
+    Apply(SettingsChange),
+}
+
+/// Settings overlay state
+#[derive(Debug, Clone)]
+pub struct SettingsForm {
+    /// Overlay is open and receives key input
+    pub active: bool,
+    /// Focused field
+    pub focus: SettingsField,
+    /// API URL being edited
+    pub api_url: String,
+    /// Refresh interval being edited (seconds)
+    pub refresh_secs: String,
+    /// Validation error from the last apply attempt
+    pub error: Option<String>,
+    current_url: String,
+    current_interval: Duration,
+}
+
+impl Default for SettingsForm {
+    fn default() -> Self {
+        Self {
+            active: false,
+            focus: SettingsField::ApiUrl,
+            api_url: String::new(),
+            refresh_secs: String::new(),
+            error: None,
+            current_url: String::new(),
+            current_interval: Duration::ZERO,
+        }
+    }
+}
+
+impl SettingsForm {
+    /// Open the overlay pre-filled with the current settings
+    pub fn open(&mut self, api_url: &str, refresh_interval: Duration) {
+        *self = Self {
+            active: true,
+            api_url: api_url.to_string(),
+            refresh_secs: refresh_interval.as_secs().to_string(),
+            current_url: api_url.to_string(),
+            current_interval: refresh_interval,
+            ..Self::default()
+        };
+    }
+
+    /// Handle a key press while the overlay is open
+    pub fn handle_key(&mut self, key: KeyEvent) -> SettingsAction {
+        match key.code {
+            KeyCode::Esc => {
+                self.active = false;
+                return SettingsAction::Cancelled;
+            }
+            KeyCode::Enter => return self.apply(),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.focus = match self.focus {
+                    SettingsField::ApiUrl => SettingsField::RefreshInterval,
+                    SettingsField::RefreshInterval => SettingsField::ApiUrl,
+                };
+            }
+            KeyCode::Backspace => {
+                self.focused_text().pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.focused_text().push(c);
+            }
+            _ => {}
+        }
+        SettingsAction::None
+    }
+
+    /// Validate the edited settings against the current ones
+    pub fn validate(&self) -> Result<SettingsChange, String> {
+        let api_url = self.api_url.trim().trim_end_matches('/');
+        if !(api_url.starts_with("http://") || api_url.starts_with("https://")) {
+            return Err("API URL must start with http:// or https://".to_string());
+        }
+
+        let secs: u64 = self
+            .refresh_secs
+            .trim()
+            .parse()
+            .map_err(|_| "Refresh interval must be a whole number of seconds".to_string())?;
+        if !(1..=MAX_REFRESH_SECS).contains(&secs) {
+            return Err(format!(
+                "Refresh interval must be between 1 and {} seconds",
+                MAX_REFRESH_SECS
+            ));
+        }
+        let interval = Duration::from_secs(secs);
+
+        Ok(SettingsChange {
+            api_url: (api_url != self.current_url).then(|| api_url.to_string()),
+            refresh_interval: (interval != self.current_interval).then_some(interval),
+        })
+    }
+
+    fn apply(&mut self) -> SettingsAction {
+        match self.validate() {
+            Ok(change) => {
+                self.error = None;
+                self.active = false;
+                SettingsAction::Apply(change)
+            }
+            Err(e) => {
+                self.error = Some(e);
+                SettingsAction::None
+            }
+        }
+    }
+
+    fn focused_text(&mut self) -> &mut String {
+        match self.focus {
+            SettingsField::ApiUrl => &mut self.api_url,
+            SettingsField::RefreshInterval => &mut self.refresh_secs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(form: &mut SettingsForm, code: KeyCode) -> SettingsAction {
+        form.handle_key(KeyEvent::from(code))
+    }
+
+    fn open_form() -> SettingsForm {
+        let mut form = SettingsForm::default();
+        form.open("http://localhost:4000", Duration::from_secs(2));
+        form
+    }
+
+    #[test]
+    fn test_unchanged_settings_apply_nothing() {
+        let mut form = open_form();
+        assert_eq!(form.refresh_secs, "2");
+
+        assert_eq!(
+            press(&mut form, KeyCode::Enter),
+            SettingsAction::Apply(SettingsChange {
+                api_url: None,
+                refresh_interval: None,
+            })
+        );
+        assert!(!form.active);
+    }
+
+    #[test]
+    fn test_edit_both_settings() {
+        let mut form = open_form();
+        for _ in 0.."localhost:4000".len() {
+            press(&mut form, KeyCode::Backspace);
+        }
+        for c in "node2:4000/".chars() {
+            press(&mut form, KeyCode::Char(c));
+        }
+        press(&mut form, KeyCode::Tab);
+        assert_eq!(form.focus, SettingsField::RefreshInterval);
+        press(&mut form, KeyCode::Backspace);
+        press(&mut form, KeyCode::Char('5'));
+
+        assert_eq!(
+            press(&mut form, KeyCode::Enter),
+            SettingsAction::Apply(SettingsChange {
+                api_url: Some("http://node2:4000".to_string()),
+                refresh_interval: Some(Duration::from_secs(5)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_settings_keep_overlay_open() {
+        let mut form = open_form();
+        press(&mut form, KeyCode::Tab);
+        press(&mut form, KeyCode::Backspace);
+        press(&mut form, KeyCode::Char('0'));
+        assert_eq!(press(&mut form, KeyCode::Enter), SettingsAction::None);
+        assert!(form.active);
+        assert!(form.error.as_ref().unwrap().contains("between 1 and"));
+
+        form.refresh_secs = "soon".to_string();
+        press(&mut form, KeyCode::Enter);
+        assert!(form.error.as_ref().unwrap().contains("whole number"));
+
+        form.refresh_secs = "10".to_string();
+        form.api_url = "localhost:4000".to_string();
+        press(&mut form, KeyCode::Enter);
+        assert!(form.error.as_ref().unwrap().contains("http://"));
+
+        assert_eq!(press(&mut form, KeyCode::Esc), SettingsAction::Cancelled);
+        assert!(!form.active);
+    }
+}
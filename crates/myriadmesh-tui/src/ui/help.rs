@@ -36,6 +36,10 @@ pub fn render(f: &mut Frame, _app: &App, area: Rect) {
             Span::styled("  ?                 ", Style::default().fg(Color::Yellow)),
             Span::raw("Show this help screen"),
         ]),
+        Line::from(vec![
+            Span::styled("  o                 ", Style::default().fg(Color::Yellow)),
+            Span::raw("Settings (API URL, refresh interval)"),
+        ]),
         Line::from(vec![
             Span::styled("  q / Ctrl+C        ", Style::default().fg(Color::Yellow)),
             Span::raw("Quit application"),
@@ -66,7 +70,19 @@ pub fn render(f: &mut Frame, _app: &App, area: Rect) {
         ]),
         Line::from(vec![
             Span::styled("  s                 ", Style::default().fg(Color::Yellow)),
-            Span::raw("Send new message (coming soon)"),
+            Span::raw("Compose message (Tab: next field, ←/→: priority, Enter: send, Esc: cancel)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Routes View",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  ↑ / ↓             ", Style::default().fg(Color::Yellow)),
+            Span::raw("Navigate onion routes"),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
@@ -84,6 +100,18 @@ pub fn render(f: &mut Frame, _app: &App, area: Rect) {
             Span::styled("  c                 ", Style::default().fg(Color::Yellow)),
             Span::raw("Clear logs"),
         ]),
+        Line::from(vec![
+            Span::styled("  F                 ", Style::default().fg(Color::Yellow)),
+            Span::raw("Filter by level and text (e.g. \"error timeout\")"),
+        ]),
+        Line::from(vec![
+            Span::styled("  /                 ", Style::default().fg(Color::Yellow)),
+            Span::raw("Search logs"),
+        ]),
+        Line::from(vec![
+            Span::styled("  n / N             ", Style::default().fg(Color::Yellow)),
+            Span::raw("Next / previous match"),
+        ]),
         Line::from(""),
         Line::from(""),
         Line::from(vec![Span::styled(
@@ -1,38 +1,77 @@
 //! Logs view - Real-time log viewer
 
 use crate::app::App;
+use crate::log_filter::LogInput;
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 /// Render logs view
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
-    let title = if app.log_follow {
-        "Logs (Following)"
+    let view = &app.log_view;
+
+    // Reserve a line for the filter/search input while it is edited
+    let (list_area, input_area) = if view.editing() {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
     } else {
-        "Logs (Paused)"
+        (area, None)
     };
 
+    let mut title = if app.log_follow {
+        "Logs (Following)".to_string()
+    } else {
+        "Logs (Paused)".to_string()
+    };
+    if !view.filter.is_empty() {
+        title.push_str(&format!(" [filter: {}]", view.filter_input.trim()));
+    }
+
     let block = Block::default().borders(Borders::ALL).title(title);
 
+    if let Some(input_area) = input_area {
+        let (prompt, text) = match view.input {
+            Some(LogInput::Filter) => ("Filter: ", &view.filter_input),
+            _ => ("Search: ", &view.search),
+        };
+        let line = Line::from(vec![
+            Span::styled(prompt, Style::default().fg(Color::Yellow)),
+            Span::raw(text.as_str()),
+        ]);
+        f.render_widget(Paragraph::new(line), input_area);
+    }
+
     if app.logs.is_empty() {
-        let text = ratatui::widgets::Paragraph::new(
-            "No logs available\n\nPress 'f' to toggle follow mode, 'c' to clear",
-        )
-        .block(block)
-        .style(Style::default().fg(Color::Gray));
-        f.render_widget(text, area);
+        let text =
+            Paragraph::new("No logs available\n\nPress 'f' to toggle follow mode, 'c' to clear")
+                .block(block)
+                .style(Style::default().fg(Color::Gray));
+        f.render_widget(text, list_area);
         return;
     }
 
-    let items: Vec<ListItem> = app
-        .logs
+    let visible = view.visible(&app.logs);
+    if visible.is_empty() {
+        let text = Paragraph::new("No logs match the filter\n\nPress 'F' then Esc to clear it")
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(text, list_area);
+        return;
+    }
+
+    let matches = view.search_matches(&visible);
+
+    let items: Vec<ListItem> = visible
         .iter()
-        .map(|log| {
+        .enumerate()
+        .map(|(i, log)| {
             let level_color = match log.level.as_str() {
                 "ERROR" => Color::Red,
                 "WARN" => Color::Yellow,
@@ -43,6 +82,13 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
             };
 
             let timestamp = log.timestamp.format("%H:%M:%S").to_string();
+            let message_style = if matches.binary_search(&i).is_ok() {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::UNDERLINED)
+            } else {
+                Style::default().fg(Color::White)
+            };
 
             let line = Line::from(vec![
                 Span::styled(timestamp, Style::default().fg(Color::Gray)),
@@ -54,14 +100,48 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(" "),
-                Span::styled(&log.message, Style::default().fg(Color::White)),
+                Span::styled(log.message.as_str(), message_style),
             ]);
 
             ListItem::new(line)
         })
         .collect();
 
-    let list = List::new(items).block(block);
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    // Scroll to the current match, or to the newest entries when following
+    let mut state = match view.current_position(&app.logs) {
+        Some(position) => ListState::default().with_selected(Some(position)),
+        None if app.log_follow => {
+            let rows = list_area.height.saturating_sub(2) as usize;
+            ListState::default().with_offset(visible.len().saturating_sub(rows))
+        }
+        None => ListState::default(),
+    };
+    f.render_stateful_widget(list, list_area, &mut state);
+}
 
-    f.render_widget(list, area);
+/// Footer summary of filter and search results
+pub fn match_summary(app: &App) -> String {
+    let view = &app.log_view;
+    let visible = view.visible(&app.logs);
+
+    let mut summary = format!("{}/{} logs", visible.len(), app.logs.len());
+    if !view.search.is_empty() {
+        let matches = view.search_matches(&visible);
+        if matches.is_empty() {
+            summary.push_str(", no matches");
+        } else {
+            summary.push_str(&format!(
+                ", match {}/{}",
+                view.current_match % matches.len() + 1,
+                matches.len()
+            ));
+        }
+    }
+    summary
 }
@@ -1,6 +1,7 @@
 //! Messages view - Message management
 
 use crate::app::App;
+use crate::compose::ComposeField;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -15,7 +16,7 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(10),   // Message list
-            Constraint::Length(5), // Send message form
+            Constraint::Length(6), // Send message form
         ])
         .split(area);
 
@@ -97,28 +98,63 @@ fn render_message_list(f: &mut Frame, app: &App, area: Rect) {
 
 /// Render send message form
 fn render_send_form(f: &mut Frame, app: &App, area: Rect) {
+    let form = &app.compose;
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("Send Message (Coming Soon)");
+        .title(if form.active {
+            "Send Message (Tab: next field, Enter: send, Esc: cancel)"
+        } else {
+            "Send Message"
+        });
 
-    let content = vec![
-        Line::from(vec![
-            Span::styled("To: ", Style::default().fg(Color::Gray)),
-            Span::styled(&app.message_destination, Style::default().fg(Color::Cyan)),
-        ]),
-        Line::from(vec![
-            Span::styled("Message: ", Style::default().fg(Color::Gray)),
-            Span::styled(&app.message_input, Style::default().fg(Color::White)),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled(
+    if !form.active {
+        let text = Paragraph::new(Line::from(Span::styled(
             "Press 's' to compose a new message",
             Style::default()
                 .fg(Color::Gray)
                 .add_modifier(Modifier::ITALIC),
-        )),
+        )))
+        .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let label = |field: ComposeField, text: &'static str| {
+        let style = if form.focus == field {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        Span::styled(text, style)
+    };
+
+    let mut content = vec![
+        Line::from(vec![
+            label(ComposeField::Destination, "To: "),
+            Span::styled(&form.destination, Style::default().fg(Color::Cyan)),
+        ]),
+        Line::from(vec![
+            label(ComposeField::Priority, "Priority: "),
+            Span::styled(
+                format!("< {} >", form.priority.name()),
+                Style::default().fg(Color::Magenta),
+            ),
+        ]),
+        Line::from(vec![
+            label(ComposeField::Payload, "Message: "),
+            Span::styled(&form.payload, Style::default().fg(Color::White)),
+        ]),
     ];
 
+    if let Some(error) = &form.error {
+        content.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
     let paragraph = Paragraph::new(content).block(block);
     f.render_widget(paragraph, area);
 }
@@ -5,6 +5,8 @@ pub mod help;
 pub mod i2p;
 pub mod logs;
 pub mod messages;
+pub mod routes;
+pub mod settings;
 
 use crate::app::{App, View};
 use ratatui::{
@@ -34,12 +36,17 @@ pub fn render(f: &mut Frame, app: &App) {
         View::Dashboard => dashboard::render(f, app, chunks[1]),
         View::Messages => messages::render(f, app, chunks[1]),
         View::I2p => i2p::render(f, app, chunks[1]),
+        View::Routes => routes::render(f, app, chunks[1]),
         View::Logs => logs::render(f, app, chunks[1]),
         View::Help => help::render(f, app, chunks[1]),
     }
 
     // Render footer
     render_footer(f, app, chunks[2]);
+
+    if app.settings.active {
+        settings::render(f, app, f.area());
+    }
 }
 
 /// Render header with navigation tabs
@@ -48,6 +55,7 @@ fn render_header(f: &mut Frame, app: &App, area: Rect) {
         View::Dashboard.title(),
         View::Messages.title(),
         View::I2p.title(),
+        View::Routes.title(),
         View::Logs.title(),
     ];
 
@@ -55,7 +63,8 @@ fn render_header(f: &mut Frame, app: &App, area: Rect) {
         View::Dashboard => 0,
         View::Messages => 1,
         View::I2p => 2,
-        View::Logs => 3,
+        View::Routes => 3,
+        View::Logs => 4,
         View::Help => 0,
     };
 
@@ -81,10 +90,16 @@ fn render_footer(f: &mut Frame, app: &App, area: Rect) {
     let mut footer_text = vec![
         Span::raw("Tab: Navigate | "),
         Span::raw("?: Help | "),
+        Span::raw("o: Settings | "),
         Span::raw("r: Refresh | "),
         Span::raw("q: Quit"),
     ];
 
+    if app.current_view == View::Logs {
+        footer_text.insert(0, Span::raw(logs::match_summary(app)));
+        footer_text.insert(1, Span::raw(" | "));
+    }
+
     // Add status indicator
     if app.is_loading {
         footer_text.insert(
@@ -94,6 +109,14 @@ fn render_footer(f: &mut Frame, app: &App, area: Rect) {
         footer_text.insert(1, Span::raw(" | "));
     }
 
+    if let Some(notice) = &app.notice {
+        footer_text.insert(
+            0,
+            Span::styled(format!(" {} ", notice), Style::default().fg(Color::Green)),
+        );
+        footer_text.insert(1, Span::raw(" | "));
+    }
+
     if let Some(error) = &app.error {
         footer_text.insert(
             0,
@@ -0,0 +1,249 @@
+//! Routes view - Active onion routes
+
+use crate::api_client::OnionRouteInfo;
+use crate::app::App;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Render routes view
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(60), // Route list
+            Constraint::Percentage(40), // Route details
+        ])
+        .split(area);
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    render_route_list(f, app, chunks[0], now);
+    render_route_details(f, app, chunks[1], now);
+}
+
+/// Render route list
+fn render_route_list(f: &mut Frame, app: &App, area: Rect, now: u64) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Onion Routes ({})", app.onion_routes.len()));
+
+    if app.onion_routes.is_empty() {
+        let text = Paragraph::new(vec![
+            Line::from("No active onion routes"),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Routes are built when private messages are sent",
+                Style::default().fg(Color::Gray),
+            )),
+        ])
+        .block(block);
+        f.render_widget(text, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .onion_routes
+        .iter()
+        .map(|route| {
+            let (expiry, expiry_color) = expiry_text(route, now);
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("#{}", route.route_id),
+                    Style::default().fg(Color::White),
+                ),
+                Span::raw(" → "),
+                Span::styled(
+                    short_id(&route.destination),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::raw(format!(
+                    " | {} hops | age {} | used {} | ",
+                    route.hops.len(),
+                    format_secs(now.saturating_sub(route.created_at)),
+                    route.use_count
+                )),
+                Span::styled(expiry, Style::default().fg(expiry_color)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let mut state = ListState::default().with_selected(Some(app.selected_route));
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+/// Render details of the selected route
+fn render_route_details(f: &mut Frame, app: &App, area: Rect, now: u64) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Route Details");
+
+    let Some(route) = app.onion_routes.get(app.selected_route) else {
+        let text = Paragraph::new("No route selected")
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(text, area);
+        return;
+    };
+
+    let (expiry, expiry_color) = expiry_text(route, now);
+
+    let mut content = vec![
+        Line::from(vec![
+            Span::styled("Route ID: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                route.route_id.to_string(),
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Destination: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                short_id(&route.destination),
+                Style::default().fg(Color::Magenta),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Age: ", Style::default().fg(Color::Gray)),
+            Span::raw(format_secs(now.saturating_sub(route.created_at))),
+        ]),
+        Line::from(vec![
+            Span::styled("Expiry: ", Style::default().fg(Color::Gray)),
+            Span::styled(expiry, Style::default().fg(expiry_color)),
+        ]),
+        Line::from(vec![
+            Span::styled("Uses: ", Style::default().fg(Color::Gray)),
+            Span::raw(route.use_count.to_string()),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!("Hops ({}):", route.hops.len()),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]),
+    ];
+
+    for (i, hop) in route.hops.iter().enumerate() {
+        content.push(Line::from(vec![
+            Span::styled(format!("  {}. ", i + 1), Style::default().fg(Color::Gray)),
+            Span::styled(short_id(hop), Style::default().fg(Color::Cyan)),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(content).block(block);
+    f.render_widget(paragraph, area);
+}
+
+/// Remaining lifetime of a route and its display color
+fn expiry_text(route: &OnionRouteInfo, now: u64) -> (String, Color) {
+    if route.expires_at <= now {
+        ("expired".to_string(), Color::Red)
+    } else {
+        let remaining = route.expires_at - now;
+        let color = if remaining < 60 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        (format!("expires in {}", format_secs(remaining)), color)
+    }
+}
+
+/// Shorten a node ID for display
+fn short_id(id: &str) -> String {
+    if id.len() > 16 {
+        format!("{}...", &id[..16])
+    } else {
+        id.to_string()
+    }
+}
+
+/// Format a duration in seconds compactly
+fn format_secs(seconds: u64) -> String {
+    if seconds >= 3600 {
+        format!("{}h {}m", seconds / 3600, (seconds % 3600) / 60)
+    } else if seconds >= 60 {
+        format!("{}m {}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn route(route_id: u64, hops: usize, use_count: u64) -> OnionRouteInfo {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        OnionRouteInfo {
+            route_id,
+            destination: format!("dest{:012}", route_id),
+            hops: (0..hops).map(|i| format!("hop{:013}", i)).collect(),
+            created_at: now - 90,
+            expires_at: now + 3600,
+            use_count,
+        }
+    }
+
+    fn draw(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|f| render(f, app, f.area())).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_routes_listed() {
+        let mut app = App::new("http://localhost:4000".to_string());
+        app.onion_routes = vec![route(11, 3, 5), route(22, 4, 0)];
+        app.selected_route = 1;
+
+        let screen = draw(&app);
+        assert!(screen.contains("Onion Routes (2)"));
+        assert!(screen.contains("#11 → dest000000000011 | 3 hops | age 1m"));
+        assert!(screen.contains("| used 5 | expires in"));
+        assert!(screen.contains("#22 → dest000000000022 | 4 hops"));
+
+        // The detail pane follows the selection
+        assert!(screen.contains("Route ID: 22"));
+        assert!(screen.contains("Hops (4):"));
+        assert!(screen.contains("4. hop0000000000003"));
+    }
+
+    #[test]
+    fn test_no_routes() {
+        let app = App::new("http://localhost:4000".to_string());
+
+        let screen = draw(&app);
+        assert!(screen.contains("Onion Routes (0)"));
+        assert!(screen.contains("No active onion routes"));
+        assert!(screen.contains("No route selected"));
+    }
+}
@@ -0,0 +1,67 @@
+//! Settings overlay - API URL and refresh interval
+
+use crate::app::App;
+use crate::settings::SettingsField;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render settings overlay centered over `area`
+pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let form = &app.settings;
+    let popup = centered(area, 70, 9);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Settings (Tab: next field, Enter: apply, Esc: cancel)");
+
+    let label = |field: SettingsField, text: &'static str| {
+        let style = if form.focus == field {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        Span::styled(text, style)
+    };
+
+    let mut content = vec![
+        Line::from(""),
+        Line::from(vec![
+            label(SettingsField::ApiUrl, "API URL: "),
+            Span::styled(&form.api_url, Style::default().fg(Color::Cyan)),
+        ]),
+        Line::from(vec![
+            label(SettingsField::RefreshInterval, "Refresh interval (s): "),
+            Span::styled(&form.refresh_secs, Style::default().fg(Color::Cyan)),
+        ]),
+        Line::from(""),
+    ];
+
+    if let Some(error) = &form.error {
+        content.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    f.render_widget(Clear, popup);
+    f.render_widget(Paragraph::new(content).block(block), popup);
+}
+
+/// Rectangle of at most `width` x `height` centered in `area`
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
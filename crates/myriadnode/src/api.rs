@@ -1,11 +1,13 @@
 use anyhow::Result;
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::net::TcpListener;
@@ -17,8 +19,10 @@ use crate::config::ApiConfig;
 use crate::failover::FailoverManager;
 use crate::heartbeat::HeartbeatService;
 use myriadmesh_appliance::{
-    types::DevicePreferences, ApplianceManager, CachedMessage, PairingRequest, PairingResponse,
+    types::{ApplianceError, DevicePreferences},
+    ApplianceManager, CachedMessage, PairingRequest, PairingResponse,
 };
+use myriadmesh_core::metrics::{render_prometheus, AdapterSnapshot, PROMETHEUS_CONTENT_TYPE};
 use myriadmesh_ledger::ChainSync;
 use myriadmesh_network::{AdapterManager, AdapterStatus as NetworkAdapterStatus};
 use myriadmesh_updates::UpdateCoordinator;
@@ -86,9 +90,13 @@ impl ApiServer {
         info!("API server listening on {}", bind_addr);
 
         let handle = tokio::spawn(async move {
-            axum::serve(listener, app)
-                .await
-                .map_err(|e| anyhow::anyhow!("Server error: {}", e))
+            // Peer addresses key per-client pairing lockout
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Server error: {}", e))
         });
 
         Ok(handle)
@@ -98,6 +106,8 @@ impl ApiServer {
         Router::new()
             // Health check
             .route("/health", get(health_check))
+            // Prometheus scrape endpoint
+            .route("/metrics", get(get_metrics))
             // Node endpoints (Web UI expects /api/ prefix)
             .route("/api/node/info", get(get_node_info))
             .route("/api/node/status", get(get_node_status))
@@ -119,6 +129,7 @@ impl ApiServer {
             .route("/api/i2p/status", get(get_i2p_status))
             .route("/api/i2p/destination", get(get_i2p_destination))
             .route("/api/i2p/tunnels", get(get_i2p_tunnels))
+            .route("/api/i2p/routes", get(get_onion_routes))
             // Update endpoints
             .route("/api/updates/schedule", post(schedule_update))
             .route("/api/updates/schedules", get(list_update_schedules))
@@ -191,6 +202,29 @@ struct HealthResponse {
     version: String,
 }
 
+// === Metrics ===
+
+async fn get_metrics(State(state): State<Arc<ApiState>>) -> impl IntoResponse {
+    let manager = state.adapter_manager.read().await;
+    let mut ids = manager.adapter_ids();
+    ids.sort();
+    let adapters: Vec<_> = ids
+        .iter()
+        .filter_map(|id| {
+            Some(AdapterSnapshot {
+                id,
+                adapter_type: manager.get_capabilities(id)?.adapter_type,
+                metrics: manager.get_metrics(id)?,
+            })
+        })
+        .collect();
+
+    // This node runs no router or onion router, so only adapters are exported
+    let body = render_prometheus(&adapters, None, None);
+
+    ([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body)
+}
+
 // === Node Endpoints ===
 
 async fn get_node_status(State(state): State<Arc<ApiState>>) -> Json<NodeStatusResponse> {
@@ -482,6 +516,7 @@ async fn get_adapter_status_internal(manager: &AdapterManager, id: &str) -> Opti
     let status_str = match network_status {
         NetworkAdapterStatus::Uninitialized => "uninitialized",
         NetworkAdapterStatus::Initializing => "initializing",
+        NetworkAdapterStatus::BuildingTunnels => "building_tunnels",
         NetworkAdapterStatus::Ready => "ready",
         NetworkAdapterStatus::Unavailable => "unavailable",
         NetworkAdapterStatus::Error => "error",
@@ -497,6 +532,7 @@ async fn get_adapter_status_internal(manager: &AdapterManager, id: &str) -> Opti
         reload_count: 0,
         reputation_score: 1.0,
         capabilities: vec![],
+        quarantined: manager.is_quarantined(id),
     })
 }
 
@@ -510,6 +546,8 @@ struct AdapterStatus {
     reload_count: u32,
     reputation_score: f64,
     capabilities: Vec<String>,
+    /// Excluded from selection after repeated send failures
+    quarantined: bool,
 }
 
 // === DHT Endpoints ===
@@ -641,6 +679,20 @@ async fn get_failover_events(
                 value: None,
                 threshold: None,
             },
+            FailoverEvent::FlapSuppressed {
+                current,
+                candidate,
+                reason,
+            } => FailoverEventResponse {
+                event_type: "flap_suppressed".to_string(),
+                adapter: None,
+                from_adapter: Some(current),
+                to_adapter: Some(candidate),
+                reason: Some(reason),
+                metric: None,
+                value: None,
+                threshold: None,
+            },
             FailoverEvent::AdapterRecovered { adapter } => FailoverEventResponse {
                 event_type: "adapter_recovered".to_string(),
                 adapter: Some(adapter),
@@ -746,6 +798,7 @@ async fn get_i2p_status(State(state): State<Arc<ApiState>>) -> Json<I2pStatusRes
             let (rs, as_str) = match status {
                 NetworkAdapterStatus::Ready => ("running", "ready"),
                 NetworkAdapterStatus::Initializing => ("starting", "initializing"),
+                NetworkAdapterStatus::BuildingTunnels => ("running", "building_tunnels"),
                 NetworkAdapterStatus::Unavailable => ("stopped", "unavailable"),
                 NetworkAdapterStatus::Error => ("error", "error"),
                 NetworkAdapterStatus::ShuttingDown => ("stopping", "shutting_down"),
@@ -826,6 +879,23 @@ struct I2pTunnelInfo {
     status: String,
 }
 
+/// Get active onion routes
+async fn get_onion_routes(State(_state): State<Arc<ApiState>>) -> Json<Vec<OnionRouteInfo>> {
+    // TODO: Get active routes from the onion router
+    Json(vec![])
+}
+
+#[derive(Serialize)]
+#[allow(dead_code)]
+struct OnionRouteInfo {
+    route_id: u64,
+    destination: String,
+    hops: Vec<String>,
+    created_at: u64,
+    expires_at: u64,
+    use_count: u64,
+}
+
 // === Appliance Endpoints ===
 
 /// Get appliance information and capabilities
@@ -864,9 +934,28 @@ async fn get_appliance_stats(
     }
 }
 
+/// HTTP status for a failed pairing step
+fn pairing_error_status(error: &ApplianceError) -> StatusCode {
+    match error {
+        ApplianceError::InvalidPin | ApplianceError::SignatureVerificationFailed => {
+            StatusCode::UNAUTHORIZED
+        }
+        ApplianceError::PairingLockedOut(_) => StatusCode::TOO_MANY_REQUESTS,
+        ApplianceError::InvalidPairingToken(_) | ApplianceError::PairingExpired => {
+            StatusCode::NOT_FOUND
+        }
+        ApplianceError::MaxDevicesReached(_) | ApplianceError::DeviceAlreadyPaired(_) => {
+            StatusCode::CONFLICT
+        }
+        ApplianceError::Configuration(_) | ApplianceError::Crypto(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
 /// Initiate device pairing
 async fn initiate_pairing(
     State(state): State<Arc<ApiState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
     Json(request): Json<serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let appliance_manager = state
@@ -878,11 +967,14 @@ async fn initiate_pairing(
     let pairing_request: PairingRequest =
         serde_json::from_value(request).map_err(|_| StatusCode::BAD_REQUEST)?;
 
-    match appliance_manager.initiate_pairing(pairing_request).await {
+    match appliance_manager
+        .initiate_pairing(pairing_request, peer.ip())
+        .await
+    {
         Ok(token) => Ok(Json(serde_json::json!(token))),
         Err(e) => {
-            tracing::error!("Pairing initiation failed: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            warn!("Pairing initiation from {} failed: {}", peer, e);
+            Err(pairing_error_status(&e))
         }
     }
 }
@@ -922,6 +1014,7 @@ async fn reject_pairing(
 /// Complete device pairing
 async fn complete_pairing(
     State(state): State<Arc<ApiState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
     Json(request): Json<serde_json::Value>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let appliance_manager = state
@@ -962,13 +1055,13 @@ async fn complete_pairing(
     let public_key = hex::decode(&public_key_hex).map_err(|_| StatusCode::BAD_REQUEST)?;
 
     match appliance_manager
-        .complete_pairing(pairing_response, device_id, node_id, public_key)
+        .complete_pairing(pairing_response, device_id, node_id, public_key, peer.ip())
         .await
     {
         Ok(result) => Ok(Json(serde_json::json!(result))),
         Err(e) => {
-            tracing::error!("Pairing completion failed: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            warn!("Pairing completion from {} failed: {}", peer, e);
+            Err(pairing_error_status(&e))
         }
     }
 }
@@ -1149,25 +1242,7 @@ struct ScheduleUpdateResponse {
 
 /// Parse adapter type from string (case-insensitive)
 fn parse_adapter_type(s: &str) -> Result<myriadmesh_protocol::types::AdapterType, StatusCode> {
-    use myriadmesh_protocol::types::AdapterType;
-
-    match s.to_lowercase().as_str() {
-        "ethernet" => Ok(AdapterType::Ethernet),
-        "bluetooth" => Ok(AdapterType::Bluetooth),
-        "bluetooth_le" | "bluetoothle" | "ble" => Ok(AdapterType::BluetoothLE),
-        "cellular" => Ok(AdapterType::Cellular),
-        "wifi_halow" | "wifihalow" | "halow" => Ok(AdapterType::WiFiHaLoW),
-        "lorawan" | "lora" => Ok(AdapterType::LoRaWAN),
-        "meshtastic" => Ok(AdapterType::Meshtastic),
-        "frsgmrs" | "frs" | "gmrs" => Ok(AdapterType::FRSGMRS),
-        "cbradio" | "cb" => Ok(AdapterType::CBRadio),
-        "shortwave" | "sw" => Ok(AdapterType::Shortwave),
-        "aprs" => Ok(AdapterType::APRS),
-        "dialup" => Ok(AdapterType::Dialup),
-        "pppoe" => Ok(AdapterType::PPPoE),
-        "i2p" => Ok(AdapterType::I2P),
-        _ => Err(StatusCode::BAD_REQUEST),
-    }
+    s.parse().map_err(|_| StatusCode::BAD_REQUEST)
 }
 
 /// Schedule an adapter update
@@ -37,154 +37,341 @@ impl Default for BackhaulConfig {
     }
 }
 
-/// Backhaul detector
-pub struct BackhaulDetector {
-    config: BackhaulConfig,
+/// Classification of a network interface by its role in the route table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceClass {
+    /// Carries a default route: uplink to the broader internet
+    Backhaul,
+    /// Routed, but only to local or link-local destinations: safe for mesh
+    Mesh,
+    /// Interface not present in the route table, routed via a gateway to
+    /// other networks, or the table is unavailable
+    Unknown,
 }
 
-impl BackhaulDetector {
-    pub fn new(config: BackhaulConfig) -> Self {
-        Self { config }
+impl From<InterfaceClass> for BackhaulStatus {
+    fn from(class: InterfaceClass) -> Self {
+        match class {
+            InterfaceClass::Backhaul => BackhaulStatus::IsBackhaul,
+            InterfaceClass::Mesh => BackhaulStatus::NotBackhaul,
+            InterfaceClass::Unknown => BackhaulStatus::Unknown,
+        }
     }
+}
 
-    /// Check if an interface is currently being used as backhaul
-    pub fn check_interface(&self, interface_name: &str) -> Result<BackhaulStatus> {
-        debug!("Checking backhaul status for interface: {}", interface_name);
+/// A single route, reduced to what classification needs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteEntry {
+    /// Destination as printed by the platform (e.g. "default", "10.0.0.0/24")
+    pub destination: String,
+    /// Next hop, if the route goes via a gateway
+    pub gateway: Option<IpAddr>,
+    /// Outgoing interface name
+    pub interface: String,
+}
 
-        // If user explicitly allows backhaul mesh, skip detection
-        if self.config.allow_backhaul_mesh {
-            debug!("Backhaul mesh explicitly allowed, returning NotBackhaul");
-            return Ok(BackhaulStatus::NotBackhaul);
-        }
+impl RouteEntry {
+    /// Check if this is an IPv4 or IPv6 default route
+    ///
+    /// Includes either half of a split default (0.0.0.0/1 + 128.0.0.0/1, or
+    /// ::/1 + 8000::/1), which VPNs install to override the real default.
+    pub fn is_default(&self) -> bool {
+        matches!(
+            self.destination.as_str(),
+            "default" | "0.0.0.0/0" | "0.0.0.0" | "::/0" | "::"
+        ) || self.is_split_default()
+    }
+
+    /// Check if this is one half of a split default route
+    fn is_split_default(&self) -> bool {
+        matches!(
+            self.destination.to_ascii_lowercase().as_str(),
+            // netstat abbreviates IPv4 destinations ("0/1", "128.0/1")
+            "0.0.0.0/1" | "0/1" | "128.0.0.0/1" | "128.0/1" | "128/1" | "::/1" | "8000::/1"
+        )
+    }
 
-        // Platform-specific detection
+    /// Check if the destination is link-local (169.254.0.0/16 or fe80::/10)
+    pub fn is_link_local(&self) -> bool {
+        let destination = self.destination.to_ascii_lowercase();
+        destination.starts_with("169.254") || destination.starts_with("fe80:")
+    }
+}
+
+/// Snapshot of the system route table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteTable {
+    pub routes: Vec<RouteEntry>,
+}
+
+impl RouteTable {
+    /// Read the route table using the platform's tooling
+    ///
+    /// Linux uses `ip route`, macOS `netstat -rn`, Windows `Get-NetRoute`.
+    pub fn from_system() -> Result<Self> {
         #[cfg(target_os = "linux")]
         {
-            self.check_interface_linux(interface_name)
+            let mut table = Self::parse_linux(&run_command("ip", &["-4", "route", "show"])?);
+            // IPv6 may be disabled; IPv4 routes alone still classify
+            if let Ok(output) = run_command("ip", &["-6", "route", "show"]) {
+                table.routes.extend(Self::parse_linux(&output).routes);
+            }
+            Ok(table)
         }
 
         #[cfg(target_os = "macos")]
         {
-            self.check_interface_macos(interface_name)
+            Ok(Self::parse_netstat(&run_command("netstat", &["-rn"])?))
         }
 
         #[cfg(target_os = "windows")]
         {
-            self.check_interface_windows(interface_name)
+            Ok(Self::parse_windows_csv(&run_command(
+                "powershell",
+                &[
+                    "-NoProfile",
+                    "-Command",
+                    "Get-NetRoute | Select-Object DestinationPrefix,NextHop,InterfaceAlias | ConvertTo-Csv -NoTypeInformation",
+                ],
+            )?))
         }
 
         #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
         {
-            warn!("Backhaul detection not implemented for this platform");
-            Ok(BackhaulStatus::Unknown)
+            anyhow::bail!("Route table inspection not implemented for this platform")
         }
     }
 
-    /// Check if a specific IP address is on an interface being used as backhaul
-    pub fn check_ip_address(&self, ip: IpAddr) -> Result<BackhaulStatus> {
-        debug!("Checking backhaul status for IP: {}", ip);
+    /// Parse `ip route show` output
+    ///
+    /// Format: "default via 192.168.1.1 dev wlan0 proto dhcp metric 600"
+    pub fn parse_linux(output: &str) -> Self {
+        let mut routes = Vec::new();
 
-        if self.config.allow_backhaul_mesh {
-            return Ok(BackhaulStatus::NotBackhaul);
+        for line in output.lines() {
+            let mut tokens = line.split_whitespace().peekable();
+            if tokens.peek() == Some(&"unicast") {
+                tokens.next();
+            }
+            let Some(destination) = tokens.next() else {
+                continue;
+            };
+            // Non-forwarding route types never carry traffic out of an interface
+            if matches!(
+                destination,
+                "unreachable"
+                    | "blackhole"
+                    | "prohibit"
+                    | "throw"
+                    | "local"
+                    | "broadcast"
+                    | "multicast"
+                    | "anycast"
+                    | "nat"
+            ) {
+                continue;
+            }
+
+            let mut gateway = None;
+            let mut interface = None;
+            while let Some(token) = tokens.next() {
+                match token {
+                    "via" => gateway = tokens.next().and_then(|g| g.parse().ok()),
+                    "dev" => interface = tokens.next(),
+                    _ => {}
+                }
+            }
+
+            if let Some(interface) = interface {
+                routes.push(RouteEntry {
+                    destination: destination.to_string(),
+                    gateway,
+                    interface: interface.to_string(),
+                });
+            }
         }
 
-        // Get interface name for this IP
-        let interface_name = self.get_interface_for_ip(ip)?;
+        Self { routes }
+    }
 
-        if let Some(iface) = interface_name {
-            self.check_interface(&iface)
-        } else {
-            Ok(BackhaulStatus::Unknown)
+    /// Parse `netstat -rn` output (macOS/BSD)
+    ///
+    /// Format: "default            192.168.1.1        UGScg          en0"
+    /// The interface column is located from each section's header, since its
+    /// position differs between OS releases.
+    pub fn parse_netstat(output: &str) -> Self {
+        let mut routes = Vec::new();
+        let mut netif_column = None;
+
+        for line in output.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.first() == Some(&"Destination") {
+                netif_column = fields.iter().position(|f| *f == "Netif");
+                continue;
+            }
+
+            let Some(column) = netif_column else {
+                continue;
+            };
+            let (Some(destination), Some(interface)) = (fields.first(), fields.get(column)) else {
+                continue;
+            };
+
+            // Strip the scope suffix from IPv6 link-local gateways ("fe80::1%en0")
+            let gateway = fields
+                .get(1)
+                .and_then(|g| g.split('%').next())
+                .and_then(|g| g.parse().ok());
+
+            routes.push(RouteEntry {
+                destination: destination.to_string(),
+                gateway,
+                interface: interface.to_string(),
+            });
         }
+
+        Self { routes }
     }
 
-    #[cfg(target_os = "linux")]
-    fn check_interface_linux(&self, interface_name: &str) -> Result<BackhaulStatus> {
-        // Use `ip route` to check for default gateway on this interface
-        let output = Command::new("ip")
-            .args(["route", "show", "default"])
-            .output()?;
-
-        if !output.status.success() {
-            warn!("Failed to execute 'ip route' command");
-            return Ok(BackhaulStatus::Unknown);
+    /// Parse `Get-NetRoute | ConvertTo-Csv` output (Windows)
+    ///
+    /// Expects the columns DestinationPrefix, NextHop, InterfaceAlias. Aliases
+    /// such as "Wi-Fi 2" contain spaces, hence CSV rather than table output.
+    pub fn parse_windows_csv(output: &str) -> Self {
+        let mut routes = Vec::new();
+
+        for line in output.lines().skip(1) {
+            let fields: Vec<&str> = line
+                .trim()
+                .split("\",\"")
+                .map(|f| f.trim_matches('"'))
+                .collect();
+            let [destination, next_hop, interface] = fields[..] else {
+                continue;
+            };
+
+            // On-link routes have an unspecified next hop
+            let gateway = next_hop
+                .parse::<IpAddr>()
+                .ok()
+                .filter(|g| !g.is_unspecified());
+
+            routes.push(RouteEntry {
+                destination: destination.to_string(),
+                gateway,
+                interface: interface.to_string(),
+            });
         }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        Self { routes }
+    }
 
-        // Check if default route uses this interface
-        // Format: "default via 192.168.1.1 dev wlan0 proto dhcp metric 600"
-        for line in stdout.lines() {
-            if line.contains("default") && line.contains(&format!("dev {}", interface_name)) {
-                debug!(
-                    "Interface {} has default route (is backhaul)",
-                    interface_name
-                );
-                return Ok(BackhaulStatus::IsBackhaul);
+    /// Classify an interface by the routes that use it
+    ///
+    /// Any default route, split defaults included, makes the interface
+    /// backhaul. An interface that is only routed on-link to local subnets or
+    /// to link-local space is a mesh link. Routes via a gateway to other
+    /// networks reach beyond the local link, so such an interface is not
+    /// assumed to be safe for mesh.
+    pub fn classify(&self, interface_name: &str) -> InterfaceClass {
+        let mut seen = false;
+        let mut gatewayed = false;
+
+        for route in self.routes.iter().filter(|r| r.interface == interface_name) {
+            if route.is_default() {
+                return InterfaceClass::Backhaul;
             }
+            if route.gateway.is_some() && !route.is_link_local() {
+                gatewayed = true;
+            }
+            seen = true;
         }
 
-        debug!(
-            "Interface {} does not have default route (not backhaul)",
-            interface_name
-        );
-        Ok(BackhaulStatus::NotBackhaul)
+        match (seen, gatewayed) {
+            (true, false) => InterfaceClass::Mesh,
+            _ => InterfaceClass::Unknown,
+        }
     }
 
-    #[cfg(target_os = "macos")]
-    fn check_interface_macos(&self, interface_name: &str) -> Result<BackhaulStatus> {
-        // Use `netstat -rn` to check for default gateway
-        let output = Command::new("netstat").args(["-rn"]).output()?;
-
-        if !output.status.success() {
-            warn!("Failed to execute 'netstat' command");
-            return Ok(BackhaulStatus::Unknown);
+    /// Interfaces carrying a default route, in route table order
+    pub fn backhaul_interfaces(&self) -> Vec<String> {
+        let mut interfaces: Vec<String> = Vec::new();
+        for route in self.routes.iter().filter(|r| r.is_default()) {
+            if !interfaces.contains(&route.interface) {
+                interfaces.push(route.interface.clone());
+            }
         }
+        interfaces
+    }
+}
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+/// Run a command and return its stdout, failing on a non-zero exit status
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn run_command(program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program).args(args).output()?;
+    if !output.status.success() {
+        anyhow::bail!("'{}' exited with {}", program, output.status);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
 
-        // Check if default route uses this interface
-        // Format: "default            192.168.1.1        UGSc           en0"
-        for line in stdout.lines() {
-            if line.starts_with("default") && line.ends_with(interface_name) {
-                debug!(
-                    "Interface {} has default route (is backhaul)",
-                    interface_name
-                );
-                return Ok(BackhaulStatus::IsBackhaul);
+/// Backhaul detector
+pub struct BackhaulDetector {
+    config: BackhaulConfig,
+}
+
+impl BackhaulDetector {
+    pub fn new(config: BackhaulConfig) -> Self {
+        Self { config }
+    }
+
+    /// Classify an interface as backhaul or mesh from the system route table
+    ///
+    /// Interfaces missing from the table, and failures to read the table,
+    /// yield `InterfaceClass::Unknown`. Ignores `allow_backhaul_mesh`.
+    pub fn classify_interface(&self, interface_name: &str) -> InterfaceClass {
+        match RouteTable::from_system() {
+            Ok(table) => {
+                let class = table.classify(interface_name);
+                debug!("Interface {} classified as {:?}", interface_name, class);
+                class
+            }
+            Err(e) => {
+                warn!("Failed to read route table: {}", e);
+                InterfaceClass::Unknown
             }
         }
-
-        debug!(
-            "Interface {} does not have default route (not backhaul)",
-            interface_name
-        );
-        Ok(BackhaulStatus::NotBackhaul)
     }
 
-    #[cfg(target_os = "windows")]
-    fn check_interface_windows(&self, interface_name: &str) -> Result<BackhaulStatus> {
-        // Use `route print` to check for default gateway
-        let output = Command::new("route").args(["print", "0.0.0.0"]).output()?;
+    /// Check if an interface is currently being used as backhaul
+    pub fn check_interface(&self, interface_name: &str) -> Result<BackhaulStatus> {
+        debug!("Checking backhaul status for interface: {}", interface_name);
 
-        if !output.status.success() {
-            warn!("Failed to execute 'route print' command");
-            return Ok(BackhaulStatus::Unknown);
+        // If user explicitly allows backhaul mesh, skip detection
+        if self.config.allow_backhaul_mesh {
+            debug!("Backhaul mesh explicitly allowed, returning NotBackhaul");
+            return Ok(BackhaulStatus::NotBackhaul);
         }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(self.classify_interface(interface_name).into())
+    }
 
-        // Look for default route (0.0.0.0) with this interface
-        // This is a simplified check - Windows interface names are complex
-        if stdout.contains(interface_name) && stdout.contains("0.0.0.0") {
-            debug!(
-                "Interface {} may be backhaul (Windows detection)",
-                interface_name
-            );
-            return Ok(BackhaulStatus::IsBackhaul);
+    /// Check if a specific IP address is on an interface being used as backhaul
+    pub fn check_ip_address(&self, ip: IpAddr) -> Result<BackhaulStatus> {
+        debug!("Checking backhaul status for IP: {}", ip);
+
+        if self.config.allow_backhaul_mesh {
+            return Ok(BackhaulStatus::NotBackhaul);
         }
 
-        Ok(BackhaulStatus::NotBackhaul)
+        // Get interface name for this IP
+        let interface_name = self.get_interface_for_ip(ip)?;
+
+        if let Some(iface) = interface_name {
+            self.check_interface(&iface)
+        } else {
+            Ok(BackhaulStatus::Unknown)
+        }
     }
 
     /// Get the interface name for a given IP address
@@ -225,47 +412,10 @@ impl BackhaulDetector {
 
     /// Check all interfaces and return a list of backhaul interfaces
     pub fn detect_all_backhauls(&self) -> Result<Vec<String>> {
-        let mut backhauls = Vec::new();
-
-        #[cfg(target_os = "linux")]
-        {
-            let output = Command::new("ip")
-                .args(["route", "show", "default"])
-                .output()?;
-
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-
-                for line in stdout.lines() {
-                    if let Some(dev_pos) = line.find("dev ") {
-                        let after_dev = &line[dev_pos + 4..];
-                        if let Some(iface_name) = after_dev.split_whitespace().next() {
-                            backhauls.push(iface_name.to_string());
-                            debug!("Detected backhaul interface: {}", iface_name);
-                        }
-                    }
-                }
-            }
-        }
-
-        #[cfg(target_os = "macos")]
-        {
-            let output = Command::new("netstat").args(["-rn"]).output()?;
-
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-
-                for line in stdout.lines() {
-                    if line.starts_with("default") {
-                        if let Some(iface_name) = line.split_whitespace().last() {
-                            backhauls.push(iface_name.to_string());
-                            debug!("Detected backhaul interface: {}", iface_name);
-                        }
-                    }
-                }
-            }
+        let backhauls = RouteTable::from_system()?.backhaul_interfaces();
+        for iface_name in &backhauls {
+            debug!("Detected backhaul interface: {}", iface_name);
         }
-
         Ok(backhauls)
     }
 }
@@ -327,4 +477,120 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_linux_route_classification() {
+        let table = RouteTable::parse_linux(
+            "default via 192.168.1.1 dev wlan0 proto dhcp metric 600\n\
+             10.42.0.0/24 dev eth1 proto kernel scope link src 10.42.0.1\n\
+             169.254.0.0/16 dev mesh0 scope link metric 1000\n\
+             192.168.1.0/24 dev wlan0 proto kernel scope link src 192.168.1.20\n\
+             unreachable 10.99.0.0/16\n\
+             fe80::/64 dev mesh0 proto kernel metric 256 pref medium\n",
+        );
+
+        assert_eq!(
+            table.routes[0],
+            RouteEntry {
+                destination: "default".to_string(),
+                gateway: Some("192.168.1.1".parse().unwrap()),
+                interface: "wlan0".to_string(),
+            }
+        );
+        assert_eq!(table.classify("wlan0"), InterfaceClass::Backhaul);
+        assert_eq!(table.classify("mesh0"), InterfaceClass::Mesh);
+        assert_eq!(table.classify("eth1"), InterfaceClass::Mesh);
+        assert_eq!(table.classify("nonexistent0"), InterfaceClass::Unknown);
+        assert_eq!(table.backhaul_interfaces(), vec!["wlan0".to_string()]);
+    }
+
+    #[test]
+    fn test_netstat_route_classification() {
+        let table = RouteTable::parse_netstat(
+            "Routing tables\n\
+             \n\
+             Internet:\n\
+             Destination        Gateway            Flags           Netif Expire\n\
+             default            192.168.1.1        UGScg             en0\n\
+             169.254            link#14            UCS             bridge0      !\n\
+             192.168.1          link#6             UCS               en0      !\n\
+             \n\
+             Internet6:\n\
+             Destination                             Gateway                                 Flags           Netif Expire\n\
+             default                                 fe80::1%en0                             UGcg              en0\n\
+             fe80::%bridge0/64                       link#14                                 UCI           bridge0\n",
+        );
+
+        assert_eq!(
+            table.routes[3].gateway,
+            Some("fe80::1".parse().unwrap()),
+            "IPv6 gateway scope suffix should be stripped"
+        );
+        assert_eq!(table.classify("en0"), InterfaceClass::Backhaul);
+        assert_eq!(table.classify("bridge0"), InterfaceClass::Mesh);
+        assert_eq!(table.classify("utun3"), InterfaceClass::Unknown);
+    }
+
+    #[test]
+    fn test_windows_route_classification() {
+        let table = RouteTable::parse_windows_csv(
+            "\"DestinationPrefix\",\"NextHop\",\"InterfaceAlias\"\r\n\
+             \"0.0.0.0/0\",\"192.168.1.1\",\"Wi-Fi 2\"\r\n\
+             \"169.254.0.0/16\",\"0.0.0.0\",\"Ethernet 3\"\r\n\
+             \"fe80::/64\",\"::\",\"Ethernet 3\"\r\n",
+        );
+
+        assert_eq!(table.routes.len(), 3);
+        assert_eq!(table.routes[1].gateway, None);
+        assert_eq!(table.classify("Wi-Fi 2"), InterfaceClass::Backhaul);
+        assert_eq!(table.classify("Ethernet 3"), InterfaceClass::Mesh);
+        assert_eq!(table.classify("Wi-Fi"), InterfaceClass::Unknown);
+    }
+
+    #[test]
+    fn test_split_default_and_gateway_routes() {
+        let table = RouteTable::parse_linux(
+            "default via 192.168.1.1 dev wlan0 proto dhcp metric 600
+             0.0.0.0/1 via 10.8.0.1 dev tun0
+             128.0.0.0/1 via 10.8.0.1 dev tun0
+             10.8.0.0/24 dev tun0 proto kernel scope link src 10.8.0.2
+             172.16.0.0/12 via 10.20.0.1 dev eth2
+             10.20.0.0/24 dev eth2 proto kernel scope link src 10.20.0.5
+             169.254.0.0/16 dev mesh0 scope link metric 1000
+",
+        );
+
+        assert_eq!(table.classify("tun0"), InterfaceClass::Backhaul);
+        assert_eq!(table.classify("eth2"), InterfaceClass::Unknown);
+        assert_eq!(table.classify("mesh0"), InterfaceClass::Mesh);
+        assert_eq!(
+            table.backhaul_interfaces(),
+            vec!["wlan0".to_string(), "tun0".to_string()]
+        );
+
+        let netstat = RouteTable::parse_netstat(
+            "Internet:
+             Destination        Gateway            Flags           Netif Expire
+             0/1                10.8.0.1           UGScg           utun3
+             128.0/1            10.8.0.1           UGSc            utun3
+",
+        );
+        assert_eq!(netstat.classify("utun3"), InterfaceClass::Backhaul);
+    }
+
+    #[test]
+    fn test_interface_class_to_status() {
+        assert_eq!(
+            BackhaulStatus::from(InterfaceClass::Backhaul),
+            BackhaulStatus::IsBackhaul
+        );
+        assert_eq!(
+            BackhaulStatus::from(InterfaceClass::Mesh),
+            BackhaulStatus::NotBackhaul
+        );
+        assert_eq!(
+            BackhaulStatus::from(InterfaceClass::Unknown),
+            BackhaulStatus::Unknown
+        );
+    }
 }
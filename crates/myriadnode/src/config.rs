@@ -113,6 +113,30 @@ pub struct FailoverConfig {
     pub latency_threshold_multiplier: f32,
     pub loss_threshold: f32,
     pub retry_attempts: u32,
+    /// Minimum time on an adapter before another score-based failover (seconds)
+    #[serde(default = "default_min_dwell_secs")]
+    pub min_dwell_secs: u64,
+    /// Fraction by which a candidate must beat the primary's score to take over
+    #[serde(default = "default_switch_margin")]
+    pub switch_margin: f64,
+    /// Adapter IDs in order of preference, most preferred first
+    #[serde(default)]
+    pub preferred_adapters: Vec<String>,
+    /// How long a more preferred adapter must stay healthy before failing back (seconds)
+    #[serde(default = "default_failback_after_secs")]
+    pub failback_after_secs: u64,
+}
+
+fn default_min_dwell_secs() -> u64 {
+    60
+}
+
+fn default_switch_margin() -> f64 {
+    0.10 // Candidate must score 10% higher
+}
+
+fn default_failback_after_secs() -> u64 {
+    120
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -125,12 +149,19 @@ pub struct ScoringConfig {
     pub weight_privacy: f64,
     #[serde(default = "default_recalculation_interval")]
     pub recalculation_interval_secs: u64,
+    /// EWMA smoothing factor for adapter metrics (0.0 - 1.0, higher reacts faster)
+    #[serde(default = "default_smoothing_factor")]
+    pub smoothing_factor: f64,
 }
 
 fn default_recalculation_interval() -> u64 {
     60 // Recalculate scores every minute
 }
 
+fn default_smoothing_factor() -> f64 {
+    crate::scoring::DEFAULT_SMOOTHING_FACTOR
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityConfig {
     pub require_signatures: bool,
@@ -382,6 +413,10 @@ impl Config {
                     latency_threshold_multiplier: 5.0,
                     loss_threshold: 0.25,
                     retry_attempts: 3,
+                    min_dwell_secs: default_min_dwell_secs(),
+                    switch_margin: default_switch_margin(),
+                    preferred_adapters: Vec::new(),
+                    failback_after_secs: default_failback_after_secs(),
                 },
                 scoring: ScoringConfig {
                     mode: "default".to_string(),
@@ -391,6 +426,7 @@ impl Config {
                     weight_power: 0.10,
                     weight_privacy: 0.15,
                     recalculation_interval_secs: 60,
+                    smoothing_factor: default_smoothing_factor(),
                 },
             },
             security: SecurityConfig {
@@ -1,13 +1,17 @@
 use anyhow::Result;
+use futures::{Stream, StreamExt};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, Notify, RwLock};
 use tokio::task::JoinHandle;
 use tokio::time::{interval, Duration, Instant};
 use tracing::{debug, error, info, warn};
 
-use crate::scoring::{AdapterMetrics, AdapterScorer, ScoringWeights};
-use myriadmesh_network::AdapterManager;
+use crate::scoring::{
+    AdapterMetrics, AdapterScore, AdapterScorer, MetricsSmoother, ScoringWeights,
+    DEFAULT_SMOOTHING_FACTOR,
+};
+use myriadmesh_network::{AdapterManager, CellularStatus};
 
 // Re-export for ergonomic imports in tests and other modules
 pub use crate::config::FailoverConfig;
@@ -32,6 +36,21 @@ pub enum FailoverEvent {
     AdapterDown { adapter: String, reason: String },
     /// Adapter recovered and is now available
     AdapterRecovered { adapter: String },
+    /// Switch to a better adapter held back by the dwell time
+    FlapSuppressed {
+        current: String,
+        candidate: String,
+        reason: String,
+    },
+}
+
+/// Current primary adapter and the state used to pick the next one
+#[derive(Debug, Clone, Default)]
+struct PrimarySelection {
+    id: Option<String>,
+    selected_at: Option<Instant>,
+    /// When each currently healthy adapter became healthy
+    healthy_since: HashMap<String, Instant>,
 }
 
 /// Adapter health status
@@ -52,6 +71,8 @@ struct AdapterHealth {
     last_check: Instant,
     current_metrics: Option<AdapterMetrics>,
     baseline_latency: Option<f64>,
+    /// The adapter's radio reports no service
+    no_service: bool,
 }
 
 impl AdapterHealth {
@@ -63,6 +84,7 @@ impl AdapterHealth {
             last_check: Instant::now(),
             current_metrics: None,
             baseline_latency: None,
+            no_service: false,
         }
     }
 
@@ -109,7 +131,7 @@ impl AdapterHealth {
 /// 1. `adapter_manager` (RwLock<AdapterManager>) - Usually read lock
 /// 2. `adapter_health` (RwLock<HashMap<String, AdapterHealth>>) - Usually write lock
 /// 3. `event_log` (RwLock<Vec<FailoverEvent>>) - Write lock via log_event()
-/// 4. `current_primary` (RwLock<Option<String>>) - Write lock for failover
+/// 4. `current_primary` (RwLock<PrimarySelection>) - Write lock for failover
 ///
 /// **LOCK RELEASE ORDER** - Always release in reverse order (explicit drop()):
 /// 1. Drop `current_primary` first
@@ -167,12 +189,16 @@ pub struct FailoverManager {
     config: FailoverConfig,
     adapter_manager: Arc<RwLock<AdapterManager>>,
     scorer: AdapterScorer,
+    smoothing_factor: f64,
     adapter_health: Arc<RwLock<HashMap<String, AdapterHealth>>>,
-    current_primary: Arc<RwLock<Option<String>>>,
+    current_primary: Arc<RwLock<PrimarySelection>>,
     event_log: Arc<RwLock<Vec<FailoverEvent>>>,
+    /// Wakes the monitor for a check ahead of its next tick
+    recheck: Arc<Notify>,
     // RESOURCE M4: Task handle management for graceful shutdown
     shutdown_tx: broadcast::Sender<()>,
     monitor_task: Arc<RwLock<Option<JoinHandle<()>>>>,
+    status_tasks: Arc<RwLock<Vec<JoinHandle<()>>>>,
 }
 
 impl FailoverManager {
@@ -188,14 +214,23 @@ impl FailoverManager {
             config,
             adapter_manager,
             scorer: AdapterScorer::new(scoring_weights),
+            smoothing_factor: DEFAULT_SMOOTHING_FACTOR,
             adapter_health: Arc::new(RwLock::new(HashMap::new())),
-            current_primary: Arc::new(RwLock::new(None)),
+            current_primary: Arc::new(RwLock::new(PrimarySelection::default())),
             event_log: Arc::new(RwLock::new(Vec::new())),
+            recheck: Arc::new(Notify::new()),
             shutdown_tx,
             monitor_task: Arc::new(RwLock::new(None)),
+            status_tasks: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
+    /// Set the EWMA smoothing factor applied to adapter metrics before scoring
+    pub fn with_smoothing_factor(mut self, smoothing_factor: f64) -> Self {
+        self.smoothing_factor = smoothing_factor;
+        self
+    }
+
     /// Start the failover monitoring loop
     pub async fn start(&self) -> Result<()> {
         if !self.config.auto_failover {
@@ -208,9 +243,12 @@ impl FailoverManager {
         let config = self.config.clone();
         let adapter_manager = Arc::clone(&self.adapter_manager);
         let scorer = self.scorer.clone();
+        // Owned by the monitor task, so it needs no lock
+        let mut smoother = MetricsSmoother::new(self.smoothing_factor);
         let adapter_health = Arc::clone(&self.adapter_health);
         let current_primary = Arc::clone(&self.current_primary);
         let event_log = Arc::clone(&self.event_log);
+        let recheck = Arc::clone(&self.recheck);
         // RESOURCE M4: Subscribe to shutdown channel
         let mut shutdown_rx = self.shutdown_tx.subscribe();
 
@@ -224,11 +262,17 @@ impl FailoverManager {
                         info!("Failover monitor shutting down");
                         break;
                     }
-                    _ = ticker.tick() => {
+                    _ = async {
+                        tokio::select! {
+                            _ = ticker.tick() => {}
+                            _ = recheck.notified() => {}
+                        }
+                    } => {
                         if let Err(e) = Self::check_and_failover(
                             &config,
                             &adapter_manager,
                             &scorer,
+                            &mut smoother,
                             &adapter_health,
                             &current_primary,
                             &event_log,
@@ -248,6 +292,96 @@ impl FailoverManager {
         Ok(())
     }
 
+    /// Follow a cellular adapter's radio status
+    ///
+    /// Loss of service marks `adapter_id` unavailable until service returns,
+    /// and every change triggers a failover check without waiting for the
+    /// next tick.
+    pub async fn watch_cellular_status(
+        &self,
+        adapter_id: String,
+        status: impl Stream<Item = CellularStatus> + Send + 'static,
+    ) {
+        let adapter_health = Arc::clone(&self.adapter_health);
+        let recheck = Arc::clone(&self.recheck);
+        let event_log = Arc::clone(&self.event_log);
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        let handle = tokio::spawn(async move {
+            let mut status = Box::pin(status);
+            loop {
+                let update = tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    update = status.next() => update,
+                };
+                let Some(update) = update else {
+                    break;
+                };
+                Self::apply_cellular_status(&adapter_id, update, &adapter_health, &event_log).await;
+                recheck.notify_one();
+            }
+        });
+
+        // RESOURCE M4: Store task handle
+        self.status_tasks.write().await.push(handle);
+    }
+
+    /// Record one cellular status change for `adapter_id`
+    async fn apply_cellular_status(
+        adapter_id: &str,
+        update: CellularStatus,
+        adapter_health: &Arc<RwLock<HashMap<String, AdapterHealth>>>,
+        event_log: &Arc<RwLock<Vec<FailoverEvent>>>,
+    ) {
+        debug!("Cellular status for '{}': {:?}", adapter_id, update);
+        let event = match update {
+            CellularStatus::ServiceLost { previous } => {
+                Self::set_no_service(adapter_health, adapter_id, true).await;
+                Some(FailoverEvent::AdapterDown {
+                    adapter: adapter_id.to_string(),
+                    reason: format!("Cellular service lost (was {:?})", previous),
+                })
+            }
+            CellularStatus::ServiceRestored { .. } => {
+                Self::set_no_service(adapter_health, adapter_id, false)
+                    .await
+                    .then(|| FailoverEvent::AdapterRecovered {
+                        adapter: adapter_id.to_string(),
+                    })
+            }
+            CellularStatus::SignalThresholdCrossed {
+                threshold,
+                signal_strength,
+                rising: false,
+            } => Some(FailoverEvent::ThresholdViolation {
+                adapter: adapter_id.to_string(),
+                metric: "signal_strength".to_string(),
+                value: signal_strength as f64,
+                threshold: threshold as f64,
+            }),
+            CellularStatus::NetworkTypeChanged { .. }
+            | CellularStatus::SignalThresholdCrossed { rising: true, .. } => None,
+        };
+        if let Some(event) = event {
+            // LOCK ORDER 3: adapter_health was released by set_no_service
+            Self::log_event(event_log, event).await;
+        }
+    }
+
+    /// Set whether `adapter_id` has radio service, returning the old flag
+    async fn set_no_service(
+        adapter_health: &Arc<RwLock<HashMap<String, AdapterHealth>>>,
+        adapter_id: &str,
+        no_service: bool,
+    ) -> bool {
+        // LOCK ORDER 2: Acquire adapter_health (write lock)
+        let mut health_map = adapter_health.write().await;
+        let health = health_map
+            .entry(adapter_id.to_string())
+            .or_insert_with(|| AdapterHealth::new(adapter_id.to_string()));
+        std::mem::replace(&mut health.no_service, no_service)
+    }
+
     /// Gracefully shutdown the failover monitor and wait for task to complete
     /// RESOURCE M4: Prevents task handle leaks and ensures cleanup
     pub async fn shutdown(&self) {
@@ -258,6 +392,9 @@ impl FailoverManager {
         if let Some(handle) = self.monitor_task.write().await.take() {
             let _ = handle.await;
         }
+        for handle in self.status_tasks.write().await.drain(..) {
+            let _ = handle.await;
+        }
     }
 
     /// Check adapter health and perform failover if needed
@@ -275,13 +412,15 @@ impl FailoverManager {
         config: &FailoverConfig,
         adapter_manager: &Arc<RwLock<AdapterManager>>,
         scorer: &AdapterScorer,
+        smoother: &mut MetricsSmoother,
         adapter_health: &Arc<RwLock<HashMap<String, AdapterHealth>>>,
-        current_primary: &Arc<RwLock<Option<String>>>,
+        current_primary: &Arc<RwLock<PrimarySelection>>,
         event_log: &Arc<RwLock<Vec<FailoverEvent>>>,
     ) -> Result<()> {
         // LOCK ORDER 1: Acquire adapter_manager (read lock)
         let manager = adapter_manager.read().await;
         let adapter_ids = manager.adapter_ids();
+        smoother.retain(&adapter_ids);
 
         // LOCK ORDER 2: Acquire adapter_health (write lock)
         // Note: manager is still held here to read adapter data
@@ -300,14 +439,22 @@ impl FailoverManager {
                 let status = adapter_guard.get_status();
 
                 // Check if adapter is available
-                if !matches!(status, myriadmesh_network::adapter::AdapterStatus::Ready) {
+                let no_service = health_map.get(adapter_id).is_some_and(|h| h.no_service);
+                if no_service
+                    || !matches!(status, myriadmesh_network::adapter::AdapterStatus::Ready)
+                {
                     if let Some(health) = health_map.get_mut(adapter_id) {
                         health.record_failure();
 
                         if health.status == HealthStatus::Failed {
+                            let reason = if no_service {
+                                "No radio service".to_string()
+                            } else {
+                                format!("Status: {:?}", status)
+                            };
                             let event = FailoverEvent::AdapterDown {
                                 adapter: adapter_id.clone(),
-                                reason: format!("Status: {:?}", status),
+                                reason,
                             };
                             // LOCK ORDER 3: log_event acquires event_log (write lock)
                             // Note: This is called while holding adapter_manager + adapter_health
@@ -364,7 +511,8 @@ impl FailoverManager {
                     }
                 }
 
-                all_metrics.insert(adapter_id.clone(), metrics);
+                // Score on smoothed metrics so one bad sample cannot flip the primary
+                all_metrics.insert(adapter_id.clone(), smoother.update(adapter_id, &metrics));
             }
         }
 
@@ -385,66 +533,176 @@ impl FailoverManager {
             return Ok(());
         }
 
-        let best = &scores[0];
+        Self::select_primary(config, &scores, current_primary, event_log, Instant::now()).await;
+
+        Ok(())
+    }
+
+    /// Decide whether to switch the primary adapter given ranked `scores`
+    ///
+    /// Hysteresis against flapping between similar adapters:
+    /// - the best candidate must beat the current primary's score by
+    ///   `switch_margin`
+    /// - no score-based switch or failback happens within `min_dwell_secs`
+    ///   of the last switch; such switches are logged as `FlapSuppressed`
+    ///
+    /// With `preferred_adapters` set, scores never move the primary to a less
+    /// preferred adapter, and the node fails back to a more preferred adapter
+    /// once it has stayed healthy for `failback_after_secs`.
+    ///
+    /// A primary that is no longer among the healthy candidates is replaced
+    /// immediately, regardless of dwell time. Without a usable primary the
+    /// most preferred healthy adapter is picked, by score among equals.
+    async fn select_primary(
+        config: &FailoverConfig,
+        scores: &[AdapterScore],
+        current_primary: &Arc<RwLock<PrimarySelection>>,
+        event_log: &Arc<RwLock<Vec<FailoverEvent>>>,
+        now: Instant,
+    ) {
+        let Some(best) = scores.first() else {
+            return;
+        };
 
         // LOCK ORDER 4: Acquire current_primary (write lock)
         // All previous locks have been dropped, so this is safe
         let mut primary = current_primary.write().await;
 
-        // Check if we should switch
-        let should_switch = if let Some(current) = &*primary {
-            // Find current adapter's score
-            let current_score = scores
+        // Any gap in health restarts an adapter's failback timer
+        primary
+            .healthy_since
+            .retain(|id, _| scores.iter().any(|s| &s.adapter_id == id));
+        for score in scores {
+            primary
+                .healthy_since
+                .entry(score.adapter_id.clone())
+                .or_insert(now);
+        }
+
+        let current_score = primary.id.as_ref().and_then(|current| {
+            scores
                 .iter()
                 .find(|s| &s.adapter_id == current)
-                .map(|s| s.total_score);
+                .map(|s| s.total_score)
+        });
 
-            if let Some(current_score) = current_score {
-                // Switch if best adapter is significantly better (>10% improvement)
-                best.total_score > current_score * 1.10
-            } else {
-                // Current adapter not in healthy list, definitely switch
-                true
-            }
-        } else {
-            // No primary set, pick the best
-            true
-        };
+        // Most preferred healthy adapter, best score among equals
+        let preferred = scores
+            .iter()
+            .min_by_key(|s| Self::preference_rank(config, &s.adapter_id))
+            .unwrap_or(best);
+
+        let (target, reason) = match (&primary.id, current_score) {
+            // No primary set, pick the most preferred
+            (None, _) => (
+                preferred,
+                format!("Better score: {:.3}", preferred.total_score),
+            ),
+            // Current adapter not in healthy list, definitely switch
+            (Some(_), None) => (
+                preferred,
+                format!(
+                    "Primary unavailable, best score: {:.3}",
+                    preferred.total_score
+                ),
+            ),
+            (Some(current), Some(current_score)) => {
+                let current_rank = Self::preference_rank(config, current);
+                let failback_after = Duration::from_secs(config.failback_after_secs);
+
+                let failback = scores
+                    .iter()
+                    .filter(|s| Self::preference_rank(config, &s.adapter_id) < current_rank)
+                    .filter(|s| {
+                        primary
+                            .healthy_since
+                            .get(&s.adapter_id)
+                            .is_some_and(|since| {
+                                now.saturating_duration_since(*since) >= failback_after
+                            })
+                    })
+                    .min_by_key(|s| Self::preference_rank(config, &s.adapter_id));
+
+                let candidate = match failback {
+                    Some(preferred) => Some((
+                        preferred,
+                        format!("Failback to preferred adapter '{}'", preferred.adapter_id),
+                    )),
+                    None => scores
+                        .iter()
+                        .find(|s| Self::preference_rank(config, &s.adapter_id) <= current_rank)
+                        .filter(|s| {
+                            s.adapter_id != *current
+                                && s.total_score > current_score * (1.0 + config.switch_margin)
+                        })
+                        .map(|s| (s, format!("Better score: {:.3}", s.total_score))),
+                };
 
-        if should_switch {
-            let from = primary.clone().unwrap_or_else(|| "none".to_string());
-            let to = best.adapter_id.clone();
+                let Some((target, reason)) = candidate else {
+                    // LOCK RELEASE: Drop primary if we didn't switch
+                    drop(primary);
+                    return;
+                };
 
-            info!(
-                "Failover: switching primary adapter from '{}' to '{}' (score: {:.3})",
-                from, to, best.total_score
-            );
+                let dwell = Duration::from_secs(config.min_dwell_secs);
+                if let Some(since) = primary
+                    .selected_at
+                    .filter(|since| now.saturating_duration_since(*since) < dwell)
+                {
+                    let event = FailoverEvent::FlapSuppressed {
+                        current: current.clone(),
+                        candidate: target.adapter_id.clone(),
+                        reason: format!(
+                            "{}; {}s left of {}s dwell time",
+                            reason,
+                            (dwell - now.saturating_duration_since(since)).as_secs(),
+                            config.min_dwell_secs
+                        ),
+                    };
+                    debug!("Failover suppressed: {:?}", event);
+
+                    // LOCK RELEASE: Drop current_primary before logging event
+                    drop(primary);
+                    // LOCK ORDER 3: log_event acquires event_log independently
+                    Self::log_event(event_log, event).await;
+                    return;
+                }
 
-            *primary = Some(to.clone());
+                (target, reason)
+            }
+        };
 
-            // LOCK RELEASE: Drop current_primary before logging event
-            // This ensures we don't hold Lock 4 while acquiring Lock 3 (deadlock prevention)
-            drop(primary);
+        let from = primary.id.clone().unwrap_or_else(|| "none".to_string());
+        let to = target.adapter_id.clone();
 
-            let event = FailoverEvent::AdapterSwitch {
-                from,
-                to,
-                reason: format!("Better score: {:.3}", best.total_score),
-            };
-            // LOCK ORDER 3: log_event acquires event_log independently
-            Self::log_event(event_log, event).await;
+        info!(
+            "Failover: switching primary adapter from '{}' to '{}' ({})",
+            from, to, reason
+        );
 
-            return Ok(());
-        }
+        primary.id = Some(to.clone());
+        primary.selected_at = Some(now);
 
-        // LOCK RELEASE: Drop primary if we didn't switch
+        // LOCK RELEASE: Drop current_primary before logging event
+        // This ensures we don't hold Lock 4 while acquiring Lock 3 (deadlock prevention)
         drop(primary);
 
-        Ok(())
+        let event = FailoverEvent::AdapterSwitch { from, to, reason };
+        // LOCK ORDER 3: log_event acquires event_log independently
+        Self::log_event(event_log, event).await;
+    }
+
+    /// Position of an adapter in the preference list; unlisted adapters rank last
+    fn preference_rank(config: &FailoverConfig, adapter_id: &str) -> usize {
+        config
+            .preferred_adapters
+            .iter()
+            .position(|id| id == adapter_id)
+            .unwrap_or(config.preferred_adapters.len())
     }
 
     /// Estimate privacy level based on adapter type
-    fn estimate_privacy_level(adapter_id: &str) -> f64 {
+    pub fn estimate_privacy_level(adapter_id: &str) -> f64 {
         if adapter_id.contains("i2p") {
             0.95
         } else if adapter_id.contains("bluetooth") && !adapter_id.contains("_le") {
@@ -475,7 +733,7 @@ impl FailoverManager {
 
     /// Get the current primary adapter
     pub async fn get_primary_adapter(&self) -> Option<String> {
-        self.current_primary.read().await.clone()
+        self.current_primary.read().await.id.clone()
     }
 
     /// Get recent failover events
@@ -493,11 +751,14 @@ impl FailoverManager {
         }
 
         let mut primary = self.current_primary.write().await;
-        let from = primary.clone().unwrap_or_else(|| "none".to_string());
+        let from = primary.id.clone().unwrap_or_else(|| "none".to_string());
 
         info!("Forced failover from '{}' to '{}'", from, adapter_id);
 
-        *primary = Some(adapter_id.clone());
+        // Manual choices also start a dwell period
+        primary.id = Some(adapter_id.clone());
+        primary.selected_at = Some(Instant::now());
+        drop(primary);
 
         let event = FailoverEvent::AdapterSwitch {
             from,
@@ -604,4 +865,280 @@ mod tests {
         assert_eq!(FailoverManager::estimate_privacy_level("cellular"), 0.10);
         assert_eq!(FailoverManager::estimate_privacy_level("unknown"), 0.50);
     }
+
+    fn failover_config(min_dwell_secs: u64, switch_margin: f64) -> FailoverConfig {
+        FailoverConfig {
+            auto_failover: true,
+            latency_threshold_multiplier: 5.0,
+            loss_threshold: 0.25,
+            retry_attempts: 3,
+            min_dwell_secs,
+            switch_margin,
+            preferred_adapters: Vec::new(),
+            failback_after_secs: 120,
+        }
+    }
+
+    fn preference_config() -> FailoverConfig {
+        FailoverConfig {
+            preferred_adapters: vec!["wifi".to_string(), "cellular".to_string()],
+            failback_after_secs: 30,
+            ..failover_config(60, 0.10)
+        }
+    }
+
+    fn ranked(scores: &[(&str, f64)]) -> Vec<AdapterScore> {
+        let mut scores: Vec<AdapterScore> = scores
+            .iter()
+            .map(|(id, total)| AdapterScore {
+                adapter_id: id.to_string(),
+                total_score: *total,
+                latency_score: 0.0,
+                bandwidth_score: 0.0,
+                reliability_score: 0.0,
+                power_score: 0.0,
+                privacy_score: 0.0,
+            })
+            .collect();
+        scores.sort_by(|a, b| b.total_score.partial_cmp(&a.total_score).unwrap());
+        scores
+    }
+
+    async fn count_events(event_log: &Arc<RwLock<Vec<FailoverEvent>>>) -> (usize, usize) {
+        let log = event_log.read().await;
+        let switches = log
+            .iter()
+            .filter(|e| matches!(e, FailoverEvent::AdapterSwitch { .. }))
+            .count();
+        let suppressed = log
+            .iter()
+            .filter(|e| matches!(e, FailoverEvent::FlapSuppressed { .. }))
+            .count();
+        (switches, suppressed)
+    }
+
+    #[tokio::test]
+    async fn test_alternating_scores_switch_once_within_dwell() {
+        let config = failover_config(60, 0.10);
+        let primary = Arc::new(RwLock::new(PrimarySelection {
+            id: Some("wifi".to_string()),
+            ..Default::default()
+        }));
+        let event_log = Arc::new(RwLock::new(Vec::new()));
+        let start = Instant::now();
+
+        // Every 10s the two adapters trade places by a wide margin
+        for tick in 0..6u64 {
+            let scores = if tick % 2 == 0 {
+                ranked(&[("cellular", 0.9), ("wifi", 0.5)])
+            } else {
+                ranked(&[("wifi", 0.9), ("cellular", 0.5)])
+            };
+            let now = start + Duration::from_secs(tick * 10);
+            FailoverManager::select_primary(&config, &scores, &primary, &event_log, now).await;
+        }
+
+        // One switch to cellular; switching back is suppressed on every odd tick
+        assert_eq!(primary.read().await.id.as_deref(), Some("cellular"));
+        assert_eq!(count_events(&event_log).await, (1, 3));
+
+        // Once the dwell time has passed, a better adapter wins again
+        let later = start + Duration::from_secs(61);
+        let scores = ranked(&[("wifi", 0.9), ("cellular", 0.5)]);
+        FailoverManager::select_primary(&config, &scores, &primary, &event_log, later).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("wifi"));
+        assert_eq!(count_events(&event_log).await, (2, 3));
+    }
+
+    #[tokio::test]
+    async fn test_switch_requires_margin() {
+        let config = failover_config(0, 0.20);
+        let primary = Arc::new(RwLock::new(PrimarySelection {
+            id: Some("wifi".to_string()),
+            ..Default::default()
+        }));
+        let event_log = Arc::new(RwLock::new(Vec::new()));
+        let now = Instant::now();
+
+        // 15% better is within the 20% margin: no switch, no suppression event
+        let scores = ranked(&[("cellular", 0.69), ("wifi", 0.6)]);
+        FailoverManager::select_primary(&config, &scores, &primary, &event_log, now).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("wifi"));
+        assert_eq!(count_events(&event_log).await, (0, 0));
+
+        let scores = ranked(&[("cellular", 0.8), ("wifi", 0.6)]);
+        FailoverManager::select_primary(&config, &scores, &primary, &event_log, now).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("cellular"));
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_primary_ignores_dwell() {
+        let config = failover_config(600, 0.10);
+        let now = Instant::now();
+        let primary = Arc::new(RwLock::new(PrimarySelection {
+            id: Some("wifi".to_string()),
+            selected_at: Some(now),
+            ..Default::default()
+        }));
+        let event_log = Arc::new(RwLock::new(Vec::new()));
+
+        // wifi dropped out of the healthy candidates
+        let scores = ranked(&[("cellular", 0.5), ("lora", 0.3)]);
+        FailoverManager::select_primary(&config, &scores, &primary, &event_log, now).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("cellular"));
+        assert_eq!(count_events(&event_log).await, (1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_preference_applies_without_usable_primary() {
+        let config = preference_config();
+        let primary = Arc::new(RwLock::new(PrimarySelection::default()));
+        let event_log = Arc::new(RwLock::new(Vec::new()));
+        let now = Instant::now();
+
+        // No primary yet: cellular is preferred over the better-scoring lora
+        let scores = ranked(&[("lora", 0.9), ("cellular", 0.5), ("bluetooth", 0.4)]);
+        FailoverManager::select_primary(&config, &scores, &primary, &event_log, now).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("cellular"));
+
+        // cellular drops out: unlisted adapters compete on score
+        let scores = ranked(&[("bluetooth", 0.4), ("lora", 0.9)]);
+        FailoverManager::select_primary(&config, &scores, &primary, &event_log, now).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("lora"));
+
+        // lora drops out while wifi is back: wifi wins despite its score
+        let scores = ranked(&[("bluetooth", 0.8), ("wifi", 0.3)]);
+        FailoverManager::select_primary(&config, &scores, &primary, &event_log, now).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("wifi"));
+    }
+
+    #[tokio::test]
+    async fn test_cellular_status_marks_adapter_down() {
+        use myriadmesh_network::NetworkType;
+
+        let manager = FailoverManager::new(
+            failover_config(60, 0.10),
+            Arc::new(RwLock::new(AdapterManager::new())),
+            ScoringWeights::default(),
+        );
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        manager
+            .watch_cellular_status("cellular".to_string(), rx)
+            .await;
+
+        let wait_for = |down: bool| {
+            let adapter_health = Arc::clone(&manager.adapter_health);
+            async move {
+                tokio::time::timeout(Duration::from_secs(2), async {
+                    while adapter_health
+                        .read()
+                        .await
+                        .get("cellular")
+                        .is_some_and(|h| h.no_service)
+                        != down
+                    {
+                        tokio::time::sleep(Duration::from_millis(5)).await;
+                    }
+                })
+                .await
+                .expect("status should be applied");
+            }
+        };
+
+        tx.unbounded_send(CellularStatus::NetworkTypeChanged {
+            previous: NetworkType::FiveG,
+            current: NetworkType::LTE,
+        })
+        .unwrap();
+        tx.unbounded_send(CellularStatus::ServiceLost {
+            previous: NetworkType::LTE,
+        })
+        .unwrap();
+        wait_for(true).await;
+        assert!(matches!(
+            manager.get_recent_events(1).await.as_slice(),
+            [FailoverEvent::AdapterDown { adapter, .. }] if adapter == "cellular"
+        ));
+
+        tx.unbounded_send(CellularStatus::ServiceRestored {
+            current: NetworkType::LTE,
+        })
+        .unwrap();
+        wait_for(false).await;
+        assert!(matches!(
+            manager.get_recent_events(1).await.as_slice(),
+            [FailoverEvent::AdapterRecovered { adapter }] if adapter == "cellular"
+        ));
+
+        manager.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_failback_after_primary_recovers() {
+        let config = preference_config();
+        let primary = Arc::new(RwLock::new(PrimarySelection {
+            id: Some("wifi".to_string()),
+            ..Default::default()
+        }));
+        let event_log = Arc::new(RwLock::new(Vec::new()));
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        // wifi fails: fail over to cellular right away
+        let without_wifi = ranked(&[("cellular", 0.9), ("lora", 0.2)]);
+        FailoverManager::select_primary(&config, &without_wifi, &primary, &event_log, at(0)).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("cellular"));
+
+        // wifi recovers with a lower score than cellular
+        let with_wifi = ranked(&[("cellular", 0.9), ("wifi", 0.6), ("lora", 0.2)]);
+        for secs in [10, 20, 30] {
+            FailoverManager::select_primary(&config, &with_wifi, &primary, &event_log, at(secs))
+                .await;
+        }
+        // Not healthy long enough yet
+        assert_eq!(primary.read().await.id.as_deref(), Some("cellular"));
+        assert_eq!(count_events(&event_log).await, (1, 0));
+
+        // Healthy for 40s, but still inside cellular's dwell time
+        FailoverManager::select_primary(&config, &with_wifi, &primary, &event_log, at(50)).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("cellular"));
+        assert_eq!(count_events(&event_log).await, (1, 1));
+
+        FailoverManager::select_primary(&config, &with_wifi, &primary, &event_log, at(60)).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("wifi"));
+        assert_eq!(count_events(&event_log).await, (2, 1));
+
+        // Back on the preferred adapter, a better score does not pull it away
+        FailoverManager::select_primary(&config, &with_wifi, &primary, &event_log, at(200)).await;
+        assert_eq!(primary.read().await.id.as_deref(), Some("wifi"));
+        assert_eq!(count_events(&event_log).await, (2, 1));
+    }
+
+    #[tokio::test]
+    async fn test_flapping_primary_does_not_fail_back() {
+        let config = preference_config();
+        let primary = Arc::new(RwLock::new(PrimarySelection {
+            id: Some("wifi".to_string()),
+            ..Default::default()
+        }));
+        let event_log = Arc::new(RwLock::new(Vec::new()));
+        let start = Instant::now();
+
+        let without_wifi = ranked(&[("cellular", 0.9), ("lora", 0.2)]);
+        let with_wifi = ranked(&[("cellular", 0.9), ("wifi", 0.6), ("lora", 0.2)]);
+
+        // wifi comes and goes every 20s, never healthy for the full 30s
+        for tick in 0..20u64 {
+            let scores = if (tick / 2) % 2 == 0 {
+                &without_wifi
+            } else {
+                &with_wifi
+            };
+            let now = start + Duration::from_secs(tick * 10);
+            FailoverManager::select_primary(&config, scores, &primary, &event_log, now).await;
+        }
+
+        assert_eq!(primary.read().await.id.as_deref(), Some("cellular"));
+        assert_eq!(count_events(&event_log).await, (1, 0));
+    }
 }
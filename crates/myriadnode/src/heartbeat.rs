@@ -1,8 +1,9 @@
 use anyhow::{bail, Result};
 use blake2::{Blake2b512, Digest};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use sodiumoxide::crypto::sign::ed25519;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::{broadcast, RwLock};
@@ -102,10 +103,75 @@ impl NodeInfo {
         self.heartbeat_count += 1;
     }
 
-    /// Check if this node is stale (hasn't sent heartbeat recently)
-    fn is_stale(&self, timeout_secs: u64) -> bool {
-        let now = current_timestamp();
-        (now - self.last_seen) > timeout_secs
+    /// Check if this node is stale at `now` (hasn't sent heartbeat recently)
+    fn is_stale(&self, timeout_secs: u64, now: u64) -> bool {
+        // Heartbeats may be timestamped slightly in the future
+        now.saturating_sub(self.last_seen) > timeout_secs
+    }
+}
+
+/// Change in a remote node's liveness, as observed through the NodeMap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeLivenessEvent {
+    /// First heartbeat from a node that was not being tracked
+    NodeUp(NodeId),
+    /// No heartbeat within `timeout_secs`; the node was removed from the NodeMap
+    NodeStale(NodeId),
+    /// Heartbeat from a node previously reported stale
+    NodeReturned(NodeId),
+}
+
+/// Capacity of the liveness event channel
+const LIVENESS_CHANNEL_CAPACITY: usize = 256;
+
+/// Publishes liveness events and remembers which nodes went stale
+struct LivenessTracker {
+    events: broadcast::Sender<NodeLivenessEvent>,
+    /// Nodes removed as stale, so their next heartbeat reports NodeReturned
+    stale: RwLock<HashSet<NodeId>>,
+    /// Bound on `stale`; nodes beyond it come back as NodeUp
+    max_stale: usize,
+}
+
+impl LivenessTracker {
+    fn new(max_stale: usize) -> Self {
+        let (events, _) = broadcast::channel(LIVENESS_CHANNEL_CAPACITY);
+        Self {
+            events,
+            stale: RwLock::new(HashSet::new()),
+            max_stale,
+        }
+    }
+
+    /// Record a node newly added to the NodeMap
+    async fn node_added(&self, node_id: NodeId) {
+        let event = if self.stale.write().await.remove(&node_id) {
+            NodeLivenessEvent::NodeReturned(node_id)
+        } else {
+            NodeLivenessEvent::NodeUp(node_id)
+        };
+        // No subscribers is not an error
+        let _ = self.events.send(event);
+    }
+
+    /// Remove nodes that are stale at `now` from `map`, returning how many
+    async fn sweep(&self, map: &mut NodeMap, timeout_secs: u64, now: u64) -> usize {
+        let stale_ids: Vec<NodeId> = map
+            .iter()
+            .filter(|(_, node_info)| node_info.is_stale(timeout_secs, now))
+            .map(|(node_id, _)| *node_id)
+            .collect();
+
+        let mut stale = self.stale.write().await;
+        for node_id in &stale_ids {
+            map.remove(node_id);
+            if stale.len() < self.max_stale {
+                stale.insert(*node_id);
+            }
+            let _ = self.events.send(NodeLivenessEvent::NodeStale(*node_id));
+        }
+
+        stale_ids.len()
     }
 }
 
@@ -209,6 +275,7 @@ pub struct HeartbeatService {
     backhaul_detector: Arc<BackhaulDetector>,
     rate_limiter: Arc<RwLock<HeartbeatRateLimiter>>,
     adapter_configs: HashMap<String, AdapterConfig>,
+    liveness: Arc<LivenessTracker>,
     // RESOURCE M4: Task handle management for graceful shutdown
     shutdown_tx: broadcast::Sender<()>,
     broadcast_task: Arc<RwLock<Option<JoinHandle<()>>>>,
@@ -228,6 +295,7 @@ impl HeartbeatService {
         let rate_limiter = Arc::new(RwLock::new(HeartbeatRateLimiter::new(30, 100)));
         // RESOURCE M4: Create shutdown channel for graceful task termination
         let (shutdown_tx, _) = broadcast::channel::<()>(1);
+        let liveness = Arc::new(LivenessTracker::new(config.max_nodes));
 
         Self {
             config,
@@ -238,6 +306,7 @@ impl HeartbeatService {
             backhaul_detector,
             rate_limiter,
             adapter_configs,
+            liveness,
             shutdown_tx,
             broadcast_task: Arc::new(RwLock::new(None)),
             cleanup_task: Arc::new(RwLock::new(None)),
@@ -261,6 +330,12 @@ impl HeartbeatService {
                 }
             }
 
+            // Quarantined adapters are out of selection until a re-probe passes
+            if manager.is_quarantined(&adapter_id) {
+                debug!("Adapter {} excluded: quarantined", adapter_id);
+                continue;
+            }
+
             // Get adapter
             let adapter_arc = match manager.get_adapter(&adapter_id) {
                 Some(a) => a,
@@ -332,24 +407,7 @@ impl HeartbeatService {
     ///
     /// SECURITY H3: Include public_key in signed message
     fn sign_heartbeat(&self, heartbeat: &HeartbeatMessage) -> Result<Vec<u8>> {
-        // SECURITY H3: Build message to sign
-        // Message format: node_id || timestamp || adapters || geolocation || public_key
-        let mut message = Vec::new();
-        message.extend_from_slice(heartbeat.node_id.as_bytes());
-        message.extend_from_slice(&heartbeat.timestamp.to_be_bytes());
-
-        // Serialize adapters (deterministic)
-        let adapters_json = serde_json::to_vec(&heartbeat.adapters)?;
-        message.extend_from_slice(&adapters_json);
-
-        // Include geolocation if present
-        if let Some(geo) = &heartbeat.geolocation {
-            let geo_json = serde_json::to_vec(geo)?;
-            message.extend_from_slice(&geo_json);
-        }
-
-        // SECURITY H3: Include public key in signed message
-        message.extend_from_slice(&heartbeat.public_key);
+        let message = signing_payload(heartbeat)?;
 
         // Sign with node's keypair
         let signature = sign_message(&self.identity, &message)?;
@@ -390,6 +448,7 @@ impl HeartbeatService {
             backhaul_detector: Arc::clone(&backhaul_detector),
             rate_limiter: Arc::clone(&self.rate_limiter),
             adapter_configs: adapter_configs.clone(),
+            liveness: Arc::clone(&self.liveness),
             shutdown_tx: self.shutdown_tx.clone(),
             broadcast_task: Arc::new(RwLock::new(None)),
             cleanup_task: Arc::new(RwLock::new(None)),
@@ -463,6 +522,7 @@ impl HeartbeatService {
 
         // RESOURCE M4: Start NodeMap cleanup task with shutdown handling
         let node_map = Arc::clone(&self.node_map);
+        let liveness = Arc::clone(&self.liveness);
         let timeout_secs = self.config.timeout_secs;
         let mut shutdown_rx2 = self.shutdown_tx.subscribe();
 
@@ -477,14 +537,14 @@ impl HeartbeatService {
                     }
                     _ = ticker.tick() => {
                         let mut map = node_map.write().await;
-                        let before = map.len();
 
                         // Remove stale nodes
-                        map.retain(|_, node_info| !node_info.is_stale(timeout_secs));
+                        let removed = liveness
+                            .sweep(&mut map, timeout_secs, current_timestamp())
+                            .await;
 
-                        let after = map.len();
-                        if before != after {
-                            debug!("Cleaned up {} stale nodes from NodeMap", before - after);
+                        if removed > 0 {
+                            debug!("Cleaned up {} stale nodes from NodeMap", removed);
                         }
                     }
                 }
@@ -552,17 +612,7 @@ impl HeartbeatService {
         }
 
         // SECURITY H3: Reconstruct signed message
-        // Message format: node_id || timestamp || adapters || geolocation || public_key
-        let mut message = Vec::new();
-        message.extend_from_slice(heartbeat.node_id.as_bytes());
-        message.extend_from_slice(&heartbeat.timestamp.to_be_bytes());
-        let adapters_json = serde_json::to_vec(&heartbeat.adapters)?;
-        message.extend_from_slice(&adapters_json);
-        if let Some(geo) = &heartbeat.geolocation {
-            let geo_json = serde_json::to_vec(geo)?;
-            message.extend_from_slice(&geo_json);
-        }
-        message.extend_from_slice(&heartbeat.public_key);
+        let message = signing_payload(&heartbeat)?;
 
         // SECURITY H3: Parse Ed25519 public key
         let public_key = ed25519::PublicKey::from_slice(&heartbeat.public_key)
@@ -608,6 +658,7 @@ impl HeartbeatService {
         }
 
         // Update or create node info
+        let is_new = !map.contains_key(&heartbeat.node_id);
         let node_info = map
             .entry(heartbeat.node_id)
             .or_insert_with(|| NodeInfo::new(heartbeat.node_id));
@@ -627,9 +678,42 @@ impl HeartbeatService {
             node_info.heartbeat_count
         );
 
+        if is_new {
+            self.liveness.node_added(heartbeat.node_id).await;
+        }
+
         Ok(())
     }
 
+    /// Subscribe to liveness changes in the NodeMap
+    ///
+    /// Emits NodeUp when a node is first seen, NodeStale when it misses
+    /// `timeout_secs` and is dropped from the NodeMap, and NodeReturned when
+    /// a stale node sends another heartbeat. A subscriber that falls behind
+    /// skips the events it missed.
+    pub fn subscribe(&self) -> impl Stream<Item = NodeLivenessEvent> {
+        futures::stream::unfold(self.liveness.events.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => return Some((event, rx)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// Remove nodes that are stale at `now` (Unix seconds), emitting NodeStale
+    ///
+    /// Runs periodically once the service is started. Returns the number of
+    /// nodes removed.
+    pub async fn remove_stale_nodes(&self, now: u64) -> usize {
+        let mut map = self.node_map.write().await;
+        self.liveness
+            .sweep(&mut map, self.config.timeout_secs, now)
+            .await
+    }
+
     /// Generate a heartbeat message from local node state
     ///
     /// SECURITY H3: Sign heartbeat to prevent route poisoning
@@ -650,31 +734,18 @@ impl HeartbeatService {
         // SECURITY H3: Extract public key bytes
         let public_key_bytes = self.identity.public_key.as_ref().to_vec();
 
-        // SECURITY H3: Build message to sign
-        // Message format: node_id || timestamp || adapters || geolocation || public_key
-        let mut message = Vec::new();
-        message.extend_from_slice(self.local_node_id.as_bytes());
-        message.extend_from_slice(&timestamp.to_be_bytes());
-        let adapters_json = serde_json::to_vec(&adapters)?;
-        message.extend_from_slice(&adapters_json);
-        if let Some(geo) = &geo {
-            let geo_json = serde_json::to_vec(geo)?;
-            message.extend_from_slice(&geo_json);
-        }
-        message.extend_from_slice(&public_key_bytes);
-
-        // SECURITY H3: Sign the message
-        let signature = sign_message(&self.identity, &message)?;
-
-        let heartbeat = HeartbeatMessage {
+        let mut heartbeat = HeartbeatMessage {
             node_id: self.local_node_id,
             timestamp,
             adapters,
             geolocation: geo,
             public_key: public_key_bytes,
-            signature: signature.as_bytes().to_vec(),
+            signature: Vec::new(),
         };
 
+        // SECURITY H3: Sign the message (covers geolocation when present)
+        heartbeat.signature = self.sign_heartbeat(&heartbeat)?;
+
         Ok(heartbeat)
     }
 
@@ -767,6 +838,27 @@ pub struct NodeMapStats {
     pub adapter_counts: HashMap<String, usize>,
 }
 
+/// Build the byte string covered by a heartbeat signature
+///
+/// SECURITY H3: Message format: node_id || timestamp || adapters || geolocation || public_key.
+/// Signing and verification must both go through here so the two can never diverge.
+fn signing_payload(heartbeat: &HeartbeatMessage) -> Result<Vec<u8>> {
+    let mut message = Vec::new();
+    message.extend_from_slice(heartbeat.node_id.as_bytes());
+    message.extend_from_slice(&heartbeat.timestamp.to_be_bytes());
+
+    // Serialize adapters (deterministic)
+    message.extend_from_slice(&serde_json::to_vec(&heartbeat.adapters)?);
+
+    // Include geolocation if present, so it cannot be added or altered in transit
+    if let Some(geo) = &heartbeat.geolocation {
+        message.extend_from_slice(&serde_json::to_vec(geo)?);
+    }
+
+    message.extend_from_slice(&heartbeat.public_key);
+    Ok(message)
+}
+
 /// Get current Unix timestamp (seconds) with graceful fallback on system time errors
 ///
 /// SECURITY: If system clock goes backwards or other time errors occur,
@@ -796,6 +888,7 @@ fn estimate_privacy_level(adapter_type: &myriadmesh_protocol::types::AdapterType
         AdapterType::Ethernet => 0.15,
         AdapterType::WiFiHaLoW => 0.15,
         AdapterType::Cellular => 0.10, // Most traceable
+        AdapterType::WebSocket => 0.15,
 
         // Bluetooth has MAC addresses but short range
         AdapterType::Bluetooth => 0.30,
@@ -805,8 +898,9 @@ fn estimate_privacy_level(adapter_type: &myriadmesh_protocol::types::AdapterType
         AdapterType::LoRaWAN => 0.50,
         AdapterType::Meshtastic => 0.50,
 
-        // I2P is anonymous
+        // I2P and Tor are anonymous
         AdapterType::I2P => 0.95,
+        AdapterType::Tor => 0.90,
 
         // Other wireless with varying privacy
         AdapterType::APRS => 0.40,
@@ -865,6 +959,46 @@ mod tests {
         )
     }
 
+    // Helper creating a service whose local node ID belongs to `identity`,
+    // so heartbeats it generates verify on other nodes
+    fn create_signing_service(
+        config: HeartbeatConfig,
+        identity: Arc<NodeIdentity>,
+    ) -> HeartbeatService {
+        let node_id = NodeId::from_bytes(*identity.node_id.as_bytes());
+        HeartbeatService::new(
+            config,
+            node_id,
+            identity,
+            Arc::new(RwLock::new(AdapterManager::new())),
+            Arc::new(BackhaulDetector::new(BackhaulConfig::default())),
+            HashMap::new(),
+        )
+    }
+
+    fn test_adapters() -> Vec<AdapterInfo> {
+        vec![AdapterInfo {
+            adapter_id: "eth0".to_string(),
+            adapter_type: "ethernet".to_string(),
+            active: true,
+            is_backhaul: false,
+            bandwidth_bps: 100_000_000,
+            latency_ms: 10,
+            reliability: 0.99,
+            privacy_level: 0.15,
+        }]
+    }
+
+    fn test_geolocation() -> GeolocationData {
+        GeolocationData {
+            latitude: 40.7128,
+            longitude: -74.0060,
+            accuracy_meters: 100.0,
+            country_code: Some("US".to_string()),
+            city: Some("New York".to_string()),
+        }
+    }
+
     #[test]
     fn test_heartbeat_config_default() {
         let config = HeartbeatConfig::default();
@@ -881,16 +1015,21 @@ mod tests {
         let mut node_info = NodeInfo::new(node_id);
 
         // Fresh node should not be stale
-        assert!(!node_info.is_stale(300));
+        let now = current_timestamp();
+        assert!(!node_info.is_stale(300, now));
 
         // Set last_seen to 10 minutes ago
-        node_info.last_seen = current_timestamp() - 600;
+        node_info.last_seen = now - 600;
 
         // Should be stale with 5 minute timeout
-        assert!(node_info.is_stale(300));
+        assert!(node_info.is_stale(300, now));
 
         // Should not be stale with 15 minute timeout
-        assert!(!node_info.is_stale(900));
+        assert!(!node_info.is_stale(900, now));
+
+        // Clock skew into the future is not stale
+        node_info.last_seen = now + 60;
+        assert!(!node_info.is_stale(300, now));
     }
 
     #[test]
@@ -1267,4 +1406,204 @@ mod tests {
         assert!(node_info.is_some());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_generated_heartbeat_updates_remote_map() -> Result<()> {
+        // SECURITY H3: A heartbeat signed by the sender verifies on the receiver
+        myriadmesh_crypto::init().ok();
+        let identity = Arc::new(NodeIdentity::generate()?);
+        let sender = create_signing_service(
+            HeartbeatConfig {
+                include_geolocation: true,
+                ..Default::default()
+            },
+            identity.clone(),
+        );
+        let receiver = create_test_service(
+            HeartbeatConfig {
+                store_remote_geolocation: true,
+                ..Default::default()
+            },
+            NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+        );
+
+        let heartbeat = sender
+            .generate_heartbeat(test_adapters(), Some(test_geolocation()))
+            .await?;
+        assert!(heartbeat.geolocation.is_some());
+        receiver.handle_heartbeat(heartbeat).await?;
+
+        let info = receiver
+            .get_node_info(&sender.local_node_id)
+            .await
+            .expect("sender should be in the NodeMap");
+        assert_eq!(info.adapters.len(), 1);
+        assert_eq!(info.heartbeat_count, 1);
+        assert_eq!(info.geolocation.map(|g| g.latitude), Some(40.7128));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_generated_heartbeat_respects_include_geolocation() -> Result<()> {
+        myriadmesh_crypto::init().ok();
+        let identity = Arc::new(NodeIdentity::generate()?);
+        let sender = create_signing_service(HeartbeatConfig::default(), identity);
+        let receiver = create_test_service(
+            HeartbeatConfig {
+                store_remote_geolocation: true,
+                ..Default::default()
+            },
+            NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+        );
+
+        // Privacy-first default omits location, and the signature still verifies
+        let heartbeat = sender
+            .generate_heartbeat(test_adapters(), Some(test_geolocation()))
+            .await?;
+        assert!(heartbeat.geolocation.is_none());
+        receiver.handle_heartbeat(heartbeat).await?;
+
+        let info = receiver.get_node_info(&sender.local_node_id).await.unwrap();
+        assert!(info.geolocation.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tampered_heartbeat_leaves_map_untouched() -> Result<()> {
+        // SECURITY H3: Forged liveness or location data must not reach the NodeMap
+        myriadmesh_crypto::init().ok();
+        let identity = Arc::new(NodeIdentity::generate()?);
+        let sender = create_signing_service(
+            HeartbeatConfig {
+                include_geolocation: true,
+                ..Default::default()
+            },
+            identity,
+        );
+        let receiver = create_test_service(
+            HeartbeatConfig {
+                store_remote_geolocation: true,
+                ..Default::default()
+            },
+            NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+        );
+
+        let original = sender
+            .generate_heartbeat(test_adapters(), Some(test_geolocation()))
+            .await?;
+
+        // Moved location
+        let mut moved = original.clone();
+        if let Some(geo) = moved.geolocation.as_mut() {
+            geo.latitude = 51.5074;
+        }
+        // Location injected into a heartbeat signed without one
+        let mut injected = sender.generate_heartbeat(test_adapters(), None).await?;
+        injected.geolocation = Some(test_geolocation());
+        // Liveness replayed with a fresher timestamp
+        let mut refreshed = original.clone();
+        refreshed.timestamp += 1;
+
+        for forged in [moved, injected, refreshed] {
+            let err = receiver.handle_heartbeat(forged).await.unwrap_err();
+            assert!(err.to_string().contains("Signature verification failed"));
+        }
+        assert!(receiver.get_node_map().await.is_empty());
+
+        // The untouched original is still accepted
+        receiver.handle_heartbeat(original).await?;
+        assert_eq!(receiver.get_node_map().await.len(), 1);
+        Ok(())
+    }
+
+    async fn next_event(
+        events: &mut (impl Stream<Item = NodeLivenessEvent> + Unpin),
+    ) -> Option<NodeLivenessEvent> {
+        use futures::StreamExt;
+        tokio::time::timeout(Duration::from_secs(1), events.next())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    #[tokio::test]
+    async fn test_liveness_events_follow_timeout() -> Result<()> {
+        myriadmesh_crypto::init().ok();
+        let sender = create_signing_service(
+            HeartbeatConfig::default(),
+            Arc::new(NodeIdentity::generate()?),
+        );
+        let receiver = create_test_service(
+            HeartbeatConfig {
+                timeout_secs: 120,
+                ..Default::default()
+            },
+            NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+        );
+        let remote = sender.local_node_id;
+        let mut events = Box::pin(receiver.subscribe());
+
+        let heartbeat = sender.generate_heartbeat(test_adapters(), None).await?;
+        let seen_at = heartbeat.timestamp;
+        receiver.handle_heartbeat(heartbeat).await?;
+        assert_eq!(
+            next_event(&mut events).await,
+            Some(NodeLivenessEvent::NodeUp(remote))
+        );
+
+        // Mock clock: exactly at the timeout the node is still live
+        assert_eq!(receiver.remove_stale_nodes(seen_at + 120).await, 0);
+        assert_eq!(receiver.remove_stale_nodes(seen_at + 121).await, 1);
+        assert_eq!(
+            next_event(&mut events).await,
+            Some(NodeLivenessEvent::NodeStale(remote))
+        );
+        assert!(receiver.get_node_info(&remote).await.is_none());
+
+        // Bypass the per-node rate limit for the follow-up heartbeat
+        receiver.rate_limiter.write().await.last_received.clear();
+        let heartbeat = sender.generate_heartbeat(test_adapters(), None).await?;
+        receiver.handle_heartbeat(heartbeat).await?;
+        assert_eq!(
+            next_event(&mut events).await,
+            Some(NodeLivenessEvent::NodeReturned(remote))
+        );
+        assert!(receiver.get_node_info(&remote).await.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_repeat_heartbeats_emit_no_events() -> Result<()> {
+        myriadmesh_crypto::init().ok();
+        let sender = create_signing_service(
+            HeartbeatConfig::default(),
+            Arc::new(NodeIdentity::generate()?),
+        );
+        let receiver = create_test_service(
+            HeartbeatConfig::default(),
+            NodeId::from_bytes([1u8; NODE_ID_SIZE]),
+        );
+        let mut events = Box::pin(receiver.subscribe());
+
+        for _ in 0..2 {
+            receiver.rate_limiter.write().await.last_received.clear();
+            let heartbeat = sender.generate_heartbeat(test_adapters(), None).await?;
+            receiver.handle_heartbeat(heartbeat).await?;
+        }
+
+        assert_eq!(
+            next_event(&mut events).await,
+            Some(NodeLivenessEvent::NodeUp(sender.local_node_id))
+        );
+        assert_eq!(next_event(&mut events).await, None);
+        assert_eq!(
+            receiver
+                .get_node_info(&sender.local_node_id)
+                .await
+                .unwrap()
+                .heartbeat_count,
+            2
+        );
+        Ok(())
+    }
 }
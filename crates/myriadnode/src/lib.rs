@@ -16,4 +16,4 @@ pub mod storage;
 // Re-export commonly used types for convenience
 pub use config::Config;
 pub use node::Node;
-pub use scoring::{AdapterScorer, ScoringWeights};
+pub use scoring::{AdapterScorer, MetricsSmoother, ScoringWeights};
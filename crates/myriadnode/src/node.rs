@@ -1,7 +1,7 @@
 use anyhow::Result;
 use std::sync::Arc;
 use tokio::signal;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tracing::{error, info, warn};
 
 use crate::api::ApiServer;
@@ -40,6 +40,8 @@ pub struct Node {
     monitor: NetworkMonitor,
     failover_manager: Arc<FailoverManager>,
     heartbeat_service: Arc<HeartbeatService>,
+    /// Stops the adapter quarantine re-probe loop
+    reprobe_shutdown: broadcast::Sender<()>,
     shutdown_tx: mpsc::Sender<()>,
     shutdown_rx: mpsc::Receiver<()>,
 }
@@ -103,18 +105,16 @@ impl Node {
         info!("✓ Network monitor initialized");
 
         // Initialize failover manager
-        let scoring_weights = match config.network.scoring.mode.as_str() {
-            "battery" => ScoringWeights::battery_optimized(),
-            "performance" => ScoringWeights::performance_optimized(),
-            "reliability" => ScoringWeights::reliability_optimized(),
-            "privacy" => ScoringWeights::privacy_optimized(),
-            _ => ScoringWeights::default(),
-        };
-        let failover_manager = Arc::new(FailoverManager::new(
-            config.network.failover.clone(),
-            Arc::clone(&adapter_manager),
-            scoring_weights,
-        ));
+        let scoring_weights =
+            ScoringWeights::for_mode(&config.network.scoring.mode).unwrap_or_default();
+        let failover_manager = Arc::new(
+            FailoverManager::new(
+                config.network.failover.clone(),
+                Arc::clone(&adapter_manager),
+                scoring_weights,
+            )
+            .with_smoothing_factor(config.network.scoring.smoothing_factor),
+        );
         info!(
             "✓ Failover manager initialized (mode: {})",
             config.network.scoring.mode
@@ -211,11 +211,28 @@ impl Node {
                 cache_config: MessageCacheConfig {
                     max_messages_per_device: config.appliance.max_cache_messages_per_device,
                     max_total_messages: config.appliance.max_total_cache_messages,
+                    ..Default::default()
                 },
                 data_directory: config.data_directory.join("appliance"),
+                ..Default::default()
             };
 
             let manager = Arc::new(ApplianceManager::new(appliance_config, signing_key).await?);
+
+            // Pairing PINs are only ever shown on the local console
+            let mut pins = manager.subscribe_pairing_pins();
+            tokio::spawn(async move {
+                loop {
+                    match pins.recv().await {
+                        Ok(pin) => info!(
+                            "Pairing PIN for device {}: {} (valid until {})",
+                            pin.device_id, pin.pin, pin.expires_at
+                        ),
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
             info!(
                 "✓ Appliance manager initialized (max devices: {}, cache: {})",
                 config.appliance.max_paired_devices, config.appliance.max_total_cache_messages
@@ -288,6 +305,7 @@ impl Node {
             monitor,
             failover_manager,
             heartbeat_service,
+            reprobe_shutdown: broadcast::channel(1).0,
             shutdown_tx,
             shutdown_rx,
         })
@@ -299,6 +317,12 @@ impl Node {
         // Start network adapters
         self.start_network_adapters().await?;
 
+        // Bring quarantined adapters back once they pass a re-probe
+        tokio::spawn(AdapterManager::run_reprobe_loop(
+            Arc::clone(&self.adapter_manager),
+            self.reprobe_shutdown.subscribe(),
+        ));
+
         // Start API server
         if let Some(api_server) = &self.api_server {
             let server_handle = api_server.start().await?;
@@ -495,6 +519,11 @@ impl Node {
             adapter.start().await?;
         }
 
+        // Loss of service fails over without waiting for a health check
+        self.failover_manager
+            .watch_cellular_status("cellular".to_string(), adapter.subscribe_status())
+            .await;
+
         let mut manager = self.adapter_manager.write().await;
         manager
             .register_adapter("cellular".to_string(), Box::new(adapter))
@@ -519,6 +548,7 @@ impl Node {
         self.monitor.stop().await?;
 
         info!("Stopping network adapters...");
+        let _ = self.reprobe_shutdown.send(());
         {
             let mut manager = self.adapter_manager.write().await;
             if let Err(e) = manager.stop_all().await {
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use tracing::debug;
 
 /// Weights for adapter scoring algorithm
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ScoringWeights {
     pub latency: f64,
     pub bandwidth: f64,
@@ -24,6 +24,19 @@ impl Default for ScoringWeights {
 }
 
 impl ScoringWeights {
+    /// Weights for a named scoring mode
+    /// ("default", "battery", "performance", "reliability" or "privacy")
+    pub fn for_mode(mode: &str) -> Option<Self> {
+        match mode {
+            "default" => Some(Self::default()),
+            "battery" => Some(Self::battery_optimized()),
+            "performance" => Some(Self::performance_optimized()),
+            "reliability" => Some(Self::reliability_optimized()),
+            "privacy" => Some(Self::privacy_optimized()),
+            _ => None,
+        }
+    }
+
     /// Battery-optimized weights (prioritize low power consumption)
     pub fn battery_optimized() -> Self {
         Self {
@@ -97,6 +110,81 @@ pub struct AdapterMetrics {
     pub privacy_level: f64,     // 0.0 (traceable/IP) to 1.0 (anonymous/non-IP)
 }
 
+/// Default EWMA smoothing factor (weight given to the newest sample)
+pub const DEFAULT_SMOOTHING_FACTOR: f64 = 0.2;
+
+/// Exponentially weighted moving average of adapter metrics
+///
+/// Smooths latency, bandwidth and reliability per adapter so that a single
+/// outlier sample does not flip adapter selection, while a sustained change
+/// still shows through after a few samples. Power consumption and privacy
+/// level are static properties and are taken from the latest sample as-is.
+#[derive(Debug, Clone)]
+pub struct MetricsSmoother {
+    alpha: f64,
+    smoothed: HashMap<String, AdapterMetrics>,
+}
+
+impl MetricsSmoother {
+    /// Create a smoother with the given smoothing factor
+    ///
+    /// `alpha` is clamped to (0.0, 1.0]; higher values track new samples
+    /// faster, 1.0 disables smoothing.
+    pub fn new(alpha: f64) -> Self {
+        let alpha = if alpha.is_finite() && alpha > 0.0 {
+            alpha.min(1.0)
+        } else {
+            DEFAULT_SMOOTHING_FACTOR
+        };
+        Self {
+            alpha,
+            smoothed: HashMap::new(),
+        }
+    }
+
+    /// Smoothing factor in use
+    pub fn smoothing_factor(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Fold a new sample into the average and return the smoothed metrics
+    ///
+    /// The first sample for an adapter is taken as-is.
+    pub fn update(&mut self, adapter_id: &str, sample: &AdapterMetrics) -> AdapterMetrics {
+        let alpha = self.alpha;
+        let smoothed = self
+            .smoothed
+            .entry(adapter_id.to_string())
+            .and_modify(|avg| {
+                avg.latency_ms += alpha * (sample.latency_ms - avg.latency_ms);
+                avg.bandwidth_bps = (avg.bandwidth_bps as f64
+                    + alpha * (sample.bandwidth_bps as f64 - avg.bandwidth_bps as f64))
+                    .round() as u64;
+                avg.reliability += alpha * (sample.reliability - avg.reliability);
+                avg.power_consumption = sample.power_consumption;
+                avg.privacy_level = sample.privacy_level;
+            })
+            .or_insert_with(|| sample.clone());
+        smoothed.clone()
+    }
+
+    /// Current smoothed metrics for an adapter
+    pub fn get(&self, adapter_id: &str) -> Option<&AdapterMetrics> {
+        self.smoothed.get(adapter_id)
+    }
+
+    /// Drop history for adapters not in `adapter_ids`
+    pub fn retain(&mut self, adapter_ids: &[String]) {
+        self.smoothed.retain(|id, _| adapter_ids.contains(id));
+    }
+}
+
+impl Default for MetricsSmoother {
+    fn default() -> Self {
+        Self::new(DEFAULT_SMOOTHING_FACTOR)
+    }
+}
+
 /// Calculated score for an adapter
 #[derive(Debug, Clone)]
 pub struct AdapterScore {
@@ -362,6 +450,19 @@ mod tests {
         assert!(weights.power > 0.30); // Should prioritize power
     }
 
+    #[test]
+    fn test_weights_for_mode() {
+        assert_eq!(
+            ScoringWeights::for_mode("battery"),
+            Some(ScoringWeights::battery_optimized())
+        );
+        assert_eq!(
+            ScoringWeights::for_mode("default"),
+            Some(ScoringWeights::default())
+        );
+        assert_eq!(ScoringWeights::for_mode("turbo"), None);
+    }
+
     #[test]
     fn test_performance_optimized_weights() {
         let weights = ScoringWeights::performance_optimized();
@@ -425,4 +526,72 @@ mod tests {
         assert_ne!(best.adapter_id, "ethernet");
         assert!(best.privacy_score > 0.75); // Should have high privacy score
     }
+
+    fn sample(latency_ms: f64) -> AdapterMetrics {
+        AdapterMetrics {
+            latency_ms,
+            bandwidth_bps: 10_000_000,
+            reliability: 0.95,
+            power_consumption: 0.3,
+            privacy_level: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_smoother_damps_latency_spike() {
+        let scorer = AdapterScorer::new_with_defaults();
+        let mut smoother = MetricsSmoother::default();
+
+        for _ in 0..10 {
+            smoother.update("eth0", &sample(50.0));
+        }
+        let baseline = scorer.calculate_score("eth0".to_string(), &sample(50.0));
+
+        // A single 900ms spike
+        let raw_spike = scorer.calculate_score("eth0".to_string(), &sample(900.0));
+        let smoothed = smoother.update("eth0", &sample(900.0));
+        let smoothed_spike = scorer.calculate_score("eth0".to_string(), &smoothed);
+
+        let raw_drop = baseline.total_score - raw_spike.total_score;
+        let smoothed_drop = baseline.total_score - smoothed_spike.total_score;
+        assert!(smoothed_drop > 0.0);
+        assert!(smoothed_drop < raw_drop * 0.25);
+
+        // Back to normal: the spike fades out
+        for _ in 0..20 {
+            smoother.update("eth0", &sample(50.0));
+        }
+        assert!((smoother.get("eth0").unwrap().latency_ms - 50.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_smoother_follows_sustained_change() {
+        let mut smoother = MetricsSmoother::new(0.3);
+
+        assert_eq!(smoother.update("lora", &sample(50.0)).latency_ms, 50.0);
+        for _ in 0..20 {
+            smoother.update("lora", &sample(600.0));
+        }
+        assert!((smoother.get("lora").unwrap().latency_ms - 600.0).abs() < 1.0);
+
+        // Adapters are tracked independently and can be pruned
+        smoother.update("eth0", &sample(10.0));
+        smoother.retain(&["eth0".to_string()]);
+        assert!(smoother.get("lora").is_none());
+        assert_eq!(smoother.get("eth0").unwrap().latency_ms, 10.0);
+    }
+
+    #[test]
+    fn test_smoothing_factor_bounds() {
+        assert_eq!(MetricsSmoother::new(0.5).smoothing_factor(), 0.5);
+        assert_eq!(MetricsSmoother::new(2.0).smoothing_factor(), 1.0);
+        assert_eq!(
+            MetricsSmoother::new(0.0).smoothing_factor(),
+            DEFAULT_SMOOTHING_FACTOR
+        );
+        assert_eq!(
+            MetricsSmoother::new(f64::NAN).smoothing_factor(),
+            DEFAULT_SMOOTHING_FACTOR
+        );
+    }
 }
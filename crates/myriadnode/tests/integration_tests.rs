@@ -174,6 +174,10 @@ async fn test_failover_manager_initialization() {
         latency_threshold_multiplier: 3.0,
         loss_threshold: 0.3,
         retry_attempts: 3,
+        min_dwell_secs: 60,
+        switch_margin: 0.10,
+        preferred_adapters: Vec::new(),
+        failback_after_secs: 120,
     };
 
     let adapter_manager = Arc::new(RwLock::new(AdapterManager::new()));
@@ -197,6 +201,10 @@ async fn test_failover_event_logging() {
         latency_threshold_multiplier: 3.0,
         loss_threshold: 0.3,
         retry_attempts: 3,
+        min_dwell_secs: 60,
+        switch_margin: 0.10,
+        preferred_adapters: Vec::new(),
+        failback_after_secs: 120,
     };
 
     let adapter_manager = Arc::new(RwLock::new(AdapterManager::new()));
@@ -216,6 +224,10 @@ async fn test_failover_force_failover_validation() {
         latency_threshold_multiplier: 3.0,
         loss_threshold: 0.3,
         retry_attempts: 3,
+        min_dwell_secs: 60,
+        switch_margin: 0.10,
+        preferred_adapters: Vec::new(),
+        failback_after_secs: 120,
     };
 
     let adapter_manager = Arc::new(RwLock::new(AdapterManager::new()));
@@ -387,6 +399,10 @@ async fn test_complete_node_component_initialization() {
         latency_threshold_multiplier: 3.0,
         loss_threshold: 0.3,
         retry_attempts: 3,
+        min_dwell_secs: 60,
+        switch_margin: 0.10,
+        preferred_adapters: Vec::new(),
+        failback_after_secs: 120,
     };
     let _failover = FailoverManager::new(failover_config, Arc::clone(&adapter_manager), weights);
 
@@ -567,6 +583,10 @@ async fn test_failover_manager_thread_safety() {
         latency_threshold_multiplier: 3.0,
         loss_threshold: 0.3,
         retry_attempts: 3,
+        min_dwell_secs: 60,
+        switch_margin: 0.10,
+        preferred_adapters: Vec::new(),
+        failback_after_secs: 120,
     };
 
     let adapter_manager = Arc::new(RwLock::new(AdapterManager::new()));
@@ -638,6 +658,10 @@ async fn test_failover_manager_graceful_shutdown() {
         latency_threshold_multiplier: 3.0,
         loss_threshold: 0.3,
         retry_attempts: 3,
+        min_dwell_secs: 60,
+        switch_margin: 0.10,
+        preferred_adapters: Vec::new(),
+        failback_after_secs: 120,
     };
 
     let adapter_manager = Arc::new(RwLock::new(AdapterManager::new()));
@@ -691,6 +715,10 @@ async fn test_concurrent_shutdown_no_deadlock() {
         latency_threshold_multiplier: 3.0,
         loss_threshold: 0.3,
         retry_attempts: 3,
+        min_dwell_secs: 60,
+        switch_margin: 0.10,
+        preferred_adapters: Vec::new(),
+        failback_after_secs: 120,
     };
 
     let heartbeat_config = HeartbeatConfig {
@@ -738,6 +766,10 @@ async fn test_failover_scenario_with_restart() {
         latency_threshold_multiplier: 3.0,
         loss_threshold: 0.3,
         retry_attempts: 3,
+        min_dwell_secs: 60,
+        switch_margin: 0.10,
+        preferred_adapters: Vec::new(),
+        failback_after_secs: 120,
     };
 
     let adapter_manager = Arc::new(RwLock::new(AdapterManager::new()));
@@ -772,6 +804,10 @@ async fn test_failover_lock_ordering_stress() {
         latency_threshold_multiplier: 3.0,
         loss_threshold: 0.3,
         retry_attempts: 3,
+        min_dwell_secs: 60,
+        switch_margin: 0.10,
+        preferred_adapters: Vec::new(),
+        failback_after_secs: 120,
     };
 
     let adapter_manager = Arc::new(RwLock::new(AdapterManager::new()));